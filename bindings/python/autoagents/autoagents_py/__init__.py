@@ -13,6 +13,8 @@ from .events import (
     CodeExecutionFailed,
     CodeExecutionStarted,
     EventStream,
+    LLMCallCompleted,
+    MemoryCompacted,
     NewTask,
     ProtocolEvent,
     SendMessage,
@@ -97,6 +99,7 @@ __all__ = [
     "ToolCallFailed",
     "TurnStarted",
     "TurnCompleted",
+    "LLMCallCompleted",
     "CodeExecutionStarted",
     "CodeExecutionConsole",
     "CodeExecutionCompleted",
@@ -104,6 +107,7 @@ __all__ = [
     "StreamChunk",
     "StreamToolCall",
     "StreamComplete",
+    "MemoryCompacted",
     "AutoAgentsError",
     "AgentConfigError",
     "AgentBuildError",
@@ -23,6 +23,7 @@ class TaskStarted:
     actor_id: str
     actor_name: str
     task_description: str
+    trace: JsonObject | None = None
 
 
 @dataclass(slots=True, frozen=True)
@@ -89,6 +90,15 @@ class TurnCompleted:
     final_turn: bool
 
 
+@dataclass(slots=True, frozen=True)
+class LLMCallCompleted:
+    sub_id: str
+    actor_id: str
+    model: str
+    duration_ms: int
+    usage: JsonObject | None
+
+
 @dataclass(slots=True, frozen=True)
 class CodeExecutionStarted:
     sub_id: str
@@ -141,6 +151,15 @@ class StreamComplete:
     sub_id: str
 
 
+@dataclass(slots=True, frozen=True)
+class MemoryCompacted:
+    actor_id: str
+    messages_before: int
+    messages_after: int
+    tokens_before: int
+    tokens_after: int
+
+
 ProtocolEvent = Union[
     NewTask,
     TaskStarted,
@@ -152,6 +171,7 @@ ProtocolEvent = Union[
     ToolCallFailed,
     TurnStarted,
     TurnCompleted,
+    LLMCallCompleted,
     CodeExecutionStarted,
     CodeExecutionConsole,
     CodeExecutionCompleted,
@@ -159,6 +179,7 @@ ProtocolEvent = Union[
     StreamChunk,
     StreamToolCall,
     StreamComplete,
+    MemoryCompacted,
 ]
 
 _EVENT_TYPES = {
@@ -172,6 +193,7 @@ _EVENT_TYPES = {
     "tool_call_failed": ToolCallFailed,
     "turn_started": TurnStarted,
     "turn_completed": TurnCompleted,
+    "llm_call_completed": LLMCallCompleted,
     "code_execution_started": CodeExecutionStarted,
     "code_execution_console": CodeExecutionConsole,
     "code_execution_completed": CodeExecutionCompleted,
@@ -179,6 +201,7 @@ _EVENT_TYPES = {
     "stream_chunk": StreamChunk,
     "stream_tool_call": StreamToolCall,
     "stream_complete": StreamComplete,
+    "memory_compacted": MemoryCompacted,
 }
 
 
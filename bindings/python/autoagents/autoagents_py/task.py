@@ -32,6 +32,9 @@ class Task:
     image: Optional[TaskImage] = None
     system_prompt: Optional[str] = None
     app_meta: Optional[JsonObject] = None
+    user_id: Optional[str] = None
+    session_id: Optional[str] = None
+    trace_attributes: Optional[JsonObject] = None
 
     def to_payload(self) -> TaskPayload:
         payload: TaskPayload = {"prompt": self.prompt}
@@ -41,4 +44,13 @@ class Task:
             payload["image"] = self.image.to_payload()
         if self.app_meta is not None:
             payload["app_meta"] = self.app_meta
+        trace: JsonObject = {}
+        if self.user_id is not None:
+            trace["user_id"] = self.user_id
+        if self.session_id is not None:
+            trace["session_id"] = self.session_id
+        if self.trace_attributes:
+            trace["attributes"] = self.trace_attributes
+        if trace:
+            payload["trace"] = trace
         return payload
@@ -34,6 +34,7 @@ class TaskPayload(TypedDict, total=False):
     system_prompt: str
     image: TaskImagePayload
     app_meta: Optional[JsonObject]
+    trace: JsonObject
 
 
 class ExecutorTask(TypedDict, total=False):
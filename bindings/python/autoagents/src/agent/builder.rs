@@ -326,6 +326,7 @@ fn event_submission_id(event: &Event) -> Option<SubmissionId> {
         | Event::ToolCallFailed { sub_id, .. }
         | Event::TurnStarted { sub_id, .. }
         | Event::TurnCompleted { sub_id, .. }
+        | Event::LLMCallCompleted { sub_id, .. }
         | Event::CodeExecutionStarted { sub_id, .. }
         | Event::CodeExecutionConsole { sub_id, .. }
         | Event::CodeExecutionCompleted { sub_id, .. }
@@ -333,7 +334,10 @@ fn event_submission_id(event: &Event) -> Option<SubmissionId> {
         | Event::StreamChunk { sub_id, .. }
         | Event::StreamToolCall { sub_id, .. }
         | Event::StreamComplete { sub_id, .. } => Some(*sub_id),
-        Event::PublishMessage { .. } | Event::NewTask { .. } | Event::SendMessage { .. } => None,
+        Event::PublishMessage { .. }
+        | Event::NewTask { .. }
+        | Event::SendMessage { .. }
+        | Event::MemoryCompacted { .. } => None,
     }
 }
 
@@ -436,6 +440,20 @@ fn py_task_to_rust_task(task_obj: &Bound<'_, PyAny>) -> PyResult<Task> {
         task = task.with_app_meta(app_meta);
     }
 
+    if let Some(trace_any) = dict.get_item("trace")?
+        && !trace_any.is_none()
+    {
+        let trace = py_any_to_json_value(&trace_any)
+            .ok()
+            .and_then(|value| serde_json::from_value(value).ok())
+            .ok_or_else(|| {
+                PyRuntimeError::new_err(
+                    "task.trace must be a dict with user_id, session_id, and attributes",
+                )
+            })?;
+        task.trace = Some(trace);
+    }
+
     if let Some(image_any) = dict.get_item("image")?
         && !image_any.is_none()
     {
@@ -856,13 +874,17 @@ mod tests {
                         "mime": "png",
                         "data": [137, 80, 78, 71]
                     },
-                    "app_meta": {"session_id": "s1", "chat_id": "c1"}
+                    "app_meta": {"session_id": "s1", "chat_id": "c1"},
+                    "trace": {"user_id": "u1", "session_id": "s1", "attributes": {"tier": "gold"}}
                 }),
             )
             .expect("dict should convert");
             let task = py_task_to_rust_task(dict_task.bind(py)).expect("dict task should parse");
             assert_eq!(task.prompt, "image task");
             assert_eq!(task.system_prompt.as_deref(), Some("system"));
+            let trace = task.trace.clone().expect("trace should parse");
+            assert_eq!(trace.user_id.as_deref(), Some("u1"));
+            assert_eq!(trace.attributes.get("tier"), Some(&json!("gold")));
             assert!(matches!(task.image, Some((ImageMime::PNG, _))));
             let app_meta = task
                 .app_meta
@@ -894,6 +916,7 @@ mod tests {
             actor_id,
             actor_name: "planner".to_string(),
             task_description: "run".to_string(),
+            trace: None,
         })
         .expect("event should send");
         tx.send(Event::TaskComplete {
@@ -69,6 +69,7 @@ async fn emit_custom_task_started(task: &Task, context: &Context) {
             actor_id: context.config().id,
             actor_name: context.config().name.clone(),
             task_description: task.prompt.clone(),
+            trace: task.trace.clone(),
         },
     )
     .await;
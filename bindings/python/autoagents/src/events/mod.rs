@@ -28,6 +28,7 @@ fn event_to_payload(event: Event) -> PyResult<Value> {
             actor_id,
             actor_name,
             task_description,
+            trace,
         } => Ok(task_payload(
             "task_started",
             sub_id,
@@ -35,6 +36,7 @@ fn event_to_payload(event: Event) -> PyResult<Value> {
             json!({
                 "actor_name": actor_name,
                 "task_description": task_description,
+                "trace": trace,
             }),
         )),
         Event::TaskComplete {
@@ -200,6 +202,22 @@ fn event_to_payload(event: Event) -> PyResult<Value> {
                 "duration_ms": duration_ms,
             }),
         )),
+        Event::LLMCallCompleted {
+            sub_id,
+            actor_id,
+            model,
+            duration_ms,
+            usage,
+        } => Ok(task_payload(
+            "llm_call_completed",
+            sub_id,
+            actor_id,
+            json!({
+                "model": model,
+                "duration_ms": duration_ms,
+                "usage": usage,
+            }),
+        )),
         Event::StreamChunk { sub_id, chunk } => stream_chunk_payload(sub_id, chunk),
         Event::StreamToolCall { sub_id, tool_call } => Ok(json!({
             "kind": "stream_tool_call",
@@ -210,6 +228,20 @@ fn event_to_payload(event: Event) -> PyResult<Value> {
             "kind": "stream_complete",
             "sub_id": sub_id.to_string(),
         })),
+        Event::MemoryCompacted {
+            actor_id,
+            messages_before,
+            messages_after,
+            tokens_before,
+            tokens_after,
+        } => Ok(json!({
+            "kind": "memory_compacted",
+            "actor_id": actor_id.to_string(),
+            "messages_before": messages_before,
+            "messages_after": messages_after,
+            "tokens_before": tokens_before,
+            "tokens_after": tokens_after,
+        })),
     }
 }
 
@@ -448,6 +480,7 @@ mod tests {
                     actor_id,
                     actor_name: "planner".to_string(),
                     task_description: "plan".to_string(),
+                    trace: None,
                 },
                 "task_started",
             ),
@@ -562,6 +595,16 @@ mod tests {
                 },
                 "turn_completed",
             ),
+            (
+                Event::LLMCallCompleted {
+                    sub_id,
+                    actor_id,
+                    model: "mock".to_string(),
+                    duration_ms: 12,
+                    usage: Some(usage.clone()),
+                },
+                "llm_call_completed",
+            ),
             (
                 Event::StreamChunk {
                     sub_id,
@@ -580,6 +623,16 @@ mod tests {
                 "stream_tool_call",
             ),
             (Event::StreamComplete { sub_id }, "stream_complete"),
+            (
+                Event::MemoryCompacted {
+                    actor_id,
+                    messages_before: 12,
+                    messages_after: 5,
+                    tokens_before: 900,
+                    tokens_after: 300,
+                },
+                "memory_compacted",
+            ),
             (
                 Event::StreamChunk {
                     sub_id,
@@ -661,6 +714,7 @@ mod tests {
             actor_id,
             actor_name: "planner".to_string(),
             task_description: "plan".to_string(),
+            trace: None,
         })
         .expect("first event should send");
         tx.send(Event::StreamComplete { sub_id })
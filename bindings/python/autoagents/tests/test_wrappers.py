@@ -117,6 +117,17 @@ def test_task_payloads_cover_optional_fields():
         "image": {"mime": "jpeg", "data": b"jpeg-bytes"},
     }
 
+    traced = Task(
+        prompt="hi",
+        user_id="u1",
+        session_id="s1",
+        trace_attributes={"tier": "gold"},
+    )
+    assert traced.to_payload() == {
+        "prompt": "hi",
+        "trace": {"user_id": "u1", "session_id": "s1", "attributes": {"tier": "gold"}},
+    }
+
 
 @pytest.mark.asyncio
 async def test_runtime_and_environment_wrap_core_types(monkeypatch):
@@ -23,6 +23,7 @@ use ractor::{ActorProcessingErr, ActorRef};
 use serde_json::Value;
 use std::fmt::Debug;
 use std::sync::Arc;
+use tracing::Instrument;
 
 /// Marker type for actor-based agents.
 ///
@@ -74,10 +75,34 @@ impl<T: AgentDeriveT + AgentExecutor + AgentHooks> Debug for ActorAgentHandle<T>
 #[derive(Debug)]
 pub struct AgentActor<T: AgentDeriveT + AgentExecutor + AgentHooks>(
     pub Arc<BaseAgent<T, ActorAgent>>,
+    Option<TaskSlots>,
 );
 
+/// Bounds how many tasks an actor runs at the same time
 #[cfg(not(target_arch = "wasm32"))]
-impl<T: AgentDeriveT + AgentExecutor + AgentHooks> AgentActor<T> {}
+#[derive(Debug, Clone)]
+struct TaskSlots {
+    semaphore: Arc<tokio::sync::Semaphore>,
+    limit: u32,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: AgentDeriveT + AgentExecutor + AgentHooks> AgentActor<T> {
+    /// An actor that runs one task at a time
+    pub fn new(agent: Arc<BaseAgent<T, ActorAgent>>) -> Self {
+        Self(agent, None)
+    }
+
+    /// Run up to `limit` tasks at the same time; further tasks wait in the mailbox
+    pub fn with_max_concurrent_tasks(mut self, limit: usize) -> Self {
+        let limit = limit.clamp(1, tokio::sync::Semaphore::MAX_PERMITS) as u32;
+        self.1 = (limit > 1).then(|| TaskSlots {
+            semaphore: Arc::new(tokio::sync::Semaphore::new(limit as usize)),
+            limit,
+        });
+        self
+    }
+}
 
 #[cfg(not(target_arch = "wasm32"))]
 impl<T: AgentDeriveT + AgentExecutor + AgentHooks> AgentBuilder<T, ActorAgent>
@@ -102,7 +127,8 @@ where
         );
 
         // Create agent actor
-        let agent_actor = AgentActor(agent.clone());
+        let agent_actor =
+            AgentActor::new(agent.clone()).with_max_concurrent_tasks(self.max_concurrent_tasks);
         let actor_ref = Actor::spawn(Some(agent_actor.0.name().into()), agent_actor, ())
             .await
             .map_err(AgentBuildError::SpawnError)?
@@ -120,6 +146,17 @@ where
         self.subscribed_topics.push(topic);
         self
     }
+
+    /// Maximum number of tasks this agent runs at the same time (default: 1)
+    ///
+    /// Tasks beyond the limit queue in the actor's mailbox in arrival order.
+    /// Keep the default for agents backed by a local model that can only
+    /// serve one request at a time, and raise it for API-backed agents
+    /// that should fan out.
+    pub fn max_concurrent_tasks(mut self, limit: usize) -> Self {
+        self.max_concurrent_tasks = limit;
+        self
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -155,7 +192,12 @@ impl<T: AgentDeriveT + AgentExecutor + AgentHooks> BaseAgent<T, ActorAgent> {
         }
 
         // Execute the agent's logic using the executor
-        match self.inner().execute(&task, context.clone()).await {
+        match self
+            .inner()
+            .execute(&task, context.clone())
+            .instrument(context.run_span(&task))
+            .await
+        {
             Ok(output) => {
                 self.finish_executor_run(&task, &context, submission_id, output)
                     .await
@@ -208,12 +250,21 @@ impl<T: AgentDeriveT + AgentExecutor + AgentHooks> BaseAgent<T, ActorAgent> {
     where
         <T as AgentExecutor>::Error: Into<RunnableAgentError>,
     {
-        match self.inner().execute_stream(&task, context).await {
+        let span = context.run_span(&task);
+        match self
+            .inner()
+            .execute_stream(&task, context)
+            .instrument(span.clone())
+            .await
+        {
             Ok(stream) => {
                 use futures::StreamExt;
                 let transformed_stream =
                     stream.map(move |result| result.map_err(|error| error.into()));
-                Ok(Box::pin(transformed_stream))
+                Ok(Box::pin(crate::utils::instrument_stream(
+                    transformed_stream,
+                    span,
+                )))
             }
             Err(error) => Err(error.into()),
         }
@@ -343,6 +394,10 @@ where
         _myself: ActorRef<Self::Msg>,
         _state: &mut Self::State,
     ) -> Result<(), ActorProcessingErr> {
+        // Let tasks still running on other slots finish first
+        if let Some(slots) = &self.1 {
+            let _all = slots.semaphore.acquire_many(slots.limit).await?;
+        }
         //Run Hook
         self.0.inner().on_agent_shutdown().await;
         Ok(())
@@ -355,23 +410,43 @@ where
         _state: &mut Self::State,
     ) -> Result<(), ActorProcessingErr> {
         let agent = self.0.clone();
-
-        // Run agent
-        let result = if agent.stream() {
-            agent.run_stream_to_completion(message).await
-        } else {
-            agent.run(message).await
+        let Some(slots) = &self.1 else {
+            run_task(agent, message).await;
+            return Ok(());
         };
 
-        // Task-level failures are surfaced on the event channel as `TaskError` (or
-        // `TaskComplete` on success) by the run helpers above. Do not propagate them
-        // to ractor — returning `Err` here terminates the actor and breaks long-running
-        // pub/sub agents after a single bad task.
-        let _ = result;
+        // Waiting for a slot keeps the remaining tasks queued in the mailbox
+        let permit = slots.semaphore.clone().acquire_owned().await?;
+        tokio::spawn(async move {
+            run_task(agent, message).await;
+            drop(permit);
+        });
         Ok(())
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+async fn run_task<T: AgentDeriveT + AgentExecutor + AgentHooks>(
+    agent: Arc<BaseAgent<T, ActorAgent>>,
+    task: Task,
+) where
+    serde_json::Value: From<<T as AgentExecutor>::Output>,
+    <T as AgentDeriveT>::Output: From<<T as AgentExecutor>::Output>,
+    <T as AgentExecutor>::Error: Into<RunnableAgentError>,
+{
+    let result = if agent.stream() {
+        agent.run_stream_to_completion(task).await
+    } else {
+        agent.run(task).await
+    };
+
+    // Task-level failures are surfaced on the event channel as `TaskError` (or
+    // `TaskComplete` on success) by the run helpers above. Do not propagate them
+    // to ractor — returning `Err` here terminates the actor and breaks long-running
+    // pub/sub agents after a single bad task.
+    let _ = result;
+}
+
 #[cfg(test)]
 #[cfg(not(target_arch = "wasm32"))]
 mod tests {
@@ -1090,4 +1165,101 @@ mod tests {
             }
         }
     }
+
+    #[derive(Debug, Clone, Default)]
+    struct SlowAgent {
+        name: String,
+        in_flight: Arc<std::sync::atomic::AtomicUsize>,
+        peak: Arc<std::sync::atomic::AtomicUsize>,
+        finished: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl AgentDeriveT for SlowAgent {
+        type Output = TestAgentOutput;
+
+        fn description(&self) -> &str {
+            "slow agent"
+        }
+
+        fn output_schema(&self) -> Option<Value> {
+            None
+        }
+
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn tools(&self) -> Vec<Box<dyn crate::tool::ToolT>> {
+            vec![]
+        }
+    }
+
+    #[async_trait]
+    impl AgentExecutor for SlowAgent {
+        type Output = TestAgentOutput;
+        type Error = TestError;
+
+        fn config(&self) -> ExecutorConfig {
+            ExecutorConfig::default()
+        }
+
+        async fn execute(
+            &self,
+            task: &Task,
+            _context: Arc<Context>,
+        ) -> Result<Self::Output, Self::Error> {
+            use std::sync::atomic::Ordering;
+            let running = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(40)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            self.finished.fetch_add(1, Ordering::SeqCst);
+            Ok(TestAgentOutput {
+                result: task.prompt.clone(),
+            })
+        }
+    }
+
+    impl AgentHooks for SlowAgent {}
+
+    async fn peak_concurrency(name: &str, limit: Option<usize>) -> usize {
+        use std::sync::atomic::Ordering;
+        let agent = SlowAgent {
+            name: name.to_string(),
+            ..SlowAgent::default()
+        };
+        let mut builder = AgentBuilder::<_, ActorAgent>::new(agent.clone())
+            .llm(Arc::new(MockLLMProvider))
+            .runtime(Arc::new(TestRuntime::new()));
+        if let Some(limit) = limit {
+            builder = builder.max_concurrent_tasks(limit);
+        }
+        let handle = builder.build().await.expect("build should succeed");
+
+        for i in 0..4 {
+            handle
+                .addr()
+                .cast(Task::new(format!("task {i}")))
+                .expect("cast should succeed");
+        }
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while agent.finished.load(Ordering::SeqCst) < 4 {
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("tasks should finish");
+        handle.addr().stop(None);
+        agent.peak.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn test_actor_runs_one_task_at_a_time_by_default() {
+        assert_eq!(peak_concurrency("serial_agent", None).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_actor_max_concurrent_tasks_bounds_parallel_runs() {
+        assert_eq!(peak_concurrency("fan_out_agent", Some(2)).await, 2);
+    }
 }
@@ -6,7 +6,7 @@ use crate::agent::{AgentExecutor, Context, output::AgentOutputT};
 use crate::tool::{ToolT, to_llm_tool};
 use async_trait::async_trait;
 use autoagents_llm::LLMProvider;
-use autoagents_llm::chat::Tool;
+use autoagents_llm::chat::{StructuredOutputFormat, Tool, ToolChoice};
 use autoagents_protocol::{ActorID, Event, SubmissionId};
 
 use serde_json::Value;
@@ -46,6 +46,40 @@ pub trait AgentDeriveT: Send + Sync + 'static + Debug {
 
     /// Get the tools available to this agent
     fn tools(&self) -> Vec<Box<dyn ToolT>>;
+
+    /// Turn limit for multi-turn executors; `None` keeps the executor default
+    fn max_turns(&self) -> Option<usize> {
+        None
+    }
+
+    /// Sampling temperature for this agent's LLM calls; `None` keeps the provider default
+    ///
+    /// Only backends that accept per-call sampling overrides apply it; see
+    /// [`SamplingOverrides`](autoagents_llm::chat::SamplingOverrides).
+    fn temperature(&self) -> Option<f32> {
+        None
+    }
+
+    /// Restricts which tools are offered to the LLM; `None` offers all tools
+    ///
+    /// `ToolChoice::Any` offers every tool like `Auto`: providers take no
+    /// per-call `tool_choice`, so forcing a call is left to their own setting.
+    fn tool_choice(&self) -> Option<ToolChoice> {
+        None
+    }
+
+    /// Overrides the `strict` flag of the structured output schema
+    fn output_strict(&self) -> Option<bool> {
+        None
+    }
+
+    /// System prompt built at run time; `None` falls back to the description
+    ///
+    /// Called before every LLM turn. A `system_prompt` set on the task takes
+    /// precedence over this.
+    fn system_prompt(&self, _context: &Context, _task: &Task) -> Option<String> {
+        None
+    }
 }
 
 pub trait AgentType: 'static + Send + Sync {
@@ -156,13 +190,18 @@ impl<T: AgentDeriveT + AgentExecutor + AgentHooks, A: AgentType> BaseAgent<T, A>
 
     pub fn agent_config(&self) -> AgentConfig {
         let output_schema = self.inner().output_schema();
-        let structured_schema =
+        let mut structured_schema: Option<StructuredOutputFormat> =
             output_schema.and_then(|schema| serde_json::from_value(schema).ok());
+        if let (Some(schema), Some(strict)) = (&mut structured_schema, self.inner.output_strict()) {
+            schema.strict = Some(strict);
+        }
         AgentConfig {
             name: self.name().into(),
             description: self.description().into(),
             id: self.id,
             output_schema: structured_schema,
+            temperature: self.inner.temperature(),
+            tool_choice: self.inner.tool_choice(),
         }
     }
 
@@ -267,6 +306,7 @@ mod tests {
             id: Uuid::new_v4(),
             description: "A test agent".to_string(),
             output_schema: Some(schema.clone()),
+            ..Default::default()
         };
 
         assert_eq!(config.name, "test_agent");
@@ -305,4 +345,75 @@ mod tests {
         assert_eq!(config.name, "ctx_agent");
         assert_eq!(config.description, "context agent");
     }
+
+    #[derive(Debug)]
+    struct TunedAgent(MockAgentImpl);
+
+    #[async_trait]
+    impl AgentDeriveT for TunedAgent {
+        type Output = <MockAgentImpl as AgentDeriveT>::Output;
+
+        fn description(&self) -> &str {
+            self.0.description()
+        }
+
+        fn output_schema(&self) -> Option<Value> {
+            self.0.output_schema()
+        }
+
+        fn name(&self) -> &str {
+            self.0.name()
+        }
+
+        fn tools(&self) -> Vec<Box<dyn ToolT>> {
+            self.0.tools()
+        }
+
+        fn temperature(&self) -> Option<f32> {
+            Some(0.1)
+        }
+
+        fn tool_choice(&self) -> Option<ToolChoice> {
+            Some(ToolChoice::None)
+        }
+
+        fn output_strict(&self) -> Option<bool> {
+            Some(false)
+        }
+    }
+
+    #[async_trait]
+    impl AgentExecutor for TunedAgent {
+        type Output = <MockAgentImpl as AgentExecutor>::Output;
+        type Error = <MockAgentImpl as AgentExecutor>::Error;
+
+        fn config(&self) -> crate::agent::ExecutorConfig {
+            self.0.config()
+        }
+
+        async fn execute(
+            &self,
+            task: &Task,
+            context: Arc<Context>,
+        ) -> Result<Self::Output, Self::Error> {
+            self.0.execute(task, context).await
+        }
+    }
+
+    impl AgentHooks for TunedAgent {}
+
+    #[tokio::test]
+    async fn test_agent_config_uses_declared_settings() {
+        let agent = TunedAgent(MockAgentImpl::new("tuned", "tuned agent"));
+        let (tx, _): (Sender<Event>, Receiver<Event>) = channel(32);
+        let base_agent =
+            BaseAgent::<_, DirectAgent>::new(agent, Arc::new(MockLLMProvider), None, tx, false)
+                .await
+                .unwrap();
+
+        let config = base_agent.agent_config();
+        assert_eq!(config.temperature, Some(0.1));
+        assert!(matches!(config.tool_choice, Some(ToolChoice::None)));
+        assert_eq!(config.output_schema.unwrap().strict, Some(false));
+    }
 }
@@ -0,0 +1,442 @@
+//! Feed one agent's streaming output into another agent as it is produced
+//!
+//! [`StreamBridge`] cuts the upstream text into segments at sentence, line or
+//! paragraph boundaries and hands each segment to the downstream agent as soon
+//! as it is complete, so a summarize → translate chain starts translating
+//! before the summary is finished. Segments pass through a bounded channel:
+//! once `buffer` segments wait for the downstream agent, the upstream stream
+//! stops being polled until one is taken.
+//!
+//! Upstream text ends at a [`TextPiece::Full`] item. The prebuilt executor
+//! outputs send one when they finish; for agents whose output is a `String`,
+//! where the last item repeats the whole text, wrap the stream in
+//! [`final_item_full`].
+
+use crate::agent::error::RunnableAgentError;
+use crate::agent::prebuilt::executor::{BasicAgentOutput, ReActAgentOutput};
+use crate::agent::task::Task;
+use crate::agent::{AgentDeriveT, AgentExecutor, AgentHooks, BaseAgent, DirectAgent};
+use crate::channel::channel;
+use crate::error::Error;
+use crate::utils::{BoxRuntimeStream, stream_from_producer};
+use futures::{Stream, StreamExt};
+use serde_json::Value;
+
+/// Text carried by one item of an upstream stream
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextPiece {
+    /// New text to append
+    Delta(String),
+    /// The complete text, sent by executors when they finish
+    Full(String),
+}
+
+/// Stream items the bridge can read text from
+///
+/// Items whose stream ends by repeating the complete text must report that
+/// item as [`TextPiece::Full`], or the text is segmented twice.
+pub trait StreamedText {
+    /// The text of this item, or `None` if it carries none (e.g. tool progress)
+    fn text_piece(&self) -> Option<TextPiece>;
+}
+
+/// Every string is a delta; see [`final_item_full`] for streams that end
+/// with the complete text
+impl StreamedText for String {
+    fn text_piece(&self) -> Option<TextPiece> {
+        Some(TextPiece::Delta(self.clone()))
+    }
+}
+
+impl StreamedText for TextPiece {
+    fn text_piece(&self) -> Option<TextPiece> {
+        Some(self.clone())
+    }
+}
+
+impl StreamedText for BasicAgentOutput {
+    fn text_piece(&self) -> Option<TextPiece> {
+        text_piece(&self.response, self.done)
+    }
+}
+
+impl StreamedText for ReActAgentOutput {
+    fn text_piece(&self) -> Option<TextPiece> {
+        text_piece(&self.response, self.done)
+    }
+}
+
+#[cfg(feature = "codeact")]
+impl StreamedText for crate::agent::prebuilt::executor::CodeActAgentOutput {
+    fn text_piece(&self) -> Option<TextPiece> {
+        text_piece(&self.response, self.done)
+    }
+}
+
+fn text_piece(response: &str, done: bool) -> Option<TextPiece> {
+    match (done, response.is_empty()) {
+        (true, _) => Some(TextPiece::Full(response.to_string())),
+        (false, true) => None,
+        (false, false) => Some(TextPiece::Delta(response.to_string())),
+    }
+}
+
+/// Mark the last text item of `upstream` as [`TextPiece::Full`]
+///
+/// For streams such as `run_stream` of an agent whose output converts to
+/// `String`, which yield deltas and then the complete text once more. Each
+/// item is held back until the next one arrives, to know whether it is the
+/// last.
+pub fn final_item_full<S, O>(upstream: S) -> BoxRuntimeStream<Result<TextPiece, Error>>
+where
+    S: Stream<Item = Result<O, Error>> + Send + 'static,
+    O: StreamedText + Send + 'static,
+{
+    let pieces = upstream.filter_map(|item| {
+        futures::future::ready(match item {
+            Ok(item) => item.text_piece().map(Ok),
+            Err(err) => Some(Err(err)),
+        })
+    });
+    let pieces = Box::pin(pieces.peekable());
+    Box::pin(futures::stream::unfold(pieces, |mut pieces| async move {
+        let piece = pieces.next().await?;
+        let last = pieces.as_mut().peek().await.is_none();
+        let piece = match piece {
+            Ok(TextPiece::Delta(text)) if last => Ok(TextPiece::Full(text)),
+            piece => piece,
+        };
+        Some((piece, pieces))
+    }))
+}
+
+/// Where the bridge may cut upstream text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SegmentBoundary {
+    /// After `.`, `!` or `?` followed by whitespace
+    #[default]
+    Sentence,
+    /// After each newline
+    Line,
+    /// After each blank line
+    Paragraph,
+}
+
+/// Pipes streamed text from one agent into another, segment by segment
+#[derive(Debug, Clone)]
+pub struct StreamBridge {
+    buffer: usize,
+    boundary: SegmentBoundary,
+    max_chars: usize,
+}
+
+impl Default for StreamBridge {
+    fn default() -> Self {
+        Self {
+            buffer: 4,
+            boundary: SegmentBoundary::default(),
+            max_chars: 2000,
+        }
+    }
+}
+
+impl StreamBridge {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum number of segments waiting for the downstream agent
+    pub fn buffer(mut self, segments: usize) -> Self {
+        self.buffer = segments.max(1);
+        self
+    }
+
+    pub fn boundary(mut self, boundary: SegmentBoundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Cut a segment at this length even if no boundary was seen
+    pub fn max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars.max(1);
+        self
+    }
+
+    /// Segment an upstream stream without running a downstream agent
+    ///
+    /// A `Full` piece ends the text; whatever it adds beyond the deltas is
+    /// still emitted. Without one, the text ends with the stream.
+    pub fn segments<S, O>(&self, upstream: S) -> BoxRuntimeStream<Result<String, Error>>
+    where
+        S: Stream<Item = Result<O, Error>> + Send + 'static,
+        O: StreamedText + Send + 'static,
+    {
+        let config = self.clone();
+        let (tx, rx) = channel(self.buffer);
+        let producer = async move {
+            let mut upstream = Box::pin(upstream);
+            let mut seen = String::new();
+            let mut pending = String::new();
+            while let Some(item) = upstream.next().await {
+                let delta = match item.map(|item| item.text_piece()) {
+                    Ok(Some(TextPiece::Delta(delta))) => delta,
+                    Ok(Some(TextPiece::Full(full))) => {
+                        if let Some(rest) = full.strip_prefix(seen.as_str()) {
+                            pending.push_str(rest);
+                        }
+                        break;
+                    }
+                    Ok(None) => continue,
+                    Err(err) => {
+                        let _ = tx.send(Err(err)).await;
+                        return;
+                    }
+                };
+                seen.push_str(&delta);
+                pending.push_str(&delta);
+                for segment in config.take_ready(&mut pending) {
+                    if tx.send(Ok(segment)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            let rest = pending.trim();
+            if !rest.is_empty() {
+                let _ = tx.send(Ok(rest.to_string())).await;
+            }
+        };
+        stream_from_producer(rx, producer)
+    }
+
+    /// Run `downstream` on each segment of `upstream` as it becomes ready
+    ///
+    /// `make_task` turns a segment into the downstream task, e.g. by wrapping
+    /// it in an instruction. Segments are processed in order, one at a time;
+    /// the stream yields one downstream output per segment and stops after
+    /// the first upstream error.
+    pub fn pipe<S, O, T, F>(
+        &self,
+        upstream: S,
+        downstream: &BaseAgent<T, DirectAgent>,
+        make_task: F,
+    ) -> BoxRuntimeStream<Result<<T as AgentDeriveT>::Output, Error>>
+    where
+        S: Stream<Item = Result<O, Error>> + Send + 'static,
+        O: StreamedText + Send + 'static,
+        T: AgentDeriveT + AgentExecutor + AgentHooks,
+        F: Fn(String) -> Task + Send + Sync + 'static,
+        Value: From<<T as AgentExecutor>::Output>,
+        <T as AgentDeriveT>::Output: From<<T as AgentExecutor>::Output>,
+        <T as AgentExecutor>::Output: Clone,
+        <T as AgentExecutor>::Error: Into<RunnableAgentError>,
+    {
+        let downstream = downstream.clone_shallow();
+        let make_task = std::sync::Arc::new(make_task);
+        Box::pin(self.segments(upstream).then(move |segment| {
+            let downstream = downstream.clone_shallow();
+            let make_task = make_task.clone();
+            async move {
+                downstream
+                    .run(make_task(segment?))
+                    .await
+                    .map_err(Error::from)
+            }
+        }))
+    }
+
+    /// Remove and return every complete segment at the front of `pending`
+    fn take_ready(&self, pending: &mut String) -> Vec<String> {
+        let mut segments = Vec::new();
+        loop {
+            let leading = pending.len() - pending.trim_start().len();
+            pending.drain(..leading);
+            let limit = pending
+                .char_indices()
+                .nth(self.max_chars)
+                .map(|(index, _)| index);
+            let cut = match (self.boundary_end(pending), limit) {
+                (Some(end), limit) if limit.is_none_or(|limit| end <= limit) => Some(end),
+                (_, Some(limit)) => {
+                    // Prefer the last whitespace, including one right at the limit.
+                    let window = pending[limit..]
+                        .chars()
+                        .next()
+                        .map_or(limit, |c| limit + c.len_utf8());
+                    Some(
+                        pending[..window]
+                            .rfind(char::is_whitespace)
+                            .filter(|&index| index > 0)
+                            .unwrap_or(limit),
+                    )
+                }
+                _ => None,
+            };
+            let Some(cut) = cut else {
+                return segments;
+            };
+            let segment = pending[..cut].trim().to_string();
+            pending.drain(..cut);
+            if !segment.is_empty() {
+                segments.push(segment);
+            }
+        }
+    }
+
+    /// Byte offset just past the first boundary in `text`
+    fn boundary_end(&self, text: &str) -> Option<usize> {
+        match self.boundary {
+            SegmentBoundary::Line => text.find('\n').map(|index| index + 1),
+            SegmentBoundary::Paragraph => text.find("\n\n").map(|index| index + 2),
+            // The whitespace after the terminator must have arrived, or
+            // "3." could still become "3.14".
+            SegmentBoundary::Sentence => text
+                .char_indices()
+                .zip(text.chars().skip(1))
+                .find(|((_, c), next)| matches!(c, '.' | '!' | '?') && next.is_whitespace())
+                .map(|((index, c), _)| index + c.len_utf8()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentBuilder;
+    use crate::agent::prebuilt::executor::BasicAgent;
+    use crate::testing::ScriptedLLM;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn deltas(items: &[&str]) -> impl Stream<Item = Result<String, Error>> + Send + 'static {
+        futures::stream::iter(
+            items
+                .iter()
+                .map(|item| Ok(item.to_string()))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    async fn collect(stream: BoxRuntimeStream<Result<String, Error>>) -> Vec<String> {
+        stream.map(|segment| segment.unwrap()).collect().await
+    }
+
+    #[tokio::test]
+    async fn test_segments_cut_at_sentence_ends() {
+        let bridge = StreamBridge::new();
+        let segments =
+            collect(bridge.segments(deltas(&["Pi is 3.", "14. It is ir", "rational! Done"]))).await;
+        assert_eq!(segments, ["Pi is 3.14.", "It is irrational!", "Done"]);
+    }
+
+    #[tokio::test]
+    async fn test_segments_keep_repeated_deltas() {
+        let bridge = StreamBridge::new().boundary(SegmentBoundary::Line);
+        let segments = collect(bridge.segments(deltas(&["ha", "ha", "\n", "\n", "done"]))).await;
+        assert_eq!(segments, ["haha", "done"]);
+    }
+
+    #[tokio::test]
+    async fn test_segments_drop_final_aggregate() {
+        let bridge = StreamBridge::new().boundary(SegmentBoundary::Line);
+        let upstream = final_item_full(deltas(&["a\nb", "\nc", "a\nb\nc"]));
+        assert_eq!(collect(bridge.segments(upstream)).await, ["a", "b", "c"]);
+        let upstream = final_item_full(deltas(&["ha", "ha", "haha"]));
+        assert_eq!(collect(bridge.segments(upstream)).await, ["haha"]);
+
+        let outputs = futures::stream::iter(vec![
+            Ok(ReActAgentOutput {
+                response: "one\n".into(),
+                tool_calls: Vec::new(),
+                done: false,
+            }),
+            Ok(ReActAgentOutput {
+                response: "one\ntwo".into(),
+                tool_calls: Vec::new(),
+                done: true,
+            }),
+        ]);
+        assert_eq!(collect(bridge.segments(outputs)).await, ["one", "two"]);
+    }
+
+    #[test]
+    fn test_long_text_is_cut_at_max_chars() {
+        let bridge = StreamBridge::new()
+            .boundary(SegmentBoundary::Paragraph)
+            .max_chars(10);
+        let mut pending = "alpha beta gamma delta\n\nrest".to_string();
+        assert_eq!(
+            bridge.take_ready(&mut pending),
+            ["alpha beta", "gamma", "delta"]
+        );
+        assert_eq!(pending, "rest");
+    }
+
+    #[tokio::test]
+    async fn test_upstream_waits_while_buffer_is_full() {
+        let polled = Arc::new(AtomicUsize::new(0));
+        let counter = polled.clone();
+        let upstream = futures::stream::iter(0..100).map(move |index| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(format!("sentence {index}. "))
+        });
+
+        let mut segments = StreamBridge::new().buffer(2).segments(upstream);
+        assert_eq!(segments.next().await.unwrap().unwrap(), "sentence 0.");
+        tokio::task::yield_now().await;
+        assert!(polled.load(Ordering::SeqCst) < 10);
+
+        assert_eq!(segments.count().await, 99);
+        assert_eq!(polled.load(Ordering::SeqCst), 100);
+    }
+
+    #[derive(Debug)]
+    struct Translator;
+
+    impl AgentDeriveT for Translator {
+        type Output = String;
+
+        fn description(&self) -> &str {
+            "Translate to French"
+        }
+
+        fn output_schema(&self) -> Option<Value> {
+            None
+        }
+
+        fn name(&self) -> &str {
+            "translator"
+        }
+
+        fn tools(&self) -> Vec<Box<dyn crate::tool::ToolT>> {
+            Vec::new()
+        }
+    }
+
+    impl AgentHooks for Translator {}
+
+    #[tokio::test]
+    async fn test_pipe_runs_downstream_per_segment() {
+        let llm = Arc::new(ScriptedLLM::new().reply("Bonjour.").reply("Au revoir."));
+        let handle = AgentBuilder::<_, DirectAgent>::new(BasicAgent::new(Translator))
+            .llm(llm.clone())
+            .build()
+            .await
+            .unwrap();
+
+        let outputs: Vec<String> = StreamBridge::new()
+            .pipe(deltas(&["Hello. Good", "bye."]), &handle.agent, |segment| {
+                Task::new(format!("Translate: {segment}"))
+            })
+            .map(|output| output.unwrap())
+            .collect()
+            .await;
+
+        assert_eq!(outputs, ["Bonjour.", "Au revoir."]);
+        let prompts: Vec<String> = llm
+            .requests()
+            .iter()
+            .map(|request| request.messages.last().unwrap().content.clone())
+            .collect();
+        assert_eq!(prompts, ["Translate: Hello.", "Translate: Goodbye."]);
+    }
+}
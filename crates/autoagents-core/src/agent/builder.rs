@@ -21,6 +21,8 @@ pub struct AgentBuilder<T: AgentDeriveT + AgentExecutor + AgentHooks, A: AgentTy
     pub(crate) runtime: Option<Arc<dyn Runtime>>,
     #[cfg(not(target_arch = "wasm32"))]
     pub(crate) subscribed_topics: Vec<Topic<Task>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) max_concurrent_tasks: usize,
     marker: PhantomData<A>,
 }
 
@@ -36,6 +38,8 @@ impl<T: AgentDeriveT + AgentExecutor + AgentHooks, A: AgentType> AgentBuilder<T,
             stream: false,
             #[cfg(not(target_arch = "wasm32"))]
             subscribed_topics: vec![],
+            #[cfg(not(target_arch = "wasm32"))]
+            max_concurrent_tasks: 1,
             marker: PhantomData,
         }
     }
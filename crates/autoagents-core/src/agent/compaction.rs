@@ -0,0 +1,385 @@
+//! On-demand conversation compaction
+//!
+//! [`BaseAgent::compact`] summarizes the older part of an agent's memory with
+//! its own LLM and replaces it with the summary, keeping pinned messages and
+//! the most recent exchanges verbatim. Long sessions then fit the small
+//! context windows of local models.
+
+use std::fmt;
+use std::sync::Arc;
+
+use autoagents_llm::chat::{ChatMessage, ChatRole, MessageType};
+use autoagents_protocol::Event;
+
+use crate::agent::base::AgentType;
+use crate::agent::error::RunnableAgentError;
+use crate::agent::executor::event_helper::EventHelper;
+use crate::agent::{AgentDeriveT, AgentExecutor, AgentHooks, BaseAgent};
+use crate::chunking::{Tokenizer, WordTokenizer};
+
+const DEFAULT_INSTRUCTIONS: &str = "Summarize the conversation below for your own future reference. \
+Keep names, identifiers, decisions, open questions and commitments; drop pleasantries. \
+Reply with the summary only.";
+
+type PinFn = dyn Fn(&ChatMessage) -> bool + Send + Sync;
+
+/// How [`BaseAgent::compact`] shrinks memory
+#[derive(Clone)]
+pub struct CompactionConfig {
+    keep_recent: usize,
+    pinned_facts: Vec<String>,
+    pin: Option<Arc<PinFn>>,
+    instructions: String,
+    tokenizer: Arc<dyn Tokenizer>,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        Self {
+            keep_recent: 6,
+            pinned_facts: Vec::new(),
+            pin: None,
+            instructions: DEFAULT_INSTRUCTIONS.to_string(),
+            tokenizer: Arc::new(WordTokenizer),
+        }
+    }
+}
+
+impl fmt::Debug for CompactionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompactionConfig")
+            .field("keep_recent", &self.keep_recent)
+            .field("pinned_facts", &self.pinned_facts)
+            .field("instructions", &self.instructions)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CompactionConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of most recent messages kept verbatim
+    ///
+    /// The kept window is widened so it never starts with a tool result whose
+    /// tool call would be summarized away.
+    pub fn keep_recent(mut self, messages: usize) -> Self {
+        self.keep_recent = messages;
+        self
+    }
+
+    /// A fact stored in front of the summary after every compaction
+    pub fn pinned_fact(mut self, fact: impl Into<String>) -> Self {
+        self.pinned_facts.push(fact.into());
+        self
+    }
+
+    /// Keep messages matching `pin` verbatim instead of summarizing them
+    pub fn pin<F>(mut self, pin: F) -> Self
+    where
+        F: Fn(&ChatMessage) -> bool + Send + Sync + 'static,
+    {
+        self.pin = Some(Arc::new(pin));
+        self
+    }
+
+    /// System prompt for the summarization call
+    pub fn instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = instructions.into();
+        self
+    }
+
+    /// Tokenizer used for the before/after counts (default: [`WordTokenizer`])
+    pub fn tokenizer(mut self, tokenizer: Arc<dyn Tokenizer>) -> Self {
+        self.tokenizer = tokenizer;
+        self
+    }
+
+    fn count_tokens(&self, messages: &[ChatMessage]) -> usize {
+        messages
+            .iter()
+            .map(|message| self.tokenizer.token_spans(&message.content).len())
+            .sum()
+    }
+}
+
+/// Memory size before and after a [`BaseAgent::compact`] call
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompactionReport {
+    pub messages_before: usize,
+    pub messages_after: usize,
+    pub tokens_before: usize,
+    pub tokens_after: usize,
+}
+
+impl<T: AgentDeriveT + AgentExecutor + AgentHooks, A: AgentType> BaseAgent<T, A> {
+    /// Summarize and prune this agent's memory
+    ///
+    /// Older messages are replaced with one summary written by the agent's
+    /// LLM; pinned facts, pinned messages and the most recent messages are
+    /// kept. Emits [`Event::MemoryCompacted`] with the before/after counts.
+    /// Memory is left untouched when there is nothing to summarize or the
+    /// summarization call fails.
+    pub async fn compact(
+        &self,
+        config: &CompactionConfig,
+    ) -> Result<CompactionReport, RunnableAgentError> {
+        let memory = self.memory().ok_or_else(|| {
+            RunnableAgentError::StateError("compaction needs an agent with memory".to_string())
+        })?;
+        let mut memory = memory.lock().await;
+        let messages = memory.recall("", None).await?;
+
+        let split = recent_start(&messages, config.keep_recent);
+        let (older, recent) = messages.split_at(split);
+        let (pinned, summarized): (Vec<&ChatMessage>, Vec<&ChatMessage>) = older
+            .iter()
+            .partition(|message| config.pin.as_ref().is_some_and(|pin| pin(message)));
+
+        let tokens_before = config.count_tokens(&messages);
+        let mut report = CompactionReport {
+            messages_before: messages.len(),
+            messages_after: messages.len(),
+            tokens_before,
+            tokens_after: tokens_before,
+        };
+        if !summarized.is_empty() {
+            let summary = self.summarize(config, &summarized).await?;
+            let mut compacted = Vec::with_capacity(pinned.len() + recent.len() + 2);
+            if !config.pinned_facts.is_empty() {
+                compacted.push(text_message(
+                    ChatRole::User,
+                    format!("Pinned facts:\n- {}", config.pinned_facts.join("\n- ")),
+                ));
+            }
+            compacted.extend(pinned.into_iter().cloned());
+            compacted.push(text_message(
+                ChatRole::Assistant,
+                format!("Summary of the earlier conversation:\n{summary}"),
+            ));
+            compacted.extend_from_slice(recent);
+
+            memory.clear().await?;
+            memory.remember_many(&compacted).await?;
+            report.messages_after = compacted.len();
+            report.tokens_after = config.count_tokens(&compacted);
+        }
+        drop(memory);
+
+        EventHelper::send(
+            &self.tx,
+            Event::MemoryCompacted {
+                actor_id: self.id,
+                messages_before: report.messages_before,
+                messages_after: report.messages_after,
+                tokens_before: report.tokens_before,
+                tokens_after: report.tokens_after,
+            },
+        )
+        .await;
+        Ok(report)
+    }
+
+    async fn summarize(
+        &self,
+        config: &CompactionConfig,
+        messages: &[&ChatMessage],
+    ) -> Result<String, RunnableAgentError> {
+        let transcript: Vec<String> = messages
+            .iter()
+            .map(|message| match &message.message_type {
+                MessageType::ToolUse(calls) => {
+                    let names: Vec<&str> = calls
+                        .iter()
+                        .map(|call| call.function.name.as_str())
+                        .collect();
+                    format!(
+                        "{}: [called {}] {}",
+                        message.role,
+                        names.join(", "),
+                        message.content
+                    )
+                }
+                MessageType::ToolResult(results) => {
+                    let outputs: Vec<&str> = results
+                        .iter()
+                        .map(|result| result.function.arguments.as_str())
+                        .collect();
+                    format!("{}: {}", message.role, outputs.join("\n"))
+                }
+                _ => format!("{}: {}", message.role, message.content),
+            })
+            .collect();
+        let request = [
+            text_message(ChatRole::System, config.instructions.clone()),
+            text_message(ChatRole::User, transcript.join("\n")),
+        ];
+        let response = self.llm().chat(&request, None).await?;
+        Ok(response.text().unwrap_or_default().trim().to_string())
+    }
+}
+
+/// Index where the verbatim window of the last `keep_recent` messages starts
+fn recent_start(messages: &[ChatMessage], keep_recent: usize) -> usize {
+    let mut start = messages.len().saturating_sub(keep_recent);
+    while start > 0 && start < messages.len() && messages[start].role == ChatRole::Tool {
+        start -= 1;
+    }
+    start
+}
+
+fn text_message(role: ChatRole, content: String) -> ChatMessage {
+    ChatMessage {
+        role,
+        message_type: MessageType::Text,
+        content,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::memory::SlidingWindowMemory;
+    use crate::agent::prebuilt::executor::BasicAgent;
+    use crate::agent::{AgentBuilder, DirectAgent};
+    use crate::testing::ScriptedLLM;
+    use crate::tool::ToolT;
+    use autoagents_llm::{FunctionCall, ToolCall};
+    use futures::StreamExt;
+    use serde_json::Value;
+
+    #[derive(Debug)]
+    struct SupportAgent;
+
+    impl AgentDeriveT for SupportAgent {
+        type Output = String;
+
+        fn description(&self) -> &str {
+            "Support agent"
+        }
+
+        fn output_schema(&self) -> Option<Value> {
+            None
+        }
+
+        fn name(&self) -> &str {
+            "support"
+        }
+
+        fn tools(&self) -> Vec<Box<dyn ToolT>> {
+            Vec::new()
+        }
+    }
+
+    impl AgentHooks for SupportAgent {}
+
+    fn message(role: ChatRole, content: &str) -> ChatMessage {
+        text_message(role, content.to_string())
+    }
+
+    #[test]
+    fn test_recent_window_does_not_start_with_tool_result() {
+        let call = ToolCall {
+            id: "1".into(),
+            call_type: "function".into(),
+            function: FunctionCall {
+                name: "lookup".into(),
+                arguments: "{}".into(),
+            },
+        };
+        let messages = vec![
+            message(ChatRole::User, "q"),
+            ChatMessage {
+                role: ChatRole::Assistant,
+                message_type: MessageType::ToolUse(vec![call.clone()]),
+                content: String::new(),
+            },
+            ChatMessage {
+                role: ChatRole::Tool,
+                message_type: MessageType::ToolResult(vec![call]),
+                content: String::new(),
+            },
+            message(ChatRole::Assistant, "a"),
+        ];
+        assert_eq!(recent_start(&messages, 2), 1);
+        assert_eq!(recent_start(&messages, 1), 3);
+        assert_eq!(recent_start(&messages, 10), 0);
+    }
+
+    #[tokio::test]
+    async fn test_compact_summarizes_older_messages_and_keeps_pins() {
+        let llm = Arc::new(ScriptedLLM::new().reply("Customer reported a login issue."));
+        let mut handle = AgentBuilder::<_, DirectAgent>::new(BasicAgent::new(SupportAgent))
+            .llm(llm.clone())
+            .memory(Box::new(SlidingWindowMemory::new(50)))
+            .build()
+            .await
+            .unwrap();
+        let mut events = handle.subscribe_events();
+
+        let history = [
+            message(
+                ChatRole::User,
+                "Hi, I cannot log in to my account since this morning",
+            ),
+            message(
+                ChatRole::Assistant,
+                "Sorry to hear that. Which browser do you use?",
+            ),
+            message(ChatRole::User, "FACT: account id is 4821"),
+            message(ChatRole::Assistant, "Thanks, looking it up."),
+            message(ChatRole::User, "Any news?"),
+            message(ChatRole::Assistant, "A reset link is on its way."),
+        ];
+        let memory = handle.agent.memory().unwrap();
+        memory.lock().await.remember_many(&history).await.unwrap();
+
+        let config = CompactionConfig::new()
+            .keep_recent(2)
+            .pinned_fact("Plan: premium")
+            .pin(|message| message.content.starts_with("FACT:"));
+        let report = handle.agent.compact(&config).await.unwrap();
+
+        let contents: Vec<String> = memory
+            .lock()
+            .await
+            .recall("", None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|message| message.content)
+            .collect();
+        assert_eq!(
+            contents,
+            [
+                "Pinned facts:\n- Plan: premium",
+                "FACT: account id is 4821",
+                "Summary of the earlier conversation:\nCustomer reported a login issue.",
+                "Any news?",
+                "A reset link is on its way.",
+            ]
+        );
+        assert_eq!(report.messages_before, 6);
+        assert_eq!(report.messages_after, 5);
+        assert!(report.tokens_after < report.tokens_before);
+
+        let transcript = &llm.requests()[0].messages[1].content;
+        assert!(transcript.contains("cannot log in"));
+        assert!(!transcript.contains("account id"));
+
+        match events.next().await.unwrap() {
+            Event::MemoryCompacted {
+                messages_before,
+                messages_after,
+                ..
+            } => assert_eq!((messages_before, messages_after), (6, 5)),
+            other => panic!("unexpected event: {other:?}"),
+        }
+
+        // Nothing left to summarize: no LLM call, memory unchanged
+        let again = handle.agent.compact(&config.keep_recent(10)).await.unwrap();
+        assert_eq!(again.messages_after, again.messages_before);
+        assert_eq!(llm.requests().len(), 1);
+    }
+}
@@ -1,6 +1,9 @@
-use autoagents_llm::chat::StructuredOutputFormat;
+use autoagents_llm::chat::{StructuredOutputFormat, ToolChoice};
 use autoagents_protocol::ActorID;
 
+/// Fields may be added in minor releases, so struct literals should end
+/// with `..Default::default()`, or build with [`AgentConfig::new`] and the
+/// `with_*` methods.
 #[derive(Clone, Default)]
 pub struct AgentConfig {
     /// The agent's name
@@ -11,6 +14,10 @@ pub struct AgentConfig {
     pub id: ActorID,
     /// The output schema for the agent
     pub output_schema: Option<StructuredOutputFormat>,
+    /// Sampling temperature applied to LLM calls, if set
+    pub temperature: Option<f32>,
+    /// Which tools are offered to the LLM, if restricted
+    pub tool_choice: Option<ToolChoice>,
 }
 
 impl AgentConfig {
@@ -20,6 +27,8 @@ impl AgentConfig {
             description,
             id: ActorID::new_v4(),
             output_schema: None,
+            temperature: None,
+            tool_choice: None,
         }
     }
 
@@ -27,6 +36,16 @@ impl AgentConfig {
         self.output_schema = Some(schema);
         self
     }
+
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    pub fn with_tool_choice(mut self, tool_choice: ToolChoice) -> Self {
+        self.tool_choice = Some(tool_choice);
+        self
+    }
 }
 
 #[cfg(test)]
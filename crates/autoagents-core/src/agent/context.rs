@@ -6,7 +6,7 @@ use crate::agent::state::AgentState;
 use crate::tool::{ToolT, to_llm_tool};
 use autoagents_llm::LLMProvider;
 use autoagents_llm::chat::{ChatMessage, Tool};
-use autoagents_protocol::Event;
+use autoagents_protocol::{Event, Task};
 use std::any::Any;
 use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
@@ -138,6 +138,17 @@ impl Context {
         &self.config
     }
 
+    /// Span wrapping a task run so logs emitted by executors, LLM backends, and tools
+    /// carry the submission they belong to.
+    pub(crate) fn run_span(&self, task: &Task) -> tracing::Span {
+        tracing::info_span!(
+            "autoagents.agent.run",
+            submission_id = %task.submission_id,
+            actor_id = %self.config.id,
+            actor_name = %self.config.name,
+        )
+    }
+
     pub fn state(&self) -> Arc<Mutex<AgentState>> {
         self.state.clone()
     }
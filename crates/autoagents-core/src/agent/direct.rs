@@ -8,6 +8,7 @@ use crate::error::Error;
 use autoagents_protocol::Event;
 use serde_json::Value;
 use std::sync::Arc;
+use tracing::Instrument;
 
 use crate::agent::constants::DEFAULT_CHANNEL_BUFFER;
 
@@ -15,7 +16,7 @@ use crate::channel::{Receiver, Sender, channel};
 
 #[cfg(not(target_arch = "wasm32"))]
 use crate::event_fanout::EventFanout;
-use crate::utils::{BoxEventStream, receiver_into_stream};
+use crate::utils::{BoxEventStream, instrument_stream, receiver_into_stream};
 #[cfg(not(target_arch = "wasm32"))]
 use futures_util::stream;
 
@@ -205,7 +206,12 @@ impl<T: AgentDeriveT + AgentExecutor + AgentHooks> BaseAgent<T, DirectAgent> {
         }
 
         // Execute the agent's logic using the executor
-        match self.inner().execute(&task, context.clone()).await {
+        match self
+            .inner()
+            .execute(&task, context.clone())
+            .instrument(context.run_span(&task))
+            .await
+        {
             Ok(output) => {
                 self.finish_executor_run(&task, &context, submission_id, output)
                     .await
@@ -251,16 +257,25 @@ impl<T: AgentDeriveT + AgentExecutor + AgentHooks> BaseAgent<T, DirectAgent> {
         }
 
         // Execute the agent's streaming logic using the executor
-        match self.inner().execute_stream(&task, context.clone()).await {
-            Ok(stream) => Ok(wrap_direct_stream_with_terminal_events(
-                self.clone_shallow(),
-                stream,
-                task,
-                context,
-                tx_event,
-                submission_id,
-                self.id,
-            )),
+        let span = context.run_span(&task);
+        match self
+            .inner()
+            .execute_stream(&task, context.clone())
+            .instrument(span.clone())
+            .await
+        {
+            Ok(stream) => Ok(Box::pin(instrument_stream(
+                wrap_direct_stream_with_terminal_events(
+                    self.clone_shallow(),
+                    stream,
+                    task,
+                    context,
+                    tx_event,
+                    submission_id,
+                    self.id,
+                ),
+                span,
+            ))),
             Err(e) => {
                 let err: RunnableAgentError = e.into();
                 #[cfg(not(target_arch = "wasm32"))]
@@ -1,6 +1,6 @@
-use autoagents_llm::chat::StreamChunk as LlmStreamChunk;
+use autoagents_llm::chat::{StreamChunk as LlmStreamChunk, Usage as LlmUsage};
 use autoagents_protocol::StreamChunk;
-use autoagents_protocol::{ActorID, Event, SubmissionId};
+use autoagents_protocol::{ActorID, Event, SubmissionId, TraceAttributes};
 use serde_json::Value;
 
 use crate::agent::error::RunnableAgentError;
@@ -39,6 +39,7 @@ impl EventHelper {
         actor_id: ActorID,
         actor_name: String,
         task_description: String,
+        trace: Option<TraceAttributes>,
     ) {
         Self::send(
             tx,
@@ -47,6 +48,7 @@ impl EventHelper {
                 actor_id,
                 actor_name,
                 task_description,
+                trace,
             },
         )
         .await;
@@ -175,6 +177,28 @@ impl EventHelper {
         .await;
     }
 
+    /// Send LLM call completed event
+    pub async fn send_llm_call_completed(
+        tx: &Option<mpsc::Sender<Event>>,
+        sub_id: SubmissionId,
+        actor_id: ActorID,
+        model: String,
+        duration: std::time::Duration,
+        usage: Option<LlmUsage>,
+    ) {
+        Self::send(
+            tx,
+            Event::LLMCallCompleted {
+                sub_id,
+                actor_id,
+                model,
+                duration_ms: duration.as_millis() as u64,
+                usage: usage.map(Into::into),
+            },
+        )
+        .await;
+    }
+
     /// Send stream chunk event
     pub async fn send_stream_chunk(
         tx: &Option<mpsc::Sender<Event>>,
@@ -309,4 +333,41 @@ mod tests {
             _ => panic!("unexpected event"),
         }
     }
+
+    #[tokio::test]
+    async fn llm_call_completed_converts_usage() {
+        let (tx, mut rx) = mpsc::channel::<Event>(1);
+        let tx = Some(tx);
+        let usage = LlmUsage {
+            prompt_tokens: 7,
+            completion_tokens: 3,
+            total_tokens: 10,
+            completion_tokens_details: None,
+            prompt_tokens_details: None,
+        };
+
+        EventHelper::send_llm_call_completed(
+            &tx,
+            SubmissionId::new_v4(),
+            ActorID::new_v4(),
+            "mock-model".to_string(),
+            std::time::Duration::from_millis(42),
+            Some(usage),
+        )
+        .await;
+
+        match rx.recv().await.expect("event") {
+            Event::LLMCallCompleted {
+                model,
+                duration_ms,
+                usage,
+                ..
+            } => {
+                assert_eq!(model, "mock-model");
+                assert_eq!(duration_ms, 42);
+                assert_eq!(usage.map(|usage| usage.prompt_tokens), Some(7));
+            }
+            _ => panic!("unexpected event"),
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::tool::{ToolCallResult, ToolT};
+use crate::tool::{ToolCallError, ToolCallResult, ToolT, collect_tool_output};
 use autoagents_llm::{FunctionCall, ToolCall};
 use autoagents_protocol::{ActorID, Event, SubmissionId};
 use serde_json::Value;
@@ -123,18 +123,25 @@ impl ToolProcessor {
     /// Execute a tool and return the result
     async fn execute_tool(tool: &dyn ToolT, tool_name: &str, tool_args: &str) -> ToolCallResult {
         match serde_json::from_str::<Value>(tool_args) {
-            Ok(parsed_args) => match tool.execute(parsed_args).await {
-                Ok(output) => ToolCallResult {
-                    tool_name: tool_name.to_string(),
-                    success: true,
-                    arguments: serde_json::from_str(tool_args).unwrap_or(Value::Null),
-                    result: output,
-                },
+            Ok(parsed_args) => match tool.validate_args(&parsed_args) {
                 Err(e) => Self::create_error_result(
                     tool_name,
                     tool_args,
-                    &format!("Tool execution failed: {e}"),
+                    &format!("Invalid tool arguments: {e}"),
                 ),
+                Ok(()) => match Self::run_tool(tool, parsed_args).await {
+                    Ok(output) => ToolCallResult {
+                        tool_name: tool_name.to_string(),
+                        success: true,
+                        arguments: serde_json::from_str(tool_args).unwrap_or(Value::Null),
+                        result: output,
+                    },
+                    Err(e) => Self::create_error_result(
+                        tool_name,
+                        tool_args,
+                        &format!("Tool execution failed: {e}"),
+                    ),
+                },
             },
             Err(e) => Self::create_error_result(
                 tool_name,
@@ -144,6 +151,16 @@ impl ToolProcessor {
         }
     }
 
+    /// Run the tool, reading streamed output within the tool's output limit
+    async fn run_tool(tool: &dyn ToolT, args: Value) -> Result<Value, ToolCallError> {
+        if !tool.supports_streaming() {
+            return tool.execute(args).await;
+        }
+        let stream = tool.execute_stream(args).await?;
+        let output = collect_tool_output(stream, &tool.output_limit()).await?;
+        Ok(Value::String(output))
+    }
+
     /// Create an error result for tool execution
     fn create_error_result(tool_name: &str, tool_args: &str, error: &str) -> ToolCallResult {
         ToolCallResult {
@@ -273,7 +290,10 @@ mod tests {
             "mock"
         }
         fn args_schema(&self) -> Value {
-            json!({"type": "object"})
+            json!({"type": "object", "properties": {"x": {"type": "integer", "maximum": 5}}})
+        }
+        fn validate_args(&self, args: &Value) -> Result<(), ToolCallError> {
+            crate::tool::validate_args(&self.args_schema(), args)
         }
     }
 
@@ -372,6 +392,78 @@ mod tests {
         assert!(result.result.to_string().contains("parse arguments"));
     }
 
+    #[tokio::test]
+    async fn test_process_single_tool_call_rejects_constraint_violation() {
+        let tools: Vec<Box<dyn ToolT>> = vec![Box::new(MockTool::new("tool_a"))];
+        let call = make_tool_call("1", "tool_a", r#"{"x":9}"#);
+        let ctx = ToolCallContext::new(
+            autoagents_protocol::SubmissionId::new_v4(),
+            autoagents_protocol::ActorID::new_v4(),
+        );
+        let result = ToolProcessor::process_single_tool_call(&tools, &call, ctx, &None).await;
+        assert!(!result.success);
+        assert_eq!(
+            result.result["error"],
+            "Invalid tool arguments: Invalid argument `x`: must be at most 5, got 9"
+        );
+    }
+
+    #[derive(Debug)]
+    struct LogTool;
+
+    impl ToolT for LogTool {
+        fn name(&self) -> &str {
+            "tail_logs"
+        }
+        fn description(&self) -> &str {
+            "stream log lines"
+        }
+        fn args_schema(&self) -> Value {
+            json!({"type": "object"})
+        }
+    }
+
+    #[async_trait]
+    impl ToolRuntime for LogTool {
+        async fn execute(&self, _args: Value) -> Result<Value, ToolCallError> {
+            unreachable!("streaming tools are read through execute_stream")
+        }
+
+        fn supports_streaming(&self) -> bool {
+            true
+        }
+
+        fn output_limit(&self) -> crate::tool::ToolOutputLimit {
+            crate::tool::ToolOutputLimit::new(7, 7).with_cutoff(Some(28))
+        }
+
+        async fn execute_stream(
+            &self,
+            _args: Value,
+        ) -> Result<crate::tool::ToolOutputStream, ToolCallError> {
+            let lines = (0..).map(|i| Ok(format!("line {i}\n")));
+            Ok(Box::pin(futures::stream::iter(lines)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_single_tool_call_truncates_streamed_output() {
+        let tools: Vec<Box<dyn ToolT>> = vec![Box::new(LogTool)];
+        let call = make_tool_call("1", "tail_logs", r#"{}"#);
+        let ctx = ToolCallContext::new(
+            autoagents_protocol::SubmissionId::new_v4(),
+            autoagents_protocol::ActorID::new_v4(),
+        );
+        let result = ToolProcessor::process_single_tool_call(&tools, &call, ctx, &None).await;
+        assert!(result.success);
+        assert_eq!(
+            result.result,
+            json!(
+                "line 0\n\n[... 14 bytes omitted ...]\nline 3\n\n[output cut off after 28 bytes]"
+            )
+        );
+    }
+
     #[test]
     fn test_create_result_tool_calls() {
         let calls = vec![make_tool_call("c1", "tool_a", r#"{"x":1}"#)];
@@ -1,14 +1,18 @@
-use crate::agent::Context;
 use crate::agent::executor::event_helper::EventHelper;
 use crate::agent::executor::memory_policy::{MemoryAdapter, MemoryPolicy};
 use crate::agent::executor::tool_processor::ToolProcessor;
 use crate::agent::hooks::AgentHooks;
 use crate::agent::task::Task;
+use crate::agent::{AgentDeriveT, Context};
 use crate::channel::{Sender, channel};
 use crate::tool::{ToolCallResult, ToolT, to_llm_tool};
 use crate::utils::stream_from_producer;
 use autoagents_llm::ToolCall;
-use autoagents_llm::chat::{ChatMessage, ChatRole, MessageType, StreamChunk, StreamResponse};
+use autoagents_llm::chat::Usage;
+use autoagents_llm::chat::{
+    ChatMessage, ChatRole, MessageType, SamplingOverrides, StreamChunk, StreamResponse, Tool,
+    ToolChoice,
+};
 use autoagents_llm::error::LLMError;
 use autoagents_protocol::{Event, SubmissionId};
 #[cfg(target_arch = "wasm32")]
@@ -19,6 +23,7 @@ use std::collections::HashSet;
 use std::pin::Pin;
 use std::sync::Arc;
 use thiserror::Error;
+use web_time::Instant;
 
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::sync::mpsc;
@@ -170,12 +175,19 @@ impl TurnEngine {
         let include_user_prompt =
             should_include_user_prompt(turn_state.memory(), turn_state.stored_user());
         let messages = self
-            .build_messages(context, task, turn_state.memory(), include_user_prompt)
+            .build_messages(
+                &system_prompt(hooks, context, task),
+                task,
+                turn_state.memory(),
+                include_user_prompt,
+            )
             .await;
         let store_user = should_store_user(turn_state);
 
         let tools = context.tools();
+        let started_at = Instant::now();
         let response = self.get_llm_response(context, &messages, tools).await?;
+        send_llm_call_completed(context, task.submission_id, started_at, response.usage()).await;
         let response_text = response.text().unwrap_or_default();
         let reasoning_content = response.thinking().unwrap_or_default();
         if store_user {
@@ -264,7 +276,12 @@ impl TurnEngine {
         let include_user_prompt =
             should_include_user_prompt(turn_state.memory(), turn_state.stored_user());
         let messages = self
-            .build_messages(&context, task, turn_state.memory(), include_user_prompt)
+            .build_messages(
+                &system_prompt(&hooks, &context, task),
+                task,
+                turn_state.memory(),
+                include_user_prompt,
+            )
             .await;
         let store_user = should_store_user(turn_state);
         if store_user {
@@ -353,15 +370,20 @@ impl TurnEngine {
         messages: &[ChatMessage],
         store_user: bool,
     ) -> Result<crate::agent::executor::TurnResult<TurnEngineOutput>, TurnEngineError> {
+        let started_at = Instant::now();
         let mut stream = self.get_structured_stream(context, messages).await?;
         if store_user {
             memory.store_user(task).await?;
         }
         let mut response_text = String::default();
         let mut reasoning_content = String::default();
+        let mut usage = None;
 
         while let Some(chunk_result) = stream.next().await {
             let chunk = chunk_result.map_err(TurnEngineError::LLMError)?;
+            if chunk.usage.is_some() {
+                usage = chunk.usage.clone();
+            }
             let delta = chunk.choices.first().map(|choice| &choice.delta);
             let content = delta
                 .and_then(|d| d.content.as_ref())
@@ -398,6 +420,7 @@ impl TurnEngine {
                 .await;
             }
         }
+        send_llm_call_completed(context, task.submission_id, started_at, usage).await;
 
         if !response_text.is_empty() {
             memory.store_assistant(&response_text).await?;
@@ -424,6 +447,7 @@ impl TurnEngine {
         messages: &[ChatMessage],
         store_user: bool,
     ) -> Result<crate::agent::executor::TurnResult<TurnEngineOutput>, TurnEngineError> {
+        let started_at = Instant::now();
         let mut stream = self.get_tool_stream(context, messages, tools).await?;
         if store_user {
             memory.store_user(task).await?;
@@ -432,6 +456,7 @@ impl TurnEngine {
         let mut reasoning_content = String::default();
         let mut tool_calls = Vec::default();
         let mut tool_call_ids: HashSet<String> = HashSet::default();
+        let mut usage = None;
 
         while let Some(chunk_result) = stream.next().await {
             let chunk = chunk_result.map_err(TurnEngineError::LLMError)?;
@@ -458,13 +483,16 @@ impl TurnEngine {
                     )
                     .await;
                 }
-                StreamChunk::Usage(_) => {}
+                StreamChunk::Usage(chunk_usage) => {
+                    usage = Some(chunk_usage);
+                }
                 _ => {}
             }
 
             let tx_event = context.tx().ok();
             EventHelper::send_stream_chunk(&tx_event, task.submission_id, chunk_clone).await;
         }
+        send_llm_call_completed(context, task.submission_id, started_at, usage).await;
 
         if tool_calls.is_empty() {
             if !response_text.is_empty() {
@@ -516,22 +544,34 @@ impl TurnEngine {
     ) -> Result<Box<dyn autoagents_llm::chat::ChatResponse>, TurnEngineError> {
         let llm = context.llm();
         let output_schema = context.config().output_schema.clone();
+        let sampling = sampling_overrides(context);
 
-        if matches!(self.config.tool_mode, ToolMode::Enabled) && !tools.is_empty() {
-            let cached = context.serialized_tools();
-            let tools_serialized = if let Some(cached) = cached {
-                cached
-            } else {
-                Arc::new(tools.iter().map(to_llm_tool).collect::<Vec<_>>())
-            };
-            llm.chat_with_tools(messages, Some(&tools_serialized), output_schema)
-                .await
-                .map_err(TurnEngineError::LLMError)
+        let tools_serialized = if matches!(self.config.tool_mode, ToolMode::Enabled) {
+            offered_tools(context, tools)
         } else {
-            llm.chat(messages, output_schema)
+            Arc::default()
+        };
+        let response = match (tools_serialized.is_empty(), &sampling) {
+            (false, Some(sampling)) => {
+                llm.chat_with_tools_and_sampling(
+                    messages,
+                    Some(&tools_serialized),
+                    output_schema,
+                    Some(sampling),
+                )
                 .await
-                .map_err(TurnEngineError::LLMError)
-        }
+            }
+            (false, None) => {
+                llm.chat_with_tools(messages, Some(&tools_serialized), output_schema)
+                    .await
+            }
+            (true, Some(sampling)) => {
+                llm.chat_and_sampling(messages, output_schema, Some(sampling))
+                    .await
+            }
+            (true, None) => llm.chat(messages, output_schema).await,
+        };
+        response.map_err(TurnEngineError::LLMError)
     }
 
     async fn get_structured_stream(
@@ -540,11 +580,16 @@ impl TurnEngine {
         messages: &[ChatMessage],
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamResponse, LLMError>> + Send>>, TurnEngineError>
     {
-        context
-            .llm()
-            .chat_stream_struct(messages, None, context.config().output_schema.clone())
-            .await
-            .map_err(TurnEngineError::LLMError)
+        let llm = context.llm();
+        let output_schema = context.config().output_schema.clone();
+        match sampling_overrides(context) {
+            Some(sampling) => {
+                llm.chat_stream_struct_and_sampling(messages, None, output_schema, Some(&sampling))
+                    .await
+            }
+            None => llm.chat_stream_struct(messages, None, output_schema).await,
+        }
+        .map_err(TurnEngineError::LLMError)
     }
 
     async fn get_tool_stream(
@@ -554,38 +599,35 @@ impl TurnEngine {
         tools: &[Box<dyn ToolT>],
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk, LLMError>> + Send>>, TurnEngineError>
     {
-        let cached = context.serialized_tools();
-        let tools_serialized = if let Some(cached) = cached {
-            cached
-        } else {
-            Arc::new(tools.iter().map(to_llm_tool).collect::<Vec<_>>())
-        };
-        context
-            .llm()
-            .chat_stream_with_tools(
-                messages,
-                if tools_serialized.is_empty() {
-                    None
-                } else {
-                    Some(&tools_serialized)
-                },
-                context.config().output_schema.clone(),
-            )
-            .await
-            .map_err(TurnEngineError::LLMError)
+        let llm = context.llm();
+        let tools_serialized = offered_tools(context, tools);
+        let tools_serialized = (!tools_serialized.is_empty()).then_some(&tools_serialized[..]);
+        let output_schema = context.config().output_schema.clone();
+        match sampling_overrides(context) {
+            Some(sampling) => {
+                llm.chat_stream_with_tools_and_sampling(
+                    messages,
+                    tools_serialized,
+                    output_schema,
+                    Some(&sampling),
+                )
+                .await
+            }
+            None => {
+                llm.chat_stream_with_tools(messages, tools_serialized, output_schema)
+                    .await
+            }
+        }
+        .map_err(TurnEngineError::LLMError)
     }
 
     async fn build_messages(
         &self,
-        context: &Context,
+        system_prompt: &str,
         task: &Task,
         memory: &MemoryAdapter,
         include_user_prompt: bool,
     ) -> Vec<ChatMessage> {
-        let system_prompt = task
-            .system_prompt
-            .as_deref()
-            .unwrap_or_else(|| &context.config().description);
         let mut messages = vec![ChatMessage {
             role: ChatRole::System,
             message_type: MessageType::Text,
@@ -603,6 +645,51 @@ impl TurnEngine {
     }
 }
 
+/// System prompt for a turn
+///
+/// A prompt set on the task wins over the agent's dynamic
+/// [`AgentDeriveT::system_prompt`], which wins over the agent description.
+pub(crate) fn system_prompt<A: AgentDeriveT + ?Sized>(
+    agent: &A,
+    context: &Context,
+    task: &Task,
+) -> String {
+    task.system_prompt
+        .clone()
+        .or_else(|| agent.system_prompt(context, task))
+        .unwrap_or_else(|| context.config().description.clone())
+}
+
+/// Per-call sampling derived from the agent's configured temperature
+pub(crate) fn sampling_overrides(context: &Context) -> Option<SamplingOverrides> {
+    context
+        .config()
+        .temperature
+        .map(SamplingOverrides::with_temperature)
+}
+
+/// Serialized tools offered to the LLM under the agent's `tool_choice`
+///
+/// `None` offers no tools and `Tool(name)` only the named tool; `Auto` and
+/// `Any` offer every tool. Forcing a tool call (`Any`) is left to the
+/// provider's own `tool_choice` setting, as there is no per-call override.
+fn offered_tools(context: &Context, tools: &[Box<dyn ToolT>]) -> Arc<Vec<Tool>> {
+    let serialized = context
+        .serialized_tools()
+        .unwrap_or_else(|| Arc::new(tools.iter().map(to_llm_tool).collect::<Vec<_>>()));
+    match &context.config().tool_choice {
+        Some(ToolChoice::None) => Arc::default(),
+        Some(ToolChoice::Tool(name)) => Arc::new(
+            serialized
+                .iter()
+                .filter(|tool| tool.function.name == *name)
+                .cloned()
+                .collect(),
+        ),
+        Some(ToolChoice::Auto | ToolChoice::Any) | None => serialized,
+    }
+}
+
 pub fn record_task_state(context: &Context, task: &Task) {
     let state = context.state();
     #[cfg(not(target_arch = "wasm32"))]
@@ -631,6 +718,24 @@ fn user_message(task: &Task) -> ChatMessage {
     }
 }
 
+async fn send_llm_call_completed(
+    context: &Context,
+    submission_id: SubmissionId,
+    started_at: Instant,
+    usage: Option<Usage>,
+) {
+    let tx_event = context.tx().ok();
+    EventHelper::send_llm_call_completed(
+        &tx_event,
+        submission_id,
+        context.config().id,
+        context.llm().model().to_string(),
+        started_at.elapsed(),
+        usage,
+    )
+    .await;
+}
+
 fn should_include_user_prompt(memory: &MemoryAdapter, stored_user: bool) -> bool {
     if !memory.is_enabled() {
         return true;
@@ -881,6 +986,7 @@ mod tests {
             name: "memory_agent".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let memory: Box<dyn MemoryProvider> = Box::new(SlidingWindowMemory::new(20));
         Context::new(llm, None)
@@ -894,6 +1000,7 @@ mod tests {
             name: "memory_agent".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let memory: Box<dyn MemoryProvider> = Box::new(FailingMemoryProvider);
         Context::new(llm, None)
@@ -1119,6 +1226,7 @@ mod tests {
             name: "test".to_string(),
             description: "test".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let llm = std::sync::Arc::new(crate::tests::MockLLMProvider {});
         let context = Context::new(llm, None).with_config(config);
@@ -1136,6 +1244,7 @@ mod tests {
             name: "test".to_string(),
             description: "default desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let llm = std::sync::Arc::new(crate::tests::MockLLMProvider {});
         let context = Context::new(llm, None).with_config(config);
@@ -1145,7 +1254,14 @@ mod tests {
         let mut task = Task::new("user input");
         task.system_prompt = Some("custom system".to_string());
 
-        let messages = engine.build_messages(&context, &task, &adapter, true).await;
+        let messages = engine
+            .build_messages(
+                &system_prompt(&crate::tests::MockAgentImpl::new("a", "b"), &context, &task),
+                &task,
+                &adapter,
+                true,
+            )
+            .await;
         // System + user
         assert_eq!(messages.len(), 2);
         assert_eq!(messages[0].content, "custom system");
@@ -1155,27 +1271,72 @@ mod tests {
 
     #[tokio::test]
     async fn test_build_messages_without_user_prompt() {
-        let config = AgentConfig {
-            id: ActorID::new_v4(),
-            name: "test".to_string(),
-            description: "desc".to_string(),
-            output_schema: None,
-        };
-        let llm = std::sync::Arc::new(crate::tests::MockLLMProvider {});
-        let context = Context::new(llm, None).with_config(config);
-
         let engine = TurnEngine::new(TurnEngineConfig::basic(1));
         let adapter = MemoryAdapter::new(None, MemoryPolicy::basic());
         let task = Task::new("user input");
 
-        let messages = engine
-            .build_messages(&context, &task, &adapter, false)
-            .await;
+        let messages = engine.build_messages("desc", &task, &adapter, false).await;
         // Only system prompt
         assert_eq!(messages.len(), 1);
         assert_eq!(messages[0].role, ChatRole::System);
     }
 
+    #[derive(Debug)]
+    struct DynamicPromptAgent(crate::tests::MockAgentImpl);
+
+    impl AgentDeriveT for DynamicPromptAgent {
+        type Output = <crate::tests::MockAgentImpl as AgentDeriveT>::Output;
+
+        fn description(&self) -> &str {
+            self.0.description()
+        }
+
+        fn output_schema(&self) -> Option<Value> {
+            None
+        }
+
+        fn name(&self) -> &str {
+            self.0.name()
+        }
+
+        fn tools(&self) -> Vec<Box<dyn ToolT>> {
+            vec![]
+        }
+
+        fn system_prompt(&self, context: &Context, task: &Task) -> Option<String> {
+            Some(format!(
+                "{} answering: {}",
+                context.config().name,
+                task.prompt
+            ))
+        }
+    }
+
+    #[test]
+    fn test_system_prompt_precedence() {
+        let config = AgentConfig {
+            id: ActorID::new_v4(),
+            name: "helper".to_string(),
+            description: "default desc".to_string(),
+            output_schema: None,
+            ..Default::default()
+        };
+        let llm = std::sync::Arc::new(crate::tests::MockLLMProvider {});
+        let context = Context::new(llm, None).with_config(config);
+        let plain = crate::tests::MockAgentImpl::new("helper", "default desc");
+        let dynamic = DynamicPromptAgent(plain.clone());
+        let mut task = Task::new("hi");
+
+        assert_eq!(system_prompt(&plain, &context, &task), "default desc");
+        assert_eq!(
+            system_prompt(&dynamic, &context, &task),
+            "helper answering: hi"
+        );
+
+        task.system_prompt = Some("custom system".to_string());
+        assert_eq!(system_prompt(&dynamic, &context, &task), "custom system");
+    }
+
     #[tokio::test]
     async fn test_run_turn_no_tools_single_turn() {
         use crate::tests::MockAgentImpl;
@@ -1184,6 +1345,7 @@ mod tests {
             name: "test".to_string(),
             description: "test desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let llm = std::sync::Arc::new(crate::tests::MockLLMProvider {});
         let context = Context::new(llm, None).with_config(config);
@@ -1234,6 +1396,7 @@ mod tests {
             name: "tool_agent".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let tool = LocalTool::new("tool_a", serde_json::json!({"ok": true}));
         let context = Context::new(llm, None)
@@ -1270,6 +1433,125 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_offered_tools_follow_tool_choice() {
+        let tools: Vec<Box<dyn ToolT>> = vec![
+            Box::new(LocalTool::new("tool_a", serde_json::json!({}))),
+            Box::new(LocalTool::new("tool_b", serde_json::json!({}))),
+        ];
+        let names = |choice: Option<ToolChoice>| {
+            let mut config = AgentConfig::new("agent".into(), "desc".into());
+            config.tool_choice = choice;
+            let context = Context::new(Arc::new(ConfigurableLLMProvider::default()), None)
+                .with_config(config);
+            offered_tools(&context, &tools)
+                .iter()
+                .map(|tool| tool.function.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(None), ["tool_a", "tool_b"]);
+        assert_eq!(names(Some(ToolChoice::Any)), ["tool_a", "tool_b"]);
+        assert_eq!(names(Some(ToolChoice::Tool("tool_b".into()))), ["tool_b"]);
+        assert!(names(Some(ToolChoice::None)).is_empty());
+    }
+
+    #[test]
+    fn test_sampling_overrides_from_temperature() {
+        let llm = Arc::new(ConfigurableLLMProvider::default());
+        let context = Context::new(llm.clone(), None);
+        assert!(sampling_overrides(&context).is_none());
+
+        let context = Context::new(llm, None)
+            .with_config(AgentConfig::new("agent".into(), "desc".into()).with_temperature(0.3));
+        assert_eq!(
+            sampling_overrides(&context),
+            Some(SamplingOverrides::with_temperature(0.3))
+        );
+    }
+
+    /// Records the sampling passed to the streaming tool path
+    #[derive(Default)]
+    struct SamplingRecorder(std::sync::Mutex<Option<Option<SamplingOverrides>>>);
+
+    #[async_trait]
+    impl autoagents_llm::chat::ChatProvider for SamplingRecorder {
+        async fn chat_with_tools(
+            &self,
+            _messages: &[ChatMessage],
+            _tools: Option<&[autoagents_llm::chat::Tool]>,
+            _json_schema: Option<autoagents_llm::chat::StructuredOutputFormat>,
+        ) -> Result<Box<dyn autoagents_llm::chat::ChatResponse>, LLMError> {
+            Err(LLMError::Generic("not used".to_string()))
+        }
+
+        async fn chat_stream_with_tools(
+            &self,
+            _messages: &[ChatMessage],
+            _tools: Option<&[autoagents_llm::chat::Tool]>,
+            _json_schema: Option<autoagents_llm::chat::StructuredOutputFormat>,
+        ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk, LLMError>> + Send>>, LLMError>
+        {
+            *self.0.lock().unwrap() = Some(None);
+            Ok(Box::pin(futures::stream::empty()))
+        }
+
+        async fn chat_stream_with_tools_and_sampling(
+            &self,
+            _messages: &[ChatMessage],
+            _tools: Option<&[autoagents_llm::chat::Tool]>,
+            _json_schema: Option<autoagents_llm::chat::StructuredOutputFormat>,
+            sampling: Option<&SamplingOverrides>,
+        ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk, LLMError>> + Send>>, LLMError>
+        {
+            *self.0.lock().unwrap() = Some(sampling.cloned());
+            Ok(Box::pin(futures::stream::empty()))
+        }
+    }
+
+    #[async_trait]
+    impl autoagents_llm::completion::CompletionProvider for SamplingRecorder {
+        async fn complete(
+            &self,
+            _req: &autoagents_llm::completion::CompletionRequest,
+            _json_schema: Option<autoagents_llm::chat::StructuredOutputFormat>,
+        ) -> Result<autoagents_llm::completion::CompletionResponse, LLMError> {
+            Err(LLMError::Generic("not used".to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl autoagents_llm::embedding::EmbeddingProvider for SamplingRecorder {
+        async fn embed(&self, _input: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[async_trait]
+    impl autoagents_llm::models::ModelsProvider for SamplingRecorder {}
+
+    impl LLMProvider for SamplingRecorder {}
+
+    #[tokio::test]
+    async fn test_tool_stream_applies_temperature() {
+        let llm = Arc::new(SamplingRecorder::default());
+        let tools: Vec<Box<dyn ToolT>> =
+            vec![Box::new(LocalTool::new("tool_a", serde_json::json!({})))];
+        let engine = TurnEngine::new(TurnEngineConfig::basic(1));
+
+        let context = Context::new(llm.clone(), None);
+        let _ = engine.get_tool_stream(&context, &[], &tools).await.unwrap();
+        assert_eq!(llm.0.lock().unwrap().take(), Some(None));
+
+        let context = Context::new(llm.clone(), None)
+            .with_config(AgentConfig::new("agent".into(), "desc".into()).with_temperature(0.3));
+        let _ = engine.get_tool_stream(&context, &[], &tools).await.unwrap();
+        assert_eq!(
+            llm.0.lock().unwrap().take(),
+            Some(Some(SamplingOverrides::with_temperature(0.3)))
+        );
+    }
+
     #[tokio::test]
     async fn test_run_turn_tool_mode_disabled_ignores_tool_calls() {
         use crate::tests::MockAgentImpl;
@@ -1297,6 +1579,7 @@ mod tests {
             name: "tool_agent".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let context = Context::new(llm, None).with_config(config);
 
@@ -1343,6 +1626,7 @@ mod tests {
             name: "reasoning_agent".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let context = Context::new(llm, None).with_config(config);
         let engine = TurnEngine::new(TurnEngineConfig::basic(1));
@@ -1398,6 +1682,7 @@ mod tests {
             name: "stream_agent".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let context = Arc::new(Context::new(llm, None).with_config(config));
         let engine = TurnEngine::new(TurnEngineConfig {
@@ -1452,6 +1737,7 @@ mod tests {
             name: "stream_reasoning_agent".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let context = Arc::new(Context::new(llm, None).with_config(config));
         let engine = TurnEngine::new(TurnEngineConfig::basic(1));
@@ -1523,6 +1809,7 @@ mod tests {
             name: "tool_stream_agent".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let tool = LocalTool::new("tool_a", serde_json::json!({"ok": true}));
         let context = Arc::new(
@@ -0,0 +1,389 @@
+//! Route tasks between agent variants and compare how they do
+//!
+//! An [`Experiment`] sends a fixed fraction of tasks to each variant and the
+//! rest to the control. Variants can differ in anything: the system prompt,
+//! the model, or the executor, as long as they produce the same output type.
+//! Assignment is sticky: tasks with the same user id, or else the same
+//! session id, always go to the same variant. Each task is tagged with the
+//! experiment and variant in its trace attributes, which are reported with
+//! `Event::TaskStarted` and on the task's telemetry span, and the experiment
+//! keeps per-variant run, failure, latency and score totals.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::agent::error::RunnableAgentError;
+use crate::agent::task::Task;
+use crate::agent::{AgentDeriveT, AgentExecutor, AgentHooks, BaseAgent, DirectAgent};
+use crate::prompt::RenderedPrompt;
+
+/// Trace attribute holding the experiment name
+pub const EXPERIMENT_ATTRIBUTE: &str = "experiment.name";
+/// Trace attribute holding the variant a task was routed to
+pub const VARIANT_ATTRIBUTE: &str = "experiment.variant";
+/// Name of the variant built by [`Variant::control`]
+pub const CONTROL_VARIANT: &str = "control";
+
+/// Something an experiment can route a task to
+#[async_trait]
+pub trait ExperimentArm<O>: Send + Sync {
+    async fn run_task(&self, task: Task) -> Result<O, RunnableAgentError>;
+}
+
+#[async_trait]
+impl<T> ExperimentArm<<T as AgentDeriveT>::Output> for BaseAgent<T, DirectAgent>
+where
+    T: AgentDeriveT + AgentExecutor + AgentHooks,
+    Value: From<<T as AgentExecutor>::Output>,
+    <T as AgentDeriveT>::Output: From<<T as AgentExecutor>::Output>,
+    <T as AgentExecutor>::Output: Clone,
+    <T as AgentExecutor>::Error: Into<RunnableAgentError>,
+{
+    async fn run_task(
+        &self,
+        task: Task,
+    ) -> Result<<T as AgentDeriveT>::Output, RunnableAgentError> {
+        self.run(task).await
+    }
+}
+
+/// One configuration under test
+pub struct Variant<O> {
+    name: String,
+    fraction: f64,
+    arm: Arc<dyn ExperimentArm<O>>,
+    prompt: Option<RenderedPrompt>,
+}
+
+impl<O> Variant<O> {
+    pub fn new(name: impl Into<String>, arm: impl ExperimentArm<O> + 'static) -> Self {
+        Self {
+            name: name.into(),
+            fraction: 0.0,
+            arm: Arc::new(arm),
+            prompt: None,
+        }
+    }
+
+    /// The variant that receives every task not routed elsewhere
+    pub fn control(arm: impl ExperimentArm<O> + 'static) -> Self {
+        Self::new(CONTROL_VARIANT, arm)
+    }
+
+    /// Share of tasks routed to this variant, in `0.0..=1.0`
+    ///
+    /// Ignored for the control, which gets what the other variants leave.
+    pub fn fraction(mut self, fraction: f64) -> Self {
+        self.fraction = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Run this variant's tasks with `prompt` as the system prompt
+    pub fn with_prompt(mut self, prompt: RenderedPrompt) -> Self {
+        self.prompt = Some(prompt);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Outcome totals for one variant
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VariantMetrics {
+    pub runs: u64,
+    pub failures: u64,
+    pub total_duration: Duration,
+    /// Number of scores recorded with [`Experiment::record_score`]
+    pub scores: u64,
+    pub score_sum: f64,
+}
+
+impl VariantMetrics {
+    pub fn success_rate(&self) -> Option<f64> {
+        (self.runs > 0).then(|| (self.runs - self.failures) as f64 / self.runs as f64)
+    }
+
+    pub fn mean_duration(&self) -> Option<Duration> {
+        (self.runs > 0).then(|| self.total_duration / self.runs as u32)
+    }
+
+    pub fn mean_score(&self) -> Option<f64> {
+        (self.scores > 0).then(|| self.score_sum / self.scores as f64)
+    }
+}
+
+/// The result of a task run through an experiment
+#[derive(Debug)]
+pub struct ExperimentRun<O> {
+    /// Name of the variant that ran the task
+    pub variant: String,
+    pub duration: Duration,
+    pub result: Result<O, RunnableAgentError>,
+}
+
+/// Splits tasks between a control and variants, recording how each does
+pub struct Experiment<O> {
+    name: String,
+    control: Variant<O>,
+    variants: Vec<Variant<O>>,
+    metrics: Mutex<BTreeMap<String, VariantMetrics>>,
+}
+
+impl<O> Experiment<O> {
+    pub fn new(name: impl Into<String>, control: Variant<O>) -> Self {
+        let metrics = BTreeMap::from([(control.name.clone(), VariantMetrics::default())]);
+        Self {
+            name: name.into(),
+            control,
+            variants: Vec::new(),
+            metrics: Mutex::new(metrics),
+        }
+    }
+
+    /// Add a variant
+    ///
+    /// Fractions are taken in the order variants are added; once they add
+    /// up to one, later variants get no tasks.
+    pub fn with_variant(mut self, variant: Variant<O>) -> Self {
+        if let Ok(mut metrics) = self.metrics.lock() {
+            metrics.entry(variant.name.clone()).or_default();
+        }
+        self.variants.push(variant);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The variant `task` is routed to
+    pub fn assign(&self, task: &Task) -> &Variant<O> {
+        let trace = task.trace.as_ref();
+        let key = trace
+            .and_then(|trace| trace.user_id.as_deref())
+            .or_else(|| trace.and_then(|trace| trace.session_id.as_deref()))
+            .map(str::to_string)
+            .unwrap_or_else(|| task.submission_id.to_string());
+        let point = bucket(&self.name, &key);
+
+        let mut upper = 0.0;
+        for variant in &self.variants {
+            upper += variant.fraction;
+            if point < upper {
+                return variant;
+            }
+        }
+        &self.control
+    }
+
+    /// Run `task` on its variant and record the outcome
+    pub async fn run(&self, task: Task) -> ExperimentRun<O> {
+        let variant = self.assign(&task);
+        let mut task = task
+            .with_trace_attribute(EXPERIMENT_ATTRIBUTE, self.name.clone())
+            .with_trace_attribute(VARIANT_ATTRIBUTE, variant.name.clone());
+        if let Some(prompt) = &variant.prompt {
+            task = prompt.apply_to(task);
+        }
+
+        let started = Instant::now();
+        let result = variant.arm.run_task(task).await;
+        let duration = started.elapsed();
+
+        if let Ok(mut metrics) = self.metrics.lock() {
+            let entry = metrics.entry(variant.name.clone()).or_default();
+            entry.runs += 1;
+            entry.failures += result.is_err() as u64;
+            entry.total_duration += duration;
+        }
+        ExperimentRun {
+            variant: variant.name.clone(),
+            duration,
+            result,
+        }
+    }
+
+    /// Record an outcome score for a run of `variant`, e.g. a user rating
+    pub fn record_score(&self, variant: &str, score: f64) {
+        if let Ok(mut metrics) = self.metrics.lock()
+            && let Some(entry) = metrics.get_mut(variant)
+        {
+            entry.scores += 1;
+            entry.score_sum += score;
+        }
+    }
+
+    /// Totals for every variant, by name
+    pub fn metrics(&self) -> BTreeMap<String, VariantMetrics> {
+        self.metrics
+            .lock()
+            .map(|metrics| metrics.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Position of `key` in `0.0..1.0`, stable across processes
+fn bucket(experiment: &str, key: &str) -> f64 {
+    let hash = experiment
+        .bytes()
+        .chain([b':'])
+        .chain(key.bytes())
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentBuilder;
+    use crate::agent::prebuilt::executor::BasicAgent;
+    use crate::prompt::PromptRegistry;
+    use crate::testing::ScriptedLLM;
+    use futures::StreamExt;
+    use serde_json::json;
+
+    /// Answers with its own name
+    struct NamedArm(&'static str);
+
+    #[async_trait]
+    impl ExperimentArm<String> for NamedArm {
+        async fn run_task(&self, task: Task) -> Result<String, RunnableAgentError> {
+            if task.prompt == "fail" {
+                return Err(RunnableAgentError::TaskError("boom".to_string()));
+            }
+            Ok(self.0.to_string())
+        }
+    }
+
+    fn experiment() -> Experiment<String> {
+        Experiment::new("prompt-v2", Variant::control(NamedArm("a")))
+            .with_variant(Variant::new("b", NamedArm("b")).fraction(0.25))
+    }
+
+    #[tokio::test]
+    async fn test_routes_the_configured_fraction() {
+        let experiment = experiment();
+        let mut to_b = 0;
+        for _ in 0..2000 {
+            let run = experiment.run(Task::new("hi")).await;
+            assert_eq!(
+                run.result.as_ref().unwrap(),
+                &run.variant.replace("control", "a")
+            );
+            to_b += (run.variant == "b") as usize;
+        }
+        assert!((400..600).contains(&to_b), "{to_b} of 2000 went to b");
+
+        let metrics = experiment.metrics();
+        assert_eq!(metrics["control"].runs + metrics["b"].runs, 2000);
+        assert_eq!(metrics["b"].success_rate(), Some(1.0));
+    }
+
+    #[test]
+    fn test_assignment_is_sticky_per_user() {
+        let experiment = experiment();
+        let first = experiment
+            .assign(&Task::new("one").with_user_id("user-42"))
+            .name()
+            .to_string();
+        for prompt in ["two", "three", "four"] {
+            let task = Task::new(prompt).with_user_id("user-42");
+            assert_eq!(experiment.assign(&task).name(), first);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_records_failures_and_scores() {
+        let experiment = Experiment::new("all-b", Variant::control(NamedArm("a")))
+            .with_variant(Variant::new("b", NamedArm("b")).fraction(1.0));
+        experiment.run(Task::new("ok")).await;
+        let failed = experiment.run(Task::new("fail")).await;
+        assert_eq!(failed.variant, "b");
+        assert!(failed.result.is_err());
+        experiment.record_score("b", 4.0);
+        experiment.record_score("b", 2.0);
+        experiment.record_score("missing", 1.0);
+
+        let metrics = experiment.metrics();
+        assert_eq!(metrics["b"].runs, 2);
+        assert_eq!(metrics["b"].failures, 1);
+        assert_eq!(metrics["b"].success_rate(), Some(0.5));
+        assert_eq!(metrics["b"].mean_score(), Some(3.0));
+        assert_eq!(metrics["control"], VariantMetrics::default());
+        assert!(!metrics.contains_key("missing"));
+    }
+
+    #[derive(Debug)]
+    struct Support;
+
+    impl AgentDeriveT for Support {
+        type Output = String;
+
+        fn description(&self) -> &str {
+            "Support agent"
+        }
+
+        fn output_schema(&self) -> Option<Value> {
+            None
+        }
+
+        fn name(&self) -> &str {
+            "support"
+        }
+
+        fn tools(&self) -> Vec<Box<dyn crate::tool::ToolT>> {
+            Vec::new()
+        }
+    }
+
+    impl AgentHooks for Support {}
+
+    #[tokio::test]
+    async fn test_variant_prompt_and_tags_reach_the_agent() {
+        let prompts = PromptRegistry::new().with_prompt("support", "v2", "Be brief.");
+        let llm = Arc::new(ScriptedLLM::new().reply("Restart it."));
+        let mut handle = AgentBuilder::<_, DirectAgent>::new(BasicAgent::new(Support))
+            .llm(llm.clone())
+            .build()
+            .await
+            .unwrap();
+        let mut events = handle.subscribe_events();
+
+        let experiment = Experiment::new("support-prompt", Variant::control(NamedArm("a")))
+            .with_variant(
+                Variant::new("v2", handle.agent)
+                    .fraction(1.0)
+                    .with_prompt(prompts.render("support", &json!({})).unwrap()),
+            );
+        let run = experiment.run(Task::new("It hangs")).await;
+        assert_eq!(run.result.unwrap(), "Restart it.");
+
+        let system = llm.requests()[0].messages[0].content.clone();
+        assert_eq!(system, "Be brief.");
+
+        let trace = loop {
+            match events.next().await.unwrap() {
+                autoagents_protocol::Event::TaskStarted { trace, .. } => break trace.unwrap(),
+                _ => continue,
+            }
+        };
+        assert_eq!(
+            trace.attributes[EXPERIMENT_ATTRIBUTE],
+            json!("support-prompt")
+        );
+        assert_eq!(trace.attributes[VARIANT_ATTRIBUTE], json!("v2"));
+        assert_eq!(
+            trace.attributes[crate::prompt::PROMPT_VERSION_ATTRIBUTE],
+            json!("v2")
+        );
+    }
+}
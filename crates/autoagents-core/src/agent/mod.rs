@@ -7,14 +7,22 @@ mod protocol;
 pub mod task;
 
 pub mod prebuilt;
+pub mod replay;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod bridge;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod experiment;
 
 // Exports for all platforms
+pub use autoagents_llm::chat::ToolChoice;
 pub use config::AgentConfig;
 pub use error::AgentResultError;
 pub use output::AgentOutputT;
 pub use protocol::AgentProtocol;
 mod base;
 mod builder;
+mod compaction;
 mod context;
 mod executor;
 // mod runnable;
@@ -29,6 +37,7 @@ pub use actor::ActorAgent;
 pub use actor::ActorAgentHandle;
 pub use base::{AgentDeriveT, BaseAgent};
 pub use builder::AgentBuilder;
+pub use compaction::{CompactionConfig, CompactionReport};
 pub use context::{Context, ContextError};
 pub use direct::{DirectAgent, DirectAgentHandle};
 pub use executor::{
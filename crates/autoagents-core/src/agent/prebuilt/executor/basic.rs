@@ -10,6 +10,7 @@ use crate::tool::{ToolCallResult, ToolT};
 use crate::utils::stream_from_producer;
 use async_trait::async_trait;
 use autoagents_llm::ToolCall;
+use autoagents_llm::chat::ToolChoice;
 use autoagents_llm::error::LLMError;
 #[cfg(target_arch = "wasm32")]
 use futures::SinkExt;
@@ -134,6 +135,26 @@ impl<T: AgentDeriveT> AgentDeriveT for BasicAgent<T> {
     fn tools(&self) -> Vec<Box<dyn ToolT>> {
         self.inner.tools()
     }
+
+    fn max_turns(&self) -> Option<usize> {
+        self.inner.max_turns()
+    }
+
+    fn temperature(&self) -> Option<f32> {
+        self.inner.temperature()
+    }
+
+    fn tool_choice(&self) -> Option<ToolChoice> {
+        self.inner.tool_choice()
+    }
+
+    fn output_strict(&self) -> Option<bool> {
+        self.inner.output_strict()
+    }
+
+    fn system_prompt(&self, context: &Context, task: &Task) -> Option<String> {
+        self.inner.system_prompt(context, task)
+    }
 }
 
 #[async_trait]
@@ -205,6 +226,7 @@ impl<T: AgentDeriveT + AgentHooks> AgentExecutor for BasicAgent<T> {
             context.config().id,
             context.config().name.clone(),
             task.prompt.clone(),
+            task.trace.clone(),
         )
         .await;
 
@@ -243,6 +265,7 @@ impl<T: AgentDeriveT + AgentHooks> AgentExecutor for BasicAgent<T> {
             context.config().id,
             context.config().name.clone(),
             task.prompt.clone(),
+            task.trace.clone(),
         )
         .await;
 
@@ -398,6 +421,7 @@ mod tests {
             name: "test_agent".to_string(),
             description: "Test agent description".to_string(),
             output_schema: None,
+            ..Default::default()
         };
 
         let context = Context::new(llm, None).with_config(config);
@@ -580,6 +604,7 @@ mod tests {
             name: "stream_agent".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let context = Arc::new(Context::new(llm, None).with_config(config));
         let task = Task::new("Test task");
@@ -638,6 +663,7 @@ mod tests {
             name: "stream_agent_reasoning".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let context = Arc::new(Context::new(llm, None).with_config(config));
         let task = Task::new("Test task");
@@ -1,7 +1,7 @@
 use crate::agent::executor::event_helper::EventHelper;
 use crate::agent::executor::memory_policy::{MemoryAdapter, MemoryPolicy};
 use crate::agent::executor::tool_processor::ToolProcessor;
-use crate::agent::executor::turn_engine::record_task_state;
+use crate::agent::executor::turn_engine::{record_task_state, sampling_overrides, system_prompt};
 use crate::agent::task::Task;
 use crate::agent::{AgentDeriveT, AgentExecutor, AgentHooks, Context, ExecutorConfig, HookOutcome};
 use crate::channel::channel;
@@ -9,7 +9,9 @@ use crate::tool::{ToolCallResult, ToolT};
 use crate::utils::stream_from_producer;
 use async_trait::async_trait;
 use autoagents_llm::ToolCall;
-use autoagents_llm::chat::{ChatMessage, ChatRole, FunctionTool, MessageType, StreamChunk, Tool};
+use autoagents_llm::chat::{
+    ChatMessage, ChatRole, FunctionTool, MessageType, StreamChunk, Tool, ToolChoice,
+};
 use autoagents_llm::error::LLMError;
 use autoagents_protocol::{Event, SubmissionId};
 #[cfg(target_arch = "wasm32")]
@@ -190,10 +192,12 @@ impl<T: AgentDeriveT> Clone for CodeActAgent<T> {
 }
 
 impl<T: AgentDeriveT> CodeActAgent<T> {
+    /// Uses the agent's declared `max_turns`, or 10 turns if it declares none
     pub fn new(inner: T) -> Self {
+        let max_turns = inner.max_turns().unwrap_or(10).max(1);
         Self {
             inner: Arc::new(inner),
-            max_turns: 10,
+            max_turns,
             sandbox_limits: CodeActSandboxLimits::default(),
         }
     }
@@ -239,6 +243,26 @@ impl<T: AgentDeriveT> AgentDeriveT for CodeActAgent<T> {
     fn tools(&self) -> Vec<Box<dyn ToolT>> {
         self.inner.tools()
     }
+
+    fn max_turns(&self) -> Option<usize> {
+        self.inner.max_turns()
+    }
+
+    fn temperature(&self) -> Option<f32> {
+        self.inner.temperature()
+    }
+
+    fn tool_choice(&self) -> Option<ToolChoice> {
+        self.inner.tool_choice()
+    }
+
+    fn output_strict(&self) -> Option<bool> {
+        self.inner.output_strict()
+    }
+
+    fn system_prompt(&self, context: &Context, task: &Task) -> Option<String> {
+        self.inner.system_prompt(context, task)
+    }
 }
 
 #[async_trait]
@@ -432,6 +456,7 @@ impl CodeActEngine {
             context.config().id,
             context.config().name.clone(),
             task.prompt.clone(),
+            task.trace.clone(),
         )
         .await;
 
@@ -462,7 +487,7 @@ impl CodeActEngine {
             hooks.on_turn_start(turn_index, &context).await;
 
             let messages = build_messages(
-                &context,
+                &system_prompt(&hooks, &context, task),
                 task,
                 &memory,
                 stored_user,
@@ -470,14 +495,26 @@ impl CodeActEngine {
             )
             .await;
             let should_store_user = should_store_user(&memory, stored_user);
-            let response = context
-                .llm()
-                .chat_with_tools(
-                    &messages,
-                    Some(tools.as_slice()),
-                    context.config().output_schema.clone(),
-                )
-                .await?;
+            let output_schema = context.config().output_schema.clone();
+            let response = match sampling_overrides(&context) {
+                Some(sampling) => {
+                    context
+                        .llm()
+                        .chat_with_tools_and_sampling(
+                            &messages,
+                            Some(tools.as_slice()),
+                            output_schema,
+                            Some(&sampling),
+                        )
+                        .await?
+                }
+                None => {
+                    context
+                        .llm()
+                        .chat_with_tools(&messages, Some(tools.as_slice()), output_schema)
+                        .await?
+                }
+            };
             let response_text = response.text().unwrap_or_default();
             if should_store_user {
                 memory.store_user(task).await?;
@@ -560,6 +597,7 @@ impl CodeActEngine {
             context.config().id,
             context.config().name.clone(),
             task.prompt.clone(),
+            task.trace.clone(),
         )
         .await;
 
@@ -598,7 +636,7 @@ impl CodeActEngine {
                 hooks.on_turn_start(turn_index, &context_clone).await;
 
                 let messages = build_messages(
-                    &context_clone,
+                    &system_prompt(&hooks, &context_clone, &task),
                     &task,
                     &memory,
                     stored_user,
@@ -836,16 +874,12 @@ struct CodeActToolBinding {
 }
 
 async fn build_messages(
-    context: &Context,
+    system_prompt: &str,
     task: &Task,
     memory: &MemoryAdapter,
     stored_user: bool,
     tool_bindings: &[CodeActToolBinding],
 ) -> Vec<ChatMessage> {
-    let system_prompt = task
-        .system_prompt
-        .as_deref()
-        .unwrap_or_else(|| &context.config().description);
     let mut messages = vec![ChatMessage {
         role: ChatRole::System,
         message_type: MessageType::Text,
@@ -2246,6 +2280,7 @@ mod tests {
             name: "codeact_test".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         Arc::new(
             Context::new(llm, None)
@@ -2265,6 +2300,7 @@ mod tests {
             name: "codeact_test".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
 
         let mut context = Context::new(llm, None)
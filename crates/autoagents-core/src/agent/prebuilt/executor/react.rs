@@ -10,6 +10,7 @@ use crate::tool::{ToolCallResult, ToolT};
 use crate::utils::stream_from_producer;
 use async_trait::async_trait;
 use autoagents_llm::ToolCall;
+use autoagents_llm::chat::ToolChoice;
 use autoagents_llm::error::LLMError;
 #[cfg(target_arch = "wasm32")]
 use futures::SinkExt;
@@ -158,10 +159,12 @@ impl<T: AgentDeriveT> Clone for ReActAgent<T> {
 }
 
 impl<T: AgentDeriveT> ReActAgent<T> {
+    /// Uses the agent's declared `max_turns`, or 10 turns if it declares none
     pub fn new(inner: T) -> Self {
+        let max_turns = inner.max_turns().unwrap_or(10).max(1);
         Self {
             inner: Arc::new(inner),
-            max_turns: 10,
+            max_turns,
         }
     }
 
@@ -201,6 +204,26 @@ impl<T: AgentDeriveT> AgentDeriveT for ReActAgent<T> {
     fn tools(&self) -> Vec<Box<dyn ToolT>> {
         self.inner.tools()
     }
+
+    fn max_turns(&self) -> Option<usize> {
+        self.inner.max_turns()
+    }
+
+    fn temperature(&self) -> Option<f32> {
+        self.inner.temperature()
+    }
+
+    fn tool_choice(&self) -> Option<ToolChoice> {
+        self.inner.tool_choice()
+    }
+
+    fn output_strict(&self) -> Option<bool> {
+        self.inner.output_strict()
+    }
+
+    fn system_prompt(&self, context: &Context, task: &Task) -> Option<String> {
+        self.inner.system_prompt(context, task)
+    }
 }
 
 #[async_trait]
@@ -275,6 +298,7 @@ impl<T: AgentDeriveT + AgentHooks> AgentExecutor for ReActAgent<T> {
             context.config().id,
             context.config().name.clone(),
             task.prompt.clone(),
+            task.trace.clone(),
         )
         .await;
 
@@ -337,6 +361,7 @@ impl<T: AgentDeriveT + AgentHooks> AgentExecutor for ReActAgent<T> {
             context.config().id,
             context.config().name.clone(),
             task.prompt.clone(),
+            task.trace.clone(),
         )
         .await;
 
@@ -676,6 +701,7 @@ mod tests {
             name: "exec_test".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let context = Arc::new(Context::new(llm, None).with_config(config));
         let task = crate::agent::task::Task::new("test");
@@ -718,6 +744,7 @@ mod tests {
             name: "exec_tool".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
 
         let tool = LocalTool::new("tool_a", serde_json::json!({"ok": true}));
@@ -758,6 +785,7 @@ mod tests {
             name: "stream_test".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let context = Arc::new(Context::new(llm, None).with_config(config));
         let task = crate::agent::task::Task::new("test");
@@ -801,6 +829,7 @@ mod tests {
             name: "stream_reasoning_test".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let context = Arc::new(Context::new(llm, None).with_config(config));
         let task = crate::agent::task::Task::new("test");
@@ -841,6 +870,7 @@ mod tests {
             name: "stream_reasoning_only_test".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let context = Arc::new(Context::new(llm, None).with_config(config));
         let task = crate::agent::task::Task::new("test");
@@ -890,6 +920,7 @@ mod tests {
             name: "stream_tool".to_string(),
             description: "desc".to_string(),
             output_schema: None,
+            ..Default::default()
         };
         let tool = LocalTool::new("tool_a", serde_json::json!({"ok": true}));
         let context = Arc::new(
@@ -0,0 +1,485 @@
+//! Step through a recorded run and resume it from any turn
+//!
+//! A run is recorded by collecting the protocol [`Event`]s of an agent (for
+//! example from `DirectAgentHandle::subscribe_events`); [`to_json_lines`] and
+//! [`from_json_lines`] store them. [`RunReplay`] groups the events of one task
+//! into turns, rebuilds the memory the executor had at the start of each turn,
+//! and can hand that state, with an edited prompt or patched tool results,
+//! back to an agent to continue with a live LLM.
+
+use crate::agent::error::RunnableAgentError;
+use crate::agent::executor::tool_processor::ToolProcessor;
+use crate::agent::task::Task;
+use crate::agent::{AgentDeriveT, AgentExecutor, AgentHooks, BaseAgent, DirectAgent};
+use crate::tool::ToolCallResult;
+use autoagents_llm::chat::{ChatMessage, ChatRole, MessageType};
+use autoagents_llm::{FunctionCall, ToolCall};
+use autoagents_protocol::{Event, SubmissionId};
+use serde_json::Value;
+
+/// Serialize events as one JSON object per line
+///
+/// Runtime-only events that cannot be serialized are skipped.
+pub fn to_json_lines(events: &[Event]) -> String {
+    events
+        .iter()
+        .filter_map(|event| serde_json::to_string(event).ok())
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// Parse events written by [`to_json_lines`]; blank lines are ignored
+pub fn from_json_lines(input: &str) -> Result<Vec<Event>, serde_json::Error> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str)
+        .collect()
+}
+
+/// A tool call made during a recorded turn
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayToolCall {
+    pub id: String,
+    pub name: String,
+    /// Raw JSON arguments as sent by the LLM
+    pub arguments: String,
+    /// The tool's result, or its error message
+    pub result: Result<Value, String>,
+}
+
+/// One executor turn of a recorded run
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayTurn {
+    pub number: usize,
+    pub tool_calls: Vec<ReplayToolCall>,
+    /// Whether the executor finished the task on this turn
+    pub final_turn: bool,
+}
+
+/// A recorded task run, split into turns
+#[derive(Debug, Clone)]
+pub struct RunReplay {
+    submission_id: SubmissionId,
+    prompt: String,
+    turns: Vec<ReplayTurn>,
+    outcome: Option<Result<String, String>>,
+}
+
+impl RunReplay {
+    /// Replay the first task started in `events`
+    pub fn from_events(events: &[Event]) -> Option<Self> {
+        let submission_id = events.iter().find_map(|event| match event {
+            Event::TaskStarted { sub_id, .. } => Some(*sub_id),
+            _ => None,
+        })?;
+        Self::for_submission(events, submission_id)
+    }
+
+    /// Replay the task with the given submission id
+    pub fn for_submission(events: &[Event], submission_id: SubmissionId) -> Option<Self> {
+        let mut prompt = None;
+        let mut turns: Vec<ReplayTurn> = Vec::new();
+        let mut outcome = None;
+
+        for event in events {
+            match event {
+                Event::TaskStarted {
+                    sub_id,
+                    task_description,
+                    ..
+                } if *sub_id == submission_id => prompt = Some(task_description.clone()),
+                Event::TurnStarted {
+                    sub_id,
+                    turn_number,
+                    ..
+                } if *sub_id == submission_id => turns.push(ReplayTurn {
+                    number: *turn_number,
+                    tool_calls: Vec::new(),
+                    final_turn: false,
+                }),
+                Event::TurnCompleted {
+                    sub_id, final_turn, ..
+                } if *sub_id == submission_id => {
+                    if let Some(turn) = turns.last_mut() {
+                        turn.final_turn = *final_turn;
+                    }
+                }
+                Event::ToolCallRequested {
+                    sub_id,
+                    id,
+                    tool_name,
+                    arguments,
+                    ..
+                } if *sub_id == submission_id => {
+                    if let Some(turn) = turns.last_mut() {
+                        turn.tool_calls.push(ReplayToolCall {
+                            id: id.clone(),
+                            name: tool_name.clone(),
+                            arguments: arguments.clone(),
+                            result: Err("tool did not finish".to_string()),
+                        });
+                    }
+                }
+                Event::ToolCallCompleted {
+                    sub_id, id, result, ..
+                } if *sub_id == submission_id => {
+                    if let Some(call) = find_call(&mut turns, id) {
+                        call.result = Ok(result.clone());
+                    }
+                }
+                Event::ToolCallFailed {
+                    sub_id, id, error, ..
+                } if *sub_id == submission_id => {
+                    if let Some(call) = find_call(&mut turns, id) {
+                        call.result = Err(error.clone());
+                    }
+                }
+                Event::TaskComplete { sub_id, result, .. } if *sub_id == submission_id => {
+                    outcome = Some(Ok(result.clone()));
+                }
+                Event::TaskError { sub_id, error, .. } if *sub_id == submission_id => {
+                    outcome = Some(Err(error.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            submission_id,
+            prompt: prompt?,
+            turns,
+            outcome,
+        })
+    }
+
+    pub fn submission_id(&self) -> SubmissionId {
+        self.submission_id
+    }
+
+    /// The user prompt of the recorded task
+    pub fn prompt(&self) -> &str {
+        &self.prompt
+    }
+
+    pub fn turns(&self) -> &[ReplayTurn] {
+        &self.turns
+    }
+
+    /// The final result, the error message, or `None` if the run never finished
+    pub fn outcome(&self) -> Option<Result<&str, &str>> {
+        self.outcome
+            .as_ref()
+            .map(|outcome| outcome.as_deref().map_err(String::as_str))
+    }
+
+    /// State at the start of turn `turn`, i.e. after all earlier turns completed
+    ///
+    /// Indexes past the last turn give the state after the whole run.
+    pub fn state_at(&self, turn: usize) -> ReplayState {
+        ReplayState {
+            prompt: self.prompt.clone(),
+            turns: self.turns.iter().take(turn).cloned().collect(),
+        }
+    }
+}
+
+fn find_call<'a>(turns: &'a mut [ReplayTurn], id: &str) -> Option<&'a mut ReplayToolCall> {
+    turns
+        .iter_mut()
+        .rev()
+        .flat_map(|turn| turn.tool_calls.iter_mut())
+        .find(|call| call.id == id)
+}
+
+/// The conversation at a point of a recorded run, ready to be edited and resumed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayState {
+    pub prompt: String,
+    /// Completed turns before this point
+    pub turns: Vec<ReplayTurn>,
+}
+
+impl ReplayState {
+    /// Replace the user prompt
+    pub fn with_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Replace the result of an earlier tool call; returns `false` if no call has that id
+    pub fn patch_tool_result(&mut self, call_id: &str, result: Value) -> bool {
+        match find_call(&mut self.turns, call_id) {
+            Some(call) => {
+                call.result = Ok(result);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Memory contents an executor would hold at this point
+    ///
+    /// The user prompt followed by an assistant tool-use message and a tool
+    /// result message per turn that called tools. Intermediate assistant text
+    /// is not part of the event stream and is left empty.
+    pub fn messages(&self) -> Vec<ChatMessage> {
+        let mut messages = vec![ChatMessage {
+            role: ChatRole::User,
+            message_type: MessageType::Text,
+            content: self.prompt.clone(),
+        }];
+        for turn in self.turns.iter().filter(|turn| !turn.tool_calls.is_empty()) {
+            let tool_calls: Vec<ToolCall> = turn
+                .tool_calls
+                .iter()
+                .map(|call| ToolCall {
+                    id: call.id.clone(),
+                    call_type: "function".to_string(),
+                    function: FunctionCall {
+                        name: call.name.clone(),
+                        arguments: call.arguments.clone(),
+                    },
+                })
+                .collect();
+            let results: Vec<ToolCallResult> = turn
+                .tool_calls
+                .iter()
+                .map(|call| ToolCallResult {
+                    tool_name: call.name.clone(),
+                    success: call.result.is_ok(),
+                    arguments: serde_json::from_str(&call.arguments).unwrap_or(Value::Null),
+                    result: match &call.result {
+                        Ok(value) => value.clone(),
+                        Err(error) => serde_json::json!({ "error": error }),
+                    },
+                })
+                .collect();
+            messages.push(ChatMessage {
+                role: ChatRole::Assistant,
+                message_type: MessageType::ToolUse(tool_calls.clone()),
+                content: String::default(),
+            });
+            messages.push(ChatMessage {
+                role: ChatRole::Tool,
+                message_type: MessageType::ToolResult(ToolProcessor::create_result_tool_calls(
+                    &tool_calls,
+                    &results,
+                )),
+                content: String::default(),
+            });
+        }
+        messages
+    }
+
+    /// Continue from this point with a live agent
+    ///
+    /// Without earlier turns this simply runs the (possibly edited) prompt.
+    /// Otherwise the agent's memory is replaced with [`Self::messages`] and
+    /// `follow_up` is sent as the next user message, since executors always
+    /// send a task prompt; the agent therefore needs a memory provider.
+    pub async fn resume<T>(
+        &self,
+        agent: &BaseAgent<T, DirectAgent>,
+        follow_up: impl Into<String>,
+    ) -> Result<<T as AgentDeriveT>::Output, RunnableAgentError>
+    where
+        T: AgentDeriveT + AgentExecutor + AgentHooks,
+        Value: From<<T as AgentExecutor>::Output>,
+        <T as AgentDeriveT>::Output: From<<T as AgentExecutor>::Output>,
+        <T as AgentExecutor>::Output: Clone,
+        <T as AgentExecutor>::Error: Into<RunnableAgentError>,
+    {
+        let memory = agent.memory();
+        if self.turns.is_empty() {
+            if let Some(memory) = &memory {
+                memory.lock().await.clear().await?;
+            }
+            return agent.run(Task::new(self.prompt.clone())).await;
+        }
+
+        let memory = memory.ok_or_else(|| {
+            RunnableAgentError::StateError(
+                "resuming a run after its first turn needs an agent with memory".to_string(),
+            )
+        })?;
+        {
+            let mut memory = memory.lock().await;
+            memory.clear().await?;
+            memory.remember_many(&self.messages()).await?;
+        }
+        agent.run(Task::new(follow_up)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentBuilder;
+    use crate::agent::memory::SlidingWindowMemory;
+    use crate::agent::prebuilt::executor::ReActAgent;
+    use crate::testing::ScriptedLLM;
+    use serde_json::json;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    #[derive(Debug)]
+    struct EchoAgent;
+
+    impl AgentDeriveT for EchoAgent {
+        type Output = String;
+
+        fn description(&self) -> &str {
+            "Answers questions"
+        }
+
+        fn output_schema(&self) -> Option<Value> {
+            None
+        }
+
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn tools(&self) -> Vec<Box<dyn crate::tool::ToolT>> {
+            Vec::new()
+        }
+    }
+
+    impl AgentHooks for EchoAgent {}
+
+    fn recorded_events() -> Vec<Event> {
+        let sub_id = Uuid::new_v4();
+        let actor_id = Uuid::new_v4();
+        vec![
+            Event::TaskStarted {
+                sub_id,
+                actor_id,
+                actor_name: "agent".into(),
+                task_description: "weather in Paris?".into(),
+                trace: None,
+            },
+            Event::TurnStarted {
+                sub_id,
+                actor_id,
+                turn_number: 0,
+                max_turns: 5,
+            },
+            Event::ToolCallRequested {
+                sub_id,
+                actor_id,
+                id: "call_1".into(),
+                tool_name: "weather".into(),
+                arguments: r#"{"city":"Paris"}"#.into(),
+            },
+            Event::ToolCallCompleted {
+                sub_id,
+                actor_id,
+                id: "call_1".into(),
+                tool_name: "weather".into(),
+                result: json!({"temp": 21}),
+            },
+            Event::TurnCompleted {
+                sub_id,
+                actor_id,
+                turn_number: 0,
+                final_turn: false,
+            },
+            Event::TurnStarted {
+                sub_id,
+                actor_id,
+                turn_number: 1,
+                max_turns: 5,
+            },
+            Event::TurnCompleted {
+                sub_id,
+                actor_id,
+                turn_number: 1,
+                final_turn: true,
+            },
+            Event::TaskComplete {
+                sub_id,
+                actor_id,
+                actor_name: "agent".into(),
+                result: "21 degrees".into(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_replay_groups_events_into_turns() {
+        let events = from_json_lines(&to_json_lines(&recorded_events())).unwrap();
+        let replay = RunReplay::from_events(&events).unwrap();
+
+        assert_eq!(replay.prompt(), "weather in Paris?");
+        assert_eq!(replay.outcome(), Some(Ok("21 degrees")));
+        assert_eq!(replay.turns().len(), 2);
+        assert_eq!(
+            replay.turns()[0].tool_calls[0].result,
+            Ok(json!({"temp": 21}))
+        );
+        assert!(replay.turns()[1].final_turn);
+        assert!(RunReplay::for_submission(&events, Uuid::new_v4()).is_none());
+    }
+
+    #[test]
+    fn test_state_rebuilds_memory_and_accepts_patches() {
+        let replay = RunReplay::from_events(&recorded_events()).unwrap();
+        assert_eq!(replay.state_at(0).messages().len(), 1);
+
+        let mut state = replay.state_at(1).with_prompt("weather in Rome?");
+        assert!(state.patch_tool_result("call_1", json!({"temp": 30})));
+        assert!(!state.patch_tool_result("call_9", json!(null)));
+
+        let messages = state.messages();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0].content, "weather in Rome?");
+        match &messages[2].message_type {
+            MessageType::ToolResult(calls) => {
+                assert_eq!(calls[0].function.arguments, r#"{"temp":30}"#)
+            }
+            other => panic!("unexpected message type: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resume_seeds_memory_before_running() {
+        let llm = Arc::new(ScriptedLLM::new().reply("30 degrees"));
+        let handle = AgentBuilder::<_, DirectAgent>::new(ReActAgent::new(EchoAgent))
+            .llm(llm.clone())
+            .memory(Box::new(SlidingWindowMemory::new(10)))
+            .build()
+            .await
+            .unwrap();
+
+        let mut state = RunReplay::from_events(&recorded_events())
+            .unwrap()
+            .state_at(1);
+        state.patch_tool_result("call_1", json!({"temp": 30}));
+        state.resume(&handle.agent, "Continue.").await.ok();
+
+        let request = &llm.requests()[0];
+        let contents: Vec<&str> = request
+            .messages
+            .iter()
+            .map(|message| message.content.as_str())
+            .collect();
+        assert_eq!(contents[1], "weather in Paris?");
+        assert_eq!(contents.last(), Some(&"Continue."));
+        assert_eq!(request.messages.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_resume_after_first_turn_requires_memory() {
+        let state = RunReplay::from_events(&recorded_events())
+            .unwrap()
+            .state_at(1);
+        let handle = AgentBuilder::<_, DirectAgent>::new(ReActAgent::new(EchoAgent))
+            .llm(Arc::new(ScriptedLLM::new()))
+            .build()
+            .await
+            .unwrap();
+        let err = state.resume(&handle.agent, "Continue.").await.unwrap_err();
+        assert!(err.to_string().contains("needs an agent with memory"));
+    }
+}
@@ -0,0 +1,278 @@
+use std::ops::Range;
+use std::path::Path;
+
+use serde_json::Value;
+use tree_sitter::{Language, Node, Parser};
+
+use super::{
+    Chunk, ChunkingError, RecursiveCharacterSplitter, TextSplitter, char_len, merge_spans,
+    validate_sizes,
+};
+
+/// Metadata key holding the language of a code chunk
+pub const LANGUAGE_KEY: &str = "language";
+/// Metadata key holding the names of the definitions a code chunk overlaps
+pub const SYMBOLS_KEY: &str = "symbols";
+
+/// Languages understood by [`CodeSplitter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodeLanguage {
+    Rust,
+    Python,
+    JavaScript,
+}
+
+impl CodeLanguage {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "rs" => Some(CodeLanguage::Rust),
+            "py" | "pyi" => Some(CodeLanguage::Python),
+            "js" | "jsx" | "mjs" | "cjs" => Some(CodeLanguage::JavaScript),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        path.as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CodeLanguage::Rust => "rust",
+            CodeLanguage::Python => "python",
+            CodeLanguage::JavaScript => "javascript",
+        }
+    }
+
+    fn grammar(&self) -> Language {
+        match self {
+            CodeLanguage::Rust => tree_sitter_rust::LANGUAGE.into(),
+            CodeLanguage::Python => tree_sitter_python::LANGUAGE.into(),
+            CodeLanguage::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+        }
+    }
+
+    fn is_definition(&self, kind: &str) -> bool {
+        match self {
+            CodeLanguage::Rust => matches!(
+                kind,
+                "function_item"
+                    | "impl_item"
+                    | "struct_item"
+                    | "enum_item"
+                    | "trait_item"
+                    | "mod_item"
+                    | "macro_definition"
+            ),
+            CodeLanguage::Python => matches!(kind, "function_definition" | "class_definition"),
+            CodeLanguage::JavaScript => matches!(
+                kind,
+                "function_declaration"
+                    | "generator_function_declaration"
+                    | "class_declaration"
+                    | "method_definition"
+            ),
+        }
+    }
+}
+
+/// Splits source code along its syntax tree
+///
+/// Top-level items are kept whole and packed together while they fit in
+/// `chunk_size`; an item that is too long is split between its children
+/// (the methods of an impl or class, then the statements of a function),
+/// and only a single oversized leaf is cut by characters. Each chunk records
+/// the language and the definitions it overlaps.
+#[derive(Debug, Clone)]
+pub struct CodeSplitter {
+    language: CodeLanguage,
+    chunk_size: usize,
+    chunk_overlap: usize,
+}
+
+impl CodeSplitter {
+    pub fn new(
+        language: CodeLanguage,
+        chunk_size: usize,
+        chunk_overlap: usize,
+    ) -> Result<Self, ChunkingError> {
+        validate_sizes(chunk_size, chunk_overlap)?;
+        Ok(Self {
+            language,
+            chunk_size,
+            chunk_overlap,
+        })
+    }
+
+    pub fn language(&self) -> CodeLanguage {
+        self.language
+    }
+
+    fn fallback(&self) -> RecursiveCharacterSplitter {
+        RecursiveCharacterSplitter::new(self.chunk_size, 0)
+            .expect("sizes were validated")
+            .with_separators(["\n\n", "\n", " ", ""])
+    }
+
+    fn collect(&self, node: Node, text: &str, range: Range<usize>, spans: &mut Vec<Range<usize>>) {
+        let mut start = range.start;
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            let end = child.end_byte();
+            if end <= start {
+                continue;
+            }
+
+            // Text between siblings (blank lines, attributes) goes with the next node
+            let span = start..end;
+            if char_len(&text[span.clone()]) <= self.chunk_size {
+                spans.push(span);
+            } else if child.child_count() > 0 {
+                self.collect(child, text, span, spans);
+            } else {
+                spans.extend(self.fallback().spans(text, span));
+            }
+            start = end;
+        }
+        if start < range.end {
+            spans.extend(self.fallback().spans(text, start..range.end));
+        }
+    }
+
+    fn collect_symbols(&self, node: Node, text: &str, symbols: &mut Vec<(Range<usize>, String)>) {
+        if self.language.is_definition(node.kind())
+            && let Some(name) = definition_name(node, text)
+        {
+            symbols.push((node.byte_range(), name));
+        }
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            self.collect_symbols(child, text, symbols);
+        }
+    }
+}
+
+fn definition_name(node: Node, text: &str) -> Option<String> {
+    let name = node
+        .child_by_field_name("name")
+        .or_else(|| node.child_by_field_name("type"))?;
+    text.get(name.byte_range()).map(str::to_string)
+}
+
+impl TextSplitter for CodeSplitter {
+    fn split_text(&self, text: &str) -> Vec<Chunk> {
+        if text.is_empty() {
+            return Vec::new();
+        }
+
+        let mut parser = Parser::new();
+        let tree = parser
+            .set_language(&self.language.grammar())
+            .ok()
+            .and_then(|_| parser.parse(text, None));
+        let Some(tree) = tree else {
+            log::warn!(
+                "Failed to parse {} source, falling back to line splitting",
+                self.language.name()
+            );
+            let spans = self.fallback().spans(text, 0..text.len());
+            return merge_spans(text, &spans, self.chunk_size, self.chunk_overlap);
+        };
+
+        let mut spans = Vec::new();
+        let mut symbols = Vec::new();
+        self.collect(tree.root_node(), text, 0..text.len(), &mut spans);
+        self.collect_symbols(tree.root_node(), text, &mut symbols);
+
+        merge_spans(text, &spans, self.chunk_size, self.chunk_overlap)
+            .into_iter()
+            .map(|mut chunk| {
+                let overlapping: Vec<Value> = symbols
+                    .iter()
+                    .filter(|(range, _)| range.start < chunk.end && range.end > chunk.start)
+                    .map(|(_, name)| Value::String(name.clone()))
+                    .collect();
+                chunk
+                    .metadata
+                    .insert(LANGUAGE_KEY.into(), self.language.name().into());
+                chunk
+                    .metadata
+                    .insert(SYMBOLS_KEY.into(), Value::Array(overlapping));
+                chunk
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn texts(chunks: &[Chunk]) -> Vec<&str> {
+        chunks.iter().map(|c| c.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_language_from_path() {
+        assert_eq!(
+            CodeLanguage::from_path("src/lib.rs"),
+            Some(CodeLanguage::Rust)
+        );
+        assert_eq!(
+            CodeLanguage::from_path("app.MJS"),
+            Some(CodeLanguage::JavaScript)
+        );
+        assert_eq!(CodeLanguage::from_path("README.md"), None);
+    }
+
+    #[test]
+    fn test_rust_items_are_not_cut() {
+        let source = "use std::fmt;\n\nfn alpha() -> u32 {\n    1\n}\n\nstruct Beta {\n    value: u32,\n}\n\nimpl Beta {\n    fn new() -> Self {\n        Self { value: 0 }\n    }\n\n    fn get(&self) -> u32 {\n        self.value\n    }\n}\n";
+        let splitter = CodeSplitter::new(CodeLanguage::Rust, 60, 0).unwrap();
+        let chunks = splitter.split_text(source);
+
+        assert_eq!(
+            texts(&chunks),
+            vec![
+                "use std::fmt;\n\nfn alpha() -> u32 {\n    1\n}",
+                "struct Beta {\n    value: u32,\n}\n\nimpl Beta {",
+                "fn new() -> Self {\n        Self { value: 0 }\n    }",
+                "fn get(&self) -> u32 {\n        self.value\n    }\n}",
+            ]
+        );
+        assert_eq!(chunks[0].metadata[SYMBOLS_KEY], json!(["alpha"]));
+        assert_eq!(chunks[3].metadata[SYMBOLS_KEY], json!(["Beta", "get"]));
+        assert_eq!(chunks[0].metadata[LANGUAGE_KEY], json!("rust"));
+        for chunk in &chunks {
+            assert_eq!(&source[chunk.start..chunk.end], chunk.text);
+        }
+    }
+
+    #[test]
+    fn test_python_and_javascript_definitions() {
+        let python = "class Greeter:\n    def hello(self):\n        return 'hi'\n\ndef main():\n    Greeter().hello()\n";
+        let chunks = CodeSplitter::new(CodeLanguage::Python, 50, 0)
+            .unwrap()
+            .split_text(python);
+        assert_eq!(
+            texts(&chunks),
+            vec![
+                "class Greeter:",
+                "def hello(self):\n        return 'hi'",
+                "def main():\n    Greeter().hello()",
+            ]
+        );
+        assert_eq!(chunks[1].metadata[SYMBOLS_KEY], json!(["Greeter", "hello"]));
+
+        let javascript = "function add(a, b) {\n  return a + b;\n}\n\nclass Counter {\n  inc() { this.n++; }\n}\n";
+        let chunks = CodeSplitter::new(CodeLanguage::JavaScript, 45, 0)
+            .unwrap()
+            .split_text(javascript);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].metadata[SYMBOLS_KEY], json!(["Counter", "inc"]));
+    }
+}
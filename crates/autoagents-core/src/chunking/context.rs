@@ -0,0 +1,193 @@
+use serde_json::{Map, Value};
+
+use super::{Chunk, HEADING_PATH_KEY, TextSplitter};
+use crate::document::Document;
+
+/// Metadata key holding the parent window a small chunk was cut from
+pub const PARENT_CONTENT_KEY: &str = "parent_content";
+/// Metadata key holding the position of the parent window within the document
+pub const PARENT_WINDOW_KEY: &str = "parent_window";
+/// Metadata key holding text embedded in front of the chunk content
+///
+/// [`Document`]'s [`Embed`](crate::embeddings::Embed) implementation prepends
+/// it to `page_content`, so the stored content stays unchanged.
+pub const EMBEDDING_CONTEXT_KEY: &str = "embedding_context";
+
+/// Splits text into large parent windows, then each window into small chunks
+///
+/// The small chunks are what gets embedded and matched, while every chunk
+/// carries its window as [`PARENT_CONTENT_KEY`] (and its position as
+/// [`PARENT_WINDOW_KEY`]) so retrieval can hand the larger window to the LLM;
+/// see [`ParentDocumentIndex`](crate::vector_store::ParentDocumentIndex).
+/// Metadata from the parent splitter, such as heading paths, is kept on the
+/// small chunks.
+pub struct ParentWindowSplitter {
+    parent: Box<dyn TextSplitter>,
+    child: Box<dyn TextSplitter>,
+}
+
+impl ParentWindowSplitter {
+    pub fn new(parent: impl TextSplitter + 'static, child: impl TextSplitter + 'static) -> Self {
+        Self {
+            parent: Box::new(parent),
+            child: Box::new(child),
+        }
+    }
+}
+
+impl TextSplitter for ParentWindowSplitter {
+    fn split_text(&self, text: &str) -> Vec<Chunk> {
+        self.parent
+            .split_text(text)
+            .into_iter()
+            .enumerate()
+            .flat_map(|(window, parent)| {
+                self.child
+                    .split_text(&parent.text)
+                    .into_iter()
+                    .map(move |child| {
+                        let mut metadata = parent.metadata.clone();
+                        metadata.extend(child.metadata);
+                        metadata.insert(PARENT_CONTENT_KEY.into(), parent.text.clone().into());
+                        metadata.insert(PARENT_WINDOW_KEY.into(), window.into());
+                        Chunk {
+                            text: child.text,
+                            start: parent.start + child.start,
+                            end: parent.start + child.end,
+                            metadata,
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// Adds the document title or source and the heading path of each chunk to
+/// the text that gets embedded
+///
+/// Chunks like "Run `make install`." match queries much better when their
+/// embedding also sees "Document: guide.md" and "Section: Setup > Linux". The
+/// context is stored as [`EMBEDDING_CONTEXT_KEY`]; the chunk content itself is
+/// not changed.
+pub struct ContextualSplitter {
+    inner: Box<dyn TextSplitter>,
+    include_document: bool,
+    include_headings: bool,
+}
+
+impl ContextualSplitter {
+    pub fn new(inner: impl TextSplitter + 'static) -> Self {
+        Self {
+            inner: Box::new(inner),
+            include_document: true,
+            include_headings: true,
+        }
+    }
+
+    /// Include the `title` metadata, or `source` if there is no title (default: true)
+    pub fn include_document(mut self, include: bool) -> Self {
+        self.include_document = include;
+        self
+    }
+
+    /// Include the [`HEADING_PATH_KEY`] metadata (default: true)
+    pub fn include_headings(mut self, include: bool) -> Self {
+        self.include_headings = include;
+        self
+    }
+
+    fn context(&self, metadata: &Map<String, Value>) -> String {
+        let mut lines = Vec::new();
+        if self.include_document
+            && let Some(name) = ["title", "source"]
+                .iter()
+                .find_map(|key| metadata.get(*key).and_then(Value::as_str))
+                .filter(|name| !name.is_empty())
+        {
+            lines.push(format!("Document: {name}"));
+        }
+        if self.include_headings
+            && let Some(Value::Array(path)) = metadata.get(HEADING_PATH_KEY)
+        {
+            let path: Vec<&str> = path.iter().filter_map(Value::as_str).collect();
+            if !path.is_empty() {
+                lines.push(format!("Section: {}", path.join(" > ")));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+impl TextSplitter for ContextualSplitter {
+    fn split_text(&self, text: &str) -> Vec<Chunk> {
+        self.inner.split_text(text)
+    }
+
+    fn split_documents(&self, documents: &[Document]) -> Vec<Document> {
+        let mut chunks = self.inner.split_documents(documents);
+        for chunk in &mut chunks {
+            if let Value::Object(metadata) = &mut chunk.metadata {
+                let context = self.context(metadata);
+                if !context.is_empty() {
+                    metadata.insert(EMBEDDING_CONTEXT_KEY.into(), context.into());
+                }
+            }
+        }
+        chunks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunking::{MarkdownSplitter, RecursiveCharacterSplitter};
+    use crate::embeddings::{Embed, TextEmbedder};
+    use serde_json::json;
+
+    #[test]
+    fn test_parent_windows_keep_offsets_and_heading_paths() {
+        let text = "# Setup\nInstall the tool. Then configure it.\n# Usage\nRun it daily.";
+        let splitter = ParentWindowSplitter::new(
+            MarkdownSplitter::new(100, 0).unwrap(),
+            RecursiveCharacterSplitter::new(20, 0).unwrap(),
+        );
+        let chunks = splitter.split_text(text);
+
+        assert!(chunks.len() > 2);
+        for chunk in &chunks {
+            assert_eq!(&text[chunk.start..chunk.end], chunk.text);
+            let parent = chunk.metadata[PARENT_CONTENT_KEY].as_str().unwrap();
+            assert!(parent.contains(&chunk.text));
+        }
+        let last = chunks.last().unwrap();
+        assert_eq!(last.metadata[PARENT_WINDOW_KEY], 1);
+        assert_eq!(last.metadata[HEADING_PATH_KEY], json!(["Usage"]));
+        assert_eq!(last.metadata[PARENT_CONTENT_KEY], "# Usage\nRun it daily.");
+    }
+
+    #[test]
+    fn test_contextual_chunks_embed_document_and_section() {
+        let doc = Document::with_metadata(
+            "# Setup\n## Linux\nRun make install.",
+            json!({"source": "guide.md"}),
+        );
+        let chunks =
+            ContextualSplitter::new(MarkdownSplitter::new(100, 0).unwrap()).split_documents(&[doc]);
+        let chunk = chunks.last().unwrap();
+        assert_eq!(
+            chunk.metadata[EMBEDDING_CONTEXT_KEY],
+            "Document: guide.md\nSection: Setup > Linux"
+        );
+
+        let mut embedder = TextEmbedder::new();
+        chunk.embed(&mut embedder).unwrap();
+        assert_eq!(
+            embedder.parts(),
+            &[format!(
+                "Document: guide.md\nSection: Setup > Linux\n\n{}",
+                chunk.page_content
+            )]
+        );
+    }
+}
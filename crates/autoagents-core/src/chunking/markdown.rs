@@ -0,0 +1,188 @@
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde_json::Value;
+
+use super::{
+    Chunk, ChunkingError, RecursiveCharacterSplitter, TextSplitter, char_len, merge_spans,
+    trimmed_chunk, validate_sizes,
+};
+
+/// Metadata key holding the titles of the headings enclosing a chunk, outermost first
+pub const HEADING_PATH_KEY: &str = "heading_path";
+
+static ATX_HEADING: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^ {0,3}(#{1,6})[ \t]+(.*?)(?:[ \t]+#+)?[ \t]*$").expect("heading regex is valid")
+});
+
+/// Splits Markdown at headings and records the heading path of each chunk
+///
+/// Every heading up to `max_heading_level` starts a new section; headings in
+/// fenced code blocks are ignored. Sections longer than `chunk_size` are split
+/// further at blank lines, lines and words, with `chunk_overlap` applied only
+/// inside a section.
+#[derive(Debug, Clone)]
+pub struct MarkdownSplitter {
+    chunk_size: usize,
+    chunk_overlap: usize,
+    max_heading_level: usize,
+}
+
+struct Section {
+    range: Range<usize>,
+    heading_path: Vec<String>,
+}
+
+impl MarkdownSplitter {
+    pub fn new(chunk_size: usize, chunk_overlap: usize) -> Result<Self, ChunkingError> {
+        validate_sizes(chunk_size, chunk_overlap)?;
+        Ok(Self {
+            chunk_size,
+            chunk_overlap,
+            max_heading_level: 6,
+        })
+    }
+
+    /// Only split at headings of this level or higher (1 = `#`)
+    pub fn with_max_heading_level(mut self, level: usize) -> Self {
+        self.max_heading_level = level.clamp(1, 6);
+        self
+    }
+
+    fn sections(&self, text: &str) -> Vec<Section> {
+        let mut sections = Vec::new();
+        let mut path: Vec<(usize, String)> = Vec::new();
+        let mut section_start = 0;
+        let mut fence: Option<&str> = None;
+        let mut offset = 0;
+
+        for line in text.split_inclusive('\n') {
+            let line_start = offset;
+            offset += line.len();
+            let trimmed = line.trim_start();
+
+            if let Some(marker) = fence {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+                continue;
+            }
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                fence = Some(&trimmed[..3]);
+                continue;
+            }
+
+            let Some(captures) = ATX_HEADING.captures(line.trim_end_matches(['\r', '\n'])) else {
+                continue;
+            };
+            let level = captures[1].len();
+            if level > self.max_heading_level {
+                continue;
+            }
+
+            if line_start > section_start {
+                sections.push(Section {
+                    range: section_start..line_start,
+                    heading_path: path.iter().map(|(_, title)| title.clone()).collect(),
+                });
+            }
+            section_start = line_start;
+            path.retain(|(existing, _)| *existing < level);
+            path.push((level, captures[2].trim().to_string()));
+        }
+
+        if section_start < text.len() {
+            sections.push(Section {
+                range: section_start..text.len(),
+                heading_path: path.into_iter().map(|(_, title)| title).collect(),
+            });
+        }
+        sections
+    }
+}
+
+impl TextSplitter for MarkdownSplitter {
+    fn split_text(&self, text: &str) -> Vec<Chunk> {
+        let inner = RecursiveCharacterSplitter::new(self.chunk_size, self.chunk_overlap)
+            .expect("sizes were validated")
+            .with_separators(["\n```", "\n\n", "\n", " ", ""]);
+
+        let mut chunks = Vec::new();
+        for section in self.sections(text) {
+            let path = Value::from(section.heading_path);
+            let section_chunks = if char_len(&text[section.range.clone()]) <= self.chunk_size {
+                trimmed_chunk(text, section.range).into_iter().collect()
+            } else {
+                let spans = inner.spans(text, section.range);
+                merge_spans(text, &spans, self.chunk_size, self.chunk_overlap)
+            };
+            chunks.extend(section_chunks.into_iter().map(|mut chunk| {
+                chunk.metadata.insert(HEADING_PATH_KEY.into(), path.clone());
+                chunk
+            }));
+        }
+        chunks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const DOC: &str = "Intro text.\n\n# Guide\nOverview.\n\n## Install\nRun it.\n```sh\n# not a heading\n```\n## Usage ##\nUse it.\n# FAQ\nAsk.\n";
+
+    #[test]
+    fn test_sections_carry_heading_path() {
+        let splitter = MarkdownSplitter::new(200, 0).unwrap();
+        let chunks = splitter.split_text(DOC);
+        let summary: Vec<(&str, Value)> = chunks
+            .iter()
+            .map(|c| {
+                (
+                    c.text.lines().next().unwrap(),
+                    c.metadata[HEADING_PATH_KEY].clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Intro text.", json!([])),
+                ("# Guide", json!(["Guide"])),
+                ("## Install", json!(["Guide", "Install"])),
+                ("## Usage ##", json!(["Guide", "Usage"])),
+                ("# FAQ", json!(["FAQ"])),
+            ]
+        );
+        assert!(chunks[2].text.contains("# not a heading"));
+        for chunk in &chunks {
+            assert_eq!(&DOC[chunk.start..chunk.end], chunk.text);
+        }
+    }
+
+    #[test]
+    fn test_long_sections_are_split_within_heading() {
+        let text = format!("# Title\n{}", "word ".repeat(20));
+        let splitter = MarkdownSplitter::new(30, 0).unwrap();
+        let chunks = splitter.split_text(&text);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| char_len(&c.text) <= 30));
+        assert!(
+            chunks
+                .iter()
+                .all(|c| c.metadata[HEADING_PATH_KEY] == json!(["Title"]))
+        );
+    }
+
+    #[test]
+    fn test_max_heading_level() {
+        let splitter = MarkdownSplitter::new(200, 0)
+            .unwrap()
+            .with_max_heading_level(1);
+        let chunks = splitter.split_text(DOC);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[1].metadata[HEADING_PATH_KEY], json!(["Guide"]));
+    }
+}
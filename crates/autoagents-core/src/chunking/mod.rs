@@ -0,0 +1,256 @@
+//! Splitting documents into chunks for embedding
+//!
+//! Splitters cut text into [`Chunk`]s that carry byte offsets into the source
+//! text. [`TextSplitter::split_documents`] turns them into [`Document`]s whose
+//! metadata is the parent's metadata plus [`PARENT_ID_KEY`], [`CHUNK_INDEX_KEY`],
+//! [`CHUNK_START_KEY`] and [`CHUNK_END_KEY`], so retrieved chunks can be traced
+//! back to the document and position they came from.
+//!
+//! Sizes and overlaps are measured in characters, except for
+//! [`TokenSplitter`] which counts tokens.
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::document::Document;
+use crate::embeddings::EmbeddingError;
+
+#[cfg(feature = "code-splitter")]
+mod code;
+mod context;
+mod markdown;
+mod recursive;
+mod semantic;
+mod sentence;
+mod token;
+
+#[cfg(feature = "code-splitter")]
+pub use code::{CodeLanguage, CodeSplitter, LANGUAGE_KEY, SYMBOLS_KEY};
+pub use context::{
+    ContextualSplitter, EMBEDDING_CONTEXT_KEY, PARENT_CONTENT_KEY, PARENT_WINDOW_KEY,
+    ParentWindowSplitter,
+};
+pub use markdown::{HEADING_PATH_KEY, MarkdownSplitter};
+pub use recursive::RecursiveCharacterSplitter;
+pub use semantic::{BreakpointThreshold, SemanticSplitter};
+pub use sentence::SentenceSplitter;
+pub use token::{TokenSplitter, Tokenizer, WordTokenizer};
+
+/// Metadata key holding the id of the document a chunk was cut from
+pub const PARENT_ID_KEY: &str = "parent_id";
+/// Metadata key holding the position of a chunk within its parent
+pub const CHUNK_INDEX_KEY: &str = "chunk_index";
+/// Metadata key holding the byte offset where a chunk starts in its parent
+pub const CHUNK_START_KEY: &str = "chunk_start";
+/// Metadata key holding the byte offset where a chunk ends in its parent
+pub const CHUNK_END_KEY: &str = "chunk_end";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ChunkingError {
+    #[error("Invalid chunking configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("Embedding error: {0}")]
+    Embedding(#[from] EmbeddingError),
+}
+
+/// A piece of text and its byte range in the text it was cut from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Chunk {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+    /// Splitter-specific metadata copied onto the chunk document
+    pub metadata: Map<String, Value>,
+}
+
+pub trait TextSplitter: Send + Sync {
+    fn split_text(&self, text: &str) -> Vec<Chunk>;
+
+    /// Split every document, keeping its metadata on each chunk
+    fn split_documents(&self, documents: &[Document]) -> Vec<Document> {
+        documents
+            .iter()
+            .flat_map(|doc| chunk_documents(doc, self.split_text(&doc.page_content)))
+            .collect()
+    }
+}
+
+/// Id recorded as [`PARENT_ID_KEY`] on the chunks of `document`
+///
+/// Uses the `id` metadata field, then `source`, and falls back to a hash of
+/// the content, so the same document gets the same id on every run.
+pub fn parent_id(document: &Document) -> String {
+    ["id", "source"]
+        .iter()
+        .find_map(|key| match document.metadata.get(key) {
+            Some(Value::String(value)) => Some(value.clone()),
+            Some(Value::Number(value)) => Some(value.to_string()),
+            _ => None,
+        })
+        .unwrap_or_else(|| {
+            Sha256::digest(document.page_content.as_bytes())
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect()
+        })
+}
+
+/// Build chunk documents for `parent` from already split `chunks`
+pub fn chunk_documents(parent: &Document, chunks: Vec<Chunk>) -> Vec<Document> {
+    let parent_id = parent_id(parent);
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut metadata = match &parent.metadata {
+                Value::Object(map) => map.clone(),
+                _ => Default::default(),
+            };
+            metadata.extend(chunk.metadata);
+            metadata.insert(PARENT_ID_KEY.into(), Value::String(parent_id.clone()));
+            metadata.insert(CHUNK_INDEX_KEY.into(), index.into());
+            metadata.insert(CHUNK_START_KEY.into(), chunk.start.into());
+            metadata.insert(CHUNK_END_KEY.into(), chunk.end.into());
+            Document::with_metadata(chunk.text, Value::Object(metadata))
+        })
+        .collect()
+}
+
+pub(crate) fn validate_sizes(chunk_size: usize, chunk_overlap: usize) -> Result<(), ChunkingError> {
+    if chunk_size == 0 {
+        return Err(ChunkingError::InvalidConfig(
+            "chunk_size must be greater than zero".to_string(),
+        ));
+    }
+    if chunk_overlap >= chunk_size {
+        return Err(ChunkingError::InvalidConfig(format!(
+            "chunk_overlap ({chunk_overlap}) must be smaller than chunk_size ({chunk_size})"
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn char_len(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Chunk for `range` with surrounding whitespace trimmed, or `None` if blank
+pub(crate) fn trimmed_chunk(text: &str, range: Range<usize>) -> Option<Chunk> {
+    let slice = &text[range.clone()];
+    let start = range.start + (slice.len() - slice.trim_start().len());
+    let end = range.end - (slice.len() - slice.trim_end().len());
+    (start < end).then(|| Chunk {
+        text: text[start..end].to_string(),
+        start,
+        end,
+        metadata: Map::new(),
+    })
+}
+
+/// Merge consecutive `spans` into chunks of at most `chunk_size` characters
+///
+/// Each span must fit in `chunk_size` on its own. After a chunk is emitted,
+/// up to `chunk_overlap` characters of its trailing spans start the next one.
+pub(crate) fn merge_spans(
+    text: &str,
+    spans: &[Range<usize>],
+    chunk_size: usize,
+    chunk_overlap: usize,
+) -> Vec<Chunk> {
+    let mut chunks = Vec::new();
+    let mut window: VecDeque<(Range<usize>, usize)> = VecDeque::new();
+    let mut total = 0usize;
+
+    for span in spans {
+        let len = char_len(&text[span.clone()]);
+        if total + len > chunk_size
+            && let (Some(first), Some(last)) = (window.front(), window.back())
+        {
+            chunks.extend(trimmed_chunk(text, first.0.start..last.0.end));
+            while total > chunk_overlap || (total > 0 && total + len > chunk_size) {
+                let Some((_, dropped)) = window.pop_front() else {
+                    break;
+                };
+                total -= dropped;
+            }
+        }
+        window.push_back((span.clone(), len));
+        total += len;
+    }
+
+    if let (Some(first), Some(last)) = (window.front(), window.back()) {
+        chunks.extend(trimmed_chunk(text, first.0.start..last.0.end));
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_spans_with_overlap() {
+        let text = "aa bb cc dd";
+        let spans = vec![0..3, 3..6, 6..9, 9..11];
+        let chunks = merge_spans(text, &spans, 6, 3);
+        let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, vec!["aa bb", "bb cc", "cc dd"]);
+        assert_eq!((chunks[1].start, chunks[1].end), (3, 8));
+    }
+
+    #[test]
+    fn test_validate_sizes() {
+        assert!(validate_sizes(10, 2).is_ok());
+        assert!(validate_sizes(0, 0).is_err());
+        assert!(validate_sizes(10, 10).is_err());
+    }
+
+    #[test]
+    fn test_chunk_documents_records_parent_and_offsets() {
+        let parent = Document::with_metadata("hello world", json!({"source": "a.txt"}));
+        let docs = chunk_documents(
+            &parent,
+            vec![
+                Chunk {
+                    text: "hello".into(),
+                    start: 0,
+                    end: 5,
+                    ..Chunk::default()
+                },
+                Chunk {
+                    text: "world".into(),
+                    start: 6,
+                    end: 11,
+                    ..Chunk::default()
+                },
+            ],
+        );
+        assert_eq!(docs.len(), 2);
+        assert_eq!(
+            docs[1].metadata,
+            json!({
+                "source": "a.txt",
+                "parent_id": "a.txt",
+                "chunk_index": 1,
+                "chunk_start": 6,
+                "chunk_end": 11,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parent_id_without_metadata_is_derived_from_content() {
+        let id = parent_id(&Document::new("hello world"));
+        assert_eq!(id, parent_id(&Document::new("hello world")));
+        assert_ne!(id, parent_id(&Document::new("hello there")));
+        assert_eq!(
+            parent_id(&Document::with_metadata("hello world", json!({"id": 7}))),
+            "7"
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use std::ops::Range;
+
+use super::{Chunk, ChunkingError, TextSplitter, char_len, merge_spans, validate_sizes};
+
+/// Splits on the coarsest separator that occurs (paragraphs, then lines, then
+/// words, then characters) and recurses into pieces that are still too long
+#[derive(Debug, Clone)]
+pub struct RecursiveCharacterSplitter {
+    chunk_size: usize,
+    chunk_overlap: usize,
+    separators: Vec<String>,
+}
+
+impl RecursiveCharacterSplitter {
+    pub fn new(chunk_size: usize, chunk_overlap: usize) -> Result<Self, ChunkingError> {
+        validate_sizes(chunk_size, chunk_overlap)?;
+        Ok(Self {
+            chunk_size,
+            chunk_overlap,
+            separators: ["\n\n", "\n", " ", ""].map(String::from).to_vec(),
+        })
+    }
+
+    /// Replace the separators, coarsest first
+    ///
+    /// An empty separator splits between characters; without one, a piece
+    /// that contains none of the separators may exceed `chunk_size`.
+    pub fn with_separators<I, S>(mut self, separators: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.separators = separators.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    pub fn chunk_overlap(&self) -> usize {
+        self.chunk_overlap
+    }
+
+    /// Byte ranges covering `range`, each at most `chunk_size` characters where possible
+    pub(crate) fn spans(&self, text: &str, range: Range<usize>) -> Vec<Range<usize>> {
+        let mut spans = Vec::new();
+        self.collect_spans(text, range, &self.separators, &mut spans);
+        spans
+    }
+
+    fn collect_spans(
+        &self,
+        text: &str,
+        range: Range<usize>,
+        separators: &[String],
+        out: &mut Vec<Range<usize>>,
+    ) {
+        let slice = &text[range.clone()];
+        if char_len(slice) <= self.chunk_size {
+            out.push(range);
+            return;
+        }
+
+        let Some(position) = separators
+            .iter()
+            .position(|sep| sep.is_empty() || slice.contains(sep.as_str()))
+        else {
+            out.push(range);
+            return;
+        };
+        let separator = &separators[position];
+        let finer = &separators[position + 1..];
+
+        if separator.is_empty() {
+            for (offset, ch) in slice.char_indices() {
+                let start = range.start + offset;
+                out.push(start..start + ch.len_utf8());
+            }
+            return;
+        }
+
+        // Keep each separator at the end of the piece before it so the
+        // pieces still tile the original text.
+        let mut last = 0;
+        let mut pieces = Vec::new();
+        for (index, _) in slice.match_indices(separator.as_str()) {
+            let end = index + separator.len();
+            pieces.push(range.start + last..range.start + end);
+            last = end;
+        }
+        if last < slice.len() {
+            pieces.push(range.start + last..range.end);
+        }
+
+        for piece in pieces {
+            if char_len(&text[piece.clone()]) <= self.chunk_size {
+                out.push(piece);
+            } else {
+                self.collect_spans(text, piece, finer, out);
+            }
+        }
+    }
+}
+
+impl TextSplitter for RecursiveCharacterSplitter {
+    fn split_text(&self, text: &str) -> Vec<Chunk> {
+        if text.is_empty() {
+            return Vec::new();
+        }
+        let spans = self.spans(text, 0..text.len());
+        merge_spans(text, &spans, self.chunk_size, self.chunk_overlap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefers_paragraph_boundaries() {
+        let text = "First paragraph here.\n\nSecond paragraph here.";
+        let splitter = RecursiveCharacterSplitter::new(25, 0).unwrap();
+        let chunks = splitter.split_text(text);
+        let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec!["First paragraph here.", "Second paragraph here."]
+        );
+        for chunk in &chunks {
+            assert_eq!(&text[chunk.start..chunk.end], chunk.text);
+        }
+    }
+
+    #[test]
+    fn test_long_words_fall_back_to_characters_with_overlap() {
+        let splitter = RecursiveCharacterSplitter::new(4, 1).unwrap();
+        let chunks = splitter.split_text("abcdefghij");
+        let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, vec!["abcd", "defg", "ghij"]);
+        assert!(splitter.split_text("").is_empty());
+    }
+
+    #[test]
+    fn test_multibyte_text_keeps_valid_offsets() {
+        let text = "héllo wörld ünïcode";
+        let splitter = RecursiveCharacterSplitter::new(6, 0).unwrap();
+        for chunk in splitter.split_text(text) {
+            assert!(char_len(&chunk.text) <= 6);
+            assert_eq!(&text[chunk.start..chunk.end], chunk.text);
+        }
+    }
+}
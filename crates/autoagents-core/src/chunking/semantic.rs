@@ -0,0 +1,190 @@
+use crate::document::Document;
+use crate::embeddings::distance::VectorDistance;
+use crate::embeddings::{EmbeddingBatchOptions, SharedEmbeddingProvider, embed_in_batches};
+
+use super::sentence::sentence_spans;
+use super::{Chunk, ChunkingError, char_len, chunk_documents, trimmed_chunk};
+
+/// When the embedding distance between neighbouring sentences starts a new chunk
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BreakpointThreshold {
+    /// Break at distances above this percentile (0-100) of all distances in the text
+    Percentile(f32),
+    /// Break at cosine distances above this value
+    Absolute(f32),
+}
+
+impl Default for BreakpointThreshold {
+    fn default() -> Self {
+        BreakpointThreshold::Percentile(95.0)
+    }
+}
+
+/// Groups sentences into chunks and starts a new chunk where the topic drifts
+///
+/// Each sentence is embedded together with `buffer_size` neighbours on each
+/// side, and a breakpoint is placed wherever the cosine distance between
+/// consecutive sentences exceeds the [`BreakpointThreshold`]. Chunks do not
+/// overlap, since their boundaries are meant to fall between topics.
+#[derive(Clone)]
+pub struct SemanticSplitter {
+    provider: SharedEmbeddingProvider,
+    threshold: BreakpointThreshold,
+    buffer_size: usize,
+    max_chunk_size: Option<usize>,
+    batch_options: EmbeddingBatchOptions,
+}
+
+impl SemanticSplitter {
+    pub fn new(provider: SharedEmbeddingProvider) -> Self {
+        Self {
+            provider,
+            threshold: BreakpointThreshold::default(),
+            buffer_size: 1,
+            max_chunk_size: None,
+            batch_options: EmbeddingBatchOptions::default(),
+        }
+    }
+
+    pub fn with_threshold(mut self, threshold: BreakpointThreshold) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Neighbouring sentences embedded with each sentence (default: 1)
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    /// Also break before a chunk would grow past this many characters
+    pub fn with_max_chunk_size(mut self, max_chunk_size: usize) -> Self {
+        self.max_chunk_size = Some(max_chunk_size);
+        self
+    }
+
+    pub fn with_batch_options(mut self, options: EmbeddingBatchOptions) -> Self {
+        self.batch_options = options;
+        self
+    }
+
+    pub async fn split_text(&self, text: &str) -> Result<Vec<Chunk>, ChunkingError> {
+        let sentences = sentence_spans(text);
+        if sentences.len() < 2 {
+            return Ok(trimmed_chunk(text, 0..text.len()).into_iter().collect());
+        }
+
+        let windows: Vec<String> = (0..sentences.len())
+            .map(|i| {
+                let first = i.saturating_sub(self.buffer_size);
+                let last = (i + self.buffer_size).min(sentences.len() - 1);
+                text[sentences[first].start..sentences[last].end].to_string()
+            })
+            .collect();
+        let vectors =
+            embed_in_batches(self.provider.as_ref(), windows, &self.batch_options).await?;
+
+        let distances: Vec<f32> = vectors
+            .windows(2)
+            .map(|pair| 1.0 - pair[0].as_slice().cosine_similarity(&pair[1], true))
+            .collect();
+        let threshold = match self.threshold {
+            BreakpointThreshold::Absolute(value) => value,
+            BreakpointThreshold::Percentile(percentile) => percentile_of(&distances, percentile),
+        };
+
+        let mut chunks = Vec::new();
+        let mut start = sentences[0].start;
+        for (i, distance) in distances.iter().enumerate() {
+            let next = &sentences[i + 1];
+            let too_long = self
+                .max_chunk_size
+                .is_some_and(|max| char_len(&text[start..next.end]) > max);
+            if *distance > threshold || too_long {
+                chunks.extend(trimmed_chunk(text, start..next.start));
+                start = next.start;
+            }
+        }
+        chunks.extend(trimmed_chunk(text, start..text.len()));
+        Ok(chunks)
+    }
+
+    /// Split every document, keeping its metadata on each chunk
+    pub async fn split_documents(
+        &self,
+        documents: &[Document],
+    ) -> Result<Vec<Document>, ChunkingError> {
+        let mut chunked = Vec::new();
+        for doc in documents {
+            let chunks = self.split_text(&doc.page_content).await?;
+            chunked.extend(chunk_documents(doc, chunks));
+        }
+        Ok(chunked)
+    }
+}
+
+fn percentile_of(values: &[f32], percentile: f32) -> f32 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let rank = (percentile.clamp(0.0, 100.0) / 100.0) * (sorted.len() - 1) as f32;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use autoagents_llm::embedding::EmbeddingProvider;
+    use autoagents_llm::error::LLMError;
+    use std::sync::Arc;
+
+    /// Embeds text about animals and text about finance in orthogonal directions
+    struct TopicProvider;
+
+    #[async_trait]
+    impl EmbeddingProvider for TopicProvider {
+        async fn embed(&self, input: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
+            Ok(input
+                .iter()
+                .map(|text| {
+                    let animals = ["cat", "dog"].iter().filter(|w| text.contains(*w)).count();
+                    let finance = ["stock", "bond"]
+                        .iter()
+                        .filter(|w| text.contains(*w))
+                        .count();
+                    vec![animals as f32, finance as f32]
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_percentile_of() {
+        let values = [0.1, 0.4, 0.2, 0.3];
+        assert!((percentile_of(&values, 0.0) - 0.1).abs() < 1e-6);
+        assert!((percentile_of(&values, 100.0) - 0.4).abs() < 1e-6);
+        assert!((percentile_of(&values, 50.0) - 0.25).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_breaks_where_topic_changes() {
+        let text = "The cat sleeps. The dog barks. A stock rallied. A bond fell.";
+        let splitter = SemanticSplitter::new(Arc::new(TopicProvider))
+            .with_buffer_size(0)
+            .with_threshold(BreakpointThreshold::Absolute(0.5));
+        let chunks = splitter.split_text(text).await.unwrap();
+        let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "The cat sleeps. The dog barks.",
+                "A stock rallied. A bond fell."
+            ]
+        );
+        assert_eq!(&text[chunks[1].start..chunks[1].end], chunks[1].text);
+
+        let capped = splitter.with_max_chunk_size(16);
+        assert_eq!(capped.split_text(text).await.unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,98 @@
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::{
+    Chunk, ChunkingError, RecursiveCharacterSplitter, TextSplitter, char_len, merge_spans,
+    validate_sizes,
+};
+
+static SENTENCE_END: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"[.!?]+["')\]]*\s+|\n\s*\n"#).expect("sentence boundary regex is valid")
+});
+
+/// Byte ranges of the sentences in `text`; each keeps its trailing whitespace
+pub(crate) fn sentence_spans(text: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for boundary in SENTENCE_END.find_iter(text) {
+        spans.push(last..boundary.end());
+        last = boundary.end();
+    }
+    if last < text.len() {
+        spans.push(last..text.len());
+    }
+    spans
+}
+
+/// Packs whole sentences into chunks, splitting a sentence only when it is
+/// longer than `chunk_size` on its own
+#[derive(Debug, Clone)]
+pub struct SentenceSplitter {
+    chunk_size: usize,
+    chunk_overlap: usize,
+}
+
+impl SentenceSplitter {
+    pub fn new(chunk_size: usize, chunk_overlap: usize) -> Result<Self, ChunkingError> {
+        validate_sizes(chunk_size, chunk_overlap)?;
+        Ok(Self {
+            chunk_size,
+            chunk_overlap,
+        })
+    }
+}
+
+impl TextSplitter for SentenceSplitter {
+    fn split_text(&self, text: &str) -> Vec<Chunk> {
+        let fallback = RecursiveCharacterSplitter::new(self.chunk_size, 0)
+            .expect("chunk_size was validated")
+            .with_separators([" ", ""]);
+        let spans: Vec<Range<usize>> = sentence_spans(text)
+            .into_iter()
+            .flat_map(|span| {
+                if char_len(&text[span.clone()]) <= self.chunk_size {
+                    vec![span]
+                } else {
+                    fallback.spans(text, span)
+                }
+            })
+            .collect();
+        merge_spans(text, &spans, self.chunk_size, self.chunk_overlap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentence_spans() {
+        let text = "One. Two! \"Three?\" Four";
+        let sentences: Vec<&str> = sentence_spans(text)
+            .into_iter()
+            .map(|span| &text[span])
+            .collect();
+        assert_eq!(sentences, vec!["One. ", "Two! ", "\"Three?\" ", "Four"]);
+    }
+
+    #[test]
+    fn test_packs_sentences_with_overlap() {
+        let text = "Cats purr. Dogs bark. Birds sing. Fish swim.";
+        let splitter = SentenceSplitter::new(23, 12).unwrap();
+        let texts: Vec<String> = splitter
+            .split_text(text)
+            .into_iter()
+            .map(|c| c.text)
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Cats purr. Dogs bark.",
+                "Dogs bark. Birds sing.",
+                "Birds sing. Fish swim."
+            ]
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::ops::Range;
+use std::sync::{Arc, LazyLock};
+
+use regex::Regex;
+
+use super::{Chunk, ChunkingError, TextSplitter, trimmed_chunk, validate_sizes};
+
+/// Finds the tokens of a text as byte ranges
+///
+/// Implement this over a model's own tokenizer to size chunks exactly to its
+/// context window; [`WordTokenizer`] is a dependency-free approximation.
+pub trait Tokenizer: Send + Sync {
+    fn token_spans(&self, text: &str) -> Vec<Range<usize>>;
+}
+
+static WORD_TOKEN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\w+|[^\w\s]").expect("word token regex is valid"));
+
+/// Treats each word and each punctuation mark as one token
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WordTokenizer;
+
+impl Tokenizer for WordTokenizer {
+    fn token_spans(&self, text: &str) -> Vec<Range<usize>> {
+        WORD_TOKEN.find_iter(text).map(|m| m.range()).collect()
+    }
+}
+
+/// Cuts text into windows of `chunk_size` tokens, consecutive windows sharing
+/// `chunk_overlap` tokens
+#[derive(Clone)]
+pub struct TokenSplitter {
+    chunk_size: usize,
+    chunk_overlap: usize,
+    tokenizer: Arc<dyn Tokenizer>,
+}
+
+impl TokenSplitter {
+    pub fn new(chunk_size: usize, chunk_overlap: usize) -> Result<Self, ChunkingError> {
+        validate_sizes(chunk_size, chunk_overlap)?;
+        Ok(Self {
+            chunk_size,
+            chunk_overlap,
+            tokenizer: Arc::new(WordTokenizer),
+        })
+    }
+
+    pub fn with_tokenizer(mut self, tokenizer: Arc<dyn Tokenizer>) -> Self {
+        self.tokenizer = tokenizer;
+        self
+    }
+}
+
+impl TextSplitter for TokenSplitter {
+    fn split_text(&self, text: &str) -> Vec<Chunk> {
+        let tokens = self.tokenizer.token_spans(text);
+        let step = self.chunk_size - self.chunk_overlap;
+
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        while start < tokens.len() {
+            let end = (start + self.chunk_size).min(tokens.len());
+            chunks.extend(trimmed_chunk(
+                text,
+                tokens[start].start..tokens[end - 1].end,
+            ));
+            if end == tokens.len() {
+                break;
+            }
+            start += step;
+        }
+        chunks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_tokenizer() {
+        let text = "Hello, world!";
+        let tokens: Vec<&str> = WordTokenizer
+            .token_spans(text)
+            .into_iter()
+            .map(|span| &text[span])
+            .collect();
+        assert_eq!(tokens, vec!["Hello", ",", "world", "!"]);
+    }
+
+    #[test]
+    fn test_token_windows_overlap() {
+        let splitter = TokenSplitter::new(3, 1).unwrap();
+        let texts: Vec<String> = splitter
+            .split_text("one two three four five six")
+            .into_iter()
+            .map(|c| c.text)
+            .collect();
+        assert_eq!(texts, vec!["one two three", "three four five", "five six"]);
+        assert!(splitter.split_text("   ").is_empty());
+    }
+}
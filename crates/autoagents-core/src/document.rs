@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::chunking::EMBEDDING_CONTEXT_KEY;
 use crate::embeddings::{Embed, EmbedError, TextEmbedder};
 
 /// Represents a piece of content along with optional metadata.
@@ -28,9 +29,19 @@ impl Document {
     }
 }
 
+/// Embeds `page_content`, preceded by the [`EMBEDDING_CONTEXT_KEY`] metadata if present.
 impl Embed for Document {
     fn embed(&self, embedder: &mut TextEmbedder) -> Result<(), EmbedError> {
-        embedder.embed(self.page_content.clone());
+        match self
+            .metadata
+            .get(EMBEDDING_CONTEXT_KEY)
+            .and_then(Value::as_str)
+        {
+            Some(context) if !context.is_empty() => {
+                embedder.embed(format!("{context}\n\n{}", self.page_content))
+            }
+            _ => embedder.embed(self.page_content.clone()),
+        }
         Ok(())
     }
 }
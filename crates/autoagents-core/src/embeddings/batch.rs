@@ -0,0 +1,368 @@
+//! Batched embedding requests
+//!
+//! [`embed_in_batches`] splits inputs so each provider call stays within the
+//! [`EmbeddingLimits`] the provider declares, runs a bounded number of calls
+//! concurrently and retries failed batches without re-sending the ones that
+//! already succeeded.
+
+use std::ops::Range;
+use std::time::Duration;
+
+use autoagents_llm::embedding::{EmbeddingLimits, EmbeddingProvider};
+use autoagents_llm::error::LLMError;
+use futures::StreamExt;
+
+use super::EmbeddingError;
+
+/// Options for [`embed_in_batches`]
+#[derive(Debug, Clone)]
+pub struct EmbeddingBatchOptions {
+    /// Extra limits applied on top of the provider's own
+    pub limits: EmbeddingLimits,
+    /// Provider calls in flight at the same time (default: 4)
+    pub concurrency: usize,
+    /// Retries per batch after a retryable error (default: 2)
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each further one (default: 200ms)
+    pub retry_backoff: Duration,
+}
+
+impl Default for EmbeddingBatchOptions {
+    fn default() -> Self {
+        Self {
+            limits: EmbeddingLimits::default(),
+            concurrency: 4,
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(200),
+        }
+    }
+}
+
+impl EmbeddingBatchOptions {
+    pub fn with_max_batch_size(mut self, max_inputs: usize) -> Self {
+        self.limits.max_inputs = Some(max_inputs);
+        self
+    }
+
+    pub fn with_max_batch_tokens(mut self, max_tokens: usize) -> Self {
+        self.limits.max_tokens = Some(max_tokens);
+        self
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+}
+
+/// Rough token count used for batch planning (about four bytes per token)
+pub fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(4).max(1)
+}
+
+/// Split `texts` into consecutive ranges that respect `limits`
+///
+/// A single text above the token limit still gets a batch of its own; the
+/// provider decides whether to truncate or reject it.
+pub fn plan_batches(texts: &[String], limits: EmbeddingLimits) -> Vec<Range<usize>> {
+    let max_inputs = limits.max_inputs.unwrap_or(usize::MAX).max(1);
+    let max_tokens = limits.max_tokens.unwrap_or(usize::MAX);
+
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut tokens = 0usize;
+    for (i, text) in texts.iter().enumerate() {
+        let text_tokens = estimate_tokens(text);
+        let full = i - start >= max_inputs || tokens.saturating_add(text_tokens) > max_tokens;
+        if i > start && full {
+            batches.push(start..i);
+            start = i;
+            tokens = 0;
+        }
+        tokens = tokens.saturating_add(text_tokens);
+    }
+    if start < texts.len() {
+        batches.push(start..texts.len());
+    }
+    batches
+}
+
+/// Embed `texts`, splitting them into batches the provider accepts
+///
+/// Vectors are returned in input order. If a batch still fails after its
+/// retries, the whole call fails with that batch's error.
+pub async fn embed_in_batches<P>(
+    provider: &P,
+    texts: Vec<String>,
+    options: &EmbeddingBatchOptions,
+) -> Result<Vec<Vec<f32>>, EmbeddingError>
+where
+    P: EmbeddingProvider + ?Sized,
+{
+    if texts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let limits = provider.embedding_limits().tightest(options.limits);
+    let batches = plan_batches(&texts, limits);
+    if batches.len() == 1 {
+        return embed_batch(provider, texts, options).await;
+    }
+
+    let texts = &texts;
+    let mut pending = futures::stream::iter(batches.into_iter().enumerate())
+        .map(|(index, range)| async move {
+            let batch = texts[range].to_vec();
+            (index, embed_batch(provider, batch, options).await)
+        })
+        .buffer_unordered(options.concurrency.max(1));
+
+    let mut results: Vec<Option<Vec<Vec<f32>>>> = Vec::new();
+    while let Some((index, result)) = pending.next().await {
+        if results.len() <= index {
+            results.resize_with(index + 1, || None);
+        }
+        results[index] = Some(result?);
+    }
+
+    Ok(results.into_iter().flatten().flatten().collect())
+}
+
+async fn embed_batch<P>(
+    provider: &P,
+    batch: Vec<String>,
+    options: &EmbeddingBatchOptions,
+) -> Result<Vec<Vec<f32>>, EmbeddingError>
+where
+    P: EmbeddingProvider + ?Sized,
+{
+    let expected = batch.len();
+    let mut attempt = 0u32;
+    loop {
+        match provider.embed(batch.clone()).await {
+            Ok(vectors) if vectors.len() < expected => {
+                return Err(EmbeddingError::EmbedFailure(format!(
+                    "embedding provider returned fewer vectors than expected ({} for {} inputs)",
+                    vectors.len(),
+                    expected
+                )));
+            }
+            Ok(mut vectors) => {
+                // Extra vectors would shift every later batch out of place
+                vectors.truncate(expected);
+                return Ok(vectors);
+            }
+            Err(err) if attempt < options.max_retries && err.is_retryable() => {
+                let retry_after = match &err {
+                    LLMError::RateLimitError { retry_after, .. }
+                    | LLMError::HttpStatusError { retry_after, .. } => *retry_after,
+                    _ => None,
+                };
+                let delay = retry_delay(options.retry_backoff, attempt, retry_after);
+                log::warn!(
+                    "Embedding batch of {expected} inputs failed (attempt {}): {err}. Retrying in {delay:?}.",
+                    attempt + 1
+                );
+                sleep(delay).await;
+                attempt += 1;
+            }
+            Err(err) => return Err(EmbeddingError::Provider(err)),
+        }
+    }
+}
+
+/// Exponential backoff from `backoff` for retry `attempt` (0-based); a longer
+/// `retry_after` takes precedence
+pub(crate) fn retry_delay(
+    backoff: Duration,
+    attempt: u32,
+    retry_after: Option<Duration>,
+) -> Duration {
+    let backoff = backoff.saturating_mul(2u32.saturating_pow(attempt));
+    retry_after.map_or(backoff, |after| after.max(backoff))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sleep(delay: Duration) {
+    tokio::time::sleep(delay).await;
+}
+
+// No timer is available without tokio, so retries are immediate in browsers
+#[cfg(target_arch = "wasm32")]
+pub(crate) async fn sleep(_delay: Duration) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    struct LimitedProvider {
+        limits: EmbeddingLimits,
+        calls: Mutex<Vec<Vec<String>>>,
+        failures: Mutex<Vec<(String, LLMError)>>,
+    }
+
+    impl LimitedProvider {
+        fn new(limits: EmbeddingLimits) -> Self {
+            Self {
+                limits,
+                calls: Mutex::new(Vec::new()),
+                failures: Mutex::new(Vec::new()),
+            }
+        }
+
+        /// Fail the next call whose batch starts with `text`
+        fn fail_once(self, text: &str, err: LLMError) -> Self {
+            self.failures.lock().unwrap().push((text.to_string(), err));
+            self
+        }
+
+        fn calls(&self) -> Vec<Vec<String>> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for LimitedProvider {
+        async fn embed(&self, input: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
+            self.calls.lock().unwrap().push(input.clone());
+            let mut failures = self.failures.lock().unwrap();
+            if let Some(pos) = failures.iter().position(|(t, _)| *t == input[0]) {
+                return Err(failures.remove(pos).1);
+            }
+            if let Some(max) = self.limits.max_inputs {
+                assert!(input.len() <= max, "batch of {} over limit", input.len());
+            }
+            Ok(input.iter().map(|t| vec![t.len() as f32]).collect())
+        }
+
+        fn embedding_limits(&self) -> EmbeddingLimits {
+            self.limits
+        }
+    }
+
+    fn texts(n: usize) -> Vec<String> {
+        (0..n).map(|i| "x".repeat(i + 1)).collect()
+    }
+
+    fn rate_limited() -> LLMError {
+        LLMError::RateLimitError {
+            status_code: 429,
+            message: "slow down".to_string(),
+            response_body: "".into(),
+            retry_after: None,
+            provider_code: None,
+        }
+    }
+
+    fn fast_retries() -> EmbeddingBatchOptions {
+        EmbeddingBatchOptions::default().with_retry_backoff(Duration::from_millis(1))
+    }
+
+    #[test]
+    fn test_plan_batches_respects_limits() {
+        let items = vec!["a".repeat(8), "b".repeat(8), "c".repeat(40), "d".repeat(4)];
+        let by_count = plan_batches(
+            &items,
+            EmbeddingLimits {
+                max_inputs: Some(3),
+                max_tokens: None,
+            },
+        );
+        assert_eq!(by_count, vec![0..3, 3..4]);
+
+        let by_tokens = plan_batches(
+            &items,
+            EmbeddingLimits {
+                max_inputs: None,
+                max_tokens: Some(5),
+            },
+        );
+        assert_eq!(by_tokens, vec![0..2, 2..3, 3..4]);
+
+        assert_eq!(plan_batches(&items, EmbeddingLimits::default()), vec![0..4]);
+        assert!(plan_batches(&[], EmbeddingLimits::default()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_splits_on_provider_limits_and_keeps_order() {
+        let provider = LimitedProvider::new(EmbeddingLimits {
+            max_inputs: Some(2),
+            max_tokens: None,
+        });
+        let vectors = embed_in_batches(&provider, texts(5), &EmbeddingBatchOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            vectors,
+            vec![vec![1.0], vec![2.0], vec![3.0], vec![4.0], vec![5.0]]
+        );
+        assert_eq!(provider.calls().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_option_limits_tighten_provider_limits() {
+        let provider = LimitedProvider::new(EmbeddingLimits::default());
+        let options = EmbeddingBatchOptions::default().with_max_batch_size(1);
+        embed_in_batches(&provider, texts(3), &options)
+            .await
+            .unwrap();
+        assert!(provider.calls().iter().all(|batch| batch.len() == 1));
+    }
+
+    #[tokio::test]
+    async fn test_retries_only_failed_batch() {
+        let provider = LimitedProvider::new(EmbeddingLimits {
+            max_inputs: Some(2),
+            max_tokens: None,
+        })
+        .fail_once("xxx", rate_limited());
+
+        let vectors = embed_in_batches(&provider, texts(4), &fast_retries())
+            .await
+            .unwrap();
+        assert_eq!(vectors.len(), 4);
+
+        let calls = provider.calls();
+        assert_eq!(calls.len(), 3);
+        let first_batch = calls.iter().filter(|b| b[0] == "x").count();
+        let retried_batch = calls.iter().filter(|b| b[0] == "xxx").count();
+        assert_eq!((first_batch, retried_batch), (1, 2));
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_error_fails_fast() {
+        let provider = LimitedProvider::new(EmbeddingLimits::default())
+            .fail_once("x", LLMError::invalid_request("input too long"));
+        let err = embed_in_batches(&provider, texts(2), &fast_retries())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EmbeddingError::Provider(_)));
+        assert_eq!(provider.calls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let provider = LimitedProvider::new(EmbeddingLimits::default())
+            .fail_once("x", rate_limited())
+            .fail_once("x", rate_limited());
+        let options = fast_retries().with_max_retries(1);
+        assert!(
+            embed_in_batches(&provider, texts(1), &options)
+                .await
+                .is_err()
+        );
+        assert_eq!(provider.calls().len(), 2);
+    }
+}
@@ -0,0 +1,565 @@
+//! Content-addressed embedding cache
+//!
+//! [`CachedEmbeddingProvider`] wraps any [`EmbeddingProvider`] and only sends
+//! texts it has not seen before to the wrapped provider. Entries are keyed by
+//! the SHA-256 of a namespace and the text, so unchanged documents are never
+//! re-embedded across ingests when a persistent [`EmbeddingCacheStore`] such
+//! as [`DiskEmbeddingCache`] is used.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use autoagents_llm::embedding::{EmbeddingLimits, EmbeddingProvider};
+use autoagents_llm::error::LLMError;
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+
+use super::{EmbeddingError, SharedEmbeddingProvider};
+
+/// A cached vector and the time it was stored
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedEmbedding {
+    pub vector: Vec<f32>,
+    /// Milliseconds since the Unix epoch
+    pub created_at: u64,
+}
+
+/// Storage backend for [`CachedEmbeddingProvider`]
+#[async_trait]
+pub trait EmbeddingCacheStore: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<CachedEmbedding>, EmbeddingError>;
+
+    async fn put(&self, key: &str, entry: CachedEmbedding) -> Result<(), EmbeddingError>;
+
+    /// Remove one entry, returning whether it existed
+    async fn remove(&self, key: &str) -> Result<bool, EmbeddingError>;
+
+    /// Remove entries created before `created_before`, returning how many were removed
+    async fn remove_older_than(&self, created_before: u64) -> Result<usize, EmbeddingError>;
+
+    async fn clear(&self) -> Result<(), EmbeddingError>;
+}
+
+/// Process-local cache store
+#[derive(Debug, Default)]
+pub struct InMemoryEmbeddingCache {
+    entries: RwLock<HashMap<String, CachedEmbedding>>,
+}
+
+impl InMemoryEmbeddingCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn len(&self) -> usize {
+        self.entries.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.entries.read().await.is_empty()
+    }
+}
+
+#[async_trait]
+impl EmbeddingCacheStore for InMemoryEmbeddingCache {
+    async fn get(&self, key: &str) -> Result<Option<CachedEmbedding>, EmbeddingError> {
+        Ok(self.entries.read().await.get(key).cloned())
+    }
+
+    async fn put(&self, key: &str, entry: CachedEmbedding) -> Result<(), EmbeddingError> {
+        self.entries.write().await.insert(key.to_string(), entry);
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<bool, EmbeddingError> {
+        Ok(self.entries.write().await.remove(key).is_some())
+    }
+
+    async fn remove_older_than(&self, created_before: u64) -> Result<usize, EmbeddingError> {
+        let mut entries = self.entries.write().await;
+        let before = entries.len();
+        entries.retain(|_, entry| entry.created_at >= created_before);
+        Ok(before - entries.len())
+    }
+
+    async fn clear(&self) -> Result<(), EmbeddingError> {
+        self.entries.write().await.clear();
+        Ok(())
+    }
+}
+
+/// Cache store that keeps one file per entry under a directory
+///
+/// Files are sharded by the first two hex characters of the key and hold the
+/// creation time followed by the little-endian `f32` vector. Writes go through
+/// a temporary file and a rename, so readers never see partial entries.
+#[derive(Debug, Clone)]
+pub struct DiskEmbeddingCache {
+    root: PathBuf,
+}
+
+const DISK_ENTRY_EXTENSION: &str = "emb";
+
+impl DiskEmbeddingCache {
+    /// Open (and create if needed) a cache rooted at `root`
+    pub async fn open(root: impl Into<PathBuf>) -> Result<Self, EmbeddingError> {
+        let root = root.into();
+        tokio::fs::create_dir_all(&root)
+            .await
+            .map_err(cache_error)?;
+        Ok(Self { root })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn entry_path(&self, key: &str) -> Result<PathBuf, EmbeddingError> {
+        if key.len() < 3 || !key.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(EmbeddingError::Cache(format!("invalid cache key '{key}'")));
+        }
+        let (shard, name) = key.split_at(2);
+        Ok(self
+            .root
+            .join(shard)
+            .join(format!("{name}.{DISK_ENTRY_EXTENSION}")))
+    }
+
+    fn encode(entry: &CachedEmbedding) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + entry.vector.len() * 4);
+        bytes.extend_from_slice(&entry.created_at.to_le_bytes());
+        for value in &entry.vector {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Option<CachedEmbedding> {
+        if bytes.len() < 8 || !(bytes.len() - 8).is_multiple_of(4) {
+            return None;
+        }
+        let (header, body) = bytes.split_at(8);
+        let created_at = u64::from_le_bytes(header.try_into().ok()?);
+        let vector = body
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+        Some(CachedEmbedding { vector, created_at })
+    }
+
+    async fn entry_files(&self) -> Result<Vec<PathBuf>, EmbeddingError> {
+        let mut files = Vec::new();
+        let mut shards = tokio::fs::read_dir(&self.root).await.map_err(cache_error)?;
+        while let Some(shard) = shards.next_entry().await.map_err(cache_error)? {
+            if !shard.file_type().await.map_err(cache_error)?.is_dir() {
+                continue;
+            }
+            let mut entries = tokio::fs::read_dir(shard.path())
+                .await
+                .map_err(cache_error)?;
+            while let Some(entry) = entries.next_entry().await.map_err(cache_error)? {
+                let path = entry.path();
+                if path
+                    .extension()
+                    .is_some_and(|ext| ext == DISK_ENTRY_EXTENSION)
+                {
+                    files.push(path);
+                }
+            }
+        }
+        Ok(files)
+    }
+}
+
+#[async_trait]
+impl EmbeddingCacheStore for DiskEmbeddingCache {
+    async fn get(&self, key: &str) -> Result<Option<CachedEmbedding>, EmbeddingError> {
+        let path = self.entry_path(key)?;
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => Ok(Self::decode(&bytes)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(cache_error(err)),
+        }
+    }
+
+    async fn put(&self, key: &str, entry: CachedEmbedding) -> Result<(), EmbeddingError> {
+        let path = self.entry_path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(cache_error)?;
+        }
+        let tmp = path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+        tokio::fs::write(&tmp, Self::encode(&entry))
+            .await
+            .map_err(cache_error)?;
+        if let Err(err) = tokio::fs::rename(&tmp, &path).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(cache_error(err));
+        }
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<bool, EmbeddingError> {
+        let path = self.entry_path(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(cache_error(err)),
+        }
+    }
+
+    async fn remove_older_than(&self, created_before: u64) -> Result<usize, EmbeddingError> {
+        let mut removed = 0;
+        for path in self.entry_files().await? {
+            let bytes = tokio::fs::read(&path).await.map_err(cache_error)?;
+            // Unreadable entries are dropped along with expired ones
+            let expired = Self::decode(&bytes).is_none_or(|e| e.created_at < created_before);
+            if expired {
+                tokio::fs::remove_file(&path).await.map_err(cache_error)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    async fn clear(&self) -> Result<(), EmbeddingError> {
+        for path in self.entry_files().await? {
+            tokio::fs::remove_file(&path).await.map_err(cache_error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Hit and miss counters of a [`CachedEmbeddingProvider`]
+///
+/// Counts are per input text, not per `embed` call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmbeddingCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl EmbeddingCacheStats {
+    /// Fraction of lookups served from the cache, or 0 before any lookup
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// [`EmbeddingProvider`] that serves repeated texts from an [`EmbeddingCacheStore`]
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use std::time::Duration;
+/// # use autoagents_core::embeddings::SharedEmbeddingProvider;
+/// use autoagents_core::embeddings::cache::{CachedEmbeddingProvider, DiskEmbeddingCache};
+///
+/// # async fn run(provider: SharedEmbeddingProvider) -> Result<(), Box<dyn std::error::Error>> {
+/// let store = DiskEmbeddingCache::open(".embedding_cache").await?;
+/// let cached = CachedEmbeddingProvider::new(provider, Arc::new(store))
+///     .with_namespace("text-embedding-3-small")
+///     .with_ttl(Duration::from_secs(7 * 24 * 60 * 60));
+/// let provider: SharedEmbeddingProvider = Arc::new(cached);
+/// # Ok(())
+/// # }
+/// ```
+pub struct CachedEmbeddingProvider {
+    inner: SharedEmbeddingProvider,
+    store: Arc<dyn EmbeddingCacheStore>,
+    namespace: String,
+    ttl: Option<Duration>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl CachedEmbeddingProvider {
+    pub fn new(inner: SharedEmbeddingProvider, store: Arc<dyn EmbeddingCacheStore>) -> Self {
+        Self {
+            inner,
+            store,
+            namespace: String::new(),
+            ttl: None,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Use a process-local [`InMemoryEmbeddingCache`]
+    pub fn in_memory(inner: SharedEmbeddingProvider) -> Self {
+        Self::new(inner, Arc::new(InMemoryEmbeddingCache::new()))
+    }
+
+    /// Scope cache keys, typically to the model name, so a shared store never
+    /// returns vectors produced by a different model
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+
+    /// Treat entries older than `ttl` as misses
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    pub fn stats(&self) -> EmbeddingCacheStats {
+        EmbeddingCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn reset_stats(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    /// Cache key for `text` in this provider's namespace
+    pub fn cache_key(&self, text: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.namespace.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(text.as_bytes());
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    /// Drop the cached vector for `text`, returning whether one existed
+    pub async fn invalidate(&self, text: &str) -> Result<bool, EmbeddingError> {
+        self.store.remove(&self.cache_key(text)).await
+    }
+
+    /// Drop every entry in the underlying store
+    pub async fn invalidate_all(&self) -> Result<(), EmbeddingError> {
+        self.store.clear().await
+    }
+
+    /// Remove entries older than the configured TTL; a no-op without a TTL
+    pub async fn purge_expired(&self) -> Result<usize, EmbeddingError> {
+        match self.ttl {
+            Some(ttl) => {
+                let cutoff = now_millis().saturating_sub(ttl.as_millis() as u64);
+                self.store.remove_older_than(cutoff).await
+            }
+            None => Ok(0),
+        }
+    }
+
+    fn is_fresh(&self, entry: &CachedEmbedding, now: u64) -> bool {
+        self.ttl
+            .is_none_or(|ttl| now.saturating_sub(entry.created_at) < ttl.as_millis() as u64)
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for CachedEmbeddingProvider {
+    async fn embed(&self, input: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
+        let now = now_millis();
+        let keys: Vec<String> = input.iter().map(|text| self.cache_key(text)).collect();
+        let mut vectors: Vec<Option<Vec<f32>>> = Vec::with_capacity(input.len());
+        for key in &keys {
+            // A failing store degrades to a miss instead of failing the embed
+            let cached = match self.store.get(key).await {
+                Ok(entry) => entry.filter(|entry| self.is_fresh(entry, now)),
+                Err(err) => {
+                    log::warn!("Embedding cache lookup failed: {err}");
+                    None
+                }
+            };
+            vectors.push(cached.map(|entry| entry.vector));
+        }
+
+        let hits = vectors.iter().filter(|v| v.is_some()).count() as u64;
+        self.hits.fetch_add(hits, Ordering::Relaxed);
+        self.misses
+            .fetch_add(input.len() as u64 - hits, Ordering::Relaxed);
+
+        // Embed each distinct missing text once
+        let mut seen = HashSet::new();
+        let missing: Vec<usize> = (0..input.len())
+            .filter(|&i| vectors[i].is_none() && seen.insert(&keys[i]))
+            .collect();
+        if !missing.is_empty() {
+            let texts = missing.iter().map(|&i| input[i].clone()).collect();
+            let fresh = self.inner.embed(texts).await?;
+            if fresh.len() != missing.len() {
+                return Err(LLMError::ProviderError(format!(
+                    "Embedding provider returned {} vectors for {} inputs",
+                    fresh.len(),
+                    missing.len()
+                )));
+            }
+
+            let mut by_key = HashMap::with_capacity(missing.len());
+            for (&i, vector) in missing.iter().zip(fresh) {
+                let entry = CachedEmbedding {
+                    vector: vector.clone(),
+                    created_at: now,
+                };
+                if let Err(err) = self.store.put(&keys[i], entry).await {
+                    log::warn!("Embedding cache write failed: {err}");
+                }
+                by_key.insert(keys[i].as_str(), vector);
+            }
+            for (slot, key) in vectors.iter_mut().zip(&keys) {
+                if slot.is_none() {
+                    *slot = by_key.get(key.as_str()).cloned();
+                }
+            }
+        }
+
+        Ok(vectors.into_iter().flatten().collect())
+    }
+
+    fn embedding_limits(&self) -> EmbeddingLimits {
+        self.inner.embedding_limits()
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn cache_error(err: std::io::Error) -> EmbeddingError {
+    EmbeddingError::Cache(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingProvider {
+        calls: Mutex<Vec<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for RecordingProvider {
+        async fn embed(&self, input: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
+            self.calls.lock().unwrap().push(input.clone());
+            Ok(input.iter().map(|t| vec![t.len() as f32, 1.0]).collect())
+        }
+    }
+
+    fn texts(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_cache_hits_skip_provider() {
+        let inner = Arc::new(RecordingProvider::default());
+        let cached = CachedEmbeddingProvider::in_memory(inner.clone());
+
+        let first = cached.embed(texts(&["a", "bb", "a"])).await.unwrap();
+        assert_eq!(first, vec![vec![1.0, 1.0], vec![2.0, 1.0], vec![1.0, 1.0]]);
+        let second = cached.embed(texts(&["bb", "ccc"])).await.unwrap();
+        assert_eq!(second, vec![vec![2.0, 1.0], vec![3.0, 1.0]]);
+
+        let calls = inner.calls.lock().unwrap().clone();
+        assert_eq!(calls, vec![texts(&["a", "bb"]), texts(&["ccc"])]);
+        assert_eq!(cached.stats(), EmbeddingCacheStats { hits: 1, misses: 4 });
+        assert!((cached.stats().hit_rate() - 0.2).abs() < 1e-9);
+
+        cached.reset_stats();
+        assert_eq!(cached.stats(), EmbeddingCacheStats::default());
+    }
+
+    #[tokio::test]
+    async fn test_namespace_and_invalidation() {
+        let inner = Arc::new(RecordingProvider::default());
+        let store: Arc<dyn EmbeddingCacheStore> = Arc::new(InMemoryEmbeddingCache::new());
+        let model_a =
+            CachedEmbeddingProvider::new(inner.clone(), store.clone()).with_namespace("a");
+        let model_b = CachedEmbeddingProvider::new(inner.clone(), store).with_namespace("b");
+        assert_ne!(model_a.cache_key("x"), model_b.cache_key("x"));
+
+        model_a.embed(texts(&["x"])).await.unwrap();
+        model_b.embed(texts(&["x"])).await.unwrap();
+        assert_eq!(inner.calls.lock().unwrap().len(), 2);
+
+        assert!(model_a.invalidate("x").await.unwrap());
+        assert!(!model_a.invalidate("x").await.unwrap());
+        model_a.embed(texts(&["x"])).await.unwrap();
+        model_b.embed(texts(&["x"])).await.unwrap();
+        assert_eq!(inner.calls.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_ttl_expires_entries() {
+        let inner = Arc::new(RecordingProvider::default());
+        let store = Arc::new(InMemoryEmbeddingCache::new());
+        store
+            .put(
+                &CachedEmbeddingProvider::in_memory(inner.clone()).cache_key("old"),
+                CachedEmbedding {
+                    vector: vec![9.0],
+                    created_at: 0,
+                },
+            )
+            .await
+            .unwrap();
+        let cached = CachedEmbeddingProvider::new(inner.clone(), store.clone())
+            .with_ttl(Duration::from_secs(60));
+
+        assert_eq!(
+            cached.embed(texts(&["old"])).await.unwrap(),
+            vec![vec![3.0, 1.0]]
+        );
+        assert_eq!(cached.stats().misses, 1);
+
+        store
+            .put(
+                "stale",
+                CachedEmbedding {
+                    vector: vec![1.0],
+                    created_at: 0,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(cached.purge_expired().await.unwrap(), 1);
+        assert_eq!(store.len().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache_persists_between_instances() {
+        let dir = tempfile::tempdir().unwrap();
+        let inner = Arc::new(RecordingProvider::default());
+
+        let store = DiskEmbeddingCache::open(dir.path()).await.unwrap();
+        let cached = CachedEmbeddingProvider::new(inner.clone(), Arc::new(store));
+        cached.embed(texts(&["persist me"])).await.unwrap();
+
+        let store = DiskEmbeddingCache::open(dir.path()).await.unwrap();
+        let reopened = CachedEmbeddingProvider::new(inner.clone(), Arc::new(store.clone()));
+        assert_eq!(
+            reopened.embed(texts(&["persist me"])).await.unwrap(),
+            vec![vec![10.0, 1.0]]
+        );
+        assert_eq!(inner.calls.lock().unwrap().len(), 1);
+        assert_eq!(reopened.stats().hits, 1);
+
+        let key = reopened.cache_key("persist me");
+        assert_eq!(store.remove_older_than(0).await.unwrap(), 0);
+        assert_eq!(store.remove_older_than(u64::MAX).await.unwrap(), 1);
+        assert!(store.get(&key).await.unwrap().is_none());
+        assert!(store.get("not-hex").await.is_err());
+    }
+}
@@ -0,0 +1,113 @@
+//! Image embeddings
+//!
+//! Documents implementing [`EmbedImage`] hand encoded images to an
+//! [`ImageEmbedder`], and an [`ImageEmbeddingProvider`] turns them into
+//! vectors. With a multimodal model such as CLIP the image vectors share a
+//! space with the text vectors of the matching text provider, so a single
+//! collection can hold both and be searched with text queries.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use autoagents_llm::embedding::ImageEmbeddingProvider;
+use autoagents_llm::error::LLMError;
+
+use super::{EmbedError, EmbeddingTransform};
+
+pub type SharedImageEmbeddingProvider = Arc<dyn ImageEmbeddingProvider + Send + Sync>;
+
+/// Collects the encoded images a document wants embedded
+#[derive(Debug, Default)]
+pub struct ImageEmbedder {
+    parts: Vec<Vec<u8>>,
+}
+
+impl ImageEmbedder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an encoded image (PNG, JPEG, WebP, ...)
+    pub fn embed(&mut self, image: impl Into<Vec<u8>>) {
+        self.parts.push(image.into());
+    }
+
+    pub fn len(&self) -> usize {
+        self.parts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    pub fn parts(&self) -> &[Vec<u8>] {
+        &self.parts
+    }
+
+    pub fn into_parts(self) -> Vec<Vec<u8>> {
+        self.parts
+    }
+}
+
+/// Image counterpart of [`Embed`](super::Embed)
+pub trait EmbedImage {
+    fn embed_image(&self, embedder: &mut ImageEmbedder) -> Result<(), EmbedError>;
+}
+
+/// [`ImageEmbeddingProvider`] that applies an [`EmbeddingTransform`] to every vector
+pub struct TransformedImageEmbeddingProvider {
+    inner: SharedImageEmbeddingProvider,
+    transform: EmbeddingTransform,
+}
+
+impl TransformedImageEmbeddingProvider {
+    pub fn new(inner: SharedImageEmbeddingProvider, transform: EmbeddingTransform) -> Self {
+        Self { inner, transform }
+    }
+}
+
+#[async_trait]
+impl ImageEmbeddingProvider for TransformedImageEmbeddingProvider {
+    async fn embed_images(&self, images: Vec<Vec<u8>>) -> Result<Vec<Vec<f32>>, LLMError> {
+        let vectors = self.inner.embed_images(images).await?;
+        self.transform.apply_all(vectors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct LengthProvider;
+
+    #[async_trait]
+    impl ImageEmbeddingProvider for LengthProvider {
+        async fn embed_images(&self, images: Vec<Vec<u8>>) -> Result<Vec<Vec<f32>>, LLMError> {
+            Ok(images
+                .iter()
+                .map(|image| vec![image.len() as f32, 0.0, 0.0])
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_image_embedder_collects_parts() {
+        let mut embedder = ImageEmbedder::new();
+        embedder.embed(vec![1u8, 2, 3]);
+        embedder.embed(&b"png"[..]);
+        assert_eq!(embedder.len(), 2);
+        assert_eq!(embedder.into_parts(), vec![vec![1, 2, 3], b"png".to_vec()]);
+    }
+
+    #[tokio::test]
+    async fn test_transformed_image_provider() {
+        let provider = TransformedImageEmbeddingProvider::new(
+            Arc::new(LengthProvider),
+            EmbeddingTransform::new()
+                .with_dimensions(2)
+                .with_normalize(true),
+        );
+        let vectors = provider.embed_images(vec![vec![0; 5]]).await.unwrap();
+        assert_eq!(vectors, vec![vec![1.0, 0.0]]);
+    }
+}
@@ -6,7 +6,18 @@ use serde::{Deserialize, Serialize};
 
 use crate::one_or_many::OneOrMany;
 
+pub mod batch;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cache;
 pub mod distance;
+pub mod image;
+pub mod transform;
+
+pub use batch::{EmbeddingBatchOptions, embed_in_batches};
+pub use image::{
+    EmbedImage, ImageEmbedder, SharedImageEmbeddingProvider, TransformedImageEmbeddingProvider,
+};
+pub use transform::{EmbeddingTransform, TransformedEmbeddingProvider};
 
 pub type SharedEmbeddingProvider = Arc<dyn EmbeddingProvider + Send + Sync>;
 pub type VecArc = Arc<[f32]>;
@@ -38,6 +49,9 @@ pub enum EmbeddingError {
 
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    #[error("Embedding cache error: {0}")]
+    Cache(String),
 }
 
 #[derive(Debug, Default)]
@@ -92,6 +106,7 @@ impl Embed for String {
 pub struct EmbeddingsBuilder<T> {
     provider: SharedEmbeddingProvider,
     documents: Vec<T>,
+    batch_options: EmbeddingBatchOptions,
 }
 
 impl<T> EmbeddingsBuilder<T>
@@ -102,9 +117,16 @@ where
         Self {
             provider,
             documents: Vec::default(),
+            batch_options: EmbeddingBatchOptions::default(),
         }
     }
 
+    /// Control how texts are split into provider requests
+    pub fn batch_options(mut self, options: EmbeddingBatchOptions) -> Self {
+        self.batch_options = options;
+        self
+    }
+
     pub fn documents(mut self, docs: impl IntoIterator<Item = T>) -> Result<Self, EmbeddingError> {
         self.documents.extend(docs);
         if self.documents.is_empty() {
@@ -136,12 +158,8 @@ where
             ranges.push((start, count));
         }
 
-        let text_copy = texts.clone();
-        let vectors = self
-            .provider
-            .embed(text_copy)
-            .await
-            .map_err(EmbeddingError::Provider)?;
+        let vectors =
+            embed_in_batches(self.provider.as_ref(), texts.clone(), &self.batch_options).await?;
 
         let mut cursor = 0usize;
         let mut results = Vec::with_capacity(self.documents.len());
@@ -0,0 +1,196 @@
+//! Dimension truncation and normalization of embedding vectors
+//!
+//! Matryoshka-trained models (OpenAI `text-embedding-3-*`, nomic v1.5, ...)
+//! keep most of their quality when vectors are cut to a prefix, which shrinks
+//! storage and speeds up search. Truncated vectors must be L2-normalized again,
+//! and query vectors must go through exactly the same steps as the documents
+//! they are compared against. [`TransformedEmbeddingProvider`] applies one
+//! [`EmbeddingTransform`] to everything a provider returns, and vector stores
+//! record the transform next to the collection so later searches match.
+
+use async_trait::async_trait;
+use autoagents_llm::embedding::{EmbeddingLimits, EmbeddingProvider};
+use autoagents_llm::error::LLMError;
+use serde::{Deserialize, Serialize};
+
+use super::SharedEmbeddingProvider;
+
+/// Post-processing applied to every vector a provider returns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingTransform {
+    /// Keep only the first `dimensions` components
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<usize>,
+    /// Scale vectors to unit length after truncation
+    #[serde(default)]
+    pub normalize: bool,
+}
+
+impl EmbeddingTransform {
+    /// Key under which vector stores persist the transform in collection metadata
+    pub const METADATA_KEY: &'static str = "embedding_transform";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_dimensions(mut self, dimensions: usize) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Whether the transform leaves vectors unchanged
+    pub fn is_identity(&self) -> bool {
+        self.dimensions.is_none() && !self.normalize
+    }
+
+    pub fn apply(&self, mut vector: Vec<f32>) -> Vec<f32> {
+        if let Some(dimensions) = self.dimensions {
+            vector.truncate(dimensions);
+        }
+        if self.normalize {
+            l2_normalize(&mut vector);
+        }
+        vector
+    }
+
+    /// Apply the transform to a batch of provider output
+    ///
+    /// Fails if a vector is shorter than the requested dimensions, since
+    /// padding it would silently mix incompatible vectors in one collection.
+    pub fn apply_all(&self, vectors: Vec<Vec<f32>>) -> Result<Vec<Vec<f32>>, LLMError> {
+        if self.is_identity() {
+            return Ok(vectors);
+        }
+        if let Some(dimensions) = self.dimensions
+            && let Some(short) = vectors.iter().find(|v| v.len() < dimensions)
+        {
+            return Err(LLMError::invalid_request(format!(
+                "cannot truncate {}-dimensional embeddings to {dimensions} dimensions",
+                short.len()
+            )));
+        }
+        Ok(vectors
+            .into_iter()
+            .map(|vector| self.apply(vector))
+            .collect())
+    }
+
+    pub fn to_metadata(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    pub fn from_metadata(value: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value.clone())
+    }
+}
+
+/// Scale `vector` to unit length; zero vectors are left as they are
+pub fn l2_normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > f32::EPSILON {
+        for value in vector.iter_mut() {
+            *value /= norm;
+        }
+    }
+}
+
+/// [`EmbeddingProvider`] that applies an [`EmbeddingTransform`] to every vector
+pub struct TransformedEmbeddingProvider {
+    inner: SharedEmbeddingProvider,
+    transform: EmbeddingTransform,
+}
+
+impl TransformedEmbeddingProvider {
+    pub fn new(inner: SharedEmbeddingProvider, transform: EmbeddingTransform) -> Self {
+        Self { inner, transform }
+    }
+
+    pub fn transform(&self) -> EmbeddingTransform {
+        self.transform
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for TransformedEmbeddingProvider {
+    async fn embed(&self, input: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
+        let vectors = self.inner.embed(input).await?;
+        self.transform.apply_all(vectors)
+    }
+
+    fn embedding_limits(&self) -> EmbeddingLimits {
+        self.inner.embedding_limits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    struct FixedProvider;
+
+    #[async_trait]
+    impl EmbeddingProvider for FixedProvider {
+        async fn embed(&self, input: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
+            Ok(input.iter().map(|_| vec![3.0, 4.0, 12.0]).collect())
+        }
+    }
+
+    #[test]
+    fn test_apply_truncates_then_normalizes() {
+        let transform = EmbeddingTransform::new()
+            .with_dimensions(2)
+            .with_normalize(true);
+        assert_eq!(transform.apply(vec![3.0, 4.0, 12.0]), vec![0.6, 0.8]);
+        assert_eq!(
+            EmbeddingTransform::new().apply(vec![3.0, 4.0]),
+            vec![3.0, 4.0]
+        );
+
+        let mut zero = vec![0.0, 0.0];
+        l2_normalize(&mut zero);
+        assert_eq!(zero, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_metadata_round_trip() {
+        let transform = EmbeddingTransform::new()
+            .with_dimensions(256)
+            .with_normalize(true);
+        let value = transform.to_metadata();
+        assert_eq!(
+            value,
+            serde_json::json!({ "dimensions": 256, "normalize": true })
+        );
+        assert_eq!(
+            EmbeddingTransform::from_metadata(&value).unwrap(),
+            transform
+        );
+        assert_eq!(
+            EmbeddingTransform::from_metadata(&serde_json::json!({})).unwrap(),
+            EmbeddingTransform::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_provider_applies_transform() {
+        let provider = TransformedEmbeddingProvider::new(
+            Arc::new(FixedProvider),
+            EmbeddingTransform::new().with_dimensions(2),
+        );
+        let vectors = provider.embed(vec!["a".to_string()]).await.unwrap();
+        assert_eq!(vectors, vec![vec![3.0, 4.0]]);
+
+        let too_long = TransformedEmbeddingProvider::new(
+            Arc::new(FixedProvider),
+            EmbeddingTransform::new().with_dimensions(8),
+        );
+        assert!(too_long.embed(vec!["a".to_string()]).await.is_err());
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use autoagents_llm::LLMProvider;
+use autoagents_llm::chat::ChatMessage;
+use serde::Deserialize;
+
+use super::{Entity, GraphError, Relation, Subgraph};
+
+const EXTRACTION_PROMPT: &str = "Extract the entities and the relations between them from the \
+text below. Reply with JSON only, in this shape:\n\
+{\"entities\": [{\"name\": \"...\", \"type\": \"...\", \"description\": \"...\"}], \
+\"relations\": [{\"source\": \"...\", \"relation\": \"...\", \"target\": \"...\"}]}\n\
+Use the entity names exactly as in the entity list for relation sources and targets. \
+Keep descriptions to one sentence.";
+
+#[derive(Deserialize)]
+struct Extraction {
+    #[serde(default)]
+    entities: Vec<ExtractedEntity>,
+    #[serde(default)]
+    relations: Vec<ExtractedRelation>,
+}
+
+#[derive(Deserialize)]
+struct ExtractedEntity {
+    name: String,
+    #[serde(default, rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ExtractedRelation {
+    source: String,
+    relation: String,
+    target: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+/// Extracts entities and relations from text with an LLM
+pub struct GraphExtractor {
+    llm: Arc<dyn LLMProvider>,
+    entity_types: Vec<String>,
+}
+
+impl GraphExtractor {
+    pub fn new(llm: Arc<dyn LLMProvider>) -> Self {
+        Self {
+            llm,
+            entity_types: Vec::new(),
+        }
+    }
+
+    /// Limit extraction to these entity types, e.g. `["person", "organization"]`
+    pub fn with_entity_types<I, S>(mut self, entity_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.entity_types = entity_types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub async fn extract(&self, text: &str) -> Result<Subgraph, GraphError> {
+        let mut prompt = EXTRACTION_PROMPT.to_string();
+        if !self.entity_types.is_empty() {
+            prompt.push_str(&format!(
+                "\nOnly extract entities of these types: {}.",
+                self.entity_types.join(", ")
+            ));
+        }
+        prompt.push_str("\n\nText:\n");
+        prompt.push_str(text);
+
+        let response = self
+            .llm
+            .chat(&[ChatMessage::user().content(prompt).build()], None)
+            .await
+            .map_err(|err| GraphError::Extraction(err.to_string()))?;
+        parse_extraction(&response.text().unwrap_or_default())
+    }
+}
+
+/// Parse the model's reply, tolerating code fences and text around the JSON object
+fn parse_extraction(reply: &str) -> Result<Subgraph, GraphError> {
+    let json = match (reply.find('{'), reply.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &reply[start..=end],
+        _ => return Err(GraphError::Parse("no JSON object in reply".to_string())),
+    };
+    let extraction: Extraction =
+        serde_json::from_str(json).map_err(|err| GraphError::Parse(err.to_string()))?;
+
+    let non_empty = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let entities: Vec<Entity> = extraction
+        .entities
+        .into_iter()
+        .filter(|entity| !entity.name.trim().is_empty())
+        .map(|entity| Entity {
+            name: entity.name.trim().to_string(),
+            kind: non_empty(entity.kind).map(|kind| kind.to_lowercase()),
+            description: non_empty(entity.description),
+        })
+        .collect();
+    let mut seen = HashSet::new();
+    let relations = extraction
+        .relations
+        .into_iter()
+        .filter(|relation| {
+            !relation.source.trim().is_empty()
+                && !relation.target.trim().is_empty()
+                && !relation.relation.trim().is_empty()
+        })
+        .map(|relation| Relation {
+            source: relation.source.trim().to_string(),
+            relation: relation.relation.trim().to_string(),
+            target: relation.target.trim().to_string(),
+            description: non_empty(relation.description),
+        })
+        .filter(|relation| {
+            seen.insert((
+                relation.source.to_lowercase(),
+                relation.relation.to_lowercase(),
+                relation.target.to_lowercase(),
+            ))
+        })
+        .collect();
+    Ok(Subgraph {
+        entities,
+        relations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_extraction_from_fenced_reply() {
+        let reply = "Here you go:\n```json\n{\"entities\": [{\"name\": \" Marie Curie \", \
+                     \"type\": \"Person\"}, {\"name\": \"\"}], \"relations\": [\
+                     {\"source\": \"Marie Curie\", \"relation\": \"won\", \"target\": \"Nobel Prize\"},\
+                     {\"source\": \"marie curie\", \"relation\": \"WON\", \"target\": \"nobel prize\"},\
+                     {\"source\": \"\", \"relation\": \"x\", \"target\": \"y\"}]}\n```";
+        let graph = parse_extraction(reply).unwrap();
+        assert_eq!(
+            graph.entities,
+            vec![Entity::new("Marie Curie").with_kind("person")]
+        );
+        assert_eq!(
+            graph.relations,
+            vec![Relation::new("Marie Curie", "won", "Nobel Prize")]
+        );
+
+        assert!(matches!(
+            parse_extraction("I could not find any entities."),
+            Err(GraphError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,177 @@
+//! Knowledge graphs for GraphRAG
+//!
+//! A [`GraphExtractor`] asks an LLM for the entities and relations mentioned
+//! in a chunk of text, and a [`GraphStore`] keeps them together with the ids
+//! of the chunks they were found in. [`GraphRetriever`] combines vector search
+//! with the graph: entities linked to the matching chunks, or named in the
+//! query, are expanded to their neighbourhood, so facts that are connected
+//! only through other entities (multi-hop questions) reach the LLM too.
+//!
+//! Extraction can run as part of an
+//! [`IngestionPipeline`](crate::ingestion::IngestionPipeline) via
+//! `with_graph`.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::vector_store::VectorStoreError;
+
+mod extract;
+mod retrieve;
+mod store;
+
+pub use extract::GraphExtractor;
+pub use retrieve::{DEFAULT_GRAPH_DEPTH, GraphRagResult, GraphRetriever};
+pub use store::InMemoryGraphStore;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GraphError {
+    #[error("Entity extraction failed: {0}")]
+    Extraction(String),
+
+    #[error("Could not parse extracted graph: {0}")]
+    Parse(String),
+
+    #[error("Graph store error: {0}")]
+    Store(String),
+
+    #[error("Vector store error: {0}")]
+    VectorStore(#[from] VectorStoreError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entity {
+    pub name: String,
+    /// Entity type such as `person` or `organization`, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl Entity {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            kind: None,
+            description: None,
+        }
+    }
+
+    pub fn with_kind(mut self, kind: impl Into<String>) -> Self {
+        self.kind = Some(kind.into());
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// A directed, labelled edge between two entities, referenced by name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Relation {
+    pub source: String,
+    pub relation: String,
+    pub target: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl Relation {
+    pub fn new(
+        source: impl Into<String>,
+        relation: impl Into<String>,
+        target: impl Into<String>,
+    ) -> Self {
+        Self {
+            source: source.into(),
+            relation: relation.into(),
+            target: target.into(),
+            description: None,
+        }
+    }
+}
+
+/// A set of entities and the relations between them
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Subgraph {
+    pub entities: Vec<Entity>,
+    pub relations: Vec<Relation>,
+}
+
+impl Subgraph {
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty() && self.relations.is_empty()
+    }
+
+    /// Render the subgraph as plain text for a prompt
+    pub fn to_context(&self) -> String {
+        let mut lines = Vec::new();
+        if !self.entities.is_empty() {
+            lines.push("Entities:".to_string());
+            for entity in &self.entities {
+                let mut line = format!("- {}", entity.name);
+                if let Some(kind) = &entity.kind {
+                    line.push_str(&format!(" ({kind})"));
+                }
+                if let Some(description) = &entity.description {
+                    line.push_str(&format!(": {description}"));
+                }
+                lines.push(line);
+            }
+        }
+        if !self.relations.is_empty() {
+            lines.push("Relations:".to_string());
+            for relation in &self.relations {
+                let mut line = format!(
+                    "- {} -[{}]-> {}",
+                    relation.source, relation.relation, relation.target
+                );
+                if let Some(description) = &relation.description {
+                    line.push_str(&format!(": {description}"));
+                }
+                lines.push(line);
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Storage for a knowledge graph and the chunks each entity was found in
+///
+/// Entities are identified by name, compared case-insensitively.
+#[async_trait]
+pub trait GraphStore: Send + Sync {
+    /// Add entities, merging them with existing entities of the same name
+    async fn upsert_entities(&self, entities: Vec<Entity>) -> Result<(), GraphError>;
+
+    /// Add relations; entities they mention that do not exist yet are created
+    async fn upsert_relations(&self, relations: Vec<Relation>) -> Result<(), GraphError>;
+
+    /// Record that `entities` are mentioned in the chunk stored as `chunk_id`
+    async fn link_chunk(&self, chunk_id: &str, entities: &[String]) -> Result<(), GraphError>;
+
+    /// Names of the entities linked to any of the chunks
+    async fn entities_for_chunks(&self, chunk_ids: &[String]) -> Result<Vec<String>, GraphError>;
+
+    /// Names of the known entities that appear in `text`
+    async fn entities_in_text(&self, text: &str) -> Result<Vec<String>, GraphError>;
+
+    /// The entities within `depth` hops of `entities` and the relations between them
+    async fn neighborhood(&self, entities: &[String], depth: usize)
+    -> Result<Subgraph, GraphError>;
+
+    /// Add everything in `graph` and link its entities to `chunk_id`
+    async fn insert_subgraph(&self, graph: Subgraph, chunk_id: &str) -> Result<(), GraphError> {
+        let mut names: Vec<String> = graph.entities.iter().map(|e| e.name.clone()).collect();
+        for relation in &graph.relations {
+            names.push(relation.source.clone());
+            names.push(relation.target.clone());
+        }
+        self.upsert_entities(graph.entities).await?;
+        self.upsert_relations(graph.relations).await?;
+        self.link_chunk(chunk_id, &names).await
+    }
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use super::{GraphError, GraphStore, Subgraph};
+use crate::document::Document;
+use crate::vector_store::{VectorSearchRequest, VectorStoreIndex};
+
+/// Default number of hops [`GraphRetriever`] expands from the seed entities
+pub const DEFAULT_GRAPH_DEPTH: usize = 1;
+
+/// Vector hits for a query and the part of the knowledge graph around them
+#[derive(Debug, Clone, Default)]
+pub struct GraphRagResult {
+    pub hits: Vec<(f64, String, Document)>,
+    pub graph: Subgraph,
+}
+
+impl GraphRagResult {
+    /// Render the graph facts followed by the matching chunks as prompt context
+    pub fn to_context(&self) -> String {
+        let mut sections = Vec::new();
+        if !self.graph.is_empty() {
+            sections.push(self.graph.to_context());
+        }
+        if !self.hits.is_empty() {
+            let passages: Vec<&str> = self
+                .hits
+                .iter()
+                .map(|(_, _, doc)| doc.page_content.as_str())
+                .collect();
+            sections.push(format!("Passages:\n{}", passages.join("\n---\n")));
+        }
+        sections.join("\n\n")
+    }
+}
+
+/// Retrieves chunks by vector search and expands them through a knowledge graph
+///
+/// The entities linked to the matching chunks and the entities named in the
+/// query seed a neighbourhood search of `depth` hops. Chunks must have been
+/// stored under the ids their entities were linked to, as
+/// [`IngestionPipeline::with_graph`](crate::ingestion::IngestionPipeline::with_graph)
+/// does.
+pub struct GraphRetriever<S> {
+    store: S,
+    graph: Arc<dyn GraphStore>,
+    depth: usize,
+    max_relations: usize,
+}
+
+impl<S> GraphRetriever<S>
+where
+    S: VectorStoreIndex,
+{
+    pub fn new(store: S, graph: Arc<dyn GraphStore>) -> Self {
+        Self {
+            store,
+            graph,
+            depth: DEFAULT_GRAPH_DEPTH,
+            max_relations: 50,
+        }
+    }
+
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Cap the number of relations returned, keeping the prompt bounded (default: 50)
+    pub fn with_max_relations(mut self, max_relations: usize) -> Self {
+        self.max_relations = max_relations;
+        self
+    }
+
+    pub async fn retrieve(
+        &self,
+        req: VectorSearchRequest<S::Filter>,
+    ) -> Result<GraphRagResult, GraphError> {
+        let query = req.query().to_string();
+        let hits: Vec<(f64, String, Document)> = self.store.top_n(req).await?;
+
+        let chunk_ids: Vec<String> = hits.iter().map(|(_, id, _)| id.clone()).collect();
+        let mut seeds = self.graph.entities_in_text(&query).await?;
+        for entity in self.graph.entities_for_chunks(&chunk_ids).await? {
+            if !seeds.iter().any(|seed| seed.eq_ignore_ascii_case(&entity)) {
+                seeds.push(entity);
+            }
+        }
+
+        let mut graph = self.graph.neighborhood(&seeds, self.depth).await?;
+        graph.relations.truncate(self.max_relations);
+        Ok(GraphRagResult { hits, graph })
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{Entity, GraphError, GraphStore, Relation, Subgraph};
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct GraphData {
+    /// Entities by normalized name
+    entities: BTreeMap<String, Entity>,
+    relations: Vec<Relation>,
+    /// Normalized entity names by chunk id
+    chunks: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// Graph store kept in memory, optionally saved to and loaded from a JSON file
+#[derive(Debug, Default)]
+pub struct InMemoryGraphStore {
+    data: RwLock<GraphData>,
+}
+
+fn key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+fn relation_key(relation: &Relation) -> (String, String, String) {
+    (
+        key(&relation.source),
+        key(&relation.relation),
+        key(&relation.target),
+    )
+}
+
+/// Whether `needle` occurs in `haystack` on word boundaries; both are lowercase
+fn contains_word(haystack: &str, needle: &str) -> bool {
+    haystack.match_indices(needle).any(|(start, _)| {
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[start + needle.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+impl InMemoryGraphStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a graph previously written with [`InMemoryGraphStore::save`]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, GraphError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|err| GraphError::Store(format!("{}: {err}", path.display())))?;
+        let data = serde_json::from_slice(&bytes)
+            .map_err(|err| GraphError::Store(format!("{}: {err}", path.display())))?;
+        Ok(Self {
+            data: RwLock::new(data),
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), GraphError> {
+        let path = path.as_ref();
+        let bytes =
+            serde_json::to_vec(&*self.read()).map_err(|err| GraphError::Store(err.to_string()))?;
+        std::fs::write(path, bytes)
+            .map_err(|err| GraphError::Store(format!("{}: {err}", path.display())))
+    }
+
+    pub fn entity_count(&self) -> usize {
+        self.read().entities.len()
+    }
+
+    pub fn relation_count(&self) -> usize {
+        self.read().relations.len()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, GraphData> {
+        self.data.read().expect("lock poisoned")
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, GraphData> {
+        self.data.write().expect("lock poisoned")
+    }
+}
+
+impl GraphData {
+    fn upsert_entity(&mut self, entity: Entity) {
+        let entity_key = key(&entity.name);
+        if entity_key.is_empty() {
+            return;
+        }
+        match self.entities.get_mut(&entity_key) {
+            Some(existing) => {
+                if existing.kind.is_none() {
+                    existing.kind = entity.kind;
+                }
+                if existing.description.is_none() {
+                    existing.description = entity.description;
+                }
+            }
+            None => {
+                self.entities.insert(entity_key, entity);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl GraphStore for InMemoryGraphStore {
+    async fn upsert_entities(&self, entities: Vec<Entity>) -> Result<(), GraphError> {
+        let mut data = self.write();
+        for entity in entities {
+            data.upsert_entity(entity);
+        }
+        Ok(())
+    }
+
+    async fn upsert_relations(&self, relations: Vec<Relation>) -> Result<(), GraphError> {
+        let mut data = self.write();
+        let mut known: HashSet<_> = data.relations.iter().map(relation_key).collect();
+        for relation in relations {
+            let (source, _, target) = relation_key(&relation);
+            if source.is_empty() || target.is_empty() || !known.insert(relation_key(&relation)) {
+                continue;
+            }
+            data.upsert_entity(Entity::new(relation.source.trim()));
+            data.upsert_entity(Entity::new(relation.target.trim()));
+            data.relations.push(relation);
+        }
+        Ok(())
+    }
+
+    async fn link_chunk(&self, chunk_id: &str, entities: &[String]) -> Result<(), GraphError> {
+        let mut data = self.write();
+        let keys: Vec<String> = entities
+            .iter()
+            .map(|name| key(name))
+            .filter(|name| data.entities.contains_key(name))
+            .collect();
+        data.chunks
+            .entry(chunk_id.to_string())
+            .or_default()
+            .extend(keys);
+        Ok(())
+    }
+
+    async fn entities_for_chunks(&self, chunk_ids: &[String]) -> Result<Vec<String>, GraphError> {
+        let data = self.read();
+        let mut seen = HashSet::new();
+        Ok(chunk_ids
+            .iter()
+            .filter_map(|chunk_id| data.chunks.get(chunk_id))
+            .flatten()
+            .filter(|entity_key| seen.insert(*entity_key))
+            .filter_map(|entity_key| data.entities.get(entity_key))
+            .map(|entity| entity.name.clone())
+            .collect())
+    }
+
+    async fn entities_in_text(&self, text: &str) -> Result<Vec<String>, GraphError> {
+        let text = text.to_lowercase();
+        Ok(self
+            .read()
+            .entities
+            .iter()
+            .filter(|(entity_key, _)| contains_word(&text, entity_key))
+            .map(|(_, entity)| entity.name.clone())
+            .collect())
+    }
+
+    async fn neighborhood(
+        &self,
+        entities: &[String],
+        depth: usize,
+    ) -> Result<Subgraph, GraphError> {
+        let data = self.read();
+        let mut order: Vec<String> = Vec::new();
+        let mut reached: HashSet<String> = HashSet::new();
+        for name in entities {
+            let entity_key = key(name);
+            if data.entities.contains_key(&entity_key) && reached.insert(entity_key.clone()) {
+                order.push(entity_key);
+            }
+        }
+
+        let mut frontier = order.clone();
+        for _ in 0..depth {
+            let mut next = Vec::new();
+            for relation in &data.relations {
+                let (source, _, target) = relation_key(relation);
+                for (from, to) in [(&source, &target), (&target, &source)] {
+                    if frontier.contains(from) && reached.insert(to.clone()) {
+                        next.push(to.clone());
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            order.extend(next.iter().cloned());
+            frontier = next;
+        }
+
+        Ok(Subgraph {
+            entities: order
+                .iter()
+                .filter_map(|entity_key| data.entities.get(entity_key).cloned())
+                .collect(),
+            relations: data
+                .relations
+                .iter()
+                .filter(|relation| {
+                    let (source, _, target) = relation_key(relation);
+                    reached.contains(&source) && reached.contains(&target)
+                })
+                .cloned()
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn store() -> InMemoryGraphStore {
+        let store = InMemoryGraphStore::new();
+        store
+            .insert_subgraph(
+                Subgraph {
+                    entities: vec![Entity::new("Ada Lovelace").with_kind("person")],
+                    relations: vec![
+                        Relation::new("Ada Lovelace", "worked with", "Charles Babbage"),
+                        Relation::new("charles babbage", "designed", "Analytical Engine"),
+                    ],
+                },
+                "chunk-1",
+            )
+            .await
+            .unwrap();
+        store
+            .upsert_relations(vec![Relation::new(
+                "Analytical Engine",
+                "inspired",
+                "Modern computers",
+            )])
+            .await
+            .unwrap();
+        store
+    }
+
+    #[tokio::test]
+    async fn test_entities_merge_case_insensitively_and_link_to_chunks() {
+        let store = store().await;
+        assert_eq!(store.entity_count(), 4);
+        assert_eq!(store.relation_count(), 3);
+
+        let linked = store
+            .entities_for_chunks(&["chunk-1".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(linked.len(), 3);
+        assert!(linked.contains(&"Charles Babbage".to_string()));
+
+        let mentioned = store
+            .entities_in_text("Who did ADA LOVELACE work with? (not Ada Lovelaces)")
+            .await
+            .unwrap();
+        assert_eq!(mentioned, vec!["Ada Lovelace"]);
+    }
+
+    #[tokio::test]
+    async fn test_neighborhood_expands_by_depth_and_round_trips_through_file() {
+        let store = store().await;
+        let one_hop = store
+            .neighborhood(&["Ada Lovelace".to_string()], 1)
+            .await
+            .unwrap();
+        let names: Vec<&str> = one_hop.entities.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["Ada Lovelace", "Charles Babbage"]);
+        assert_eq!(one_hop.relations.len(), 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.json");
+        store.save(&path).unwrap();
+        let loaded = InMemoryGraphStore::load(&path).unwrap();
+        let two_hops = loaded
+            .neighborhood(&["ada lovelace".to_string()], 2)
+            .await
+            .unwrap();
+        assert_eq!(two_hops.entities.len(), 3);
+        assert_eq!(
+            two_hops.to_context(),
+            "Entities:\n- Ada Lovelace (person)\n- Charles Babbage\n- Analytical Engine\n\
+             Relations:\n- Ada Lovelace -[worked with]-> Charles Babbage\n\
+             - charles babbage -[designed]-> Analytical Engine"
+        );
+    }
+}
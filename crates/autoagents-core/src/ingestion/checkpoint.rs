@@ -0,0 +1,110 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use super::IngestionError;
+
+/// Remembers which documents a pipeline has already stored
+///
+/// Keys are recorded after the batch containing the document has been
+/// upserted, so an interrupted run resumes with the first unfinished batch.
+pub trait IngestionCheckpoint: Send + Sync {
+    fn completed(&self) -> Result<HashSet<String>, IngestionError>;
+
+    fn record(&self, keys: &[String]) -> Result<(), IngestionError>;
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct CheckpointFile {
+    completed: Vec<String>,
+}
+
+/// Checkpoint kept in a JSON file, rewritten atomically after every batch
+#[derive(Debug)]
+pub struct FileCheckpoint {
+    path: PathBuf,
+    completed: Mutex<Option<HashSet<String>>>,
+}
+
+impl FileCheckpoint {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            completed: Mutex::new(None),
+        }
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Forget all recorded documents so the next run ingests everything again
+    pub fn clear(&self) -> Result<(), IngestionError> {
+        *self.lock() = Some(HashSet::new());
+        match fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(self.error(err)),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<HashSet<String>>> {
+        self.completed
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn error(&self, err: impl std::fmt::Display) -> IngestionError {
+        IngestionError::Checkpoint(format!("{}: {err}", self.path.display()))
+    }
+
+    fn read(&self) -> Result<HashSet<String>, IngestionError> {
+        match fs::read(&self.path) {
+            Ok(bytes) => {
+                let file: CheckpointFile =
+                    serde_json::from_slice(&bytes).map_err(|err| self.error(err))?;
+                Ok(file.completed.into_iter().collect())
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(HashSet::new()),
+            Err(err) => Err(self.error(err)),
+        }
+    }
+}
+
+impl IngestionCheckpoint for FileCheckpoint {
+    fn completed(&self) -> Result<HashSet<String>, IngestionError> {
+        let mut guard = self.lock();
+        if guard.is_none() {
+            *guard = Some(self.read()?);
+        }
+        Ok(guard.clone().unwrap_or_default())
+    }
+
+    fn record(&self, keys: &[String]) -> Result<(), IngestionError> {
+        let mut guard = self.lock();
+        if guard.is_none() {
+            *guard = Some(self.read()?);
+        }
+        let completed = guard.get_or_insert_with(HashSet::new);
+        completed.extend(keys.iter().cloned());
+
+        let mut sorted: Vec<String> = completed.iter().cloned().collect();
+        sorted.sort();
+        let bytes = serde_json::to_vec(&CheckpointFile { completed: sorted })
+            .map_err(|err| self.error(err))?;
+        write_atomically(&self.path, &bytes).map_err(|err| self.error(err))
+    }
+}
+
+/// Write `bytes` to a temporary file next to `path`, then rename it into place
+pub(super) fn write_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, bytes)?;
+    fs::rename(&tmp, path)
+}
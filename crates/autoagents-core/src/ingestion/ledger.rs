@@ -0,0 +1,128 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use super::IngestionError;
+use super::checkpoint::write_atomically;
+
+/// What the ledger knows about one stored document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// Hash of the document content as it was last embedded
+    pub content_hash: String,
+    /// Source modification time (seconds since the Unix epoch), if the source reports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<u64>,
+    /// Chunks stored in the vector store for this document: chunk id → content hash
+    pub chunks: BTreeMap<String, String>,
+}
+
+/// Tracks which chunks are stored for each document so that a pipeline only
+/// re-embeds changed chunks and deletes the chunks of removed documents
+///
+/// Entries are keyed by the document id (see [`crate::chunking::parent_id`]).
+pub trait DocumentLedger: Send + Sync {
+    fn entries(&self) -> Result<HashMap<String, LedgerEntry>, IngestionError>;
+
+    /// Insert or replace the entries of the given documents
+    fn update(&self, entries: &[(String, LedgerEntry)]) -> Result<(), IngestionError>;
+
+    fn remove(&self, ids: &[String]) -> Result<(), IngestionError>;
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct LedgerFile {
+    documents: BTreeMap<String, LedgerEntry>,
+}
+
+/// Ledger kept in a JSON file, rewritten atomically after every change
+#[derive(Debug)]
+pub struct FileLedger {
+    path: PathBuf,
+    entries: Mutex<Option<HashMap<String, LedgerEntry>>>,
+}
+
+impl FileLedger {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            entries: Mutex::new(None),
+        }
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    fn error(&self, err: impl std::fmt::Display) -> IngestionError {
+        IngestionError::Ledger(format!("{}: {err}", self.path.display()))
+    }
+
+    fn read(&self) -> Result<HashMap<String, LedgerEntry>, IngestionError> {
+        match fs::read(&self.path) {
+            Ok(bytes) => {
+                let file: LedgerFile =
+                    serde_json::from_slice(&bytes).map_err(|err| self.error(err))?;
+                Ok(file.documents.into_iter().collect())
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(err) => Err(self.error(err)),
+        }
+    }
+
+    /// Apply `change` to the loaded entries and persist the result
+    fn modify(
+        &self,
+        change: impl FnOnce(&mut HashMap<String, LedgerEntry>),
+    ) -> Result<(), IngestionError> {
+        let mut guard = self
+            .entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if guard.is_none() {
+            *guard = Some(self.read()?);
+        }
+        let entries = guard.get_or_insert_with(HashMap::new);
+        change(entries);
+
+        let file = LedgerFile {
+            documents: entries.clone().into_iter().collect(),
+        };
+        let bytes = serde_json::to_vec(&file).map_err(|err| self.error(err))?;
+        write_atomically(&self.path, &bytes).map_err(|err| self.error(err))
+    }
+}
+
+impl DocumentLedger for FileLedger {
+    fn entries(&self) -> Result<HashMap<String, LedgerEntry>, IngestionError> {
+        let mut guard = self
+            .entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if guard.is_none() {
+            *guard = Some(self.read()?);
+        }
+        Ok(guard.clone().unwrap_or_default())
+    }
+
+    fn update(&self, entries: &[(String, LedgerEntry)]) -> Result<(), IngestionError> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        self.modify(|stored| stored.extend(entries.iter().cloned()))
+    }
+
+    fn remove(&self, ids: &[String]) -> Result<(), IngestionError> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.modify(|stored| {
+            for id in ids {
+                stored.remove(id);
+            }
+        })
+    }
+}
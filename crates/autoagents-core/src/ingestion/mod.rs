@@ -0,0 +1,868 @@
+//! Ingestion pipelines: read → transform → chunk → embed → upsert
+//!
+//! An [`IngestionPipeline`] loads documents from one or more
+//! [`DocumentSource`]s, runs them through [`DocumentTransform`]s (for example
+//! [`Deduplicate`], [`EnrichMetadata`] and [`ScrubPii`]), splits them with a
+//! [`TextSplitter`] and upserts the chunks into a [`VectorStoreIndex`], which
+//! embeds them with its configured provider.
+//!
+//! Chunk ids are derived from the parent document's `id` or `source` and the
+//! chunk position, so running a pipeline again overwrites earlier chunks
+//! instead of duplicating them. With an [`IngestionCheckpoint`] an interrupted
+//! run skips the documents that were already stored.
+//!
+//! With a [`DocumentLedger`] runs are incremental: unchanged documents are
+//! skipped, only the chunks of a changed document whose content changed are
+//! embedded again, and the chunks of documents that are no longer produced by
+//! any source are deleted from the store.
+//!
+//! With [`NearDuplicates`] chunks that nearly repeat an earlier chunk of the
+//! run are skipped, or merged into it, before they are embedded.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::chunking::{TextSplitter, parent_id};
+use crate::document::Document;
+use crate::graph::{GraphError, GraphExtractor, GraphStore};
+use crate::readers::simple_directory_reader::{ReaderError, SimpleDirectoryReader};
+use crate::vector_store::{VectorStoreError, VectorStoreIndex};
+
+mod checkpoint;
+mod ledger;
+mod near_duplicates;
+mod transforms;
+
+pub use checkpoint::{FileCheckpoint, IngestionCheckpoint};
+pub use ledger::{DocumentLedger, FileLedger, LedgerEntry};
+pub use near_duplicates::{DUPLICATES_KEY, DuplicateAction, NearDuplicates};
+pub use transforms::{Deduplicate, DocumentTransform, EnrichMetadata, ScrubPii};
+
+/// Default number of chunks upserted per batch
+pub const DEFAULT_INGESTION_BATCH_SIZE: usize = 64;
+/// Default number of batches upserted concurrently
+pub const DEFAULT_INGESTION_CONCURRENCY: usize = 4;
+
+/// Metadata field holding the source modification time, in seconds since the Unix epoch
+pub const MODIFIED_KEY: &str = "modified";
+
+#[derive(Debug, thiserror::Error)]
+pub enum IngestionError {
+    #[error("Reader error: {0}")]
+    Reader(#[from] ReaderError),
+
+    #[error("Vector store error: {0}")]
+    VectorStore(#[from] VectorStoreError),
+
+    #[error("Checkpoint error: {0}")]
+    Checkpoint(String),
+
+    #[error("Ledger error: {0}")]
+    Ledger(String),
+
+    #[error("Graph error: {0}")]
+    Graph(#[from] GraphError),
+
+    #[error("Source error: {0}")]
+    Source(String),
+
+    #[error("Invalid ingestion configuration: {0}")]
+    InvalidConfig(String),
+}
+
+/// Where an [`IngestionPipeline`] reads its documents from
+#[async_trait]
+pub trait DocumentSource: Send + Sync {
+    async fn load(&self) -> Result<Vec<Document>, IngestionError>;
+}
+
+#[async_trait]
+impl DocumentSource for SimpleDirectoryReader {
+    async fn load(&self) -> Result<Vec<Document>, IngestionError> {
+        Ok(self.load_data()?)
+    }
+}
+
+#[async_trait]
+impl DocumentSource for Vec<Document> {
+    async fn load(&self) -> Result<Vec<Document>, IngestionError> {
+        Ok(self.clone())
+    }
+}
+
+/// Progress of a running pipeline, reported after loading and after every batch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IngestionProgress {
+    /// Documents to ingest in this run, excluding skipped and unchanged ones
+    pub documents_total: usize,
+    pub documents_done: usize,
+    pub chunks_total: usize,
+    pub chunks_done: usize,
+}
+
+/// Summary of a finished pipeline run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IngestionReport {
+    pub documents_read: usize,
+    /// Documents removed by transforms
+    pub documents_dropped: usize,
+    /// Documents already stored according to the checkpoint
+    pub documents_skipped: usize,
+    /// Documents whose content matches the ledger
+    pub documents_unchanged: usize,
+    pub documents_ingested: usize,
+    /// Ledger entries whose document no source produced anymore
+    pub documents_removed: usize,
+    pub chunks_upserted: usize,
+    pub chunks_deleted: usize,
+    /// Chunks skipped or merged as near duplicates
+    pub chunks_deduplicated: usize,
+}
+
+type ProgressFn = dyn Fn(&IngestionProgress) + Send + Sync;
+
+/// A document ready to be stored
+struct PreparedParent {
+    id: String,
+    /// Checkpoint key: the id plus a content hash, so edited documents are ingested again
+    key: String,
+    /// Number of source documents sharing this id, e.g. the pages of a PDF
+    documents: usize,
+    entry: LedgerEntry,
+    /// Chunks to embed and upsert, with their ids
+    chunks: Vec<(String, Document)>,
+    /// Ids of previously stored chunks that no longer exist
+    stale: Vec<String>,
+}
+
+/// Composes sources, transforms, a splitter and a vector store into one ingestion run
+pub struct IngestionPipeline<S> {
+    store: Arc<S>,
+    sources: Vec<Arc<dyn DocumentSource>>,
+    transforms: Vec<Arc<dyn DocumentTransform>>,
+    splitter: Option<Arc<dyn TextSplitter>>,
+    checkpoint: Option<Arc<dyn IngestionCheckpoint>>,
+    ledger: Option<Arc<dyn DocumentLedger>>,
+    graph: Option<(Arc<GraphExtractor>, Arc<dyn GraphStore>)>,
+    near_duplicates: Option<NearDuplicates>,
+    progress: Option<Arc<ProgressFn>>,
+    batch_size: usize,
+    concurrency: usize,
+}
+
+impl<S> IngestionPipeline<S>
+where
+    S: VectorStoreIndex + 'static,
+{
+    pub fn new(store: Arc<S>) -> Self {
+        Self {
+            store,
+            sources: Vec::new(),
+            transforms: Vec::new(),
+            splitter: None,
+            checkpoint: None,
+            ledger: None,
+            graph: None,
+            near_duplicates: None,
+            progress: None,
+            batch_size: DEFAULT_INGESTION_BATCH_SIZE,
+            concurrency: DEFAULT_INGESTION_CONCURRENCY,
+        }
+    }
+
+    pub fn with_source(mut self, source: impl DocumentSource + 'static) -> Self {
+        self.sources.push(Arc::new(source));
+        self
+    }
+
+    /// Add a transform; transforms run in the order they were added
+    pub fn with_transform(mut self, transform: impl DocumentTransform + 'static) -> Self {
+        self.transforms.push(Arc::new(transform));
+        self
+    }
+
+    /// Split documents into chunks before storing them (default: store documents whole)
+    pub fn with_splitter(mut self, splitter: impl TextSplitter + 'static) -> Self {
+        self.splitter = Some(Arc::new(splitter));
+        self
+    }
+
+    pub fn with_checkpoint(mut self, checkpoint: impl IngestionCheckpoint + 'static) -> Self {
+        self.checkpoint = Some(Arc::new(checkpoint));
+        self
+    }
+
+    /// Track stored documents in `ledger` to index incrementally
+    ///
+    /// The ledger treats the sources as the complete corpus: documents it has
+    /// seen before that no source returns are deleted from the store, which
+    /// requires [`VectorStoreIndex::delete_documents_by_ids`].
+    pub fn with_ledger(mut self, ledger: impl DocumentLedger + 'static) -> Self {
+        self.ledger = Some(Arc::new(ledger));
+        self
+    }
+
+    /// Extract entities and relations from every upserted chunk into `graph`
+    ///
+    /// Entities are linked to the chunk ids, which is what
+    /// [`GraphRetriever`](crate::graph::GraphRetriever) expands from.
+    pub fn with_graph(mut self, extractor: GraphExtractor, graph: Arc<dyn GraphStore>) -> Self {
+        self.graph = Some((Arc::new(extractor), graph));
+        self
+    }
+
+    /// Drop chunks that nearly duplicate an earlier chunk of the same run
+    pub fn with_near_duplicates(mut self, near_duplicates: NearDuplicates) -> Self {
+        self.near_duplicates = Some(near_duplicates);
+        self
+    }
+
+    /// Target number of chunks per upsert; documents are never split across batches
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Number of batches embedded and upserted at the same time
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub fn on_progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(&IngestionProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    pub fn store(&self) -> &Arc<S> {
+        &self.store
+    }
+
+    pub async fn run(&self) -> Result<IngestionReport, IngestionError> {
+        if self.batch_size == 0 || self.concurrency == 0 {
+            return Err(IngestionError::InvalidConfig(
+                "batch_size and concurrency must be greater than zero".to_string(),
+            ));
+        }
+
+        let loaded =
+            futures::future::try_join_all(self.sources.iter().map(|source| source.load())).await?;
+        let mut documents: Vec<Document> = loaded.into_iter().flatten().collect();
+        let mut report = IngestionReport {
+            documents_read: documents.len(),
+            ..IngestionReport::default()
+        };
+
+        for transform in &self.transforms {
+            documents = transform.transform(documents);
+        }
+        report.documents_dropped = report.documents_read.saturating_sub(documents.len());
+
+        let completed = match &self.checkpoint {
+            Some(checkpoint) => checkpoint.completed()?,
+            None => HashSet::new(),
+        };
+        let known = match &self.ledger {
+            Some(ledger) => ledger.entries()?,
+            None => HashMap::new(),
+        };
+
+        let mut seen = HashSet::new();
+        let mut touched = Vec::new();
+        let mut parents = Vec::new();
+        for (id, documents) in group_by_parent(documents) {
+            seen.insert(id.clone());
+            let content_hash = group_hash(&documents);
+            let key = format!("{id}#{}", &content_hash[..16]);
+            let modified = documents
+                .iter()
+                .filter_map(|doc| doc.metadata.get(MODIFIED_KEY).and_then(Value::as_u64))
+                .max();
+            let previous = known.get(&id);
+
+            if completed.contains(&key) {
+                report.documents_skipped += documents.len();
+                continue;
+            }
+            if let Some(previous) = previous.filter(|entry| entry.content_hash == content_hash) {
+                report.documents_unchanged += documents.len();
+                if previous.modified != modified {
+                    let entry = LedgerEntry {
+                        modified,
+                        ..previous.clone()
+                    };
+                    touched.push((id, entry));
+                }
+                continue;
+            }
+
+            let chunks = self.chunk(&id, &documents);
+            let entry = LedgerEntry {
+                content_hash,
+                modified,
+                chunks: chunks
+                    .iter()
+                    .map(|(chunk_id, chunk)| (chunk_id.clone(), chunk_hash(chunk)))
+                    .collect(),
+            };
+            let (chunks, stale) = match previous {
+                Some(previous) => (
+                    chunks
+                        .into_iter()
+                        .filter(|(chunk_id, _)| {
+                            previous.chunks.get(chunk_id) != entry.chunks.get(chunk_id)
+                        })
+                        .collect(),
+                    previous
+                        .chunks
+                        .keys()
+                        .filter(|chunk_id| !entry.chunks.contains_key(*chunk_id))
+                        .cloned()
+                        .collect(),
+                ),
+                None => (chunks, Vec::new()),
+            };
+            parents.push(PreparedParent {
+                id,
+                key,
+                documents: documents.len(),
+                entry,
+                chunks,
+                stale,
+            });
+        }
+        if let Some(ledger) = &self.ledger {
+            ledger.update(&touched)?;
+        }
+        if let Some(near_duplicates) = &self.near_duplicates {
+            report.chunks_deduplicated = drop_near_duplicates(near_duplicates, &mut parents);
+        }
+
+        let mut progress = IngestionProgress {
+            documents_total: parents.iter().map(|parent| parent.documents).sum(),
+            chunks_total: parents.iter().map(|parent| parent.chunks.len()).sum(),
+            ..IngestionProgress::default()
+        };
+        self.report_progress(&progress);
+
+        let store = self.store.as_ref();
+        let graph = self.graph.as_ref();
+        let mut upserts = futures::stream::iter(self.batches(parents).into_iter().map(
+            |mut batch| async move {
+                let chunks: Vec<(String, Document)> = batch
+                    .iter_mut()
+                    .flat_map(|parent| std::mem::take(&mut parent.chunks))
+                    .collect();
+                let stale: Vec<String> = batch
+                    .iter_mut()
+                    .flat_map(|parent| std::mem::take(&mut parent.stale))
+                    .collect();
+                let upserted = chunks.len();
+                let texts: Vec<(String, String)> = match graph {
+                    Some(_) => chunks
+                        .iter()
+                        .map(|(id, chunk)| (id.clone(), chunk.page_content.clone()))
+                        .collect(),
+                    None => Vec::new(),
+                };
+                if !chunks.is_empty() {
+                    store.insert_documents_with_ids(chunks).await?;
+                }
+                if let Some((extractor, graph)) = graph {
+                    futures::future::try_join_all(texts.iter().map(|(id, text)| async move {
+                        let subgraph = extractor.extract(text).await?;
+                        graph.insert_subgraph(subgraph, id).await
+                    }))
+                    .await?;
+                }
+                if !stale.is_empty() {
+                    store.delete_documents_by_ids(&stale).await?;
+                }
+                Ok::<_, IngestionError>((batch, upserted, stale.len()))
+            },
+        ))
+        .buffer_unordered(self.concurrency);
+
+        while let Some(result) = upserts.next().await {
+            let (batch, upserted, deleted) = result?;
+            let documents: usize = batch.iter().map(|parent| parent.documents).sum();
+            if let Some(checkpoint) = &self.checkpoint {
+                let keys: Vec<String> = batch.iter().map(|parent| parent.key.clone()).collect();
+                checkpoint.record(&keys)?;
+            }
+            if let Some(ledger) = &self.ledger {
+                let entries: Vec<(String, LedgerEntry)> = batch
+                    .into_iter()
+                    .map(|parent| (parent.id, parent.entry))
+                    .collect();
+                ledger.update(&entries)?;
+            }
+            progress.documents_done += documents;
+            progress.chunks_done += upserted;
+            report.documents_ingested += documents;
+            report.chunks_upserted += upserted;
+            report.chunks_deleted += deleted;
+            self.report_progress(&progress);
+        }
+
+        if let Some(ledger) = &self.ledger {
+            let removed: Vec<String> = known
+                .keys()
+                .filter(|id| !seen.contains(*id))
+                .cloned()
+                .collect();
+            let stale: Vec<String> = removed
+                .iter()
+                .flat_map(|id| known[id].chunks.keys().cloned())
+                .collect();
+            if !stale.is_empty() {
+                store.delete_documents_by_ids(&stale).await?;
+            }
+            ledger.remove(&removed)?;
+            report.documents_removed = removed.len();
+            report.chunks_deleted += stale.len();
+        }
+
+        log::info!(
+            "Ingested {} documents as {} chunks ({} skipped, {} unchanged, {} removed)",
+            report.documents_ingested,
+            report.chunks_upserted,
+            report.documents_skipped,
+            report.documents_unchanged,
+            report.documents_removed
+        );
+        Ok(report)
+    }
+
+    /// Split the documents sharing `id` and give every chunk an id derived
+    /// from `id` and its position
+    fn chunk(&self, id: &str, documents: &[Document]) -> Vec<(String, Document)> {
+        let chunks = match &self.splitter {
+            Some(splitter) => splitter.split_documents(documents),
+            None => documents.to_vec(),
+        };
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| (stable_uuid(&[id, &index.to_string()]), chunk))
+            .collect()
+    }
+
+    /// Group whole documents into batches of roughly `batch_size` chunks
+    fn batches(&self, parents: Vec<PreparedParent>) -> Vec<Vec<PreparedParent>> {
+        let mut batches = Vec::new();
+        let mut batch = Vec::new();
+        let mut chunks = 0;
+        for parent in parents {
+            chunks += parent.chunks.len();
+            batch.push(parent);
+            if chunks >= self.batch_size {
+                batches.push(std::mem::take(&mut batch));
+                chunks = 0;
+            }
+        }
+        if !batch.is_empty() {
+            batches.push(batch);
+        }
+        batches
+    }
+
+    fn report_progress(&self, progress: &IngestionProgress) {
+        if let Some(callback) = &self.progress {
+            callback(progress);
+        }
+    }
+}
+
+/// Remove near-duplicate chunks from `parents`, returning how many were removed
+///
+/// Removed chunks are left out of the ledger entry too, so a later run checks
+/// them again instead of treating them as stored.
+fn drop_near_duplicates(config: &NearDuplicates, parents: &mut [PreparedParent]) -> usize {
+    let mut detector = config.detector();
+    let mut kept: Vec<(usize, String)> = Vec::new();
+    let mut merges: Vec<(usize, String)> = Vec::new();
+    let mut removed = 0;
+    for (position, parent) in parents.iter_mut().enumerate() {
+        let mut chunks = Vec::with_capacity(parent.chunks.len());
+        for (chunk_id, chunk) in std::mem::take(&mut parent.chunks) {
+            match detector.check(&chunk.page_content) {
+                Ok(_) => {
+                    kept.push((position, chunk_id.clone()));
+                    chunks.push((chunk_id, chunk));
+                }
+                Err(near_duplicates::DuplicateOf(index)) => {
+                    parent.entry.chunks.remove(&chunk_id);
+                    if kept[index].0 != position {
+                        merges.push((index, parent.id.clone()));
+                    }
+                    removed += 1;
+                }
+            }
+        }
+        parent.chunks = chunks;
+    }
+
+    if detector.action() == DuplicateAction::Merge {
+        for (index, duplicate_parent) in merges {
+            let (position, chunk_id) = &kept[index];
+            if let Some((_, chunk)) = parents[*position]
+                .chunks
+                .iter_mut()
+                .find(|(id, _)| id == chunk_id)
+            {
+                near_duplicates::record_duplicate(chunk, &duplicate_parent);
+            }
+        }
+    }
+    removed
+}
+
+/// Group documents by [`parent_id`], keeping the order in which ids first appear
+fn group_by_parent(documents: Vec<Document>) -> Vec<(String, Vec<Document>)> {
+    let mut groups: Vec<(String, Vec<Document>)> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for document in documents {
+        let id = parent_id(&document);
+        match positions.get(&id) {
+            Some(&position) => groups[position].1.push(document),
+            None => {
+                positions.insert(id.clone(), groups.len());
+                groups.push((id, vec![document]));
+            }
+        }
+    }
+    groups
+}
+
+/// Hash of the content and metadata of all documents sharing an id
+///
+/// The modification time is left out so touching a file without changing
+/// it does not trigger re-embedding.
+fn group_hash(documents: &[Document]) -> String {
+    let mut hasher = Sha256::new();
+    for document in documents {
+        hasher.update(fingerprint(document));
+        hasher.update([0]);
+    }
+    hex(&hasher.finalize())
+}
+
+fn chunk_hash(chunk: &Document) -> String {
+    hex(&Sha256::digest(fingerprint(chunk)))
+}
+
+fn fingerprint(document: &Document) -> Vec<u8> {
+    let mut metadata = document.metadata.clone();
+    if let Value::Object(map) = &mut metadata {
+        map.remove(MODIFIED_KEY);
+    }
+    let mut bytes = document.page_content.as_bytes().to_vec();
+    bytes.push(0);
+    bytes.extend(metadata.to_string().into_bytes());
+    bytes
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn stable_uuid(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    uuid::Builder::from_custom_bytes(bytes)
+        .into_uuid()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunking::RecursiveCharacterSplitter;
+    use crate::embeddings::SharedEmbeddingProvider;
+    use crate::testing::KeywordEmbeddingProvider;
+    use crate::vector_store::in_memory_store::InMemoryVectorStore;
+    use crate::vector_store::request::VectorSearchRequest;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    fn store() -> Arc<InMemoryVectorStore> {
+        let provider: SharedEmbeddingProvider = Arc::new(KeywordEmbeddingProvider::flat());
+        Arc::new(InMemoryVectorStore::new(provider))
+    }
+
+    async fn stored(store: &InMemoryVectorStore) -> Vec<(String, Document)> {
+        let request = VectorSearchRequest::builder()
+            .query("anything")
+            .samples(100)
+            .build()
+            .unwrap();
+        let mut results: Vec<(String, Document)> = store
+            .top_n::<Document>(request)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(_, id, doc)| (id, doc))
+            .collect();
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        results
+    }
+
+    fn documents() -> Vec<Document> {
+        vec![
+            Document::with_metadata("alpha beta gamma delta", json!({"source": "a.txt"})),
+            Document::with_metadata("alpha beta gamma delta", json!({"source": "copy.txt"})),
+            Document::new("contact bob@example.com"),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_transforms_chunks_and_upserts() {
+        let store = store();
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let seen = updates.clone();
+
+        let pipeline = IngestionPipeline::new(store.clone())
+            .with_source(documents())
+            .with_transform(Deduplicate::new())
+            .with_transform(ScrubPii::new())
+            .with_splitter(RecursiveCharacterSplitter::new(12, 0).unwrap())
+            .with_batch_size(1)
+            .on_progress(move |progress| seen.lock().unwrap().push(progress.clone()));
+        let report = pipeline.run().await.unwrap();
+
+        assert_eq!(
+            report,
+            IngestionReport {
+                documents_read: 3,
+                documents_dropped: 1,
+                documents_skipped: 0,
+                documents_unchanged: 0,
+                documents_ingested: 2,
+                documents_removed: 0,
+                chunks_upserted: 4,
+                chunks_deleted: 0,
+                chunks_deduplicated: 0,
+            }
+        );
+        {
+            let updates = updates.lock().unwrap();
+            assert_eq!(updates.len(), 3);
+            assert_eq!(updates.last().unwrap().chunks_done, 4);
+        }
+
+        let chunks = stored(&store).await;
+        assert_eq!(chunks.len(), 4);
+        assert!(
+            chunks
+                .iter()
+                .all(|(_, doc)| !doc.page_content.contains('@'))
+        );
+        assert!(
+            chunks
+                .iter()
+                .any(|(_, doc)| doc.metadata["parent_id"] == "a.txt")
+        );
+
+        // Ids are deterministic, so a second run overwrites instead of duplicating
+        pipeline.run().await.unwrap();
+        let ids = |chunks: &[(String, Document)]| -> Vec<String> {
+            chunks.iter().map(|(id, _)| id.clone()).collect()
+        };
+        assert_eq!(ids(&stored(&store).await), ids(&chunks));
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_resumes_and_reingests_changed_documents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ingest.json");
+        let store = store();
+
+        let run = async |docs: Vec<Document>| {
+            IngestionPipeline::new(store.clone())
+                .with_source(docs)
+                .with_checkpoint(FileCheckpoint::new(&path))
+                .run()
+                .await
+        };
+
+        let first = run(documents()).await.unwrap();
+        assert_eq!(first.documents_ingested, 3);
+
+        let mut changed = documents();
+        changed[0].page_content = "alpha beta gamma epsilon".into();
+        let second = run(changed).await.unwrap();
+        assert_eq!(second.documents_skipped, 2);
+        assert_eq!(second.documents_ingested, 1);
+        // The unsplit document keeps its id and is overwritten in place
+        assert_eq!(stored(&store).await.len(), 3);
+
+        FileCheckpoint::new(&path).clear().unwrap();
+        assert_eq!(run(documents()).await.unwrap().documents_skipped, 0);
+    }
+
+    #[tokio::test]
+    async fn test_near_duplicate_chunks_are_merged_before_upsert() {
+        let footer = "Subscribe to our newsletter for weekly updates on products and offers";
+        let store = store();
+        let report = IngestionPipeline::new(store.clone())
+            .with_source(vec![
+                Document::with_metadata(
+                    format!("Rust ownership explained in depth\n\n{footer}"),
+                    json!({"source": "a.html"}),
+                ),
+                Document::with_metadata(
+                    format!(
+                        "Async runtimes compared side by side\n\n{}",
+                        footer.to_uppercase()
+                    ),
+                    json!({"source": "b.html"}),
+                ),
+            ])
+            .with_splitter(RecursiveCharacterSplitter::new(80, 0).unwrap())
+            .with_near_duplicates(NearDuplicates::new().action(DuplicateAction::Merge))
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(report.chunks_deduplicated, 1);
+        assert_eq!(report.chunks_upserted, 3);
+        let chunks = stored(&store).await;
+        let kept = chunks
+            .iter()
+            .find(|(_, doc)| doc.page_content == footer)
+            .unwrap();
+        assert_eq!(kept.1.metadata[DUPLICATES_KEY], json!(["b.html"]));
+    }
+
+    #[tokio::test]
+    async fn test_ledger_reembeds_changed_chunks_and_deletes_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.json");
+        let store = store();
+
+        let run = async |docs: Vec<(&str, &str)>| {
+            let docs: Vec<Document> = docs
+                .into_iter()
+                .map(|(source, text)| Document::with_metadata(text, json!({"source": source})))
+                .collect();
+            IngestionPipeline::new(store.clone())
+                .with_source(docs)
+                .with_splitter(RecursiveCharacterSplitter::new(12, 0).unwrap())
+                .with_ledger(FileLedger::new(&path))
+                .run()
+                .await
+                .unwrap()
+        };
+
+        let first = run(vec![
+            ("a.txt", "alpha beta gamma delta"),
+            ("b.txt", "one two"),
+            ("c.txt", "three four"),
+        ])
+        .await;
+        assert_eq!(first.chunks_upserted, 4);
+        assert_eq!(stored(&store).await.len(), 4);
+
+        let second = run(vec![
+            ("a.txt", "alpha beta gamma omega"),
+            ("b.txt", "one two"),
+        ])
+        .await;
+        assert_eq!(second.documents_unchanged, 1);
+        assert_eq!(second.documents_ingested, 1);
+        assert_eq!(second.chunks_upserted, 1);
+        assert_eq!(second.documents_removed, 1);
+        assert_eq!(second.chunks_deleted, 1);
+        let chunks = stored(&store).await;
+        assert_eq!(chunks.len(), 3);
+        assert!(
+            chunks
+                .iter()
+                .any(|(_, doc)| doc.page_content == "gamma omega")
+        );
+        assert!(
+            chunks
+                .iter()
+                .all(|(_, doc)| doc.metadata["source"] != "c.txt")
+        );
+
+        let third = run(vec![("a.txt", "alpha beta"), ("b.txt", "one two")]).await;
+        assert_eq!(third.chunks_upserted, 0);
+        assert_eq!(third.chunks_deleted, 1);
+        assert_eq!(stored(&store).await.len(), 2);
+
+        let entries = FileLedger::new(&path).entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries["a.txt"].chunks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_graph_extraction_feeds_graph_retrieval() {
+        use crate::graph::{GraphRetriever, InMemoryGraphStore};
+        use crate::tests::{ConfigurableLLMProvider, StaticChatResponse};
+
+        let reply = r#"{"entities": [{"name": "Ada Lovelace", "type": "person"}],
+            "relations": [{"source": "Ada Lovelace", "relation": "worked with", "target": "Charles Babbage"},
+                          {"source": "Charles Babbage", "relation": "designed", "target": "Analytical Engine"}]}"#;
+        let llm = Arc::new(ConfigurableLLMProvider {
+            chat_response: StaticChatResponse {
+                text: Some(reply.to_string()),
+                tool_calls: None,
+                usage: None,
+                thinking: None,
+            },
+            ..ConfigurableLLMProvider::default()
+        });
+        let graph = Arc::new(InMemoryGraphStore::new());
+        let store = store();
+
+        IngestionPipeline::new(store.clone())
+            .with_source(vec![Document::with_metadata(
+                "Ada Lovelace worked with Charles Babbage.",
+                json!({"source": "bio.txt"}),
+            )])
+            .with_graph(GraphExtractor::new(llm), graph.clone())
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(graph.relation_count(), 2);
+
+        let retriever = GraphRetriever::new(store.as_ref().clone(), graph).with_depth(2);
+        let req = VectorSearchRequest::builder()
+            .query("What did Ada's collaborator design?")
+            .samples(1)
+            .build()
+            .unwrap();
+        let result = retriever.retrieve(req).await.unwrap();
+        assert_eq!(result.hits.len(), 1);
+        // The design relation is two hops from the chunk's own entity
+        let context = result.to_context();
+        assert!(context.contains("Charles Babbage -[designed]-> Analytical Engine"));
+        assert!(context.contains("Passages:\nAda Lovelace worked with Charles Babbage."));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_batch_size() {
+        let err = IngestionPipeline::new(store())
+            .with_batch_size(0)
+            .run()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, IngestionError::InvalidConfig(_)));
+    }
+}
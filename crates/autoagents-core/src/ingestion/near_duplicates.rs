@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use serde_json::Value;
+
+use crate::document::Document;
+
+/// Metadata field listing the parents of chunks merged into a kept chunk
+pub const DUPLICATES_KEY: &str = "duplicates";
+
+const SIGNATURE_LEN: usize = 64;
+const BANDS: usize = 16;
+const ROWS: usize = SIGNATURE_LEN / BANDS;
+
+/// What happens to a chunk that nearly duplicates an earlier one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateAction {
+    /// Drop the chunk
+    #[default]
+    Skip,
+    /// Drop the chunk and record its parent under [`DUPLICATES_KEY`] of the kept chunk
+    Merge,
+}
+
+/// Detects chunks whose text nearly repeats an earlier chunk
+///
+/// Chunks are compared by the Jaccard similarity of their word shingles,
+/// estimated with MinHash signatures and banded so each chunk is only compared
+/// with likely matches. Unlike [`Deduplicate`](super::Deduplicate) this
+/// catches repeated boilerplate and lightly edited copies of the same passage.
+/// Only chunks embedded in the same run are compared.
+#[derive(Debug, Clone)]
+pub struct NearDuplicates {
+    threshold: f64,
+    shingle_size: usize,
+    action: DuplicateAction,
+}
+
+impl Default for NearDuplicates {
+    fn default() -> Self {
+        Self {
+            threshold: 0.9,
+            shingle_size: 3,
+            action: DuplicateAction::default(),
+        }
+    }
+}
+
+impl NearDuplicates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Minimum estimated Jaccard similarity, in `0.0..=1.0`, to treat two chunks as duplicates
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Number of consecutive words per shingle
+    pub fn shingle_size(mut self, words: usize) -> Self {
+        self.shingle_size = words.max(1);
+        self
+    }
+
+    pub fn action(mut self, action: DuplicateAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    pub(crate) fn detector(&self) -> DuplicateDetector<'_> {
+        DuplicateDetector {
+            config: self,
+            kept: Vec::new(),
+            buckets: HashMap::new(),
+        }
+    }
+
+    fn signature(&self, text: &str) -> Option<[u64; SIGNATURE_LEN]> {
+        let words: Vec<String> = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        if words.is_empty() {
+            return None;
+        }
+        let shingle_hashes: Vec<u64> = words
+            .windows(self.shingle_size.min(words.len()))
+            .map(|shingle| {
+                let mut hasher = DefaultHasher::new();
+                shingle.hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect();
+
+        let mut signature = [u64::MAX; SIGNATURE_LEN];
+        for (seed, slot) in signature.iter_mut().enumerate() {
+            for shingle in &shingle_hashes {
+                *slot = (*slot).min(mix(*shingle, seed as u64));
+            }
+        }
+        Some(signature)
+    }
+}
+
+/// Cheap seeded rehash of a shingle hash, one per signature slot
+fn mix(hash: u64, seed: u64) -> u64 {
+    let mut value = hash ^ seed.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    value ^= value >> 33;
+    value = value.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+    value ^= value >> 33;
+    value
+}
+
+/// A chunk that duplicates an earlier one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DuplicateOf(pub(crate) usize);
+
+/// Signatures of the chunks kept so far in one run
+pub(crate) struct DuplicateDetector<'a> {
+    config: &'a NearDuplicates,
+    kept: Vec<[u64; SIGNATURE_LEN]>,
+    buckets: HashMap<(usize, u64), Vec<usize>>,
+}
+
+impl DuplicateDetector<'_> {
+    pub(crate) fn action(&self) -> DuplicateAction {
+        self.config.action
+    }
+
+    /// Check `text` against the kept chunks; a new chunk is kept and gets the next index
+    pub(crate) fn check(&mut self, text: &str) -> Result<usize, DuplicateOf> {
+        let Some(signature) = self.config.signature(text) else {
+            self.kept.push([u64::MAX; SIGNATURE_LEN]);
+            return Ok(self.kept.len() - 1);
+        };
+        let bands: Vec<(usize, u64)> = signature
+            .chunks(ROWS)
+            .enumerate()
+            .map(|(band, rows)| {
+                let mut hasher = DefaultHasher::new();
+                rows.hash(&mut hasher);
+                (band, hasher.finish())
+            })
+            .collect();
+
+        let candidate = bands
+            .iter()
+            .filter_map(|band| self.buckets.get(band))
+            .flatten()
+            .find(|&&index| similarity(&self.kept[index], &signature) >= self.config.threshold);
+        if let Some(&index) = candidate {
+            return Err(DuplicateOf(index));
+        }
+
+        let index = self.kept.len();
+        self.kept.push(signature);
+        for band in bands {
+            self.buckets.entry(band).or_default().push(index);
+        }
+        Ok(index)
+    }
+}
+
+fn similarity(a: &[u64; SIGNATURE_LEN], b: &[u64; SIGNATURE_LEN]) -> f64 {
+    let equal = a.iter().zip(b).filter(|(a, b)| a == b).count();
+    equal as f64 / SIGNATURE_LEN as f64
+}
+
+/// Record `parent` under [`DUPLICATES_KEY`] of `kept`
+pub(crate) fn record_duplicate(kept: &mut Document, parent: &str) {
+    if !kept.metadata.is_object() {
+        kept.metadata = Value::Object(Default::default());
+    }
+    let Value::Object(map) = &mut kept.metadata else {
+        return;
+    };
+    let duplicates = map
+        .entry(DUPLICATES_KEY)
+        .or_insert_with(|| Value::Array(Vec::new()));
+    if let Value::Array(list) = duplicates
+        && !list.iter().any(|value| value == parent)
+    {
+        list.push(Value::String(parent.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSAGE: &str = "Subscribe to our newsletter to receive weekly updates about new \
+        products, seasonal discounts and exclusive offers for members of our community";
+
+    #[test]
+    fn test_near_copies_are_detected() {
+        let config = NearDuplicates::new().threshold(0.7);
+        let mut detector = config.detector();
+
+        assert_eq!(detector.check(PASSAGE), Ok(0));
+        let edited = PASSAGE.replace("weekly", "monthly");
+        assert_eq!(detector.check(&edited), Err(DuplicateOf(0)));
+        assert_eq!(detector.check(&PASSAGE.to_uppercase()), Err(DuplicateOf(0)));
+        assert_eq!(
+            detector.check("Quarterly revenue grew by twelve percent in the northern region"),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_threshold_controls_sensitivity() {
+        let edited = PASSAGE.replace("weekly updates about", "a digest covering");
+        let strict = NearDuplicates::new().threshold(0.95);
+        let mut detector = strict.detector();
+        detector.check(PASSAGE).unwrap();
+        assert!(detector.check(&edited).is_ok());
+
+        let signature = strict.signature(PASSAGE).unwrap();
+        assert_eq!(similarity(&signature, &signature), 1.0);
+    }
+
+    #[test]
+    fn test_record_duplicate_lists_each_parent_once() {
+        let mut kept = Document::new("text");
+        record_duplicate(&mut kept, "b.html");
+        record_duplicate(&mut kept, "b.html");
+        record_duplicate(&mut kept, "c.html");
+        assert_eq!(
+            kept.metadata[DUPLICATES_KEY],
+            serde_json::json!(["b.html", "c.html"])
+        );
+    }
+}
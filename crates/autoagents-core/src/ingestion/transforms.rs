@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::document::Document;
+use crate::pii::{self, PiiKind};
+
+/// A step that rewrites, drops or adds documents before they are chunked
+pub trait DocumentTransform: Send + Sync {
+    fn transform(&self, documents: Vec<Document>) -> Vec<Document>;
+}
+
+impl<F> DocumentTransform for F
+where
+    F: Fn(Vec<Document>) -> Vec<Document> + Send + Sync,
+{
+    fn transform(&self, documents: Vec<Document>) -> Vec<Document> {
+        self(documents)
+    }
+}
+
+/// Drops documents whose content repeats an earlier document
+///
+/// Content is compared after collapsing whitespace, and optionally ignoring
+/// case, so reflowed copies of the same text are caught too.
+#[derive(Debug, Clone, Default)]
+pub struct Deduplicate {
+    ignore_case: bool,
+}
+
+impl Deduplicate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+}
+
+impl DocumentTransform for Deduplicate {
+    fn transform(&self, documents: Vec<Document>) -> Vec<Document> {
+        let mut seen = HashSet::new();
+        documents
+            .into_iter()
+            .filter(|doc| {
+                let mut normalized = doc
+                    .page_content
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                if self.ignore_case {
+                    normalized = normalized.to_lowercase();
+                }
+                seen.insert(Sha256::digest(normalized.as_bytes()))
+            })
+            .collect()
+    }
+}
+
+type EnrichFn = dyn Fn(&Document) -> Map<String, Value> + Send + Sync;
+
+/// Adds metadata fields to every document
+///
+/// Fields computed by the enricher overwrite existing fields of the same name.
+pub struct EnrichMetadata {
+    enrich: Box<EnrichFn>,
+}
+
+impl EnrichMetadata {
+    /// Compute the fields to add from each document
+    pub fn new<F>(enrich: F) -> Self
+    where
+        F: Fn(&Document) -> Map<String, Value> + Send + Sync + 'static,
+    {
+        Self {
+            enrich: Box::new(enrich),
+        }
+    }
+
+    /// Set `key` to the same `value` on every document
+    pub fn field(key: impl Into<String>, value: impl Into<Value>) -> Self {
+        let key = key.into();
+        let value = value.into();
+        Self::new(move |_| Map::from_iter([(key.clone(), value.clone())]))
+    }
+}
+
+impl DocumentTransform for EnrichMetadata {
+    fn transform(&self, documents: Vec<Document>) -> Vec<Document> {
+        documents
+            .into_iter()
+            .map(|mut doc| {
+                let fields = (self.enrich)(&doc);
+                match &mut doc.metadata {
+                    Value::Object(map) => map.extend(fields),
+                    other => *other = Value::Object(fields),
+                }
+                doc
+            })
+            .collect()
+    }
+}
+
+/// Replaces e-mail addresses, phone numbers, social security numbers and
+/// card numbers in document content before it is embedded and stored
+///
+/// Uses the shared [`PII_PATTERNS`](crate::pii::PII_PATTERNS). Documents that
+/// were changed get `pii_redacted: true` in their metadata.
+#[derive(Debug, Clone)]
+pub struct ScrubPii {
+    pub email_replacement: String,
+    pub phone_replacement: String,
+    pub ssn_replacement: String,
+    pub card_replacement: String,
+}
+
+impl Default for ScrubPii {
+    fn default() -> Self {
+        Self {
+            email_replacement: PiiKind::Email.default_replacement().to_string(),
+            phone_replacement: PiiKind::Phone.default_replacement().to_string(),
+            ssn_replacement: PiiKind::Ssn.default_replacement().to_string(),
+            card_replacement: PiiKind::Card.default_replacement().to_string(),
+        }
+    }
+}
+
+impl ScrubPii {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn scrub(&self, text: &str) -> String {
+        pii::redact_pii(text, |kind| match kind {
+            PiiKind::Email => self.email_replacement.as_str(),
+            PiiKind::Phone => self.phone_replacement.as_str(),
+            PiiKind::Ssn => self.ssn_replacement.as_str(),
+            PiiKind::Card => self.card_replacement.as_str(),
+        })
+        .into_owned()
+    }
+}
+
+impl DocumentTransform for ScrubPii {
+    fn transform(&self, documents: Vec<Document>) -> Vec<Document> {
+        documents
+            .into_iter()
+            .map(|mut doc| {
+                let scrubbed = self.scrub(&doc.page_content);
+                if scrubbed != doc.page_content {
+                    doc.page_content = scrubbed;
+                    if let Value::Object(map) = &mut doc.metadata {
+                        map.insert("pii_redacted".into(), Value::Bool(true));
+                    }
+                }
+                doc
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deduplicate_normalizes_whitespace_and_case() {
+        let docs = vec![
+            Document::new("Hello   world"),
+            Document::new("Hello world"),
+            Document::new("hello WORLD"),
+            Document::new("Other"),
+        ];
+        assert_eq!(Deduplicate::new().transform(docs.clone()).len(), 3);
+        let unique = Deduplicate::new().ignore_case(true).transform(docs);
+        let texts: Vec<&str> = unique.iter().map(|d| d.page_content.as_str()).collect();
+        assert_eq!(texts, vec!["Hello   world", "Other"]);
+    }
+
+    #[test]
+    fn test_enrich_metadata_and_scrub_pii() {
+        let docs = vec![Document::with_metadata(
+            "Mail jane@example.com or call 555-123-4567.",
+            json!({"source": "a.txt"}),
+        )];
+        let docs = EnrichMetadata::field("tenant", "acme").transform(docs);
+        let docs = ScrubPii::new().transform(docs);
+
+        assert_eq!(
+            docs[0].page_content,
+            "Mail [redacted:email] or call [redacted:phone]."
+        );
+        assert_eq!(
+            docs[0].metadata,
+            json!({"source": "a.txt", "tenant": "acme", "pii_redacted": true})
+        );
+    }
+}
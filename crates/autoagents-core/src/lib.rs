@@ -11,13 +11,21 @@ pub mod agent;
 
 // Common modules available on all platforms
 mod channel;
+pub mod chunking;
 pub mod document;
 pub mod embeddings;
 pub mod error;
 #[cfg(not(target_arch = "wasm32"))]
 mod event_fanout;
+pub mod graph;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ingestion;
 pub mod one_or_many;
+pub mod pii;
+pub mod prompt;
 pub mod readers;
+#[cfg(all(not(target_arch = "wasm32"), any(test, feature = "testing")))]
+pub mod testing;
 pub mod tool;
 pub mod utils;
 pub mod vector_store;
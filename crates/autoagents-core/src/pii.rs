@@ -0,0 +1,88 @@
+//! Built-in patterns for common personally identifiable information
+//!
+//! One table shared by the ingestion [`ScrubPii`](crate::ingestion::ScrubPii)
+//! transform, the guardrails PII guard and telemetry redaction, so all of
+//! them catch the same things.
+
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// A kind of PII matched by [`PII_PATTERNS`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PiiKind {
+    Email,
+    Phone,
+    Ssn,
+    Card,
+}
+
+impl PiiKind {
+    /// Placeholder used when callers do not pick their own, e.g. `[redacted:email]`
+    pub fn default_replacement(self) -> &'static str {
+        match self {
+            Self::Email => "[redacted:email]",
+            Self::Phone => "[redacted:phone]",
+            Self::Ssn => "[redacted:ssn]",
+            Self::Card => "[redacted:card]",
+        }
+    }
+}
+
+/// Patterns for each [`PiiKind`], in the order they are applied
+pub static PII_PATTERNS: LazyLock<[(PiiKind, Regex); 4]> = LazyLock::new(|| {
+    [
+        (
+            PiiKind::Email,
+            Regex::new(r"(?i)\b[a-z0-9._%+\-]+@[a-z0-9.\-]+\.[a-z]{2,}\b")
+                .expect("email regex is valid"),
+        ),
+        (
+            PiiKind::Phone,
+            Regex::new(r"\b(?:\+?1[-.\s]?)?(?:\(?\d{3}\)?[-.\s]?)\d{3}[-.\s]?\d{4}\b")
+                .expect("phone regex is valid"),
+        ),
+        (
+            PiiKind::Ssn,
+            Regex::new(r"\b\d{3}-\d{2}-\d{4}\b").expect("ssn regex is valid"),
+        ),
+        (
+            PiiKind::Card,
+            Regex::new(r"\b(?:\d[ -]*?){13,19}\b").expect("card regex is valid"),
+        ),
+    ]
+});
+
+/// Replace every PII match in `text` with the placeholder `replacement` picks
+/// for its kind
+///
+/// Returns the input unchanged (borrowed) when nothing matched.
+pub fn redact_pii<R: AsRef<str>>(text: &str, replacement: impl Fn(PiiKind) -> R) -> Cow<'_, str> {
+    let mut text = Cow::Borrowed(text);
+    for (kind, pattern) in PII_PATTERNS.iter() {
+        let placeholder = replacement(*kind);
+        if let Cow::Owned(redacted) = pattern.replace_all(&text, placeholder.as_ref()) {
+            text = Cow::Owned(redacted);
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_pii_replaces_each_kind() {
+        let text = "Mail a@b.io, call 555-123-4567, SSN 123-45-6789, card 4111 1111 1111 1111";
+        assert_eq!(
+            redact_pii(text, PiiKind::default_replacement),
+            "Mail [redacted:email], call [redacted:phone], SSN [redacted:ssn], card [redacted:card]"
+        );
+        assert!(matches!(
+            redact_pii("nothing here", PiiKind::default_replacement),
+            Cow::Borrowed(_)
+        ));
+    }
+}
@@ -0,0 +1,355 @@
+//! Named, versioned prompt templates
+//!
+//! A [`PromptRegistry`] holds prompt templates by name and version, plus
+//! shared partials. Templates are [minijinja] (Jinja2) templates: `{{ variable }}`
+//! inserts a value, `{% if %}` and `{% for %}` work as usual, and
+//! `{% include "partial" %}` includes a partial. Rendering a prompt yields a
+//! [`RenderedPrompt`] that remembers the version it came from; apply it to a
+//! [`Task`] to use it as the system prompt and record the name and version
+//! in the task's trace attributes, which are reported with
+//! `Event::TaskStarted` and on the task's telemetry span.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use autoagents_protocol::Task;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+mod template;
+
+/// Trace attribute holding the name of the prompt a task ran with
+pub const PROMPT_NAME_ATTRIBUTE: &str = "prompt.name";
+/// Trace attribute holding the version of the prompt a task ran with
+pub const PROMPT_VERSION_ATTRIBUTE: &str = "prompt.version";
+/// Version given to prompts loaded from a file without a version directory
+pub const DEFAULT_PROMPT_VERSION: &str = "1";
+/// Directory under a prompt directory that holds partials
+pub const PARTIALS_DIR: &str = "partials";
+
+#[derive(Debug, thiserror::Error)]
+pub enum PromptError {
+    #[error("Prompt `{0}` not found")]
+    NotFound(String),
+
+    #[error("Prompt `{name}` has no version `{version}`")]
+    VersionNotFound { name: String, version: String },
+
+    #[error("Prompt `{prompt}` uses variable `{variable}`, which was not provided")]
+    MissingVariable { prompt: String, variable: String },
+
+    #[error("Prompt `{prompt}` includes unknown partial `{partial}`")]
+    UnknownPartial { prompt: String, partial: String },
+
+    #[error("Invalid template for prompt `{prompt}`: {message}")]
+    Syntax { prompt: String, message: String },
+
+    #[error("Failed to read prompt file {path:?}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// One version of a named prompt template
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    pub name: String,
+    pub version: String,
+    pub template: String,
+}
+
+impl PromptTemplate {
+    pub fn new(
+        name: impl Into<String>,
+        version: impl Into<String>,
+        template: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            template: template.into(),
+        }
+    }
+
+    /// Variables the template uses directly, sorted
+    pub fn variables(&self) -> Vec<String> {
+        template::variables(&self.template)
+    }
+}
+
+/// A rendered prompt and the template version it was rendered from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenderedPrompt {
+    pub name: String,
+    pub version: String,
+    pub text: String,
+}
+
+impl RenderedPrompt {
+    /// Use this prompt as the task's system prompt and record its name and version
+    pub fn apply_to(&self, task: Task) -> Task {
+        task.with_system_prompt(self.text.clone())
+            .with_trace_attribute(PROMPT_NAME_ATTRIBUTE, self.name.clone())
+            .with_trace_attribute(PROMPT_VERSION_ATTRIBUTE, self.version.clone())
+    }
+}
+
+/// Prompt templates by name and version, with shared partials
+///
+/// Prompts are referenced as `name`, which picks the latest version, or
+/// `name@version`. Versions compare segment by segment, numerically where
+/// both segments are numbers, so `v10` is later than `v9` and `1.10` later
+/// than `1.2`; a leading `v` is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct PromptRegistry {
+    prompts: HashMap<String, Vec<PromptTemplate>>,
+    partials: HashMap<String, String>,
+}
+
+impl PromptRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every prompt under `dir`; see [`PromptRegistry::load_dir`]
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self, PromptError> {
+        Self::new().load_dir(dir)
+    }
+
+    /// Add a prompt version, replacing one with the same name and version
+    pub fn with_prompt(
+        mut self,
+        name: impl Into<String>,
+        version: impl Into<String>,
+        template: impl Into<String>,
+    ) -> Self {
+        self.insert(PromptTemplate::new(name, version, template));
+        self
+    }
+
+    /// Add a partial that templates can include with `{% include "name" %}`
+    pub fn with_partial(mut self, name: impl Into<String>, template: impl Into<String>) -> Self {
+        self.partials.insert(name.into(), template.into());
+        self
+    }
+
+    /// Load prompts from a directory
+    ///
+    /// - `<dir>/<name>/<version>.<ext>` adds version `<version>` of `<name>`
+    /// - `<dir>/<name>.<ext>` adds version [`DEFAULT_PROMPT_VERSION`] of `<name>`
+    /// - `<dir>/partials/<name>.<ext>` adds the partial `<name>`
+    ///
+    /// Names and versions are file stems, so any extension works. Hidden
+    /// files are skipped.
+    pub fn load_dir(mut self, dir: impl AsRef<Path>) -> Result<Self, PromptError> {
+        let dir = dir.as_ref();
+        for (path, stem) in read_dir_entries(dir)? {
+            if path.is_dir() {
+                let files = read_dir_entries(&path)?;
+                for (file, version) in files.into_iter().filter(|(file, _)| file.is_file()) {
+                    let text = read_file(&file)?;
+                    if stem == PARTIALS_DIR {
+                        self.partials.insert(version, text);
+                    } else {
+                        self.insert(PromptTemplate::new(stem.clone(), version, text));
+                    }
+                }
+            } else {
+                let text = read_file(&path)?;
+                self.insert(PromptTemplate::new(stem, DEFAULT_PROMPT_VERSION, text));
+            }
+        }
+        Ok(self)
+    }
+
+    /// Add a prompt version, replacing one with the same name and version
+    pub fn insert(&mut self, prompt: PromptTemplate) {
+        let versions = self.prompts.entry(prompt.name.clone()).or_default();
+        versions.retain(|existing| existing.version != prompt.version);
+        versions.push(prompt);
+        versions.sort_by(|a, b| compare_versions(&a.version, &b.version));
+    }
+
+    /// Registered prompt names, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.prompts.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Versions of `name`, oldest first
+    pub fn versions(&self, name: &str) -> Vec<&str> {
+        self.prompts
+            .get(name)
+            .map(|versions| versions.iter().map(|p| p.version.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Look up `name` (latest version) or `name@version`
+    pub fn get(&self, reference: &str) -> Result<&PromptTemplate, PromptError> {
+        let (name, version) = match reference.split_once('@') {
+            Some((name, version)) => (name, Some(version)),
+            None => (reference, None),
+        };
+        let versions = self
+            .prompts
+            .get(name)
+            .ok_or_else(|| PromptError::NotFound(name.to_string()))?;
+        match version {
+            Some(version) => versions
+                .iter()
+                .find(|prompt| prompt.version == version)
+                .ok_or_else(|| PromptError::VersionNotFound {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                }),
+            None => versions
+                .last()
+                .ok_or_else(|| PromptError::NotFound(name.to_string())),
+        }
+    }
+
+    /// Render the referenced prompt with `vars`, a JSON object of variables
+    pub fn render(&self, reference: &str, vars: &Value) -> Result<RenderedPrompt, PromptError> {
+        let prompt = self.get(reference)?;
+        let text = template::render(&prompt.name, &prompt.template, vars, &self.partials)?;
+        Ok(RenderedPrompt {
+            name: prompt.name.clone(),
+            version: prompt.version.clone(),
+            text,
+        })
+    }
+}
+
+/// Non-hidden entries of `dir` with their file stems, sorted by path
+fn read_dir_entries(dir: &Path) -> Result<Vec<(PathBuf, String)>, PromptError> {
+    let io_error = |source| PromptError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if !stem.starts_with('.') {
+            entries.push((path.clone(), stem.to_string()));
+        }
+    }
+    entries.sort();
+    Ok(entries)
+}
+
+fn read_file(path: &Path) -> Result<String, PromptError> {
+    std::fs::read_to_string(path).map_err(|source| PromptError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let segments = |version: &str| -> Vec<String> {
+        version
+            .trim_start_matches(['v', 'V'])
+            .split(['.', '-'])
+            .map(str::to_string)
+            .collect()
+    };
+    let (a_segments, b_segments) = (segments(a), segments(b));
+    for (a, b) in a_segments.iter().zip(&b_segments) {
+        let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            _ => a.cmp(b),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a_segments.len().cmp(&b_segments.len()).then(a.cmp(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn registry() -> PromptRegistry {
+        PromptRegistry::new()
+            .with_prompt(
+                "support",
+                "v2",
+                "You help {{ product }} users. {% include 'tone' %}",
+            )
+            .with_prompt(
+                "support",
+                "v10",
+                "You support {{ product }}. {% include 'tone' %}",
+            )
+            .with_prompt("support", "v9", "Old")
+            .with_partial("tone", "Be kind.")
+    }
+
+    #[test]
+    fn test_get_picks_latest_or_pinned_version() {
+        let registry = registry();
+        assert_eq!(registry.versions("support"), vec!["v2", "v9", "v10"]);
+        assert_eq!(registry.get("support").unwrap().version, "v10");
+        assert_eq!(registry.get("support@v2").unwrap().version, "v2");
+        assert!(matches!(
+            registry.get("support@v3"),
+            Err(PromptError::VersionNotFound { .. })
+        ));
+        assert!(matches!(
+            registry.get("sales"),
+            Err(PromptError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.2", "1.10"), Ordering::Less);
+        assert_eq!(compare_versions("v3", "2"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0", "1.0.1"), Ordering::Less);
+        assert_eq!(compare_versions("2024-05-01", "2024-11-02"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_rendered_prompt_is_recorded_on_the_task() {
+        let rendered = registry()
+            .render("support@v2", &json!({"product": "AutoAgents"}))
+            .unwrap();
+        assert_eq!(rendered.text, "You help AutoAgents users. Be kind.");
+
+        let task = rendered.apply_to(Task::new("My agent hangs"));
+        assert_eq!(task.system_prompt.as_deref(), Some(rendered.text.as_str()));
+        let trace = task.trace.unwrap();
+        assert_eq!(trace.attributes[PROMPT_NAME_ATTRIBUTE], json!("support"));
+        assert_eq!(trace.attributes[PROMPT_VERSION_ATTRIBUTE], json!("v2"));
+    }
+
+    #[test]
+    fn test_load_dir_reads_versions_and_partials() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("support")).unwrap();
+        std::fs::create_dir_all(root.join(PARTIALS_DIR)).unwrap();
+        std::fs::write(root.join("support/v1.md"), "One {% include 'sig' %}").unwrap();
+        std::fs::write(root.join("support/v2.md"), "Two {% include 'sig' %}").unwrap();
+        std::fs::write(root.join("summarize.txt"), "Summarize {{ text }}").unwrap();
+        std::fs::write(root.join(PARTIALS_DIR).join("sig.md"), "-- bot").unwrap();
+        std::fs::write(root.join(".DS_Store"), "").unwrap();
+
+        let registry = PromptRegistry::from_dir(root).unwrap();
+        assert_eq!(registry.names(), vec!["summarize", "support"]);
+        assert_eq!(
+            registry.render("support", &json!({})).unwrap().text,
+            "Two -- bot"
+        );
+        let summarize = registry.get("summarize").unwrap();
+        assert_eq!(summarize.version, DEFAULT_PROMPT_VERSION);
+        assert_eq!(summarize.variables(), vec!["text".to_string()]);
+    }
+}
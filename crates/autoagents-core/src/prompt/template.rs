@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use minijinja::{AutoEscape, Environment, ErrorKind, UndefinedBehavior};
+use serde_json::Value;
+
+use super::PromptError;
+
+/// Render `template` with minijinja, with `partials` available to `{% include %}`
+///
+/// Undefined variables are an error rather than empty text, and nothing is
+/// HTML-escaped.
+pub(crate) fn render(
+    prompt: &str,
+    template: &str,
+    vars: &Value,
+    partials: &HashMap<String, String>,
+) -> Result<String, PromptError> {
+    let mut env = Environment::new();
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    env.set_auto_escape_callback(|_| AutoEscape::None);
+    for (name, text) in partials {
+        env.add_template(name, text)
+            .map_err(|err| template_error(prompt, err, template, partials))?;
+    }
+    env.template_from_named_str(prompt, template)
+        .and_then(|compiled| compiled.render(vars))
+        .map_err(|err| template_error(prompt, err, template, partials))
+}
+
+/// Variable names used by `template`, sorted, not following includes
+pub(crate) fn variables(template: &str) -> Vec<String> {
+    let env = Environment::new();
+    let Ok(compiled) = env.template_from_str(template) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = compiled.undeclared_variables(false).into_iter().collect();
+    names.sort_unstable();
+    names
+}
+
+fn template_error(
+    prompt: &str,
+    err: minijinja::Error,
+    template: &str,
+    partials: &HashMap<String, String>,
+) -> PromptError {
+    // Errors inside a partial arrive wrapped in the include that failed
+    let mut err = &err;
+    while let Some(inner) =
+        std::error::Error::source(err).and_then(|source| source.downcast_ref::<minijinja::Error>())
+    {
+        err = inner;
+    }
+    // The source text that failed: a variable, or a whole include tag
+    let failing = || {
+        let source = match err.name() {
+            Some(name) if name != prompt => partials.get(name)?,
+            _ => template,
+        };
+        Some(source.get(err.range()?)?.trim())
+    };
+    match err.kind() {
+        ErrorKind::UndefinedError => match failing() {
+            Some(variable) => PromptError::MissingVariable {
+                prompt: prompt.to_string(),
+                variable: variable.to_string(),
+            },
+            None => syntax_error(prompt, err),
+        },
+        ErrorKind::TemplateNotFound => match failing().and_then(quoted_name) {
+            Some(partial) => PromptError::UnknownPartial {
+                prompt: prompt.to_string(),
+                partial: partial.to_string(),
+            },
+            None => syntax_error(prompt, err),
+        },
+        _ => syntax_error(prompt, err),
+    }
+}
+
+/// The first quoted string in `text`, e.g. the name in `include 'tone'`
+fn quoted_name(text: &str) -> Option<&str> {
+    let start = text.find(['"', '\''])?;
+    let quote = text[start..].chars().next()?;
+    let rest = &text[start + 1..];
+    rest.find(quote).map(|end| &rest[..end])
+}
+
+fn syntax_error(prompt: &str, err: &minijinja::Error) -> PromptError {
+    PromptError::Syntax {
+        prompt: prompt.to_string(),
+        message: err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_inserts_variables_and_partials() {
+        let partials = HashMap::from([
+            ("tone".to_string(), "Be {{ style }}.".to_string()),
+            (
+                "footer".to_string(),
+                "{% include 'tone' %} Thanks!".to_string(),
+            ),
+        ]);
+        let text = render(
+            "support",
+            "Hi {{ user }}, you have {{count}} tickets. {% include 'footer' %}",
+            &json!({"user": "Ada <ada@example.com>", "count": 3, "style": "brief"}),
+            &partials,
+        )
+        .unwrap();
+        assert_eq!(
+            text,
+            "Hi Ada <ada@example.com>, you have 3 tickets. Be brief. Thanks!"
+        );
+    }
+
+    #[test]
+    fn test_render_supports_conditions_and_loops() {
+        let text = render(
+            "p",
+            "{% if vip %}Priority. {% endif %}{% for tag in tags %}#{{ tag }} {% endfor %}",
+            &json!({"vip": true, "tags": ["billing", "refund"]}),
+            &HashMap::new(),
+        )
+        .unwrap();
+        assert_eq!(text, "Priority. #billing #refund ");
+    }
+
+    #[test]
+    fn test_render_reports_missing_variables_and_partials() {
+        let err = render("p", "Hi {{ user }}", &json!({}), &HashMap::new()).unwrap_err();
+        assert!(
+            matches!(err, PromptError::MissingVariable { ref variable, .. } if variable == "user"),
+            "{err:?}"
+        );
+
+        let partials = HashMap::from([("sig".to_string(), "-- {{ agent }}".to_string())]);
+        let err = render("p", "{% include 'sig' %}", &json!({}), &partials).unwrap_err();
+        assert!(
+            matches!(err, PromptError::MissingVariable { ref variable, .. } if variable == "agent"),
+            "{err:?}"
+        );
+
+        let err = render("p", "{% include 'nope' %}", &json!({}), &HashMap::new()).unwrap_err();
+        assert!(
+            matches!(err, PromptError::UnknownPartial { ref partial, .. } if partial == "nope"),
+            "{err:?}"
+        );
+
+        let err = render("p", "Hi {{user", &json!({"user": "x"}), &HashMap::new()).unwrap_err();
+        assert!(matches!(err, PromptError::Syntax { .. }));
+    }
+
+    #[test]
+    fn test_render_rejects_recursive_partials() {
+        let partials = HashMap::from([("loop".to_string(), "{% include 'loop' %}".to_string())]);
+        let err = render("p", "{% include 'loop' %}", &json!({}), &partials).unwrap_err();
+        assert!(matches!(err, PromptError::Syntax { .. }));
+    }
+
+    #[test]
+    fn test_variables_lists_each_name_once() {
+        assert_eq!(
+            variables("{{ b }} {% include 'part' %} {{ a }} {{ b }}"),
+            vec!["a".to_string(), "b".to_string()]
+        );
+    }
+}
@@ -1 +1,3 @@
+#[cfg(feature = "pdf")]
+pub mod pdf_reader;
 pub mod simple_directory_reader;
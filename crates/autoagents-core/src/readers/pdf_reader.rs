@@ -0,0 +1,425 @@
+use std::fs;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::Path;
+use std::sync::Arc;
+
+use pdf_extract::{Document as PdfDocument, Object, PlainTextOutput, decode_text_string};
+use serde_json::{Map, Value};
+
+use super::simple_directory_reader::{FileReader, ReaderError};
+use crate::document::Document;
+
+const INFO_FIELDS: [(&[u8], &str); 6] = [
+    (b"Title", "title"),
+    (b"Author", "author"),
+    (b"Subject", "subject"),
+    (b"Creator", "creator"),
+    (b"Producer", "producer"),
+    (b"CreationDate", "creation_date"),
+];
+
+/// Recognizes the text of a rendered PDF page
+///
+/// Used by [`PdfReader`] for pages whose text layer is missing or too short,
+/// such as scanned documents.
+pub trait PdfOcr: Send + Sync {
+    /// Text of page `page` (1-based) of the PDF in `pdf`
+    fn recognize_page(&self, pdf: &[u8], page: u32) -> Result<String, String>;
+}
+
+/// Extracts the text layer of PDF files, one document per page by default
+///
+/// Every document carries `source`, `page_count` and, when split by page,
+/// `page` metadata, plus the title, author, subject, creator, producer and
+/// creation date from the PDF info dictionary when present. Pages without
+/// text are skipped unless an OCR fallback recovers them, in which case the
+/// document is marked with `ocr: true`. Pages whose text layer cannot be
+/// extracted, which `pdf_extract` reports by panicking on many real files,
+/// go to the OCR fallback too, or are skipped with a warning without one.
+///
+/// Register it with
+/// [`SimpleDirectoryReader::with_file_reader`](super::simple_directory_reader::SimpleDirectoryReader::with_file_reader)
+/// to ingest directories that mix PDFs and text files.
+#[derive(Clone)]
+pub struct PdfReader {
+    split_pages: bool,
+    password: String,
+    ocr: Option<Arc<dyn PdfOcr>>,
+    ocr_min_chars: usize,
+}
+
+impl Default for PdfReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfReader {
+    pub fn new() -> Self {
+        Self {
+            split_pages: true,
+            password: String::new(),
+            ocr: None,
+            ocr_min_chars: 1,
+        }
+    }
+
+    /// Return one document per page (default) or one for the whole file
+    pub fn split_pages(mut self, split_pages: bool) -> Self {
+        self.split_pages = split_pages;
+        self
+    }
+
+    /// Password used to decrypt encrypted PDFs (default: empty)
+    pub fn with_password(mut self, password: impl Into<String>) -> Self {
+        self.password = password.into();
+        self
+    }
+
+    /// Run `ocr` on pages whose extracted text is shorter than `ocr_min_chars`
+    pub fn with_ocr(mut self, ocr: Arc<dyn PdfOcr>) -> Self {
+        self.ocr = Some(ocr);
+        self
+    }
+
+    /// Minimum number of non-whitespace characters for a page to skip OCR (default: 1)
+    pub fn ocr_min_chars(mut self, ocr_min_chars: usize) -> Self {
+        self.ocr_min_chars = ocr_min_chars;
+        self
+    }
+
+    pub fn load_file(&self, path: impl AsRef<Path>) -> Result<Vec<Document>, ReaderError> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|source| ReaderError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        self.load_bytes(&bytes, path)
+    }
+
+    /// Extract documents from an in-memory PDF; `source` is recorded as metadata
+    pub fn load_bytes(
+        &self,
+        bytes: &[u8],
+        source: impl AsRef<Path>,
+    ) -> Result<Vec<Document>, ReaderError> {
+        let source = source.as_ref();
+        let parse_error = |message: String| ReaderError::Parse {
+            path: source.to_path_buf(),
+            message,
+        };
+
+        let mut pdf = PdfDocument::load_mem(bytes).map_err(|err| parse_error(err.to_string()))?;
+        if pdf.is_encrypted() {
+            pdf.decrypt(&self.password)
+                .map_err(|err| parse_error(format!("failed to decrypt: {err}")))?;
+        }
+
+        let pages: Vec<u32> = pdf.get_pages().into_keys().collect();
+        let mut metadata = info_metadata(&pdf);
+        metadata.insert(
+            "source".into(),
+            Value::String(source.to_string_lossy().to_string()),
+        );
+        metadata.insert("page_count".into(), pages.len().into());
+
+        let mut texts = Vec::with_capacity(pages.len());
+        for page in pages {
+            let text = extract_page(&pdf, page)
+                .map_err(|message| parse_error(format!("page {page}: {message}")));
+            let needs_ocr = match &text {
+                Ok(text) => non_whitespace_len(text) < self.ocr_min_chars,
+                Err(_) => true,
+            };
+
+            let (text, used_ocr) = match (&self.ocr, text) {
+                (Some(ocr), _) if needs_ocr => {
+                    let text =
+                        ocr.recognize_page(bytes, page)
+                            .map_err(|message| ReaderError::Ocr {
+                                path: source.to_path_buf(),
+                                page,
+                                message,
+                            })?;
+                    (text, true)
+                }
+                (_, Ok(text)) => (text, false),
+                (_, Err(err)) => {
+                    log::warn!("Skipping unreadable PDF page: {err}");
+                    continue;
+                }
+            };
+            texts.push((page, text.trim().to_string(), used_ocr));
+        }
+
+        if !self.split_pages {
+            let used_ocr = texts.iter().any(|(_, _, ocr)| *ocr);
+            let content = texts
+                .into_iter()
+                .map(|(_, text, _)| text)
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            if used_ocr {
+                metadata.insert("ocr".into(), Value::Bool(true));
+            }
+            return Ok(vec![Document::with_metadata(
+                content,
+                Value::Object(metadata),
+            )]);
+        }
+
+        Ok(texts
+            .into_iter()
+            .filter(|(_, text, _)| !text.is_empty())
+            .map(|(page, text, used_ocr)| {
+                let mut metadata = metadata.clone();
+                metadata.insert("page".into(), page.into());
+                if used_ocr {
+                    metadata.insert("ocr".into(), Value::Bool(true));
+                }
+                Document::with_metadata(text, Value::Object(metadata))
+            })
+            .collect())
+    }
+}
+
+impl FileReader for PdfReader {
+    fn extensions(&self) -> &[&str] {
+        &["pdf"]
+    }
+
+    fn read_file(&self, path: &Path) -> Result<Vec<Document>, ReaderError> {
+        self.load_file(path)
+    }
+}
+
+/// Text layer of `page`, with a panic inside `pdf_extract` reported as an error
+fn extract_page(pdf: &PdfDocument, page: u32) -> Result<String, String> {
+    catch_unwind(AssertUnwindSafe(|| {
+        let mut text = String::new();
+        pdf_extract::output_doc_page(pdf, &mut PlainTextOutput::new(&mut text), page)
+            .map(|()| text)
+            .map_err(|err| err.to_string())
+    }))
+    .unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "text extraction panicked".to_string());
+        Err(format!("text extraction panicked: {message}"))
+    })
+}
+
+fn info_metadata(pdf: &PdfDocument) -> Map<String, Value> {
+    let mut metadata = Map::new();
+    let info = match pdf.trailer.get(b"Info") {
+        Ok(Object::Reference(id)) => pdf.get_dictionary(*id).ok(),
+        Ok(Object::Dictionary(dict)) => Some(dict),
+        _ => None,
+    };
+    let Some(info) = info else {
+        return metadata;
+    };
+
+    for (field, key) in INFO_FIELDS {
+        if let Ok(value) = info.get(field).and_then(decode_text_string) {
+            let value = value.trim_matches('\0').trim();
+            if !value.is_empty() {
+                metadata.insert(key.into(), Value::String(value.to_string()));
+            }
+        }
+    }
+    metadata
+}
+
+fn non_whitespace_len(text: &str) -> usize {
+    text.chars().filter(|c| !c.is_whitespace()).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::readers::simple_directory_reader::SimpleDirectoryReader;
+    use pdf_extract::content::{Content, Operation};
+    use pdf_extract::{Stream, dictionary};
+
+    /// Build a PDF with one page per entry; `None` makes a page without text
+    fn build_pdf(pages: &[Option<&str>]) -> Vec<u8> {
+        let mut doc = PdfDocument::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let font_id = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+        let resources_id = doc.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => font_id },
+        });
+
+        let mut kids = Vec::new();
+        for text in pages {
+            let mut operations = Vec::new();
+            if let Some(text) = text {
+                operations = vec![
+                    Operation::new("BT", vec![]),
+                    Operation::new("Tf", vec!["F1".into(), 24.into()]),
+                    Operation::new("Td", vec![72.into(), 700.into()]),
+                    Operation::new("Tj", vec![Object::string_literal(*text)]),
+                    Operation::new("ET", vec![]),
+                ];
+            }
+            let content = Content { operations };
+            let content_id = doc.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+            let page_id = doc.add_object(dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            });
+            kids.push(page_id.into());
+        }
+
+        let count = kids.len() as i64;
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => count,
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        let info_id = doc.add_object(dictionary! {
+            "Title" => Object::string_literal("Quarterly Report"),
+            "Author" => Object::string_literal("Finance Team"),
+        });
+        doc.trailer.set("Root", catalog_id);
+        doc.trailer.set("Info", info_id);
+
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    /// A two-page PDF whose second page uses a Type0 font without descendants
+    fn broken_font_pdf() -> Vec<u8> {
+        let pdf = build_pdf(&[Some("Readable page"), Some("Broken page")]);
+        let mut doc = PdfDocument::load_mem(&pdf).unwrap();
+        let pages = doc.get_pages();
+        let broken_font = doc.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type0",
+            "BaseFont" => "Broken",
+        });
+        let resources = doc.add_object(dictionary! {
+            "Font" => dictionary! { "F1" => broken_font },
+        });
+        doc.get_object_mut(pages[&2])
+            .unwrap()
+            .as_dict_mut()
+            .unwrap()
+            .set("Resources", resources);
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    struct FixedOcr;
+
+    impl PdfOcr for FixedOcr {
+        fn recognize_page(&self, _pdf: &[u8], page: u32) -> Result<String, String> {
+            Ok(format!("scanned page {page}"))
+        }
+    }
+
+    #[test]
+    fn test_pages_and_info_metadata() {
+        let pdf = build_pdf(&[Some("Revenue grew"), Some("Costs fell")]);
+        let docs = PdfReader::new().load_bytes(&pdf, "report.pdf").unwrap();
+
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].page_content, "Revenue grew");
+        assert_eq!(docs[1].page_content, "Costs fell");
+        assert_eq!(docs[1].metadata["page"], 2);
+        assert_eq!(docs[1].metadata["page_count"], 2);
+        assert_eq!(docs[0].metadata["source"], "report.pdf");
+        assert_eq!(docs[0].metadata["title"], "Quarterly Report");
+        assert_eq!(docs[0].metadata["author"], "Finance Team");
+        assert!(docs[0].metadata.get("ocr").is_none());
+
+        let whole = PdfReader::new()
+            .split_pages(false)
+            .load_bytes(&pdf, "report.pdf")
+            .unwrap();
+        assert_eq!(whole.len(), 1);
+        assert_eq!(whole[0].page_content, "Revenue grew\n\nCosts fell");
+        assert!(whole[0].metadata.get("page").is_none());
+    }
+
+    #[test]
+    fn test_ocr_fallback_for_pages_without_text() {
+        let pdf = build_pdf(&[Some("Typed page"), None]);
+
+        let docs = PdfReader::new().load_bytes(&pdf, "scan.pdf").unwrap();
+        assert_eq!(docs.len(), 1);
+
+        let docs = PdfReader::new()
+            .with_ocr(Arc::new(FixedOcr))
+            .load_bytes(&pdf, "scan.pdf")
+            .unwrap();
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[1].page_content, "scanned page 2");
+        assert_eq!(docs[1].metadata["ocr"], true);
+        assert!(docs[0].metadata.get("ocr").is_none());
+    }
+
+    #[test]
+    fn test_pages_that_panic_are_recovered_or_skipped() {
+        let pdf = broken_font_pdf();
+        let doc = PdfDocument::load_mem(&pdf).unwrap();
+        let err = extract_page(&doc, 2).unwrap_err();
+        assert!(err.starts_with("text extraction panicked"), "{err}");
+
+        let docs = PdfReader::new().load_bytes(&pdf, "broken.pdf").unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].page_content, "Readable page");
+
+        let docs = PdfReader::new()
+            .with_ocr(Arc::new(FixedOcr))
+            .load_bytes(&pdf, "broken.pdf")
+            .unwrap();
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[1].page_content, "scanned page 2");
+        assert_eq!(docs[1].metadata["ocr"], true);
+    }
+
+    #[test]
+    fn test_invalid_pdf_and_directory_reader() {
+        let err = PdfReader::new()
+            .load_bytes(b"not a pdf", "bad.pdf")
+            .unwrap_err();
+        assert!(matches!(err, ReaderError::Parse { .. }));
+
+        let dir = std::env::temp_dir().join("autoagents_test_pdf_reader");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("doc.pdf"), build_pdf(&[Some("Hello PDF")])).unwrap();
+
+        let docs = SimpleDirectoryReader::new(&dir)
+            .with_file_reader(Arc::new(PdfReader::new()))
+            .load_data()
+            .unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].page_content, "Hello PDF");
+        assert_eq!(docs[0].metadata["source"], "doc.pdf");
+        assert_eq!(docs[0].metadata["extension"], "pdf");
+        assert_eq!(docs[0].metadata["page"], 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
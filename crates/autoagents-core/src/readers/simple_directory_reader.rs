@@ -1,9 +1,11 @@
 use std::collections::HashSet;
 use std::ffi::OsStr;
+use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use serde_json::json;
+use serde_json::{Value, json};
 use walkdir::WalkDir;
 
 use crate::document::Document;
@@ -21,13 +23,49 @@ pub enum ReaderError {
 
     #[error("File {0:?} is not valid UTF-8")]
     Utf8(PathBuf),
+
+    #[error("Failed to parse file {path:?}: {message}")]
+    Parse { path: PathBuf, message: String },
+
+    #[error("OCR failed for page {page} of {path:?}: {message}")]
+    Ocr {
+        path: PathBuf,
+        page: u32,
+        message: String,
+    },
 }
 
-#[derive(Clone, Debug)]
+/// Turns a file that is not plain text into documents
+///
+/// [`SimpleDirectoryReader`] hands files with one of the reader's extensions
+/// to it instead of reading them as UTF-8, then adds its own `source`,
+/// `absolute_path`, `extension` and `modified` metadata to every document
+/// returned.
+pub trait FileReader: Send + Sync {
+    /// Extensions (without dots) this reader handles, compared case-insensitively
+    fn extensions(&self) -> &[&str];
+
+    fn read_file(&self, path: &Path) -> Result<Vec<Document>, ReaderError>;
+}
+
+#[derive(Clone)]
 pub struct SimpleDirectoryReader {
     root: PathBuf,
     recursive: bool,
     extensions: Option<HashSet<String>>,
+    file_readers: Vec<Arc<dyn FileReader>>,
+}
+
+impl fmt::Debug for SimpleDirectoryReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let readers: Vec<&[&str]> = self.file_readers.iter().map(|r| r.extensions()).collect();
+        f.debug_struct("SimpleDirectoryReader")
+            .field("root", &self.root)
+            .field("recursive", &self.recursive)
+            .field("extensions", &self.extensions)
+            .field("file_readers", &readers)
+            .finish()
+    }
 }
 
 impl SimpleDirectoryReader {
@@ -36,6 +74,7 @@ impl SimpleDirectoryReader {
             root: root.into(),
             recursive: true,
             extensions: None,
+            file_readers: Vec::new(),
         }
     }
 
@@ -54,6 +93,24 @@ impl SimpleDirectoryReader {
         self
     }
 
+    /// Read files with the reader's extensions through `reader`
+    ///
+    /// Readers registered later take precedence for the same extension.
+    pub fn with_file_reader(mut self, reader: Arc<dyn FileReader>) -> Self {
+        self.file_readers.push(reader);
+        self
+    }
+
+    fn file_reader_for(&self, path: &Path) -> Option<&Arc<dyn FileReader>> {
+        let ext = path.extension().and_then(OsStr::to_str)?;
+        self.file_readers.iter().rev().find(|reader| {
+            reader
+                .extensions()
+                .iter()
+                .any(|candidate| candidate.eq_ignore_ascii_case(ext))
+        })
+    }
+
     pub fn load_data(&self) -> Result<Vec<Document>, ReaderError> {
         if !self.root.exists() {
             return Err(ReaderError::MissingPath(self.root.clone()));
@@ -91,6 +148,26 @@ impl SimpleDirectoryReader {
                 }
             }
 
+            let relative = path_relative_to(entry.path(), &self.root)
+                .unwrap_or_else(|| entry.file_name().to_string_lossy().to_string());
+
+            let mut metadata = json!({
+                "source": relative,
+                "absolute_path": entry.path().to_string_lossy(),
+                "extension": entry.path().extension().and_then(OsStr::to_str).unwrap_or_default(),
+            });
+            if let Some(modified) = modified_secs(&entry) {
+                metadata["modified"] = modified.into();
+            }
+
+            if let Some(reader) = self.file_reader_for(entry.path()) {
+                for mut doc in reader.read_file(entry.path())? {
+                    merge_metadata(&mut doc.metadata, &metadata);
+                    docs.push(doc);
+                }
+                continue;
+            }
+
             let content = match fs::read_to_string(entry.path()) {
                 Ok(content) => content,
                 Err(err) if err.kind() == std::io::ErrorKind::InvalidData => {
@@ -104,15 +181,6 @@ impl SimpleDirectoryReader {
                 }
             };
 
-            let relative = path_relative_to(entry.path(), &self.root)
-                .unwrap_or_else(|| entry.file_name().to_string_lossy().to_string());
-
-            let metadata = json!({
-                "source": relative,
-                "absolute_path": entry.path().to_string_lossy(),
-                "extension": entry.path().extension().and_then(OsStr::to_str).unwrap_or_default(),
-            });
-
             docs.push(Document::with_metadata(content, metadata));
         }
 
@@ -120,6 +188,25 @@ impl SimpleDirectoryReader {
     }
 }
 
+/// Modification time of the file in seconds since the Unix epoch
+fn modified_secs(entry: &walkdir::DirEntry) -> Option<u64> {
+    let modified = entry.metadata().ok()?.modified().ok()?;
+    modified
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|elapsed| elapsed.as_secs())
+}
+
+fn merge_metadata(target: &mut Value, extra: &Value) {
+    let Value::Object(extra) = extra else {
+        return;
+    };
+    match target {
+        Value::Object(map) => map.extend(extra.clone()),
+        _ => *target = Value::Object(extra.clone()),
+    }
+}
+
 fn path_relative_to(path: &Path, base: &Path) -> Option<String> {
     path.strip_prefix(base)
         .ok()
@@ -160,6 +247,7 @@ mod tests {
         assert_eq!(docs.len(), 1);
         assert_eq!(docs[0].page_content, "hello world");
         assert_eq!(docs[0].metadata["extension"], "txt");
+        assert!(docs[0].metadata["modified"].as_u64().unwrap() > 0);
 
         fs::remove_dir_all(&dir).ok();
     }
@@ -224,6 +312,41 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    struct UpperReader;
+
+    impl FileReader for UpperReader {
+        fn extensions(&self) -> &[&str] {
+            &["up"]
+        }
+
+        fn read_file(&self, path: &Path) -> Result<Vec<Document>, ReaderError> {
+            let content = fs::read_to_string(path).unwrap();
+            Ok(vec![Document::with_metadata(
+                content.to_uppercase(),
+                json!({"source": "ignored", "page": 1}),
+            )])
+        }
+    }
+
+    #[test]
+    fn test_file_reader_handles_matching_extension() {
+        let dir = std::env::temp_dir().join("autoagents_test_file_reader");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.UP"), "shout").unwrap();
+        fs::write(dir.join("b.txt"), "plain").unwrap();
+
+        let reader = SimpleDirectoryReader::new(&dir).with_file_reader(Arc::new(UpperReader));
+        let mut docs = reader.load_data().unwrap();
+        docs.sort_by(|a, b| a.page_content.cmp(&b.page_content));
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].page_content, "SHOUT");
+        assert_eq!(docs[0].metadata["source"], "a.UP");
+        assert_eq!(docs[0].metadata["page"], 1);
+        assert_eq!(docs[1].page_content, "plain");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_path_relative_to_fn() {
         let result = path_relative_to(Path::new("/a/b/c.txt"), Path::new("/a/b"));
@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use autoagents_llm::embedding::EmbeddingProvider;
+use autoagents_llm::error::LLMError;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Embedding provider that places text by which keywords it mentions
+///
+/// Each keyword is one dimension: `1.0` when the text contains it, plus
+/// `0.01` so texts without any keyword still have a direction. A
+/// [`flat`](Self::flat) provider has no keywords and embeds every text the
+/// same, making all documents equal candidates. The number of texts embedded
+/// is counted, so tests can check what was (re-)embedded.
+#[derive(Debug, Default)]
+pub struct KeywordEmbeddingProvider {
+    keywords: Vec<String>,
+    embedded: AtomicUsize,
+}
+
+impl KeywordEmbeddingProvider {
+    pub fn new<K: Into<String>>(keywords: impl IntoIterator<Item = K>) -> Self {
+        Self {
+            keywords: keywords.into_iter().map(Into::into).collect(),
+            embedded: AtomicUsize::new(0),
+        }
+    }
+
+    /// A provider that gives every text the vector `[1.0]`
+    pub fn flat() -> Self {
+        Self::default()
+    }
+
+    /// Texts embedded so far
+    pub fn embedded(&self) -> usize {
+        self.embedded.load(Ordering::SeqCst)
+    }
+
+    fn vector(&self, text: &str) -> Vec<f32> {
+        if self.keywords.is_empty() {
+            return vec![1.0];
+        }
+        self.keywords
+            .iter()
+            .map(|word| text.contains(word.as_str()) as u8 as f32 + 0.01)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for KeywordEmbeddingProvider {
+    async fn embed(&self, input: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
+        self.embedded.fetch_add(input.len(), Ordering::SeqCst);
+        Ok(input.iter().map(|text| self.vector(text)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_keywords_are_dimensions_and_calls_are_counted() {
+        let provider = KeywordEmbeddingProvider::new(["rust", "python"]);
+        let vectors = provider
+            .embed(vec!["rust code".to_string(), "prose".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(vectors, vec![vec![1.01, 0.01], vec![0.01, 0.01]]);
+        assert_eq!(provider.embedded(), 2);
+
+        let flat = KeywordEmbeddingProvider::flat();
+        assert_eq!(flat.embed(vec!["rust".to_string()]).await.unwrap(), [[1.0]]);
+    }
+}
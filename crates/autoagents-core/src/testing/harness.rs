@@ -0,0 +1,369 @@
+use super::scripted::{ScriptedLLM, ScriptedRequest};
+use crate::agent::error::RunnableAgentError;
+use crate::agent::task::Task;
+use crate::agent::{
+    AgentBuilder, AgentDeriveT, AgentExecutor, AgentHooks, DirectAgent, DirectAgentHandle,
+};
+use crate::error::Error;
+use autoagents_protocol::Event;
+use futures::{FutureExt, StreamExt};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Runs an agent in-process against a [`ScriptedLLM`] and its real tools
+///
+/// Each [`run`](Self::run) returns a [`TestRun`] holding the output, the
+/// protocol events, the LLM calls the run made and assertion helpers.
+pub struct TestAgentHarness<T: AgentDeriveT + AgentExecutor + AgentHooks> {
+    handle: DirectAgentHandle<T>,
+    llm: Arc<ScriptedLLM>,
+}
+
+impl<T: AgentDeriveT + AgentExecutor + AgentHooks> TestAgentHarness<T> {
+    #[allow(clippy::result_large_err)]
+    pub async fn new(agent: T, llm: ScriptedLLM) -> Result<Self, Error> {
+        let llm = Arc::new(llm);
+        let handle = AgentBuilder::<_, DirectAgent>::new(agent)
+            .llm(llm.clone())
+            .build()
+            .await?;
+        Ok(Self { handle, llm })
+    }
+
+    /// The scripted LLM, e.g. to check that every reply was used
+    pub fn llm(&self) -> &ScriptedLLM {
+        &self.llm
+    }
+
+    /// Run one task and capture everything it did
+    pub async fn run(&mut self, task: Task) -> TestRun<<T as AgentDeriveT>::Output>
+    where
+        Value: From<<T as AgentExecutor>::Output>,
+        <T as AgentDeriveT>::Output: From<<T as AgentExecutor>::Output>,
+        <T as AgentExecutor>::Output: Clone,
+        <T as AgentExecutor>::Error: Into<RunnableAgentError>,
+    {
+        let seen_requests = self.llm.requests().len();
+        let output = self.handle.agent.run(task).await;
+
+        // The agent keeps its sender, so drain only what this run already emitted.
+        let mut events = Vec::new();
+        while let Some(Some(event)) = self.handle.rx.next().now_or_never() {
+            events.push(event);
+        }
+
+        TestRun {
+            output,
+            events,
+            llm_requests: self.llm.requests().split_off(seen_requests),
+            output_schema: self.handle.agent.inner().output_schema(),
+        }
+    }
+}
+
+/// A tool call executed during a [`TestRun`]
+#[derive(Debug, Clone)]
+pub struct RecordedToolCall {
+    pub id: String,
+    pub name: String,
+    pub arguments: Value,
+    /// The tool's JSON result, or the error message if it failed
+    pub result: Result<Value, String>,
+}
+
+/// Transcript of one [`TestAgentHarness::run`]
+#[derive(Debug)]
+pub struct TestRun<O> {
+    pub output: Result<O, RunnableAgentError>,
+    pub events: Vec<Event>,
+    pub llm_requests: Vec<ScriptedRequest>,
+    output_schema: Option<Value>,
+}
+
+impl<O: serde::Serialize> TestRun<O> {
+    /// The output of a successful run; panics with the error otherwise
+    pub fn output(&self) -> &O {
+        match &self.output {
+            Ok(output) => output,
+            Err(err) => panic!("agent run failed: {err}"),
+        }
+    }
+
+    /// Tool calls in the order they were requested, paired with their outcome
+    pub fn tool_calls(&self) -> Vec<RecordedToolCall> {
+        let mut calls: Vec<RecordedToolCall> = Vec::new();
+        for event in &self.events {
+            match event {
+                Event::ToolCallRequested {
+                    id,
+                    tool_name,
+                    arguments,
+                    ..
+                } => calls.push(RecordedToolCall {
+                    id: id.clone(),
+                    name: tool_name.clone(),
+                    arguments: serde_json::from_str(arguments)
+                        .unwrap_or_else(|_| Value::String(arguments.clone())),
+                    result: Err("tool did not finish".to_string()),
+                }),
+                Event::ToolCallCompleted { id, result, .. } => {
+                    if let Some(call) = calls.iter_mut().find(|call| call.id == *id) {
+                        call.result = Ok(result.clone());
+                    }
+                }
+                Event::ToolCallFailed { id, error, .. } => {
+                    if let Some(call) = calls.iter_mut().find(|call| call.id == *id) {
+                        call.result = Err(error.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+        calls
+    }
+
+    /// Assert that `name` was called with arguments matching `predicate`
+    pub fn assert_tool_called(
+        &self,
+        name: &str,
+        predicate: impl Fn(&Value) -> bool,
+    ) -> RecordedToolCall {
+        let calls = self.tool_calls();
+        calls
+            .iter()
+            .find(|call| call.name == name && predicate(&call.arguments))
+            .cloned()
+            .unwrap_or_else(|| {
+                let seen: Vec<String> = calls
+                    .iter()
+                    .map(|call| format!("{}({})", call.name, call.arguments))
+                    .collect();
+                panic!("no call to `{name}` matched; calls made: {seen:?}")
+            })
+    }
+
+    /// Assert that `name` was never called
+    pub fn assert_tool_not_called(&self, name: &str) {
+        if let Some(call) = self.tool_calls().iter().find(|call| call.name == name) {
+            panic!("`{name}` was called with {}", call.arguments);
+        }
+    }
+
+    /// Assert that the output fits the agent's structured output schema
+    ///
+    /// Checks `type`, `required`, `properties` and `items`; agents without an
+    /// output schema always pass.
+    pub fn assert_output_matches_schema(&self) {
+        let Some(schema) = self
+            .output_schema
+            .as_ref()
+            .and_then(|format| format.get("schema"))
+        else {
+            return;
+        };
+        let value = serde_json::to_value(self.output()).expect("output must serialize");
+        if let Err(mismatch) = check_schema(schema, &value, "output") {
+            panic!("{mismatch}");
+        }
+    }
+}
+
+fn check_schema(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(kind) => vec![kind.as_str()],
+            Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        let matches = |kind: &str| match kind {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            _ => true,
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|kind| matches(kind)) {
+            return Err(format!("{path}: expected {expected}, got {value}"));
+        }
+    }
+    if let Value::Object(fields) = value {
+        for required in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if !fields.contains_key(required) {
+                return Err(format!("{path}: missing required field `{required}`"));
+            }
+        }
+        if let Some(Value::Object(properties)) = schema.get("properties") {
+            for (name, property) in properties {
+                if let Some(field) = fields.get(name) {
+                    check_schema(property, field, &format!("{path}.{name}"))?;
+                }
+            }
+        }
+    }
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (index, item) in items.iter().enumerate() {
+            check_schema(item_schema, item, &format!("{path}[{index}]"))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentOutputT;
+    use crate::agent::prebuilt::executor::{ReActAgent, ReActAgentOutput};
+    use crate::tool::{ToolCallError, ToolRuntime, ToolT};
+    use async_trait::async_trait;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Debug)]
+    struct AddTool;
+
+    impl ToolT for AddTool {
+        fn name(&self) -> &str {
+            "add"
+        }
+
+        fn description(&self) -> &str {
+            "Add two numbers"
+        }
+
+        fn args_schema(&self) -> Value {
+            json!({
+                "type": "object",
+                "properties": {"a": {"type": "integer"}, "b": {"type": "integer"}},
+                "required": ["a", "b"]
+            })
+        }
+    }
+
+    #[async_trait]
+    impl ToolRuntime for AddTool {
+        async fn execute(&self, args: Value) -> Result<Value, ToolCallError> {
+            let a = args["a"].as_i64().unwrap_or_default();
+            let b = args["b"].as_i64().unwrap_or_default();
+            Ok(json!(a + b))
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct SumOutput {
+        sum: i64,
+    }
+
+    impl AgentOutputT for SumOutput {
+        fn output_schema() -> &'static str {
+            r#"{"type":"object","properties":{"sum":{"type":"integer"}},"required":["sum"]}"#
+        }
+
+        fn structured_output_format() -> Value {
+            json!({"name": "SumOutput", "schema": serde_json::from_str::<Value>(Self::output_schema()).unwrap()})
+        }
+    }
+
+    impl From<ReActAgentOutput> for SumOutput {
+        fn from(output: ReActAgentOutput) -> Self {
+            serde_json::from_str(&output.response).unwrap_or(SumOutput { sum: -1 })
+        }
+    }
+
+    #[derive(Debug)]
+    struct Calculator;
+
+    impl AgentDeriveT for Calculator {
+        type Output = SumOutput;
+
+        fn description(&self) -> &str {
+            "Add numbers with the add tool"
+        }
+
+        fn output_schema(&self) -> Option<Value> {
+            Some(SumOutput::structured_output_format())
+        }
+
+        fn name(&self) -> &str {
+            "calculator"
+        }
+
+        fn tools(&self) -> Vec<Box<dyn ToolT>> {
+            vec![Box::new(AddTool)]
+        }
+    }
+
+    impl AgentHooks for Calculator {}
+
+    async fn harness(llm: ScriptedLLM) -> TestAgentHarness<ReActAgent<Calculator>> {
+        TestAgentHarness::new(ReActAgent::new(Calculator), llm)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_harness_captures_tool_calls_and_output() {
+        let llm = ScriptedLLM::new()
+            .call_tool("add", json!({"a": 2, "b": 3}))
+            .reply(r#"{"sum": 5}"#);
+        let mut harness = harness(llm).await;
+
+        let run = harness.run(Task::new("What is 2 + 3?")).await;
+        assert_eq!(run.output().sum, 5);
+        run.assert_output_matches_schema();
+
+        let call = run.assert_tool_called("add", |args| args["a"] == 2);
+        assert_eq!(call.result, Ok(json!(5)));
+        run.assert_tool_not_called("subtract");
+
+        assert_eq!(run.llm_requests.len(), 2);
+        assert_eq!(run.llm_requests[0].tools, ["add"]);
+        assert_eq!(harness.llm().pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_harness_reports_failed_runs() {
+        let mut harness = harness(ScriptedLLM::new().fail("rate limited")).await;
+        let run = harness.run(Task::new("What is 2 + 3?")).await;
+        assert!(run.output.is_err());
+        assert!(run.tool_calls().is_empty());
+        assert!(
+            run.events
+                .iter()
+                .any(|event| matches!(event, Event::TaskError { .. }))
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "missing required field `sum`")]
+    async fn test_output_schema_mismatch_panics() {
+        let run = TestRun {
+            output: Ok(json!({"total": 5})),
+            events: Vec::new(),
+            llm_requests: Vec::new(),
+            output_schema: Some(<SumOutput as AgentOutputT>::structured_output_format()),
+        };
+        run.assert_output_matches_schema();
+    }
+
+    #[test]
+    fn test_check_schema_follows_nested_types() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"items": {"type": "array", "items": {"type": ["string", "null"]}}}
+        });
+        assert!(check_schema(&schema, &json!({"items": ["a", null]}), "output").is_ok());
+        assert_eq!(
+            check_schema(&schema, &json!({"items": ["a", 1]}), "output").unwrap_err(),
+            r#"output.items[1]: expected ["string","null"], got 1"#
+        );
+    }
+}
@@ -0,0 +1,15 @@
+//! In-process testing of agents against a scripted LLM
+//!
+//! [`ScriptedLLM`] replays a fixed sequence of replies, tool calls and stream
+//! chunks, with optional errors and latency, and records every chat call;
+//! [`TestAgentHarness`] runs an agent on it with its real tools and returns a
+//! [`TestRun`] transcript with assertion helpers. [`KeywordEmbeddingProvider`]
+//! gives vector store tests predictable embeddings.
+
+mod embeddings;
+mod harness;
+mod scripted;
+
+pub use embeddings::KeywordEmbeddingProvider;
+pub use harness::{RecordedToolCall, TestAgentHarness, TestRun};
+pub use scripted::{ScriptedLLM, ScriptedReply, ScriptedRequest};
@@ -0,0 +1,490 @@
+use async_trait::async_trait;
+use autoagents_llm::chat::{
+    ChatMessage, ChatProvider, ChatResponse, StreamChoice, StreamChunk, StreamDelta,
+    StreamResponse, StructuredOutputFormat, Tool,
+};
+use autoagents_llm::completion::{CompletionProvider, CompletionRequest, CompletionResponse};
+use autoagents_llm::embedding::EmbeddingProvider;
+use autoagents_llm::error::LLMError;
+use autoagents_llm::models::ModelsProvider;
+use autoagents_llm::{FunctionCall, LLMProvider, ToolCall};
+use futures::Stream;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::time::Duration;
+
+type ScriptedStream<T> = Pin<Box<dyn Stream<Item = Result<T, LLMError>> + Send>>;
+
+/// One scripted reply of a [`ScriptedLLM`]
+#[derive(Debug, Clone)]
+pub enum ScriptedReply {
+    /// A plain text answer
+    Text(String),
+    /// A request to run the given tool calls
+    ToolCalls(Vec<ToolCall>),
+    /// Streaming chunks, delivered as they are; an `Err` item breaks the
+    /// stream at that point
+    Stream(Vec<Result<StreamChunk, LLMError>>),
+    /// A provider failure
+    Error(LLMError),
+}
+
+/// LLM provider that answers chat calls from a fixed script
+///
+/// Replies are returned in order, one per chat call, whether the call streams
+/// or not: text and tool-call replies are turned into chunks for streaming
+/// calls, and a [`ScriptedReply::Stream`] is assembled into one response for
+/// plain ones. Once the script runs out every further call fails. The
+/// messages and tool names of each call are recorded so tests can inspect
+/// and assert on what the agent sent.
+#[derive(Debug, Default)]
+pub struct ScriptedLLM {
+    replies: Mutex<VecDeque<ScriptedReply>>,
+    requests: Mutex<Vec<ScriptedRequest>>,
+    latency: Duration,
+}
+
+/// What an agent sent on one chat call
+#[derive(Debug, Clone)]
+pub struct ScriptedRequest {
+    pub messages: Vec<ChatMessage>,
+    /// Names of the tools offered on this call
+    pub tools: Vec<String>,
+    /// Whether the call asked for a streamed response
+    pub streamed: bool,
+}
+
+impl ScriptedLLM {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a text reply
+    pub fn reply(self, text: impl Into<String>) -> Self {
+        self.push(ScriptedReply::Text(text.into()))
+    }
+
+    /// Queue a reply that calls one tool with JSON arguments
+    pub fn call_tool(self, name: impl Into<String>, arguments: Value) -> Self {
+        self.call_tools([(name, arguments)])
+    }
+
+    /// Queue one reply that calls several tools at once, in order
+    pub fn call_tools<N: Into<String>>(self, calls: impl IntoIterator<Item = (N, Value)>) -> Self {
+        let reply = self.pending();
+        let calls = calls
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, arguments))| ToolCall {
+                id: format!("call_{reply}_{index}"),
+                call_type: "function".to_string(),
+                function: FunctionCall {
+                    name: name.into(),
+                    arguments: arguments.to_string(),
+                },
+            })
+            .collect();
+        self.push(ScriptedReply::ToolCalls(calls))
+    }
+
+    /// Queue a reply streamed as exactly these chunks
+    pub fn stream(self, chunks: impl IntoIterator<Item = StreamChunk>) -> Self {
+        self.push(ScriptedReply::Stream(chunks.into_iter().map(Ok).collect()))
+    }
+
+    /// Queue a streamed reply that breaks with `error` after `chunks`
+    pub fn stream_then_fail(
+        self,
+        chunks: impl IntoIterator<Item = StreamChunk>,
+        error: LLMError,
+    ) -> Self {
+        let mut items: Vec<_> = chunks.into_iter().map(Ok).collect();
+        items.push(Err(error));
+        self.push(ScriptedReply::Stream(items))
+    }
+
+    /// Queue a provider error
+    pub fn fail(self, message: impl Into<String>) -> Self {
+        self.fail_with(LLMError::ProviderError(message.into()))
+    }
+
+    /// Queue a specific error, e.g. a rate limit to exercise retries
+    pub fn fail_with(self, error: LLMError) -> Self {
+        self.push(ScriptedReply::Error(error))
+    }
+
+    /// Delay every call by `latency` before it answers
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Queue any reply
+    pub fn push(self, reply: ScriptedReply) -> Self {
+        self.replies.lock().unwrap().push_back(reply);
+        self
+    }
+
+    /// Number of replies not yet consumed
+    pub fn pending(&self) -> usize {
+        self.replies.lock().unwrap().len()
+    }
+
+    /// Every chat call received so far, oldest first
+    pub fn requests(&self) -> Vec<ScriptedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Assert that exactly `count` chat calls were made
+    pub fn assert_calls(&self, count: usize) {
+        let made = self.requests.lock().unwrap().len();
+        assert_eq!(made, count, "expected {count} LLM calls, got {made}");
+    }
+
+    /// Assert that every scripted reply was consumed
+    pub fn assert_exhausted(&self) {
+        let pending = self.pending();
+        assert_eq!(pending, 0, "{pending} scripted replies were never used");
+    }
+
+    /// Assert that call `index` (0-based) matches `predicate`
+    pub fn assert_request(&self, index: usize, predicate: impl Fn(&ScriptedRequest) -> bool) {
+        let requests = self.requests();
+        let Some(request) = requests.get(index) else {
+            panic!("no LLM call {index}; {} calls were made", requests.len());
+        };
+        assert!(
+            predicate(request),
+            "LLM call {index} did not match: {request:?}"
+        );
+    }
+
+    async fn next_reply(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        streamed: bool,
+    ) -> Result<ScriptedReply, LLMError> {
+        self.requests.lock().unwrap().push(ScriptedRequest {
+            messages: messages.to_vec(),
+            tools: tools
+                .unwrap_or_default()
+                .iter()
+                .map(|tool| tool.function.name.clone())
+                .collect(),
+            streamed,
+        });
+        let reply = self.replies.lock().unwrap().pop_front();
+        if !self.latency.is_zero() {
+            tokio::time::sleep(self.latency).await;
+        }
+        match reply {
+            Some(ScriptedReply::Error(err)) => Err(err),
+            Some(reply) => Ok(reply),
+            None => Err(LLMError::ProviderError(
+                "scripted LLM has no replies left".to_string(),
+            )),
+        }
+    }
+
+    async fn next_chunks(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+    ) -> Result<Vec<Result<StreamChunk, LLMError>>, LLMError> {
+        Ok(into_chunks(self.next_reply(messages, tools, true).await?))
+    }
+}
+
+fn into_response(reply: ScriptedReply) -> Result<Box<dyn ChatResponse>, LLMError> {
+    let (text, tool_calls) = match reply {
+        ScriptedReply::Text(text) => (Some(text), None),
+        ScriptedReply::ToolCalls(calls) => (None, Some(calls)),
+        ScriptedReply::Stream(chunks) => {
+            let mut text = String::new();
+            let mut calls = Vec::new();
+            for chunk in chunks {
+                match chunk? {
+                    StreamChunk::Text(delta) => text.push_str(&delta),
+                    StreamChunk::ToolUseComplete { tool_call, .. } => calls.push(tool_call),
+                    _ => {}
+                }
+            }
+            (
+                (!text.is_empty()).then_some(text),
+                (!calls.is_empty()).then_some(calls),
+            )
+        }
+        ScriptedReply::Error(err) => return Err(err),
+    };
+    Ok(Box::new(ScriptedResponse { text, tool_calls }))
+}
+
+/// Chunks a provider would stream for `reply`, ending with `Done`
+fn into_chunks(reply: ScriptedReply) -> Vec<Result<StreamChunk, LLMError>> {
+    let done = |stop_reason: &str| {
+        Ok(StreamChunk::Done {
+            stop_reason: stop_reason.to_string(),
+        })
+    };
+    match reply {
+        ScriptedReply::Text(text) => vec![Ok(StreamChunk::Text(text)), done("end_turn")],
+        ScriptedReply::ToolCalls(calls) => {
+            let mut chunks = Vec::new();
+            for (index, tool_call) in calls.into_iter().enumerate() {
+                chunks.push(Ok(StreamChunk::ToolUseStart {
+                    index,
+                    id: tool_call.id.clone(),
+                    name: tool_call.function.name.clone(),
+                }));
+                chunks.push(Ok(StreamChunk::ToolUseInputDelta {
+                    index,
+                    partial_json: tool_call.function.arguments.clone(),
+                }));
+                chunks.push(Ok(StreamChunk::ToolUseComplete { index, tool_call }));
+            }
+            chunks.push(done("tool_use"));
+            chunks
+        }
+        ScriptedReply::Stream(chunks) => chunks,
+        ScriptedReply::Error(err) => vec![Err(err)],
+    }
+}
+
+/// The same chunk in the OpenAI-style shape of `chat_stream_struct`
+fn into_stream_response(chunk: StreamChunk) -> Option<StreamResponse> {
+    let delta = |content, reasoning_content, tool_calls| StreamResponse {
+        choices: vec![StreamChoice {
+            delta: StreamDelta {
+                content,
+                reasoning_content,
+                tool_calls,
+            },
+        }],
+        usage: None,
+    };
+    match chunk {
+        StreamChunk::Text(text) => Some(delta(Some(text), None, None)),
+        StreamChunk::ReasoningContent(text) => Some(delta(None, Some(text), None)),
+        StreamChunk::ToolUseComplete { tool_call, .. } => {
+            Some(delta(None, None, Some(vec![tool_call])))
+        }
+        StreamChunk::Usage(usage) => Some(StreamResponse {
+            choices: Vec::new(),
+            usage: Some(usage),
+        }),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+struct ScriptedResponse {
+    text: Option<String>,
+    tool_calls: Option<Vec<ToolCall>>,
+}
+
+impl fmt::Display for ScriptedResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text.as_deref().unwrap_or_default())
+    }
+}
+
+impl ChatResponse for ScriptedResponse {
+    fn text(&self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn tool_calls(&self) -> Option<Vec<ToolCall>> {
+        self.tool_calls.clone()
+    }
+}
+
+#[async_trait]
+impl ChatProvider for ScriptedLLM {
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        _json_schema: Option<StructuredOutputFormat>,
+    ) -> Result<Box<dyn ChatResponse>, LLMError> {
+        into_response(self.next_reply(messages, tools, false).await?)
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: &[ChatMessage],
+        _json_schema: Option<StructuredOutputFormat>,
+    ) -> Result<ScriptedStream<String>, LLMError> {
+        let chunks = self.next_chunks(messages, None).await?;
+        let texts = chunks.into_iter().filter_map(|chunk| match chunk {
+            Ok(StreamChunk::Text(text)) => Some(Ok(text)),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        });
+        Ok(Box::pin(futures::stream::iter(texts.collect::<Vec<_>>())))
+    }
+
+    async fn chat_stream_struct(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        _json_schema: Option<StructuredOutputFormat>,
+    ) -> Result<ScriptedStream<StreamResponse>, LLMError> {
+        let chunks = self.next_chunks(messages, tools).await?;
+        let responses = chunks
+            .into_iter()
+            .filter_map(|chunk| chunk.map(into_stream_response).transpose());
+        Ok(Box::pin(futures::stream::iter(
+            responses.collect::<Vec<_>>(),
+        )))
+    }
+
+    async fn chat_stream_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        _json_schema: Option<StructuredOutputFormat>,
+    ) -> Result<ScriptedStream<StreamChunk>, LLMError> {
+        let chunks = self.next_chunks(messages, tools).await?;
+        Ok(Box::pin(futures::stream::iter(chunks)))
+    }
+}
+
+#[async_trait]
+impl CompletionProvider for ScriptedLLM {
+    async fn complete(
+        &self,
+        _req: &CompletionRequest,
+        _json_schema: Option<StructuredOutputFormat>,
+    ) -> Result<CompletionResponse, LLMError> {
+        Err(LLMError::ProviderError(
+            "scripted LLM only answers chat calls".to_string(),
+        ))
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for ScriptedLLM {
+    async fn embed(&self, _input: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
+        Err(LLMError::ProviderError(
+            "scripted LLM only answers chat calls".to_string(),
+        ))
+    }
+}
+
+#[async_trait]
+impl ModelsProvider for ScriptedLLM {}
+
+impl LLMProvider for ScriptedLLM {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use serde_json::json;
+
+    fn user(text: &str) -> Vec<ChatMessage> {
+        vec![ChatMessage::user().content(text).build()]
+    }
+
+    #[tokio::test]
+    async fn test_replies_are_served_in_order_and_recorded() {
+        let llm = ScriptedLLM::new()
+            .call_tools([("add", json!({"a": 1})), ("sub", json!({"b": 2}))])
+            .reply("done")
+            .fail_with(LLMError::AuthError {
+                message: "bad key".to_string(),
+                status_code: Some(401),
+                response_body: None,
+            });
+
+        let response = llm.chat(&user("first"), None).await.unwrap();
+        let calls = response.tool_calls().unwrap();
+        let names: Vec<&str> = calls.iter().map(|c| c.function.name.as_str()).collect();
+        assert_eq!(names, ["add", "sub"]);
+        assert_ne!(calls[0].id, calls[1].id);
+
+        let response = llm.chat(&user("second"), None).await.unwrap();
+        assert_eq!(response.text().as_deref(), Some("done"));
+        assert!(matches!(
+            llm.chat(&user("third"), None).await,
+            Err(LLMError::AuthError { .. })
+        ));
+        assert!(llm.chat(&user("fourth"), None).await.is_err());
+
+        llm.assert_calls(4);
+        llm.assert_exhausted();
+        llm.assert_request(1, |request| {
+            !request.streamed && request.messages[0].content == "second"
+        });
+    }
+
+    #[tokio::test]
+    async fn test_streaming_calls_replay_scripts_and_convert_replies() {
+        let llm = ScriptedLLM::new()
+            .stream_then_fail(
+                [
+                    StreamChunk::Text("Hel".to_string()),
+                    StreamChunk::Text("lo".to_string()),
+                ],
+                LLMError::HttpError("connection reset".to_string()),
+            )
+            .call_tool("add", json!({"a": 1}))
+            .stream([StreamChunk::Text("Hi".to_string())]);
+
+        let items: Vec<_> = llm
+            .chat_stream_with_tools(&user("stream"), None, None)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert!(matches!(&items[1], Ok(StreamChunk::Text(text)) if text == "lo"));
+        assert!(matches!(items[2], Err(LLMError::HttpError(_))));
+
+        let chunks: Vec<StreamChunk> = llm
+            .chat_stream_with_tools(&user("tools"), None, None)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert!(matches!(
+            &chunks[2],
+            StreamChunk::ToolUseComplete { tool_call, .. } if tool_call.function.name == "add"
+        ));
+        assert!(matches!(
+            chunks.last(),
+            Some(StreamChunk::Done { stop_reason }) if stop_reason == "tool_use"
+        ));
+
+        let response = llm.chat(&user("plain"), None).await.unwrap();
+        assert_eq!(response.text().as_deref(), Some("Hi"));
+        llm.assert_request(0, |request| request.streamed);
+    }
+
+    #[tokio::test]
+    async fn test_struct_streams_carry_text_deltas() {
+        let llm = ScriptedLLM::new().reply("Bonjour");
+        let deltas: Vec<String> = llm
+            .chat_stream_struct(&user("hi"), None, None)
+            .await
+            .unwrap()
+            .filter_map(|chunk| async move { chunk.unwrap().choices[0].delta.content.clone() })
+            .collect()
+            .await;
+        assert_eq!(deltas, ["Bonjour"]);
+    }
+
+    #[tokio::test]
+    async fn test_latency_delays_each_call() {
+        let llm = ScriptedLLM::new()
+            .reply("slow")
+            .with_latency(Duration::from_millis(20));
+        let started = std::time::Instant::now();
+        llm.chat(&user("hi"), None).await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(20));
+    }
+}
@@ -1,3 +1,4 @@
+pub use autoagents_llm::ToolCall;
 use autoagents_llm::chat::{FunctionTool, Tool};
 pub use autoagents_protocol::ToolCallResult;
 use schemars::JsonSchema;
@@ -6,8 +7,15 @@ use serde_json::Value;
 use std::fmt::Debug;
 use std::sync::Arc;
 mod runtime;
+mod stream;
+mod validation;
 use async_trait::async_trait;
 pub use runtime::ToolRuntime;
+pub use stream::{
+    DEFAULT_CUTOFF_BYTES, DEFAULT_HEAD_BYTES, DEFAULT_TAIL_BYTES, ToolOutputLimit,
+    ToolOutputStream, collect_tool_output,
+};
+pub use validation::validate_args;
 
 #[cfg(feature = "wasmtime")]
 pub use runtime::{WasmRuntime, WasmRuntimeError};
@@ -19,6 +27,18 @@ pub enum ToolCallError {
 
     #[error("Serde Error {0}")]
     SerdeError(#[from] serde_json::Error),
+
+    /// Tool arguments did not match the input type; `field` is the path to the offending value
+    #[error("Invalid argument `{field}`: {source}")]
+    InvalidArgument {
+        field: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    /// Tool arguments violated a declared constraint such as `min` or `pattern`
+    #[error("Invalid argument `{field}`: {message}")]
+    ConstraintViolation { field: String, message: String },
 }
 
 pub trait ToolT: Send + Sync + Debug + ToolRuntime {
@@ -32,11 +52,41 @@ pub trait ToolT: Send + Sync + Debug + ToolRuntime {
     fn output_schema(&self) -> Option<Value> {
         None
     }
+    /// Check the arguments against the input constraints before `execute` runs.
+    fn validate_args(&self, _args: &Value) -> Result<(), ToolCallError> {
+        Ok(())
+    }
 }
 
 /// Marker trait for input types used by `#[derive(ToolInput)]` macros.
 pub trait ToolInputT {
     fn io_schema() -> &'static str;
+
+    /// Deserialize tool call arguments into the input type.
+    ///
+    /// Unlike `serde_json::from_value`, errors inside nested structs, enums
+    /// and lists name the offending field, e.g. `filter.range.min`, so the
+    /// LLM can correct its call.
+    fn from_args(args: Value) -> Result<Self, ToolCallError>
+    where
+        Self: Sized + DeserializeOwned,
+    {
+        serde_path_to_error::deserialize(args).map_err(|err| {
+            let field = err.path().to_string();
+            let source = err.into_inner();
+            if field == "." {
+                ToolCallError::SerdeError(source)
+            } else {
+                ToolCallError::InvalidArgument { field, source }
+            }
+        })
+    }
+
+    /// Check arguments against constraints declared with
+    /// `#[input(min, max, pattern, max_length)]`; see [`validate_args`].
+    fn validate_args(_args: &Value) -> Result<(), ToolCallError> {
+        Ok(())
+    }
 }
 
 /// Parsed schema hook generated by `#[derive(ToolInput)]` for use with `#[tool]`.
@@ -81,6 +131,18 @@ impl ToolRuntime for SharedTool {
     async fn execute(&self, args: Value) -> Result<Value, ToolCallError> {
         self.inner.execute(args).await
     }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn output_limit(&self) -> ToolOutputLimit {
+        self.inner.output_limit()
+    }
+
+    async fn execute_stream(&self, args: Value) -> Result<ToolOutputStream, ToolCallError> {
+        self.inner.execute_stream(args).await
+    }
 }
 
 impl ToolT for SharedTool {
@@ -99,6 +161,10 @@ impl ToolT for SharedTool {
     fn output_schema(&self) -> Option<Value> {
         self.inner.output_schema()
     }
+
+    fn validate_args(&self, args: &Value) -> Result<(), ToolCallError> {
+        self.inner.validate_args(args)
+    }
 }
 
 /// Helper function to convert Vec<Arc<dyn ToolT>> to Vec<Box<dyn ToolT>>
@@ -245,6 +311,40 @@ mod tests {
         assert!(matches!(error, ToolCallError::SerdeError(_)));
     }
 
+    #[test]
+    fn test_from_args_names_offending_field() {
+        #[derive(Debug, Deserialize)]
+        struct Range {
+            min: u32,
+        }
+        #[derive(Debug, Deserialize)]
+        struct Args {
+            ranges: Vec<Range>,
+        }
+        impl ToolInputT for Args {
+            fn io_schema() -> &'static str {
+                "{}"
+            }
+        }
+
+        let err = Args::from_args(json!({"ranges": [{"min": 1}, {"min": "two"}]})).unwrap_err();
+        match &err {
+            ToolCallError::InvalidArgument { field, .. } => assert_eq!(field, "ranges[1].min"),
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert!(
+            err.to_string()
+                .starts_with("Invalid argument `ranges[1].min`")
+        );
+
+        let err = Args::from_args(json!({})).unwrap_err();
+        assert!(matches!(err, ToolCallError::SerdeError(_)));
+        assert!(err.to_string().contains("missing field `ranges`"));
+
+        let args = Args::from_args(json!({"ranges": [{"min": 3}]})).unwrap();
+        assert_eq!(args.ranges[0].min, 3);
+    }
+
     #[test]
     fn test_tool_call_error_from_box_error() {
         let box_error: Box<dyn std::error::Error + Send + Sync> = "Test error".into();
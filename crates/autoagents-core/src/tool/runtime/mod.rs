@@ -1,5 +1,6 @@
-use super::ToolCallError;
+use super::{ToolCallError, ToolOutputLimit, ToolOutputStream};
 use async_trait::async_trait;
+use serde_json::Value;
 use std::fmt::Debug;
 
 #[cfg(feature = "wasmtime")]
@@ -16,4 +17,27 @@ pub trait ToolRuntime: Send + Sync + Debug {
     /// Execute the tool with the provided JSON arguments, returning a JSON
     /// value on success or a `ToolCallError` on failure.
     async fn execute(&self, args: serde_json::Value) -> Result<serde_json::Value, ToolCallError>;
+
+    /// Whether the executor should call `execute_stream` instead of `execute`.
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
+    /// How much of a streamed output is passed back to the LLM.
+    fn output_limit(&self) -> ToolOutputLimit {
+        ToolOutputLimit::default()
+    }
+
+    /// Execute the tool, producing its output as a stream of text chunks.
+    ///
+    /// The executor reads the stream within `output_limit` and drops it
+    /// once the cutoff is reached. The default runs `execute` and yields its result as
+    /// one chunk.
+    async fn execute_stream(&self, args: Value) -> Result<ToolOutputStream, ToolCallError> {
+        let output = match self.execute(args).await? {
+            Value::String(text) => text,
+            other => serde_json::to_string(&other)?,
+        };
+        Ok(Box::pin(futures::stream::once(async move { Ok(output) })))
+    }
 }
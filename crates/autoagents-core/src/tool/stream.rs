@@ -0,0 +1,200 @@
+use super::ToolCallError;
+use futures::{Stream, StreamExt};
+use std::pin::Pin;
+
+/// Text chunks produced by a streaming tool, in order
+pub type ToolOutputStream = Pin<Box<dyn Stream<Item = Result<String, ToolCallError>> + Send>>;
+
+/// Bytes kept from the start of streamed output by default
+pub const DEFAULT_HEAD_BYTES: usize = 16 * 1024;
+/// Bytes kept from the end of streamed output by default
+pub const DEFAULT_TAIL_BYTES: usize = 4 * 1024;
+/// Bytes read from a stream by default before it is cut off
+pub const DEFAULT_CUTOFF_BYTES: usize = 1024 * 1024;
+
+/// How much of a streamed tool output is passed back to the model
+///
+/// The start and the end of the output are kept and the middle is replaced
+/// by a note with the number of bytes left out, so memory use stays bounded
+/// however long the tool runs. Once `cutoff_bytes` have arrived the stream
+/// is dropped, which stops the tool early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolOutputLimit {
+    pub head_bytes: usize,
+    pub tail_bytes: usize,
+    pub cutoff_bytes: Option<usize>,
+}
+
+impl Default for ToolOutputLimit {
+    fn default() -> Self {
+        Self {
+            head_bytes: DEFAULT_HEAD_BYTES,
+            tail_bytes: DEFAULT_TAIL_BYTES,
+            cutoff_bytes: Some(DEFAULT_CUTOFF_BYTES),
+        }
+    }
+}
+
+impl ToolOutputLimit {
+    /// Keep the first `head_bytes` and the last `tail_bytes` of the output
+    pub fn new(head_bytes: usize, tail_bytes: usize) -> Self {
+        Self {
+            head_bytes,
+            tail_bytes,
+            ..Self::default()
+        }
+    }
+
+    /// Stop reading after `bytes`; `None` reads the stream to its end
+    pub fn with_cutoff(mut self, bytes: Option<usize>) -> Self {
+        self.cutoff_bytes = bytes;
+        self
+    }
+}
+
+/// Read a tool output stream into a single message within `limit`
+///
+/// An error from the stream fails the whole call, even if some output was
+/// already received.
+pub async fn collect_tool_output(
+    mut stream: ToolOutputStream,
+    limit: &ToolOutputLimit,
+) -> Result<String, ToolCallError> {
+    let mut head = String::new();
+    let mut head_full = limit.head_bytes == 0;
+    let mut tail = String::new();
+    let mut received = 0usize;
+    let mut cut_off = false;
+
+    while let Some(chunk) = stream.next().await {
+        let mut chunk = chunk?;
+        if chunk.is_empty() {
+            continue;
+        }
+        if let Some(cutoff) = limit.cutoff_bytes {
+            let room = cutoff.saturating_sub(received);
+            if chunk.len() > room {
+                chunk.truncate(floor_char_boundary(&chunk, room));
+                cut_off = true;
+            }
+        }
+        received += chunk.len();
+
+        let mut rest = chunk.as_str();
+        if !head_full {
+            let split = floor_char_boundary(rest, limit.head_bytes - head.len());
+            head.push_str(&rest[..split]);
+            rest = &rest[split..];
+            head_full = !rest.is_empty() || head.len() == limit.head_bytes;
+        }
+        if !rest.is_empty() {
+            tail.push_str(rest);
+            if tail.len() > limit.tail_bytes {
+                let start = ceil_char_boundary(&tail, tail.len() - limit.tail_bytes);
+                tail.drain(..start);
+            }
+        }
+
+        if cut_off {
+            break;
+        }
+    }
+
+    let omitted = received - head.len() - tail.len();
+    let mut output = head;
+    if omitted > 0 {
+        output.push_str(&format!("\n[... {omitted} bytes omitted ...]\n"));
+    }
+    output.push_str(&tail);
+    if cut_off {
+        output.push_str(&format!("\n[output cut off after {received} bytes]"));
+    }
+    Ok(output)
+}
+
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn chunks(items: &[&str]) -> ToolOutputStream {
+        let items: Vec<Result<String, ToolCallError>> =
+            items.iter().map(|item| Ok(item.to_string())).collect();
+        Box::pin(stream::iter(items))
+    }
+
+    #[tokio::test]
+    async fn test_short_output_is_kept_whole() {
+        let output = collect_tool_output(chunks(&["line 1\n", "line 2\n"]), &Default::default())
+            .await
+            .unwrap();
+        assert_eq!(output, "line 1\nline 2\n");
+    }
+
+    #[tokio::test]
+    async fn test_long_output_keeps_head_and_tail() {
+        let limit = ToolOutputLimit::new(4, 3).with_cutoff(None);
+        let output = collect_tool_output(chunks(&["abc", "defgh", "ijkl"]), &limit)
+            .await
+            .unwrap();
+        assert_eq!(output, "abcd\n[... 5 bytes omitted ...]\njkl");
+    }
+
+    #[tokio::test]
+    async fn test_cutoff_stops_reading_the_stream() {
+        let polled = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&polled);
+        let stream: ToolOutputStream = Box::pin(stream::iter(0..100).map(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok("0123456789".to_string())
+        }));
+
+        let limit = ToolOutputLimit::new(5, 5).with_cutoff(Some(25));
+        let output = collect_tool_output(stream, &limit).await.unwrap();
+
+        assert_eq!(polled.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            output,
+            "01234\n[... 15 bytes omitted ...]\n01234\n[output cut off after 25 bytes]"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_truncation_respects_char_boundaries() {
+        let limit = ToolOutputLimit::new(2, 2).with_cutoff(None);
+        let output = collect_tool_output(chunks(&["éé", "xx", "éé"]), &limit)
+            .await
+            .unwrap();
+        assert_eq!(output, "é\n[... 6 bytes omitted ...]\né");
+    }
+
+    #[tokio::test]
+    async fn test_stream_error_fails_the_call() {
+        let stream: ToolOutputStream = Box::pin(stream::iter(vec![
+            Ok("partial".to_string()),
+            Err(ToolCallError::RuntimeError("disk gone".into())),
+        ]));
+        let err = collect_tool_output(stream, &Default::default())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("disk gone"));
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, PoisonError};
+
+use regex::Regex;
+use serde_json::Value;
+
+use super::ToolCallError;
+
+/// Check tool arguments against the constraints in a JSON schema
+///
+/// Only the keywords emitted by `#[input(min, max, pattern, max_length)]` are
+/// checked: `minimum`, `maximum`, `pattern`, `maxLength` and `maxItems`. The
+/// checker follows `properties`, `items` and externally tagged `oneOf`
+/// variants; type mismatches are left to deserialization.
+///
+/// Each `pattern` is compiled on first use and reused by later calls.
+/// `#[derive(ToolInput)]` rejects invalid patterns at compile time, so an
+/// invalid one can only come from a hand-written schema.
+pub fn validate_args(schema: &Value, args: &Value) -> Result<(), ToolCallError> {
+    check(schema, args, "")
+}
+
+fn check(schema: &Value, value: &Value, path: &str) -> Result<(), ToolCallError> {
+    let violation = |message: String| ToolCallError::ConstraintViolation {
+        field: if path.is_empty() {
+            ".".to_string()
+        } else {
+            path.to_string()
+        },
+        message,
+    };
+
+    match value {
+        Value::Number(number) => {
+            if let Some(number) = number.as_f64() {
+                if let Some(min) = schema.get("minimum").and_then(Value::as_f64)
+                    && number < min
+                {
+                    return Err(violation(format!("must be at least {min}, got {number}")));
+                }
+                if let Some(max) = schema.get("maximum").and_then(Value::as_f64)
+                    && number > max
+                {
+                    return Err(violation(format!("must be at most {max}, got {number}")));
+                }
+            }
+        }
+        Value::String(text) => {
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                let len = text.chars().count();
+                if len as u64 > max {
+                    return Err(violation(format!(
+                        "must be at most {max} characters, got {len}"
+                    )));
+                }
+            }
+            if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+                let regex = compiled(pattern)
+                    .map_err(|err| violation(format!("invalid pattern `{pattern}`: {err}")))?;
+                if !regex.is_match(text) {
+                    return Err(violation(format!("must match the pattern `{pattern}`")));
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+                && items.len() as u64 > max
+            {
+                return Err(violation(format!(
+                    "must have at most {max} items, got {}",
+                    items.len()
+                )));
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    check(item_schema, item, &format!("{path}[{index}]"))?;
+                }
+            }
+        }
+        Value::Object(fields) => {
+            if let Some(Value::Object(properties)) = schema.get("properties") {
+                for (name, property) in properties {
+                    if let Some(field) = fields.get(name) {
+                        let field_path = if path.is_empty() {
+                            name.clone()
+                        } else {
+                            format!("{path}.{name}")
+                        };
+                        check(property, field, &field_path)?;
+                    }
+                }
+            }
+            // Externally tagged enum: pick the variant whose only property is present.
+            if let Some(Value::Array(variants)) = schema.get("oneOf")
+                && let Some(variant) = variants.iter().find(|variant| {
+                    variant
+                        .get("properties")
+                        .and_then(Value::as_object)
+                        .is_some_and(|properties| {
+                            fields.keys().all(|key| properties.contains_key(key))
+                        })
+                })
+            {
+                check(variant, value, path)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// The regex for a `pattern` keyword, compiled once per distinct pattern
+fn compiled(pattern: &str) -> Result<Regex, String> {
+    static PATTERNS: LazyLock<Mutex<HashMap<String, Result<Regex, String>>>> =
+        LazyLock::new(Mutex::default);
+    let mut patterns = PATTERNS.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(regex) = patterns.get(pattern) {
+        return regex.clone();
+    }
+    let regex = Regex::new(pattern).map_err(|err| err.to_string());
+    if let Err(err) = &regex {
+        log::warn!("tool schema pattern `{pattern}` is invalid, so every value fails it: {err}");
+    }
+    patterns.insert(pattern.to_string(), regex.clone());
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "limit": {"type": "integer", "minimum": 1, "maximum": 50},
+                "query": {"type": "string", "maxLength": 5, "pattern": "^[a-z]+$"},
+                "tags": {"type": "array", "maxItems": 2, "items": {"type": "string", "maxLength": 3}},
+                "mode": {"oneOf": [
+                    {"type": "string", "enum": ["plain"]},
+                    {
+                        "type": "object",
+                        "properties": {"scaled": {
+                            "type": "object",
+                            "properties": {"ratio": {"type": "number", "minimum": 0.5}}
+                        }},
+                        "required": ["scaled"]
+                    }
+                ]}
+            }
+        })
+    }
+
+    fn violation(args: Value) -> (String, String) {
+        match validate_args(&schema(), &args).unwrap_err() {
+            ToolCallError::ConstraintViolation { field, message } => (field, message),
+            other => panic!("unexpected error: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_valid_and_missing_fields_pass() {
+        validate_args(
+            &schema(),
+            &json!({"limit": 50, "query": "abc", "tags": ["a"]}),
+        )
+        .unwrap();
+        validate_args(&schema(), &json!({"limit": null, "mode": "plain"})).unwrap();
+        validate_args(&schema(), &json!({})).unwrap();
+    }
+
+    #[test]
+    fn test_violations_name_the_field() {
+        assert_eq!(
+            violation(json!({"limit": 0})),
+            ("limit".into(), "must be at least 1, got 0".into())
+        );
+        assert_eq!(violation(json!({"limit": 51})).0, "limit");
+        assert_eq!(
+            violation(json!({"query": "abcdef"})).1,
+            "must be at most 5 characters, got 6"
+        );
+        assert_eq!(
+            violation(json!({"query": "AB"})).1,
+            "must match the pattern `^[a-z]+$`"
+        );
+        assert_eq!(violation(json!({"tags": ["a", "b", "c"]})).0, "tags");
+        assert_eq!(violation(json!({"tags": ["a", "long"]})).0, "tags[1]");
+        assert_eq!(
+            violation(json!({"mode": {"scaled": {"ratio": 0.1}}})).0,
+            "mode.scaled.ratio"
+        );
+    }
+
+    #[test]
+    fn test_violation_message_names_the_field() {
+        let err = validate_args(&schema(), &json!({"limit": 0})).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid argument `limit`: must be at least 1, got 0"
+        );
+    }
+
+    #[test]
+    fn test_patterns_are_compiled_once() {
+        let first = compiled("^[a-z]+$").unwrap();
+        let second = compiled("^[a-z]+$").unwrap();
+        assert_eq!(first.as_str(), second.as_str());
+
+        let schema = json!({"properties": {"code": {"type": "string", "pattern": "("}}});
+        for _ in 0..2 {
+            let err = validate_args(&schema, &json!({"code": "x"})).unwrap_err();
+            assert!(err.to_string().contains("invalid pattern `(`"), "{err}");
+        }
+    }
+}
@@ -67,6 +67,23 @@ impl<T> Stream for WasiDrivenStream<T> {
     }
 }
 
+/// Enter `span` whenever `stream` is polled, so logs emitted while the
+/// stream is consumed belong to it
+pub(crate) fn instrument_stream<S>(
+    mut stream: S,
+    span: tracing::Span,
+) -> impl Stream<Item = S::Item>
+where
+    S: Stream + Unpin,
+{
+    use futures::StreamExt;
+
+    futures::stream::poll_fn(move |cx| {
+        let _entered = span.enter();
+        stream.poll_next_unpin(cx)
+    })
+}
+
 // Platform-specific spawn functions
 #[cfg(not(target_arch = "wasm32"))]
 pub(crate) fn spawn_future<F>(fut: F) -> tokio::task::JoinHandle<F::Output>
@@ -92,8 +109,9 @@ where
     F: std::future::Future<Output = ()> + Send + 'static,
 {
     use tokio_stream::wrappers::ReceiverStream;
+    use tracing::Instrument;
 
-    spawn_future(producer);
+    spawn_future(producer.in_current_span());
     Box::pin(ReceiverStream::new(rx))
 }
 
@@ -103,7 +121,9 @@ where
     T: 'static + Send,
     F: std::future::Future<Output = ()> + 'static,
 {
-    spawn_future(producer);
+    use tracing::Instrument;
+
+    spawn_future(producer.in_current_span());
     Box::pin(rx)
 }
 
@@ -113,8 +133,10 @@ where
     T: 'static + Send,
     F: std::future::Future<Output = ()> + 'static,
 {
+    use tracing::Instrument;
+
     Box::pin(WasiDrivenStream {
-        producer: Box::pin(producer),
+        producer: Box::pin(producer.in_current_span()),
         receiver: Box::pin(rx),
         producer_done: false,
     })
@@ -165,4 +187,31 @@ mod tests {
         let values: Vec<_> = stream.collect().await;
         assert_eq!(values, vec![1, 2, 3]);
     }
+
+    fn current_span_name() -> Option<&'static str> {
+        tracing::Span::current()
+            .metadata()
+            .map(|metadata| metadata.name())
+    }
+
+    #[tokio::test]
+    async fn producers_and_consumers_stay_inside_the_run_span() {
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry());
+        let span = tracing::info_span!("run");
+
+        let (tx, rx) = channel(1);
+        let produced = span.in_scope(|| {
+            stream_from_producer(rx, async move {
+                tx.send(current_span_name()).await.expect("send span name");
+            })
+        });
+        let produced: Vec<_> = produced.collect().await;
+        assert_eq!(produced, vec![Some("run")]);
+
+        let polled =
+            futures::stream::poll_fn(|_| std::task::Poll::Ready(Some(current_span_name())));
+        let polled: Vec<_> = instrument_stream(polled.take(1), span).collect().await;
+        assert_eq!(polled, vec![Some("run")]);
+        assert_eq!(current_span_name(), None);
+    }
 }
@@ -0,0 +1,403 @@
+//! Batched inserts for large document sets
+//!
+//! [`BatchInserter`] splits documents into fixed-size batches, embeds and
+//! upserts a bounded number of them at a time through any
+//! [`VectorStoreIndex`], and retries batches that fail with a datastore
+//! error. Embedding calls are already retried by the store through
+//! [`embed_in_batches`](crate::embeddings::batch::embed_in_batches), so
+//! embedding errors are not retried again here. Streamed input is consumed
+//! as batches complete, so only `batch_size * concurrency` documents are
+//! held in memory at once.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+
+use super::{VectorStoreError, VectorStoreIndex};
+use crate::embeddings::Embed;
+use crate::embeddings::batch::{retry_delay, sleep};
+
+/// Default number of documents per batch
+pub const DEFAULT_INSERT_BATCH_SIZE: usize = 64;
+/// Default number of batches inserted at the same time
+pub const DEFAULT_INSERT_CONCURRENCY: usize = 4;
+
+/// Progress of a [`BatchInserter`] run, reported after every batch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchProgress {
+    /// Known up front for [`BatchInserter::insert`], `None` for streams
+    pub documents_total: Option<usize>,
+    pub documents_done: usize,
+    pub batches_done: usize,
+    /// Failed attempts that were retried so far
+    pub retries: usize,
+}
+
+/// Summary of a finished [`BatchInserter`] run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchInsertReport {
+    pub documents_inserted: usize,
+    pub batches: usize,
+    pub retries: usize,
+}
+
+type ProgressFn = dyn Fn(&BatchProgress) + Send + Sync;
+
+/// Inserts documents into a vector store in bounded, retried batches
+pub struct BatchInserter<'a, S: ?Sized> {
+    store: &'a S,
+    batch_size: usize,
+    concurrency: usize,
+    max_retries: u32,
+    retry_backoff: Duration,
+    progress: Option<Arc<ProgressFn>>,
+}
+
+impl<'a, S> BatchInserter<'a, S>
+where
+    S: VectorStoreIndex + ?Sized,
+{
+    pub fn new(store: &'a S) -> Self {
+        Self {
+            store,
+            batch_size: DEFAULT_INSERT_BATCH_SIZE,
+            concurrency: DEFAULT_INSERT_CONCURRENCY,
+            max_retries: 3,
+            retry_backoff: Duration::from_millis(500),
+            progress: None,
+        }
+    }
+
+    /// Documents embedded and upserted per store call
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Batches in flight at the same time
+    ///
+    /// A batch larger than the provider's input limits is split again by the
+    /// store into concurrent embedding calls; keep `batch_size` within those
+    /// limits so each batch costs a single provider call.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Retries per batch after a datastore error (default: 3)
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Delay before the first retry, doubled for each further one (default: 500ms)
+    pub fn with_retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    pub fn on_progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(&BatchProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Insert `documents` with their ids
+    pub async fn insert<T>(
+        &self,
+        documents: Vec<(String, T)>,
+    ) -> Result<BatchInsertReport, VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+    {
+        let total = documents.len();
+        self.run(futures::stream::iter(documents), Some(total))
+            .await
+    }
+
+    /// Insert documents as `documents` yields them
+    pub async fn insert_stream<T, D>(
+        &self,
+        documents: D,
+    ) -> Result<BatchInsertReport, VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+        D: Stream<Item = (String, T)>,
+    {
+        self.run(documents, None).await
+    }
+
+    async fn run<T, D>(
+        &self,
+        documents: D,
+        documents_total: Option<usize>,
+    ) -> Result<BatchInsertReport, VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+        D: Stream<Item = (String, T)>,
+    {
+        let mut progress = BatchProgress {
+            documents_total,
+            ..BatchProgress::default()
+        };
+        let pending = documents
+            .chunks(self.batch_size.max(1))
+            .map(|batch| self.insert_batch(batch))
+            .buffer_unordered(self.concurrency.max(1));
+        let mut pending = std::pin::pin!(pending);
+
+        while let Some(result) = pending.next().await {
+            let (inserted, retries) = result?;
+            progress.documents_done += inserted;
+            progress.batches_done += 1;
+            progress.retries += retries;
+            if let Some(callback) = &self.progress {
+                callback(&progress);
+            }
+        }
+
+        Ok(BatchInsertReport {
+            documents_inserted: progress.documents_done,
+            batches: progress.batches_done,
+            retries: progress.retries,
+        })
+    }
+
+    /// Insert one batch, returning its size and the number of retries it took
+    async fn insert_batch<T>(
+        &self,
+        batch: Vec<(String, T)>,
+    ) -> Result<(usize, usize), VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+    {
+        let len = batch.len();
+        let mut attempt = 0u32;
+        loop {
+            match self.store.insert_documents_with_ids(batch.clone()).await {
+                Ok(()) => return Ok((len, attempt as usize)),
+                Err(err @ VectorStoreError::DatastoreError(_)) if attempt < self.max_retries => {
+                    let delay = retry_delay(self.retry_backoff, attempt, None);
+                    log::warn!(
+                        "Inserting a batch of {len} documents failed (attempt {}): {err}. Retrying in {delay:?}.",
+                        attempt + 1
+                    );
+                    sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::Document;
+    use crate::embeddings::{EmbeddingError, SharedEmbeddingProvider};
+    use crate::testing::KeywordEmbeddingProvider;
+    use crate::vector_store::in_memory_store::InMemoryVectorStore;
+    use crate::vector_store::{NamedVectorDocument, VectorSearchRequest};
+    use async_trait::async_trait;
+    use autoagents_llm::error::LLMError;
+    use serde::Deserialize;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Wraps an in-memory store and fails the first `failures` inserts
+    struct FlakyStore {
+        inner: InMemoryVectorStore,
+        failures: AtomicUsize,
+        error: fn() -> VectorStoreError,
+        batch_sizes: Mutex<Vec<usize>>,
+    }
+
+    impl FlakyStore {
+        fn new(failures: usize, error: fn() -> VectorStoreError) -> Self {
+            let provider: SharedEmbeddingProvider = Arc::new(KeywordEmbeddingProvider::flat());
+            Self {
+                inner: InMemoryVectorStore::new(provider),
+                failures: AtomicUsize::new(failures),
+                error,
+                batch_sizes: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl VectorStoreIndex for FlakyStore {
+        type Filter = <InMemoryVectorStore as VectorStoreIndex>::Filter;
+
+        async fn insert_documents<T>(&self, documents: Vec<T>) -> Result<(), VectorStoreError>
+        where
+            T: Embed + Serialize + Send + Sync + Clone,
+        {
+            self.inner.insert_documents(documents).await
+        }
+
+        async fn insert_documents_with_ids<T>(
+            &self,
+            documents: Vec<(String, T)>,
+        ) -> Result<(), VectorStoreError>
+        where
+            T: Embed + Serialize + Send + Sync + Clone,
+        {
+            self.batch_sizes.lock().unwrap().push(documents.len());
+            if self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Err((self.error)());
+            }
+            self.inner.insert_documents_with_ids(documents).await
+        }
+
+        async fn top_n<T>(
+            &self,
+            req: VectorSearchRequest<Self::Filter>,
+        ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+        where
+            T: for<'de> Deserialize<'de> + Send + Sync,
+        {
+            self.inner.top_n(req).await
+        }
+
+        async fn top_n_ids(
+            &self,
+            req: VectorSearchRequest<Self::Filter>,
+        ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+            self.inner.top_n_ids(req).await
+        }
+
+        async fn insert_documents_with_named_vectors<T>(
+            &self,
+            documents: Vec<NamedVectorDocument<T>>,
+        ) -> Result<(), VectorStoreError>
+        where
+            T: Serialize + Send + Sync + Clone,
+        {
+            self.inner
+                .insert_documents_with_named_vectors(documents)
+                .await
+        }
+    }
+
+    fn documents(n: usize) -> Vec<(String, Document)> {
+        (0..n)
+            .map(|i| (format!("doc-{i}"), Document::new(format!("document {i}"))))
+            .collect()
+    }
+
+    fn unavailable() -> VectorStoreError {
+        VectorStoreError::DatastoreError("connection reset".into())
+    }
+
+    fn unsupported() -> VectorStoreError {
+        VectorStoreError::Unsupported("nope".to_string())
+    }
+
+    fn rate_limited() -> VectorStoreError {
+        VectorStoreError::EmbeddingError(EmbeddingError::Provider(LLMError::RateLimitError {
+            status_code: 429,
+            message: "slow down".to_string(),
+            response_body: "".into(),
+            retry_after: None,
+            provider_code: None,
+        }))
+    }
+
+    async fn stored(store: &FlakyStore) -> usize {
+        let req = VectorSearchRequest::builder()
+            .query("document")
+            .samples(100)
+            .build()
+            .unwrap();
+        store.top_n_ids(req).await.unwrap().len()
+    }
+
+    #[tokio::test]
+    async fn test_inserts_in_batches_and_reports_progress() {
+        let store = FlakyStore::new(0, unavailable);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorded = seen.clone();
+        let report = BatchInserter::new(&store)
+            .with_batch_size(4)
+            .with_concurrency(1)
+            .on_progress(move |progress| recorded.lock().unwrap().push(progress.clone()))
+            .insert(documents(10))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report,
+            BatchInsertReport {
+                documents_inserted: 10,
+                batches: 3,
+                retries: 0,
+            }
+        );
+        assert_eq!(*store.batch_sizes.lock().unwrap(), vec![4, 4, 2]);
+        let done: Vec<usize> = seen
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|p| p.documents_done)
+            .collect();
+        assert_eq!(done, vec![4, 8, 10]);
+        assert_eq!(seen.lock().unwrap()[0].documents_total, Some(10));
+        assert_eq!(stored(&store).await, 10);
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_are_retried() {
+        let store = FlakyStore::new(2, unavailable);
+        let report = BatchInserter::new(&store)
+            .with_batch_size(5)
+            .with_retry_backoff(Duration::from_millis(1))
+            .insert_stream(futures::stream::iter(documents(10)))
+            .await
+            .unwrap();
+
+        assert_eq!(report.documents_inserted, 10);
+        assert_eq!(report.retries, 2);
+        assert_eq!(store.batch_sizes.lock().unwrap().len(), 4);
+        assert_eq!(stored(&store).await, 10);
+    }
+
+    #[tokio::test]
+    async fn test_permanent_failures_and_exhausted_retries_fail() {
+        let store = FlakyStore::new(1, unsupported);
+        let err = BatchInserter::new(&store)
+            .insert(documents(3))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, VectorStoreError::Unsupported(_)));
+        assert_eq!(store.batch_sizes.lock().unwrap().len(), 1);
+
+        let store = FlakyStore::new(2, unavailable);
+        let result = BatchInserter::new(&store)
+            .with_max_retries(1)
+            .with_retry_backoff(Duration::from_millis(1))
+            .insert(documents(3))
+            .await;
+        assert!(matches!(result, Err(VectorStoreError::DatastoreError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_embedding_failures_are_not_retried_again() {
+        let store = FlakyStore::new(1, rate_limited);
+        let err = BatchInserter::new(&store)
+            .with_retry_backoff(Duration::from_millis(1))
+            .insert(documents(3))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, VectorStoreError::EmbeddingError(_)));
+        assert_eq!(store.batch_sizes.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::query_transform::{DEFAULT_RRF_K, reciprocal_rank_fusion};
+
+/// Name of the sparse vector stored next to the dense embedding
+pub const DEFAULT_SPARSE_VECTOR_NAME: &str = "sparse";
+
+/// A sparse term vector, with `indices` sorted and unique
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SparseVector {
+    pub indices: Vec<u32>,
+    pub values: Vec<f32>,
+}
+
+impl SparseVector {
+    /// Build from `(index, value)` pairs, summing values of repeated indices
+    pub fn from_pairs(pairs: impl IntoIterator<Item = (u32, f32)>) -> Self {
+        let mut merged: BTreeMap<u32, f32> = BTreeMap::new();
+        for (index, value) in pairs {
+            *merged.entry(index).or_default() += value;
+        }
+        let (indices, values) = merged.into_iter().unzip();
+        Self { indices, values }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    pub fn weights(&self) -> impl Iterator<Item = (u32, f32)> + '_ {
+        self.indices
+            .iter()
+            .copied()
+            .zip(self.values.iter().copied())
+    }
+}
+
+/// Turns text into sparse vectors for keyword retrieval
+///
+/// Implement this for learned encoders such as SPLADE; [`Bm25Encoder`] is
+/// the built-in lexical one. Documents and queries may be encoded
+/// differently.
+pub trait SparseEncoder: Send + Sync {
+    fn encode_document(&self, text: &str) -> SparseVector;
+
+    fn encode_query(&self, text: &str) -> SparseVector {
+        self.encode_document(text)
+    }
+}
+
+pub type SharedSparseEncoder = Arc<dyn SparseEncoder>;
+
+/// BM25 term weights over hashed words
+///
+/// Documents get the BM25 term-frequency component; queries get a weight
+/// of one per distinct term. The inverse document frequency depends on the
+/// whole collection, so the store applies it at search time. Words are
+/// lowercased runs of letters, digits and `_`, which keeps code identifiers
+/// intact, and are hashed into the `u32` index space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bm25Encoder {
+    k1: f32,
+    b: f32,
+    avg_doc_len: f32,
+}
+
+impl Default for Bm25Encoder {
+    fn default() -> Self {
+        Self {
+            k1: 1.2,
+            b: 0.75,
+            avg_doc_len: 256.0,
+        }
+    }
+}
+
+impl Bm25Encoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Term frequency saturation (default `1.2`)
+    pub fn k1(mut self, k1: f32) -> Self {
+        self.k1 = k1.max(0.0);
+        self
+    }
+
+    /// Document length normalization, in `0.0..=1.0` (default `0.75`)
+    pub fn b(mut self, b: f32) -> Self {
+        self.b = b.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Expected document length in words (default `256`)
+    pub fn avg_doc_len(mut self, words: f32) -> Self {
+        self.avg_doc_len = words.max(1.0);
+        self
+    }
+
+    fn term_counts(text: &str) -> (HashMap<u32, f32>, usize) {
+        let mut counts = HashMap::new();
+        let mut len = 0;
+        for word in text
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter(|word| !word.is_empty())
+        {
+            *counts.entry(term_index(&word.to_lowercase())).or_default() += 1.0;
+            len += 1;
+        }
+        (counts, len)
+    }
+}
+
+impl SparseEncoder for Bm25Encoder {
+    fn encode_document(&self, text: &str) -> SparseVector {
+        let (counts, len) = Self::term_counts(text);
+        let norm = self.k1 * (1.0 - self.b + self.b * len as f32 / self.avg_doc_len);
+        SparseVector::from_pairs(
+            counts
+                .into_iter()
+                .map(|(index, tf)| (index, tf * (self.k1 + 1.0) / (tf + norm))),
+        )
+    }
+
+    fn encode_query(&self, text: &str) -> SparseVector {
+        let (counts, _) = Self::term_counts(text);
+        SparseVector::from_pairs(counts.into_keys().map(|index| (index, 1.0)))
+    }
+}
+
+/// FNV-1a, so indices stay stable across processes and Rust versions
+fn term_index(term: &str) -> u32 {
+    term.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// Inverse document frequency as used by BM25 and Qdrant's `idf` modifier
+pub fn bm25_idf(documents: usize, containing: usize) -> f64 {
+    let (n, df) = (documents as f64, containing as f64);
+    (1.0 + (n - df + 0.5) / (df + 0.5)).ln()
+}
+
+/// How [`super::VectorStoreIndex::top_n_hybrid`] merges the dense and sparse rankings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum HybridFusion {
+    /// Reciprocal rank fusion with rank constant `k`; ignores raw scores
+    Rrf { k: f64 },
+    /// Min-max normalize each ranking, then weight the dense score by
+    /// `dense_weight` and the sparse score by `1 - dense_weight`
+    Weighted { dense_weight: f64 },
+}
+
+impl Default for HybridFusion {
+    fn default() -> Self {
+        Self::Rrf { k: DEFAULT_RRF_K }
+    }
+}
+
+/// Merge a dense and a sparse ranking into the `limit` best results
+///
+/// The dense payload is kept for results found by both searches.
+pub fn fuse_hybrid<T>(
+    dense: Vec<(f64, String, T)>,
+    sparse: Vec<(f64, String, T)>,
+    fusion: HybridFusion,
+    limit: usize,
+) -> Vec<(f64, String, T)> {
+    let dense_weight = match fusion {
+        HybridFusion::Rrf { k } => return reciprocal_rank_fusion(vec![dense, sparse], k, limit),
+        HybridFusion::Weighted { dense_weight } => dense_weight.clamp(0.0, 1.0),
+    };
+
+    let mut fused: HashMap<String, (f64, usize, T)> = HashMap::new();
+    let mut order = 0;
+    for (ranking, weight) in [(dense, dense_weight), (sparse, 1.0 - dense_weight)] {
+        let (min, max) = ranking.iter().fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(min, max), (score, ..)| (min.min(*score), max.max(*score)),
+        );
+        for (score, id, item) in ranking {
+            let normalized = if max > min {
+                (score - min) / (max - min)
+            } else {
+                1.0
+            };
+            match fused.get_mut(&id) {
+                Some(entry) => entry.0 += weight * normalized,
+                None => {
+                    fused.insert(id, (weight * normalized, order, item));
+                    order += 1;
+                }
+            }
+        }
+    }
+
+    let mut results: Vec<(f64, usize, String, T)> = fused
+        .into_iter()
+        .map(|(id, (score, order, item))| (score, order, id, item))
+        .collect();
+    results.sort_by(|a, b| {
+        b.0.partial_cmp(&a.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.1.cmp(&b.1))
+    });
+    results.truncate(limit);
+    results
+        .into_iter()
+        .map(|(score, _, id, item)| (score, id, item))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranking(items: &[(f64, &str)]) -> Vec<(f64, String, ())> {
+        items
+            .iter()
+            .map(|(score, id)| (*score, id.to_string(), ()))
+            .collect()
+    }
+
+    fn ids(results: &[(f64, String, ())]) -> Vec<&str> {
+        results.iter().map(|(_, id, _)| id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_bm25_encoder_weights_terms() {
+        let encoder = Bm25Encoder::new().avg_doc_len(4.0);
+        let document = encoder.encode_document("parse_config parse_config reads Config");
+        assert_eq!(document.indices.len(), 3);
+        assert!(document.indices.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let weight = |vector: &SparseVector, term: &str| {
+            vector
+                .weights()
+                .find(|(index, _)| *index == term_index(term))
+                .map(|(_, value)| value)
+        };
+        assert!(weight(&document, "parse_config") > weight(&document, "reads"));
+        assert!(weight(&document, "config").is_some());
+
+        let query = encoder.encode_query("Config config parse_config");
+        assert_eq!(query.values, vec![1.0, 1.0]);
+        assert!(encoder.encode_query("  ").is_empty());
+    }
+
+    #[test]
+    fn test_sparse_vector_from_pairs_merges_indices() {
+        let vector = SparseVector::from_pairs([(7, 1.0), (3, 0.5), (7, 2.0)]);
+        assert_eq!(vector.indices, vec![3, 7]);
+        assert_eq!(vector.values, vec![0.5, 3.0]);
+    }
+
+    #[test]
+    fn test_rrf_fusion_rewards_results_in_both_rankings() {
+        let dense = ranking(&[(0.9, "a"), (0.8, "b"), (0.7, "c")]);
+        let sparse = ranking(&[(12.0, "c"), (3.0, "d")]);
+        let fused = fuse_hybrid(dense, sparse, HybridFusion::default(), 3);
+        assert_eq!(ids(&fused), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn test_weighted_fusion_normalizes_scores() {
+        let dense = ranking(&[(0.9, "a"), (0.5, "b")]);
+        let sparse = ranking(&[(40.0, "b"), (10.0, "c")]);
+
+        let fused = fuse_hybrid(
+            dense.clone(),
+            sparse.clone(),
+            HybridFusion::Weighted { dense_weight: 0.7 },
+            10,
+        );
+        assert_eq!(ids(&fused), vec!["a", "b", "c"]);
+        assert!((fused[0].0 - 0.7).abs() < 1e-9);
+        assert!((fused[1].0 - 0.3).abs() < 1e-9);
+
+        let keyword_heavy = fuse_hybrid(
+            dense,
+            sparse,
+            HybridFusion::Weighted { dense_weight: 0.2 },
+            1,
+        );
+        assert_eq!(ids(&keyword_heavy), vec!["b"]);
+    }
+}
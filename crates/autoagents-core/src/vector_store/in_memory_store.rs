@@ -5,17 +5,25 @@ use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
 use crate::embeddings::distance::VectorDistance;
-use crate::embeddings::{Embedding, EmbeddingError, SharedEmbeddingProvider, VecArc};
+use crate::embeddings::{
+    EmbedImage, Embedding, EmbeddingError, EmbeddingTransform, SharedEmbeddingProvider,
+    SharedImageEmbeddingProvider, TransformedEmbeddingProvider, TransformedImageEmbeddingProvider,
+    VecArc,
+};
 use crate::vector_store::request::Filter;
 use crate::vector_store::{
-    DEFAULT_VECTOR_NAME, NamedVectorDocument, PreparedDocument, PreparedNamedVectorDocument,
-    VectorSearchRequest, VectorStoreError, VectorStoreIndex, embed_documents,
-    embed_named_documents, normalize_id,
+    DEFAULT_VECTOR_NAME, HybridFusion, NamedVectorDocument, PreparedDocument,
+    PreparedNamedVectorDocument, ScrollPage, SharedSparseEncoder, SparseVector,
+    VectorSearchRequest, VectorStoreError, VectorStoreIndex, bm25_idf, embed_documents,
+    embed_image_documents, embed_named_documents, fuse_hybrid, normalize_id,
 };
 
 #[derive(Clone)]
 pub struct InMemoryVectorStore {
     provider: SharedEmbeddingProvider,
+    transform: EmbeddingTransform,
+    image_provider: Option<SharedImageEmbeddingProvider>,
+    sparse_encoder: Option<SharedSparseEncoder>,
     embeddings: Arc<RwLock<HashMap<String, StoredEntry>>>,
 }
 
@@ -24,17 +32,68 @@ struct StoredEntry {
     raw: serde_json::Value,
     embeddings: crate::one_or_many::OneOrMany<Embedding>,
     named_vectors: HashMap<String, VecArc>,
+    sparse: Option<SparseVector>,
 }
 
 impl InMemoryVectorStore {
     pub fn new(provider: SharedEmbeddingProvider) -> Self {
         Self {
             provider,
+            transform: EmbeddingTransform::default(),
+            image_provider: None,
+            sparse_encoder: None,
             embeddings: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
-    fn insert_prepared(&self, documents: Vec<PreparedDocument>) {
+    /// Truncate and/or normalize document and query vectors alike
+    pub fn with_embedding_transform(mut self, transform: EmbeddingTransform) -> Self {
+        self.provider = Arc::new(TransformedEmbeddingProvider::new(self.provider, transform));
+        self.transform = transform;
+        self
+    }
+
+    pub fn embedding_transform(&self) -> EmbeddingTransform {
+        self.transform
+    }
+
+    /// Enable [`VectorStoreIndex::insert_image_documents_with_ids`]
+    ///
+    /// The image provider must embed into the same space as the text
+    /// provider (e.g. the two halves of a CLIP model).
+    pub fn with_image_embedding_provider(mut self, provider: SharedImageEmbeddingProvider) -> Self {
+        self.image_provider = Some(provider);
+        self
+    }
+
+    /// Enable [`VectorStoreIndex::top_n_hybrid`]
+    ///
+    /// Documents inserted from then on also get a sparse vector of their
+    /// text; term weights are scaled by BM25 inverse document frequency over
+    /// the stored documents at search time.
+    pub fn with_sparse_encoder(mut self, encoder: SharedSparseEncoder) -> Self {
+        self.sparse_encoder = Some(encoder);
+        self
+    }
+
+    fn image_provider(&self) -> Result<SharedImageEmbeddingProvider, VectorStoreError> {
+        let provider = self.image_provider.clone().ok_or_else(|| {
+            VectorStoreError::Unsupported("no image embedding provider configured".to_string())
+        })?;
+        if self.transform.is_identity() {
+            return Ok(provider);
+        }
+        Ok(Arc::new(TransformedImageEmbeddingProvider::new(
+            provider,
+            self.transform,
+        )))
+    }
+
+    /// Insert documents that are already embedded, replacing any with the same id
+    ///
+    /// Lets stores that persist vectors elsewhere rebuild the index on load
+    /// without calling the embedding provider again.
+    pub fn insert_prepared(&self, documents: Vec<PreparedDocument>) {
         let mut guard = self.embeddings.write().expect("lock poisoned");
         for doc in documents {
             let mut combined =
@@ -57,18 +116,29 @@ impl InMemoryVectorStore {
                 named_vectors.insert(DEFAULT_VECTOR_NAME.to_string(), combined.into());
             }
 
+            let sparse = self.sparse_encoder.as_ref().map(|encoder| {
+                let text: Vec<&str> = doc
+                    .embeddings
+                    .iter()
+                    .map(|embedding| embedding.document.as_str())
+                    .collect();
+                encoder.encode_document(&text.join("\n"))
+            });
+
             guard.insert(
                 doc.id,
                 StoredEntry {
                     raw: doc.raw,
                     embeddings: doc.embeddings,
                     named_vectors,
+                    sparse,
                 },
             );
         }
     }
 
-    fn insert_prepared_named(&self, documents: Vec<PreparedNamedVectorDocument>) {
+    /// [`insert_prepared`](Self::insert_prepared) for named-vector documents
+    pub fn insert_prepared_named(&self, documents: Vec<PreparedNamedVectorDocument>) {
         let mut guard = self.embeddings.write().expect("lock poisoned");
         for doc in documents {
             let PreparedNamedVectorDocument { id, raw, vectors } = doc;
@@ -85,6 +155,7 @@ impl InMemoryVectorStore {
                     // Keep `embeddings` empty to avoid duplicating vector storage.
                     embeddings: crate::one_or_many::OneOrMany::Many(Vec::new()),
                     named_vectors,
+                    sparse: None,
                 },
             );
         }
@@ -106,6 +177,56 @@ impl InMemoryVectorStore {
             .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
     }
 
+    /// Keyword ranking of the stored documents for [`VectorStoreIndex::top_n_hybrid`]
+    fn sparse_search<T>(
+        &self,
+        encoder: &SharedSparseEncoder,
+        req: &VectorSearchRequest<Filter<serde_json::Value>>,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        let query = encoder.encode_query(req.query());
+        let guard = self.embeddings.read().expect("lock poisoned");
+        let indexed: Vec<(&String, &StoredEntry, &SparseVector)> = guard
+            .iter()
+            .filter_map(|(id, entry)| entry.sparse.as_ref().map(|sparse| (id, entry, sparse)))
+            .collect();
+
+        let mut containing: HashMap<u32, usize> = HashMap::new();
+        for (_, _, sparse) in &indexed {
+            for index in &sparse.indices {
+                *containing.entry(*index).or_default() += 1;
+            }
+        }
+
+        let mut matches = Vec::new();
+        for (id, entry, sparse) in indexed.iter().copied() {
+            if let Some(filter) = req.filter()
+                && !filter.satisfies(&entry.raw)
+            {
+                continue;
+            }
+
+            let score: f64 = query
+                .weights()
+                .filter_map(|(index, weight)| {
+                    let position = sparse.indices.binary_search(&index).ok()?;
+                    let idf = bm25_idf(indexed.len(), containing[&index]);
+                    Some(idf * weight as f64 * sparse.values[position] as f64)
+                })
+                .sum();
+            if score > 0.0 {
+                let parsed: T = serde_json::from_value(entry.raw.clone())?;
+                matches.push((score, id.clone(), parsed));
+            }
+        }
+
+        matches.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        matches.truncate(req.samples() as usize);
+        Ok(matches)
+    }
+
     fn named_similarity(
         entry: &StoredEntry,
         query_vector_name: &str,
@@ -150,6 +271,23 @@ impl VectorStoreIndex for InMemoryVectorStore {
         Ok(())
     }
 
+    async fn insert_image_documents_with_ids<T>(
+        &self,
+        documents: Vec<(String, T)>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: EmbedImage + serde::Serialize + Send + Sync + Clone,
+    {
+        let provider = self.image_provider()?;
+        let normalized: Vec<(String, T)> = documents
+            .into_iter()
+            .map(|(id, doc)| (normalize_id(Some(id)), doc))
+            .collect();
+        let prepared = embed_image_documents(&provider, normalized).await?;
+        self.insert_prepared(prepared);
+        Ok(())
+    }
+
     async fn top_n<T>(
         &self,
         req: VectorSearchRequest<Self::Filter>,
@@ -157,6 +295,9 @@ impl VectorStoreIndex for InMemoryVectorStore {
     where
         T: for<'de> serde::Deserialize<'de> + Send + Sync,
     {
+        if req.reranker().is_some() {
+            return super::rerank_top_n(self, req).await;
+        }
         let vectors = self
             .provider
             .embed(vec![req.query().to_string()])
@@ -212,6 +353,9 @@ impl VectorStoreIndex for InMemoryVectorStore {
         &self,
         req: VectorSearchRequest<Self::Filter>,
     ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        if req.reranker().is_some() {
+            return super::rerank_top_n_ids(self, req).await;
+        }
         let vectors = self
             .provider
             .embed(vec![req.query().to_string()])
@@ -262,6 +406,23 @@ impl VectorStoreIndex for InMemoryVectorStore {
         Ok(matches)
     }
 
+    async fn top_n_hybrid<T>(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+        fusion: HybridFusion,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+    where
+        T: for<'de> serde::Deserialize<'de> + Send + Sync,
+    {
+        let encoder = self.sparse_encoder.clone().ok_or_else(|| {
+            VectorStoreError::Unsupported("no sparse encoder configured".to_string())
+        })?;
+        let sparse = self.sparse_search(&encoder, &req)?;
+        let limit = req.samples() as usize;
+        let dense = self.top_n(req).await?;
+        Ok(fuse_hybrid(dense, sparse, fusion, limit))
+    }
+
     async fn insert_documents_with_named_vectors<T>(
         &self,
         documents: Vec<NamedVectorDocument<T>>,
@@ -281,6 +442,57 @@ impl VectorStoreIndex for InMemoryVectorStore {
         self.insert_prepared_named(prepared);
         Ok(())
     }
+
+    async fn delete_documents_by_ids(&self, ids: &[String]) -> Result<(), VectorStoreError> {
+        let mut guard = self.embeddings.write().expect("lock poisoned");
+        for id in ids {
+            guard.remove(id);
+        }
+        Ok(())
+    }
+
+    /// Pages are in id order; the cursor is the id that starts the next page.
+    async fn scroll<T>(
+        &self,
+        filter: Option<Self::Filter>,
+        cursor: Option<String>,
+        limit: u64,
+    ) -> Result<ScrollPage<T>, VectorStoreError>
+    where
+        T: for<'de> serde::Deserialize<'de> + Send + Sync,
+    {
+        let guard = self.embeddings.read().expect("lock poisoned");
+        let mut ids: Vec<&String> = guard
+            .iter()
+            .filter(|(id, entry)| {
+                cursor.as_ref().is_none_or(|cursor| *id >= cursor)
+                    && filter.as_ref().is_none_or(|f| f.satisfies(&entry.raw))
+            })
+            .map(|(id, _)| id)
+            .collect();
+        ids.sort();
+
+        let limit = limit.max(1) as usize;
+        let next_cursor = ids.get(limit).map(|id| id.to_string());
+        let documents = ids
+            .into_iter()
+            .take(limit)
+            .map(|id| Ok((id.clone(), serde_json::from_value(guard[id].raw.clone())?)))
+            .collect::<Result<_, VectorStoreError>>()?;
+
+        Ok(ScrollPage {
+            documents,
+            next_cursor,
+        })
+    }
+
+    async fn count(&self, filter: Option<Self::Filter>) -> Result<u64, VectorStoreError> {
+        let guard = self.embeddings.read().expect("lock poisoned");
+        Ok(guard
+            .values()
+            .filter(|entry| filter.as_ref().is_none_or(|f| f.satisfies(&entry.raw)))
+            .count() as u64)
+    }
 }
 
 #[cfg(test)]
@@ -312,6 +524,96 @@ mod tests {
         assert_eq!(results[0].2.page_content, "hello world");
     }
 
+    #[tokio::test]
+    async fn test_embedding_transform_applies_to_documents() {
+        let transform = EmbeddingTransform::new()
+            .with_dimensions(2)
+            .with_normalize(true);
+        let store = make_store().with_embedding_transform(transform);
+        assert_eq!(store.embedding_transform(), transform);
+
+        store
+            .insert_documents_with_ids(vec![("doc1".to_string(), Document::new("hello"))])
+            .await
+            .unwrap();
+        let guard = store.embeddings.read().unwrap();
+        let stored = &guard["doc1"].named_vectors[DEFAULT_VECTOR_NAME];
+        assert_eq!(stored.len(), 2);
+        let norm: f32 = stored.iter().map(|v| v * v).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-6);
+    }
+
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+    struct Photo {
+        caption: String,
+        bytes: Vec<u8>,
+    }
+
+    impl EmbedImage for Photo {
+        fn embed_image(
+            &self,
+            embedder: &mut crate::embeddings::ImageEmbedder,
+        ) -> Result<(), crate::embeddings::EmbedError> {
+            embedder.embed(self.bytes.clone());
+            Ok(())
+        }
+    }
+
+    /// Maps a leading byte of 1 close to the mock text vector, anything else away from it
+    struct MockImageProvider;
+
+    #[async_trait]
+    impl autoagents_llm::embedding::ImageEmbeddingProvider for MockImageProvider {
+        async fn embed_images(
+            &self,
+            images: Vec<Vec<u8>>,
+        ) -> Result<Vec<Vec<f32>>, autoagents_llm::error::LLMError> {
+            Ok(images
+                .iter()
+                .map(|image| match image.first() {
+                    Some(1) => vec![0.1, 0.2, 0.3],
+                    _ => vec![0.3, -0.2, 0.0],
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mixed_text_and_image_documents() {
+        let photo = |caption: &str, byte: u8| Photo {
+            caption: caption.to_string(),
+            bytes: vec![byte, 0, 0],
+        };
+        assert!(matches!(
+            make_store()
+                .insert_image_documents_with_ids(vec![("p".to_string(), photo("cat", 1))])
+                .await,
+            Err(VectorStoreError::Unsupported(_))
+        ));
+
+        let store = make_store().with_image_embedding_provider(Arc::new(MockImageProvider));
+        store
+            .insert_documents_with_ids(vec![("text".to_string(), Document::new("a cat"))])
+            .await
+            .unwrap();
+        store
+            .insert_image_documents_with_ids(vec![
+                ("cat.png".to_string(), photo("cat", 1)),
+                ("car.png".to_string(), photo("car", 2)),
+            ])
+            .await
+            .unwrap();
+
+        let req = VectorSearchRequest::builder()
+            .query("cat")
+            .samples(3)
+            .build()
+            .unwrap();
+        let results = store.top_n_ids(req).await.unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[2].1, "car.png");
+    }
+
     #[tokio::test]
     async fn test_insert_with_ids_and_top_n_ids() {
         let store = make_store();
@@ -328,6 +630,32 @@ mod tests {
         assert_eq!(results[0].1, "doc1");
     }
 
+    #[tokio::test]
+    async fn test_delete_documents_by_ids() {
+        let store = make_store();
+        let docs = vec![
+            ("doc1".to_string(), Document::new("first doc")),
+            ("doc2".to_string(), Document::new("second doc")),
+        ];
+        for doc in docs {
+            store.insert_documents_with_ids(vec![doc]).await.unwrap();
+        }
+
+        store
+            .delete_documents_by_ids(&["doc1".to_string(), "missing".to_string()])
+            .await
+            .unwrap();
+
+        let req = VectorSearchRequest::builder()
+            .query("doc")
+            .samples(5)
+            .build()
+            .unwrap();
+        let results = store.top_n_ids(req).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1, "doc2");
+    }
+
     #[tokio::test]
     async fn test_empty_store_query() {
         let store = make_store();
@@ -362,6 +690,104 @@ mod tests {
         assert!(results.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_metadata_filters() {
+        let store = make_store();
+        let docs = vec![
+            (
+                "a".to_string(),
+                Document::with_metadata(
+                    "rust guide",
+                    serde_json::json!({"lang": "rust", "year": 2021}),
+                ),
+            ),
+            (
+                "b".to_string(),
+                Document::with_metadata(
+                    "go guide",
+                    serde_json::json!({"lang": "go", "year": 2024}),
+                ),
+            ),
+            ("c".to_string(), Document::new("untagged notes")),
+        ];
+        for doc in docs {
+            store.insert_documents_with_ids(vec![doc]).await.unwrap();
+        }
+
+        let search = |filter: Filter<serde_json::Value>| {
+            let store = store.clone();
+            async move {
+                let req = VectorSearchRequest::builder()
+                    .query("guide")
+                    .samples(5)
+                    .filter(filter)
+                    .build()
+                    .unwrap();
+                let mut ids: Vec<String> = store
+                    .top_n_ids(req)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|(_, id)| id)
+                    .collect();
+                ids.sort();
+                ids
+            }
+        };
+
+        let existing_lang = SearchFilter::exists("metadata.lang".to_string());
+        assert_eq!(search(existing_lang).await, vec!["a", "b"]);
+        let recent = SearchFilter::between(
+            "metadata.year".to_string(),
+            serde_json::json!(2022),
+            serde_json::json!(2025),
+        );
+        assert_eq!(search(recent).await, vec!["b"]);
+        let not_go = SearchFilter::ne("metadata.lang".to_string(), serde_json::json!("go"));
+        assert_eq!(search(not_go).await, vec!["a", "c"]);
+        let mentions_rust =
+            SearchFilter::contains("page_content".to_string(), serde_json::json!("rust"));
+        assert_eq!(search(mentions_rust).await, vec!["a"]);
+    }
+
+    #[tokio::test]
+    async fn test_scroll_pages_and_count() {
+        let store = make_store();
+        for index in 0..5 {
+            let doc = Document::with_metadata(
+                format!("doc {index}"),
+                serde_json::json!({"even": index % 2 == 0}),
+            );
+            store
+                .insert_documents_with_ids(vec![(format!("id-{index}"), doc)])
+                .await
+                .unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page: ScrollPage<Document> = store.scroll(None, cursor, 2).await.unwrap();
+            assert!(page.documents.len() <= 2);
+            seen.extend(page.documents.into_iter().map(|(id, _)| id));
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(seen, vec!["id-0", "id-1", "id-2", "id-3", "id-4"]);
+
+        let even: Filter<serde_json::Value> =
+            SearchFilter::eq("metadata.even".to_string(), serde_json::json!(true));
+        let page: ScrollPage<Document> = store.scroll(Some(even.clone()), None, 10).await.unwrap();
+        assert_eq!(page.documents.len(), 3);
+        assert!(page.next_cursor.is_none());
+        assert_eq!(page.documents[1].1.page_content, "doc 2");
+
+        assert_eq!(store.count(None).await.unwrap(), 5);
+        assert_eq!(store.count(Some(even)).await.unwrap(), 3);
+    }
+
     #[tokio::test]
     async fn test_threshold_filtering() {
         let store = make_store();
@@ -438,12 +864,48 @@ mod tests {
         assert_eq!(results[0].1, "doc1");
     }
 
+    #[tokio::test]
+    async fn test_top_n_hybrid_lifts_keyword_matches() {
+        use crate::vector_store::Bm25Encoder;
+
+        let store = make_store().with_sparse_encoder(Arc::new(Bm25Encoder::new()));
+        for (id, text) in [
+            ("a", "the request took too long"),
+            ("b", "ERR_TIMEOUT raised by the http client"),
+            ("c", "the client retried"),
+        ] {
+            store
+                .insert_documents_with_ids(vec![(id.to_string(), Document::new(text))])
+                .await
+                .unwrap();
+        }
+
+        let req = VectorSearchRequest::builder()
+            .query("err_timeout")
+            .samples(3)
+            .build()
+            .unwrap();
+        let results: Vec<(f64, String, Document)> = store
+            .top_n_hybrid(req.clone(), HybridFusion::default())
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].1, "b");
+
+        let err = make_store()
+            .top_n_hybrid::<Document>(req, HybridFusion::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, VectorStoreError::Unsupported(_)));
+    }
+
     #[test]
     fn test_best_similarity_uses_named_vectors_when_embeddings_empty() {
         let entry = StoredEntry {
             raw: serde_json::json!({"k": "v"}),
             embeddings: crate::one_or_many::OneOrMany::Many(Vec::new()),
             named_vectors: HashMap::from([("alt".to_string(), vec![1.0_f32, 0.0_f32].into())]),
+            sparse: None,
         };
         let query = Embedding {
             document: "q".to_string(),
@@ -459,6 +921,7 @@ mod tests {
             raw: serde_json::json!({"k": "v"}),
             embeddings: crate::one_or_many::OneOrMany::Many(Vec::new()),
             named_vectors: HashMap::new(),
+            sparse: None,
         };
         let query = Embedding {
             document: "q".to_string(),
@@ -1,9 +1,24 @@
+pub use batch::{
+    BatchInsertReport, BatchInserter, BatchProgress, DEFAULT_INSERT_BATCH_SIZE,
+    DEFAULT_INSERT_CONCURRENCY,
+};
+pub use hybrid::{
+    Bm25Encoder, DEFAULT_SPARSE_VECTOR_NAME, HybridFusion, SharedSparseEncoder, SparseEncoder,
+    SparseVector, bm25_idf, fuse_hybrid,
+};
+pub use namespace::{NAMESPACE_FIELD, NamespacedIndex};
+pub use parent_document::{DEFAULT_PARENT_OVERSAMPLE, ParentDocumentIndex};
 pub use payload::{
     NamedVectorPayloadDocument, PayloadDocument, PreparedNamedVectorPayloadDocument,
     PreparedPayloadDocument, embed_documents_with_payload_fields, embed_named_payload_documents,
     embed_payload_documents, mirrored_payload_fields, mirrored_payload_fields_for,
 };
-pub use request::VectorSearchRequest;
+pub use query_transform::{DEFAULT_RRF_K, QueryTransformIndex, reciprocal_rank_fusion};
+pub use request::{QueryTransform, VectorSearchRequest};
+pub use rerank::{
+    DEFAULT_RERANK_OVERSAMPLE, RerankStage, Reranker, SharedReranker, rerank_text, rerank_top_n,
+    rerank_top_n_ids,
+};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -11,13 +26,22 @@ use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::document::Document;
-use crate::embeddings::{Embed, Embedding, EmbeddingError, SharedEmbeddingProvider};
+use crate::embeddings::{
+    Embed, EmbedImage, Embedding, EmbeddingError, ImageEmbedder, SharedEmbeddingProvider,
+    SharedImageEmbeddingProvider,
+};
 use crate::one_or_many::OneOrMany;
 use crate::vector_store::request::{FilterError, SearchFilter};
 
+mod batch;
+mod hybrid;
 pub mod in_memory_store;
+mod namespace;
+mod parent_document;
 pub mod payload;
+mod query_transform;
 pub mod request;
+mod rerank;
 
 pub const DEFAULT_VECTOR_NAME: &str = "default";
 
@@ -37,6 +61,15 @@ pub enum VectorStoreError {
 
     #[error("Error while building VectorSearchRequest: {0}")]
     BuilderError(String),
+
+    #[error("Unsupported operation: {0}")]
+    Unsupported(String),
+
+    #[error("Query transformation error: {0}")]
+    QueryTransformError(String),
+
+    #[error("Reranking error: {0}")]
+    RerankError(String),
 }
 
 #[async_trait]
@@ -72,6 +105,112 @@ pub trait VectorStoreIndex: Send + Sync {
     ) -> Result<(), VectorStoreError>
     where
         T: Serialize + Send + Sync + Clone;
+
+    /// Search with both the dense embedding and a sparse keyword vector of
+    /// the query, and merge the two rankings with `fusion`.
+    ///
+    /// Catches exact keyword matches (identifiers, error codes, product
+    /// names) that dense retrieval alone ranks poorly. Stores need a
+    /// [`SparseEncoder`] configured before documents are inserted. The
+    /// request's threshold only applies to the dense search.
+    async fn top_n_hybrid<T>(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+        fusion: HybridFusion,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        let _ = (req, fusion);
+        Err(VectorStoreError::Unsupported(
+            "this vector store does not support hybrid search".to_string(),
+        ))
+    }
+
+    /// Insert documents embedded from their images rather than their text.
+    ///
+    /// Stores that support it embed the images with a configured
+    /// [`ImageEmbeddingProvider`](autoagents_llm::embedding::ImageEmbeddingProvider)
+    /// and keep them next to text documents, so text queries return both.
+    async fn insert_image_documents_with_ids<T>(
+        &self,
+        documents: Vec<(String, T)>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: EmbedImage + Serialize + Send + Sync + Clone,
+    {
+        let _ = documents;
+        Err(VectorStoreError::Unsupported(
+            "this vector store does not support image documents".to_string(),
+        ))
+    }
+
+    /// Delete documents by the ids they were inserted with.
+    ///
+    /// Ids that are not in the store are ignored.
+    async fn delete_documents_by_ids(&self, ids: &[String]) -> Result<(), VectorStoreError> {
+        let _ = ids;
+        Err(VectorStoreError::Unsupported(
+            "this vector store does not support deleting documents".to_string(),
+        ))
+    }
+
+    /// Page through stored documents matching `filter`, without a query.
+    ///
+    /// Pass `None` as the cursor for the first page, then the returned
+    /// [`ScrollPage::next_cursor`] until it is `None`. Cursors are opaque and
+    /// specific to the store. Pages follow a stable store-defined order, so
+    /// documents inserted or deleted while scrolling may or may not be seen.
+    async fn scroll<T>(
+        &self,
+        filter: Option<Self::Filter>,
+        cursor: Option<String>,
+        limit: u64,
+    ) -> Result<ScrollPage<T>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        let _ = (filter, cursor, limit);
+        Err(VectorStoreError::Unsupported(
+            "this vector store does not support scrolling".to_string(),
+        ))
+    }
+
+    /// Number of stored documents matching `filter`.
+    async fn count(&self, filter: Option<Self::Filter>) -> Result<u64, VectorStoreError> {
+        let _ = filter;
+        Err(VectorStoreError::Unsupported(
+            "this vector store does not support counting documents".to_string(),
+        ))
+    }
+
+    /// Key that [`Self::Filter`] uses to match `field` of the stored documents.
+    ///
+    /// Stores that filter on a payload wrapping the document map the
+    /// field to its path inside that payload.
+    fn field_filter_key(&self, field: &str) -> String {
+        field.to_string()
+    }
+
+    /// Limit this store to one tenant; see [`NamespacedIndex`].
+    fn with_namespace(
+        self,
+        namespace: impl Into<String>,
+    ) -> Result<NamespacedIndex<Self>, VectorStoreError>
+    where
+        Self: Sized,
+    {
+        NamespacedIndex::new(std::sync::Arc::new(self), namespace)
+    }
+}
+
+/// One page of [`VectorStoreIndex::scroll`] results
+#[derive(Debug, Clone)]
+pub struct ScrollPage<T> {
+    /// Documents with the ids they were inserted with
+    pub documents: Vec<(String, T)>,
+    /// Cursor for the next page; `None` on the last page
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -150,6 +289,65 @@ where
         .collect())
 }
 
+/// Embed documents through their [`EmbedImage`] implementation
+///
+/// The resulting embeddings carry an empty `document`, since there is no
+/// source text to keep.
+pub async fn embed_image_documents<T>(
+    provider: &SharedImageEmbeddingProvider,
+    documents: Vec<(String, T)>,
+) -> Result<Vec<PreparedDocument>, VectorStoreError>
+where
+    T: EmbedImage + Serialize + Send + Sync + Clone,
+{
+    let mut images = Vec::new();
+    let mut counts = Vec::with_capacity(documents.len());
+    for (_, doc) in &documents {
+        let mut embedder = ImageEmbedder::default();
+        doc.embed_image(&mut embedder)
+            .map_err(|err| EmbeddingError::EmbedFailure(err.to_string()))?;
+        if embedder.is_empty() {
+            return Err(EmbeddingError::Empty.into());
+        }
+        counts.push(embedder.len());
+        images.extend(embedder.into_parts());
+    }
+    if images.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let expected = images.len();
+    let vectors = provider
+        .embed_images(images)
+        .await
+        .map_err(EmbeddingError::Provider)?;
+    if vectors.len() < expected {
+        return Err(EmbeddingError::EmbedFailure(
+            "image embedding provider returned fewer vectors than expected".into(),
+        )
+        .into());
+    }
+
+    let mut vectors = vectors.into_iter();
+    let mut prepared = Vec::with_capacity(documents.len());
+    for ((id, doc), count) in documents.into_iter().zip(counts) {
+        let embeddings: Vec<Embedding> = vectors
+            .by_ref()
+            .take(count)
+            .map(|vector| Embedding {
+                document: String::new(),
+                vec: vector.into(),
+            })
+            .collect();
+        prepared.push(PreparedDocument {
+            id,
+            raw: serde_json::to_value(doc)?,
+            embeddings: OneOrMany::from(embeddings),
+        });
+    }
+    Ok(prepared)
+}
+
 pub fn normalize_id(id: Option<String>) -> String {
     id.unwrap_or_else(|| Uuid::new_v4().to_string())
 }
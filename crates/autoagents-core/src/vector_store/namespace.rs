@@ -0,0 +1,410 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::request::SearchFilter;
+use super::{
+    HybridFusion, NamedVectorDocument, ScrollPage, VectorSearchRequest, VectorStoreError,
+    VectorStoreIndex, normalize_id,
+};
+use crate::embeddings::{Embed, EmbedError, EmbedImage, ImageEmbedder, TextEmbedder};
+
+/// Document field holding the namespace of every document written through a [`NamespacedIndex`]
+pub const NAMESPACE_FIELD: &str = "namespace";
+
+/// Separates the namespace from the caller's id in stored ids
+const ID_SEPARATOR: char = '/';
+
+/// A view of a vector store limited to one tenant
+///
+/// Every document written through it gets a [`NAMESPACE_FIELD`] field and an
+/// id prefixed with the namespace, and every search, scroll and count is
+/// filtered to that namespace, so tenants sharing one store never see, and
+/// cannot overwrite or delete, each other's documents. Ids are returned
+/// without the prefix. Documents must serialize as JSON objects.
+///
+/// Create one per request with [`NamespacedIndex::for_namespace`] to serve
+/// many tenants from one store.
+pub struct NamespacedIndex<S> {
+    store: Arc<S>,
+    namespace: String,
+    id_prefix: String,
+}
+
+impl<S> Clone for NamespacedIndex<S> {
+    fn clone(&self) -> Self {
+        Self {
+            store: Arc::clone(&self.store),
+            namespace: self.namespace.clone(),
+            id_prefix: self.id_prefix.clone(),
+        }
+    }
+}
+
+impl<S> NamespacedIndex<S> {
+    /// Limit `store` to `namespace`, which must be non-empty and free of `/`
+    pub fn new(store: Arc<S>, namespace: impl Into<String>) -> Result<Self, VectorStoreError> {
+        let namespace = namespace.into();
+        if namespace.is_empty() || namespace.contains(ID_SEPARATOR) {
+            return Err(VectorStoreError::BuilderError(format!(
+                "namespace must be non-empty and must not contain '{ID_SEPARATOR}', got '{namespace}'"
+            )));
+        }
+        Ok(Self {
+            store,
+            id_prefix: format!("{namespace}{ID_SEPARATOR}"),
+            namespace,
+        })
+    }
+
+    /// The same store limited to another namespace
+    pub fn for_namespace(&self, namespace: impl Into<String>) -> Result<Self, VectorStoreError> {
+        Self::new(Arc::clone(&self.store), namespace)
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.store
+    }
+
+    fn stored_id(&self, id: &str) -> String {
+        format!("{}{id}", self.id_prefix)
+    }
+
+    /// The caller's id, or `None` for documents of other namespaces
+    fn caller_id(&self, id: String) -> Option<String> {
+        id.strip_prefix(&self.id_prefix).map(str::to_string)
+    }
+
+    fn tag<T>(&self, id: &str, document: T) -> (String, Namespaced<T>) {
+        (
+            self.stored_id(id),
+            Namespaced {
+                document,
+                namespace: self.namespace.clone(),
+            },
+        )
+    }
+}
+
+impl<S> NamespacedIndex<S>
+where
+    S: VectorStoreIndex,
+    <S::Filter as SearchFilter>::Value: From<String>,
+{
+    fn namespace_filter(&self) -> S::Filter {
+        S::Filter::eq(
+            self.store.field_filter_key(NAMESPACE_FIELD),
+            self.namespace.clone().into(),
+        )
+    }
+
+    fn scoped(&self, req: VectorSearchRequest<S::Filter>) -> VectorSearchRequest<S::Filter> {
+        req.and_filter(self.namespace_filter())
+    }
+
+    fn scoped_filter(&self, filter: Option<S::Filter>) -> S::Filter {
+        match filter {
+            Some(filter) => self.namespace_filter().and(filter),
+            None => self.namespace_filter(),
+        }
+    }
+
+    fn own_results<T>(&self, results: Vec<(f64, String, T)>) -> Vec<(f64, String, T)> {
+        results
+            .into_iter()
+            .filter_map(|(score, id, document)| Some((score, self.caller_id(id)?, document)))
+            .collect()
+    }
+}
+
+/// A document with the namespace field added
+#[derive(Clone, Serialize)]
+struct Namespaced<T> {
+    #[serde(flatten)]
+    document: T,
+    namespace: String,
+}
+
+impl<T: Embed> Embed for Namespaced<T> {
+    fn embed(&self, embedder: &mut TextEmbedder) -> Result<(), EmbedError> {
+        self.document.embed(embedder)
+    }
+}
+
+impl<T: EmbedImage> EmbedImage for Namespaced<T> {
+    fn embed_image(&self, embedder: &mut ImageEmbedder) -> Result<(), EmbedError> {
+        self.document.embed_image(embedder)
+    }
+}
+
+#[async_trait]
+impl<S> VectorStoreIndex for NamespacedIndex<S>
+where
+    S: VectorStoreIndex,
+    <S::Filter as SearchFilter>::Value: From<String>,
+{
+    type Filter = S::Filter;
+
+    async fn insert_documents<T>(&self, documents: Vec<T>) -> Result<(), VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+    {
+        let documents = documents
+            .into_iter()
+            .map(|document| (normalize_id(None), document))
+            .collect();
+        self.insert_documents_with_ids(documents).await
+    }
+
+    async fn insert_documents_with_ids<T>(
+        &self,
+        documents: Vec<(String, T)>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+    {
+        let documents = documents
+            .into_iter()
+            .map(|(id, document)| self.tag(&id, document))
+            .collect();
+        self.store.insert_documents_with_ids(documents).await
+    }
+
+    async fn top_n<T>(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        let results = self.store.top_n(self.scoped(req)).await?;
+        Ok(self.own_results(results))
+    }
+
+    async fn top_n_ids(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        let results = self.store.top_n_ids(self.scoped(req)).await?;
+        Ok(results
+            .into_iter()
+            .filter_map(|(score, id)| Some((score, self.caller_id(id)?)))
+            .collect())
+    }
+
+    async fn insert_documents_with_named_vectors<T>(
+        &self,
+        documents: Vec<NamedVectorDocument<T>>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: Serialize + Send + Sync + Clone,
+    {
+        let documents = documents
+            .into_iter()
+            .map(|document| {
+                let (id, raw) = self.tag(&document.id, document.raw);
+                NamedVectorDocument {
+                    id,
+                    raw,
+                    vectors: document.vectors,
+                }
+            })
+            .collect();
+        self.store
+            .insert_documents_with_named_vectors(documents)
+            .await
+    }
+
+    async fn top_n_hybrid<T>(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+        fusion: HybridFusion,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        let results = self.store.top_n_hybrid(self.scoped(req), fusion).await?;
+        Ok(self.own_results(results))
+    }
+
+    async fn insert_image_documents_with_ids<T>(
+        &self,
+        documents: Vec<(String, T)>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: EmbedImage + Serialize + Send + Sync + Clone,
+    {
+        let documents = documents
+            .into_iter()
+            .map(|(id, document)| self.tag(&id, document))
+            .collect();
+        self.store.insert_image_documents_with_ids(documents).await
+    }
+
+    async fn delete_documents_by_ids(&self, ids: &[String]) -> Result<(), VectorStoreError> {
+        let ids: Vec<String> = ids.iter().map(|id| self.stored_id(id)).collect();
+        self.store.delete_documents_by_ids(&ids).await
+    }
+
+    async fn scroll<T>(
+        &self,
+        filter: Option<Self::Filter>,
+        cursor: Option<String>,
+        limit: u64,
+    ) -> Result<ScrollPage<T>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        let page = self
+            .store
+            .scroll(Some(self.scoped_filter(filter)), cursor, limit)
+            .await?;
+        Ok(ScrollPage {
+            documents: page
+                .documents
+                .into_iter()
+                .filter_map(|(id, document)| Some((self.caller_id(id)?, document)))
+                .collect(),
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    async fn count(&self, filter: Option<Self::Filter>) -> Result<u64, VectorStoreError> {
+        self.store.count(Some(self.scoped_filter(filter))).await
+    }
+
+    fn field_filter_key(&self, field: &str) -> String {
+        self.store.field_filter_key(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::Document;
+    use crate::embeddings::SharedEmbeddingProvider;
+    use crate::testing::KeywordEmbeddingProvider;
+    use crate::vector_store::in_memory_store::InMemoryVectorStore;
+    use crate::vector_store::request::Filter;
+    use serde_json::json;
+
+    async fn tenants() -> (
+        NamespacedIndex<InMemoryVectorStore>,
+        NamespacedIndex<InMemoryVectorStore>,
+    ) {
+        let provider: SharedEmbeddingProvider = Arc::new(KeywordEmbeddingProvider::flat());
+        let alice = InMemoryVectorStore::new(provider)
+            .with_namespace("alice")
+            .unwrap();
+        let bob = alice.for_namespace("bob").unwrap();
+        alice
+            .insert_documents_with_ids(vec![
+                ("notes".to_string(), Document::new("alice notes")),
+                ("todo".to_string(), Document::new("alice todo")),
+            ])
+            .await
+            .unwrap();
+        bob.insert_documents_with_ids(vec![("notes".to_string(), Document::new("bob notes"))])
+            .await
+            .unwrap();
+        (alice, bob)
+    }
+
+    fn request(filter: Option<Filter<serde_json::Value>>) -> VectorSearchRequest {
+        let builder = VectorSearchRequest::builder().query("notes").samples(10);
+        match filter {
+            Some(filter) => builder.filter(filter),
+            None => builder,
+        }
+        .build()
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_searches_only_see_the_own_namespace() {
+        let (alice, bob) = tenants().await;
+
+        let results: Vec<(f64, String, Document)> = bob.top_n(request(None)).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1, "notes");
+        assert_eq!(results[0].2.page_content, "bob notes");
+
+        // A caller filter cannot widen the search to other tenants
+        let filter = Filter::eq(NAMESPACE_FIELD.to_string(), json!("alice"));
+        assert!(
+            bob.top_n_ids(request(Some(filter)))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let mut ids: Vec<String> = alice
+            .top_n_ids(request(None))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(_, id)| id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["notes", "todo"]);
+        assert_eq!(alice.inner().count(None).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_same_ids_in_different_namespaces_do_not_collide() {
+        let (alice, bob) = tenants().await;
+
+        bob.delete_documents_by_ids(&["notes".to_string(), "todo".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(bob.count(None).await.unwrap(), 0);
+        assert_eq!(alice.count(None).await.unwrap(), 2);
+
+        let page: ScrollPage<Document> = alice.scroll(None, None, 10).await.unwrap();
+        let mut ids: Vec<&str> = page.documents.iter().map(|(id, _)| id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["notes", "todo"]);
+    }
+
+    #[derive(Clone, Serialize)]
+    struct Note {
+        text: String,
+        namespace: String,
+    }
+
+    impl Embed for Note {
+        fn embed(&self, embedder: &mut TextEmbedder) -> Result<(), EmbedError> {
+            embedder.embed(self.text.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_documents_cannot_spoof_their_namespace() {
+        let (alice, bob) = tenants().await;
+        let note = Note {
+            text: "sneaky".to_string(),
+            namespace: "alice".to_string(),
+        };
+        bob.insert_documents_with_ids(vec![("spoof".to_string(), note)])
+            .await
+            .unwrap();
+
+        assert_eq!(alice.count(None).await.unwrap(), 2);
+        assert_eq!(bob.count(None).await.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_namespace_names_are_validated() {
+        let provider: SharedEmbeddingProvider = Arc::new(KeywordEmbeddingProvider::flat());
+        let store = Arc::new(InMemoryVectorStore::new(provider));
+        assert!(NamespacedIndex::new(Arc::clone(&store), "").is_err());
+        assert!(NamespacedIndex::new(Arc::clone(&store), "a/b").is_err());
+        assert_eq!(NamespacedIndex::new(store, "a").unwrap().namespace(), "a");
+    }
+}
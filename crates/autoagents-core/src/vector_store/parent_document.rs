@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{
+    NamedVectorDocument, ScrollPage, VectorSearchRequest, VectorStoreError, VectorStoreIndex,
+};
+use crate::chunking::{PARENT_CONTENT_KEY, PARENT_ID_KEY, PARENT_WINDOW_KEY};
+use crate::embeddings::{Embed, EmbedImage};
+
+/// Default factor by which [`ParentDocumentIndex`] over-fetches chunks
+pub const DEFAULT_PARENT_OVERSAMPLE: u64 = 3;
+
+/// Wraps a vector store to return parent windows instead of the small chunks that matched
+///
+/// Chunks produced by [`ParentWindowSplitter`](crate::chunking::ParentWindowSplitter)
+/// are matched as usual; each hit is then replaced by its parent window, and
+/// further hits from the same window are dropped so the window is returned
+/// once, with the score of its best chunk. Because of that the wrapped store
+/// is asked for `oversample` times more results than requested. Hits without
+/// [`PARENT_CONTENT_KEY`] are returned unchanged.
+pub struct ParentDocumentIndex<S> {
+    store: S,
+    oversample: u64,
+}
+
+impl<S> ParentDocumentIndex<S> {
+    pub fn new(store: S) -> Self {
+        Self {
+            store,
+            oversample: DEFAULT_PARENT_OVERSAMPLE,
+        }
+    }
+
+    pub fn with_oversample(mut self, oversample: u64) -> Self {
+        self.oversample = oversample.max(1);
+        self
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.store
+    }
+}
+
+impl<S> ParentDocumentIndex<S>
+where
+    S: VectorStoreIndex,
+{
+    /// Matching hits as stored documents, expanded into their parent windows
+    async fn parent_hits(
+        &self,
+        req: VectorSearchRequest<S::Filter>,
+    ) -> Result<Vec<(f64, String, Value)>, VectorStoreError> {
+        let samples = req.samples();
+        let hits: Vec<(f64, String, Value)> = self
+            .store
+            .top_n(req.with_samples(samples.saturating_mul(self.oversample)))
+            .await?;
+
+        let mut windows = HashSet::new();
+        let mut results = Vec::new();
+        for (score, id, mut document) in hits {
+            let Some(parent) = document
+                .get_mut("metadata")
+                .and_then(Value::as_object_mut)
+                .and_then(|metadata| metadata.remove(PARENT_CONTENT_KEY))
+            else {
+                results.push((score, id, document));
+                continue;
+            };
+            let metadata = &document["metadata"];
+            let window = match metadata.get(PARENT_ID_KEY) {
+                Some(parent_id) => format!(
+                    "{parent_id}#{}",
+                    metadata.get(PARENT_WINDOW_KEY).unwrap_or(&Value::Null)
+                ),
+                None => parent.to_string(),
+            };
+            if windows.insert(window) {
+                document["page_content"] = parent;
+                results.push((score, id, document));
+            }
+        }
+        results.truncate(samples as usize);
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl<S> VectorStoreIndex for ParentDocumentIndex<S>
+where
+    S: VectorStoreIndex,
+{
+    type Filter = S::Filter;
+
+    async fn insert_documents<T>(&self, documents: Vec<T>) -> Result<(), VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+    {
+        self.store.insert_documents(documents).await
+    }
+
+    async fn insert_documents_with_ids<T>(
+        &self,
+        documents: Vec<(String, T)>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+    {
+        self.store.insert_documents_with_ids(documents).await
+    }
+
+    async fn top_n<T>(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        if req.reranker().is_some() {
+            return super::rerank_top_n(self, req).await;
+        }
+        self.parent_hits(req)
+            .await?
+            .into_iter()
+            .map(|(score, id, document)| Ok((score, id, serde_json::from_value(document)?)))
+            .collect()
+    }
+
+    async fn top_n_ids(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        if req.reranker().is_some() {
+            return super::rerank_top_n_ids(self, req).await;
+        }
+        Ok(self
+            .parent_hits(req)
+            .await?
+            .into_iter()
+            .map(|(score, id, _)| (score, id))
+            .collect())
+    }
+
+    async fn insert_documents_with_named_vectors<T>(
+        &self,
+        documents: Vec<NamedVectorDocument<T>>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: Serialize + Send + Sync + Clone,
+    {
+        self.store
+            .insert_documents_with_named_vectors(documents)
+            .await
+    }
+
+    async fn insert_image_documents_with_ids<T>(
+        &self,
+        documents: Vec<(String, T)>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: EmbedImage + Serialize + Send + Sync + Clone,
+    {
+        self.store.insert_image_documents_with_ids(documents).await
+    }
+
+    async fn delete_documents_by_ids(&self, ids: &[String]) -> Result<(), VectorStoreError> {
+        self.store.delete_documents_by_ids(ids).await
+    }
+
+    async fn scroll<T>(
+        &self,
+        filter: Option<Self::Filter>,
+        cursor: Option<String>,
+        limit: u64,
+    ) -> Result<ScrollPage<T>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        self.store.scroll(filter, cursor, limit).await
+    }
+
+    async fn count(&self, filter: Option<Self::Filter>) -> Result<u64, VectorStoreError> {
+        self.store.count(filter).await
+    }
+
+    fn field_filter_key(&self, field: &str) -> String {
+        self.store.field_filter_key(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunking::{ParentWindowSplitter, RecursiveCharacterSplitter, TextSplitter};
+    use crate::document::Document;
+    use crate::embeddings::SharedEmbeddingProvider;
+    use crate::testing::KeywordEmbeddingProvider;
+    use crate::vector_store::in_memory_store::InMemoryVectorStore;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_hits_are_replaced_by_their_parent_window_once() {
+        let provider: SharedEmbeddingProvider =
+            Arc::new(KeywordEmbeddingProvider::new(["install", "usage"]));
+        let store = InMemoryVectorStore::new(provider);
+        let text = "To install, download it. Then install the plugin.\n\nUsage is simple. Run it.";
+        let splitter = ParentWindowSplitter::new(
+            RecursiveCharacterSplitter::new(60, 0).unwrap(),
+            RecursiveCharacterSplitter::new(26, 0).unwrap(),
+        );
+        let chunks =
+            splitter.split_documents(&[Document::with_metadata(text, json!({"id": "guide"}))]);
+        assert_eq!(chunks.len(), 3);
+        let chunks = chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| (format!("chunk-{index}"), chunk))
+            .collect();
+        store.insert_documents_with_ids(chunks).await.unwrap();
+
+        let index = ParentDocumentIndex::new(store);
+        let req = VectorSearchRequest::builder()
+            .query("install")
+            .samples(2)
+            .build()
+            .unwrap();
+        let results: Vec<(f64, String, Document)> = index.top_n(req).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[0].2.page_content,
+            "To install, download it. Then install the plugin."
+        );
+        assert!(results[0].2.metadata.get(PARENT_CONTENT_KEY).is_none());
+        assert_eq!(results[1].2.page_content, "Usage is simple. Run it.");
+    }
+}
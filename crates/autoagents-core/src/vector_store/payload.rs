@@ -2,7 +2,10 @@ use std::collections::{HashMap, HashSet};
 
 use serde::Serialize;
 
-use crate::embeddings::{Embed, Embedding, EmbeddingError, SharedEmbeddingProvider, TextEmbedder};
+use crate::embeddings::{
+    Embed, Embedding, EmbeddingBatchOptions, EmbeddingError, SharedEmbeddingProvider, TextEmbedder,
+    embed_in_batches,
+};
 use crate::one_or_many::OneOrMany;
 
 use super::{NamedVectorDocument, VectorStoreError};
@@ -194,10 +197,12 @@ where
         ids.push(id.clone());
     }
 
-    let vectors = provider
-        .embed(all_texts.clone())
-        .await
-        .map_err(EmbeddingError::Provider)?;
+    let vectors = embed_in_batches(
+        provider.as_ref(),
+        all_texts.clone(),
+        &EmbeddingBatchOptions::default(),
+    )
+    .await?;
 
     let mut prepared = Vec::with_capacity(ids.len());
     let mut vectors_iter = vectors.into_iter();
@@ -271,10 +276,12 @@ where
         ids.push(doc.id.clone());
     }
 
-    let vectors = provider
-        .embed(all_texts.clone())
-        .await
-        .map_err(EmbeddingError::Provider)?;
+    let vectors = embed_in_batches(
+        provider.as_ref(),
+        all_texts.clone(),
+        &EmbeddingBatchOptions::default(),
+    )
+    .await?;
 
     let mut prepared = Vec::with_capacity(ids.len());
     let mut vectors_iter = vectors.into_iter();
@@ -351,10 +358,12 @@ where
         ids.push(doc.id);
     }
 
-    let vectors = provider
-        .embed(all_texts.clone())
-        .await
-        .map_err(EmbeddingError::Provider)?;
+    let vectors = embed_in_batches(
+        provider.as_ref(),
+        all_texts.clone(),
+        &EmbeddingBatchOptions::default(),
+    )
+    .await?;
 
     let mut prepared = Vec::with_capacity(ids.len());
     let mut vectors_iter = vectors.into_iter();
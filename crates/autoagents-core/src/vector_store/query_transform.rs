@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+
+use async_trait::async_trait;
+use autoagents_llm::LLMProvider;
+use autoagents_llm::chat::ChatMessage;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::request::QueryTransform;
+use super::{
+    HybridFusion, NamedVectorDocument, ScrollPage, VectorSearchRequest, VectorStoreError,
+    VectorStoreIndex,
+};
+use crate::embeddings::{Embed, EmbedImage};
+
+/// Rank constant of reciprocal rank fusion, as proposed by Cormack et al.
+pub const DEFAULT_RRF_K: f64 = 60.0;
+
+const HYDE_PROMPT: &str = "Write a short passage that answers the question below, as it would \
+appear in a document about the topic. Reply with the passage only.\n\nQuestion: ";
+
+const MULTI_QUERY_PROMPT: &str = "Write {count} different search queries that would find \
+documents answering the question below. Vary the wording and the angle. Reply with one query \
+per line and nothing else.\n\nQuestion: ";
+
+/// Leading list markers such as `1.`, `2)`, `-` or `*`
+static LIST_MARKER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:\d+[.)]|[-*•])\s*").expect("list marker regex is valid"));
+
+/// Wraps a vector store to apply the request's [`QueryTransform`] before searching
+///
+/// The configured LLM writes a hypothetical answer (HyDE) or several rewrites
+/// of the query. The store is searched with the original query and every
+/// generated one, and the result lists are merged with reciprocal rank fusion,
+/// so the returned scores are RRF scores rather than similarities. Requests
+/// without a transform, and all inserts, go straight to the wrapped store.
+pub struct QueryTransformIndex<S> {
+    store: S,
+    llm: Arc<dyn LLMProvider>,
+    rrf_k: f64,
+}
+
+impl<S> QueryTransformIndex<S> {
+    pub fn new(store: S, llm: Arc<dyn LLMProvider>) -> Self {
+        Self {
+            store,
+            llm,
+            rrf_k: DEFAULT_RRF_K,
+        }
+    }
+
+    pub fn with_rrf_k(mut self, rrf_k: f64) -> Self {
+        self.rrf_k = rrf_k;
+        self
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.store
+    }
+
+    /// The original query followed by the queries generated for `transform`
+    pub async fn expand_query(
+        &self,
+        query: &str,
+        transform: &QueryTransform,
+    ) -> Result<Vec<String>, VectorStoreError> {
+        let prompt = match transform {
+            QueryTransform::Hyde => format!("{HYDE_PROMPT}{query}"),
+            QueryTransform::MultiQuery { count: 0 } => return Ok(vec![query.to_string()]),
+            QueryTransform::MultiQuery { count } => {
+                format!(
+                    "{}{query}",
+                    MULTI_QUERY_PROMPT.replace("{count}", &count.to_string())
+                )
+            }
+        };
+        let response = self
+            .llm
+            .chat(&[ChatMessage::user().content(prompt).build()], None)
+            .await
+            .map_err(|err| VectorStoreError::QueryTransformError(err.to_string()))?;
+        let text = response.text().unwrap_or_default();
+
+        let mut queries = vec![query.to_string()];
+        match transform {
+            QueryTransform::Hyde => {
+                let passage = text.trim();
+                if !passage.is_empty() {
+                    queries.push(passage.to_string());
+                }
+            }
+            QueryTransform::MultiQuery { count } => {
+                let rewrites = text
+                    .lines()
+                    .map(|line| LIST_MARKER_RE.replace(line, "").trim().to_string())
+                    .filter(|line| !line.is_empty());
+                for rewrite in rewrites {
+                    if queries.len() > *count {
+                        break;
+                    }
+                    if !queries.contains(&rewrite) {
+                        queries.push(rewrite);
+                    }
+                }
+            }
+        }
+        Ok(queries)
+    }
+
+    async fn expanded_requests(
+        &self,
+        req: &VectorSearchRequest<S::Filter>,
+        transform: &QueryTransform,
+    ) -> Result<Vec<VectorSearchRequest<S::Filter>>, VectorStoreError>
+    where
+        S: VectorStoreIndex,
+        S::Filter: Clone,
+    {
+        let queries = self.expand_query(req.query(), transform).await?;
+        Ok(queries.into_iter().map(|q| req.with_query(q)).collect())
+    }
+}
+
+/// Merge ranked result lists with reciprocal rank fusion
+///
+/// Each result scores `1 / (k + rank)` per list it appears in (rank starting
+/// at 1). The first payload seen for an id is kept, and the `limit` best
+/// results are returned ordered by fused score.
+pub fn reciprocal_rank_fusion<T>(
+    rankings: Vec<Vec<(f64, String, T)>>,
+    k: f64,
+    limit: usize,
+) -> Vec<(f64, String, T)> {
+    let mut fused: HashMap<String, (f64, usize, T)> = HashMap::new();
+    let mut order = 0;
+    for ranking in rankings {
+        for (rank, (_, id, item)) in ranking.into_iter().enumerate() {
+            let score = 1.0 / (k + rank as f64 + 1.0);
+            match fused.get_mut(&id) {
+                Some(entry) => entry.0 += score,
+                None => {
+                    fused.insert(id, (score, order, item));
+                    order += 1;
+                }
+            }
+        }
+    }
+
+    let mut results: Vec<(f64, usize, String, T)> = fused
+        .into_iter()
+        .map(|(id, (score, order, item))| (score, order, id, item))
+        .collect();
+    // Ties keep the order in which results were first seen
+    results.sort_by(|a, b| {
+        b.0.partial_cmp(&a.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.1.cmp(&b.1))
+    });
+    results.truncate(limit);
+    results
+        .into_iter()
+        .map(|(score, _, id, item)| (score, id, item))
+        .collect()
+}
+
+#[async_trait]
+impl<S> VectorStoreIndex for QueryTransformIndex<S>
+where
+    S: VectorStoreIndex,
+    S::Filter: Clone,
+{
+    type Filter = S::Filter;
+
+    async fn insert_documents<T>(&self, documents: Vec<T>) -> Result<(), VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+    {
+        self.store.insert_documents(documents).await
+    }
+
+    async fn insert_documents_with_ids<T>(
+        &self,
+        documents: Vec<(String, T)>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+    {
+        self.store.insert_documents_with_ids(documents).await
+    }
+
+    async fn top_n<T>(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        if req.reranker().is_some() {
+            return super::rerank_top_n(self, req).await;
+        }
+        let Some(transform) = req.query_transform().cloned() else {
+            return self.store.top_n(req).await;
+        };
+        let requests = self.expanded_requests(&req, &transform).await?;
+        let rankings =
+            futures::future::try_join_all(requests.into_iter().map(|r| self.store.top_n(r)))
+                .await?;
+        Ok(reciprocal_rank_fusion(
+            rankings,
+            self.rrf_k,
+            req.samples() as usize,
+        ))
+    }
+
+    async fn top_n_ids(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        if req.reranker().is_some() {
+            return super::rerank_top_n_ids(self, req).await;
+        }
+        let Some(transform) = req.query_transform().cloned() else {
+            return self.store.top_n_ids(req).await;
+        };
+        let requests = self.expanded_requests(&req, &transform).await?;
+        let rankings =
+            futures::future::try_join_all(requests.into_iter().map(|r| self.store.top_n_ids(r)))
+                .await?;
+        let rankings = rankings
+            .into_iter()
+            .map(|ranking| {
+                ranking
+                    .into_iter()
+                    .map(|(score, id)| (score, id, ()))
+                    .collect()
+            })
+            .collect();
+        Ok(
+            reciprocal_rank_fusion(rankings, self.rrf_k, req.samples() as usize)
+                .into_iter()
+                .map(|(score, id, ())| (score, id))
+                .collect(),
+        )
+    }
+
+    /// Hybrid searches go straight to the wrapped store, without the query transform
+    async fn top_n_hybrid<T>(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+        fusion: HybridFusion,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        self.store.top_n_hybrid(req, fusion).await
+    }
+
+    async fn insert_documents_with_named_vectors<T>(
+        &self,
+        documents: Vec<NamedVectorDocument<T>>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: Serialize + Send + Sync + Clone,
+    {
+        self.store
+            .insert_documents_with_named_vectors(documents)
+            .await
+    }
+
+    async fn insert_image_documents_with_ids<T>(
+        &self,
+        documents: Vec<(String, T)>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: EmbedImage + Serialize + Send + Sync + Clone,
+    {
+        self.store.insert_image_documents_with_ids(documents).await
+    }
+
+    async fn delete_documents_by_ids(&self, ids: &[String]) -> Result<(), VectorStoreError> {
+        self.store.delete_documents_by_ids(ids).await
+    }
+
+    async fn scroll<T>(
+        &self,
+        filter: Option<Self::Filter>,
+        cursor: Option<String>,
+        limit: u64,
+    ) -> Result<ScrollPage<T>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        self.store.scroll(filter, cursor, limit).await
+    }
+
+    async fn count(&self, filter: Option<Self::Filter>) -> Result<u64, VectorStoreError> {
+        self.store.count(filter).await
+    }
+
+    fn field_filter_key(&self, field: &str) -> String {
+        self.store.field_filter_key(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::Document;
+    use crate::embeddings::SharedEmbeddingProvider;
+    use crate::testing::KeywordEmbeddingProvider;
+    use crate::tests::{ConfigurableLLMProvider, StaticChatResponse};
+    use crate::vector_store::in_memory_store::InMemoryVectorStore;
+
+    fn llm(reply: &str) -> Arc<dyn LLMProvider> {
+        Arc::new(ConfigurableLLMProvider {
+            chat_response: StaticChatResponse {
+                text: Some(reply.to_string()),
+                tool_calls: None,
+                usage: None,
+                thinking: None,
+            },
+            ..ConfigurableLLMProvider::default()
+        })
+    }
+
+    async fn index(reply: &str) -> QueryTransformIndex<InMemoryVectorStore> {
+        let provider: SharedEmbeddingProvider = Arc::new(KeywordEmbeddingProvider::new([
+            "rust",
+            "ownership",
+            "python",
+        ]));
+        let store = InMemoryVectorStore::new(provider);
+        store
+            .insert_documents_with_ids(vec![
+                ("rust".to_string(), Document::new("rust")),
+                ("ownership".to_string(), Document::new("rust ownership")),
+                ("python".to_string(), Document::new("python")),
+            ])
+            .await
+            .unwrap();
+        QueryTransformIndex::new(store, llm(reply))
+    }
+
+    #[tokio::test]
+    async fn test_multi_query_rewrites_are_parsed_and_capped() {
+        let index = index("1. rust borrow rules\n2) ownership in rust\n- rust\n\n* extra").await;
+        let queries = index
+            .expand_query("rust", &QueryTransform::MultiQuery { count: 2 })
+            .await
+            .unwrap();
+        assert_eq!(
+            queries,
+            vec!["rust", "rust borrow rules", "ownership in rust"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hyde_results_are_fused() {
+        let index = index("python ownership").await;
+        let plain = VectorSearchRequest::builder()
+            .query("rust")
+            .samples(3)
+            .build()
+            .unwrap();
+        let ids = |results: Vec<(f64, String)>| -> Vec<String> {
+            results.into_iter().map(|(_, id)| id).collect()
+        };
+        assert_eq!(
+            ids(index.top_n_ids(plain).await.unwrap()),
+            vec!["rust", "ownership", "python"]
+        );
+
+        let req = VectorSearchRequest::builder()
+            .query("rust")
+            .samples(3)
+            .query_transform(QueryTransform::Hyde)
+            .build()
+            .unwrap();
+        let results = index.top_n_ids(req.clone()).await.unwrap();
+        // The passage ranks python first, which lifts it over ownership
+        assert!((results[0].0 - (1.0 / 61.0 + 1.0 / 63.0)).abs() < 1e-9);
+        assert_eq!(ids(results), vec!["rust", "python", "ownership"]);
+
+        let documents: Vec<(f64, String, Document)> = index.top_n(req).await.unwrap();
+        assert_eq!(documents[1].2.page_content, "python");
+    }
+
+    #[test]
+    fn test_reciprocal_rank_fusion_ties_keep_first_seen_order() {
+        let fused = reciprocal_rank_fusion(
+            vec![
+                vec![(0.9, "a".to_string(), ()), (0.8, "b".to_string(), ())],
+                vec![(0.7, "b".to_string(), ()), (0.6, "a".to_string(), ())],
+                vec![(0.5, "c".to_string(), ())],
+            ],
+            DEFAULT_RRF_K,
+            10,
+        );
+        let ids: Vec<&str> = fused.iter().map(|(_, id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+    }
+}
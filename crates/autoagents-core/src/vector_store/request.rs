@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::VectorStoreError;
+use super::rerank::{RerankStage, SharedReranker};
 
 /// A vector search request - used in the [`super::VectorStoreIndex`] trait.
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -11,6 +12,20 @@ pub struct VectorSearchRequest<F = Filter<serde_json::Value>> {
     threshold: Option<f64>,
     additional_params: Option<serde_json::Value>,
     filter: Option<F>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    query_transform: Option<QueryTransform>,
+    #[serde(skip)]
+    reranker: Option<RerankStage>,
+}
+
+/// How a [`super::QueryTransformIndex`] rewrites the query before searching
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum QueryTransform {
+    /// Also search with a hypothetical answer written by the LLM (HyDE)
+    Hyde,
+    /// Also search with `count` rewrites of the query written by the LLM
+    MultiQuery { count: usize },
 }
 
 impl<Filter> VectorSearchRequest<Filter> {
@@ -38,6 +53,65 @@ impl<Filter> VectorSearchRequest<Filter> {
         &self.filter
     }
 
+    pub fn query_transform(&self) -> Option<&QueryTransform> {
+        self.query_transform.as_ref()
+    }
+
+    pub fn reranker(&self) -> Option<&RerankStage> {
+        self.reranker.as_ref()
+    }
+
+    /// Rerank the results with `reranker` before returning them
+    ///
+    /// The store fetches more candidates than `samples` by vector
+    /// similarity, scores them with the reranker and returns the best
+    /// `samples`. Rerankers are not serialized with the request.
+    pub fn with_reranker(mut self, reranker: SharedReranker) -> Self {
+        self.reranker = Some(RerankStage::new(reranker));
+        self
+    }
+
+    /// Number of candidates to rerank; only takes effect with a reranker set
+    pub fn with_rerank_candidates(mut self, candidates: u64) -> Self {
+        self.reranker = self.reranker.map(|stage| stage.with_candidates(candidates));
+        self
+    }
+
+    /// The same request without its reranking step
+    pub fn without_reranker(mut self) -> Self {
+        self.reranker = None;
+        self
+    }
+
+    /// The same request with a different query and no query transform
+    pub fn with_query(&self, query: impl Into<String>) -> Self
+    where
+        Filter: Clone,
+    {
+        Self {
+            query: query.into(),
+            query_transform: None,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_samples(mut self, samples: u64) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    /// The same request, also requiring `filter` to match
+    pub fn and_filter(mut self, filter: Filter) -> Self
+    where
+        Filter: SearchFilter,
+    {
+        self.filter = Some(match self.filter.take() {
+            Some(existing) => filter.and(existing),
+            None => filter,
+        });
+        self
+    }
+
     pub fn map_filter<T, F>(self, f: F) -> VectorSearchRequest<T>
     where
         F: Fn(Filter) -> T,
@@ -49,6 +123,8 @@ impl<Filter> VectorSearchRequest<Filter> {
             threshold: self.threshold,
             additional_params: self.additional_params,
             filter: self.filter.map(f),
+            query_transform: self.query_transform,
+            reranker: self.reranker,
         }
     }
 }
@@ -73,6 +149,11 @@ pub trait SearchFilter {
     fn eq(key: String, value: Self::Value) -> Self;
     fn gt(key: String, value: Self::Value) -> Self;
     fn lt(key: String, value: Self::Value) -> Self;
+    fn ne(key: String, value: Self::Value) -> Self;
+    fn is_in(key: String, values: Vec<Self::Value>) -> Self;
+    fn contains(key: String, value: Self::Value) -> Self;
+    fn between(key: String, low: Self::Value, high: Self::Value) -> Self;
+    fn exists(key: String) -> Self;
     fn and(self, rhs: Self) -> Self;
     fn or(self, rhs: Self) -> Self;
 }
@@ -84,8 +165,19 @@ where
     V: std::fmt::Debug + Clone,
 {
     Eq(String, V),
+    /// Also matches when `key` is missing
+    NotEq(String, V),
     Gt(String, V),
     Lt(String, V),
+    /// Equal to any of the values
+    In(String, Vec<V>),
+    /// Substring of a string value, or element of an array value
+    Contains(String, V),
+    /// Inclusive on both ends
+    Between(String, V, V),
+    /// Present, not null and not an empty array, as Qdrant's `is_empty`
+    /// defines it
+    Exists(String),
     And(Box<Self>, Box<Self>),
     Or(Box<Self>, Box<Self>),
 }
@@ -108,6 +200,26 @@ where
         Self::Lt(key, value)
     }
 
+    fn ne(key: String, value: Self::Value) -> Self {
+        Self::NotEq(key, value)
+    }
+
+    fn is_in(key: String, values: Vec<Self::Value>) -> Self {
+        Self::In(key, values)
+    }
+
+    fn contains(key: String, value: Self::Value) -> Self {
+        Self::Contains(key, value)
+    }
+
+    fn between(key: String, low: Self::Value, high: Self::Value) -> Self {
+        Self::Between(key, low, high)
+    }
+
+    fn exists(key: String) -> Self {
+        Self::Exists(key)
+    }
+
     fn and(self, rhs: Self) -> Self {
         Self::And(self.into(), rhs.into())
     }
@@ -129,6 +241,11 @@ where
             Self::Eq(key, val) => F::eq(key, val),
             Self::Gt(key, val) => F::gt(key, val),
             Self::Lt(key, val) => F::lt(key, val),
+            Self::NotEq(key, val) => F::ne(key, val),
+            Self::In(key, vals) => F::is_in(key, vals),
+            Self::Contains(key, val) => F::contains(key, val),
+            Self::Between(key, low, high) => F::between(key, low, high),
+            Self::Exists(key) => F::exists(key),
             Self::And(lhs, rhs) => F::and(lhs.interpret(), rhs.interpret()),
             Self::Or(lhs, rhs) => F::or(lhs.interpret(), rhs.interpret()),
         }
@@ -136,9 +253,17 @@ where
 }
 
 impl Filter<serde_json::Value> {
+    /// Evaluate the filter against a stored document
+    ///
+    /// Keys name a top-level field or a dot-separated path into nested
+    /// objects, such as `metadata.lang`. Every comparison is made against
+    /// the value found under the key, so `Eq` matches when that field equals
+    /// the value whatever else the document holds, and `Gt`/`Lt` match when
+    /// it orders after or before it. `Exists` matches any value but `null`
+    /// and `[]`; an empty string or object still exists.
     pub fn satisfies(&self, value: &serde_json::Value) -> bool {
         use Filter::*;
-        use serde_json::{Value, Value::*, json};
+        use serde_json::{Value, Value::*};
         use std::cmp::Ordering;
 
         fn compare_pair(l: &Value, r: &Value) -> Option<std::cmp::Ordering> {
@@ -156,14 +281,36 @@ impl Filter<serde_json::Value> {
             }
         }
 
+        fn lookup<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+            value.get(key).or_else(|| {
+                key.split('.')
+                    .try_fold(value, |value, part| value.get(part))
+            })
+        }
+
+        let compare =
+            |k: &str, v: &Value| lookup(value, k).and_then(|found| compare_pair(found, v));
+
         match self {
-            Eq(k, v) => &json!({ k: v }) == value,
-            Gt(k, v) => {
-                compare_pair(&json!({k: v}), value).is_some_and(|ord| ord == Ordering::Greater)
-            }
-            Lt(k, v) => {
-                compare_pair(&json!({k: v}), value).is_some_and(|ord| ord == Ordering::Less)
+            Eq(k, v) => lookup(value, k) == Some(v),
+            NotEq(k, v) => lookup(value, k) != Some(v),
+            Gt(k, v) => compare(k, v) == Some(Ordering::Greater),
+            Lt(k, v) => compare(k, v) == Some(Ordering::Less),
+            In(k, vs) => lookup(value, k).is_some_and(|found| vs.contains(found)),
+            Contains(k, v) => match (lookup(value, k), v) {
+                (Some(String(found)), String(part)) => found.contains(part.as_str()),
+                (Some(Array(items)), v) => items.contains(v),
+                _ => false,
+            },
+            Between(k, low, high) => {
+                compare(k, low).is_some_and(|ord| ord != Ordering::Less)
+                    && compare(k, high).is_some_and(|ord| ord != Ordering::Greater)
             }
+            Exists(k) => lookup(value, k).is_some_and(|found| match found {
+                Null => false,
+                Array(items) => !items.is_empty(),
+                _ => true,
+            }),
             And(l, r) => l.satisfies(value) && r.satisfies(value),
             Or(l, r) => l.satisfies(value) || r.satisfies(value),
         }
@@ -178,6 +325,9 @@ pub struct VectorSearchRequestBuilder<F = Filter<serde_json::Value>> {
     threshold: Option<f64>,
     additional_params: Option<serde_json::Value>,
     filter: Option<F>,
+    query_transform: Option<QueryTransform>,
+    #[serde(skip)]
+    reranker: Option<RerankStage>,
 }
 
 impl<F> Default for VectorSearchRequestBuilder<F> {
@@ -189,6 +339,8 @@ impl<F> Default for VectorSearchRequestBuilder<F> {
             threshold: None,
             additional_params: None,
             filter: None,
+            query_transform: None,
+            reranker: None,
         }
     }
 }
@@ -236,6 +388,18 @@ where
         self
     }
 
+    /// Rewrite the query with an LLM before searching; requires a [`super::QueryTransformIndex`]
+    pub fn query_transform(mut self, transform: QueryTransform) -> Self {
+        self.query_transform = Some(transform);
+        self
+    }
+
+    /// Rerank the results before returning them (see [`VectorSearchRequest::with_reranker`])
+    pub fn reranker(mut self, reranker: SharedReranker) -> Self {
+        self.reranker = Some(RerankStage::new(reranker));
+        self
+    }
+
     pub fn build(self) -> Result<VectorSearchRequest<F>, VectorStoreError> {
         let Some(query) = self.query else {
             return Err(VectorStoreError::BuilderError(
@@ -267,6 +431,8 @@ where
             threshold: self.threshold,
             additional_params,
             filter: self.filter,
+            query_transform: self.query_transform,
+            reranker: self.reranker,
         })
     }
 }
@@ -382,9 +548,8 @@ mod tests {
             Box::new(Filter::Eq("a".to_string(), json!(1))),
             Box::new(Filter::Eq("b".to_string(), json!(2))),
         );
-        // Note: satisfies checks json!({k:v}) == value, so both must match same value
-        // This won't match a single object with both - the Eq check is per-key
         assert!(!f.satisfies(&json!({"a": 1})));
+        assert!(f.satisfies(&json!({"a": 1, "b": 2})));
     }
 
     #[test]
@@ -402,11 +567,60 @@ mod tests {
     fn test_filter_satisfies_gt_and_lt() {
         let gt = Filter::Gt("score".to_string(), json!(5));
         assert!(!gt.satisfies(&json!({"score": 3})));
-        assert!(!gt.satisfies(&json!({"score": 7})));
+        assert!(gt.satisfies(&json!({"score": 7})));
 
         let lt = Filter::Lt("score".to_string(), json!(5));
         assert!(!lt.satisfies(&json!({"score": 7})));
-        assert!(!lt.satisfies(&json!({"score": 3})));
+        assert!(lt.satisfies(&json!({"score": 3})));
+        assert!(!lt.satisfies(&json!({"rank": 3})));
+    }
+
+    #[test]
+    fn test_filter_satisfies_nested_keys() {
+        let doc = json!({"page_content": "text", "metadata": {"lang": "rust", "year": 2024}});
+        assert!(Filter::Eq("metadata.lang".to_string(), json!("rust")).satisfies(&doc));
+        assert!(Filter::Gt("metadata.year".to_string(), json!(2020)).satisfies(&doc));
+        assert!(!Filter::Eq("lang".to_string(), json!("rust")).satisfies(&doc));
+    }
+
+    #[test]
+    fn test_filter_satisfies_not_eq_and_in() {
+        let doc = json!({"lang": "rust"});
+        assert!(Filter::NotEq("lang".to_string(), json!("go")).satisfies(&doc));
+        assert!(!Filter::NotEq("lang".to_string(), json!("rust")).satisfies(&doc));
+        assert!(Filter::NotEq("missing".to_string(), json!("rust")).satisfies(&doc));
+
+        let any = Filter::In("lang".to_string(), vec![json!("go"), json!("rust")]);
+        assert!(any.satisfies(&doc));
+        assert!(!any.satisfies(&json!({"lang": "python"})));
+        assert!(!Filter::In("lang".to_string(), vec![]).satisfies(&doc));
+    }
+
+    #[test]
+    fn test_filter_satisfies_contains() {
+        let doc = json!({"title": "Async Rust in practice", "tags": ["tokio", "async"]});
+        assert!(Filter::Contains("title".to_string(), json!("Rust")).satisfies(&doc));
+        assert!(!Filter::Contains("title".to_string(), json!("rust")).satisfies(&doc));
+        assert!(Filter::Contains("tags".to_string(), json!("tokio")).satisfies(&doc));
+        assert!(!Filter::Contains("tags".to_string(), json!("tok")).satisfies(&doc));
+        assert!(!Filter::Contains("missing".to_string(), json!("x")).satisfies(&doc));
+    }
+
+    #[test]
+    fn test_filter_satisfies_between_and_exists() {
+        let between = Filter::Between("year".to_string(), json!(2020), json!(2024));
+        assert!(between.satisfies(&json!({"year": 2020})));
+        assert!(between.satisfies(&json!({"year": 2024})));
+        assert!(!between.satisfies(&json!({"year": 2025})));
+        assert!(!between.satisfies(&json!({"year": "2022"})));
+
+        let exists = Filter::Exists("author".to_string());
+        assert!(exists.satisfies(&json!({"author": "ada"})));
+        assert!(!exists.satisfies(&json!({"author": null})));
+        assert!(!exists.satisfies(&json!({"title": "x"})));
+        assert!(!exists.satisfies(&json!({"author": []})));
+        assert!(exists.satisfies(&json!({"author": [null]})));
+        assert!(exists.satisfies(&json!({"author": ""})));
     }
 
     #[test]
@@ -447,6 +661,19 @@ mod tests {
         assert!(mapped.filter().is_some());
     }
 
+    #[test]
+    fn test_filter_interpret_new_variants() {
+        let f: Filter<serde_json::Value> = Filter::Between("x".to_string(), json!(1), json!(2))
+            .and(Filter::In("y".to_string(), vec![json!("a")]))
+            .or(Filter::Exists("z".to_string()));
+        let interpreted: Filter<serde_json::Value> = f.interpret();
+        let Filter::Or(lhs, rhs) = interpreted else {
+            panic!("expected or");
+        };
+        assert!(matches!(*lhs, Filter::And(ref l, _) if matches!(**l, Filter::Between(..))));
+        assert!(matches!(*rhs, Filter::Exists(ref k) if k == "z"));
+    }
+
     #[test]
     fn test_filter_serialize_deserialize() {
         let filter: Filter<serde_json::Value> = Filter::Eq("name".to_string(), json!("test"));
@@ -0,0 +1,282 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{VectorSearchRequest, VectorStoreError, VectorStoreIndex};
+
+/// Candidates fetched per requested result when a request is reranked
+pub const DEFAULT_RERANK_OVERSAMPLE: u64 = 4;
+
+/// Scores documents against a query, usually with a cross-encoder
+///
+/// Rerankers read the query and each document together, which ranks far
+/// better than comparing embeddings but is too slow to run over a whole
+/// store. Attach one to a search with [`VectorSearchRequest::with_reranker`]
+/// to rerank the candidates found by vector similarity.
+#[async_trait]
+pub trait Reranker: Send + Sync {
+    /// Relevance of each document to `query`, in the order given
+    ///
+    /// Higher is more relevant. Scores only need to be comparable within
+    /// one call.
+    async fn rerank(&self, query: &str, documents: &[String])
+    -> Result<Vec<f64>, VectorStoreError>;
+}
+
+pub type SharedReranker = Arc<dyn Reranker>;
+
+/// The reranking step of a [`VectorSearchRequest`]
+#[derive(Clone)]
+pub struct RerankStage {
+    reranker: SharedReranker,
+    candidates: Option<u64>,
+}
+
+impl RerankStage {
+    pub fn new(reranker: SharedReranker) -> Self {
+        Self {
+            reranker,
+            candidates: None,
+        }
+    }
+
+    /// Fetch `candidates` results to rerank instead of
+    /// [`DEFAULT_RERANK_OVERSAMPLE`] per requested result
+    pub fn with_candidates(mut self, candidates: u64) -> Self {
+        self.candidates = Some(candidates);
+        self
+    }
+
+    pub fn reranker(&self) -> &SharedReranker {
+        &self.reranker
+    }
+
+    /// Number of results fetched for a request asking for `samples`
+    pub fn candidates(&self, samples: u64) -> u64 {
+        self.candidates
+            .unwrap_or_else(|| samples.saturating_mul(DEFAULT_RERANK_OVERSAMPLE))
+            .max(samples)
+    }
+}
+
+impl std::fmt::Debug for RerankStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RerankStage")
+            .field("candidates", &self.candidates)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Text a reranker sees for a stored document
+///
+/// Strings are used as they are, objects by their `page_content` or `text`
+/// field, and anything else as JSON.
+pub fn rerank_text(document: &Value) -> String {
+    match document {
+        Value::String(text) => text.clone(),
+        Value::Object(fields) => fields
+            .get("page_content")
+            .or_else(|| fields.get("text"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| document.to_string()),
+        other => other.to_string(),
+    }
+}
+
+/// Run `req` against `store` and rerank the results with the request's reranker
+///
+/// Over-fetches candidates without the reranker, scores them against the
+/// query, and returns the best `samples` ordered by reranker score, which
+/// becomes the returned score. The request's threshold still applies to the
+/// vector similarity of the candidates. Stores call this from `top_n` when
+/// [`VectorSearchRequest::reranker`] is set.
+pub async fn rerank_top_n<S, T>(
+    store: &S,
+    req: VectorSearchRequest<S::Filter>,
+) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+where
+    S: VectorStoreIndex + ?Sized,
+    T: for<'de> Deserialize<'de> + Send + Sync,
+{
+    let Some(stage) = req.reranker().cloned() else {
+        return store.top_n(req).await;
+    };
+    let samples = req.samples();
+    let query = req.query().to_string();
+    let candidates: Vec<(f64, String, Value)> = store
+        .top_n(
+            req.without_reranker()
+                .with_samples(stage.candidates(samples)),
+        )
+        .await?;
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let texts: Vec<String> = candidates
+        .iter()
+        .map(|(_, _, document)| rerank_text(document))
+        .collect();
+    let scores = stage.reranker.rerank(&query, &texts).await?;
+    if scores.len() != candidates.len() {
+        return Err(VectorStoreError::RerankError(format!(
+            "reranker returned {} scores for {} documents",
+            scores.len(),
+            candidates.len()
+        )));
+    }
+
+    let mut ranked: Vec<(f64, String, Value)> = scores
+        .into_iter()
+        .zip(candidates)
+        .map(|(score, (_, id, document))| (score, id, document))
+        .collect();
+    // Stable, so ties keep the vector search order
+    ranked.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    ranked.truncate(samples as usize);
+    ranked
+        .into_iter()
+        .map(|(score, id, document)| Ok((score, id, serde_json::from_value(document)?)))
+        .collect()
+}
+
+/// [`rerank_top_n`] returning only scores and ids
+pub async fn rerank_top_n_ids<S>(
+    store: &S,
+    req: VectorSearchRequest<S::Filter>,
+) -> Result<Vec<(f64, String)>, VectorStoreError>
+where
+    S: VectorStoreIndex + ?Sized,
+{
+    let results: Vec<(f64, String, Value)> = rerank_top_n(store, req).await?;
+    Ok(results
+        .into_iter()
+        .map(|(score, id, _)| (score, id))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::Document;
+    use crate::embeddings::SharedEmbeddingProvider;
+    use crate::testing::KeywordEmbeddingProvider;
+    use crate::vector_store::in_memory_store::InMemoryVectorStore;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Scores documents by how often they mention the query
+    #[derive(Default)]
+    struct CountingReranker {
+        seen: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Reranker for CountingReranker {
+        async fn rerank(
+            &self,
+            query: &str,
+            documents: &[String],
+        ) -> Result<Vec<f64>, VectorStoreError> {
+            self.seen.fetch_add(documents.len(), Ordering::SeqCst);
+            Ok(documents
+                .iter()
+                .map(|document| document.matches(query).count() as f64)
+                .collect())
+        }
+    }
+
+    struct BrokenReranker;
+
+    #[async_trait]
+    impl Reranker for BrokenReranker {
+        async fn rerank(&self, _: &str, _: &[String]) -> Result<Vec<f64>, VectorStoreError> {
+            Ok(vec![1.0])
+        }
+    }
+
+    async fn store() -> InMemoryVectorStore {
+        let provider: SharedEmbeddingProvider = Arc::new(KeywordEmbeddingProvider::flat());
+        let store = InMemoryVectorStore::new(provider);
+        store
+            .insert_documents_with_ids(
+                [
+                    ("a", "nothing here"),
+                    ("b", "rust once"),
+                    ("c", "rust rust rust"),
+                    ("d", "rust rust"),
+                    ("e", "still nothing"),
+                ]
+                .into_iter()
+                .map(|(id, text)| (id.to_string(), Document::new(text)))
+                .collect(),
+            )
+            .await
+            .unwrap();
+        store
+    }
+
+    #[tokio::test]
+    async fn test_reranker_reorders_over_fetched_candidates() {
+        let store = store().await;
+        let reranker = Arc::new(CountingReranker::default());
+        let req = VectorSearchRequest::builder()
+            .query("rust")
+            .samples(2)
+            .reranker(reranker.clone())
+            .build()
+            .unwrap();
+
+        let results: Vec<(f64, String, Document)> = store.top_n(req).await.unwrap();
+        let ids: Vec<&str> = results.iter().map(|(_, id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "d"]);
+        assert_eq!(results[0].0, 3.0);
+        assert_eq!(results[0].2.page_content, "rust rust rust");
+        // Two results at the default oversample, capped by the five stored
+        assert_eq!(reranker.seen.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_candidate_count_limits_the_reranked_pool() {
+        let store = store().await;
+        let reranker = Arc::new(CountingReranker::default());
+        let req = VectorSearchRequest::builder()
+            .query("rust")
+            .samples(1)
+            .build()
+            .unwrap()
+            .with_reranker(reranker.clone())
+            .with_rerank_candidates(3);
+
+        let ids = store.top_n_ids(req).await.unwrap();
+        assert_eq!(ids.len(), 1);
+        assert_eq!(reranker.seen.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_score_count_is_an_error() {
+        let store = store().await;
+        let req = VectorSearchRequest::builder()
+            .query("rust")
+            .samples(2)
+            .reranker(Arc::new(BrokenReranker))
+            .build()
+            .unwrap();
+        let err = store.top_n_ids(req).await.unwrap_err();
+        assert!(matches!(err, VectorStoreError::RerankError(_)));
+    }
+
+    #[test]
+    fn test_rerank_text_prefers_page_content() {
+        assert_eq!(rerank_text(&json!("plain")), "plain");
+        assert_eq!(
+            rerank_text(&json!({ "page_content": "body", "metadata": {} })),
+            "body"
+        );
+        assert_eq!(rerank_text(&json!({ "text": "t" })), "t");
+        assert_eq!(rerank_text(&json!({ "n": 1 })), r#"{"n":1}"#);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::resolve;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{DeriveInput, Error, ExprPath, Ident, LitStr, Path, Result, parse_macro_input};
+
+/// Hooks that `#[hooks(...)]` can delegate, in `AgentHooks` declaration order
+const HOOK_NAMES: &[&str] = &[
+    "on_agent_create",
+    "on_run_start",
+    "on_run_complete",
+    "on_turn_start",
+    "on_turn_complete",
+    "on_tool_call",
+    "on_tool_start",
+    "on_tool_result",
+    "on_tool_error",
+    "on_agent_shutdown",
+];
+
+/// `#[hooks(on_tool_result = my_fn, on_run_start = "checks::gate")]`
+///
+/// Each entry names an async function taking `&Self` followed by the hook's
+/// own arguments; hooks without an entry keep their default body.
+#[derive(Debug, Default)]
+pub(crate) struct HookDelegates {
+    pub(crate) delegates: Vec<(Ident, Path)>,
+}
+
+impl HookDelegates {
+    pub(crate) fn from_attrs(attrs: &[syn::Attribute]) -> Result<Self> {
+        let mut parsed = HookDelegates::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("hooks")) {
+            attr.parse_nested_meta(|meta| {
+                let hook = meta.path.require_ident()?.clone();
+                if !HOOK_NAMES.contains(&hook.to_string().as_str()) {
+                    return Err(meta.error(format!(
+                        "unknown hook `{hook}`; expected one of: {}",
+                        HOOK_NAMES.join(", ")
+                    )));
+                }
+                if parsed.delegates.iter().any(|(name, _)| *name == hook) {
+                    return Err(meta.error(format!("duplicate hook `{hook}`")));
+                }
+                let value = meta.value()?;
+                let path = if value.peek(LitStr) {
+                    value.parse::<LitStr>()?.parse::<Path>()?
+                } else {
+                    value.parse::<ExprPath>()?.path
+                };
+                parsed.delegates.push((hook, path));
+                Ok(())
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
+/// The delegating `AgentHooks` method for one hook
+fn hook_method(hook: &Ident, path: &Path, core: &Path) -> TokenStream2 {
+    let context = quote! { &#core::agent::Context };
+    let tool_call = quote! { &#core::tool::ToolCall };
+    match hook.to_string().as_str() {
+        "on_agent_create" | "on_agent_shutdown" => quote! {
+            async fn #hook(&self) {
+                #path(self).await
+            }
+        },
+        "on_run_start" => quote! {
+            async fn #hook(
+                &self,
+                task: &#core::agent::task::Task,
+                ctx: #context,
+            ) -> #core::agent::HookOutcome {
+                #path(self, task, ctx).await
+            }
+        },
+        "on_run_complete" => quote! {
+            async fn #hook(
+                &self,
+                task: &#core::agent::task::Task,
+                result: &<Self as #core::agent::AgentDeriveT>::Output,
+                ctx: #context,
+            ) {
+                #path(self, task, result, ctx).await
+            }
+        },
+        "on_turn_start" | "on_turn_complete" => quote! {
+            async fn #hook(&self, turn_index: usize, ctx: #context) {
+                #path(self, turn_index, ctx).await
+            }
+        },
+        "on_tool_call" => quote! {
+            async fn #hook(&self, tool_call: #tool_call, ctx: #context) -> #core::agent::HookOutcome {
+                #path(self, tool_call, ctx).await
+            }
+        },
+        "on_tool_start" => quote! {
+            async fn #hook(&self, tool_call: #tool_call, ctx: #context) {
+                #path(self, tool_call, ctx).await
+            }
+        },
+        "on_tool_result" => quote! {
+            async fn #hook(
+                &self,
+                tool_call: #tool_call,
+                result: &#core::tool::ToolCallResult,
+                ctx: #context,
+            ) {
+                #path(self, tool_call, result, ctx).await
+            }
+        },
+        "on_tool_error" => quote! {
+            async fn #hook(&self, tool_call: #tool_call, err: ::serde_json::Value, ctx: #context) {
+                #path(self, tool_call, err, ctx).await
+            }
+        },
+        other => unreachable!("unvalidated hook `{other}`"),
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct HooksParser {}
+
+impl HooksParser {
+    pub fn parse(&self, input: TokenStream) -> TokenStream {
+        let input = parse_macro_input!(input as DeriveInput);
+        self.expand(&input)
+            .unwrap_or_else(Error::into_compile_error)
+            .into()
+    }
+
+    fn expand(&self, input: &DeriveInput) -> Result<TokenStream2> {
+        let HookDelegates { delegates } = HookDelegates::from_attrs(&input.attrs)?;
+        let (core, async_trait) = resolve::resolve_core_and_async_trait_paths()?;
+        let name = &input.ident;
+        let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+        let methods = delegates
+            .iter()
+            .map(|(hook, path)| hook_method(hook, path, &core));
+
+        Ok(quote! {
+            #[#async_trait]
+            impl #impl_generics #core::agent::AgentHooks for #name #ty_generics #where_clause {
+                #(#methods)*
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delegates(source: &str) -> Result<HookDelegates> {
+        let input: DeriveInput = syn::parse_str(source).unwrap();
+        HookDelegates::from_attrs(&input.attrs)
+    }
+
+    #[test]
+    fn hooks_attribute_accepts_paths_and_strings() {
+        let parsed = delegates(
+            r#"
+            #[hooks(on_tool_result = log_result)]
+            #[hooks(on_run_start = "checks::gate")]
+            struct Agent;
+            "#,
+        )
+        .unwrap();
+        let names: Vec<String> = parsed
+            .delegates
+            .iter()
+            .map(|(hook, path)| format!("{hook}={}", quote!(#path)))
+            .collect();
+        assert_eq!(
+            names,
+            ["on_tool_result=log_result", "on_run_start=checks :: gate"]
+        );
+        assert!(delegates("struct Agent;").unwrap().delegates.is_empty());
+    }
+
+    #[test]
+    fn hooks_attribute_rejects_unknown_and_duplicate_hooks() {
+        let err = delegates("#[hooks(on_finish = done)] struct Agent;").unwrap_err();
+        assert!(err.to_string().contains("unknown hook `on_finish`"));
+
+        let err =
+            delegates("#[hooks(on_tool_start = a, on_tool_start = b)] struct Agent;").unwrap_err();
+        assert!(err.to_string().contains("duplicate hook `on_tool_start`"));
+
+        assert!(delegates("#[hooks(on_tool_start)] struct Agent;").is_err());
+    }
+
+    #[test]
+    fn every_hook_has_a_delegating_method() {
+        let core: Path = syn::parse_quote!(::autoagents_core);
+        let path: Path = syn::parse_quote!(handler);
+        for hook in HOOK_NAMES {
+            let hook = Ident::new(hook, proc_macro2::Span::call_site());
+            let method = hook_method(&hook, &path, &core).to_string();
+            assert!(method.contains(&format!("async fn {hook}")), "{method}");
+            assert!(method.contains("handler (self"), "{method}");
+        }
+    }
+}
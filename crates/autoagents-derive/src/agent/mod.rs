@@ -1,19 +1,58 @@
-use crate::resolve;
+use crate::{doc, resolve};
 use proc_macro::TokenStream;
 use quote::quote;
 use strum::{Display, EnumString};
 use syn::{
-    Expr, Ident, ItemStruct, LitStr, Token, Type, bracketed, parse::Parse, parse_macro_input,
-    punctuated::Punctuated,
+    Expr, ExprPath, Ident, ItemStruct, Lit, LitBool, LitInt, LitStr, Token, Type, bracketed,
+    parse::Parse, parse_macro_input, punctuated::Punctuated,
 };
 
+pub(crate) mod hooks;
 pub(crate) mod output;
 
 pub(crate) struct AgentAttributes {
     pub(crate) name: LitStr,
-    pub(crate) description: LitStr,
+    /// Falls back to the doc comment of the agent struct when omitted
+    pub(crate) description: Option<LitStr>,
     pub(crate) tools: Option<Vec<Expr>>,
     pub(crate) output: Option<Type>,
+    pub(crate) max_turns: Option<LitInt>,
+    pub(crate) temperature: Option<f32>,
+    pub(crate) tool_choice: Option<ToolChoiceAttr>,
+    pub(crate) output_strict: Option<LitBool>,
+    /// `fn(&Context, &Task) -> impl Into<String>` building the system prompt per run
+    pub(crate) prompt_fn: Option<ExprPath>,
+}
+
+/// `tool_choice = "auto" | "none" | "<tool name>"`
+pub(crate) enum ToolChoiceAttr {
+    Auto,
+    None,
+    Tool(LitStr),
+}
+
+impl ToolChoiceAttr {
+    fn from_lit(lit: LitStr) -> syn::Result<Self> {
+        Ok(match lit.value().as_str() {
+            "auto" => Self::Auto,
+            "none" => Self::None,
+            // Tools are narrowed per call, but providers take no per-call
+            // `tool_choice`, so a forced call cannot be honoured here.
+            "required" | "any" => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "tool_choice = \"required\" is not supported; set `tool_choice` on the LLM provider to force a tool call",
+                ));
+            }
+            "" => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "tool_choice must be \"auto\", \"none\" or a tool name",
+                ));
+            }
+            _ => Self::Tool(lit),
+        })
+    }
 }
 
 #[derive(EnumString, Display)]
@@ -26,6 +65,16 @@ pub(crate) enum AgentAttributeKeys {
     Tools,
     #[strum(serialize = "output")]
     Output,
+    #[strum(serialize = "max_turns")]
+    MaxTurns,
+    #[strum(serialize = "temperature")]
+    Temperature,
+    #[strum(serialize = "tool_choice")]
+    ToolChoice,
+    #[strum(serialize = "output_strict")]
+    OutputStrict,
+    #[strum(serialize = "prompt_fn")]
+    PromptFn,
     Unknown(String),
 }
 
@@ -36,6 +85,11 @@ impl From<Ident> for AgentAttributeKeys {
             "description" => Self::Description,
             "tools" => Self::Tools,
             "output" => Self::Output,
+            "max_turns" => Self::MaxTurns,
+            "temperature" => Self::Temperature,
+            "tool_choice" => Self::ToolChoice,
+            "output_strict" => Self::OutputStrict,
+            "prompt_fn" => Self::PromptFn,
             other => Self::Unknown(other.to_string()),
         }
     }
@@ -47,6 +101,11 @@ impl Parse for AgentAttributes {
         let mut description = None;
         let mut tools = None;
         let mut output = None;
+        let mut max_turns = None;
+        let mut temperature = None;
+        let mut tool_choice = None;
+        let mut output_strict = None;
+        let mut prompt_fn = None;
 
         while !input.is_empty() {
             let key: Ident = input.parse()?;
@@ -73,6 +132,42 @@ impl Parse for AgentAttributes {
                         content.parse_terminated(Expr::parse, Token![,])?;
                     tools = Some(punctuated_exprs.into_iter().collect::<Vec<Expr>>());
                 }
+                AgentAttributeKeys::MaxTurns => {
+                    let lit = input.parse::<LitInt>()?;
+                    if lit.base10_parse::<usize>()? == 0 {
+                        return Err(syn::Error::new(lit.span(), "max_turns must be at least 1"));
+                    }
+                    max_turns = Some(lit);
+                }
+                AgentAttributeKeys::Temperature => {
+                    let lit = input.parse::<Lit>()?;
+                    let value = match &lit {
+                        Lit::Float(float) => float.base10_parse::<f32>()?,
+                        Lit::Int(int) => int.base10_parse::<f32>()?,
+                        _ => {
+                            return Err(syn::Error::new(
+                                lit.span(),
+                                "temperature must be a number",
+                            ));
+                        }
+                    };
+                    if !value.is_finite() || value < 0.0 {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "temperature must be a non-negative number",
+                        ));
+                    }
+                    temperature = Some(value);
+                }
+                AgentAttributeKeys::ToolChoice => {
+                    tool_choice = Some(ToolChoiceAttr::from_lit(input.parse::<LitStr>()?)?);
+                }
+                AgentAttributeKeys::OutputStrict => {
+                    output_strict = Some(input.parse::<LitBool>()?);
+                }
+                AgentAttributeKeys::PromptFn => {
+                    prompt_fn = Some(input.parse::<ExprPath>()?);
+                }
                 AgentAttributeKeys::Unknown(other) => {
                     return Err(syn::Error::new(
                         key_span,
@@ -91,14 +186,14 @@ impl Parse for AgentAttributes {
                     format!("Missing attribute: {}", AgentAttributeKeys::Name),
                 )
             })?,
-            description: description.ok_or_else(|| {
-                syn::Error::new(
-                    input.span(),
-                    format!("Missing attribute: {}", AgentAttributeKeys::Description),
-                )
-            })?,
+            description,
             output,
             tools,
+            max_turns,
+            temperature,
+            tool_choice,
+            output_strict,
+            prompt_fn,
         })
     }
 }
@@ -111,19 +206,30 @@ impl AgentParser {
         let agent_attrs = parse_macro_input!(attr as AgentAttributes);
         let input_struct = parse_macro_input!(item as ItemStruct);
 
-        let core = match resolve::resolve_core_path() {
-            Ok(core) => core,
-            Err(err) => return err.to_compile_error().into(),
-        };
-        let core = &core;
-
         let struct_name = &input_struct.ident;
         let AgentAttributes {
             name: agent_name_literal,
             description: agent_description,
             tools,
             output: output_type,
+            max_turns,
+            temperature,
+            tool_choice,
+            output_strict,
+            prompt_fn,
         } = agent_attrs;
+        let agent_description =
+            match doc::description_or_docs(agent_description, &input_struct.attrs) {
+                Ok(description) => description,
+                Err(err) => return err.to_compile_error().into(),
+            };
+
+        let core = match resolve::resolve_core_path() {
+            Ok(core) => core,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let core = &core;
+
         let tool_initializers = tools
             .unwrap_or_default()
             .into_iter()
@@ -155,6 +261,54 @@ impl AgentParser {
             }
         };
 
+        let max_turns_impl = max_turns.map(|max_turns| {
+            quote! {
+                fn max_turns(&self) -> Option<usize> {
+                    Some(#max_turns)
+                }
+            }
+        });
+        let temperature_impl = temperature.map(|temperature| {
+            quote! {
+                fn temperature(&self) -> Option<f32> {
+                    Some(#temperature)
+                }
+            }
+        });
+        let tool_choice_impl = tool_choice.map(|tool_choice| {
+            let choice = match tool_choice {
+                ToolChoiceAttr::Auto => quote! { #core::agent::ToolChoice::Auto },
+                ToolChoiceAttr::None => quote! { #core::agent::ToolChoice::None },
+                ToolChoiceAttr::Tool(name) => {
+                    quote! { #core::agent::ToolChoice::Tool(#name.to_string()) }
+                }
+            };
+            quote! {
+                fn tool_choice(&self) -> Option<#core::agent::ToolChoice> {
+                    Some(#choice)
+                }
+            }
+        });
+        let output_strict_impl = output_strict.map(|strict| {
+            quote! {
+                fn output_strict(&self) -> Option<bool> {
+                    Some(#strict)
+                }
+            }
+        });
+
+        let prompt_fn_impl = prompt_fn.map(|prompt_fn| {
+            quote! {
+                fn system_prompt(
+                    &self,
+                    context: &#core::agent::Context,
+                    task: &#core::agent::task::Task,
+                ) -> Option<String> {
+                    Some(::std::convert::Into::into(#prompt_fn(context, task)))
+                }
+            }
+        });
+
         let expanded = quote! {
             #input_struct
 
@@ -178,6 +332,12 @@ impl AgentParser {
                         ),*
                     ]
                 }
+
+                #max_turns_impl
+                #temperature_impl
+                #tool_choice_impl
+                #output_strict_impl
+                #prompt_fn_impl
             }
 
             impl std::fmt::Debug for #struct_name {
@@ -199,7 +359,7 @@ mod tests {
         let attrs: AgentAttributes =
             syn::parse_str(r#"name = "TestAgent", description = "Test description""#).unwrap();
         assert_eq!(attrs.name.value(), "TestAgent");
-        assert_eq!(attrs.description.value(), "Test description");
+        assert_eq!(attrs.description.unwrap().value(), "Test description");
         assert!(attrs.tools.is_none());
         assert!(attrs.output.is_none());
     }
@@ -224,6 +384,12 @@ mod tests {
         assert!(err.to_string().contains("Unexpected attribute key"));
     }
 
+    #[test]
+    fn parse_attributes_description_is_optional() {
+        let attrs: AgentAttributes = syn::parse_str(r#"name = "TestAgent""#).unwrap();
+        assert!(attrs.description.is_none());
+    }
+
     #[test]
     fn parse_attributes_missing_name_errors() {
         let err = syn::parse_str::<AgentAttributes>(r#"description = "Test description""#)
@@ -232,6 +398,68 @@ mod tests {
         assert!(err.to_string().contains("Missing attribute: name"));
     }
 
+    #[test]
+    fn parse_attributes_with_executor_settings() {
+        let attrs: AgentAttributes = syn::parse_str(
+            r#"name = "TestAgent", description = "Test description", max_turns = 4, temperature = 0.2, tool_choice = "lookup", output_strict = false"#,
+        )
+        .unwrap();
+        assert_eq!(attrs.max_turns.unwrap().base10_parse::<usize>().unwrap(), 4);
+        assert_eq!(attrs.temperature, Some(0.2));
+        assert!(
+            matches!(attrs.tool_choice, Some(ToolChoiceAttr::Tool(name)) if name.value() == "lookup")
+        );
+        assert!(!attrs.output_strict.unwrap().value);
+
+        let attrs: AgentAttributes = syn::parse_str(
+            r#"name = "A", description = "B", temperature = 1, tool_choice = "none""#,
+        )
+        .unwrap();
+        assert_eq!(attrs.temperature, Some(1.0));
+        assert!(matches!(attrs.tool_choice, Some(ToolChoiceAttr::None)));
+    }
+
+    #[test]
+    fn parse_attributes_rejects_invalid_executor_settings() {
+        for (source, message) in [
+            (
+                r#"name = "A", description = "B", max_turns = 0"#,
+                "at least 1",
+            ),
+            (
+                r#"name = "A", description = "B", temperature = -0.5"#,
+                "non-negative",
+            ),
+            (
+                r#"name = "A", description = "B", temperature = "hot""#,
+                "must be a number",
+            ),
+            (
+                r#"name = "A", description = "B", tool_choice = """#,
+                "tool_choice must be",
+            ),
+            (
+                r#"name = "A", description = "B", tool_choice = "required""#,
+                "is not supported",
+            ),
+        ] {
+            let err = syn::parse_str::<AgentAttributes>(source)
+                .err()
+                .expect("expected parse error");
+            assert!(err.to_string().contains(message), "{source}: {err}");
+        }
+    }
+
+    #[test]
+    fn parse_attributes_with_prompt_fn() {
+        let attrs: AgentAttributes =
+            syn::parse_str(r#"name = "A", prompt_fn = prompts::system"#).unwrap();
+        let prompt_fn = attrs.prompt_fn.unwrap();
+        assert_eq!(quote!(#prompt_fn).to_string(), "prompts :: system");
+
+        assert!(syn::parse_str::<AgentAttributes>(r#"name = "A", prompt_fn = "system""#).is_err());
+    }
+
     #[test]
     fn agent_attribute_keys_from_ident() {
         let name: AgentAttributeKeys = syn::parse_str::<Ident>("name").unwrap().into();
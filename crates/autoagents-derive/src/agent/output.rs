@@ -2,16 +2,18 @@ use super::super::tool::{
     field::{Choice, FieldSchemaAttr},
     json::JsonType,
 };
+use crate::doc::doc_description;
 use crate::resolve;
 use crate::schema_emit;
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use serde::Serialize;
 use std::collections::BTreeMap;
 use strum::{Display, EnumString};
 use syn::{
-    Attribute, Data, DataStruct, DeriveInput, Error, Field, Ident, LitStr, Result, Type,
-    parse_macro_input, spanned::Spanned,
+    Attribute, Data, DataStruct, DeriveInput, Error, Field, Ident, LitStr, Path, Result, Token,
+    Type, parse::Parse, parse_macro_input, spanned::Spanned,
 };
 
 #[derive(EnumString, Display)]
@@ -48,10 +50,180 @@ pub(crate) struct StructuredOutputFormat {
     strict: Option<bool>,
 }
 
+/// Executor whose output the derive converts from, `#[output(from = ...)]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExecutorOutput {
+    Basic,
+    ReAct,
+    CodeAct,
+}
+
+impl ExecutorOutput {
+    fn from_ident(ident: &Ident) -> Result<Self> {
+        match ident.to_string().as_str() {
+            "basic" => Ok(Self::Basic),
+            "react" => Ok(Self::ReAct),
+            "codeact" => Ok(Self::CodeAct),
+            other => Err(Error::new(
+                ident.span(),
+                format!("unknown executor `{other}`, expected `basic`, `react` or `codeact`"),
+            )),
+        }
+    }
+
+    fn type_tokens(self, core: &Path) -> TokenStream2 {
+        match self {
+            Self::Basic => quote!(#core::agent::prebuilt::executor::BasicAgentOutput),
+            Self::ReAct => quote!(#core::agent::prebuilt::executor::ReActAgentOutput),
+            Self::CodeAct => quote!(#core::agent::prebuilt::executor::CodeActAgentOutput),
+        }
+    }
+}
+
+/// Container-level `#[output(from = ..., fallback = ..., error = ...)]`
+///
+/// Generates `From<ExecutorOutput>` impls that parse the response as JSON. When
+/// parsing fails, the value comes from `fallback_with`, or is built with the raw
+/// response in the `fallback` field and `Default::default()` everywhere else.
+/// The `error` field receives the parse error message.
+#[derive(Debug, Default)]
+struct OutputConversion {
+    from: Vec<ExecutorOutput>,
+    fallback: Option<Ident>,
+    fallback_with: Option<Path>,
+    error: Option<Ident>,
+}
+
+impl OutputConversion {
+    fn parse(attr: &Attribute) -> Result<Self> {
+        let mut conversion = OutputConversion::default();
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("from") {
+                let value = meta.value()?;
+                let executors: Vec<Ident> = if value.peek(syn::token::Bracket) {
+                    let content;
+                    syn::bracketed!(content in value);
+                    content
+                        .parse_terminated(Ident::parse, Token![,])?
+                        .into_iter()
+                        .collect()
+                } else {
+                    vec![value.parse()?]
+                };
+                for ident in &executors {
+                    let executor = ExecutorOutput::from_ident(ident)?;
+                    if conversion.from.contains(&executor) {
+                        return Err(Error::new(ident.span(), "duplicate executor"));
+                    }
+                    conversion.from.push(executor);
+                }
+                Ok(())
+            } else if meta.path.is_ident("fallback") {
+                conversion.fallback = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("fallback_with") {
+                conversion.fallback_with = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("error") {
+                conversion.error = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error(
+                    "unsupported `#[output]` option, expected `from`, `fallback`, `fallback_with` or `error`",
+                ))
+            }
+        })?;
+
+        if conversion.from.is_empty() {
+            return Err(Error::new(
+                attr.span(),
+                "`#[output(...)]` on an AgentOutput struct needs `from = react` (or `basic`, `codeact`, or a list)",
+            ));
+        }
+        if let (Some(_), Some(path)) = (&conversion.fallback, &conversion.fallback_with) {
+            return Err(Error::new(
+                path.span(),
+                "`fallback` and `fallback_with` cannot be combined",
+            ));
+        }
+        if let (Some(fallback), Some(error)) = (&conversion.fallback, &conversion.error)
+            && fallback == error
+        {
+            return Err(Error::new(
+                error.span(),
+                "`fallback` and `error` must name different fields",
+            ));
+        }
+        Ok(conversion)
+    }
+
+    fn validate_fields(&self, fields: &[Ident]) -> Result<()> {
+        for ident in self.fallback.iter().chain(&self.error) {
+            if !fields.contains(ident) {
+                return Err(Error::new(
+                    ident.span(),
+                    format!("no field named `{ident}` in this struct"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn to_tokens(&self, ident: &Ident, fields: &[Ident], core: &Path) -> TokenStream2 {
+        let error_value = quote!(::std::convert::Into::into(
+            ::std::string::ToString::to_string(&err)
+        ));
+        let fallback = match &self.fallback_with {
+            Some(path) => match &self.error {
+                Some(error) => quote! {{
+                    let mut value: Self = #path(&output.response);
+                    value.#error = #error_value;
+                    value
+                }},
+                None => quote!(#path(&output.response)),
+            },
+            None => {
+                let inits = fields.iter().map(|field| {
+                    if self.fallback.as_ref() == Some(field) {
+                        quote!(#field: ::std::convert::Into::into(output.response))
+                    } else if self.error.as_ref() == Some(field) {
+                        quote!(#field: #error_value)
+                    } else {
+                        quote!(#field: ::std::default::Default::default())
+                    }
+                });
+                quote!(Self { #(#inits),* })
+            }
+        };
+        let err_binding = if self.error.is_some() {
+            quote!(err)
+        } else {
+            quote!(_)
+        };
+
+        let impls = self.from.iter().map(|executor| {
+            let source = executor.type_tokens(core);
+            quote! {
+                impl ::std::convert::From<#source> for #ident {
+                    fn from(output: #source) -> Self {
+                        match ::serde_json::from_str::<Self>(output.response.trim()) {
+                            Ok(value) => value,
+                            Err(#err_binding) => #fallback,
+                        }
+                    }
+                }
+            }
+        });
+        quote!(#(#impls)*)
+    }
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct OutputParser {
     output_data: StructuredOutputFormat,
     ident: Option<Ident>,
+    conversion: Option<OutputConversion>,
+    fields: Vec<Ident>,
 }
 
 impl OutputParser {
@@ -72,6 +244,12 @@ impl OutputParser {
             return err.to_compile_error().into();
         }
 
+        if let Some(conversion) = &self.conversion
+            && let Err(err) = conversion.validate_fields(&self.fields)
+        {
+            return err.to_compile_error().into();
+        }
+
         let core = match resolve::resolve_core_path() {
             Ok(core) => core,
             Err(err) => return err.to_compile_error().into(),
@@ -96,6 +274,10 @@ impl OutputParser {
         };
 
         let schema_literal = LitStr::new(&serialized_data, struct_span);
+        let conversions = self
+            .conversion
+            .as_ref()
+            .map(|conversion| conversion.to_tokens(&struct_ident, &self.fields, core));
 
         let expanded = quote! {
             impl #core::agent::AgentOutputT for #struct_ident {
@@ -109,32 +291,17 @@ impl OutputParser {
                     (*SCHEMA).clone()
                 }
             }
+
+            #conversions
         };
         TokenStream::from(expanded)
     }
 
     fn parse_struct_attributes(&mut self, attrs: &[Attribute]) -> Result<()> {
+        // Doc comments on the struct become the schema description.
+        self.output_data.description = doc_description(attrs);
         for attr in attrs {
-            if attr.path().is_ident("doc") {
-                // Extract documentation comments as description
-                // Doc comments are stored as #[doc = "content"] attributes
-                if let syn::Meta::NameValue(meta) = &attr.meta
-                    && let syn::Expr::Lit(syn::ExprLit {
-                        lit: syn::Lit::Str(lit_str),
-                        ..
-                    }) = &meta.value
-                {
-                    let doc_value = lit_str.value().trim().to_string();
-                    if !doc_value.is_empty() {
-                        let description =
-                            self.output_data.description.get_or_insert_with(String::new);
-                        if !description.is_empty() {
-                            description.push(' ');
-                        }
-                        description.push_str(&doc_value);
-                    }
-                }
-            } else if attr.path().is_ident("strict") {
+            if attr.path().is_ident("strict") {
                 if let Ok(strict_value) = attr.parse_args::<syn::LitBool>() {
                     self.output_data.strict = Some(strict_value.value);
                 } else {
@@ -143,6 +310,17 @@ impl OutputParser {
                         "`#[strict]` on AgentOutput structs must be a boolean literal, e.g. `#[strict(true)]`",
                     ));
                 }
+            } else if attr
+                .path()
+                .is_ident(OutputAttrIdent::Output.to_string().as_str())
+            {
+                if self.conversion.is_some() {
+                    return Err(Error::new(
+                        attr.span(),
+                        "only one `#[output(...)]` attribute is allowed on the struct",
+                    ));
+                }
+                self.conversion = Some(OutputConversion::parse(attr)?);
             }
         }
         Ok(())
@@ -170,12 +348,13 @@ impl OutputParser {
                     let field_name = field.ident.as_ref().ok_or_else(|| {
                         Error::new(field.span(), "named fields must have an identifier")
                     })?;
+                    self.fields.push(field_name.clone());
                     let field_name = field_name.to_string();
 
                     let has_field_output_attr = field.attrs.iter().any(|attr| {
                         attr.path()
                             .is_ident(OutputAttrIdent::Output.to_string().as_str())
-                    });
+                    }) || doc_description(&field.attrs).is_some();
 
                     if has_field_output_attr {
                         has_output_attribute = true;
@@ -191,7 +370,7 @@ impl OutputParser {
                 if !has_output_attribute {
                     return Err(Error::new(
                         proc_macro2::Span::call_site(),
-                        "AgentOutput structs must have at least one field with an #[output(description = \"...\")] attribute",
+                        "AgentOutput structs must have at least one field with an #[output(description = \"...\")] attribute or a doc comment",
                     ));
                 }
             }
@@ -224,16 +403,17 @@ impl OutputParser {
             }
         }
 
+        let docs = || doc_description(&field.attrs);
         if let Some(schema) = field_schema {
             Ok(OutputSchemaProperty {
                 _type: json_type.to_string(),
-                description: schema.description.map(|lit| lit.value()),
+                description: schema.description.map(|lit| lit.value()).or_else(docs),
                 _enum: schema.choice.map(choices_to_json_values).transpose()?,
             })
         } else {
             Ok(OutputSchemaProperty {
                 _type: json_type.to_string(),
-                description: None,
+                description: docs(),
                 _enum: None,
             })
         }
@@ -293,6 +473,12 @@ impl OutputParser {
         field_type: &JsonType,
     ) -> Result<FieldSchemaAttr> {
         let attributes = attribute.parse_args::<FieldSchemaAttr>()?;
+        if attributes.has_constraints() {
+            return Err(Error::new(
+                attribute.span(),
+                "`min`, `max`, `pattern` and `max_length` are only supported on ToolInput fields",
+            ));
+        }
 
         if let Some(ref enum_vals) = attributes.choice {
             let invalid_choice = enum_vals.iter().find(|c| {
@@ -454,6 +640,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn doc_comments_describe_fields() {
+        let input: DeriveInput = syn::parse_str(
+            r#"
+            struct MyOutput {
+                /// The computed value
+                value: i64,
+                #[output(description = "Explicit")]
+                /// Ignored in favour of the attribute
+                note: String,
+            }
+            "#,
+        )
+        .unwrap();
+
+        let parser = build_parser(input);
+        let properties = &parser.output_data.schema.properties;
+        assert_eq!(
+            properties["value"].description.as_deref(),
+            Some("The computed value")
+        );
+        assert_eq!(properties["note"].description.as_deref(), Some("Explicit"));
+    }
+
     #[test]
     fn missing_output_attribute_errors() {
         let input: DeriveInput = syn::parse_str(
@@ -486,4 +696,83 @@ mod tests {
                 .contains("Tuple or Unit structs not yet supported")
         );
     }
+
+    #[test]
+    fn struct_output_attribute_configures_conversion() {
+        let input: DeriveInput = syn::parse_str(
+            r#"
+            #[output(from = [react, basic], fallback = explanation, error = parse_error)]
+            struct MyOutput {
+                #[output(description = "Value")]
+                value: i64,
+                #[output(description = "Explanation")]
+                explanation: String,
+                parse_error: Option<String>,
+            }
+            "#,
+        )
+        .unwrap();
+
+        let parser = build_parser(input);
+        let conversion = parser.conversion.as_ref().unwrap();
+        assert_eq!(
+            conversion.from,
+            vec![ExecutorOutput::ReAct, ExecutorOutput::Basic]
+        );
+        conversion.validate_fields(&parser.fields).unwrap();
+
+        let tokens = conversion
+            .to_tokens(
+                parser
+                    .ident
+                    .as_ref()
+                    .unwrap_or(&syn::parse_quote!(MyOutput)),
+                &parser.fields,
+                &syn::parse_quote!(autoagents_core),
+            )
+            .to_string();
+        assert!(tokens.contains("ReActAgentOutput"));
+        assert!(tokens.contains("BasicAgentOutput"));
+        assert!(
+            tokens.contains("explanation : :: std :: convert :: Into :: into (output . response)")
+        );
+        assert!(tokens.contains("value : :: std :: default :: Default :: default ()"));
+    }
+
+    #[test]
+    fn invalid_conversion_attributes_error() {
+        let cases = [
+            ("#[output(fallback = explanation)]", "needs `from = react`"),
+            (
+                "#[output(from = streaming)]",
+                "unknown executor `streaming`",
+            ),
+            ("#[output(from = [react, react])]", "duplicate executor"),
+            (
+                "#[output(from = react, fallback = explanation, fallback_with = make)]",
+                "cannot be combined",
+            ),
+            (
+                "#[output(from = react, retry = 2)]",
+                "unsupported `#[output]` option",
+            ),
+        ];
+        for (attr, expected) in cases {
+            let attr: Attribute = syn::parse_str::<DeriveInput>(&format!("{attr} struct S {{}}"))
+                .unwrap()
+                .attrs
+                .remove(0);
+            let err = OutputConversion::parse(&attr).unwrap_err();
+            assert!(err.to_string().contains(expected), "{attr:?}: {err}");
+        }
+
+        let conversion = OutputConversion::parse(&syn::parse_quote!(
+            #[output(from = react, fallback = missing)]
+        ))
+        .unwrap();
+        let err = conversion
+            .validate_fields(&[syn::parse_quote!(value)])
+            .unwrap_err();
+        assert!(err.to_string().contains("no field named `missing`"));
+    }
 }
@@ -0,0 +1,74 @@
+use proc_macro2::Span;
+use syn::{Attribute, Expr, ExprLit, Lit, LitStr, Meta};
+
+/// Join the `///` doc comment lines of an item into one description
+///
+/// Lines are trimmed and joined with spaces; blank lines are dropped. Returns
+/// `None` when there is no non-empty doc comment.
+pub(crate) fn doc_description(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit), ..
+                }) => Some(lit.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+    (!lines.is_empty()).then(|| lines.join(" "))
+}
+
+/// The explicit `description = "..."`, or else the doc comment of the item
+pub(crate) fn description_or_docs(
+    description: Option<LitStr>,
+    attrs: &[Attribute],
+) -> syn::Result<LitStr> {
+    if let Some(description) = description {
+        return Ok(description);
+    }
+    doc_description(attrs)
+        .map(|docs| LitStr::new(&docs, Span::call_site()))
+        .ok_or_else(|| {
+            syn::Error::new(
+                Span::call_site(),
+                "Missing attribute: description (set `description = \"...\"` or add a doc comment)",
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::DeriveInput;
+
+    #[test]
+    fn doc_lines_are_trimmed_and_joined() {
+        let input: DeriveInput = syn::parse_str(
+            r#"
+            /// Look up a word
+            ///
+            ///   in the dictionary.
+            #[derive(Debug)]
+            struct Lookup;
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            doc_description(&input.attrs).as_deref(),
+            Some("Look up a word in the dictionary.")
+        );
+    }
+
+    #[test]
+    fn missing_or_blank_docs_are_none() {
+        let input: DeriveInput = syn::parse_str("#[doc = \"  \"] struct Lookup;").unwrap();
+        assert!(doc_description(&input.attrs).is_none());
+        let input: DeriveInput = syn::parse_str("struct Lookup;").unwrap();
+        assert!(doc_description(&input.attrs).is_none());
+    }
+}
@@ -1,11 +1,10 @@
 extern crate proc_macro;
-use agent::{AgentParser, output::OutputParser};
+use agent::{AgentParser, hooks::HooksParser, output::OutputParser};
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{DeriveInput, parse_macro_input};
-use tool::{ToolParser, input::InputParser};
+use tool::{ToolParser, input::InputParser, toolkit::ToolkitParser};
 
 mod agent;
+mod doc;
 mod resolve;
 mod schema_emit;
 mod tool;
@@ -25,30 +24,17 @@ pub fn tool(attr: TokenStream, item: TokenStream) -> TokenStream {
     ToolParser::default().parse(attr, item)
 }
 
+#[proc_macro_attribute]
+pub fn toolkit(attr: TokenStream, item: TokenStream) -> TokenStream {
+    ToolkitParser::default().parse(attr, item)
+}
+
 #[proc_macro_attribute]
 pub fn agent(attr: TokenStream, item: TokenStream) -> TokenStream {
     AgentParser::default().parse(attr, item)
 }
 
-#[proc_macro_derive(AgentHooks)]
+#[proc_macro_derive(AgentHooks, attributes(hooks))]
 pub fn derive_agent_hooks(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let name = input.ident;
-
-    let (core, async_trait) = match resolve::resolve_agent_hooks_paths() {
-        Ok(paths) => paths,
-        Err(err) => return err.to_compile_error().into(),
-    };
-    let core = &core;
-    let async_trait = &async_trait;
-
-    // Correctly handle generics
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
-
-    let expanded = quote! {
-        #[#async_trait]
-        impl #impl_generics #core::agent::AgentHooks for #name #ty_generics #where_clause {}
-    };
-
-    TokenStream::from(expanded)
+    HooksParser::default().parse(input)
 }
@@ -7,8 +7,8 @@ enum DependencyLayout {
     Facade { core: Path, async_trait: Path },
 }
 
-/// Resolves core and `async_trait` paths for `#[derive(AgentHooks)]` with a single layout lookup.
-pub(crate) fn resolve_agent_hooks_paths() -> Result<(Path, Path)> {
+/// Resolves core and `async_trait` paths for `#[derive(AgentHooks)]` and `#[toolkit]` with a single layout lookup.
+pub(crate) fn resolve_core_and_async_trait_paths() -> Result<(Path, Path)> {
     match resolve_layout()? {
         DependencyLayout::DirectCore { core } => {
             let async_trait = resolve_direct_async_trait_path()?;
@@ -49,7 +49,7 @@ fn resolve_direct_async_trait_path() -> Result<Path> {
     let name = proc_macro_crate::crate_name("async-trait").map_err(|_| {
         Error::new(
             Span::call_site(),
-            "when using `autoagents-core` directly, `async-trait` must also be a direct dependency for `#[derive(AgentHooks)]`",
+            "when using `autoagents-core` directly, `async-trait` must also be a direct dependency for `#[derive(AgentHooks)]` and `#[toolkit]`",
         )
     })?;
     let crate_path = crate_name_to_path(name, "async-trait");
@@ -6,7 +6,8 @@ use syn::{
 
 pub(crate) struct ToolAttributes {
     pub(crate) name: LitStr,
-    pub(crate) description: LitStr,
+    /// Falls back to the doc comment of the tool struct when omitted
+    pub(crate) description: Option<LitStr>,
     pub(crate) input: Type,
     pub(crate) output: Option<Type>,
 }
@@ -80,12 +81,7 @@ impl Parse for ToolAttributes {
                     format!("Missing attribute: {}", ToolAttributeKeys::Name),
                 )
             })?,
-            description: description.ok_or_else(|| {
-                syn::Error::new(
-                    input.span(),
-                    format!("Missing attribute: {}", ToolAttributeKeys::Description),
-                )
-            })?,
+            description,
             input: args.ok_or_else(|| {
                 syn::Error::new(
                     input.span(),
@@ -109,7 +105,7 @@ mod tests {
                 .expect("expected attributes to parse");
 
         assert_eq!(attrs.name.value(), "lookup");
-        assert_eq!(attrs.description.value(), "Look up values");
+        assert_eq!(attrs.description.unwrap().value(), "Look up values");
 
         match attrs.input {
             Type::Path(path) => {
@@ -139,11 +135,18 @@ mod tests {
 
     #[test]
     fn test_tool_attributes_missing_field() {
-        let err = match syn::parse_str::<ToolAttributes>(r#"name = "x", input = String"#) {
-            Ok(_) => panic!("expected missing description error"),
+        let err = match syn::parse_str::<ToolAttributes>(r#"name = "x", description = "y""#) {
+            Ok(_) => panic!("expected missing input error"),
             Err(err) => err,
         };
-        assert!(err.to_string().contains("Missing attribute: description"));
+        assert!(err.to_string().contains("Missing attribute: input"));
+    }
+
+    #[test]
+    fn test_tool_attributes_description_is_optional() {
+        let attrs: ToolAttributes =
+            syn::parse_str(r#"name = "x", input = String"#).expect("expected attributes to parse");
+        assert!(attrs.description.is_none());
     }
 
     #[test]
@@ -1,3 +1,4 @@
+use proc_macro2::Span;
 use strum::{Display, EnumString};
 use syn::{
     Ident, Lit, LitInt, LitStr, Result, Token,
@@ -44,9 +45,47 @@ impl Parse for Choice {
     }
 }
 
+/// A numeric `min`/`max` bound; integer or float literal, optionally negative.
+pub(crate) struct Bound {
+    pub(crate) value: f64,
+    pub(crate) span: Span,
+}
+
+impl Parse for Bound {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let negative = input.parse::<Option<Token![-]>>()?.is_some();
+        let lit: Lit = input.parse()?;
+        let (value, span) = match &lit {
+            Lit::Int(int) => (int.base10_parse::<f64>()?, int.span()),
+            Lit::Float(float) => (float.base10_parse::<f64>()?, float.span()),
+            _ => return Err(syn::Error::new(lit.span(), "expected a numeric literal")),
+        };
+        Ok(Bound {
+            value: if negative { -value } else { value },
+            span,
+        })
+    }
+}
+
 pub(crate) struct FieldSchemaAttr {
     pub(crate) description: Option<LitStr>,
     pub(crate) choice: Option<Vec<Choice>>,
+    pub(crate) min: Option<Bound>,
+    pub(crate) max: Option<Bound>,
+    pub(crate) pattern: Option<LitStr>,
+    pub(crate) max_length: Option<LitInt>,
+    /// `nested`: the field's type derives `ToolInput`, so its schema is merged in
+    pub(crate) nested: bool,
+}
+
+impl FieldSchemaAttr {
+    /// Whether any validation constraint (`min`, `max`, `pattern`, `max_length`) is set
+    pub(crate) fn has_constraints(&self) -> bool {
+        self.min.is_some()
+            || self.max.is_some()
+            || self.pattern.is_some()
+            || self.max_length.is_some()
+    }
 }
 
 #[derive(EnumString, Display)]
@@ -55,6 +94,16 @@ pub(crate) enum FieldAttributeKeys {
     Description,
     #[strum(serialize = "choice")]
     Choice,
+    #[strum(serialize = "min")]
+    Min,
+    #[strum(serialize = "max")]
+    Max,
+    #[strum(serialize = "pattern")]
+    Pattern,
+    #[strum(serialize = "max_length")]
+    MaxLength,
+    #[strum(serialize = "nested")]
+    Nested,
     Unknown(String),
 }
 
@@ -63,6 +112,11 @@ impl From<Ident> for FieldAttributeKeys {
         match value.to_string().as_str() {
             "description" => Self::Description,
             "choice" => Self::Choice,
+            "min" => Self::Min,
+            "max" => Self::Max,
+            "pattern" => Self::Pattern,
+            "max_length" => Self::MaxLength,
+            "nested" => Self::Nested,
             other => Self::Unknown(other.to_string()),
         }
     }
@@ -82,12 +136,19 @@ impl Parse for FieldSchemaAttr {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut description = None;
         let mut choice: Vec<Choice> = vec![];
+        let mut min = None;
+        let mut max = None;
+        let mut pattern = None;
+        let mut max_length = None;
+        let mut nested = false;
         while !input.is_empty() {
             let key: Ident = input.parse()?;
             let key_span = key.span();
             let field_attr_key: FieldAttributeKeys = key.into();
-            //Move forward one token
-            input.parse::<Token![=]>()?;
+            // `nested` is a bare flag; every other key takes `= value`
+            if !matches!(field_attr_key, FieldAttributeKeys::Nested) {
+                input.parse::<Token![=]>()?;
+            }
             match field_attr_key {
                 FieldAttributeKeys::Description => {
                     description = Some(input.parse()?);
@@ -95,6 +156,21 @@ impl Parse for FieldSchemaAttr {
                 FieldAttributeKeys::Choice => {
                     choice = Self::parse_choice(input)?;
                 }
+                FieldAttributeKeys::Min => {
+                    min = Some(input.parse()?);
+                }
+                FieldAttributeKeys::Max => {
+                    max = Some(input.parse()?);
+                }
+                FieldAttributeKeys::Pattern => {
+                    pattern = Some(input.parse()?);
+                }
+                FieldAttributeKeys::MaxLength => {
+                    max_length = Some(input.parse()?);
+                }
+                FieldAttributeKeys::Nested => {
+                    nested = true;
+                }
                 FieldAttributeKeys::Unknown(other) => {
                     return Err(syn::Error::new(
                         key_span,
@@ -115,6 +191,11 @@ impl Parse for FieldSchemaAttr {
             } else {
                 None
             },
+            min,
+            max,
+            pattern,
+            max_length,
+            nested,
         })
     }
 }
@@ -1,15 +1,20 @@
 use super::field::{Choice, FieldSchemaAttr};
+use super::serde_attr::{SerdeAttrs, rename_variant};
+use crate::doc::doc_description;
 use crate::resolve;
 use crate::schema_emit;
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use schemars::schema::{
-    InstanceType, Metadata, ObjectValidation, RootSchema, Schema, SchemaObject, SingleOrVec,
+    ArrayValidation, InstanceType, Metadata, ObjectValidation, RootSchema, Schema, SchemaObject,
+    SingleOrVec, SubschemaValidation,
 };
 use strum::{Display, EnumString};
 use syn::{
-    Attribute, Data, DataStruct, DeriveInput, Error, Field, GenericArgument, Ident, LitStr,
-    PathArguments, Result, Type, parse_macro_input, spanned::Spanned,
+    Attribute, Data, DataEnum, DataStruct, DeriveInput, Error, Field, Fields, FieldsNamed,
+    GenericArgument, Ident, LitStr, PathArguments, Result, Type, parse_macro_input,
+    spanned::Spanned,
 };
 
 #[derive(EnumString, Display)]
@@ -18,140 +23,340 @@ enum InputAttrIdent {
     Input,
 }
 
+/// How the Rust type of a field maps onto JSON Schema.
+#[derive(Debug)]
+enum FieldKind {
+    Primitive(InstanceType),
+    Array(Box<FieldKind>),
+    Map,
+    /// Any JSON value; emitted as an empty schema.
+    Any,
+    /// Another `ToolInput` type whose schema is merged in at runtime.
+    Nested(Box<Type>),
+}
+
+impl FieldKind {
+    /// Whether a nested `ToolInput` type sits at or below this kind
+    fn is_nested(&self) -> bool {
+        match self {
+            Self::Nested(_) => true,
+            Self::Array(items) => items.is_nested(),
+            _ => false,
+        }
+    }
+}
+
+/// A nested `ToolInput` schema to merge into the generated schema at `pointer`.
+#[derive(Debug)]
+struct NestedSchema {
+    pointer: String,
+    ty: Box<Type>,
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct InputParser {
     root_schema: RootSchema,
     ident: Option<Ident>,
+    nested: Vec<NestedSchema>,
+    /// Whether any field declares `min`, `max`, `pattern` or `max_length`
+    constrained: bool,
 }
 
 impl InputParser {
     pub fn parse(&mut self, input: TokenStream) -> TokenStream {
         let input = parse_macro_input!(input as DeriveInput);
+        match self.expand(&input) {
+            Ok(expanded) => TokenStream::from(expanded),
+            Err(err) => err.to_compile_error().into(),
+        }
+    }
+
+    /// Generate the `ToolInputT` and `ToolInputSchema` impls for `input`
+    pub(crate) fn expand(&mut self, input: &DeriveInput) -> Result<TokenStream2> {
         let struct_ident = input.ident.clone();
         let struct_span = struct_ident.span();
-        self.ident = Some(input.ident);
 
-        if let Err(err) = self.parse_data(input.data) {
-            return err.to_compile_error().into();
-        }
+        self.parse_data(input)?;
+        self.ident = Some(input.ident.clone());
 
-        let core = match resolve::resolve_core_path() {
-            Ok(core) => core,
-            Err(err) => return err.to_compile_error().into(),
-        };
+        let core = resolve::resolve_core_path()?;
         let core = &core;
 
-        let serialized_data = match serde_json::to_string(&self.root_schema.schema) {
-            Ok(data) => data,
-            Err(err) => {
-                return Error::new(
-                    struct_span,
-                    format!("failed to serialize tool input schema: {err}"),
-                )
-                .to_compile_error()
-                .into();
+        let serialized_data = serde_json::to_string(&self.root_schema.schema).map_err(|err| {
+            Error::new(
+                struct_span,
+                format!("failed to serialize tool input schema: {err}"),
+            )
+        })?;
+
+        let schema_tokens = schema_emit::schema_str_to_tokens(&serialized_data, struct_span)?;
+
+        // Nested types may declare constraints of their own, which end up in the merged schema.
+        let validate_args = (self.constrained || !self.nested.is_empty()).then(|| {
+            quote! {
+                fn validate_args(
+                    args: &::serde_json::Value,
+                ) -> ::std::result::Result<(), #core::tool::ToolCallError> {
+                    #core::tool::validate_args(
+                        <#struct_ident as #core::tool::ToolInputSchema>::io_schema_value(),
+                        args,
+                    )
+                }
             }
-        };
+        });
+
+        let expanded = if self.nested.is_empty() {
+            let schema_literal = LitStr::new(&serialized_data, struct_span);
+            quote! {
+                impl #core::tool::ToolInputT for #struct_ident {
+                    fn io_schema() -> &'static str {
+                        #schema_literal
+                    }
 
-        let schema_tokens = match schema_emit::schema_str_to_tokens(&serialized_data, struct_span) {
-            Ok(tokens) => tokens,
-            Err(err) => return err.to_compile_error().into(),
-        };
+                    #validate_args
+                }
 
-        let schema_literal = LitStr::new(&serialized_data, struct_span);
-        let expanded = quote! {
-            impl #core::tool::ToolInputT for #struct_ident {
-                fn io_schema() -> &'static str {
-                    #schema_literal
+                impl #core::tool::ToolInputSchema for #struct_ident {
+                    fn io_schema_value() -> &'static ::serde_json::Value {
+                        static SCHEMA: ::std::sync::LazyLock<::serde_json::Value> =
+                            ::std::sync::LazyLock::new(|| #schema_tokens);
+                        &SCHEMA
+                    }
                 }
             }
+        } else {
+            // Nested types only know their schema at runtime, so it is merged
+            // into the placeholders when the schema is first requested.
+            let merges = self.nested.iter().map(|NestedSchema { pointer, ty }| {
+                quote! {
+                    if let ::std::option::Option::Some(::serde_json::Value::Object(slot)) =
+                        schema.pointer_mut(#pointer)
+                    {
+                        if let ::serde_json::Value::Object(nested) =
+                            <#ty as #core::tool::ToolInputSchema>::io_schema_value()
+                        {
+                            for (key, value) in nested {
+                                slot.entry(key.clone()).or_insert_with(|| value.clone());
+                            }
+                        }
+                    }
+                }
+            });
+            quote! {
+                impl #core::tool::ToolInputT for #struct_ident {
+                    fn io_schema() -> &'static str {
+                        static SCHEMA: ::std::sync::LazyLock<::std::string::String> =
+                            ::std::sync::LazyLock::new(|| {
+                                <#struct_ident as #core::tool::ToolInputSchema>::io_schema_value()
+                                    .to_string()
+                            });
+                        SCHEMA.as_str()
+                    }
 
-            impl #core::tool::ToolInputSchema for #struct_ident {
-                fn io_schema_value() -> &'static ::serde_json::Value {
-                    static SCHEMA: ::std::sync::LazyLock<::serde_json::Value> =
-                        ::std::sync::LazyLock::new(|| #schema_tokens);
-                    &SCHEMA
+                    #validate_args
+                }
+
+                impl #core::tool::ToolInputSchema for #struct_ident {
+                    fn io_schema_value() -> &'static ::serde_json::Value {
+                        static SCHEMA: ::std::sync::LazyLock<::serde_json::Value> =
+                            ::std::sync::LazyLock::new(|| {
+                                let mut schema = #schema_tokens;
+                                #(#merges)*
+                                schema
+                            });
+                        &SCHEMA
+                    }
                 }
             }
         };
-        TokenStream::from(expanded)
+        Ok(expanded)
     }
 
-    fn parse_data(&mut self, input: Data) -> Result<()> {
-        match &input {
-            Data::Struct(struct_data) => self.parse_struct(struct_data)?,
-            _ => {
-                return Err(Error::new(
-                    proc_macro2::Span::call_site(),
-                    "Union or Enums not yet supported!",
-                ));
-            }
-        };
-        Ok(())
+    fn parse_data(&mut self, input: &DeriveInput) -> Result<()> {
+        match &input.data {
+            Data::Struct(struct_data) => self.parse_struct(struct_data),
+            Data::Enum(enum_data) => self.parse_enum(enum_data, &input.attrs),
+            Data::Union(_) => Err(Error::new(
+                input.ident.span(),
+                "ToolInput cannot be derived for unions",
+            )),
+        }
     }
 
     fn parse_struct(&mut self, input: &DataStruct) -> Result<()> {
-        self.root_schema.schema.instance_type =
-            Some(SingleOrVec::Single(Box::new(InstanceType::Object)));
-        self.root_schema.schema.object = Some(Box::new(ObjectValidation::default()));
-
         match &input.fields {
-            syn::Fields::Named(fields) => {
-                for field in fields.named.iter() {
-                    let field_name = field.ident.as_ref().ok_or_else(|| {
-                        Error::new(field.span(), "named fields must have an identifier")
-                    })?;
-                    let field_name = field_name.to_string();
-                    let (schema, optional) = self.parse_field(field_name.clone(), field)?;
-
-                    let object = self.root_schema.schema.object.as_mut().ok_or_else(|| {
-                        Error::new(
-                            proc_macro2::Span::call_site(),
-                            "internal error: tool input schema object missing",
-                        )
-                    })?;
-                    object.properties.insert(field_name.clone(), schema);
-                    if !optional {
-                        object.required.insert(field_name);
-                    }
-                }
-            }
-            _ => {
-                return Err(Error::new(
-                    proc_macro2::Span::call_site(),
-                    "Union or Enums not yet supported!",
-                ));
+            Fields::Named(fields) => {
+                self.root_schema.schema = self.object_schema(fields, "")?;
+                Ok(())
             }
+            _ => Err(Error::new(
+                input.fields.span(),
+                "ToolInput structs must have named fields",
+            )),
         }
-        Ok(())
     }
 
-    fn parse_field(&mut self, _name: String, field: &Field) -> Result<(Schema, bool)> {
-        // Determine JSON schema type from the Rust type.
-        let (instance_type, optional) = self.get_json_type(&field.ty)?;
+    /// Unit-only enums become a string `enum`; enums with data become a
+    /// `oneOf` following serde's externally tagged representation.
+    fn parse_enum(&mut self, input: &DataEnum, attrs: &[Attribute]) -> Result<()> {
+        let container = SerdeAttrs::from_attrs(attrs)?;
+        if let Some(representation) = container.representation {
+            return Err(Error::new(
+                proc_macro2::Span::call_site(),
+                format!(
+                    "ToolInput enums must use serde's externally tagged representation; `{representation}` is not supported"
+                ),
+            ));
+        }
+        if input.variants.is_empty() {
+            return Err(Error::new(
+                proc_macro2::Span::call_site(),
+                "ToolInput enums must have at least one variant",
+            ));
+        }
+
+        let mut names = Vec::with_capacity(input.variants.len());
+        for variant in &input.variants {
+            let serde = SerdeAttrs::from_attrs(&variant.attrs)?;
+            let name = match (serde.rename, &container.rename_all) {
+                (Some(rename), _) => rename,
+                (None, Some(rule)) => rename_variant(&variant.ident.to_string(), rule)?,
+                (None, None) => variant.ident.to_string(),
+            };
+            names.push(name);
+        }
 
-        let mut schema_obj = SchemaObject {
-            instance_type: Some(SingleOrVec::Single(Box::new(instance_type))),
+        if input
+            .variants
+            .iter()
+            .all(|variant| matches!(variant.fields, Fields::Unit))
+        {
+            self.root_schema.schema = string_enum(names);
+            return Ok(());
+        }
+
+        let mut one_of = Vec::with_capacity(input.variants.len());
+        for (index, (variant, name)) in input.variants.iter().zip(names).enumerate() {
+            let pointer = pointer_push(&pointer_push("/oneOf", &index.to_string()), "properties");
+            let pointer = pointer_push(&pointer, &name);
+            let payload = match &variant.fields {
+                Fields::Unit => {
+                    one_of.push(Schema::Object(string_enum(vec![name])));
+                    continue;
+                }
+                Fields::Named(fields) => Schema::Object(self.object_schema(fields, &pointer)?),
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    self.parse_field(&fields.unnamed[0], &pointer)?.0
+                }
+                Fields::Unnamed(fields) => {
+                    return Err(Error::new(
+                        fields.span(),
+                        "ToolInput enum variants can hold at most one unnamed field",
+                    ));
+                }
+            };
+
+            let mut object = ObjectValidation {
+                additional_properties: Some(Box::new(Schema::Bool(false))),
+                ..Default::default()
+            };
+            object.properties.insert(name.clone(), payload);
+            object.required.insert(name);
+            one_of.push(Schema::Object(SchemaObject {
+                instance_type: Some(SingleOrVec::Single(Box::new(InstanceType::Object))),
+                object: Some(Box::new(object)),
+                metadata: doc_description(&variant.attrs).map(|description| {
+                    Box::new(Metadata {
+                        description: Some(description),
+                        ..Default::default()
+                    })
+                }),
+                ..Default::default()
+            }));
+        }
+
+        self.root_schema.schema = SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                one_of: Some(one_of),
+                ..Default::default()
+            })),
             ..Default::default()
         };
+        Ok(())
+    }
 
-        let mut tool_property: Option<FieldSchemaAttr> = None;
+    fn object_schema(&mut self, fields: &FieldsNamed, pointer: &str) -> Result<SchemaObject> {
+        let mut object = ObjectValidation::default();
+        for field in fields.named.iter() {
+            let field_name = field
+                .ident
+                .as_ref()
+                .ok_or_else(|| Error::new(field.span(), "named fields must have an identifier"))?;
+            let field_name = field_name.to_string();
+            let field_pointer = pointer_push(&pointer_push(pointer, "properties"), &field_name);
+            let (schema, optional) = self.parse_field(field, &field_pointer)?;
+
+            object.properties.insert(field_name.clone(), schema);
+            if !optional {
+                object.required.insert(field_name);
+            }
+        }
+
+        Ok(SchemaObject {
+            instance_type: Some(SingleOrVec::Single(Box::new(InstanceType::Object))),
+            object: Some(Box::new(object)),
+            ..Default::default()
+        })
+    }
 
+    fn parse_field(&mut self, field: &Field, pointer: &str) -> Result<(Schema, bool)> {
+        let mut tool_property: Option<FieldSchemaAttr> = None;
         for attr in &field.attrs {
             if attr
                 .path()
                 .is_ident(InputAttrIdent::Input.to_string().as_str())
             {
-                tool_property = Some(self.parse_macro_attributes(attr, &instance_type)?);
+                tool_property = Some(attr.parse_args::<FieldSchemaAttr>()?);
             }
         }
+        let nested = tool_property
+            .as_ref()
+            .is_some_and(|property| property.nested);
+
+        // Determine JSON schema type from the Rust type.
+        let (kind, optional) = self.get_field_kind(&field.ty, nested)?;
+        if nested && !kind.is_nested() {
+            return Err(Error::new(
+                field.ty.span(),
+                "`nested` can only be used on fields whose type derives `ToolInput`",
+            ));
+        }
+        let instance_type = match &kind {
+            FieldKind::Primitive(instance_type) => Some(*instance_type),
+            FieldKind::Array(_) => Some(InstanceType::Array),
+            FieldKind::Map => Some(InstanceType::Object),
+            FieldKind::Any | FieldKind::Nested(_) => None,
+        };
+        if let Some(property) = &tool_property {
+            self.check_macro_attributes(property, instance_type.as_ref())?;
+        }
+        let mut schema_obj = self.kind_schema(kind, pointer);
+
+        // An explicit `description` wins over the field's doc comment.
+        let description = tool_property
+            .as_mut()
+            .and_then(|property| property.description.take())
+            .map(|description| description.value())
+            .or_else(|| doc_description(&field.attrs));
+        if let Some(description) = description {
+            schema_obj.metadata = Some(Box::new(Metadata {
+                description: Some(description),
+                ..Default::default()
+            }));
+        }
 
         if let Some(property) = tool_property {
-            let mut metadata = Metadata::default();
-            if let Some(desc) = property.description {
-                metadata.description = Some(desc.value());
-            }
-            schema_obj.metadata = Some(Box::new(metadata));
+            self.constrained |= property.has_constraints();
 
             if let Some(choices) = property.choice {
                 let enum_values = choices
@@ -160,12 +365,87 @@ impl InputParser {
                     .collect::<Result<Vec<_>>>()?;
                 schema_obj.enum_values = Some(enum_values);
             }
+
+            if let Some(min) = property.min {
+                schema_obj
+                    .extensions
+                    .insert("minimum".to_string(), bound_value(min.value));
+            }
+            if let Some(max) = property.max {
+                schema_obj
+                    .extensions
+                    .insert("maximum".to_string(), bound_value(max.value));
+            }
+            if let Some(pattern) = property.pattern {
+                schema_obj
+                    .string
+                    .get_or_insert_with(Default::default)
+                    .pattern = Some(pattern.value());
+            }
+            if let Some(max_length) = property.max_length {
+                let max_length = max_length.base10_parse::<u32>()?;
+                if instance_type == Some(InstanceType::Array) {
+                    schema_obj
+                        .array
+                        .get_or_insert_with(Default::default)
+                        .max_items = Some(max_length);
+                } else {
+                    schema_obj
+                        .string
+                        .get_or_insert_with(Default::default)
+                        .max_length = Some(max_length);
+                }
+            }
         }
 
         Ok((Schema::Object(schema_obj), optional))
     }
 
-    fn get_json_type(&mut self, field_type: &Type) -> Result<(InstanceType, bool)> {
+    /// Build the schema for `kind`, registering nested types found at or below `pointer`.
+    fn kind_schema(&mut self, kind: FieldKind, pointer: &str) -> SchemaObject {
+        match kind {
+            FieldKind::Primitive(instance_type) => SchemaObject {
+                instance_type: Some(SingleOrVec::Single(Box::new(instance_type))),
+                ..Default::default()
+            },
+            FieldKind::Array(items) => {
+                let items = match *items {
+                    FieldKind::Any => None,
+                    items => {
+                        let items = self.kind_schema(items, &pointer_push(pointer, "items"));
+                        Some(SingleOrVec::Single(Box::new(Schema::Object(items))))
+                    }
+                };
+                SchemaObject {
+                    instance_type: Some(SingleOrVec::Single(Box::new(InstanceType::Array))),
+                    array: items.map(|items| {
+                        Box::new(ArrayValidation {
+                            items: Some(items),
+                            ..Default::default()
+                        })
+                    }),
+                    ..Default::default()
+                }
+            }
+            FieldKind::Map => SchemaObject {
+                instance_type: Some(SingleOrVec::Single(Box::new(InstanceType::Object))),
+                ..Default::default()
+            },
+            FieldKind::Any => SchemaObject::default(),
+            FieldKind::Nested(ty) => {
+                self.nested.push(NestedSchema {
+                    pointer: pointer.to_string(),
+                    ty,
+                });
+                SchemaObject::default()
+            }
+        }
+    }
+
+    /// Map `field_type` onto JSON Schema. Unrecognised types are strings
+    /// unless the field is marked `#[input(nested)]`, in which case the
+    /// innermost type is merged in as a nested `ToolInput` schema.
+    fn get_field_kind(&mut self, field_type: &Type, nested: bool) -> Result<(FieldKind, bool)> {
         match field_type {
             Type::Path(path) => {
                 let Some(segment) = path.path.segments.last() else {
@@ -174,60 +454,87 @@ impl InputParser {
                         "Invalid type path",
                     ));
                 };
+                let ident = segment.ident.to_string();
+                let inner = match &segment.arguments {
+                    PathArguments::AngleBracketed(args) => {
+                        args.args.iter().find_map(|arg| match arg {
+                            GenericArgument::Type(ty) => Some(ty),
+                            _ => None,
+                        })
+                    }
+                    _ => None,
+                };
 
-                if segment.ident == "Option"
-                    && let PathArguments::AngleBracketed(args) = &segment.arguments
-                    && let Some(GenericArgument::Type(inner)) = args.args.first()
-                {
-                    let (instance_type, _) = self.get_json_type(inner)?;
-                    return Ok((instance_type, true));
-                }
-
-                if segment.ident == "Option" {
-                    return Err(Error::new(
+                match (ident.as_str(), inner) {
+                    ("Option", Some(inner)) => {
+                        let (kind, _) = self.get_field_kind(inner, nested)?;
+                        Ok((kind, true))
+                    }
+                    ("Option", None) => Err(Error::new(
                         proc_macro2::Span::call_site(),
                         "Unsupported Option type",
-                    ));
+                    )),
+                    ("Box" | "Arc" | "Rc", Some(inner)) => self.get_field_kind(inner, nested),
+                    ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", items) => {
+                        let items = match items {
+                            Some(items) => self.get_field_kind(items, nested)?.0,
+                            None => FieldKind::Any,
+                        };
+                        Ok((FieldKind::Array(Box::new(items)), false))
+                    }
+                    ("HashMap" | "BTreeMap" | "Map", _) => Ok((FieldKind::Map, false)),
+                    ("Value", None) => Ok((FieldKind::Any, false)),
+                    (type_str, _) => Ok((
+                        match self.get_base_json_type(type_str) {
+                            Some(instance_type) => FieldKind::Primitive(instance_type),
+                            None if nested => FieldKind::Nested(Box::new(field_type.clone())),
+                            None => FieldKind::Primitive(InstanceType::String),
+                        },
+                        false,
+                    )),
                 }
-
-                let instance_type = self.get_base_json_type(&segment.ident.to_string());
-                Ok((instance_type, false))
             }
-            Type::Reference(reference) => self.get_json_type(&reference.elem),
-            Type::Group(group) => self.get_json_type(&group.elem),
-            Type::Paren(paren) => self.get_json_type(&paren.elem),
-            _ => Ok((InstanceType::String, false)),
+            Type::Reference(reference) => self.get_field_kind(&reference.elem, nested),
+            Type::Group(group) => self.get_field_kind(&group.elem, nested),
+            Type::Paren(paren) => self.get_field_kind(&paren.elem, nested),
+            Type::Slice(slice) => {
+                let (items, _) = self.get_field_kind(&slice.elem, nested)?;
+                Ok((FieldKind::Array(Box::new(items)), false))
+            }
+            Type::Array(array) => {
+                let (items, _) = self.get_field_kind(&array.elem, nested)?;
+                Ok((FieldKind::Array(Box::new(items)), false))
+            }
+            _ => Ok((FieldKind::Primitive(InstanceType::String), false)),
         }
     }
 
-    fn get_base_json_type(&self, type_str: &str) -> InstanceType {
+    /// Primitive JSON type for well-known Rust types; `None` for anything else.
+    fn get_base_json_type(&self, type_str: &str) -> Option<InstanceType> {
         match type_str {
-            "String" | "str" => InstanceType::String,
+            "String" | "str" | "char" | "PathBuf" | "Path" => Some(InstanceType::String),
             "i8" | "i32" | "u32" | "u8" | "i64" | "u64" | "i16" | "u16" | "usize" | "isize" => {
-                InstanceType::Integer
+                Some(InstanceType::Integer)
             }
-            "f64" | "f32" => InstanceType::Number,
-            "bool" => InstanceType::Boolean,
-            "Vec" => InstanceType::Array,
-            _ => InstanceType::String,
+            "f64" | "f32" => Some(InstanceType::Number),
+            "bool" => Some(InstanceType::Boolean),
+            _ => None,
         }
     }
 
-    fn parse_macro_attributes(
-        &mut self,
-        attribute: &Attribute,
-        instance_type: &InstanceType,
-    ) -> Result<FieldSchemaAttr> {
-        let attributes = attribute.parse_args::<FieldSchemaAttr>()?;
-
+    fn check_macro_attributes(
+        &self,
+        attributes: &FieldSchemaAttr,
+        instance_type: Option<&InstanceType>,
+    ) -> Result<()> {
         if let Some(ref enum_vals) = attributes.choice {
             let invalid_choice = enum_vals.iter().any(|c| {
                 !matches!(
                     (c, instance_type),
-                    (Choice::String(_), InstanceType::String)
+                    (Choice::String(_), Some(InstanceType::String))
                         | (
                             Choice::Number(_),
-                            InstanceType::Integer | InstanceType::Number
+                            Some(InstanceType::Integer | InstanceType::Number)
                         )
                 )
             });
@@ -240,10 +547,76 @@ impl InputParser {
             }
         }
 
-        Ok(attributes)
+        let numeric = matches!(
+            instance_type,
+            Some(InstanceType::Integer | InstanceType::Number)
+        );
+        for bound in attributes.min.iter().chain(&attributes.max) {
+            if !numeric {
+                return Err(Error::new(
+                    bound.span,
+                    "`min` and `max` can only be used on numeric fields",
+                ));
+            }
+        }
+        if let (Some(min), Some(max)) = (&attributes.min, &attributes.max)
+            && min.value > max.value
+        {
+            return Err(Error::new(max.span, "`max` must not be less than `min`"));
+        }
+        if let Some(pattern) = &attributes.pattern {
+            if instance_type != Some(&InstanceType::String) {
+                return Err(Error::new(
+                    pattern.span(),
+                    "`pattern` can only be used on string fields",
+                ));
+            }
+            if let Err(err) = regex::Regex::new(&pattern.value()) {
+                return Err(Error::new(
+                    pattern.span(),
+                    format!("invalid `pattern`: {err}"),
+                ));
+            }
+        }
+        if let Some(max_length) = &attributes.max_length {
+            if !matches!(
+                instance_type,
+                Some(InstanceType::String | InstanceType::Array)
+            ) {
+                return Err(Error::new(
+                    max_length.span(),
+                    "`max_length` can only be used on string and list fields",
+                ));
+            }
+            max_length.base10_parse::<u32>()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Emit whole-number bounds as JSON integers so `min = 1` appears as `1`, not `1.0`
+fn bound_value(value: f64) -> serde_json::Value {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        serde_json::Value::from(value as i64)
+    } else {
+        serde_json::Value::from(value)
     }
 }
 
+fn string_enum(names: Vec<String>) -> SchemaObject {
+    SchemaObject {
+        instance_type: Some(SingleOrVec::Single(Box::new(InstanceType::String))),
+        enum_values: Some(names.into_iter().map(serde_json::Value::String).collect()),
+        ..Default::default()
+    }
+}
+
+/// Append `key` to a JSON pointer, escaping it per RFC 6901
+fn pointer_push(pointer: &str, key: &str) -> String {
+    format!("{pointer}/{}", key.replace('~', "~0").replace('/', "~1"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -265,7 +638,7 @@ mod tests {
         .unwrap();
 
         let mut parser = InputParser::default();
-        parser.parse_data(input.data).unwrap();
+        parser.parse_data(&input).unwrap();
 
         let object = parser.root_schema.schema.object.as_ref().unwrap();
         assert!(object.properties.contains_key("id"));
@@ -322,4 +695,198 @@ mod tests {
         assert!(serialized_data.contains("\"required\":[\"name\"]"));
         assert!(!serialized_data.contains("\"required\":[\"age\"]"));
     }
+
+    fn serialized(source: &str) -> (serde_json::Value, Vec<String>) {
+        let input: DeriveInput = syn::parse_str(source).unwrap();
+        let mut parser = InputParser::default();
+        parser.parse_data(&input).unwrap();
+        let schema = serde_json::to_value(&parser.root_schema.schema).unwrap();
+        let pointers = parser.nested.into_iter().map(|n| n.pointer).collect();
+        (schema, pointers)
+    }
+
+    #[test]
+    fn nested_vec_and_option_fields() {
+        let (schema, pointers) = serialized(
+            r#"
+            struct SearchArgs {
+                #[input(description = "Tags")]
+                tags: Vec<String>,
+                #[input(description = "Filters", nested)]
+                filters: Option<Vec<Filter>>,
+                #[input(nested)]
+                range: Range,
+                id: uuid::Uuid,
+                extra: serde_json::Value,
+            }
+            "#,
+        );
+
+        assert_eq!(schema["properties"]["tags"]["type"], "array");
+        assert_eq!(schema["properties"]["tags"]["items"]["type"], "string");
+        assert_eq!(schema["properties"]["filters"]["type"], "array");
+        assert_eq!(schema["properties"]["filters"]["description"], "Filters");
+        assert_eq!(schema["properties"]["id"]["type"], "string");
+        assert_eq!(schema["properties"]["extra"], serde_json::json!({}));
+        assert_eq!(
+            schema["required"],
+            serde_json::json!(["extra", "id", "range", "tags"])
+        );
+        assert_eq!(pointers, ["/properties/filters/items", "/properties/range"]);
+    }
+
+    #[test]
+    fn unit_enum_uses_serde_names() {
+        let (schema, pointers) = serialized(
+            r#"
+            #[serde(rename_all = "snake_case")]
+            enum Mode {
+                FastMode,
+                #[serde(rename = "careful")]
+                SlowMode,
+            }
+            "#,
+        );
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["enum"], serde_json::json!(["fast_mode", "careful"]));
+        assert!(pointers.is_empty());
+    }
+
+    #[test]
+    fn data_enum_is_externally_tagged_one_of() {
+        let (schema, pointers) = serialized(
+            r#"
+            enum Shape {
+                Empty,
+                Circle { #[input(description = "Radius")] radius: f64 },
+                Custom(#[input(nested)] Polygon),
+            }
+            "#,
+        );
+        let one_of = schema["oneOf"].as_array().unwrap();
+        assert_eq!(one_of.len(), 3);
+        assert_eq!(one_of[0]["enum"], serde_json::json!(["Empty"]));
+        assert_eq!(one_of[1]["required"], serde_json::json!(["Circle"]));
+        assert_eq!(one_of[1]["additionalProperties"], false);
+        assert_eq!(
+            one_of[1]["properties"]["Circle"]["properties"]["radius"]["type"],
+            "number"
+        );
+        assert_eq!(pointers, ["/oneOf/2/properties/Custom"]);
+    }
+
+    #[test]
+    fn unsupported_enum_shapes_are_rejected() {
+        for source in [
+            r#"#[serde(tag = "kind")] enum Tagged { A { x: u32 } }"#,
+            r#"enum Pair { Both(u32, u32) }"#,
+            r#"struct Tuple(u32);"#,
+        ] {
+            let input: DeriveInput = syn::parse_str(source).unwrap();
+            assert!(
+                InputParser::default().parse_data(&input).is_err(),
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn constraint_attributes_emit_schema_keywords() {
+        let input: DeriveInput = syn::parse_str(
+            r#"
+            struct SearchArgs {
+                #[input(description = "Limit", min = 1, max = 50)]
+                limit: u32,
+                #[input(description = "Score", min = -0.5, max = 1.5)]
+                score: Option<f64>,
+                #[input(description = "Query", pattern = "^[a-z ]+$", max_length = 64)]
+                query: String,
+                #[input(description = "Tags", max_length = 3)]
+                tags: Vec<String>,
+            }
+            "#,
+        )
+        .unwrap();
+
+        let mut parser = InputParser::default();
+        parser.parse_data(&input).unwrap();
+        assert!(parser.constrained);
+
+        let schema = serde_json::to_value(&parser.root_schema.schema).unwrap();
+        let properties = &schema["properties"];
+        assert_eq!(properties["limit"]["minimum"], 1);
+        assert_eq!(properties["limit"]["maximum"], 50);
+        assert_eq!(properties["score"]["minimum"], -0.5);
+        assert_eq!(properties["query"]["pattern"], "^[a-z ]+$");
+        assert_eq!(properties["query"]["maxLength"], 64);
+        assert_eq!(properties["tags"]["maxItems"], 3);
+        assert_eq!(properties["tags"]["items"]["type"], "string");
+    }
+
+    #[test]
+    fn constraint_attributes_are_checked_against_field_types() {
+        let cases = [
+            (r#"#[input(min = 1)] name: String"#, "numeric fields"),
+            (r#"#[input(pattern = "a")] count: u32"#, "string fields"),
+            (
+                r#"#[input(max_length = 3)] flag: bool"#,
+                "string and list fields",
+            ),
+            (
+                r#"#[input(min = 5, max = 1)] count: u32"#,
+                "must not be less than",
+            ),
+            (
+                r#"#[input(pattern = "(")] name: String"#,
+                "invalid `pattern`",
+            ),
+            (r#"#[input(nested)] name: String"#, "derives `ToolInput`"),
+        ];
+        for (field, expected) in cases {
+            let input: DeriveInput = syn::parse_str(&format!("struct Args {{ {field} }}")).unwrap();
+            let err = InputParser::default().parse_data(&input).unwrap_err();
+            assert!(err.to_string().contains(expected), "{field}: {err}");
+        }
+    }
+
+    #[test]
+    fn doc_comments_describe_fields_and_variants() {
+        let input: DeriveInput = syn::parse_str(
+            r#"
+            struct ToolArgs {
+                /// Word to look up
+                word: String,
+                /// Ignored in favour of the attribute
+                #[input(description = "Number of senses")]
+                senses: u8,
+            }
+            "#,
+        )
+        .unwrap();
+        let mut parser = InputParser::default();
+        parser.parse_data(&input).unwrap();
+        let schema = serde_json::to_value(&parser.root_schema.schema).unwrap();
+        assert_eq!(
+            schema["properties"]["word"]["description"],
+            "Word to look up"
+        );
+        assert_eq!(
+            schema["properties"]["senses"]["description"],
+            "Number of senses"
+        );
+
+        let input: DeriveInput = syn::parse_str(
+            r#"
+            enum Filter {
+                /// Match a tag
+                Tag(String),
+            }
+            "#,
+        )
+        .unwrap();
+        let mut parser = InputParser::default();
+        parser.parse_data(&input).unwrap();
+        let schema = serde_json::to_value(&parser.root_schema.schema).unwrap();
+        assert_eq!(schema["oneOf"][0]["description"], "Match a tag");
+    }
 }
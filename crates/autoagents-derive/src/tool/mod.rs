@@ -2,7 +2,9 @@ mod attr;
 pub(crate) mod field;
 pub(crate) mod input;
 pub(crate) mod json;
-use crate::resolve;
+mod serde_attr;
+pub(crate) mod toolkit;
+use crate::{doc, resolve};
 use attr::ToolAttributes;
 use proc_macro::TokenStream;
 use quote::quote;
@@ -15,6 +17,11 @@ impl ToolParser {
     pub fn parse(&self, attr: TokenStream, item: TokenStream) -> TokenStream {
         let tool_attrs = parse_macro_input!(attr as ToolAttributes);
         let input_struct = parse_macro_input!(item as syn::ItemStruct);
+        let tool_description =
+            match doc::description_or_docs(tool_attrs.description, &input_struct.attrs) {
+                Ok(description) => description,
+                Err(err) => return err.to_compile_error().into(),
+            };
 
         let core = match resolve::resolve_core_path() {
             Ok(core) => core,
@@ -24,7 +31,6 @@ impl ToolParser {
 
         let struct_name = &input_struct.ident;
         let tool_name_literal = tool_attrs.name.clone();
-        let tool_description = tool_attrs.description;
         let args_type = tool_attrs.input;
         let output_schema_impl = tool_attrs.output.map(|output_type| {
             quote! {
@@ -48,6 +54,14 @@ impl ToolParser {
                     use #core::tool::ToolInputSchema;
                     <#args_type as ToolInputSchema>::io_schema_value().clone()
                 }
+                fn validate_args(
+                    &self,
+                    args: &::serde_json::Value,
+                ) -> ::std::result::Result<(), #core::tool::ToolCallError> {
+                    <#args_type as #core::tool::ToolInputT>::validate_args(args)?;
+                    // Deserialize up front so type errors name the offending field.
+                    <#args_type as #core::tool::ToolInputT>::from_args(args.clone()).map(|_| ())
+                }
                 #output_schema_impl
             }
 
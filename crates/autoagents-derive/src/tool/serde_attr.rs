@@ -0,0 +1,127 @@
+use syn::{Attribute, Error, LitStr, Result, Token};
+
+/// The subset of `#[serde(...)]` container and variant attributes that
+/// changes the JSON shape of an enum.
+#[derive(Debug, Default)]
+pub(crate) struct SerdeAttrs {
+    pub(crate) rename: Option<String>,
+    pub(crate) rename_all: Option<LitStr>,
+    /// `tag`, `content` or `untagged`, which the schema does not model.
+    pub(crate) representation: Option<String>,
+}
+
+impl SerdeAttrs {
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = SerdeAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                let key = meta
+                    .path
+                    .get_ident()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                match key.as_str() {
+                    "rename" if meta.input.peek(Token![=]) => {
+                        let value: LitStr = meta.value()?.parse()?;
+                        parsed.rename = Some(value.value());
+                    }
+                    "rename_all" if meta.input.peek(Token![=]) => {
+                        parsed.rename_all = Some(meta.value()?.parse()?);
+                    }
+                    "tag" | "content" | "untagged" => parsed.representation = Some(key),
+                    _ => {}
+                }
+                skip_meta_value(&meta)
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
+/// Consume whatever follows a serde attribute key we do not interpret
+fn skip_meta_value(meta: &syn::meta::ParseNestedMeta) -> Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|nested| skip_meta_value(&nested))?;
+    }
+    Ok(())
+}
+
+/// Apply a serde `rename_all` rule to a `PascalCase` variant name
+pub(crate) fn rename_variant(name: &str, rule: &LitStr) -> Result<String> {
+    let snake = || {
+        let mut out = String::with_capacity(name.len() + 4);
+        for (i, ch) in name.char_indices() {
+            if ch.is_uppercase() && i > 0 {
+                out.push('_');
+            }
+            out.extend(ch.to_lowercase());
+        }
+        out
+    };
+    Ok(match rule.value().as_str() {
+        "lowercase" => name.to_lowercase(),
+        "UPPERCASE" => name.to_uppercase(),
+        "PascalCase" => name.to_string(),
+        "camelCase" => {
+            let mut chars = name.chars();
+            chars
+                .next()
+                .map(|first| first.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        "snake_case" => snake(),
+        "SCREAMING_SNAKE_CASE" => snake().to_uppercase(),
+        "kebab-case" => snake().replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => snake().replace('_', "-").to_uppercase(),
+        other => {
+            return Err(Error::new(
+                rule.span(),
+                format!("unsupported serde rename_all rule: {other}"),
+            ));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proc_macro2::Span;
+
+    fn rule(value: &str) -> LitStr {
+        LitStr::new(value, Span::call_site())
+    }
+
+    #[test]
+    fn rename_variant_follows_serde_rules() {
+        assert_eq!(
+            rename_variant("FastMode", &rule("snake_case")).unwrap(),
+            "fast_mode"
+        );
+        assert_eq!(
+            rename_variant("FastMode", &rule("kebab-case")).unwrap(),
+            "fast-mode"
+        );
+        assert_eq!(
+            rename_variant("FastMode", &rule("camelCase")).unwrap(),
+            "fastMode"
+        );
+        assert_eq!(
+            rename_variant("FastMode", &rule("SCREAMING_SNAKE_CASE")).unwrap(),
+            "FAST_MODE"
+        );
+        assert!(rename_variant("FastMode", &rule("Title Case")).is_err());
+    }
+
+    #[test]
+    fn serde_attrs_ignore_unrelated_keys() {
+        let attrs: Vec<Attribute> = vec![
+            syn::parse_quote!(#[serde(deny_unknown_fields, rename_all = "lowercase")]),
+            syn::parse_quote!(#[serde(tag = "kind", bound(serialize = "T: Clone"))]),
+        ];
+        let parsed = SerdeAttrs::from_attrs(&attrs).unwrap();
+        assert_eq!(parsed.rename_all.unwrap().value(), "lowercase");
+        assert_eq!(parsed.representation.as_deref(), Some("tag"));
+    }
+}
@@ -0,0 +1,477 @@
+use super::input::InputParser;
+use crate::doc;
+use crate::resolve;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, DeriveInput, Error, FnArg, Ident, ImplItem, ImplItemFn, ItemImpl, LitStr, Pat,
+    Result, ReturnType, Type, Visibility, parse::Parse, parse::ParseStream, parse_macro_input,
+    spanned::Spanned,
+};
+
+/// `#[toolkit(prefix = "...")]` on an `impl` block
+#[derive(Default)]
+pub(crate) struct ToolkitAttributes {
+    /// Prepended to the name of every tool in the toolkit
+    prefix: Option<LitStr>,
+}
+
+impl Parse for ToolkitAttributes {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut attrs = ToolkitAttributes::default();
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+            match key.to_string().as_str() {
+                "prefix" => attrs.prefix = Some(input.parse()?),
+                other => {
+                    return Err(Error::new(
+                        key.span(),
+                        format!("Unexpected toolkit attribute key: {other}"),
+                    ));
+                }
+            }
+            if input.peek(syn::Token![,]) {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+        Ok(attrs)
+    }
+}
+
+/// `#[tool(name = "...", description = "...")]` on a toolkit method; both keys are optional
+#[derive(Default)]
+struct MethodToolAttr {
+    name: Option<LitStr>,
+    description: Option<LitStr>,
+}
+
+impl MethodToolAttr {
+    fn parse(attr: &Attribute) -> Result<Self> {
+        let mut parsed = MethodToolAttr::default();
+        if matches!(attr.meta, syn::Meta::Path(_)) {
+            return Ok(parsed);
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                parsed.name = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("description") {
+                parsed.description = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta
+                    .error("unsupported toolkit `#[tool]` key, expected `name` or `description`"));
+            }
+            Ok(())
+        })?;
+        Ok(parsed)
+    }
+}
+
+/// One `#[tool]` method turned into a tool struct and an argument struct
+struct ToolMethod {
+    vis: Visibility,
+    method: Ident,
+    name: String,
+    description: LitStr,
+    is_async: bool,
+    returns_result: bool,
+    params: Vec<(Ident, Type, Vec<Attribute>)>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ToolkitParser {}
+
+impl ToolkitParser {
+    pub fn parse(&self, attr: TokenStream, item: TokenStream) -> TokenStream {
+        let attrs = parse_macro_input!(attr as ToolkitAttributes);
+        let item_impl = parse_macro_input!(item as ItemImpl);
+        match self.expand(attrs, item_impl) {
+            Ok(expanded) => expanded.into(),
+            Err(err) => err.to_compile_error().into(),
+        }
+    }
+
+    fn expand(&self, attrs: ToolkitAttributes, mut item_impl: ItemImpl) -> Result<TokenStream2> {
+        let toolkit_ident = toolkit_ident(&item_impl)?;
+        let prefix = attrs
+            .prefix
+            .map(|prefix| prefix.value())
+            .unwrap_or_default();
+
+        let mut methods = Vec::new();
+        for item in &mut item_impl.items {
+            if let ImplItem::Fn(method) = item
+                && let Some(tool_method) = take_tool_method(method, &prefix)?
+            {
+                methods.push(tool_method);
+            }
+        }
+        if methods.is_empty() {
+            return Err(Error::new(
+                item_impl.self_ty.span(),
+                "#[toolkit] needs at least one method marked with #[tool]",
+            ));
+        }
+
+        let (core, async_trait) = resolve::resolve_core_and_async_trait_paths()?;
+        let core = &core;
+
+        let mut tool_idents = Vec::with_capacity(methods.len());
+        let mut generated = Vec::with_capacity(methods.len());
+        for method in &methods {
+            let method_pascal = pascal_case(&method.method.to_string());
+            let tool_ident = format_ident!("{toolkit_ident}{method_pascal}");
+            let args_ident = format_ident!("{toolkit_ident}{method_pascal}Args");
+            generated.push(expand_tool(
+                method,
+                &toolkit_ident,
+                &tool_ident,
+                &args_ident,
+                core,
+                &async_trait,
+            )?);
+            tool_idents.push(tool_ident);
+        }
+
+        Ok(quote! {
+            #item_impl
+
+            impl #toolkit_ident {
+                /// Every `#[tool]` method of this toolkit as a tool, all sharing this instance
+                pub fn into_tools(
+                    self: ::std::sync::Arc<Self>,
+                ) -> ::std::vec::Vec<::std::boxed::Box<dyn #core::tool::ToolT>> {
+                    ::std::vec![
+                        #(
+                            ::std::boxed::Box::new(#tool_idents::new(::std::sync::Arc::clone(&self)))
+                                as ::std::boxed::Box<dyn #core::tool::ToolT>
+                        ),*
+                    ]
+                }
+            }
+
+            #(#generated)*
+        })
+    }
+}
+
+fn toolkit_ident(item_impl: &ItemImpl) -> Result<Ident> {
+    if item_impl.trait_.is_some() {
+        return Err(Error::new(
+            item_impl.span(),
+            "#[toolkit] must be placed on an inherent impl block",
+        ));
+    }
+    if !item_impl.generics.params.is_empty() {
+        return Err(Error::new(
+            item_impl.generics.span(),
+            "#[toolkit] does not support generic impl blocks",
+        ));
+    }
+    match &*item_impl.self_ty {
+        Type::Path(path) if path.qself.is_none() && path.path.segments.len() == 1 => {
+            Ok(path.path.segments[0].ident.clone())
+        }
+        other => Err(Error::new(
+            other.span(),
+            "#[toolkit] must be placed on `impl StructName { ... }`",
+        )),
+    }
+}
+
+/// Strip the `#[tool]` and parameter `#[input]` attributes from `method` and
+/// describe the tool it defines, or `None` if it is not marked with `#[tool]`
+fn take_tool_method(method: &mut ImplItemFn, prefix: &str) -> Result<Option<ToolMethod>> {
+    let Some(index) = method
+        .attrs
+        .iter()
+        .position(|attr| attr.path().is_ident("tool"))
+    else {
+        return Ok(None);
+    };
+    let attr = method.attrs.remove(index);
+    let tool_attr = MethodToolAttr::parse(&attr)?;
+    let sig = &mut method.sig;
+
+    let takes_shared_self = matches!(
+        sig.inputs.first(),
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() && receiver.mutability.is_none()
+    );
+    if !takes_shared_self {
+        return Err(Error::new(
+            sig.ident.span(),
+            "toolkit tools must take `&self`",
+        ));
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(Error::new(
+            sig.generics.span(),
+            "toolkit tools cannot be generic",
+        ));
+    }
+
+    let mut params = Vec::new();
+    for input in sig.inputs.iter_mut().skip(1) {
+        let FnArg::Typed(pat_type) = input else {
+            continue;
+        };
+        let Pat::Ident(pat_ident) = &*pat_type.pat else {
+            return Err(Error::new(
+                pat_type.pat.span(),
+                "toolkit tool parameters must be plain identifiers",
+            ));
+        };
+        if matches!(&*pat_type.ty, Type::Reference(_)) {
+            return Err(Error::new(
+                pat_type.ty.span(),
+                "toolkit tool parameters must be owned types, e.g. `String` instead of `&str`",
+            ));
+        }
+        let (input_attrs, rest): (Vec<_>, Vec<_>) = pat_type
+            .attrs
+            .drain(..)
+            .partition(|attr| attr.path().is_ident("input"));
+        pat_type.attrs = rest;
+        params.push((pat_ident.ident.clone(), (*pat_type.ty).clone(), input_attrs));
+    }
+
+    let description = doc::description_or_docs(tool_attr.description, &method.attrs)?;
+    let name = tool_attr
+        .name
+        .map(|name| name.value())
+        .unwrap_or_else(|| sig.ident.to_string());
+
+    Ok(Some(ToolMethod {
+        vis: method.vis.clone(),
+        method: sig.ident.clone(),
+        name: format!("{prefix}{name}"),
+        description,
+        is_async: sig.asyncness.is_some(),
+        returns_result: returns_result(&sig.output),
+        params,
+    }))
+}
+
+fn returns_result(output: &ReturnType) -> bool {
+    match output {
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Result"),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    }
+}
+
+fn expand_tool(
+    method: &ToolMethod,
+    toolkit_ident: &Ident,
+    tool_ident: &Ident,
+    args_ident: &Ident,
+    core: &syn::Path,
+    async_trait: &syn::Path,
+) -> Result<TokenStream2> {
+    let ToolMethod {
+        vis,
+        method: method_ident,
+        name,
+        description,
+        is_async,
+        returns_result,
+        params,
+    } = method;
+
+    let field_idents: Vec<&Ident> = params.iter().map(|(ident, _, _)| ident).collect();
+    let field_types: Vec<&Type> = params.iter().map(|(_, ty, _)| ty).collect();
+    let field_attrs: Vec<&Vec<Attribute>> = params.iter().map(|(_, _, attrs)| attrs).collect();
+
+    // The schema is built from the parameters with their `#[input]` attributes;
+    // the emitted struct leaves those out since it does not derive `ToolInput`.
+    let schema_input: DeriveInput = syn::parse_quote! {
+        struct #args_ident {
+            #( #(#field_attrs)* #field_idents: #field_types, )*
+        }
+    };
+    let input_impls = InputParser::default().expand(&schema_input)?;
+
+    let call = if *is_async {
+        quote!(self.toolkit.#method_ident(#(#field_idents),*).await)
+    } else {
+        quote!(self.toolkit.#method_ident(#(#field_idents),*))
+    };
+    let output = if *returns_result {
+        quote!(#call?)
+    } else {
+        quote!(#call)
+    };
+    let tool_doc = format!("Tool `{name}` generated from `{toolkit_ident}::{method_ident}`");
+
+    Ok(quote! {
+        #[doc(hidden)]
+        #[derive(::serde::Deserialize)]
+        #vis struct #args_ident {
+            #( #field_idents: #field_types, )*
+        }
+
+        #input_impls
+
+        #[doc = #tool_doc]
+        #vis struct #tool_ident {
+            toolkit: ::std::sync::Arc<#toolkit_ident>,
+        }
+
+        impl #tool_ident {
+            #vis fn new(toolkit: impl ::std::convert::Into<::std::sync::Arc<#toolkit_ident>>) -> Self {
+                Self {
+                    toolkit: toolkit.into(),
+                }
+            }
+        }
+
+        impl #core::tool::ToolT for #tool_ident {
+            fn name(&self) -> &str {
+                #name
+            }
+            fn description(&self) -> &str {
+                #description
+            }
+            fn args_schema(&self) -> ::serde_json::Value {
+                <#args_ident as #core::tool::ToolInputSchema>::io_schema_value().clone()
+            }
+            fn validate_args(
+                &self,
+                args: &::serde_json::Value,
+            ) -> ::std::result::Result<(), #core::tool::ToolCallError> {
+                <#args_ident as #core::tool::ToolInputT>::validate_args(args)
+            }
+        }
+
+        impl ::std::fmt::Debug for #tool_ident {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(#name)
+            }
+        }
+
+        #[#async_trait]
+        impl #core::tool::ToolRuntime for #tool_ident {
+            async fn execute(
+                &self,
+                args: ::serde_json::Value,
+            ) -> ::std::result::Result<::serde_json::Value, #core::tool::ToolCallError> {
+                let #args_ident { #(#field_idents),* } =
+                    <#args_ident as #core::tool::ToolInputT>::from_args(args)?;
+                let output = #output;
+                ::std::result::Result::Ok(::serde_json::to_value(output)?)
+            }
+        }
+    })
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_methods_are_collected_and_attributes_stripped() {
+        let mut method: ImplItemFn = syn::parse_quote! {
+            /// Read a file
+            #[tool(name = "read")]
+            pub async fn read_file(
+                &self,
+                #[input(description = "Relative path", max_length = 200)] path: String,
+                lines: Option<u32>,
+            ) -> Result<String, ToolCallError> {
+                todo!()
+            }
+        };
+        let tool = take_tool_method(&mut method, "fs_").unwrap().unwrap();
+        assert_eq!(tool.name, "fs_read");
+        assert_eq!(tool.description.value(), "Read a file");
+        assert!(tool.is_async);
+        assert!(tool.returns_result);
+        assert_eq!(tool.params.len(), 2);
+        assert_eq!(tool.params[0].2.len(), 1);
+
+        assert!(!method.attrs.iter().any(|attr| attr.path().is_ident("tool")));
+        let FnArg::Typed(path) = &method.sig.inputs[1] else {
+            panic!("expected a typed parameter");
+        };
+        assert!(path.attrs.is_empty());
+    }
+
+    #[test]
+    fn methods_without_tool_attribute_are_left_alone() {
+        let mut method: ImplItemFn = syn::parse_quote! {
+            fn helper(&self) -> u32 { 1 }
+        };
+        assert!(take_tool_method(&mut method, "").unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid_tool_methods_error() {
+        let cases: [(ImplItemFn, &str); 4] = [
+            (
+                syn::parse_quote!(
+                    #[tool]
+                    /// Docs
+                    fn build(name: String) {}
+                ),
+                "must take `&self`",
+            ),
+            (
+                syn::parse_quote!(
+                    #[tool]
+                    /// Docs
+                    fn greet(&self, name: &str) {}
+                ),
+                "must be owned types",
+            ),
+            (
+                syn::parse_quote!(
+                    #[tool]
+                    /// Docs
+                    fn pick(&self, (a, b): (u8, u8)) {}
+                ),
+                "plain identifiers",
+            ),
+            (
+                syn::parse_quote!(
+                    #[tool]
+                    fn silent(&self) {}
+                ),
+                "Missing attribute: description",
+            ),
+        ];
+        for (mut method, expected) in cases {
+            let err = match take_tool_method(&mut method, "") {
+                Err(err) => err,
+                Ok(_) => panic!("expected error containing {expected}"),
+            };
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+    }
+
+    #[test]
+    fn pascal_case_joins_snake_case_words() {
+        assert_eq!(pascal_case("read_file"), "ReadFile");
+        assert_eq!(pascal_case("list"), "List");
+        assert_eq!(pascal_case("_private_call"), "PrivateCall");
+    }
+}
@@ -6,6 +6,7 @@ const COMPILE_FAIL_FIXTURES: &[&str] = &[
     "tests/ui/compile_fail/invalid_choice.rs",
     "tests/ui/compile_fail/invalid_output_field.rs",
     "tests/ui/compile_fail/invalid_strict.rs",
+    "tests/ui/compile_fail/tool_missing_description.rs",
     "tests/ui/compile_fail/tool_missing_input.rs",
     "tests/ui/compile_fail/unsupported_agent_output_type.rs",
 ];
@@ -0,0 +1,7 @@
+use autoagents_derive::tool;
+
+// Without `description = "..."` the doc comment is used, so a tool needs one of the two.
+#[tool(name = "undocumented", input = String)]
+struct UndocumentedTool;
+
+fn main() {}
@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use autoagents_llm::error::LLMError;
+
+use crate::{FastEmbedConfig, FastEmbedModel, FastEmbedProvider};
+
+/// Builder for FastEmbedProvider.
+#[derive(Default)]
+pub struct FastEmbedProviderBuilder {
+    config: FastEmbedConfig,
+}
+
+impl FastEmbedProviderBuilder {
+    /// Create a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the embedding model.
+    pub fn model(mut self, model: FastEmbedModel) -> Self {
+        self.config.model = model;
+        self
+    }
+
+    /// Truncate inputs to at most this many tokens.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.config.max_length = Some(max_length);
+        self
+    }
+
+    /// Set how many texts are embedded per inference call.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.config.batch_size = batch_size;
+        self
+    }
+
+    /// Set the directory downloaded models are cached in.
+    pub fn cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.config.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    /// Print a progress bar while downloading model files.
+    pub fn show_download_progress(mut self, show: bool) -> Self {
+        self.config.show_download_progress = show;
+        self
+    }
+
+    /// Truncate vectors to their first `dimensions` components.
+    pub fn dimensions(mut self, dimensions: usize) -> Self {
+        self.config.dimensions = Some(dimensions);
+        self
+    }
+
+    /// L2-normalize vectors after truncation.
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.config.normalize = normalize;
+        self
+    }
+
+    /// Build the provider. The model is loaded on first use.
+    pub fn build(self) -> Result<FastEmbedProvider, LLMError> {
+        FastEmbedProvider::from_config(self.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_maps_config_fields() {
+        let provider = FastEmbedProviderBuilder::new()
+            .model(FastEmbedModel::MultilingualE5Small)
+            .max_length(256)
+            .batch_size(8)
+            .cache_dir("/tmp/fastembed")
+            .show_download_progress(true)
+            .dimensions(128)
+            .normalize(true)
+            .build()
+            .unwrap();
+        let config = provider.config();
+        assert_eq!(config.model, FastEmbedModel::MultilingualE5Small);
+        assert_eq!(config.max_length, Some(256));
+        assert_eq!(config.batch_size, 8);
+        assert_eq!(config.cache_dir, Some(PathBuf::from("/tmp/fastembed")));
+        assert!(config.show_download_progress);
+        assert_eq!(config.dimensions, Some(128));
+        assert!(config.normalize);
+        assert_eq!(provider.dimensions(), 128);
+    }
+}
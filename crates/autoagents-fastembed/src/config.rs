@@ -0,0 +1,286 @@
+//! Configuration structures for the fastembed provider.
+
+use fastembed::EmbeddingModel;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::error::FastEmbedProviderError;
+
+/// Embedding models supported out of the box.
+///
+/// Weights are fetched from Hugging Face on first use and cached under
+/// [`FastEmbedConfig::cache_dir`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FastEmbedModel {
+    /// BAAI/bge-small-en-v1.5, 384 dimensions.
+    #[default]
+    BgeSmallEnV15,
+    /// BAAI/bge-base-en-v1.5, 768 dimensions.
+    BgeBaseEnV15,
+    /// BAAI/bge-large-en-v1.5, 1024 dimensions.
+    BgeLargeEnV15,
+    /// nomic-ai/nomic-embed-text-v1.5, 768 dimensions, 8192 token context.
+    NomicEmbedTextV15,
+    /// intfloat/multilingual-e5-small, 384 dimensions.
+    MultilingualE5Small,
+    /// intfloat/multilingual-e5-base, 768 dimensions.
+    MultilingualE5Base,
+    /// intfloat/multilingual-e5-large, 1024 dimensions.
+    MultilingualE5Large,
+    /// sentence-transformers/all-MiniLM-L6-v2, 384 dimensions.
+    AllMiniLmL6V2,
+    /// Qdrant/clip-ViT-B-32-text, 512 dimensions. Text half of CLIP; pair
+    /// with `FastEmbedImageModel::ClipVitB32` (feature `image`) for
+    /// text-to-image search.
+    ClipVitB32Text,
+}
+
+impl FastEmbedModel {
+    pub const ALL: [FastEmbedModel; 9] = [
+        FastEmbedModel::BgeSmallEnV15,
+        FastEmbedModel::BgeBaseEnV15,
+        FastEmbedModel::BgeLargeEnV15,
+        FastEmbedModel::NomicEmbedTextV15,
+        FastEmbedModel::MultilingualE5Small,
+        FastEmbedModel::MultilingualE5Base,
+        FastEmbedModel::MultilingualE5Large,
+        FastEmbedModel::AllMiniLmL6V2,
+        FastEmbedModel::ClipVitB32Text,
+    ];
+
+    /// Short name accepted by [`FromStr`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            FastEmbedModel::BgeSmallEnV15 => "bge-small-en-v1.5",
+            FastEmbedModel::BgeBaseEnV15 => "bge-base-en-v1.5",
+            FastEmbedModel::BgeLargeEnV15 => "bge-large-en-v1.5",
+            FastEmbedModel::NomicEmbedTextV15 => "nomic-embed-text-v1.5",
+            FastEmbedModel::MultilingualE5Small => "multilingual-e5-small",
+            FastEmbedModel::MultilingualE5Base => "multilingual-e5-base",
+            FastEmbedModel::MultilingualE5Large => "multilingual-e5-large",
+            FastEmbedModel::AllMiniLmL6V2 => "all-minilm-l6-v2",
+            FastEmbedModel::ClipVitB32Text => "clip-vit-b-32-text",
+        }
+    }
+
+    /// Length of the vectors produced by the model.
+    pub fn dimensions(&self) -> usize {
+        match self {
+            FastEmbedModel::BgeSmallEnV15
+            | FastEmbedModel::MultilingualE5Small
+            | FastEmbedModel::AllMiniLmL6V2 => 384,
+            FastEmbedModel::BgeBaseEnV15
+            | FastEmbedModel::NomicEmbedTextV15
+            | FastEmbedModel::MultilingualE5Base => 768,
+            FastEmbedModel::BgeLargeEnV15 | FastEmbedModel::MultilingualE5Large => 1024,
+            FastEmbedModel::ClipVitB32Text => 512,
+        }
+    }
+}
+
+impl From<FastEmbedModel> for EmbeddingModel {
+    fn from(value: FastEmbedModel) -> Self {
+        match value {
+            FastEmbedModel::BgeSmallEnV15 => EmbeddingModel::BGESmallENV15,
+            FastEmbedModel::BgeBaseEnV15 => EmbeddingModel::BGEBaseENV15,
+            FastEmbedModel::BgeLargeEnV15 => EmbeddingModel::BGELargeENV15,
+            FastEmbedModel::NomicEmbedTextV15 => EmbeddingModel::NomicEmbedTextV15,
+            FastEmbedModel::MultilingualE5Small => EmbeddingModel::MultilingualE5Small,
+            FastEmbedModel::MultilingualE5Base => EmbeddingModel::MultilingualE5Base,
+            FastEmbedModel::MultilingualE5Large => EmbeddingModel::MultilingualE5Large,
+            FastEmbedModel::AllMiniLmL6V2 => EmbeddingModel::AllMiniLML6V2,
+            FastEmbedModel::ClipVitB32Text => EmbeddingModel::ClipVitB32,
+        }
+    }
+}
+
+impl fmt::Display for FastEmbedModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for FastEmbedModel {
+    type Err = FastEmbedProviderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Accept both the short name and the Hugging Face repo id.
+        let name = s.rsplit('/').next().unwrap_or(s).to_ascii_lowercase();
+        FastEmbedModel::ALL
+            .into_iter()
+            .find(|model| model.name() == name)
+            .ok_or_else(|| FastEmbedProviderError::Config(format!("unknown model '{}'", s)))
+    }
+}
+
+/// Configuration for [`FastEmbedProvider`](crate::FastEmbedProvider).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FastEmbedConfig {
+    pub model: FastEmbedModel,
+    /// Inputs longer than this many tokens are truncated. Defaults to the
+    /// model's own limit.
+    pub max_length: Option<usize>,
+    /// Number of texts run through the model at once.
+    pub batch_size: usize,
+    /// Directory for downloaded weights. Defaults to `FASTEMBED_CACHE_DIR`
+    /// or `.fastembed_cache`.
+    pub cache_dir: Option<PathBuf>,
+    pub show_download_progress: bool,
+    /// Keep only the first `dimensions` components of each vector
+    /// (Matryoshka truncation, e.g. for nomic-embed-text-v1.5).
+    #[serde(default)]
+    pub dimensions: Option<usize>,
+    /// L2-normalize vectors after truncation.
+    #[serde(default)]
+    pub normalize: bool,
+}
+
+impl Default for FastEmbedConfig {
+    fn default() -> Self {
+        Self {
+            model: FastEmbedModel::default(),
+            max_length: None,
+            batch_size: 256,
+            cache_dir: None,
+            show_download_progress: false,
+            dimensions: None,
+            normalize: false,
+        }
+    }
+}
+
+impl FastEmbedConfig {
+    pub(crate) fn validate(&self) -> Result<(), FastEmbedProviderError> {
+        if self.batch_size == 0 {
+            return Err(FastEmbedProviderError::Config(
+                "batch_size must be greater than zero".to_string(),
+            ));
+        }
+        if self.max_length == Some(0) {
+            return Err(FastEmbedProviderError::Config(
+                "max_length must be greater than zero".to_string(),
+            ));
+        }
+        if let Some(dimensions) = self.dimensions
+            && (dimensions == 0 || dimensions > self.model.dimensions())
+        {
+            return Err(FastEmbedProviderError::Config(format!(
+                "dimensions must be between 1 and {} for {}",
+                self.model.dimensions(),
+                self.model
+            )));
+        }
+        Ok(())
+    }
+
+    /// Length of the vectors after truncation.
+    pub fn output_dimensions(&self) -> usize {
+        self.dimensions.unwrap_or_else(|| self.model.dimensions())
+    }
+
+    /// Truncate and normalize a vector produced by the model.
+    pub(crate) fn post_process(&self, mut vector: Vec<f32>) -> Vec<f32> {
+        if let Some(dimensions) = self.dimensions {
+            vector.truncate(dimensions);
+        }
+        if self.normalize {
+            let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+            if norm > f32::EPSILON {
+                vector.iter_mut().for_each(|v| *v /= norm);
+            }
+        }
+        vector
+    }
+
+    pub(crate) fn init_options(&self) -> fastembed::TextInitOptions {
+        let mut options = fastembed::TextInitOptions::new(self.model.into())
+            .with_show_download_progress(self.show_download_progress);
+        if let Some(max_length) = self.max_length {
+            options = options.with_max_length(max_length);
+        }
+        if let Some(cache_dir) = &self.cache_dir {
+            options = options.with_cache_dir(cache_dir.clone());
+        }
+        options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_names_round_trip() {
+        for model in FastEmbedModel::ALL {
+            assert_eq!(model.name().parse::<FastEmbedModel>().unwrap(), model);
+        }
+        assert_eq!(
+            "BAAI/bge-small-en-v1.5".parse::<FastEmbedModel>().unwrap(),
+            FastEmbedModel::BgeSmallEnV15
+        );
+        assert!("text-embedding-3-small".parse::<FastEmbedModel>().is_err());
+    }
+
+    #[test]
+    fn test_dimensions_match_fastembed() {
+        for model in FastEmbedModel::ALL {
+            let model_name = EmbeddingModel::from(model);
+            let info = fastembed::TextEmbedding::get_model_info(&model_name).unwrap();
+            assert_eq!(model.dimensions(), info.dim, "{model}");
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(FastEmbedConfig::default().validate().is_ok());
+        let config = FastEmbedConfig {
+            batch_size: 0,
+            ..FastEmbedConfig::default()
+        };
+        assert!(config.validate().is_err());
+        let config = FastEmbedConfig {
+            max_length: Some(0),
+            ..FastEmbedConfig::default()
+        };
+        assert!(config.validate().is_err());
+        let config = FastEmbedConfig {
+            dimensions: Some(512),
+            ..FastEmbedConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_post_process_truncates_and_normalizes() {
+        let config = FastEmbedConfig {
+            model: FastEmbedModel::NomicEmbedTextV15,
+            dimensions: Some(2),
+            normalize: true,
+            ..FastEmbedConfig::default()
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(config.output_dimensions(), 2);
+        assert_eq!(config.post_process(vec![3.0, 4.0, 12.0]), vec![0.6, 0.8]);
+        assert_eq!(
+            FastEmbedConfig::default().post_process(vec![3.0, 4.0]),
+            vec![3.0, 4.0]
+        );
+    }
+
+    #[test]
+    fn test_init_options() {
+        let config = FastEmbedConfig {
+            model: FastEmbedModel::NomicEmbedTextV15,
+            max_length: Some(128),
+            cache_dir: Some(PathBuf::from("/tmp/models")),
+            ..FastEmbedConfig::default()
+        };
+        let options = config.init_options();
+        assert_eq!(options.model_name, EmbeddingModel::NomicEmbedTextV15);
+        assert_eq!(options.max_length, 128);
+        assert_eq!(options.cache_dir, PathBuf::from("/tmp/models"));
+    }
+}
@@ -0,0 +1,67 @@
+//! Error handling and conversions for the fastembed backend.
+
+use autoagents_llm::error::LLMError;
+use std::fmt;
+
+/// Internal error type for fastembed operations.
+#[derive(Debug)]
+pub enum FastEmbedProviderError {
+    /// Model download or initialization failed.
+    ModelLoad(String),
+    /// Embedding inference failed.
+    Embedding(String),
+    /// Configuration error.
+    Config(String),
+    /// Generic error.
+    Other(String),
+}
+
+impl fmt::Display for FastEmbedProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FastEmbedProviderError::ModelLoad(e) => write!(f, "Model Load Error: {}", e),
+            FastEmbedProviderError::Embedding(e) => write!(f, "Embedding Error: {}", e),
+            FastEmbedProviderError::Config(e) => write!(f, "Configuration Error: {}", e),
+            FastEmbedProviderError::Other(e) => write!(f, "fastembed Error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FastEmbedProviderError {}
+
+impl From<FastEmbedProviderError> for LLMError {
+    fn from(err: FastEmbedProviderError) -> Self {
+        match err {
+            FastEmbedProviderError::ModelLoad(e) => {
+                LLMError::ProviderError(format!("Failed to load model: {}", e))
+            }
+            FastEmbedProviderError::Embedding(e) => {
+                LLMError::ProviderError(format!("Embedding failed: {}", e))
+            }
+            FastEmbedProviderError::Config(e) => {
+                LLMError::invalid_request(format!("Invalid configuration: {}", e))
+            }
+            FastEmbedProviderError::Other(e) => {
+                LLMError::ProviderError(format!("fastembed error: {}", e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_display() {
+        let err = FastEmbedProviderError::ModelLoad("no network".to_string());
+        assert_eq!(err.to_string(), "Model Load Error: no network");
+    }
+
+    #[test]
+    fn test_error_to_llm_error() {
+        let err = FastEmbedProviderError::Config("bad config".to_string());
+        let llm_err: LLMError = err.into();
+        assert!(llm_err.to_string().contains("Invalid configuration"));
+    }
+}
@@ -0,0 +1,218 @@
+//! fastembed-backed [`ImageEmbeddingProvider`] implementation.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use autoagents_llm::{embedding::ImageEmbeddingProvider, error::LLMError};
+use fastembed::{ImageEmbedding, ImageEmbeddingModel, ImageInitOptions};
+use serde::{Deserialize, Serialize};
+
+use crate::error::FastEmbedProviderError;
+
+/// Image embedding models supported out of the box.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FastEmbedImageModel {
+    /// Qdrant/clip-ViT-B-32-vision, 512 dimensions. Shares its space with
+    /// [`FastEmbedModel::ClipVitB32Text`](crate::FastEmbedModel::ClipVitB32Text).
+    #[default]
+    ClipVitB32,
+    /// nomic-ai/nomic-embed-vision-v1.5, 768 dimensions. Shares its space with
+    /// [`FastEmbedModel::NomicEmbedTextV15`](crate::FastEmbedModel::NomicEmbedTextV15).
+    NomicEmbedVisionV15,
+}
+
+impl FastEmbedImageModel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FastEmbedImageModel::ClipVitB32 => "clip-vit-b-32-vision",
+            FastEmbedImageModel::NomicEmbedVisionV15 => "nomic-embed-vision-v1.5",
+        }
+    }
+
+    /// Length of the vectors produced by the model.
+    pub fn dimensions(&self) -> usize {
+        match self {
+            FastEmbedImageModel::ClipVitB32 => 512,
+            FastEmbedImageModel::NomicEmbedVisionV15 => 768,
+        }
+    }
+}
+
+impl From<FastEmbedImageModel> for ImageEmbeddingModel {
+    fn from(value: FastEmbedImageModel) -> Self {
+        match value {
+            FastEmbedImageModel::ClipVitB32 => ImageEmbeddingModel::ClipVitB32,
+            FastEmbedImageModel::NomicEmbedVisionV15 => ImageEmbeddingModel::NomicEmbedVisionV15,
+        }
+    }
+}
+
+impl fmt::Display for FastEmbedImageModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Configuration for [`FastEmbedImageProvider`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FastEmbedImageConfig {
+    pub model: FastEmbedImageModel,
+    /// Number of images run through the model at once.
+    pub batch_size: usize,
+    /// Directory for downloaded weights. Defaults to `FASTEMBED_CACHE_DIR`
+    /// or `.fastembed_cache`.
+    pub cache_dir: Option<PathBuf>,
+    pub show_download_progress: bool,
+}
+
+impl Default for FastEmbedImageConfig {
+    fn default() -> Self {
+        Self {
+            model: FastEmbedImageModel::default(),
+            batch_size: 32,
+            cache_dir: None,
+            show_download_progress: false,
+        }
+    }
+}
+
+impl FastEmbedImageConfig {
+    fn init_options(&self) -> ImageInitOptions {
+        let mut options = ImageInitOptions::new(self.model.into())
+            .with_show_download_progress(self.show_download_progress);
+        if let Some(cache_dir) = &self.cache_dir {
+            options = options.with_cache_dir(cache_dir.clone());
+        }
+        options
+    }
+}
+
+/// Local image embedding provider running ONNX vision encoders through fastembed.
+///
+/// Like [`FastEmbedProvider`](crate::FastEmbedProvider), the model is loaded on
+/// first use and shared between clones. Inputs are encoded images in any
+/// format the `image` crate can decode (PNG, JPEG, WebP, ...).
+#[derive(Clone)]
+pub struct FastEmbedImageProvider {
+    config: FastEmbedImageConfig,
+    model: Arc<Mutex<Option<ImageEmbedding>>>,
+}
+
+impl FastEmbedImageProvider {
+    pub fn new(model: FastEmbedImageModel) -> Self {
+        Self {
+            config: FastEmbedImageConfig {
+                model,
+                ..FastEmbedImageConfig::default()
+            },
+            model: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Create a provider from a configuration without loading the model.
+    pub fn from_config(config: FastEmbedImageConfig) -> Result<Self, LLMError> {
+        if config.batch_size == 0 {
+            return Err(FastEmbedProviderError::Config(
+                "batch_size must be greater than zero".to_string(),
+            )
+            .into());
+        }
+        Ok(Self {
+            config,
+            model: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    pub fn config(&self) -> &FastEmbedImageConfig {
+        &self.config
+    }
+
+    /// Length of the vectors returned by [`embed_images`](ImageEmbeddingProvider::embed_images).
+    pub fn dimensions(&self) -> usize {
+        self.config.model.dimensions()
+    }
+
+    /// Download (if needed) and load the model now instead of on first use.
+    pub async fn load(&self) -> Result<(), LLMError> {
+        self.run_blocking(|_| Ok(())).await
+    }
+
+    async fn run_blocking<T, F>(&self, task: F) -> Result<T, LLMError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut ImageEmbedding) -> Result<T, FastEmbedProviderError> + Send + 'static,
+    {
+        let config = self.config.clone();
+        let model = self.model.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut guard = model
+                .lock()
+                .map_err(|_| FastEmbedProviderError::Other("model lock poisoned".to_string()))?;
+            if guard.is_none() {
+                log::debug!("Loading fastembed image model {}", config.model);
+                let loaded = ImageEmbedding::try_new(config.init_options())
+                    .map_err(|e| FastEmbedProviderError::ModelLoad(e.to_string()))?;
+                *guard = Some(loaded);
+            }
+            match guard.as_mut() {
+                Some(model) => task(model),
+                None => Err(FastEmbedProviderError::ModelLoad(
+                    "model was not initialized".to_string(),
+                )),
+            }
+        })
+        .await
+        .map_err(|e| LLMError::ProviderError(format!("Embedding task failed: {}", e)))?
+        .map_err(LLMError::from)
+    }
+}
+
+#[async_trait]
+impl ImageEmbeddingProvider for FastEmbedImageProvider {
+    async fn embed_images(&self, images: Vec<Vec<u8>>) -> Result<Vec<Vec<f32>>, LLMError> {
+        if images.is_empty() {
+            return Ok(Vec::new());
+        }
+        let batch_size = self.config.batch_size;
+        self.run_blocking(move |model| {
+            let refs: Vec<&[u8]> = images.iter().map(Vec::as_slice).collect();
+            model
+                .embed_bytes(&refs, Some(batch_size))
+                .map_err(|e| FastEmbedProviderError::Embedding(e.to_string()))
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dimensions_match_fastembed() {
+        for model in [
+            FastEmbedImageModel::ClipVitB32,
+            FastEmbedImageModel::NomicEmbedVisionV15,
+        ] {
+            let info = ImageEmbedding::get_model_info(&model.into());
+            assert_eq!(model.dimensions(), info.dim, "{model}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_empty_input_skips_model_load() {
+        let provider = FastEmbedImageProvider::new(FastEmbedImageModel::ClipVitB32);
+        assert!(provider.embed_images(Vec::new()).await.unwrap().is_empty());
+        assert!(provider.model.lock().unwrap().is_none());
+        assert!(
+            FastEmbedImageProvider::from_config(FastEmbedImageConfig {
+                batch_size: 0,
+                ..FastEmbedImageConfig::default()
+            })
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,47 @@
+//! # AutoAgents fastembed Backend
+//!
+//! Local text embeddings for AutoAgents using [fastembed](https://github.com/Anush008/fastembed-rs),
+//! so vector stores and RAG pipelines run without a hosted embedding API.
+//!
+//! ## Features
+//!
+//! - **Popular Models**: bge, nomic-embed-text, multilingual-e5 and MiniLM
+//! - **On-Demand Download**: Weights are fetched from Hugging Face on first use
+//! - **Batch Inference**: Inputs are embedded in configurable batches
+//! - **Truncation**: Inputs are truncated to a configurable token limit
+//! - **Matryoshka Dimensions**: Vectors can be cut to a prefix and re-normalized
+//! - **Image Embeddings**: CLIP and nomic-embed-vision encoders behind the `image` feature
+//!
+//! ```no_run
+//! use autoagents_fastembed::{FastEmbedModel, FastEmbedProvider};
+//! use autoagents_llm::embedding::EmbeddingProvider;
+//!
+//! # async fn run() -> Result<(), autoagents_llm::error::LLMError> {
+//! let provider = FastEmbedProvider::builder()
+//!     .model(FastEmbedModel::BgeSmallEnV15)
+//!     .max_length(512)
+//!     .build()?;
+//! let vectors = provider.embed(vec!["hello world".to_string()]).await?;
+//! assert_eq!(vectors[0].len(), provider.dimensions());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! e5 and nomic models are trained with task prefixes (`"query: "`/`"passage: "`
+//! and `"search_query: "`/`"search_document: "`); include them in the input
+//! text for best retrieval quality.
+
+pub mod builder;
+pub mod config;
+pub mod error;
+#[cfg(feature = "image")]
+pub mod image;
+pub mod provider;
+
+// Re-exports for convenience
+pub use builder::FastEmbedProviderBuilder;
+pub use config::{FastEmbedConfig, FastEmbedModel};
+pub use error::FastEmbedProviderError;
+#[cfg(feature = "image")]
+pub use image::{FastEmbedImageConfig, FastEmbedImageModel, FastEmbedImageProvider};
+pub use provider::FastEmbedProvider;
@@ -0,0 +1,150 @@
+//! fastembed-backed [`EmbeddingProvider`] implementation.
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use autoagents_llm::{embedding::EmbeddingProvider, error::LLMError};
+use fastembed::TextEmbedding;
+
+use crate::{FastEmbedConfig, FastEmbedProviderBuilder, error::FastEmbedProviderError};
+
+/// Local embedding provider running ONNX models through fastembed.
+///
+/// The model is downloaded and loaded on the first call to
+/// [`embed`](EmbeddingProvider::embed), or eagerly with
+/// [`FastEmbedProvider::load`]. Clones share the loaded model.
+#[derive(Clone)]
+pub struct FastEmbedProvider {
+    config: FastEmbedConfig,
+    model: Arc<Mutex<Option<TextEmbedding>>>,
+}
+
+impl FastEmbedProvider {
+    /// Create a builder for FastEmbedProvider.
+    pub fn builder() -> FastEmbedProviderBuilder {
+        FastEmbedProviderBuilder::new()
+    }
+
+    /// Create a provider from a configuration without loading the model.
+    pub fn from_config(config: FastEmbedConfig) -> Result<Self, LLMError> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            model: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// Get reference to the configuration.
+    pub fn config(&self) -> &FastEmbedConfig {
+        &self.config
+    }
+
+    /// Length of the vectors returned by [`embed`](EmbeddingProvider::embed).
+    pub fn dimensions(&self) -> usize {
+        self.config.output_dimensions()
+    }
+
+    /// Download (if needed) and load the model now instead of on first use.
+    pub async fn load(&self) -> Result<(), LLMError> {
+        self.run_blocking(|_| Ok(())).await
+    }
+
+    async fn run_blocking<T, F>(&self, task: F) -> Result<T, LLMError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut TextEmbedding) -> Result<T, FastEmbedProviderError> + Send + 'static,
+    {
+        let config = self.config.clone();
+        let model = self.model.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut guard = model
+                .lock()
+                .map_err(|_| FastEmbedProviderError::Other("model lock poisoned".to_string()))?;
+            if guard.is_none() {
+                log::debug!("Loading fastembed model {}", config.model);
+                let loaded = TextEmbedding::try_new(config.init_options())
+                    .map_err(|e| FastEmbedProviderError::ModelLoad(e.to_string()))?;
+                *guard = Some(loaded);
+            }
+            match guard.as_mut() {
+                Some(model) => task(model),
+                None => Err(FastEmbedProviderError::ModelLoad(
+                    "model was not initialized".to_string(),
+                )),
+            }
+        })
+        .await
+        .map_err(|e| LLMError::ProviderError(format!("Embedding task failed: {}", e)))?
+        .map_err(LLMError::from)
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for FastEmbedProvider {
+    async fn embed(&self, input: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
+        if input.is_empty() {
+            return Ok(Vec::new());
+        }
+        let batch_size = self.config.batch_size;
+        let vectors = self
+            .run_blocking(move |model| {
+                model
+                    .embed(input, Some(batch_size))
+                    .map_err(|e| FastEmbedProviderError::Embedding(e.to_string()))
+            })
+            .await?;
+        Ok(vectors
+            .into_iter()
+            .map(|vector| self.config.post_process(vector))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FastEmbedModel;
+
+    #[tokio::test]
+    async fn test_empty_input_skips_model_load() {
+        let provider = FastEmbedProvider::from_config(FastEmbedConfig::default()).unwrap();
+        assert!(provider.embed(Vec::new()).await.unwrap().is_empty());
+        assert!(provider.model.lock().unwrap().is_none());
+        assert_eq!(provider.dimensions(), 384);
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        let config = FastEmbedConfig {
+            batch_size: 0,
+            ..FastEmbedConfig::default()
+        };
+        assert!(matches!(
+            FastEmbedProvider::from_config(config),
+            Err(LLMError::InvalidRequest { .. })
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore = "downloads model weights from Hugging Face"]
+    async fn test_embed_with_downloaded_model() {
+        let provider = FastEmbedProvider::builder()
+            .model(FastEmbedModel::BgeSmallEnV15)
+            .max_length(64)
+            .batch_size(2)
+            .build()
+            .unwrap();
+        let texts = vec![
+            "The cat sat on the mat".to_string(),
+            "A feline rested on the rug".to_string(),
+            "Quarterly revenue grew by 4%".to_string(),
+            "word ".repeat(500),
+        ];
+        let embeddings = provider.embed(texts).await.unwrap();
+        assert_eq!(embeddings.len(), 4);
+        assert!(embeddings.iter().all(|e| e.len() == provider.dimensions()));
+
+        let dot = |a: &[f32], b: &[f32]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>();
+        assert!(dot(&embeddings[0], &embeddings[1]) > dot(&embeddings[0], &embeddings[2]));
+    }
+}
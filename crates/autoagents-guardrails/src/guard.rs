@@ -23,6 +23,9 @@ pub struct GuardContext {
     pub request_id: u64,
     pub operation: GuardOperation,
     pub created_at: SystemTime,
+    /// Latest user message or prompt of the request, so output guards can
+    /// judge a response in the context of what it answers.
+    pub prompt: Option<String>,
 }
 
 impl GuardContext {
@@ -31,8 +34,14 @@ impl GuardContext {
             request_id: REQUEST_COUNTER.fetch_add(1, Ordering::Relaxed),
             operation,
             created_at: SystemTime::now(),
+            prompt: None,
         }
     }
+
+    pub fn with_prompt(mut self, prompt: Option<String>) -> Self {
+        self.prompt = prompt;
+        self
+    }
 }
 
 /// LLM operation currently evaluated by guardrails.
@@ -1,7 +1,13 @@
+mod moderation;
 mod prompt_injection;
 mod regex_pii_redaction;
 mod toxicity;
 
+#[cfg(all(feature = "openai", not(target_arch = "wasm32")))]
+pub use moderation::OpenAIModerationBackend;
+pub use moderation::{
+    LlamaGuardBackend, ModerationAction, ModerationBackend, ModerationGuard, ModerationScores,
+};
 pub use prompt_injection::PromptInjectionGuard;
 pub use regex_pii_redaction::RegexPiiRedactionGuard;
 pub use toxicity::ToxicityGuard;
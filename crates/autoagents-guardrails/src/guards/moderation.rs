@@ -0,0 +1,601 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use async_trait::async_trait;
+use autoagents_llm::{
+    LLMProvider,
+    chat::{ChatMessage, ChatRole},
+};
+use serde_json::Value;
+
+use crate::{
+    guard::{
+        GuardContext, GuardDecision, GuardError, GuardViolation, GuardedInput, GuardedOutput,
+        InputGuard, OutputGuard,
+    },
+    policy::{GuardCategory, GuardSeverity},
+};
+
+/// Per-category scores in `0.0..=1.0`, keyed by the backend's category names.
+pub type ModerationScores = BTreeMap<String, f64>;
+
+/// Service that scores text for moderation categories.
+#[async_trait]
+pub trait ModerationBackend: Send + Sync + 'static {
+    /// Stable identifier used for diagnostics.
+    fn name(&self) -> &'static str;
+
+    /// Score `text`; categories missing from the result count as `0.0`.
+    async fn score(&self, text: &str) -> Result<ModerationScores, GuardError>;
+
+    /// Score `response` as the answer to `prompt`.
+    ///
+    /// Defaults to scoring the response alone; backends that grade replies
+    /// in the context of the conversation override it.
+    async fn score_response(
+        &self,
+        prompt: &str,
+        response: &str,
+    ) -> Result<ModerationScores, GuardError> {
+        let _ = prompt;
+        self.score(response).await
+    }
+}
+
+/// What to do when a category reaches its threshold.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ModerationAction {
+    /// Report a violation and let the enforcement policy handle it.
+    Block,
+    /// Log the hit and let the payload through.
+    Flag,
+}
+
+/// Guard that scores inputs and outputs with a [`ModerationBackend`].
+///
+/// Every category is checked against its own threshold, or the default
+/// threshold (`0.5`, block) when none was set. Blocked hits are rejected with
+/// the highest-scoring category; flagged hits are only logged. Input guards
+/// score the latest user message, output guards the response text as the
+/// reply to the request's prompt when one is known.
+#[derive(Clone)]
+pub struct ModerationGuard {
+    backend: Arc<dyn ModerationBackend>,
+    thresholds: BTreeMap<String, (f64, ModerationAction)>,
+    default_threshold: Option<(f64, ModerationAction)>,
+}
+
+impl ModerationGuard {
+    pub fn new<B: ModerationBackend>(backend: B) -> Self {
+        Self::from_arc(Arc::new(backend))
+    }
+
+    pub fn from_arc(backend: Arc<dyn ModerationBackend>) -> Self {
+        Self {
+            backend,
+            thresholds: BTreeMap::new(),
+            default_threshold: Some((0.5, ModerationAction::Block)),
+        }
+    }
+
+    /// Set the threshold and action for one category.
+    pub fn threshold(
+        mut self,
+        category: impl Into<String>,
+        threshold: f64,
+        action: ModerationAction,
+    ) -> Self {
+        self.thresholds.insert(category.into(), (threshold, action));
+        self
+    }
+
+    /// Set the threshold and action for categories without their own threshold.
+    pub fn default_threshold(mut self, threshold: f64, action: ModerationAction) -> Self {
+        self.default_threshold = Some((threshold, action));
+        self
+    }
+
+    /// Ignore categories without their own threshold.
+    pub fn configured_categories_only(mut self) -> Self {
+        self.default_threshold = None;
+        self
+    }
+
+    async fn evaluate(
+        &self,
+        text: &str,
+        prompt: Option<&str>,
+        context: &GuardContext,
+    ) -> Result<GuardDecision, GuardError> {
+        if text.trim().is_empty() {
+            return Ok(GuardDecision::Pass);
+        }
+
+        let scores = match prompt.filter(|prompt| !prompt.trim().is_empty()) {
+            Some(prompt) => self.backend.score_response(prompt, text).await?,
+            None => self.backend.score(text).await?,
+        };
+        let mut blocked: Option<(&str, f64)> = None;
+        let mut flagged = Vec::new();
+        for (category, score) in &scores {
+            let Some((threshold, action)) = self
+                .thresholds
+                .get(category)
+                .copied()
+                .or(self.default_threshold)
+            else {
+                continue;
+            };
+            if *score < threshold {
+                continue;
+            }
+            match action {
+                ModerationAction::Block => {
+                    if blocked.is_none_or(|(_, best)| *score > best) {
+                        blocked = Some((category, *score));
+                    }
+                }
+                ModerationAction::Flag => flagged.push(category.as_str()),
+            }
+        }
+
+        if !flagged.is_empty() {
+            log::warn!(
+                "moderation flagged content: request_id={}, op={}, backend={}, categories={}",
+                context.request_id,
+                context.operation,
+                self.backend.name(),
+                flagged.join(","),
+            );
+        }
+
+        let Some((category, score)) = blocked else {
+            return Ok(GuardDecision::Pass);
+        };
+        Ok(GuardDecision::Reject(
+            GuardViolation::new(
+                format!("moderation_{category}"),
+                GuardCategory::Custom(category.to_string()),
+                severity_for(score),
+                format!("{} scored {category} at {score:.2}", self.backend.name()),
+            )
+            .with_metadata(serde_json::json!({
+                "backend": self.backend.name(),
+                "scores": scores,
+                "flagged": flagged,
+            })),
+        ))
+    }
+}
+
+fn severity_for(score: f64) -> GuardSeverity {
+    match score {
+        s if s >= 0.9 => GuardSeverity::Critical,
+        s if s >= 0.7 => GuardSeverity::High,
+        s if s >= 0.4 => GuardSeverity::Medium,
+        _ => GuardSeverity::Low,
+    }
+}
+
+#[async_trait]
+impl InputGuard for ModerationGuard {
+    fn name(&self) -> &'static str {
+        "moderation"
+    }
+
+    async fn inspect(
+        &self,
+        input: &mut GuardedInput,
+        context: &GuardContext,
+    ) -> Result<GuardDecision, GuardError> {
+        let text = match input {
+            GuardedInput::Chat(chat) => chat
+                .messages
+                .iter()
+                .rev()
+                .find(|message| message.role == ChatRole::User)
+                .map(|message| message.content.clone())
+                .unwrap_or_default(),
+            GuardedInput::Completion(completion) => completion.request.prompt.clone(),
+            GuardedInput::WebSearch(web) => web.input.clone(),
+        };
+        self.evaluate(&text, None, context).await
+    }
+}
+
+#[async_trait]
+impl OutputGuard for ModerationGuard {
+    fn name(&self) -> &'static str {
+        "moderation"
+    }
+
+    async fn inspect(
+        &self,
+        output: &mut GuardedOutput,
+        context: &GuardContext,
+    ) -> Result<GuardDecision, GuardError> {
+        let text = match output {
+            GuardedOutput::Chat(chat) => chat.text.clone().unwrap_or_default(),
+            GuardedOutput::Completion(completion) => completion.text.clone(),
+        };
+        self.evaluate(&text, context.prompt.as_deref(), context)
+            .await
+    }
+}
+
+/// Llama Guard hazard categories, indexed by their `S<n>` code.
+const LLAMA_GUARD_CATEGORIES: &[&str] = &[
+    "violent_crimes",
+    "non_violent_crimes",
+    "sex_related_crimes",
+    "child_sexual_exploitation",
+    "defamation",
+    "specialized_advice",
+    "privacy",
+    "intellectual_property",
+    "indiscriminate_weapons",
+    "hate",
+    "suicide_self_harm",
+    "sexual_content",
+    "elections",
+    "code_interpreter_abuse",
+];
+
+/// Category reported for an `unsafe` verdict that names no `S<n>` code.
+pub const LLAMA_GUARD_UNSPECIFIED_CATEGORY: &str = "unspecified";
+
+/// Moderation backend running a Llama Guard model through any LLM provider,
+/// e.g. a local llama.cpp provider loaded with a Llama Guard GGUF.
+///
+/// The model's chat template builds the classification prompt; the verdict
+/// (`safe`, or `unsafe` followed by `S<n>` codes) maps each reported category
+/// to a score of `1.0`, and a bare `unsafe` to
+/// [`LLAMA_GUARD_UNSPECIFIED_CATEGORY`]. Responses are sent as the assistant
+/// turn after the user's prompt, so the model grades them as agent replies.
+#[derive(Clone)]
+pub struct LlamaGuardBackend {
+    llm: Arc<dyn LLMProvider>,
+}
+
+impl LlamaGuardBackend {
+    pub fn new(llm: Arc<dyn LLMProvider>) -> Self {
+        Self { llm }
+    }
+}
+
+#[async_trait]
+impl ModerationBackend for LlamaGuardBackend {
+    fn name(&self) -> &'static str {
+        "llama-guard"
+    }
+
+    async fn score(&self, text: &str) -> Result<ModerationScores, GuardError> {
+        self.classify(&[ChatMessage::user().content(text).build()])
+            .await
+    }
+
+    async fn score_response(
+        &self,
+        prompt: &str,
+        response: &str,
+    ) -> Result<ModerationScores, GuardError> {
+        self.classify(&llama_guard_response_messages(prompt, response))
+            .await
+    }
+}
+
+impl LlamaGuardBackend {
+    async fn classify(&self, messages: &[ChatMessage]) -> Result<ModerationScores, GuardError> {
+        let response = self
+            .llm
+            .chat(messages, None)
+            .await
+            .map_err(|err| GuardError::new(format!("llama guard request failed: {err}")))?;
+        parse_llama_guard_verdict(&response.text().unwrap_or_default())
+    }
+}
+
+fn llama_guard_response_messages(prompt: &str, response: &str) -> [ChatMessage; 2] {
+    [
+        ChatMessage::user().content(prompt).build(),
+        ChatMessage::assistant().content(response).build(),
+    ]
+}
+
+fn parse_llama_guard_verdict(verdict: &str) -> Result<ModerationScores, GuardError> {
+    let mut lines = verdict.trim().lines().map(str::trim);
+    match lines.next().map(str::to_lowercase).as_deref() {
+        Some("safe") => Ok(ModerationScores::new()),
+        Some("unsafe") => {
+            let mut scores: ModerationScores = lines
+                .flat_map(|line| line.split(','))
+                .map(str::trim)
+                .filter(|code| !code.is_empty())
+                .map(|code| {
+                    let category = code
+                        .strip_prefix('S')
+                        .and_then(|index| index.parse::<usize>().ok())
+                        .and_then(|index| LLAMA_GUARD_CATEGORIES.get(index.wrapping_sub(1)))
+                        .map_or_else(|| code.to_string(), |name| name.to_string());
+                    (category, 1.0)
+                })
+                .collect();
+            if scores.is_empty() {
+                scores.insert(LLAMA_GUARD_UNSPECIFIED_CATEGORY.to_string(), 1.0);
+            }
+            Ok(scores)
+        }
+        _ => Err(GuardError::new(format!(
+            "unexpected llama guard verdict: {verdict}"
+        ))),
+    }
+}
+
+/// Moderation backend for the OpenAI moderation endpoint.
+#[cfg(all(feature = "openai", not(target_arch = "wasm32")))]
+#[derive(Debug, Clone)]
+pub struct OpenAIModerationBackend {
+    client: reqwest::Client,
+    api_key: String,
+    model: String,
+    base_url: String,
+}
+
+#[cfg(all(feature = "openai", not(target_arch = "wasm32")))]
+impl OpenAIModerationBackend {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key: api_key.into(),
+            model: "omni-moderation-latest".to_string(),
+            base_url: "https://api.openai.com/v1".to_string(),
+        }
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+}
+
+#[cfg(all(feature = "openai", not(target_arch = "wasm32")))]
+#[async_trait]
+impl ModerationBackend for OpenAIModerationBackend {
+    fn name(&self) -> &'static str {
+        "openai-moderation"
+    }
+
+    async fn score(&self, text: &str) -> Result<ModerationScores, GuardError> {
+        let url = format!("{}/moderations", self.base_url.trim_end_matches('/'));
+        let response = self
+            .client
+            .post(url)
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({ "model": self.model, "input": text }))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| GuardError::new(format!("moderation request failed: {err}")))?;
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|err| GuardError::new(format!("invalid moderation response: {err}")))?;
+        parse_openai_scores(&body)
+    }
+}
+
+#[cfg_attr(
+    not(all(feature = "openai", not(target_arch = "wasm32"))),
+    allow(dead_code)
+)]
+fn parse_openai_scores(body: &Value) -> Result<ModerationScores, GuardError> {
+    let scores = body
+        .pointer("/results/0/category_scores")
+        .and_then(Value::as_object)
+        .ok_or_else(|| GuardError::new("moderation response has no category scores"))?;
+    Ok(scores
+        .iter()
+        .filter_map(|(category, score)| Some((category.clone(), score.as_f64()?)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::guard::{CompletionGuardOutput, GuardOperation};
+
+    use super::*;
+
+    struct FixedScores(Vec<(&'static str, f64)>);
+
+    #[async_trait]
+    impl ModerationBackend for FixedScores {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        async fn score(&self, _text: &str) -> Result<ModerationScores, GuardError> {
+            Ok(self
+                .0
+                .iter()
+                .map(|(category, score)| (category.to_string(), *score))
+                .collect())
+        }
+    }
+
+    async fn inspect_output(guard: &ModerationGuard, text: &str) -> GuardDecision {
+        let mut output = GuardedOutput::Completion(CompletionGuardOutput {
+            text: text.to_string(),
+        });
+        OutputGuard::inspect(
+            guard,
+            &mut output,
+            &GuardContext::new(GuardOperation::Complete),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn blocks_highest_category_over_threshold() {
+        let guard = ModerationGuard::new(FixedScores(vec![
+            ("hate", 0.6),
+            ("violence", 0.95),
+            ("sexual", 0.1),
+        ]));
+
+        let GuardDecision::Reject(violation) = inspect_output(&guard, "text").await else {
+            panic!("expected a rejection");
+        };
+        assert_eq!(violation.rule_id, "moderation_violence");
+        assert_eq!(violation.severity, GuardSeverity::Critical);
+    }
+
+    #[tokio::test]
+    async fn per_category_thresholds_and_flags() {
+        let guard = ModerationGuard::new(FixedScores(vec![("hate", 0.6), ("violence", 0.3)]))
+            .threshold("hate", 0.8, ModerationAction::Block)
+            .threshold("violence", 0.2, ModerationAction::Flag);
+        assert!(matches!(
+            inspect_output(&guard, "text").await,
+            GuardDecision::Pass
+        ));
+
+        let guard = ModerationGuard::new(FixedScores(vec![("hate", 0.99)]))
+            .threshold("violence", 0.2, ModerationAction::Block)
+            .configured_categories_only();
+        assert!(matches!(
+            inspect_output(&guard, "text").await,
+            GuardDecision::Pass
+        ));
+    }
+
+    #[tokio::test]
+    async fn scores_latest_user_message() {
+        struct RejectWord;
+
+        #[async_trait]
+        impl ModerationBackend for RejectWord {
+            fn name(&self) -> &'static str {
+                "reject-word"
+            }
+
+            async fn score(&self, text: &str) -> Result<ModerationScores, GuardError> {
+                let score = if text.contains("bad") { 1.0 } else { 0.0 };
+                Ok(ModerationScores::from([("hate".to_string(), score)]))
+            }
+        }
+
+        let guard = ModerationGuard::new(RejectWord);
+        let mut input = GuardedInput::Chat(crate::guard::ChatGuardInput {
+            messages: vec![
+                ChatMessage::user().content("bad earlier turn").build(),
+                ChatMessage::assistant().content("bad answer").build(),
+                ChatMessage::user().content("fine question").build(),
+            ],
+            tools: None,
+            json_schema: None,
+        });
+        let decision =
+            InputGuard::inspect(&guard, &mut input, &GuardContext::new(GuardOperation::Chat))
+                .await
+                .unwrap();
+        assert!(matches!(decision, GuardDecision::Pass));
+    }
+
+    #[test]
+    fn parses_llama_guard_verdicts() {
+        assert!(parse_llama_guard_verdict("safe").unwrap().is_empty());
+
+        let scores = parse_llama_guard_verdict("unsafe\nS1,S10\n").unwrap();
+        assert_eq!(scores.get("violent_crimes"), Some(&1.0));
+        assert_eq!(scores.get("hate"), Some(&1.0));
+
+        assert!(parse_llama_guard_verdict("I cannot answer").is_err());
+    }
+
+    #[tokio::test]
+    async fn bare_unsafe_verdict_is_blocked() {
+        struct BareUnsafe;
+
+        #[async_trait]
+        impl ModerationBackend for BareUnsafe {
+            fn name(&self) -> &'static str {
+                "bare-unsafe"
+            }
+
+            async fn score(&self, _text: &str) -> Result<ModerationScores, GuardError> {
+                parse_llama_guard_verdict("unsafe")
+            }
+        }
+
+        let GuardDecision::Reject(violation) =
+            inspect_output(&ModerationGuard::new(BareUnsafe), "text").await
+        else {
+            panic!("expected a rejection");
+        };
+        assert_eq!(violation.rule_id, "moderation_unspecified");
+    }
+
+    #[tokio::test]
+    async fn output_is_scored_as_reply_to_prompt() {
+        struct RecordPrompt(std::sync::Mutex<Option<String>>);
+
+        #[async_trait]
+        impl ModerationBackend for RecordPrompt {
+            fn name(&self) -> &'static str {
+                "record-prompt"
+            }
+
+            async fn score(&self, _text: &str) -> Result<ModerationScores, GuardError> {
+                Ok(ModerationScores::new())
+            }
+
+            async fn score_response(
+                &self,
+                prompt: &str,
+                _response: &str,
+            ) -> Result<ModerationScores, GuardError> {
+                *self.0.lock().unwrap() = Some(prompt.to_string());
+                Ok(ModerationScores::new())
+            }
+        }
+
+        let backend = Arc::new(RecordPrompt(Default::default()));
+        let guard = ModerationGuard::from_arc(backend.clone());
+        let mut output = GuardedOutput::Completion(CompletionGuardOutput {
+            text: "answer".to_string(),
+        });
+        let context =
+            GuardContext::new(GuardOperation::Complete).with_prompt(Some("question".to_string()));
+        OutputGuard::inspect(&guard, &mut output, &context)
+            .await
+            .unwrap();
+        assert_eq!(backend.0.lock().unwrap().as_deref(), Some("question"));
+
+        let [user, assistant] = llama_guard_response_messages("question", "answer");
+        assert_eq!(
+            (user.role, user.content.as_str()),
+            (ChatRole::User, "question")
+        );
+        assert_eq!(
+            (assistant.role, assistant.content.as_str()),
+            (ChatRole::Assistant, "answer")
+        );
+    }
+
+    #[test]
+    fn parses_openai_category_scores() {
+        let body = serde_json::json!({
+            "results": [{"flagged": true, "category_scores": {"hate": 0.7, "self-harm": 0.01}}]
+        });
+        let scores = parse_openai_scores(&body).unwrap();
+        assert_eq!(scores.get("hate"), Some(&0.7));
+        assert_eq!(scores.len(), 2);
+
+        assert!(parse_openai_scores(&serde_json::json!({})).is_err());
+    }
+}
@@ -1,18 +1,12 @@
 use async_trait::async_trait;
-use once_cell::sync::Lazy;
-use regex::Regex;
+use autoagents_core::pii::{PiiKind, redact_pii};
+use std::borrow::Cow;
 
 use crate::guard::{GuardContext, GuardDecision, GuardError, GuardedInput, InputGuard};
 
-static EMAIL_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"(?i)\b[a-z0-9._%+\-]+@[a-z0-9.\-]+\.[a-z]{2,}\b").unwrap());
-static PHONE_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"\b(?:\+?1[-.\s]?)?(?:\(?\d{3}\)?[-.\s]?)\d{3}[-.\s]?\d{4}\b").unwrap()
-});
-static SSN_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b\d{3}-\d{2}-\d{4}\b").unwrap());
-static CARD_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(?:\d[ -]*?){13,19}\b").unwrap());
-
 /// Input guard that redacts common PII patterns using regular expressions.
+///
+/// Matches the shared `autoagents_core::pii::PII_PATTERNS`.
 #[derive(Debug, Clone)]
 pub struct RegexPiiRedactionGuard {
     pub email_replacement: String,
@@ -24,44 +18,26 @@ pub struct RegexPiiRedactionGuard {
 impl Default for RegexPiiRedactionGuard {
     fn default() -> Self {
         Self {
-            email_replacement: "[redacted:email]".to_string(),
-            phone_replacement: "[redacted:phone]".to_string(),
-            ssn_replacement: "[redacted:ssn]".to_string(),
-            card_replacement: "[redacted:card]".to_string(),
+            email_replacement: PiiKind::Email.default_replacement().to_string(),
+            phone_replacement: PiiKind::Phone.default_replacement().to_string(),
+            ssn_replacement: PiiKind::Ssn.default_replacement().to_string(),
+            card_replacement: PiiKind::Card.default_replacement().to_string(),
         }
     }
 }
 
 impl RegexPiiRedactionGuard {
     fn redact_text(&self, text: &str) -> (String, bool) {
-        let mut out = text.to_string();
-        let mut changed = false;
-
-        let replaced = EMAIL_RE.replace_all(&out, self.email_replacement.as_str());
-        if replaced != out {
-            changed = true;
-            out = replaced.into_owned();
-        }
-
-        let replaced = PHONE_RE.replace_all(&out, self.phone_replacement.as_str());
-        if replaced != out {
-            changed = true;
-            out = replaced.into_owned();
-        }
-
-        let replaced = SSN_RE.replace_all(&out, self.ssn_replacement.as_str());
-        if replaced != out {
-            changed = true;
-            out = replaced.into_owned();
-        }
-
-        let replaced = CARD_RE.replace_all(&out, self.card_replacement.as_str());
-        if replaced != out {
-            changed = true;
-            out = replaced.into_owned();
+        let redacted = redact_pii(text, |kind| match kind {
+            PiiKind::Email => self.email_replacement.as_str(),
+            PiiKind::Phone => self.phone_replacement.as_str(),
+            PiiKind::Ssn => self.ssn_replacement.as_str(),
+            PiiKind::Card => self.card_replacement.as_str(),
+        });
+        match redacted {
+            Cow::Borrowed(_) => (text.to_string(), false),
+            Cow::Owned(out) => (out, true),
         }
-
-        (out, changed)
     }
 }
 
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use autoagents_llm::{
     LLMProvider,
     chat::{
-        ChatMessage, ChatProvider, ChatResponse, StreamChunk, StreamResponse,
+        ChatMessage, ChatProvider, ChatResponse, ChatRole, StreamChunk, StreamResponse,
         StructuredOutputFormat, Tool,
     },
     completion::{CompletionProvider, CompletionRequest, CompletionResponse},
@@ -23,6 +23,14 @@ use crate::{
     stream::{StructGuardedStream, TextGuardedStream, ToolGuardedStream},
 };
 
+fn latest_user_text(messages: &[ChatMessage]) -> Option<String> {
+    messages
+        .iter()
+        .rev()
+        .find(|message| message.role == ChatRole::User)
+        .map(|message| message.content.clone())
+}
+
 pub(crate) struct GuardedProvider {
     inner: Arc<dyn LLMProvider>,
     engine: Arc<GuardrailsEngine>,
@@ -132,7 +140,7 @@ impl ChatProvider for GuardedProvider {
         } else {
             GuardOperation::Chat
         };
-        let context = GuardContext::new(operation);
+        let context = GuardContext::new(operation).with_prompt(latest_user_text(messages));
 
         let response = if self.engine.has_input_guards() {
             let mut input = GuardedInput::Chat(ChatGuardInput {
@@ -161,7 +169,8 @@ impl ChatProvider for GuardedProvider {
     }
 
     async fn chat_with_web_search(&self, input: String) -> Result<Box<dyn ChatResponse>, LLMError> {
-        let context = GuardContext::new(GuardOperation::ChatWithWebSearch);
+        let context =
+            GuardContext::new(GuardOperation::ChatWithWebSearch).with_prompt(Some(input.clone()));
 
         let response = if self.engine.has_input_guards() {
             let mut guarded = GuardedInput::WebSearch(WebSearchGuardInput { input });
@@ -186,7 +195,8 @@ impl ChatProvider for GuardedProvider {
         messages: &[ChatMessage],
         json_schema: Option<StructuredOutputFormat>,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<String, LLMError>> + Send>>, LLMError> {
-        let context = GuardContext::new(GuardOperation::ChatStream);
+        let context =
+            GuardContext::new(GuardOperation::ChatStream).with_prompt(latest_user_text(messages));
 
         let stream = if self.engine.has_input_guards() {
             let mut input = GuardedInput::Chat(ChatGuardInput {
@@ -227,7 +237,8 @@ impl ChatProvider for GuardedProvider {
         json_schema: Option<StructuredOutputFormat>,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamResponse, LLMError>> + Send>>, LLMError>
     {
-        let context = GuardContext::new(GuardOperation::ChatStreamStruct);
+        let context = GuardContext::new(GuardOperation::ChatStreamStruct)
+            .with_prompt(latest_user_text(messages));
 
         let stream = if self.engine.has_input_guards() {
             let mut input = GuardedInput::Chat(ChatGuardInput {
@@ -269,7 +280,8 @@ impl ChatProvider for GuardedProvider {
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk, LLMError>> + Send>>, LLMError> {
-        let context = GuardContext::new(GuardOperation::ChatStreamWithTools);
+        let context = GuardContext::new(GuardOperation::ChatStreamWithTools)
+            .with_prompt(latest_user_text(messages));
 
         let stream = if self.engine.has_input_guards() {
             let mut input = GuardedInput::Chat(ChatGuardInput {
@@ -313,7 +325,8 @@ impl CompletionProvider for GuardedProvider {
         req: &CompletionRequest,
         json_schema: Option<StructuredOutputFormat>,
     ) -> Result<CompletionResponse, LLMError> {
-        let context = GuardContext::new(GuardOperation::Complete);
+        let context =
+            GuardContext::new(GuardOperation::Complete).with_prompt(Some(req.prompt.clone()));
 
         let response = if self.engine.has_input_guards() {
             let mut input = GuardedInput::Completion(CompletionGuardInput {
@@ -0,0 +1,144 @@
+//! IndexedDB access through `idb`
+
+use std::future::Future;
+
+use async_trait::async_trait;
+use autoagents_core::vector_store::VectorStoreError;
+use futures::channel::oneshot;
+use idb::builder::{DatabaseBuilder, ObjectStoreBuilder};
+use idb::{Database, ObjectStore, TransactionMode};
+use wasm_bindgen::JsValue;
+
+use crate::{OBJECT_STORE, RecordStore};
+
+/// Bumped when the object stores of the database change
+const DATABASE_VERSION: u32 = 1;
+
+/// Records in the object store [`OBJECT_STORE`] of one database
+pub(crate) struct IdbRecords {
+    database: String,
+}
+
+impl IdbRecords {
+    pub(crate) fn new(database: &str) -> Self {
+        Self {
+            database: database.to_string(),
+        }
+    }
+}
+
+/// An IndexedDB error, rendered while still on the browser task
+struct IdbFailure(String);
+
+impl From<idb::Error> for IdbFailure {
+    fn from(err: idb::Error) -> Self {
+        Self(err.to_string())
+    }
+}
+
+fn datastore_error(message: String) -> VectorStoreError {
+    VectorStoreError::DatastoreError(message.into())
+}
+
+async fn open(name: &str) -> Result<Database, idb::Error> {
+    DatabaseBuilder::new(name)
+        .version(DATABASE_VERSION)
+        .add_object_store(ObjectStoreBuilder::new(OBJECT_STORE))
+        .build()
+        .await
+}
+
+/// Run `work` on the object store in one transaction, then close the database
+async fn transact<T>(
+    name: &str,
+    mode: TransactionMode,
+    work: impl AsyncFnOnce(&ObjectStore) -> Result<T, IdbFailure>,
+) -> Result<T, IdbFailure> {
+    let database = open(name).await?;
+    let result: Result<T, IdbFailure> = async {
+        let transaction = database.transaction(&[OBJECT_STORE], mode)?;
+        let value = work(&transaction.object_store(OBJECT_STORE)?).await?;
+        if transaction.commit()?.await?.is_aborted() {
+            return Err(IdbFailure("IndexedDB transaction was aborted".to_string()));
+        }
+        Ok(value)
+    }
+    .await;
+    database.close();
+    result
+}
+
+/// Run `task` on the browser event loop
+///
+/// IndexedDB handles are JS objects and not `Send`, while store futures
+/// must be, so the work runs as a local task and only its result crosses
+/// back.
+fn run<T, F>(task: F) -> impl Future<Output = Result<T, VectorStoreError>> + Send
+where
+    T: Send + 'static,
+    F: Future<Output = Result<T, IdbFailure>> + 'static,
+{
+    let (sender, receiver) = oneshot::channel();
+    wasm_bindgen_futures::spawn_local(async move {
+        let _ = sender.send(task.await.map_err(|failure| failure.0));
+    });
+    async move {
+        receiver
+            .await
+            .map_err(|_| datastore_error("IndexedDB task was dropped".to_string()))?
+            .map_err(datastore_error)
+    }
+}
+
+#[async_trait]
+impl RecordStore for IdbRecords {
+    async fn put(&self, records: Vec<(String, String)>) -> Result<(), VectorStoreError> {
+        let database = self.database.clone();
+        run(async move {
+            transact(&database, TransactionMode::ReadWrite, async |store| {
+                for (id, record) in records {
+                    store
+                        .put(&JsValue::from_str(&record), Some(&JsValue::from_str(&id)))?
+                        .await?;
+                }
+                Ok(())
+            })
+            .await
+        })
+        .await
+    }
+
+    async fn delete(&self, ids: Vec<String>) -> Result<(), VectorStoreError> {
+        let database = self.database.clone();
+        run(async move {
+            transact(&database, TransactionMode::ReadWrite, async |store| {
+                for id in ids {
+                    store.delete(JsValue::from_str(&id))?.await?;
+                }
+                Ok(())
+            })
+            .await
+        })
+        .await
+    }
+
+    async fn load(&self) -> Result<Vec<String>, VectorStoreError> {
+        let database = self.database.clone();
+        run(async move {
+            transact(&database, TransactionMode::ReadOnly, async |store| {
+                store
+                    .get_all(None, None)?
+                    .await?
+                    .into_iter()
+                    .map(|value| {
+                        value
+                            .as_string()
+                            .ok_or_else(|| IdbFailure("record is not a string".to_string()))
+                    })
+                    .collect()
+            })
+            .await
+        })
+        .await
+    }
+}
@@ -0,0 +1,379 @@
+//! IndexedDB vector store for AutoAgents
+//!
+//! For agents running in the browser. Documents and their embeddings are
+//! written to an IndexedDB object store, so they survive page reloads and
+//! work offline, and are searched in an in-memory flat index that is rebuilt
+//! from the database when the store is opened. Reopening a store never calls
+//! the embedding provider for documents already saved.
+//!
+//! [`IndexedDbVectorStore::open`] is available on `wasm32-unknown-unknown`;
+//! the crate builds elsewhere so shared agent code can name the type.
+
+#[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
+mod browser;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use autoagents_core::embeddings::{Embed, Embedding, SharedEmbeddingProvider};
+use autoagents_core::one_or_many::OneOrMany;
+use autoagents_core::vector_store::in_memory_store::InMemoryVectorStore;
+use autoagents_core::vector_store::request::Filter;
+use autoagents_core::vector_store::{
+    NamedVectorDocument, PreparedDocument, PreparedNamedVectorDocument, ScrollPage,
+    VectorSearchRequest, VectorStoreError, VectorStoreIndex, embed_documents,
+    embed_named_documents, normalize_id,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Database used by [`IndexedDbVectorStore::open_default`]
+pub const DEFAULT_DATABASE: &str = "autoagents-vectors";
+
+/// Object store holding the documents of a database
+pub const OBJECT_STORE: &str = "documents";
+
+/// Where [`IndexedDbVectorStore`] keeps its records, as JSON keyed by document id
+#[async_trait]
+trait RecordStore: Send + Sync {
+    /// Write `(id, record)` pairs, replacing existing ids, in one transaction
+    async fn put(&self, records: Vec<(String, String)>) -> Result<(), VectorStoreError>;
+
+    async fn delete(&self, ids: Vec<String>) -> Result<(), VectorStoreError>;
+
+    async fn load(&self) -> Result<Vec<String>, VectorStoreError>;
+}
+
+/// A document as saved in IndexedDB
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredRecord {
+    id: String,
+    raw: Value,
+    /// Embedded chunks of a text document
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    embeddings: Vec<Embedding>,
+    /// Vectors of a named-vector document
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    vectors: HashMap<String, Vec<f32>>,
+}
+
+impl StoredRecord {
+    fn entry(&self) -> Result<(String, String), VectorStoreError> {
+        Ok((self.id.clone(), serde_json::to_string(self)?))
+    }
+}
+
+/// Vector store persisting to a browser IndexedDB database
+///
+/// Writes go to IndexedDB first and reach the search index once they are
+/// committed. Searching, scrolling and counting only touch memory. Other
+/// tabs writing to the same database are not seen until the store is
+/// opened again.
+#[derive(Clone)]
+pub struct IndexedDbVectorStore {
+    index: InMemoryVectorStore,
+    provider: SharedEmbeddingProvider,
+    records: Arc<dyn RecordStore>,
+}
+
+impl IndexedDbVectorStore {
+    /// Open or create the database `database` and load its documents
+    #[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
+    pub async fn open(
+        database: &str,
+        provider: SharedEmbeddingProvider,
+    ) -> Result<Self, VectorStoreError> {
+        Self::with_records(Arc::new(browser::IdbRecords::new(database)), provider).await
+    }
+
+    /// [`open`](Self::open) on [`DEFAULT_DATABASE`]
+    #[cfg(all(target_arch = "wasm32", not(target_os = "wasi")))]
+    pub async fn open_default(provider: SharedEmbeddingProvider) -> Result<Self, VectorStoreError> {
+        Self::open(DEFAULT_DATABASE, provider).await
+    }
+
+    #[cfg_attr(
+        not(all(target_arch = "wasm32", not(target_os = "wasi"))),
+        allow(dead_code)
+    )]
+    async fn with_records(
+        records: Arc<dyn RecordStore>,
+        provider: SharedEmbeddingProvider,
+    ) -> Result<Self, VectorStoreError> {
+        let index = InMemoryVectorStore::new(provider.clone());
+        let mut documents = Vec::new();
+        let mut named = Vec::new();
+        for record in records.load().await? {
+            let record: StoredRecord = serde_json::from_str(&record)?;
+            if record.vectors.is_empty() {
+                documents.push(PreparedDocument {
+                    id: record.id,
+                    raw: record.raw,
+                    embeddings: OneOrMany::Many(record.embeddings),
+                });
+            } else {
+                named.push(PreparedNamedVectorDocument {
+                    id: record.id,
+                    raw: record.raw,
+                    vectors: record.vectors,
+                });
+            }
+        }
+        index.insert_prepared(documents);
+        index.insert_prepared_named(named);
+        Ok(Self {
+            index,
+            provider,
+            records,
+        })
+    }
+}
+
+#[async_trait]
+impl VectorStoreIndex for IndexedDbVectorStore {
+    type Filter = Filter<Value>;
+
+    async fn insert_documents<T>(&self, documents: Vec<T>) -> Result<(), VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+    {
+        let documents = documents
+            .into_iter()
+            .map(|doc| (normalize_id(None), doc))
+            .collect();
+        self.insert_documents_with_ids(documents).await
+    }
+
+    async fn insert_documents_with_ids<T>(
+        &self,
+        documents: Vec<(String, T)>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+    {
+        let documents = documents
+            .into_iter()
+            .map(|(id, doc)| (normalize_id(Some(id)), doc))
+            .collect();
+        let prepared = embed_documents(&self.provider, documents).await?;
+        let entries = prepared
+            .iter()
+            .map(|doc| {
+                StoredRecord {
+                    id: doc.id.clone(),
+                    raw: doc.raw.clone(),
+                    embeddings: doc.embeddings.iter().cloned().collect(),
+                    vectors: HashMap::new(),
+                }
+                .entry()
+            })
+            .collect::<Result<_, _>>()?;
+        self.records.put(entries).await?;
+        self.index.insert_prepared(prepared);
+        Ok(())
+    }
+
+    async fn top_n<T>(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        self.index.top_n(req).await
+    }
+
+    async fn top_n_ids(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        self.index.top_n_ids(req).await
+    }
+
+    async fn insert_documents_with_named_vectors<T>(
+        &self,
+        documents: Vec<NamedVectorDocument<T>>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: Serialize + Send + Sync + Clone,
+    {
+        let documents = documents
+            .into_iter()
+            .map(|doc| NamedVectorDocument {
+                id: normalize_id(Some(doc.id)),
+                raw: doc.raw,
+                vectors: doc.vectors,
+            })
+            .collect();
+        let prepared = embed_named_documents(&self.provider, documents).await?;
+        let entries = prepared
+            .iter()
+            .map(|doc| {
+                StoredRecord {
+                    id: doc.id.clone(),
+                    raw: doc.raw.clone(),
+                    embeddings: Vec::new(),
+                    vectors: doc.vectors.clone(),
+                }
+                .entry()
+            })
+            .collect::<Result<_, _>>()?;
+        self.records.put(entries).await?;
+        self.index.insert_prepared_named(prepared);
+        Ok(())
+    }
+
+    async fn delete_documents_by_ids(&self, ids: &[String]) -> Result<(), VectorStoreError> {
+        self.records.delete(ids.to_vec()).await?;
+        self.index.delete_documents_by_ids(ids).await
+    }
+
+    async fn scroll<T>(
+        &self,
+        filter: Option<Self::Filter>,
+        cursor: Option<String>,
+        limit: u64,
+    ) -> Result<ScrollPage<T>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        self.index.scroll(filter, cursor, limit).await
+    }
+
+    async fn count(&self, filter: Option<Self::Filter>) -> Result<u64, VectorStoreError> {
+        self.index.count(filter).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autoagents_core::document::Document;
+    use autoagents_core::testing::KeywordEmbeddingProvider;
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+
+    /// Records kept in a map, standing in for IndexedDB
+    #[derive(Default)]
+    struct MemoryRecords(Mutex<BTreeMap<String, String>>);
+
+    #[async_trait]
+    impl RecordStore for MemoryRecords {
+        async fn put(&self, records: Vec<(String, String)>) -> Result<(), VectorStoreError> {
+            self.0.lock().unwrap().extend(records);
+            Ok(())
+        }
+
+        async fn delete(&self, ids: Vec<String>) -> Result<(), VectorStoreError> {
+            let mut records = self.0.lock().unwrap();
+            for id in ids {
+                records.remove(&id);
+            }
+            Ok(())
+        }
+
+        async fn load(&self) -> Result<Vec<String>, VectorStoreError> {
+            Ok(self.0.lock().unwrap().values().cloned().collect())
+        }
+    }
+
+    fn provider() -> Arc<KeywordEmbeddingProvider> {
+        Arc::new(KeywordEmbeddingProvider::new(["rust", "python", "browser"]))
+    }
+
+    fn request(query: &str, samples: u64) -> VectorSearchRequest<Filter<Value>> {
+        VectorSearchRequest::builder()
+            .query(query)
+            .samples(samples)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reopen_restores_index_without_reembedding() {
+        let records = Arc::new(MemoryRecords::default());
+        let provider = provider();
+        let store = IndexedDbVectorStore::with_records(records.clone(), provider.clone())
+            .await
+            .unwrap();
+        store
+            .insert_documents_with_ids(vec![
+                ("a".to_string(), Document::new("rust ownership")),
+                ("b".to_string(), Document::new("python typing")),
+                ("c".to_string(), Document::new("rust in the browser")),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(provider.embedded(), 3);
+
+        let reopened = IndexedDbVectorStore::with_records(records, provider.clone())
+            .await
+            .unwrap();
+        assert_eq!(reopened.count(None).await.unwrap(), 3);
+        let results: Vec<(f64, String, Document)> =
+            reopened.top_n(request("browser", 1)).await.unwrap();
+        assert_eq!(results[0].1, "c");
+        assert_eq!(results[0].2.page_content, "rust in the browser");
+        // Only the query was embedded after reopening
+        assert_eq!(provider.embedded(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_named_vectors_survive_reopen() {
+        let records = Arc::new(MemoryRecords::default());
+        let provider = provider();
+        let store = IndexedDbVectorStore::with_records(records.clone(), provider.clone())
+            .await
+            .unwrap();
+        store
+            .insert_documents_with_named_vectors(vec![NamedVectorDocument {
+                id: "doc".to_string(),
+                raw: "raw".to_string(),
+                vectors: HashMap::from([("title".to_string(), "python".to_string())]),
+            }])
+            .await
+            .unwrap();
+
+        let reopened = IndexedDbVectorStore::with_records(records, provider)
+            .await
+            .unwrap();
+        let req = VectorSearchRequest::builder()
+            .query("python")
+            .samples(1)
+            .query_vector_name("title")
+            .build()
+            .unwrap();
+        let results: Vec<(f64, String, String)> = reopened.top_n(req).await.unwrap();
+        assert_eq!(results[0].1, "doc");
+        assert_eq!(results[0].2, "raw");
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_saved_records() {
+        let records = Arc::new(MemoryRecords::default());
+        let provider = provider();
+        let store = IndexedDbVectorStore::with_records(records.clone(), provider.clone())
+            .await
+            .unwrap();
+        store
+            .insert_documents_with_ids(vec![
+                ("a".to_string(), Document::new("rust")),
+                ("b".to_string(), Document::new("python")),
+            ])
+            .await
+            .unwrap();
+        store
+            .delete_documents_by_ids(&["a".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(store.count(None).await.unwrap(), 1);
+
+        let reopened = IndexedDbVectorStore::with_records(records, provider)
+            .await
+            .unwrap();
+        let page: ScrollPage<Document> = reopened.scroll(None, None, 10).await.unwrap();
+        let ids: Vec<&str> = page.documents.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, ["b"]);
+    }
+}
@@ -2089,6 +2089,17 @@ impl ChatProvider for LlamaCppProvider {
         self.chat_stream_with_tools_impl(messages, tools, json_schema, None)
             .await
     }
+
+    async fn chat_stream_with_tools_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk, LLMError>> + Send>>, LLMError> {
+        self.chat_stream_with_tools_impl(messages, tools, json_schema, sampling)
+            .await
+    }
 }
 
 fn prepare_messages_with_system(
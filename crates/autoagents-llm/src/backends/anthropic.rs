@@ -8,8 +8,8 @@ use crate::{
     FunctionCall, ToolCall,
     builder::{LLMBackend, LLMBuilder},
     chat::{
-        ChatMessage, ChatProvider, ChatResponse, ChatRole, MessageType, StreamChunk,
-        StructuredOutputFormat, Tool, ToolChoice, Usage,
+        ChatMessage, ChatProvider, ChatResponse, ChatRole, MessageType, SamplingOverrides,
+        StreamChunk, StructuredOutputFormat, Tool, ToolChoice, Usage,
     },
     completion::{CompletionProvider, CompletionRequest, CompletionResponse},
     config::resolve_request_timeout,
@@ -468,7 +468,9 @@ impl Anthropic {
         &'a self,
         messages: &'a [ChatMessage],
         json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
     ) -> Result<AnthropicCompleteRequest<'a>, LLMError> {
+        let sampling = self.sampling(sampling);
         let anthropic_messages = Self::convert_messages_to_anthropic(messages)?;
 
         let system_message = messages
@@ -479,11 +481,11 @@ impl Anthropic {
         Ok(AnthropicCompleteRequest {
             messages: anthropic_messages,
             model: &self.model,
-            max_tokens: Some(self.max_tokens),
-            temperature: Some(self.temperature),
+            max_tokens: sampling.max_tokens,
+            temperature: sampling.temperature,
             system: system_message,
             stream: Some(true),
-            top_p: self.top_p,
+            top_p: sampling.top_p,
             top_k: self.top_k,
             tools: None,
             tool_choice: None,
@@ -496,6 +498,18 @@ impl Anthropic {
         })
     }
 
+    /// The configured sampling with this call's `overrides` applied
+    fn sampling(&self, overrides: Option<&SamplingOverrides>) -> SamplingOverrides {
+        SamplingOverrides::resolve(
+            overrides,
+            SamplingOverrides {
+                temperature: Some(self.temperature),
+                top_p: self.top_p,
+                max_tokens: Some(self.max_tokens),
+            },
+        )
+    }
+
     /// Prepares Anthropic tools and tool_choice from the provided tools and instance configuration.
     ///
     /// Returns a tuple of (anthropic_tools, final_tool_choice) ready for the API request.
@@ -604,12 +618,26 @@ impl ChatProvider for Anthropic {
         messages: &[ChatMessage],
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
+    ) -> Result<Box<dyn ChatResponse>, LLMError> {
+        self.chat_with_tools_and_sampling(messages, tools, json_schema, None)
+            .await
+    }
+
+    /// Sends a chat request with tools, applying per-call sampling overrides
+    /// on top of the configured temperature, top_p and max_tokens.
+    async fn chat_with_tools_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
     ) -> Result<Box<dyn ChatResponse>, LLMError> {
         if self.api_key.is_empty() {
             return Err(LLMError::missing_api_key(
                 "Missing Anthropic API key".to_string(),
             ));
         }
+        let sampling = self.sampling(sampling);
 
         let anthropic_messages = Self::convert_messages_to_anthropic(messages)?;
         let (anthropic_tools, final_tool_choice) =
@@ -634,11 +662,11 @@ impl ChatProvider for Anthropic {
         let req_body = AnthropicCompleteRequest {
             messages: anthropic_messages,
             model: &self.model,
-            max_tokens: Some(self.max_tokens),
-            temperature: Some(self.temperature),
+            max_tokens: sampling.max_tokens,
+            temperature: sampling.temperature,
             system: system_message,
             stream: Some(false),
-            top_p: self.top_p,
+            top_p: sampling.top_p,
             top_k: self.top_k,
             tools: anthropic_tools,
             tool_choice: final_tool_choice,
@@ -713,6 +741,18 @@ impl ChatProvider for Anthropic {
         messages: &[ChatMessage],
         json_schema: Option<StructuredOutputFormat>,
     ) -> Result<std::pin::Pin<Box<dyn Stream<Item = Result<String, LLMError>> + Send>>, LLMError>
+    {
+        self.chat_stream_and_sampling(messages, json_schema, None)
+            .await
+    }
+
+    /// Sends a streaming chat request with per-call sampling overrides.
+    async fn chat_stream_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
+    ) -> Result<std::pin::Pin<Box<dyn Stream<Item = Result<String, LLMError>> + Send>>, LLMError>
     {
         if self.api_key.is_empty() {
             return Err(LLMError::missing_api_key(
@@ -720,7 +760,7 @@ impl ChatProvider for Anthropic {
             ));
         }
 
-        let req_body = self.build_stream_request(messages, json_schema)?;
+        let req_body = self.build_stream_request(messages, json_schema, sampling)?;
 
         let request = self
             .client
@@ -756,12 +796,26 @@ impl ChatProvider for Anthropic {
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
     ) -> Result<std::pin::Pin<Box<dyn Stream<Item = Result<StreamChunk, LLMError>> + Send>>, LLMError>
+    {
+        self.chat_stream_with_tools_and_sampling(messages, tools, json_schema, None)
+            .await
+    }
+
+    /// Sends a streaming chat request with tools and per-call sampling overrides.
+    async fn chat_stream_with_tools_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
+    ) -> Result<std::pin::Pin<Box<dyn Stream<Item = Result<StreamChunk, LLMError>> + Send>>, LLMError>
     {
         if self.api_key.is_empty() {
             return Err(LLMError::missing_api_key(
                 "Missing Anthropic API key".to_string(),
             ));
         }
+        let sampling = self.sampling(sampling);
 
         let anthropic_messages = Self::convert_messages_to_anthropic(messages)?;
         let (anthropic_tools, final_tool_choice) =
@@ -775,11 +829,11 @@ impl ChatProvider for Anthropic {
         let req_body = AnthropicCompleteRequest {
             messages: anthropic_messages,
             model: &self.model,
-            max_tokens: Some(self.max_tokens),
-            temperature: Some(self.temperature),
+            max_tokens: sampling.max_tokens,
+            temperature: sampling.temperature,
             system: system_message,
             stream: Some(true),
-            top_p: self.top_p,
+            top_p: sampling.top_p,
             top_k: self.top_k,
             tools: anthropic_tools,
             tool_choice: final_tool_choice,
@@ -1783,7 +1837,7 @@ data: {"type": "ping"}
         }];
 
         let request = provider
-            .build_stream_request(&messages, None)
+            .build_stream_request(&messages, None, None)
             .expect("PDF should be accepted in Anthropic stream requests");
 
         assert_eq!(request.messages.len(), 1);
@@ -1793,6 +1847,38 @@ data: {"type": "ping"}
         assert_eq!(source.media_type, "application/pdf");
     }
 
+    #[test]
+    fn test_build_stream_request_applies_sampling_overrides() {
+        let provider = Anthropic::new(
+            "key",
+            Some("claude-test".to_string()),
+            Some(64),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let messages = [ChatMessage::user().content("hi").build()];
+
+        let request = provider
+            .build_stream_request(
+                &messages,
+                None,
+                Some(&SamplingOverrides::with_temperature(0.1)),
+            )
+            .unwrap();
+        assert_eq!(request.temperature, Some(0.1));
+        assert_eq!(request.max_tokens, Some(64));
+
+        let request = provider
+            .build_stream_request(&messages, None, None)
+            .unwrap();
+        assert_eq!(request.temperature, Some(0.7));
+    }
+
     #[test]
     fn test_prepare_tools_and_choice_and_constructor_cover_remaining_variants() {
         let tool = Tool {
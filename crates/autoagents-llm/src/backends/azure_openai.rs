@@ -18,7 +18,7 @@ use crate::{
     chat::Tool,
     chat::{ChatMessage, ChatProvider, ChatRole, MessageType, StructuredOutputFormat},
     completion::{CompletionProvider, CompletionRequest, CompletionResponse},
-    embedding::EmbeddingProvider,
+    embedding::{EmbeddingLimits, EmbeddingProvider},
     error::LLMError,
     models::ModelsProvider,
 };
@@ -550,6 +550,13 @@ impl crate::HasConfig for AzureOpenAI {
     type Config = crate::NoConfig;
 }
 
+/// Azure OpenAI embedding deployments accept at most 2048 inputs per request.
+#[cfg(feature = "azure_openai")]
+const AZURE_OPENAI_EMBEDDING_LIMITS: EmbeddingLimits = EmbeddingLimits {
+    max_inputs: Some(2048),
+    max_tokens: None,
+};
+
 #[cfg(feature = "azure_openai")]
 #[async_trait]
 impl EmbeddingProvider for AzureOpenAI {
@@ -594,6 +601,10 @@ impl EmbeddingProvider for AzureOpenAI {
         let embeddings = json_resp.data.into_iter().map(|d| d.embedding).collect();
         Ok(embeddings)
     }
+
+    fn embedding_limits(&self) -> EmbeddingLimits {
+        AZURE_OPENAI_EMBEDDING_LIMITS
+    }
 }
 
 #[async_trait]
@@ -4,8 +4,8 @@
 //! DeepSeek uses an OpenAI-compatible API, so we leverage the OpenAICompatibleProvider.
 
 use crate::chat::{
-    ChatMessage, ChatProvider, ChatResponse, StreamChunk, StreamResponse, StructuredOutputFormat,
-    Tool,
+    ChatMessage, ChatProvider, ChatResponse, SamplingOverrides, StreamChunk, StreamResponse,
+    StructuredOutputFormat, Tool,
 };
 use crate::providers::openai_compatible::{OpenAICompatibleProvider, OpenAIProviderConfig};
 use crate::{
@@ -171,6 +171,54 @@ impl ChatProvider for DeepSeek {
             .await
     }
 
+    async fn chat_with_tools_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
+    ) -> Result<Box<dyn ChatResponse>, LLMError> {
+        self.provider
+            .chat_with_tools_and_sampling(messages, tools, json_schema, sampling)
+            .await
+    }
+
+    async fn chat_stream_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<String, LLMError>> + Send>>, LLMError> {
+        self.provider
+            .chat_stream_and_sampling(messages, json_schema, sampling)
+            .await
+    }
+
+    async fn chat_stream_struct_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamResponse, LLMError>> + Send>>, LLMError>
+    {
+        self.provider
+            .chat_stream_struct_and_sampling(messages, tools, json_schema, sampling)
+            .await
+    }
+
+    async fn chat_stream_with_tools_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk, LLMError>> + Send>>, LLMError> {
+        self.provider
+            .chat_stream_with_tools_and_sampling(messages, tools, json_schema, sampling)
+            .await
+    }
+
     fn model(&self) -> &str {
         self.provider.model()
     }
@@ -49,3 +49,6 @@ pub mod openrouter;
 
 #[cfg(all(feature = "minimax", not(target_arch = "wasm32")))]
 pub mod minimax;
+
+#[cfg(all(feature = "stability", not(target_arch = "wasm32")))]
+pub mod stability;
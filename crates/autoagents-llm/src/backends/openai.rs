@@ -15,6 +15,10 @@ use crate::embedding::EmbeddingBuilder;
 use crate::http::ensure_success;
 #[cfg(wasi_http)]
 use crate::http::map_http_status_to_error;
+#[cfg(native)]
+use crate::image_generation::{
+    GeneratedImage, ImageGenerationBuilder, ImageGenerationProvider, ImageGenerationRequest,
+};
 #[cfg(any(native, wasi_http))]
 use crate::models::StandardModelListResponse;
 #[cfg(native)]
@@ -26,11 +30,11 @@ use crate::providers::openai_compatible::{OpenAICompatibleProvider, OpenAIProvid
 use crate::{
     FunctionCall, LLMProvider, ToolCall,
     chat::{
-        ChatMessage, ChatProvider, ChatResponse, ChatRole, MessageType, StructuredOutputFormat,
-        Tool, ToolChoice,
+        ChatMessage, ChatProvider, ChatResponse, ChatRole, MessageType, SamplingOverrides,
+        StructuredOutputFormat, Tool, ToolChoice,
     },
     completion::{CompletionProvider, CompletionRequest, CompletionResponse},
-    embedding::EmbeddingProvider,
+    embedding::{EmbeddingLimits, EmbeddingProvider},
     error::LLMError,
     models::{ModelListRequest, ModelListResponse, ModelsProvider},
 };
@@ -662,16 +666,27 @@ impl ChatProvider for OpenAI {
         messages: &[ChatMessage],
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
+    ) -> Result<Box<dyn ChatResponse>, LLMError> {
+        self.chat_with_tools_and_sampling(messages, tools, json_schema, None)
+            .await
+    }
+
+    async fn chat_with_tools_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
     ) -> Result<Box<dyn ChatResponse>, LLMError> {
         match self.api_mode {
             OpenAIApiMode::Responses => {
-                self.chat_with_tools_responses(messages, tools, json_schema)
+                self.chat_with_tools_responses(messages, tools, json_schema, sampling)
                     .await
             }
             OpenAIApiMode::ChatCompletions => {
                 #[cfg(native)]
                 {
-                    self.chat_with_tools_legacy(messages, tools, json_schema)
+                    self.chat_with_tools_legacy(messages, tools, json_schema, sampling)
                         .await
                 }
                 #[cfg(wasi_http)]
@@ -700,7 +715,20 @@ impl ChatProvider for OpenAI {
         messages: &[ChatMessage],
         json_schema: Option<StructuredOutputFormat>,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<String, LLMError>> + Send>>, LLMError> {
-        let struct_stream = self.chat_stream_struct(messages, None, json_schema).await?;
+        self.chat_stream_and_sampling(messages, json_schema, None)
+            .await
+    }
+
+    #[cfg(any(native, wasi_http))]
+    async fn chat_stream_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<String, LLMError>> + Send>>, LLMError> {
+        let struct_stream = self
+            .chat_stream_struct_and_sampling(messages, None, json_schema, sampling)
+            .await?;
         let content_stream = struct_stream.filter_map(|result| async move {
             match result {
                 Ok(stream_response) => {
@@ -733,6 +761,19 @@ impl ChatProvider for OpenAI {
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamResponse, LLMError>> + Send>>, LLMError>
+    {
+        self.chat_stream_struct_and_sampling(messages, tools, json_schema, None)
+            .await
+    }
+
+    #[cfg(any(native, wasi_http))]
+    async fn chat_stream_struct_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamResponse, LLMError>> + Send>>, LLMError>
     {
         match self.api_mode {
             OpenAIApiMode::Responses => {
@@ -740,18 +781,18 @@ impl ChatProvider for OpenAI {
                 // the `StreamChunk` -> `StreamResponse` projection is shared.
                 #[cfg(native)]
                 let chunk_stream = self
-                    .chat_stream_with_tools_responses(messages, tools, json_schema)
+                    .chat_stream_with_tools_responses(messages, tools, json_schema, sampling)
                     .await?;
                 #[cfg(wasi_http)]
                 let chunk_stream = self
-                    .chat_stream_with_tools_responses_wasi(messages, tools, json_schema)
+                    .chat_stream_with_tools_responses_wasi(messages, tools, json_schema, sampling)
                     .await?;
                 Ok(responses_chunk_stream_to_struct_stream(chunk_stream))
             }
             OpenAIApiMode::ChatCompletions => {
                 #[cfg(native)]
                 {
-                    self.chat_stream_struct_legacy(messages, tools, json_schema)
+                    self.chat_stream_struct_legacy(messages, tools, json_schema, sampling)
                         .await
                 }
                 #[cfg(wasi_http)]
@@ -775,25 +816,42 @@ impl ChatProvider for OpenAI {
         messages: &[ChatMessage],
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk, LLMError>> + Send>>, LLMError> {
+        self.chat_stream_with_tools_and_sampling(messages, tools, json_schema, None)
+            .await
+    }
+
+    #[cfg(any(native, wasi_http))]
+    async fn chat_stream_with_tools_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk, LLMError>> + Send>>, LLMError> {
         match self.api_mode {
             OpenAIApiMode::Responses => {
                 #[cfg(native)]
                 {
-                    self.chat_stream_with_tools_responses(messages, tools, json_schema)
+                    self.chat_stream_with_tools_responses(messages, tools, json_schema, sampling)
                         .await
                 }
                 #[cfg(wasi_http)]
                 {
-                    self.chat_stream_with_tools_responses_wasi(messages, tools, json_schema)
-                        .await
+                    self.chat_stream_with_tools_responses_wasi(
+                        messages,
+                        tools,
+                        json_schema,
+                        sampling,
+                    )
+                    .await
                 }
             }
             OpenAIApiMode::ChatCompletions => {
                 #[cfg(native)]
                 {
                     self.provider
-                        .chat_stream_with_tools(messages, tools, json_schema)
+                        .chat_stream_with_tools_and_sampling(messages, tools, json_schema, sampling)
                         .await
                 }
                 #[cfg(wasi_http)]
@@ -1051,7 +1109,9 @@ impl OpenAI {
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
         stream: bool,
+        sampling: Option<&SamplingOverrides>,
     ) -> Result<OpenAIResponsesRequest, LLMError> {
+        let sampling = self.provider.sampling(sampling);
         let (instructions, input) = self.build_responses_input_items(messages)?;
         let request_tools = self.build_responses_function_tools(tools);
         let tool_choice = self.resolve_responses_tool_choice_for_request(&request_tools);
@@ -1063,10 +1123,10 @@ impl OpenAI {
             model: self.provider.model.clone(),
             instructions,
             input,
-            max_output_tokens: self.provider.max_tokens,
-            temperature: self.provider.temperature,
+            max_output_tokens: sampling.max_tokens,
+            temperature: sampling.temperature,
             stream,
-            top_p: self.provider.top_p,
+            top_p: sampling.top_p,
             top_k: self.provider.top_k,
             tools: request_tools,
             tool_choice,
@@ -1339,8 +1399,9 @@ impl OpenAI {
         messages: &[ChatMessage],
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
     ) -> Result<Box<dyn ChatResponse>, LLMError> {
-        let body = self.build_responses_request(messages, tools, json_schema, false)?;
+        let body = self.build_responses_request(messages, tools, json_schema, false, sampling)?;
         let resp_text = self.send_responses_request_text(&body).await?;
         let json_resp: Result<OpenAIResponsesResponse, serde_json::Error> =
             serde_json::from_str(&resp_text);
@@ -1359,7 +1420,9 @@ impl OpenAI {
         messages: &[ChatMessage],
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
     ) -> Result<Box<dyn ChatResponse>, LLMError> {
+        let sampling = self.provider.sampling(sampling);
         let openai_msgs = self.provider.prepare_messages(messages)?;
         let response_format: Option<OpenAIResponseFormat> = json_schema.clone().map(|s| s.into());
         let final_tools = self.build_legacy_function_tools(tools);
@@ -1368,11 +1431,11 @@ impl OpenAI {
             model: self.provider.model.as_str(),
             messages: openai_msgs,
             input: None,
-            max_completion_tokens: self.provider.max_tokens,
+            max_completion_tokens: sampling.max_tokens,
             max_output_tokens: None,
-            temperature: self.provider.temperature,
+            temperature: sampling.temperature,
             stream: false,
-            top_p: self.provider.top_p,
+            top_p: sampling.top_p,
             top_k: self.provider.top_k,
             tools: final_tools,
             tool_choice: request_tool_choice,
@@ -1419,8 +1482,10 @@ impl OpenAI {
         messages: &[ChatMessage],
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamResponse, LLMError>> + Send>>, LLMError>
     {
+        let sampling = self.provider.sampling(sampling);
         let openai_msgs = self.provider.prepare_messages(messages)?;
         let openai_tools = self.build_legacy_function_tools(tools);
         let response_schema: Option<OpenAIResponseFormat> = json_schema.map(|schema| schema.into());
@@ -1428,11 +1493,11 @@ impl OpenAI {
             model: &self.provider.model,
             messages: openai_msgs,
             input: None,
-            max_completion_tokens: self.provider.max_tokens,
+            max_completion_tokens: sampling.max_tokens,
             max_output_tokens: None,
-            temperature: self.provider.temperature,
+            temperature: sampling.temperature,
             stream: true,
-            top_p: self.provider.top_p,
+            top_p: sampling.top_p,
             top_k: self.provider.top_k,
             tools: openai_tools,
             tool_choice: self.provider.tool_choice.clone(),
@@ -1468,8 +1533,9 @@ impl OpenAI {
         messages: &[ChatMessage],
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk, LLMError>> + Send>>, LLMError> {
-        let body = self.build_responses_request(messages, tools, json_schema, true)?;
+        let body = self.build_responses_request(messages, tools, json_schema, true, sampling)?;
         let response = self.send_responses_request(&body).await?;
         Ok(create_responses_tool_stream(response))
     }
@@ -1489,8 +1555,9 @@ impl OpenAI {
         messages: &[ChatMessage],
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk, LLMError>> + Send>>, LLMError> {
-        let body = self.build_responses_request(messages, tools, json_schema, true)?;
+        let body = self.build_responses_request(messages, tools, json_schema, true, sampling)?;
 
         if log::log_enabled!(log::Level::Trace) {
             log::trace!(
@@ -2156,6 +2223,13 @@ impl LLMBuilder<OpenAI> {
     }
 }
 
+/// OpenAI rejects embedding requests above 2048 inputs or 300k total tokens.
+#[cfg(all(feature = "openai", any(not(target_arch = "wasm32"), wasi_http)))]
+const OPENAI_EMBEDDING_LIMITS: EmbeddingLimits = EmbeddingLimits {
+    max_inputs: Some(2048),
+    max_tokens: Some(300_000),
+};
+
 // Embeddings use the same `/embeddings` endpoint on both native and WASI;
 // only the transport changes.
 #[cfg(all(feature = "openai", not(target_arch = "wasm32")))]
@@ -2198,6 +2272,10 @@ impl EmbeddingProvider for OpenAI {
         let embeddings = json_resp.data.into_iter().map(|d| d.embedding).collect();
         Ok(embeddings)
     }
+
+    fn embedding_limits(&self) -> EmbeddingLimits {
+        OPENAI_EMBEDDING_LIMITS
+    }
 }
 
 #[cfg(all(feature = "openai", wasi_http))]
@@ -2249,6 +2327,10 @@ impl EmbeddingProvider for OpenAI {
 
         Ok(json_resp.data.into_iter().map(|d| d.embedding).collect())
     }
+
+    fn embedding_limits(&self) -> EmbeddingLimits {
+        OPENAI_EMBEDDING_LIMITS
+    }
 }
 
 impl EmbeddingBuilder<OpenAI> {
@@ -2291,6 +2373,139 @@ impl EmbeddingBuilder<OpenAI> {
     }
 }
 
+#[cfg(native)]
+#[derive(Serialize, Debug)]
+struct OpenAIImageRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+    n: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<&'static str>,
+}
+
+#[cfg(native)]
+#[derive(Deserialize, Debug)]
+struct OpenAIImageResponse {
+    data: Vec<OpenAIImageData>,
+}
+
+#[cfg(native)]
+#[derive(Deserialize, Debug)]
+struct OpenAIImageData {
+    b64_json: Option<String>,
+    revised_prompt: Option<String>,
+}
+
+/// GPT image models always return base64 data; DALL-E models return URLs
+/// unless asked otherwise. Negative prompts and seeds are not supported.
+#[cfg(native)]
+#[async_trait]
+impl ImageGenerationProvider for OpenAI {
+    async fn generate_images(
+        &self,
+        request: &ImageGenerationRequest,
+    ) -> Result<Vec<GeneratedImage>, LLMError> {
+        if self.provider.api_key.is_empty() {
+            return Err(LLMError::missing_api_key(
+                "Missing OpenAI API key".to_string(),
+            ));
+        }
+
+        let model = self.provider.model.as_str();
+        let body = OpenAIImageRequest {
+            model,
+            prompt: &request.prompt,
+            n: request.count,
+            size: request
+                .width
+                .zip(request.height)
+                .map(|(width, height)| format!("{width}x{height}")),
+            response_format: model.starts_with("dall-e").then_some("b64_json"),
+        };
+
+        let url = self
+            .provider
+            .base_url
+            .join("images/generations")
+            .map_err(|e| LLMError::HttpError(e.to_string()))?;
+        let resp = self
+            .provider
+            .client
+            .post(url)
+            .bearer_auth(&self.provider.api_key)
+            .json(&body)
+            .send()
+            .await?;
+        let resp = ensure_success(resp, "OpenAI").await?;
+        let json_resp: OpenAIImageResponse = resp.json().await?;
+
+        json_resp
+            .data
+            .into_iter()
+            .map(|image| {
+                let encoded = image
+                    .b64_json
+                    .ok_or_else(|| LLMError::ResponseFormatError {
+                        message: "OpenAI image response has no b64_json data".to_string(),
+                        raw_response: String::new(),
+                    })?;
+                Ok(GeneratedImage {
+                    data: BASE64
+                        .decode(encoded)
+                        .map_err(|e| LLMError::ResponseFormatError {
+                            message: format!("Invalid base64 image data: {e}"),
+                            raw_response: String::new(),
+                        })?,
+                    mime_type: "image/png".to_string(),
+                    revised_prompt: image.revised_prompt,
+                    seed: None,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(native)]
+impl ImageGenerationBuilder<OpenAI> {
+    /// Build an OpenAI image generation provider.
+    pub fn build(self) -> Result<Arc<OpenAI>, LLMError> {
+        let api_key = self.api_key.ok_or_else(|| {
+            LLMError::invalid_request("No API key provided for OpenAI".to_string())
+        })?;
+
+        let model = self.model.unwrap_or_else(|| "gpt-image-1".to_string());
+
+        let provider = OpenAI::new(
+            api_key,
+            self.base_url,
+            Some(model),
+            None,
+            None,
+            self.timeout_seconds,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            OpenAIApiMode::Responses,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )?;
+
+        Ok(Arc::new(provider))
+    }
+}
+
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
     use super::*;
@@ -2528,7 +2743,13 @@ mod tests {
         ];
 
         let request = provider
-            .build_responses_request(&messages, Some(&[sample_function_tool()]), None, false)
+            .build_responses_request(
+                &messages,
+                Some(&[sample_function_tool()]),
+                None,
+                false,
+                None,
+            )
             .unwrap();
         let serialized = serde_json::to_value(&request).unwrap();
 
@@ -2600,7 +2821,7 @@ mod tests {
         };
 
         let request = provider
-            .build_responses_request(&messages, None, Some(schema), false)
+            .build_responses_request(&messages, None, Some(schema), false, None)
             .unwrap();
         let serialized = serde_json::to_value(&request).unwrap();
 
@@ -3006,6 +3227,51 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_image_generation_uses_mock_server() {
+        let server = MockServer::start();
+        let base_url = format!("{}/v1", server.base_url());
+
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/v1/images/generations")
+                .header("authorization", "Bearer key")
+                .json_body(json!({
+                    "model": "dall-e-3",
+                    "prompt": "a lighthouse",
+                    "n": 1,
+                    "size": "1024x1024",
+                    "response_format": "b64_json"
+                }));
+            then.status(200).json_body(json!({
+                "data": [{
+                    "b64_json": BASE64.encode(b"png-bytes"),
+                    "revised_prompt": "a lighthouse at dusk"
+                }]
+            }));
+        });
+
+        let provider = ImageGenerationBuilder::<OpenAI>::new()
+            .api_key("key")
+            .base_url(base_url)
+            .model("dall-e-3")
+            .build()
+            .unwrap();
+        let images = provider
+            .generate_images(&ImageGenerationRequest::new("a lighthouse").size(1024, 1024))
+            .await
+            .unwrap();
+
+        mock.assert();
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].data, b"png-bytes");
+        assert_eq!(images[0].mime_type, "image/png");
+        assert_eq!(
+            images[0].revised_prompt.as_deref(),
+            Some("a lighthouse at dusk")
+        );
+    }
+
     #[tokio::test]
     async fn test_responses_chat_stream_and_web_search_use_mock_server() {
         let server = MockServer::start();
@@ -0,0 +1,252 @@
+//! Stability AI image generation client.
+//!
+//! Stability only generates images, so [`StabilityAI`] implements
+//! [`ImageGenerationProvider`] rather than the full [`LLMProvider`](crate::LLMProvider).
+
+use crate::config::resolve_request_timeout;
+use crate::error::LLMError;
+use crate::http::ensure_success;
+use crate::image_generation::{
+    GeneratedImage, ImageGenerationBuilder, ImageGenerationProvider, ImageGenerationRequest,
+};
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const DEFAULT_BASE_URL: &str = "https://api.stability.ai/";
+const DEFAULT_ENGINE: &str = "stable-diffusion-xl-1024-v1-0";
+
+/// Client for the Stability AI text-to-image API.
+pub struct StabilityAI {
+    api_key: String,
+    base_url: url::Url,
+    /// Engine identifier (e.g. "stable-diffusion-xl-1024-v1-0")
+    pub engine: String,
+    pub timeout_seconds: u64,
+    client: Client,
+}
+
+#[derive(Serialize, Debug)]
+struct StabilityTextPrompt<'a> {
+    text: &'a str,
+    weight: f32,
+}
+
+#[derive(Serialize, Debug)]
+struct StabilityRequest<'a> {
+    text_prompts: Vec<StabilityTextPrompt<'a>>,
+    samples: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+}
+
+#[derive(Deserialize, Debug)]
+struct StabilityResponse {
+    artifacts: Vec<StabilityArtifact>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct StabilityArtifact {
+    base64: String,
+    seed: Option<u64>,
+    finish_reason: Option<String>,
+}
+
+impl StabilityAI {
+    /// Creates a new Stability AI client.
+    pub fn new(
+        api_key: impl Into<String>,
+        base_url: Option<String>,
+        engine: Option<String>,
+        timeout_seconds: Option<u64>,
+    ) -> Result<Self, LLMError> {
+        let mut base_url = base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        if !base_url.ends_with('/') {
+            base_url.push('/');
+        }
+        let base_url =
+            url::Url::parse(&base_url).map_err(|e| LLMError::HttpError(e.to_string()))?;
+        let timeout_seconds = resolve_request_timeout(timeout_seconds);
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(timeout_seconds))
+            .build()
+            .map_err(|e| LLMError::HttpError(e.to_string()))?;
+
+        Ok(Self {
+            api_key: api_key.into(),
+            base_url,
+            engine: engine.unwrap_or_else(|| DEFAULT_ENGINE.to_string()),
+            timeout_seconds,
+            client,
+        })
+    }
+}
+
+#[async_trait]
+impl ImageGenerationProvider for StabilityAI {
+    async fn generate_images(
+        &self,
+        request: &ImageGenerationRequest,
+    ) -> Result<Vec<GeneratedImage>, LLMError> {
+        if self.api_key.is_empty() {
+            return Err(LLMError::missing_api_key(
+                "Missing Stability AI API key".to_string(),
+            ));
+        }
+
+        let mut text_prompts = vec![StabilityTextPrompt {
+            text: &request.prompt,
+            weight: 1.0,
+        }];
+        if let Some(negative) = request.negative_prompt.as_deref() {
+            text_prompts.push(StabilityTextPrompt {
+                text: negative,
+                weight: -1.0,
+            });
+        }
+        let body = StabilityRequest {
+            text_prompts,
+            samples: request.count,
+            width: request.width,
+            height: request.height,
+            seed: request.seed,
+        };
+
+        let url = self
+            .base_url
+            .join(&format!("v1/generation/{}/text-to-image", self.engine))
+            .map_err(|e| LLMError::HttpError(e.to_string()))?;
+        let resp = self
+            .client
+            .post(url)
+            .bearer_auth(&self.api_key)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .json(&body)
+            .send()
+            .await?;
+        let resp = ensure_success(resp, "Stability AI").await?;
+        let json_resp: StabilityResponse = resp.json().await?;
+
+        json_resp
+            .artifacts
+            .into_iter()
+            .map(|artifact| {
+                if let Some(reason) = artifact
+                    .finish_reason
+                    .as_deref()
+                    .filter(|reason| *reason != "SUCCESS")
+                {
+                    return Err(LLMError::ProviderError(format!(
+                        "Stability AI did not finish the image: {reason}"
+                    )));
+                }
+                Ok(GeneratedImage {
+                    data: BASE64.decode(artifact.base64).map_err(|e| {
+                        LLMError::ResponseFormatError {
+                            message: format!("Invalid base64 image data: {e}"),
+                            raw_response: String::new(),
+                        }
+                    })?,
+                    mime_type: "image/png".to_string(),
+                    revised_prompt: None,
+                    seed: artifact.seed,
+                })
+            })
+            .collect()
+    }
+}
+
+impl ImageGenerationBuilder<StabilityAI> {
+    /// Build a Stability AI image generation provider.
+    pub fn build(self) -> Result<Arc<StabilityAI>, LLMError> {
+        let api_key = self.api_key.ok_or_else(|| {
+            LLMError::invalid_request("No API key provided for Stability AI".to_string())
+        })?;
+
+        Ok(Arc::new(StabilityAI::new(
+            api_key,
+            self.base_url,
+            self.model,
+            self.timeout_seconds,
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::{Method::POST, MockServer};
+    use serde_json::json;
+
+    fn provider(base_url: String) -> Arc<StabilityAI> {
+        ImageGenerationBuilder::<StabilityAI>::new()
+            .api_key("key")
+            .base_url(base_url)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_generate_images_sends_weighted_prompts() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/v1/generation/stable-diffusion-xl-1024-v1-0/text-to-image")
+                .header("authorization", "Bearer key")
+                .json_body(json!({
+                    "text_prompts": [
+                        {"text": "a lighthouse", "weight": 1.0},
+                        {"text": "people", "weight": -1.0}
+                    ],
+                    "samples": 2,
+                    "seed": 7
+                }));
+            then.status(200).json_body(json!({
+                "artifacts": [
+                    {"base64": BASE64.encode(b"one"), "seed": 7, "finishReason": "SUCCESS"},
+                    {"base64": BASE64.encode(b"two"), "seed": 8, "finishReason": "SUCCESS"}
+                ]
+            }));
+        });
+
+        let request = ImageGenerationRequest::new("a lighthouse")
+            .negative_prompt("people")
+            .count(2)
+            .seed(7);
+        let images = provider(server.base_url())
+            .generate_images(&request)
+            .await
+            .unwrap();
+
+        mock.assert();
+        assert_eq!(images.len(), 2);
+        assert_eq!(images[1].data, b"two");
+        assert_eq!(images[1].seed, Some(8));
+    }
+
+    #[tokio::test]
+    async fn test_generate_images_rejects_filtered_artifacts() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST);
+            then.status(200).json_body(json!({
+                "artifacts": [
+                    {"base64": BASE64.encode(b"blurred"), "seed": 1, "finishReason": "CONTENT_FILTERED"}
+                ]
+            }));
+        });
+
+        let err = provider(server.base_url())
+            .generate_images(&ImageGenerationRequest::new("x"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("CONTENT_FILTERED"));
+    }
+}
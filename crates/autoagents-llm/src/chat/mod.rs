@@ -393,11 +393,13 @@ pub trait ChatResponse: std::fmt::Debug + std::fmt::Display + Send + Sync {
 
 /// Per-call sampling overrides for [`ChatProvider`] methods.
 ///
-/// Backends that support per-call sampling (e.g. `LlamaCppProvider`) apply
-/// these overrides on top of the defaults configured at provider construction.
-/// Backends that do not support per-call overrides (the default trait impl)
-/// silently ignore overrides — passing `Some(SamplingOverrides::...)` is safe
-/// against any backend.
+/// Backends that support per-call sampling apply these overrides on top of
+/// the defaults configured at provider construction: the OpenAI-compatible
+/// backends (OpenAI, DeepSeek, Groq, MiniMax, OpenRouter), Anthropic and
+/// `LlamaCppProvider`. The other backends, and wrapping layers such as
+/// `RetryProvider`, use the trait's default impl, which ignores the overrides
+/// and logs a warning once — passing `Some(SamplingOverrides::...)` is safe
+/// against any backend but only takes effect on the ones listed.
 ///
 /// `None` on any field means "use the provider default" (no override). Passing
 /// `sampling: None` to the `_and_sampling` methods is equivalent to calling
@@ -442,6 +444,32 @@ impl SamplingOverrides {
             ..Self::default()
         }
     }
+
+    /// The sampling to send for one call: each field set in `overrides`
+    /// replaces the matching field of the provider's `defaults`.
+    pub fn resolve(overrides: Option<&Self>, defaults: Self) -> Self {
+        let Some(overrides) = overrides else {
+            return defaults;
+        };
+        Self {
+            temperature: overrides.temperature.or(defaults.temperature),
+            top_p: overrides.top_p.or(defaults.top_p),
+            max_tokens: overrides.max_tokens.or(defaults.max_tokens),
+        }
+    }
+}
+
+/// Warn, once per process, that a backend dropped per-call sampling overrides
+fn warn_sampling_ignored(model: &str, sampling: Option<&SamplingOverrides>) {
+    static WARNED: std::sync::Once = std::sync::Once::new();
+    if sampling.is_some_and(|sampling| *sampling != SamplingOverrides::default()) {
+        WARNED.call_once(|| {
+            log::warn!(
+                "per-call sampling overrides are not supported by this provider (model {model:?}) \
+and were ignored; configure temperature, top_p and max_tokens on the provider instead"
+            );
+        });
+    }
 }
 
 /// Trait for providers that support chat-style interactions.
@@ -488,7 +516,7 @@ pub trait ChatProvider: Sync + Send {
     /// Equivalent to [`ChatProvider::chat`] when `sampling` is `None`. When
     /// `sampling` is `Some(...)`, backends that support per-call sampling
     /// apply the overrides on top of provider-construction defaults; backends
-    /// that do not (the default impl) silently ignore them.
+    /// that do not (the default impl) ignore them with a warning.
     ///
     /// Backwards compatible: callers that don't need per-call sampling should
     /// continue to use [`ChatProvider::chat`].
@@ -507,11 +535,11 @@ pub trait ChatProvider: Sync + Send {
     /// Equivalent to [`ChatProvider::chat_with_tools`] when `sampling` is
     /// `None`. When `sampling` is `Some(...)`, backends that support per-call
     /// sampling apply the overrides on top of provider-construction defaults;
-    /// backends that do not (the default impl) silently ignore them.
+    /// backends that do not (the default impl) ignore them with a warning.
     ///
     /// Backwards compatible: the default implementation delegates to
-    /// [`ChatProvider::chat_with_tools`], dropping `sampling` on the floor.
-    /// Backends that wish to honour per-call sampling override this method.
+    /// [`ChatProvider::chat_with_tools`] without `sampling`. Backends that
+    /// wish to honour per-call sampling override this method.
     async fn chat_with_tools_and_sampling(
         &self,
         messages: &[ChatMessage],
@@ -520,7 +548,7 @@ pub trait ChatProvider: Sync + Send {
         sampling: Option<&SamplingOverrides>,
     ) -> Result<Box<dyn ChatResponse>, LLMError> {
         // Default impl: ignore sampling, delegate to existing chat_with_tools.
-        let _ = sampling;
+        warn_sampling_ignored(self.model(), sampling);
         self.chat_with_tools(messages, tools, json_schema).await
     }
 
@@ -650,7 +678,7 @@ pub trait ChatProvider: Sync + Send {
     }
 
     /// Streaming variant of [`ChatProvider::chat_stream`] with per-call
-    /// sampling overrides. Default impl ignores `sampling` and delegates to
+    /// sampling overrides. Default impl warns, ignores `sampling` and delegates to
     /// [`ChatProvider::chat_stream`]. Backends that honour per-call sampling
     /// override this method.
     async fn chat_stream_and_sampling(
@@ -660,12 +688,12 @@ pub trait ChatProvider: Sync + Send {
         sampling: Option<&SamplingOverrides>,
     ) -> Result<std::pin::Pin<Box<dyn Stream<Item = Result<String, LLMError>> + Send>>, LLMError>
     {
-        let _ = sampling;
+        warn_sampling_ignored(self.model(), sampling);
         self.chat_stream(messages, json_schema).await
     }
 
     /// Streaming variant of [`ChatProvider::chat_stream_struct`] with per-call
-    /// sampling overrides. Default impl ignores `sampling` and delegates to
+    /// sampling overrides. Default impl warns, ignores `sampling` and delegates to
     /// [`ChatProvider::chat_stream_struct`]. Backends that honour per-call
     /// sampling override this method.
     async fn chat_stream_struct_and_sampling(
@@ -678,10 +706,26 @@ pub trait ChatProvider: Sync + Send {
         std::pin::Pin<Box<dyn Stream<Item = Result<StreamResponse, LLMError>> + Send>>,
         LLMError,
     > {
-        let _ = sampling;
+        warn_sampling_ignored(self.model(), sampling);
         self.chat_stream_struct(messages, tools, json_schema).await
     }
 
+    /// Streaming variant of [`ChatProvider::chat_stream_with_tools`] with
+    /// per-call sampling overrides. Default impl warns, ignores `sampling` and
+    /// delegates to [`ChatProvider::chat_stream_with_tools`]. Backends that
+    /// honour per-call sampling override this method.
+    async fn chat_stream_with_tools_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamChunk, LLMError>> + Send>>, LLMError> {
+        warn_sampling_ignored(self.model(), sampling);
+        self.chat_stream_with_tools(messages, tools, json_schema)
+            .await
+    }
+
     /// Returns the model identifier this provider was configured with.
     ///
     /// Default returns an empty string for backwards compatibility with impls
@@ -4,9 +4,52 @@ use async_trait::async_trait;
 pub mod model_provider;
 pub use model_provider::EmbeddingBuilder;
 
+/// Limits a provider enforces on a single [`EmbeddingProvider::embed`] call.
+///
+/// `None` means the provider does not declare a limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmbeddingLimits {
+    /// Maximum number of input texts per request.
+    pub max_inputs: Option<usize>,
+    /// Maximum total input tokens per request.
+    pub max_tokens: Option<usize>,
+}
+
+impl EmbeddingLimits {
+    /// Limits satisfying both `self` and `other`.
+    pub fn tightest(self, other: EmbeddingLimits) -> EmbeddingLimits {
+        fn min(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            }
+        }
+        EmbeddingLimits {
+            max_inputs: min(self.max_inputs, other.max_inputs),
+            max_tokens: min(self.max_tokens, other.max_tokens),
+        }
+    }
+}
+
 #[async_trait]
 pub trait EmbeddingProvider: Sync + Send {
     async fn embed(&self, input: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError>;
+
+    /// Request limits used by callers that split large inputs into batches.
+    fn embedding_limits(&self) -> EmbeddingLimits {
+        EmbeddingLimits::default()
+    }
+}
+
+/// Embeds images into vectors.
+///
+/// Multimodal models such as CLIP place images and text in the same space, so
+/// an image provider paired with the matching text [`EmbeddingProvider`] lets
+/// text queries retrieve images.
+#[async_trait]
+pub trait ImageEmbeddingProvider: Sync + Send {
+    /// Embed encoded images (PNG, JPEG, WebP, ...), one vector per image.
+    async fn embed_images(&self, images: Vec<Vec<u8>>) -> Result<Vec<Vec<f32>>, LLMError>;
 }
 
 #[cfg(test)]
@@ -14,6 +57,29 @@ mod tests {
     use super::*;
     use crate::error::LLMError;
 
+    #[test]
+    fn test_embedding_limits_tightest() {
+        let a = EmbeddingLimits {
+            max_inputs: Some(100),
+            max_tokens: None,
+        };
+        let b = EmbeddingLimits {
+            max_inputs: Some(10),
+            max_tokens: Some(500),
+        };
+        assert_eq!(
+            a.tightest(b),
+            EmbeddingLimits {
+                max_inputs: Some(10),
+                max_tokens: Some(500),
+            }
+        );
+        assert_eq!(
+            EmbeddingLimits::default().tightest(EmbeddingLimits::default()),
+            EmbeddingLimits::default()
+        );
+    }
+
     // Mock embedding provider for testing
     struct MockEmbeddingProvider {
         should_fail: bool,
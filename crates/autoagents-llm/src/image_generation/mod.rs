@@ -0,0 +1,144 @@
+use crate::error::LLMError;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+pub mod model_provider;
+pub use model_provider::ImageGenerationBuilder;
+
+/// Parameters for a single [`ImageGenerationProvider::generate_images`] call.
+///
+/// Options a backend does not support are ignored by that backend.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageGenerationRequest {
+    /// Description of the image to generate.
+    pub prompt: String,
+    /// What the image should not contain.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub negative_prompt: Option<String>,
+    /// Output width in pixels; the backend default when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// Output height in pixels; the backend default when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// Number of images to generate.
+    #[serde(default = "default_count")]
+    pub count: u32,
+    /// Seed for reproducible output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+impl ImageGenerationRequest {
+    /// Create a request for one image of the given prompt.
+    pub fn new(prompt: impl Into<String>) -> Self {
+        Self {
+            prompt: prompt.into(),
+            negative_prompt: None,
+            width: None,
+            height: None,
+            count: default_count(),
+            seed: None,
+        }
+    }
+
+    /// Set what the image should not contain.
+    pub fn negative_prompt(mut self, negative_prompt: impl Into<String>) -> Self {
+        self.negative_prompt = Some(negative_prompt.into());
+        self
+    }
+
+    /// Set the output size in pixels.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+
+    /// Set the number of images to generate (at least one).
+    pub fn count(mut self, count: u32) -> Self {
+        self.count = count.max(1);
+        self
+    }
+
+    /// Set the seed for reproducible output.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+/// An encoded image returned by an [`ImageGenerationProvider`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedImage {
+    /// Encoded image bytes.
+    pub data: Vec<u8>,
+    /// MIME type of `data`, such as `image/png`.
+    pub mime_type: String,
+    /// The prompt the backend actually used, when it rewrites prompts.
+    pub revised_prompt: Option<String>,
+    /// The seed used, when the backend reports it.
+    pub seed: Option<u64>,
+}
+
+impl GeneratedImage {
+    /// File extension matching [`mime_type`](Self::mime_type), without the dot.
+    pub fn extension(&self) -> &str {
+        match self.mime_type.as_str() {
+            "image/jpeg" => "jpg",
+            "image/webp" => "webp",
+            "image/gif" => "gif",
+            _ => "png",
+        }
+    }
+}
+
+/// Generates images from text prompts.
+#[async_trait]
+pub trait ImageGenerationProvider: Sync + Send {
+    /// Generate `request.count` images.
+    async fn generate_images(
+        &self,
+        request: &ImageGenerationRequest,
+    ) -> Result<Vec<GeneratedImage>, LLMError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_generation_request_builders() {
+        let request = ImageGenerationRequest::new("a lighthouse at dusk")
+            .negative_prompt("people")
+            .size(1024, 768)
+            .count(0)
+            .seed(42);
+        assert_eq!(request.prompt, "a lighthouse at dusk");
+        assert_eq!(request.negative_prompt.as_deref(), Some("people"));
+        assert_eq!((request.width, request.height), (Some(1024), Some(768)));
+        assert_eq!(request.count, 1);
+        assert_eq!(request.seed, Some(42));
+
+        let parsed: ImageGenerationRequest =
+            serde_json::from_value(serde_json::json!({"prompt": "a cat"})).unwrap();
+        assert_eq!(parsed, ImageGenerationRequest::new("a cat"));
+    }
+
+    #[test]
+    fn test_generated_image_extension() {
+        let image = |mime_type: &str| GeneratedImage {
+            data: Vec::new(),
+            mime_type: mime_type.to_string(),
+            revised_prompt: None,
+            seed: None,
+        };
+        assert_eq!(image("image/jpeg").extension(), "jpg");
+        assert_eq!(image("image/webp").extension(), "webp");
+        assert_eq!(image("image/png").extension(), "png");
+    }
+}
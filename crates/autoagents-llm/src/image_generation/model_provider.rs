@@ -0,0 +1,64 @@
+use std::marker::PhantomData;
+
+use crate::config::resolve_request_timeout;
+use crate::image_generation::ImageGenerationProvider;
+
+/// Builder for creating image generation providers without going through the LLM builder.
+pub struct ImageGenerationBuilder<P: ImageGenerationProvider> {
+    backend: PhantomData<P>,
+    pub(crate) api_key: Option<String>,
+    pub(crate) base_url: Option<String>,
+    pub(crate) model: Option<String>,
+    pub(crate) timeout_seconds: Option<u64>,
+}
+
+impl<P: ImageGenerationProvider> Default for ImageGenerationBuilder<P> {
+    fn default() -> Self {
+        Self {
+            backend: PhantomData,
+            api_key: None,
+            base_url: None,
+            model: None,
+            timeout_seconds: None,
+        }
+    }
+}
+
+impl<P: ImageGenerationProvider> ImageGenerationBuilder<P> {
+    /// Create a new image generation provider builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the API key for the provider.
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Set a custom base URL for the provider.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Set the model (or engine) identifier for image generation.
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Set a request timeout in seconds.
+    ///
+    /// When unset, [`resolved_timeout_seconds`](Self::resolved_timeout_seconds) returns
+    /// [`DEFAULT_REQUEST_TIMEOUT_SECS`](crate::config::DEFAULT_REQUEST_TIMEOUT_SECS) (120 seconds).
+    pub fn timeout_seconds(mut self, timeout: u64) -> Self {
+        self.timeout_seconds = Some(timeout);
+        self
+    }
+
+    /// Returns the effective HTTP request timeout in seconds.
+    pub fn resolved_timeout_seconds(&self) -> u64 {
+        resolve_request_timeout(self.timeout_seconds)
+    }
+}
@@ -38,7 +38,8 @@ use serde::{Deserialize, Serialize};
         feature = "groq",
         feature = "azure_openai",
         feature = "openrouter",
-        feature = "minimax"
+        feature = "minimax",
+        feature = "stability"
     )
 ))]
 compile_error!(
@@ -64,7 +65,8 @@ compile_error!(
         feature = "groq",
         feature = "azure_openai",
         feature = "openrouter",
-        feature = "minimax"
+        feature = "minimax",
+        feature = "stability"
     )
 ))]
 compile_error!(
@@ -102,7 +104,8 @@ Rebuild with `--features openai,wasi-http`."
         feature = "groq",
         feature = "azure_openai",
         feature = "openrouter",
-        feature = "minimax"
+        feature = "minimax",
+        feature = "stability"
     )
 ))]
 compile_error!(
@@ -125,6 +128,9 @@ pub mod completion;
 /// Vector embeddings generation for text
 pub mod embedding;
 
+/// Image generation from text prompts
+pub mod image_generation;
+
 /// Error types and handling
 pub mod error;
 
@@ -616,8 +622,8 @@ mod tests {
     async fn test_chat_and_sampling_default_impl_ignores_sampling() {
         // Default trait impl delegates to chat_with_tools, dropping sampling.
         // Backends without per-call sampling support must not error when
-        // overrides are passed — `Some(SamplingOverrides::...)` is silently
-        // ignored. Asserts the contract documented on
+        // overrides are passed — `Some(SamplingOverrides::...)` is ignored,
+        // with a warning. Asserts the contract documented on
         // `chat_with_tools_and_sampling`.
         let provider = MockLLMProvider;
         let messages = vec![chat::ChatMessage::user().content("Test").build()];
@@ -682,6 +688,20 @@ mod tests {
         let max_tok = chat::SamplingOverrides::with_max_tokens(128);
         assert_eq!(max_tok.max_tokens, Some(128));
 
+        let configured = chat::SamplingOverrides {
+            temperature: Some(0.7),
+            top_p: Some(0.9),
+            max_tokens: None,
+        };
+        let resolved = chat::SamplingOverrides::resolve(Some(&max_tok), configured.clone());
+        assert_eq!(resolved.temperature, Some(0.7));
+        assert_eq!(resolved.top_p, Some(0.9));
+        assert_eq!(resolved.max_tokens, Some(128));
+        assert_eq!(
+            chat::SamplingOverrides::resolve(None, configured.clone()),
+            configured
+        );
+
         // SamplingOverrides is re-exported at crate root.
         let from_root = SamplingOverrides::with_temperature(0.5);
         assert_eq!(from_root.temperature, Some(0.5));
@@ -46,7 +46,7 @@ use crate::{
         StreamResponse, StructuredOutputFormat, Tool,
     },
     completion::{CompletionProvider, CompletionRequest, CompletionResponse},
-    embedding::EmbeddingProvider,
+    embedding::{EmbeddingLimits, EmbeddingProvider},
     error::LLMError,
     models::{ModelListRequest, ModelListResponse, ModelsProvider},
     pipeline::LLMLayer,
@@ -891,6 +891,10 @@ impl EmbeddingProvider for InMemoryCacheLayer {
 
         Ok(result)
     }
+
+    fn embedding_limits(&self) -> EmbeddingLimits {
+        self.inner.embedding_limits()
+    }
 }
 
 // ---------------------------------------------------------------------------
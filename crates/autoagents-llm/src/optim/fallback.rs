@@ -50,7 +50,7 @@ use crate::{
         StructuredOutputFormat, Tool,
     },
     completion::{CompletionProvider, CompletionRequest, CompletionResponse},
-    embedding::EmbeddingProvider,
+    embedding::{EmbeddingLimits, EmbeddingProvider},
     error::LLMError,
     models::{ModelListRequest, ModelListResponse, ModelsProvider},
     pipeline::LLMLayer,
@@ -338,6 +338,14 @@ impl EmbeddingProvider for FallbackProvider {
         })
         .await
     }
+
+    fn embedding_limits(&self) -> EmbeddingLimits {
+        // Any provider may end up serving the request
+        self.providers
+            .iter()
+            .map(|p| p.embedding_limits())
+            .fold(EmbeddingLimits::default(), EmbeddingLimits::tightest)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -38,7 +38,7 @@ use crate::{
         StructuredOutputFormat, Tool,
     },
     completion::{CompletionProvider, CompletionRequest, CompletionResponse},
-    embedding::EmbeddingProvider,
+    embedding::{EmbeddingLimits, EmbeddingProvider},
     error::LLMError,
     models::{ModelListRequest, ModelListResponse, ModelsProvider},
     pipeline::LLMLayer,
@@ -375,6 +375,10 @@ impl EmbeddingProvider for RetryProvider {
     async fn embed(&self, input: Vec<String>) -> Result<Vec<Vec<f32>>, LLMError> {
         retry_call(&self.config, || self.inner.embed(input.clone())).await
     }
+
+    fn embedding_limits(&self) -> EmbeddingLimits {
+        self.inner.embedding_limits()
+    }
 }
 
 // ---------------------------------------------------------------------------
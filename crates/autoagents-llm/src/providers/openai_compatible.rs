@@ -26,7 +26,7 @@ use crate::http::ensure_success;
 use crate::{
     ToolCall,
     chat::ChatResponse,
-    chat::{StructuredOutputFormat, Tool, ToolChoice, Usage},
+    chat::{SamplingOverrides, StructuredOutputFormat, Tool, ToolChoice, Usage},
     default_call_type,
 };
 #[cfg(not(target_arch = "wasm32"))]
@@ -416,6 +416,17 @@ impl<T: OpenAIProviderConfig> OpenAICompatibleProvider<T> {
         }
         Ok(openai_msgs)
     }
+    /// The configured sampling with this call's `overrides` applied
+    pub fn sampling(&self, overrides: Option<&SamplingOverrides>) -> SamplingOverrides {
+        SamplingOverrides::resolve(
+            overrides,
+            SamplingOverrides {
+                temperature: self.temperature,
+                top_p: self.top_p,
+                max_tokens: self.max_tokens,
+            },
+        )
+    }
 }
 
 /// Native chat-completions / streaming transport. These impls and helpers go
@@ -430,6 +441,18 @@ impl<T: OpenAIProviderConfig> ChatProvider for OpenAICompatibleProvider<T> {
         messages: &[ChatMessage],
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
+    ) -> Result<Box<dyn ChatResponse>, LLMError> {
+        self.chat_with_tools_and_sampling(messages, tools, json_schema, None)
+            .await
+    }
+
+    /// Perform a chat request with tool calls and per-call sampling overrides
+    async fn chat_with_tools_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
     ) -> Result<Box<dyn ChatResponse>, LLMError> {
         if self.api_key.is_empty() {
             return Err(LLMError::missing_api_key(format!(
@@ -437,6 +460,7 @@ impl<T: OpenAIProviderConfig> ChatProvider for OpenAICompatibleProvider<T> {
                 T::PROVIDER_NAME
             )));
         }
+        let sampling = self.sampling(sampling);
         let openai_msgs = self.prepare_messages(messages)?;
         let response_format: Option<OpenAIResponseFormat> = if T::SUPPORTS_STRUCTURED_OUTPUT {
             json_schema.clone().map(|s| s.into())
@@ -462,10 +486,10 @@ impl<T: OpenAIProviderConfig> ChatProvider for OpenAICompatibleProvider<T> {
         let body = OpenAIChatRequest {
             model: &self.model,
             messages: openai_msgs,
-            max_tokens: self.max_tokens,
-            temperature: self.temperature,
+            max_tokens: sampling.max_tokens,
+            temperature: sampling.temperature,
             stream: false,
-            top_p: self.top_p,
+            top_p: sampling.top_p,
             top_k: self.top_k,
             tools: request_tools,
             tool_choice: request_tool_choice,
@@ -527,7 +551,21 @@ impl<T: OpenAIProviderConfig> ChatProvider for OpenAICompatibleProvider<T> {
         json_schema: Option<StructuredOutputFormat>,
     ) -> Result<std::pin::Pin<Box<dyn Stream<Item = Result<String, LLMError>> + Send>>, LLMError>
     {
-        let struct_stream = self.chat_stream_struct(messages, None, json_schema).await?;
+        self.chat_stream_and_sampling(messages, json_schema, None)
+            .await
+    }
+
+    /// Stream chat responses as strings, with per-call sampling overrides
+    async fn chat_stream_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
+    ) -> Result<std::pin::Pin<Box<dyn Stream<Item = Result<String, LLMError>> + Send>>, LLMError>
+    {
+        let struct_stream = self
+            .chat_stream_struct_and_sampling(messages, None, json_schema, sampling)
+            .await?;
         let content_stream = struct_stream.filter_map(|result| async move {
             match result {
                 Ok(stream_response) => {
@@ -554,6 +592,21 @@ impl<T: OpenAIProviderConfig> ChatProvider for OpenAICompatibleProvider<T> {
     ) -> Result<
         std::pin::Pin<Box<dyn Stream<Item = Result<StreamResponse, LLMError>> + Send>>,
         LLMError,
+    > {
+        self.chat_stream_struct_and_sampling(messages, tools, json_schema, None)
+            .await
+    }
+
+    /// Structured streaming with per-call sampling overrides
+    async fn chat_stream_struct_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
+    ) -> Result<
+        std::pin::Pin<Box<dyn Stream<Item = Result<StreamResponse, LLMError>> + Send>>,
+        LLMError,
     > {
         if self.api_key.is_empty() {
             return Err(LLMError::missing_api_key(format!(
@@ -561,6 +614,7 @@ impl<T: OpenAIProviderConfig> ChatProvider for OpenAICompatibleProvider<T> {
                 T::PROVIDER_NAME
             )));
         }
+        let sampling = self.sampling(sampling);
         let openai_msgs = self.prepare_messages(messages)?;
         let request_tools = tools.map(|t| t.to_vec());
 
@@ -573,10 +627,10 @@ impl<T: OpenAIProviderConfig> ChatProvider for OpenAICompatibleProvider<T> {
         let body = OpenAIChatRequest {
             model: &self.model,
             messages: openai_msgs,
-            max_tokens: self.max_tokens,
-            temperature: self.temperature,
+            max_tokens: sampling.max_tokens,
+            temperature: sampling.temperature,
             stream: true,
-            top_p: self.top_p,
+            top_p: sampling.top_p,
             top_k: self.top_k,
             tools: request_tools,
             tool_choice: self.tool_choice.clone(),
@@ -646,6 +700,19 @@ impl<T: OpenAIProviderConfig> ChatProvider for OpenAICompatibleProvider<T> {
         tools: Option<&[Tool]>,
         json_schema: Option<StructuredOutputFormat>,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatStreamChunk, LLMError>> + Send>>, LLMError>
+    {
+        self.chat_stream_with_tools_and_sampling(messages, tools, json_schema, None)
+            .await
+    }
+
+    /// Tool-aware streaming with per-call sampling overrides
+    async fn chat_stream_with_tools_and_sampling(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[Tool]>,
+        json_schema: Option<StructuredOutputFormat>,
+        sampling: Option<&SamplingOverrides>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<ChatStreamChunk, LLMError>> + Send>>, LLMError>
     {
         if self.api_key.is_empty() {
             return Err(LLMError::missing_api_key(format!(
@@ -653,6 +720,7 @@ impl<T: OpenAIProviderConfig> ChatProvider for OpenAICompatibleProvider<T> {
                 T::PROVIDER_NAME
             )));
         }
+        let sampling = self.sampling(sampling);
 
         let openai_msgs = self.prepare_messages(messages)?;
 
@@ -667,10 +735,10 @@ impl<T: OpenAIProviderConfig> ChatProvider for OpenAICompatibleProvider<T> {
         let body = OpenAIChatRequest {
             model: &self.model,
             messages: openai_msgs,
-            max_tokens: self.max_tokens,
-            temperature: self.temperature,
+            max_tokens: sampling.max_tokens,
+            temperature: sampling.temperature,
             stream: true,
-            top_p: self.top_p,
+            top_p: sampling.top_p,
             top_k: self.top_k,
             tools: requested_tools,
             tool_choice: self.tool_choice.clone(),
@@ -2095,6 +2163,33 @@ mod tests {
         response_mock.assert();
     }
 
+    #[tokio::test]
+    async fn test_chat_with_tools_and_sampling_sends_overrides() {
+        let server = MockServer::start();
+        let response_mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/v1/chat/completions")
+                .body_includes("\"temperature\":0.25")
+                .body_includes("\"max_tokens\":32");
+            then.status(200).json_body(json!({
+                "choices": [{"message": {"role": "assistant", "content": "ok"}}]
+            }));
+        });
+
+        let provider = full_support_provider(format!("{}/v1", server.base_url()));
+        let messages = vec![ChatMessage::user().content("hello").build()];
+        let sampling = SamplingOverrides {
+            temperature: Some(0.25),
+            max_tokens: Some(32),
+            ..SamplingOverrides::default()
+        };
+        provider
+            .chat_with_tools_and_sampling(&messages, None, None, Some(&sampling))
+            .await
+            .expect("chat_with_tools_and_sampling should succeed");
+        response_mock.assert();
+    }
+
     #[tokio::test]
     async fn test_chat_with_tools_returns_error_for_status_and_invalid_json() {
         let server = MockServer::start();
@@ -10,5 +10,5 @@ pub use llm::{
 pub use protocol::{
     ActorID, Event, EventId, InternalEvent, RuntimeID, StreamingTurnResult, SubmissionId,
 };
-pub use task::Task;
+pub use task::{Task, TraceAttributes};
 pub use tool::ToolCallResult;
@@ -1,6 +1,6 @@
-use crate::StreamChunk;
-use crate::task::Task;
+use crate::task::{Task, TraceAttributes};
 use crate::tool::ToolCallResult;
+use crate::{StreamChunk, Usage};
 use serde::{Deserialize, Serialize};
 use std::any::{Any, TypeId};
 use std::fmt::Debug;
@@ -34,6 +34,9 @@ pub enum Event {
         actor_id: ActorID,
         actor_name: String,
         task_description: String,
+        /// User/session attribution and custom span attributes supplied with the task.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        trace: Option<TraceAttributes>,
     },
 
     /// A task has been completed
@@ -141,6 +144,15 @@ pub enum Event {
         final_turn: bool,
     },
 
+    /// An LLM call issued during a turn has completed
+    LLMCallCompleted {
+        sub_id: SubmissionId,
+        actor_id: ActorID,
+        model: String,
+        duration_ms: u64,
+        usage: Option<Usage>,
+    },
+
     /// Streaming chunk from agent
     StreamChunk {
         sub_id: SubmissionId,
@@ -157,6 +169,15 @@ pub enum Event {
     StreamComplete {
         sub_id: SubmissionId,
     },
+
+    /// An agent's memory was summarized and pruned
+    MemoryCompacted {
+        actor_id: ActorID,
+        messages_before: usize,
+        messages_after: usize,
+        tokens_before: usize,
+        tokens_after: usize,
+    },
 }
 
 /// Internal events that are processed within the runtime
@@ -206,6 +227,10 @@ mod tests {
             actor_id: Default::default(),
             actor_name: String::from("test"),
             task_description: "Started task".to_string(),
+            trace: Some(TraceAttributes {
+                user_id: Some("user-1".to_string()),
+                ..Default::default()
+            }),
         };
 
         let serialized = serde_json::to_string(&event).unwrap();
@@ -213,9 +238,12 @@ mod tests {
 
         match deserialized {
             Event::TaskStarted {
-                task_description, ..
+                task_description,
+                trace,
+                ..
             } => {
                 assert_eq!(task_description, "Started task");
+                assert_eq!(trace.and_then(|t| t.user_id).as_deref(), Some("user-1"));
             }
             _ => panic!("Expected TaskStarted variant"),
         }
@@ -410,6 +438,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_event_serialization_llm_call_completed() {
+        let event = Event::LLMCallCompleted {
+            sub_id: Uuid::new_v4(),
+            actor_id: Uuid::new_v4(),
+            model: "gpt-4o-mini".to_string(),
+            duration_ms: 120,
+            usage: Some(Usage {
+                prompt_tokens: 10,
+                completion_tokens: 5,
+                total_tokens: 15,
+                completion_tokens_details: None,
+                prompt_tokens_details: None,
+            }),
+        };
+
+        let serialized = serde_json::to_string(&event).unwrap();
+        let deserialized: Event = serde_json::from_str(&serialized).unwrap();
+
+        match deserialized {
+            Event::LLMCallCompleted {
+                model,
+                duration_ms,
+                usage,
+                ..
+            } => {
+                assert_eq!(model, "gpt-4o-mini");
+                assert_eq!(duration_ms, 120);
+                assert_eq!(usage.map(|usage| usage.total_tokens), Some(15));
+            }
+            _ => panic!("Expected LLMCallCompleted variant"),
+        }
+    }
+
     #[test]
     fn test_uuid_types() {
         let submission_id: SubmissionId = Uuid::new_v4();
@@ -2,8 +2,20 @@ use crate::SubmissionId;
 use crate::llm::ImageMime;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+/// User, session, and custom attributes attached to the telemetry spans of a task.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TraceAttributes {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, Value>,
+}
+
 /// A unit of work submitted to an agent.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
@@ -17,6 +29,9 @@ pub struct Task {
     /// Arbitrary application-provided metadata (session/chat isolation, app context, anything the app threads through).
     #[serde(default)]
     pub app_meta: Option<Value>,
+    /// Attribution forwarded to telemetry (user, session, custom span attributes).
+    #[serde(default)]
+    pub trace: Option<TraceAttributes>,
 }
 
 impl Task {
@@ -30,6 +45,7 @@ impl Task {
             completed: false,
             result: None,
             app_meta: None,
+            trace: None,
         }
     }
 
@@ -47,6 +63,7 @@ impl Task {
             completed: false,
             result: None,
             app_meta: None,
+            trace: None,
         }
     }
 
@@ -60,6 +77,31 @@ impl Task {
         self.app_meta = Some(meta);
         self
     }
+
+    /// Attribute the task's spans to an end user.
+    pub fn with_user_id<T: Into<String>>(mut self, user_id: T) -> Self {
+        self.trace.get_or_insert_with(Default::default).user_id = Some(user_id.into());
+        self
+    }
+
+    /// Group the task's spans under a session.
+    pub fn with_session_id<T: Into<String>>(mut self, session_id: T) -> Self {
+        self.trace.get_or_insert_with(Default::default).session_id = Some(session_id.into());
+        self
+    }
+
+    /// Attach a custom attribute to the task's spans.
+    pub fn with_trace_attribute<K: Into<String>, V: Into<Value>>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> Self {
+        self.trace
+            .get_or_insert_with(Default::default)
+            .attributes
+            .insert(key.into(), value.into());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -103,4 +145,20 @@ mod tests {
         assert_eq!(meta.get("session_id").and_then(|v| v.as_str()), Some("s1"));
         assert_eq!(meta.get("chat_id").and_then(|v| v.as_str()), Some("c1"));
     }
+
+    #[test]
+    fn trace_builders_populate_attributes() {
+        let task = Task::new("hi")
+            .with_user_id("u1")
+            .with_session_id("s1")
+            .with_trace_attribute("tier", "gold");
+        let trace = task.trace.expect("trace attributes set");
+        assert_eq!(trace.user_id.as_deref(), Some("u1"));
+        assert_eq!(trace.session_id.as_deref(), Some("s1"));
+        assert_eq!(trace.attributes.get("tier"), Some(&Value::from("gold")));
+
+        let json = serde_json::to_value(&trace).unwrap();
+        let back: TraceAttributes = serde_json::from_value(json).unwrap();
+        assert_eq!(back, trace);
+    }
 }
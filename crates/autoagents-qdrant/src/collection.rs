@@ -0,0 +1,88 @@
+use qdrant_client::qdrant::{Distance, FieldType, HnswConfigDiff};
+
+/// Similarity metric of a collection's dense vectors
+///
+/// With [`DistanceMetric::Euclid`] and [`DistanceMetric::Manhattan`] scores
+/// are distances: lower is closer, and a request threshold is an upper bound.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DistanceMetric {
+    #[default]
+    Cosine,
+    Dot,
+    Euclid,
+    Manhattan,
+}
+
+impl From<DistanceMetric> for Distance {
+    fn from(metric: DistanceMetric) -> Self {
+        match metric {
+            DistanceMetric::Cosine => Distance::Cosine,
+            DistanceMetric::Dot => Distance::Dot,
+            DistanceMetric::Euclid => Distance::Euclid,
+            DistanceMetric::Manhattan => Distance::Manhattan,
+        }
+    }
+}
+
+/// HNSW index parameters; unset fields keep Qdrant's defaults
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HnswParams {
+    /// Edges per node; higher improves recall and uses more memory
+    pub m: Option<u64>,
+    /// Candidates considered while building; higher improves index quality
+    pub ef_construct: Option<u64>,
+    /// Segment size in KB below which search skips the index
+    pub full_scan_threshold: Option<u64>,
+    /// Keep the index on disk instead of in memory
+    pub on_disk: Option<bool>,
+}
+
+impl From<HnswParams> for HnswConfigDiff {
+    fn from(params: HnswParams) -> Self {
+        HnswConfigDiff {
+            m: params.m,
+            ef_construct: params.ef_construct,
+            full_scan_threshold: params.full_scan_threshold,
+            on_disk: params.on_disk,
+            ..Default::default()
+        }
+    }
+}
+
+/// Type of a payload field index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadIndexType {
+    Keyword,
+    Integer,
+    Float,
+    Bool,
+    /// Full-text index; text matches then require every word instead of a substring
+    Text,
+    Datetime,
+    Uuid,
+}
+
+impl From<PayloadIndexType> for FieldType {
+    fn from(index: PayloadIndexType) -> Self {
+        match index {
+            PayloadIndexType::Keyword => FieldType::Keyword,
+            PayloadIndexType::Integer => FieldType::Integer,
+            PayloadIndexType::Float => FieldType::Float,
+            PayloadIndexType::Bool => FieldType::Bool,
+            PayloadIndexType::Text => FieldType::Text,
+            PayloadIndexType::Datetime => FieldType::Datetime,
+            PayloadIndexType::Uuid => FieldType::Uuid,
+        }
+    }
+}
+
+/// Settings applied when [`QdrantVectorStore`](crate::QdrantVectorStore) creates its collection
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollectionConfig {
+    pub distance: DistanceMetric,
+    pub hnsw: Option<HnswParams>,
+    /// Keep payloads on disk instead of in memory
+    pub on_disk_payload: Option<bool>,
+    /// Payload keys to index, such as `raw.metadata.lang`
+    pub payload_indexes: Vec<(String, PayloadIndexType)>,
+}
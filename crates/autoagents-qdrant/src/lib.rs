@@ -1,31 +1,45 @@
 use std::collections::HashMap;
 
 use async_trait::async_trait;
-use autoagents_core::embeddings::{Embed, Embedding, EmbeddingError, SharedEmbeddingProvider};
+use autoagents_core::embeddings::{
+    Embed, EmbedImage, Embedding, EmbeddingError, EmbeddingTransform, SharedEmbeddingProvider,
+    SharedImageEmbeddingProvider, TransformedEmbeddingProvider, TransformedImageEmbeddingProvider,
+};
 use autoagents_core::one_or_many::OneOrMany;
 use autoagents_core::vector_store::request::{Filter, FilterError};
 use autoagents_core::vector_store::{
-    DEFAULT_VECTOR_NAME, NamedVectorDocument, NamedVectorPayloadDocument, PayloadDocument,
-    PreparedDocument, PreparedNamedVectorDocument, PreparedNamedVectorPayloadDocument,
-    PreparedPayloadDocument, VectorSearchRequest, VectorStoreError, VectorStoreIndex,
-    embed_documents, embed_named_documents, embed_named_payload_documents, embed_payload_documents,
-    normalize_id,
+    DEFAULT_SPARSE_VECTOR_NAME, DEFAULT_VECTOR_NAME, HybridFusion, NamedVectorDocument,
+    NamedVectorPayloadDocument, PayloadDocument, PreparedDocument, PreparedNamedVectorDocument,
+    PreparedNamedVectorPayloadDocument, PreparedPayloadDocument, ScrollPage, SharedSparseEncoder,
+    VectorSearchRequest, VectorStoreError, VectorStoreIndex, embed_documents,
+    embed_image_documents, embed_named_documents, embed_named_payload_documents,
+    embed_payload_documents, fuse_hybrid, normalize_id, rerank_top_n, rerank_top_n_ids,
 };
 use qdrant_client::Payload;
 use qdrant_client::Qdrant;
 use qdrant_client::qdrant::{
-    Condition, CreateCollectionBuilder, DeletePointsBuilder, Distance, Filter as QdrantFilter,
-    PointStruct, Range, SearchPointsBuilder, UpsertPointsBuilder, VectorParamsBuilder,
-    VectorsConfigBuilder, condition, with_payload_selector,
+    Condition, CountPointsBuilder, CreateCollectionBuilder, CreateFieldIndexCollectionBuilder,
+    DeletePointsBuilder, FieldType, Filter as QdrantFilter, Modifier, PointId, PointStruct, Range,
+    ScoredPoint, ScrollPointsBuilder, SearchPointsBuilder, SparseVectorParamsBuilder,
+    SparseVectorsConfigBuilder, UpsertPointsBuilder, Vector, VectorParamsBuilder, Vectors,
+    VectorsConfigBuilder, condition, point_id::PointIdOptions, with_payload_selector,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+mod collection;
+
+pub use collection::{CollectionConfig, DistanceMetric, HnswParams, PayloadIndexType};
+
 #[derive(Clone)]
 pub struct QdrantVectorStore {
     client: Qdrant,
     collection_name: String,
     provider: SharedEmbeddingProvider,
+    transform: EmbeddingTransform,
+    image_provider: Option<SharedImageEmbeddingProvider>,
+    sparse_encoder: Option<SharedSparseEncoder>,
+    collection: CollectionConfig,
 }
 
 impl QdrantVectorStore {
@@ -66,60 +80,297 @@ impl QdrantVectorStore {
             client,
             collection_name: collection_name.into(),
             provider,
+            transform: EmbeddingTransform::default(),
+            image_provider: None,
+            sparse_encoder: None,
+            collection: CollectionConfig::default(),
         })
     }
 
-    async fn ensure_collection(&self, dimension: u64) -> Result<(), VectorStoreError> {
-        let request = CreateCollectionBuilder::new(self.collection_name.clone())
-            .vectors_config(VectorParamsBuilder::new(dimension, Distance::Cosine))
-            .build();
+    /// Truncate and/or normalize every document and query vector.
+    ///
+    /// The transform is recorded in the collection metadata when the
+    /// collection is created, so other processes can pick it up with
+    /// [`QdrantVectorStore::with_collection_transform`].
+    pub fn with_embedding_transform(mut self, transform: EmbeddingTransform) -> Self {
+        if !transform.is_identity() {
+            self.provider =
+                std::sync::Arc::new(TransformedEmbeddingProvider::new(self.provider, transform));
+        }
+        self.transform = transform;
+        self
+    }
+
+    pub fn embedding_transform(&self) -> EmbeddingTransform {
+        self.transform
+    }
+
+    /// Enable [`VectorStoreIndex::insert_image_documents_with_ids`].
+    ///
+    /// Image vectors are stored in the same collection as text vectors, so
+    /// the image provider must share the text provider's embedding space
+    /// (e.g. the two halves of a CLIP model).
+    pub fn with_image_embedding_provider(mut self, provider: SharedImageEmbeddingProvider) -> Self {
+        self.image_provider = Some(provider);
+        self
+    }
+
+    /// Enable [`VectorStoreIndex::top_n_hybrid`].
+    ///
+    /// Collections are then created with a sparse vector named
+    /// [`DEFAULT_SPARSE_VECTOR_NAME`] using Qdrant's `idf` modifier, and every
+    /// text document gets a sparse vector next to its dense embedding. The
+    /// encoder must be set before the collection is created; documents with
+    /// named vectors only get dense vectors.
+    pub fn with_sparse_encoder(mut self, encoder: SharedSparseEncoder) -> Self {
+        self.sparse_encoder = Some(encoder);
+        self
+    }
+
+    /// Replace all collection settings at once.
+    ///
+    /// Like the `with_*` setters below, this only affects collections this
+    /// store creates; use [`QdrantVectorStore::create_payload_indexes`] to
+    /// index an existing collection.
+    pub fn with_collection_config(mut self, config: CollectionConfig) -> Self {
+        self.collection = config;
+        self
+    }
+
+    /// Distance metric of the dense vectors (default cosine).
+    pub fn with_distance(mut self, distance: DistanceMetric) -> Self {
+        self.collection.distance = distance;
+        self
+    }
+
+    pub fn with_hnsw(mut self, params: HnswParams) -> Self {
+        self.collection.hnsw = Some(params);
+        self
+    }
+
+    pub fn with_on_disk_payload(mut self, on_disk: bool) -> Self {
+        self.collection.on_disk_payload = Some(on_disk);
+        self
+    }
+
+    /// Index a payload key that filters use often.
+    ///
+    /// Documents are stored under `raw`, so a [`Document`] metadata field is
+    /// indexed as `raw.metadata.<field>`; fields mirrored with
+    /// [`QdrantVectorStore::insert_documents_with_payload_fields`] are at the root.
+    ///
+    /// [`Document`]: autoagents_core::document::Document
+    pub fn with_payload_index(mut self, key: impl Into<String>, index: PayloadIndexType) -> Self {
+        self.collection.payload_indexes.push((key.into(), index));
+        self
+    }
+
+    pub fn collection_config(&self) -> &CollectionConfig {
+        &self.collection
+    }
+
+    /// Create the configured payload indexes; indexes that exist already are kept.
+    pub async fn create_payload_indexes(&self) -> Result<(), VectorStoreError> {
+        for (key, index) in &self.collection.payload_indexes {
+            let request = CreateFieldIndexCollectionBuilder::new(
+                self.collection_name.clone(),
+                key.clone(),
+                FieldType::from(*index),
+            )
+            .wait(true);
+            self.client
+                .create_field_index(request)
+                .await
+                .map_err(|err| VectorStoreError::DatastoreError(Box::new(err)))?;
+        }
+        Ok(())
+    }
 
-        let result = self.client.create_collection(request).await;
-        if let Err(err) = result {
+    async fn create_collection(
+        &self,
+        request: qdrant_client::qdrant::CreateCollection,
+    ) -> Result<(), VectorStoreError> {
+        match self.client.create_collection(request).await {
+            Ok(_) => self.create_payload_indexes().await,
             // Ignore already existing collections to keep the operation idempotent.
-            let message = err.to_string();
-            if !message.contains("already exists") {
-                return Err(VectorStoreError::DatastoreError(Box::new(err)));
-            }
+            Err(err) if err.to_string().contains("already exists") => Ok(()),
+            Err(err) => Err(VectorStoreError::DatastoreError(Box::new(err))),
         }
+    }
 
-        Ok(())
+    fn image_provider(&self) -> Result<SharedImageEmbeddingProvider, VectorStoreError> {
+        let provider = self.image_provider.clone().ok_or_else(|| {
+            VectorStoreError::Unsupported("no image embedding provider configured".to_string())
+        })?;
+        if self.transform.is_identity() {
+            return Ok(provider);
+        }
+        Ok(std::sync::Arc::new(TransformedImageEmbeddingProvider::new(
+            provider,
+            self.transform,
+        )))
+    }
+
+    /// Reads the transform stored in the collection metadata.
+    ///
+    /// Returns `None` when the collection does not exist or was created
+    /// without one.
+    pub async fn load_embedding_transform(
+        &self,
+    ) -> Result<Option<EmbeddingTransform>, VectorStoreError> {
+        let exists = self
+            .client
+            .collection_exists(self.collection_name.clone())
+            .await
+            .map_err(|err| VectorStoreError::DatastoreError(Box::new(err)))?;
+        if !exists {
+            return Ok(None);
+        }
+
+        let info = self
+            .client
+            .collection_info(self.collection_name.clone())
+            .await
+            .map_err(|err| VectorStoreError::DatastoreError(Box::new(err)))?;
+        let metadata = info
+            .result
+            .and_then(|info| info.config)
+            .map(|config| config.metadata)
+            .unwrap_or_default();
+        Self::transform_from_metadata(&metadata)
+    }
+
+    /// Applies the transform stored with an existing collection, so queries
+    /// are processed the same way as the documents already indexed.
+    pub async fn with_collection_transform(self) -> Result<Self, VectorStoreError> {
+        match self.load_embedding_transform().await? {
+            Some(transform) => Ok(self.with_embedding_transform(transform)),
+            None => Ok(self),
+        }
+    }
+
+    fn transform_metadata(transform: &EmbeddingTransform) -> HashMap<String, serde_json::Value> {
+        HashMap::from([(
+            EmbeddingTransform::METADATA_KEY.to_string(),
+            transform.to_metadata(),
+        )])
+    }
+
+    fn transform_from_metadata(
+        metadata: &HashMap<String, qdrant_client::qdrant::Value>,
+    ) -> Result<Option<EmbeddingTransform>, VectorStoreError> {
+        let Some(value) = metadata.get(EmbeddingTransform::METADATA_KEY) else {
+            return Ok(None);
+        };
+        let value = serde_json::to_value(value)?;
+        Ok(Some(EmbeddingTransform::from_metadata(&value)?))
+    }
+
+    async fn ensure_collection(&self, dimension: u64) -> Result<(), VectorStoreError> {
+        let request = Self::collection_request(
+            &self.collection_name,
+            dimension,
+            &self.transform,
+            self.sparse_encoder.is_some(),
+            &self.collection,
+        );
+        self.create_collection(request).await
     }
 
     async fn ensure_named_collection(
         &self,
         dimensions: &HashMap<String, u64>,
     ) -> Result<(), VectorStoreError> {
-        let request = Self::named_collection_request(&self.collection_name, dimensions);
+        let request = Self::named_collection_request(
+            &self.collection_name,
+            dimensions,
+            &self.transform,
+            &self.collection,
+        );
+        self.create_collection(request).await
+    }
 
-        let result = self.client.create_collection(request).await;
-        if let Err(err) = result {
-            let message = err.to_string();
-            if !message.contains("already exists") {
-                return Err(VectorStoreError::DatastoreError(Box::new(err)));
-            }
+    fn collection_request(
+        collection_name: &str,
+        dimension: u64,
+        transform: &EmbeddingTransform,
+        sparse: bool,
+        config: &CollectionConfig,
+    ) -> qdrant_client::qdrant::CreateCollection {
+        let mut request = Self::collection_builder(collection_name, transform, config)
+            .vectors_config(VectorParamsBuilder::new(dimension, config.distance.into()));
+        if sparse {
+            let mut config = SparseVectorsConfigBuilder::default();
+            config.add_named_vector_params(
+                DEFAULT_SPARSE_VECTOR_NAME,
+                SparseVectorParamsBuilder::default().modifier(Modifier::Idf),
+            );
+            request = request.sparse_vectors_config(config);
         }
+        request.build()
+    }
 
-        Ok(())
+    /// The dense vector, plus the sparse vector of the embedded text when
+    /// a sparse encoder is configured
+    fn point_vectors(
+        embeddings: &OneOrMany<Embedding>,
+        sparse_encoder: Option<&SharedSparseEncoder>,
+    ) -> Result<Vectors, VectorStoreError> {
+        let dense = combine_embeddings(embeddings)?;
+        let Some(encoder) = sparse_encoder else {
+            return Ok(dense.into());
+        };
+        let text: Vec<&str> = embeddings
+            .iter()
+            .map(|embedding| embedding.document.as_str())
+            .collect();
+        let sparse = encoder.encode_document(&text.join("\n"));
+        Ok(HashMap::from([
+            // Qdrant addresses the unnamed dense vector by the empty name
+            (String::new(), Vector::new_dense(dense)),
+            (
+                DEFAULT_SPARSE_VECTOR_NAME.to_string(),
+                Vector::new_sparse(sparse.indices, sparse.values),
+            ),
+        ])
+        .into())
     }
 
     fn named_collection_request(
         collection_name: &str,
         dimensions: &HashMap<String, u64>,
+        transform: &EmbeddingTransform,
+        config: &CollectionConfig,
     ) -> qdrant_client::qdrant::CreateCollection {
-        let mut config = VectorsConfigBuilder::default();
+        let mut vectors = VectorsConfigBuilder::default();
         for (name, dimension) in dimensions {
-            config.add_named_vector_params(
+            vectors.add_named_vector_params(
                 name.clone(),
-                VectorParamsBuilder::new(*dimension, Distance::Cosine),
+                VectorParamsBuilder::new(*dimension, config.distance.into()),
             );
         }
 
-        CreateCollectionBuilder::new(collection_name.to_string())
-            .vectors_config(config)
+        Self::collection_builder(collection_name, transform, config)
+            .vectors_config(vectors)
             .build()
     }
 
+    fn collection_builder(
+        collection_name: &str,
+        transform: &EmbeddingTransform,
+        config: &CollectionConfig,
+    ) -> CreateCollectionBuilder {
+        let mut builder = CreateCollectionBuilder::new(collection_name.to_string())
+            .metadata(Self::transform_metadata(transform));
+        if let Some(hnsw) = config.hnsw {
+            builder = builder.hnsw_config(hnsw);
+        }
+        if let Some(on_disk) = config.on_disk_payload {
+            builder = builder.on_disk_payload(on_disk);
+        }
+        builder
+    }
+
     fn payload_for(doc: &PreparedDocument) -> Result<Payload, VectorStoreError> {
         let payload = serde_json::json!({
             "raw": doc.raw,
@@ -157,30 +408,89 @@ impl QdrantVectorStore {
         }
     }
 
-    /// Deletes documents using their logical/source IDs (the IDs used for upsert).
-    pub async fn delete_documents_by_ids(
+    fn point_source_id(point: &ScoredPoint) -> String {
+        Self::source_id(&point.payload, point.id.as_ref())
+    }
+
+    fn source_id(
+        payload: &HashMap<String, qdrant_client::qdrant::Value>,
+        point_id: Option<&PointId>,
+    ) -> String {
+        Self::decode_id(payload)
+            .or_else(|| point_id.map(|id| format!("{id:?}")))
+            .unwrap_or_default()
+    }
+
+    fn decode_points<T>(points: Vec<ScoredPoint>) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let mut results = Vec::new();
+        for point in points {
+            let id = Self::point_source_id(&point);
+            if let Some(raw) = Self::decode_raw::<T>(&point.payload)? {
+                results.push((point.score as f64, id, raw));
+            }
+        }
+        Ok(results)
+    }
+
+    fn search_builder(
         &self,
-        source_ids: &[String],
-    ) -> Result<(), VectorStoreError> {
-        if source_ids.is_empty() {
-            return Ok(());
+        vector: Vec<f32>,
+        req: &VectorSearchRequest<Filter<serde_json::Value>>,
+        vector_name: Option<&str>,
+        with_threshold: bool,
+    ) -> Result<SearchPointsBuilder, VectorStoreError> {
+        let mut search =
+            SearchPointsBuilder::new(self.collection_name.clone(), vector, req.samples())
+                .with_payload(with_payload_selector::SelectorOptions::Enable(true));
+
+        if let Some(vector_name) = vector_name
+            && vector_name != DEFAULT_VECTOR_NAME
+        {
+            search = search.vector_name(vector_name.to_string());
         }
 
-        let point_ids = source_ids
-            .iter()
-            .map(|source_id| Self::stable_point_id(source_id))
-            .collect::<Vec<_>>();
+        if let Some(filter) = req.filter() {
+            search = search.filter(to_qdrant_filter(filter.clone())?);
+        }
 
-        self.client
-            .delete_points(
-                DeletePointsBuilder::new(self.collection_name.clone())
-                    .points(point_ids)
-                    .wait(true),
-            )
+        if with_threshold && let Some(threshold) = req.threshold() {
+            search = search.score_threshold(threshold as f32);
+        }
+
+        Ok(search)
+    }
+
+    async fn run_search(
+        &self,
+        search: SearchPointsBuilder,
+    ) -> Result<Vec<ScoredPoint>, VectorStoreError> {
+        let response = self
+            .client
+            .search_points(search)
             .await
             .map_err(|err| VectorStoreError::DatastoreError(Box::new(err)))?;
+        Ok(response.result)
+    }
 
-        Ok(())
+    async fn dense_search(
+        &self,
+        req: &VectorSearchRequest<Filter<serde_json::Value>>,
+    ) -> Result<Vec<ScoredPoint>, VectorStoreError> {
+        let vectors = self
+            .provider
+            .embed(vec![req.query().to_string()])
+            .await
+            .map_err(EmbeddingError::Provider)?;
+
+        let Some(vector) = vectors.into_iter().next() else {
+            return Ok(Vec::new());
+        };
+
+        let search = self.search_builder(vector, req, req.query_vector_name(), true)?;
+        self.run_search(search).await
     }
 
     /// Deletes this collection if it already exists.
@@ -300,6 +610,42 @@ impl QdrantVectorStore {
         self.upsert_prepared_named_payload_documents(prepared).await
     }
 
+    async fn upsert_prepared_documents(
+        &self,
+        prepared: Vec<PreparedDocument>,
+    ) -> Result<(), VectorStoreError> {
+        let Some(first) = prepared.first() else {
+            return Ok(());
+        };
+
+        let dim = first
+            .embeddings
+            .iter()
+            .next()
+            .map(|e| e.vec.len())
+            .unwrap_or(0);
+        self.ensure_collection(dim as u64).await?;
+
+        let mut points = Vec::new();
+        for doc in prepared {
+            let payload = Self::payload_for(&doc)?;
+            let vector = Self::point_vectors(&doc.embeddings, self.sparse_encoder.as_ref())?;
+
+            // Keep logical id in payload and map point id to a stable UUID.
+            let point_id = Self::stable_point_id(&doc.id);
+
+            points.push(PointStruct::new(point_id, vector, payload.clone()));
+        }
+
+        let request = UpsertPointsBuilder::new(self.collection_name.clone(), points).build();
+        self.client
+            .upsert_points(request)
+            .await
+            .map_err(|err| VectorStoreError::DatastoreError(Box::new(err)))?;
+
+        Ok(())
+    }
+
     async fn upsert_prepared_payload_documents(
         &self,
         prepared: Vec<PreparedPayloadDocument>,
@@ -319,7 +665,7 @@ impl QdrantVectorStore {
         let mut points = Vec::new();
         for doc in prepared {
             let payload = Self::payload_for_shaped(&doc)?;
-            let vector = combine_embeddings(&doc.embeddings)?;
+            let vector = Self::point_vectors(&doc.embeddings, self.sparse_encoder.as_ref())?;
             let point_id = Self::stable_point_id(&doc.id);
             points.push(PointStruct::new(point_id, vector, payload));
         }
@@ -384,140 +730,165 @@ impl VectorStoreIndex for QdrantVectorStore {
             .map(|(id, doc)| (normalize_id(Some(id)), doc))
             .collect();
         let prepared = embed_documents(&self.provider, normalized).await?;
-        let Some(first) = prepared.first() else {
-            return Ok(());
-        };
-
-        let dim = first
-            .embeddings
-            .iter()
-            .next()
-            .map(|e| e.vec.len())
-            .unwrap_or(0);
-        self.ensure_collection(dim as u64).await?;
-
-        let mut points = Vec::new();
-        for doc in prepared {
-            let payload = Self::payload_for(&doc)?;
-            let vector = combine_embeddings(&doc.embeddings)?;
+        self.upsert_prepared_documents(prepared).await
+    }
 
-            // Keep logical id in payload and map point id to a stable UUID.
-            let point_id = Self::stable_point_id(&doc.id);
+    async fn insert_image_documents_with_ids<T>(
+        &self,
+        documents: Vec<(String, T)>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: EmbedImage + Serialize + Send + Sync + Clone,
+    {
+        let provider = self.image_provider()?;
+        let normalized: Vec<(String, T)> = documents
+            .into_iter()
+            .map(|(id, doc)| (normalize_id(Some(id)), doc))
+            .collect();
+        let prepared = embed_image_documents(&provider, normalized).await?;
+        self.upsert_prepared_documents(prepared).await
+    }
 
-            points.push(PointStruct::new(point_id, vector, payload.clone()));
+    /// Deletes documents using their logical/source IDs (the IDs used for upsert).
+    async fn delete_documents_by_ids(&self, source_ids: &[String]) -> Result<(), VectorStoreError> {
+        if source_ids.is_empty() {
+            return Ok(());
         }
 
-        let request = UpsertPointsBuilder::new(self.collection_name.clone(), points).build();
+        let point_ids = source_ids
+            .iter()
+            .map(|source_id| Self::stable_point_id(source_id))
+            .collect::<Vec<_>>();
+
         self.client
-            .upsert_points(request)
+            .delete_points(
+                DeletePointsBuilder::new(self.collection_name.clone())
+                    .points(point_ids)
+                    .wait(true),
+            )
             .await
             .map_err(|err| VectorStoreError::DatastoreError(Box::new(err)))?;
 
         Ok(())
     }
 
-    async fn top_n<T>(
+    /// Pages are in Qdrant point id order; the cursor is the point id that
+    /// starts the next page.
+    async fn scroll<T>(
         &self,
-        req: VectorSearchRequest<Self::Filter>,
-    ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+        filter: Option<Self::Filter>,
+        cursor: Option<String>,
+        limit: u64,
+    ) -> Result<ScrollPage<T>, VectorStoreError>
     where
         T: for<'de> Deserialize<'de> + Send + Sync,
     {
-        let vectors = self
-            .provider
-            .embed(vec![req.query().to_string()])
-            .await
-            .map_err(EmbeddingError::Provider)?;
-
-        let Some(vector) = vectors.into_iter().next() else {
-            return Ok(Vec::new());
-        };
-
-        let mut search =
-            SearchPointsBuilder::new(self.collection_name.clone(), vector, req.samples())
-                .with_payload(with_payload_selector::SelectorOptions::Enable(true));
-
-        if let Some(vector_name) = req.query_vector_name()
-            && vector_name != DEFAULT_VECTOR_NAME
-        {
-            search = search.vector_name(vector_name.to_string());
-        }
-
-        if let Some(filter) = req.filter() {
-            search = search.filter(to_qdrant_filter(filter.clone())?);
+        let limit = u32::try_from(limit.max(1)).unwrap_or(u32::MAX);
+        let mut scroll = ScrollPointsBuilder::new(self.collection_name.clone())
+            .limit(limit)
+            .with_payload(true)
+            .with_vectors(false);
+        if let Some(filter) = filter {
+            scroll = scroll.filter(to_qdrant_filter(filter)?);
         }
-
-        if let Some(threshold) = req.threshold() {
-            search = search.score_threshold(threshold as f32);
+        if let Some(cursor) = cursor {
+            scroll = scroll.offset(cursor_to_point_id(cursor));
         }
 
         let response = self
             .client
-            .search_points(search)
+            .scroll(scroll)
             .await
             .map_err(|err| VectorStoreError::DatastoreError(Box::new(err)))?;
 
-        let mut results = Vec::new();
+        let mut documents = Vec::with_capacity(response.result.len());
         for point in response.result {
-            let id = Self::decode_id(&point.payload)
-                .or_else(|| point.id.map(|id| format!("{id:?}")))
-                .unwrap_or_default();
-
             if let Some(raw) = Self::decode_raw::<T>(&point.payload)? {
-                results.push((point.score as f64, id, raw));
+                documents.push((Self::source_id(&point.payload, point.id.as_ref()), raw));
             }
         }
 
-        Ok(results)
+        Ok(ScrollPage {
+            documents,
+            next_cursor: response.next_page_offset.and_then(point_id_to_cursor),
+        })
     }
 
-    async fn top_n_ids(
-        &self,
-        req: VectorSearchRequest<Self::Filter>,
-    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
-        let vectors = self
-            .provider
-            .embed(vec![req.query().to_string()])
-            .await
-            .map_err(EmbeddingError::Provider)?;
-
-        let Some(vector) = vectors.into_iter().next() else {
-            return Ok(Vec::new());
-        };
-
-        let mut search =
-            SearchPointsBuilder::new(self.collection_name.clone(), vector, req.samples())
-                .with_payload(with_payload_selector::SelectorOptions::Enable(true));
-
-        if let Some(vector_name) = req.query_vector_name()
-            && vector_name != DEFAULT_VECTOR_NAME
-        {
-            search = search.vector_name(vector_name.to_string());
-        }
-
-        if let Some(filter) = req.filter() {
-            search = search.filter(to_qdrant_filter(filter.clone())?);
-        }
-
-        if let Some(threshold) = req.threshold() {
-            search = search.score_threshold(threshold as f32);
+    async fn count(&self, filter: Option<Self::Filter>) -> Result<u64, VectorStoreError> {
+        let mut count = CountPointsBuilder::new(self.collection_name.clone()).exact(true);
+        if let Some(filter) = filter {
+            count = count.filter(to_qdrant_filter(filter)?);
         }
 
         let response = self
             .client
-            .search_points(search)
+            .count(count)
             .await
             .map_err(|err| VectorStoreError::DatastoreError(Box::new(err)))?;
+        Ok(response.result.map_or(0, |result| result.count))
+    }
 
-        let mut results = Vec::new();
-        for point in response.result {
-            let id = Self::decode_id(&point.payload)
-                .or_else(|| point.id.map(|id| format!("{id:?}")))
-                .unwrap_or_default();
-            results.push((point.score as f64, id));
+    /// Documents are stored under the `raw` payload key
+    fn field_filter_key(&self, field: &str) -> String {
+        format!("raw.{field}")
+    }
+
+    async fn top_n<T>(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        if req.reranker().is_some() {
+            return rerank_top_n(self, req).await;
         }
+        let points = self.dense_search(&req).await?;
+        Self::decode_points(points)
+    }
 
-        Ok(results)
+    async fn top_n_ids(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        if req.reranker().is_some() {
+            return rerank_top_n_ids(self, req).await;
+        }
+        let points = self.dense_search(&req).await?;
+        Ok(points
+            .into_iter()
+            .map(|point| (point.score as f64, Self::point_source_id(&point)))
+            .collect())
+    }
+
+    async fn top_n_hybrid<T>(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+        fusion: HybridFusion,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        let encoder = self.sparse_encoder.as_ref().ok_or_else(|| {
+            VectorStoreError::Unsupported("no sparse encoder configured".to_string())
+        })?;
+        let query = encoder.encode_query(req.query());
+        let dense = self.dense_search(&req).await?;
+        let sparse = if query.is_empty() {
+            Vec::new()
+        } else {
+            let search = self
+                .search_builder(query.values, &req, Some(DEFAULT_SPARSE_VECTOR_NAME), false)?
+                .sparse_indices(query.indices);
+            self.run_search(search).await?
+        };
+
+        Ok(fuse_hybrid(
+            Self::decode_points(dense)?,
+            Self::decode_points(sparse)?,
+            fusion,
+            req.samples() as usize,
+        ))
     }
 
     async fn insert_documents_with_named_vectors<T>(
@@ -623,12 +994,63 @@ fn to_qdrant_filter(filter: Filter<serde_json::Value>) -> Result<QdrantFilter, V
             ));
             Ok(filter)
         }
+        NotEq(key, value) => {
+            let mut filter = empty();
+            filter
+                .must_not
+                .push(Condition::matches(key, value_to_match_value(value)?));
+            Ok(filter)
+        }
+        In(key, values) => {
+            let mut filter = empty();
+            filter
+                .must
+                .push(Condition::matches(key, values_to_match_any(values)?));
+            Ok(filter)
+        }
+        // Without a full-text index on `key` Qdrant matches text as a substring;
+        // with one, every word of `value` must appear in the field.
+        Contains(key, serde_json::Value::String(text)) => {
+            let mut filter = empty();
+            filter.must.push(Condition::matches_text(key, text));
+            Ok(filter)
+        }
+        // Qdrant matches array payloads when any element matches
+        Contains(key, value) => to_qdrant_filter(Eq(key, value)),
+        Between(key, low, high) => {
+            let mut filter = empty();
+            filter.must.push(Condition::range(
+                key,
+                Range {
+                    gte: Some(number_to_f64(&low)?),
+                    lte: Some(number_to_f64(&high)?),
+                    gt: None,
+                    lt: None,
+                },
+            ));
+            Ok(filter)
+        }
+        // `is_empty` covers a missing key, `null` and `[]`, which is exactly
+        // what `Exists` excludes
+        Exists(key) => {
+            let mut filter = empty();
+            filter.must_not.push(Condition::is_empty(key));
+            Ok(filter)
+        }
         And(lhs, rhs) => {
             let mut left = to_qdrant_filter(*lhs)?;
             let right = to_qdrant_filter(*rhs)?;
 
             left.must.extend(right.must);
-            left.must.extend(right.should);
+            left.must_not.extend(right.must_not);
+            if !right.should.is_empty() {
+                left.must.push(Condition {
+                    condition_one_of: Some(condition::ConditionOneOf::Filter(QdrantFilter {
+                        should: right.should,
+                        ..empty()
+                    })),
+                });
+            }
             Ok(left)
         }
         Or(lhs, rhs) => {
@@ -652,6 +1074,20 @@ fn to_qdrant_filter(filter: Filter<serde_json::Value>) -> Result<QdrantFilter, V
     }
 }
 
+fn point_id_to_cursor(point_id: PointId) -> Option<String> {
+    match point_id.point_id_options? {
+        PointIdOptions::Num(num) => Some(num.to_string()),
+        PointIdOptions::Uuid(uuid) => Some(uuid),
+    }
+}
+
+fn cursor_to_point_id(cursor: String) -> PointId {
+    match cursor.parse::<u64>() {
+        Ok(num) => num.into(),
+        Err(_) => cursor.into(),
+    }
+}
+
 fn value_to_match_value(
     value: serde_json::Value,
 ) -> Result<qdrant_client::qdrant::r#match::MatchValue, VectorStoreError> {
@@ -672,6 +1108,29 @@ fn value_to_match_value(
     }
 }
 
+fn values_to_match_any(
+    values: Vec<serde_json::Value>,
+) -> Result<qdrant_client::qdrant::r#match::MatchValue, VectorStoreError> {
+    if values.iter().all(serde_json::Value::is_i64) {
+        return Ok(values
+            .iter()
+            .filter_map(serde_json::Value::as_i64)
+            .collect::<Vec<_>>()
+            .into());
+    }
+    values
+        .into_iter()
+        .map(|value| match value {
+            serde_json::Value::String(s) => Ok(s),
+            other => Err(FilterError::TypeError(format!(
+                "`in` needs all strings or all integers, got {other:?}"
+            ))
+            .into()),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Into::into)
+}
+
 fn number_to_f64(value: &serde_json::Value) -> Result<f64, VectorStoreError> {
     value
         .as_f64()
@@ -718,7 +1177,7 @@ mod tests {
     use autoagents_core::embeddings::Embedding;
     use autoagents_core::one_or_many::OneOrMany;
     use autoagents_core::vector_store::request::{Filter, SearchFilter};
-    use qdrant_client::qdrant::{vectors, vectors_config};
+    use qdrant_client::qdrant::{Distance, vectors, vectors_config};
     use std::sync::Arc;
 
     #[test]
@@ -801,6 +1260,8 @@ mod tests {
         let request = QdrantVectorStore::named_collection_request(
             "docs",
             &HashMap::from([("title".to_string(), 2_u64), ("body".to_string(), 3_u64)]),
+            &EmbeddingTransform::default(),
+            &CollectionConfig::default(),
         );
 
         let vectors_config = request.vectors_config.expect("vectors config");
@@ -817,6 +1278,151 @@ mod tests {
         assert_eq!(params.map["body"].distance, Distance::Cosine as i32);
     }
 
+    #[test]
+    fn test_collection_request_adds_idf_sparse_vector() {
+        let request = QdrantVectorStore::collection_request(
+            "docs",
+            3,
+            &EmbeddingTransform::default(),
+            true,
+            &CollectionConfig::default(),
+        );
+        let sparse = request
+            .sparse_vectors_config
+            .expect("sparse vectors config");
+        assert_eq!(
+            sparse.map[DEFAULT_SPARSE_VECTOR_NAME].modifier,
+            Some(Modifier::Idf as i32)
+        );
+
+        let dense_only = QdrantVectorStore::collection_request(
+            "docs",
+            3,
+            &EmbeddingTransform::default(),
+            false,
+            &CollectionConfig::default(),
+        );
+        assert!(dense_only.sparse_vectors_config.is_none());
+    }
+
+    #[test]
+    fn test_point_vectors_include_sparse_text_vector() {
+        use autoagents_core::vector_store::{Bm25Encoder, SparseEncoder};
+
+        let embeddings = OneOrMany::One(Embedding {
+            document: "ERR_TIMEOUT in http client".to_string(),
+            vec: Arc::from(vec![0.1_f32, 0.2_f32]),
+        });
+        let encoder: SharedSparseEncoder = Arc::new(Bm25Encoder::new());
+
+        let vectors::VectorsOptions::Vectors(named) =
+            QdrantVectorStore::point_vectors(&embeddings, Some(&encoder))
+                .unwrap()
+                .vectors_options
+                .expect("named vectors")
+        else {
+            panic!("expected named vectors");
+        };
+        assert_eq!(
+            named.vectors[""].clone().try_into_dense().unwrap(),
+            vec![0.1, 0.2]
+        );
+        let expected = Bm25Encoder::new().encode_document("ERR_TIMEOUT in http client");
+        let sparse = match named.vectors[DEFAULT_SPARSE_VECTOR_NAME].clone().vector {
+            Some(qdrant_client::qdrant::vector::Vector::Sparse(sparse)) => sparse,
+            other => panic!("expected sparse vector, got {other:?}"),
+        };
+        assert_eq!(sparse.indices, expected.indices);
+
+        let dense_only = QdrantVectorStore::point_vectors(&embeddings, None).unwrap();
+        assert!(matches!(
+            dense_only.vectors_options,
+            Some(vectors::VectorsOptions::Vector(_))
+        ));
+    }
+
+    #[test]
+    fn test_collection_request_applies_collection_config() {
+        let config = CollectionConfig {
+            distance: DistanceMetric::Dot,
+            hnsw: Some(HnswParams {
+                m: Some(32),
+                ef_construct: Some(200),
+                ..Default::default()
+            }),
+            on_disk_payload: Some(true),
+            payload_indexes: vec![("raw.metadata.lang".to_string(), PayloadIndexType::Keyword)],
+        };
+
+        let request = QdrantVectorStore::collection_request(
+            "docs",
+            3,
+            &EmbeddingTransform::default(),
+            false,
+            &config,
+        );
+        let vectors_config::Config::Params(params) = request
+            .vectors_config
+            .and_then(|config| config.config)
+            .expect("vector params")
+        else {
+            panic!("expected single vector params");
+        };
+        assert_eq!(params.distance, Distance::Dot as i32);
+        let hnsw = request.hnsw_config.expect("hnsw config");
+        assert_eq!((hnsw.m, hnsw.ef_construct), (Some(32), Some(200)));
+        assert_eq!(hnsw.full_scan_threshold, None);
+        assert_eq!(request.on_disk_payload, Some(true));
+
+        let named = QdrantVectorStore::named_collection_request(
+            "docs",
+            &HashMap::from([("body".to_string(), 3_u64)]),
+            &EmbeddingTransform::default(),
+            &CollectionConfig {
+                distance: DistanceMetric::Euclid,
+                ..config
+            },
+        );
+        let vectors_config::Config::ParamsMap(params) = named
+            .vectors_config
+            .and_then(|config| config.config)
+            .expect("named vector params")
+        else {
+            panic!("expected named vector params map");
+        };
+        assert_eq!(params.map["body"].distance, Distance::Euclid as i32);
+
+        let defaults = QdrantVectorStore::collection_request(
+            "docs",
+            3,
+            &EmbeddingTransform::default(),
+            false,
+            &CollectionConfig::default(),
+        );
+        assert!(defaults.hnsw_config.is_none());
+        assert!(defaults.on_disk_payload.is_none());
+    }
+
+    #[test]
+    fn test_collection_request_records_embedding_transform() {
+        let transform = EmbeddingTransform::new()
+            .with_dimensions(256)
+            .with_normalize(true);
+        let request = QdrantVectorStore::named_collection_request(
+            "docs",
+            &HashMap::from([("body".to_string(), 256_u64)]),
+            &transform,
+            &CollectionConfig::default(),
+        );
+
+        let loaded = QdrantVectorStore::transform_from_metadata(&request.metadata).unwrap();
+        assert_eq!(loaded, Some(transform));
+        assert_eq!(
+            QdrantVectorStore::transform_from_metadata(&HashMap::new()).unwrap(),
+            None
+        );
+    }
+
     #[test]
     fn test_named_document_point_uses_named_vectors_and_source_payload() {
         let doc = PreparedNamedVectorDocument {
@@ -935,6 +1541,110 @@ mod tests {
         assert_eq!(qdrant.should.len(), 2);
     }
 
+    #[test]
+    fn test_to_qdrant_filter_and_keeps_nested_or() {
+        let filter = Filter::Eq("field".to_string(), serde_json::json!("x")).and(
+            Filter::Eq("lang".to_string(), serde_json::json!("rust"))
+                .or(Filter::Eq("lang".to_string(), serde_json::json!("go"))),
+        );
+        let qdrant = to_qdrant_filter(filter).unwrap();
+        assert_eq!(qdrant.must.len(), 2);
+        assert!(qdrant.should.is_empty());
+        match &qdrant.must[1].condition_one_of {
+            Some(condition::ConditionOneOf::Filter(nested)) => assert_eq!(nested.should.len(), 2),
+            other => panic!("expected nested filter, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_to_qdrant_filter_extended_conditions() {
+        use qdrant_client::qdrant::r#match::MatchValue;
+
+        let match_value = |filter: &QdrantFilter| match &filter.must[0].condition_one_of {
+            Some(condition::ConditionOneOf::Field(field)) => {
+                field.r#match.as_ref().and_then(|m| m.match_value.clone())
+            }
+            _ => None,
+        };
+
+        let filter = Filter::NotEq("lang".to_string(), serde_json::json!("go"))
+            .and(Filter::Exists("author".to_string()));
+        let qdrant = to_qdrant_filter(filter).unwrap();
+        assert!(qdrant.must.is_empty());
+        assert_eq!(qdrant.must_not.len(), 2);
+        // Like `Filter::satisfies`, `Exists` rejects `[]` as well as null and missing keys
+        assert!(matches!(
+            &qdrant.must_not[1].condition_one_of,
+            Some(condition::ConditionOneOf::IsEmpty(is_empty)) if is_empty.key == "author"
+        ));
+        assert!(
+            !Filter::Exists("author".to_string()).satisfies(&serde_json::json!({"author": []}))
+        );
+
+        let keywords = Filter::In(
+            "lang".to_string(),
+            vec![serde_json::json!("go"), serde_json::json!("rust")],
+        );
+        let qdrant = to_qdrant_filter(keywords).unwrap();
+        assert!(
+            matches!(match_value(&qdrant), Some(MatchValue::Keywords(k)) if k.strings.len() == 2)
+        );
+
+        let integers = Filter::In("year".to_string(), vec![serde_json::json!(2024)]);
+        let qdrant = to_qdrant_filter(integers).unwrap();
+        assert!(matches!(
+            match_value(&qdrant),
+            Some(MatchValue::Integers(_))
+        ));
+
+        let mixed = Filter::In(
+            "year".to_string(),
+            vec![serde_json::json!(2024), serde_json::json!("2025")],
+        );
+        assert!(to_qdrant_filter(mixed).is_err());
+
+        let text = Filter::Contains("title".to_string(), serde_json::json!("rust"));
+        let qdrant = to_qdrant_filter(text).unwrap();
+        assert!(matches!(match_value(&qdrant), Some(MatchValue::Text(t)) if t == "rust"));
+
+        let member = Filter::Contains("ids".to_string(), serde_json::json!(7));
+        let qdrant = to_qdrant_filter(member).unwrap();
+        assert!(matches!(match_value(&qdrant), Some(MatchValue::Integer(7))));
+
+        let between = Filter::Between(
+            "year".to_string(),
+            serde_json::json!(2020),
+            serde_json::json!(2024),
+        );
+        let qdrant = to_qdrant_filter(between).unwrap();
+        match &qdrant.must[0].condition_one_of {
+            Some(condition::ConditionOneOf::Field(field)) => {
+                let range = field.range.as_ref().unwrap();
+                assert_eq!((range.gte, range.lte), (Some(2020.0), Some(2024.0)));
+            }
+            other => panic!("expected range, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_scroll_cursor_roundtrips_point_ids() {
+        let uuid = QdrantVectorStore::stable_point_id("doc-1");
+        let cursor = point_id_to_cursor(PointId::from(uuid.clone())).unwrap();
+        assert_eq!(cursor, uuid);
+        assert_eq!(cursor_to_point_id(cursor), PointId::from(uuid));
+
+        let cursor = point_id_to_cursor(PointId::from(42u64)).unwrap();
+        assert_eq!(cursor, "42");
+        assert_eq!(cursor_to_point_id(cursor), PointId::from(42u64));
+
+        assert!(
+            point_id_to_cursor(PointId {
+                point_id_options: None
+            })
+            .is_none()
+        );
+    }
+
     #[test]
     fn test_decode_helpers_missing_fields() {
         let payload: HashMap<String, qdrant_client::qdrant::Value> = HashMap::new();
@@ -0,0 +1,661 @@
+//! Encoding and decoding of compressed audio.
+//!
+//! [`decode`] turns WAV, MP3, FLAC, Ogg Vorbis, and Ogg Opus bytes into
+//! [`AudioData`], so STT can accept uploads as they arrive from clients.
+//! [`encode`] produces WAV, MP3, FLAC, or Ogg Opus bytes, so TTS output can be
+//! served to browsers without a separate transcoding step.
+
+use crate::{AudioData, AudioFormat};
+use std::io::Cursor;
+
+/// Errors raised while encoding or decoding audio.
+#[derive(Debug, thiserror::Error)]
+pub enum CodecError {
+    #[error("Unsupported audio format for {operation}: {format:?}")]
+    UnsupportedFormat {
+        format: AudioFormat,
+        operation: &'static str,
+    },
+    #[error("Could not detect the audio format")]
+    UnknownFormat,
+    #[error("Failed to decode {format:?} audio: {message}")]
+    Decode {
+        format: AudioFormat,
+        message: String,
+    },
+    #[error("Failed to encode {format:?} audio: {message}")]
+    Encode {
+        format: AudioFormat,
+        message: String,
+    },
+}
+
+impl CodecError {
+    fn decode(format: AudioFormat, message: impl ToString) -> Self {
+        Self::Decode {
+            format,
+            message: message.to_string(),
+        }
+    }
+
+    fn encode(format: AudioFormat, message: impl ToString) -> Self {
+        Self::Encode {
+            format,
+            message: message.to_string(),
+        }
+    }
+}
+
+pub type CodecResult<T> = Result<T, CodecError>;
+
+impl AudioData {
+    /// Decode compressed audio; the format is detected when `format` is `None`.
+    pub fn decode(bytes: &[u8], format: Option<AudioFormat>) -> CodecResult<Self> {
+        decode(bytes, format)
+    }
+
+    /// Encode these samples into `format`.
+    pub fn encode(&self, format: AudioFormat) -> CodecResult<Vec<u8>> {
+        encode(self, format)
+    }
+}
+
+/// Decode compressed audio into interleaved samples.
+///
+/// The format is detected from the leading bytes when `format` is `None`.
+/// Opus always decodes at 48kHz.
+pub fn decode(bytes: &[u8], format: Option<AudioFormat>) -> CodecResult<AudioData> {
+    let format = format
+        .or_else(|| AudioFormat::detect(bytes))
+        .ok_or(CodecError::UnknownFormat)?;
+    match format {
+        AudioFormat::Wav => decode_wav(bytes),
+        AudioFormat::Opus => opus::decode(bytes),
+        AudioFormat::Mp3 | AudioFormat::Flac | AudioFormat::Ogg => decode_symphonia(bytes, format),
+    }
+}
+
+/// Encode samples into `format`.
+///
+/// WAV and FLAC are written as 16-bit PCM and MP3 at 128kbps. Opus accepts
+/// 8, 12, 16, 24, and 48kHz input directly; other rates are resampled to
+/// 48kHz. Ogg Vorbis encoding is not supported.
+pub fn encode(audio: &AudioData, format: AudioFormat) -> CodecResult<Vec<u8>> {
+    if audio.channels == 0 || audio.sample_rate == 0 {
+        return Err(CodecError::encode(
+            format,
+            format!(
+                "invalid layout: {} channel(s) at {}Hz",
+                audio.channels, audio.sample_rate
+            ),
+        ));
+    }
+    match format {
+        AudioFormat::Wav => encode_wav(audio),
+        AudioFormat::Mp3 => encode_mp3(audio),
+        AudioFormat::Flac => encode_flac(audio),
+        AudioFormat::Opus => opus::encode(audio),
+        AudioFormat::Ogg => Err(CodecError::UnsupportedFormat {
+            format,
+            operation: "encoding",
+        }),
+    }
+}
+
+fn to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+}
+
+/// Mono or stereo view of the samples; encoders below only take one or two channels.
+fn mono_or_stereo(audio: &AudioData) -> (Vec<f32>, usize) {
+    if audio.channels <= 2 {
+        return (audio.samples.clone(), audio.channels);
+    }
+    (audio.to_mono().samples, 1)
+}
+
+fn decode_wav(bytes: &[u8]) -> CodecResult<AudioData> {
+    let format = AudioFormat::Wav;
+    let mut reader =
+        hound::WavReader::new(Cursor::new(bytes)).map_err(|e| CodecError::decode(format, e))?;
+    let spec = reader.spec();
+    let samples: Vec<f32> = match (spec.sample_format, spec.bits_per_sample) {
+        (hound::SampleFormat::Float, _) => reader.samples::<f32>().collect::<Result<_, _>>(),
+        (hound::SampleFormat::Int, bits @ 1..=32) => {
+            let scale = (1i64 << (bits - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|v| v as f32 / scale))
+                .collect::<Result<_, _>>()
+        }
+        (hound::SampleFormat::Int, bits) => {
+            return Err(CodecError::decode(
+                format,
+                format!("unsupported bit depth: {bits}"),
+            ));
+        }
+    }
+    .map_err(|e| CodecError::decode(format, e))?;
+
+    Ok(AudioData {
+        samples,
+        channels: spec.channels as usize,
+        sample_rate: spec.sample_rate,
+    })
+}
+
+fn encode_wav(audio: &AudioData) -> CodecResult<Vec<u8>> {
+    let format = AudioFormat::Wav;
+    let spec = hound::WavSpec {
+        channels: audio.channels as u16,
+        sample_rate: audio.sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer =
+        hound::WavWriter::new(&mut cursor, spec).map_err(|e| CodecError::encode(format, e))?;
+    for &sample in &audio.samples {
+        writer
+            .write_sample(to_i16(sample))
+            .map_err(|e| CodecError::encode(format, e))?;
+    }
+    writer
+        .finalize()
+        .map_err(|e| CodecError::encode(format, e))?;
+    Ok(cursor.into_inner())
+}
+
+fn decode_symphonia(bytes: &[u8], format: AudioFormat) -> CodecResult<AudioData> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::DecoderOptions;
+    use symphonia::core::errors::Error as SymphoniaError;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::{MediaSourceStream, MediaSourceStreamOptions};
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let source = Cursor::new(bytes.to_vec());
+    let mss = MediaSourceStream::new(Box::new(source), MediaSourceStreamOptions::default());
+    let mut hint = Hint::new();
+    hint.with_extension(format.extension());
+
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            mss,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| CodecError::decode(format, e))?;
+    let mut reader = probed.format;
+    let track = reader
+        .default_track()
+        .ok_or_else(|| CodecError::decode(format, "no audio track found"))?;
+    let sample_rate = track
+        .codec_params
+        .sample_rate
+        .ok_or_else(|| CodecError::decode(format, "unknown sample rate"))?;
+    let mut channels = track.codec_params.channels.map(|c| c.count()).unwrap_or(0);
+    let total_frames = track.codec_params.n_frames;
+    let track_id = track.id;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| CodecError::decode(format, e))?;
+
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+    let mut samples = Vec::new();
+    loop {
+        let packet = match reader.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(_)) | Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(CodecError::decode(format, e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                channels = decoded.spec().channels.count();
+                let buffer = buffer.get_or_insert_with(|| {
+                    SampleBuffer::new(decoded.capacity() as u64, *decoded.spec())
+                });
+                buffer.copy_interleaved_ref(decoded);
+                samples.extend_from_slice(buffer.samples());
+            }
+            Err(SymphoniaError::IoError(_)) | Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(e) => return Err(CodecError::decode(format, e)),
+        }
+    }
+
+    // The final packet is decoded in full; drop samples past the stream length.
+    let channels = channels.max(1);
+    if let Some(frames) = total_frames {
+        samples.truncate(frames as usize * channels);
+    }
+    Ok(AudioData {
+        samples,
+        channels,
+        sample_rate,
+    })
+}
+
+fn encode_mp3(audio: &AudioData) -> CodecResult<Vec<u8>> {
+    use mp3lame_encoder::{Bitrate, Builder, FlushNoGap, InterleavedPcm, MonoPcm, Quality};
+
+    let format = AudioFormat::Mp3;
+    let (samples, channels) = mono_or_stereo(audio);
+    let mut encoder = Builder::new()
+        .ok_or_else(|| CodecError::encode(format, "failed to allocate LAME encoder"))?
+        .with_num_channels(channels as u8)
+        .and_then(|b| b.with_sample_rate(audio.sample_rate))
+        .and_then(|b| b.with_brate(Bitrate::Kbps128))
+        .and_then(|b| b.with_quality(Quality::Good))
+        .and_then(|b| b.build())
+        .map_err(|e| CodecError::encode(format, e))?;
+
+    let frames = samples.len() / channels;
+    let mut out = Vec::with_capacity(mp3lame_encoder::max_required_buffer_size(frames));
+    let encoded = if channels == 1 {
+        encoder.encode_to_vec(MonoPcm(samples.as_slice()), &mut out)
+    } else {
+        encoder.encode_to_vec(InterleavedPcm(samples.as_slice()), &mut out)
+    };
+    encoded.map_err(|e| CodecError::encode(format, e))?;
+    out.reserve(7200);
+    encoder
+        .flush_to_vec::<FlushNoGap>(&mut out)
+        .map_err(|e| CodecError::encode(format, e))?;
+    Ok(out)
+}
+
+fn encode_flac(audio: &AudioData) -> CodecResult<Vec<u8>> {
+    use flacenc::component::BitRepr;
+    use flacenc::error::Verify;
+
+    let format = AudioFormat::Flac;
+    let config = flacenc::config::Encoder::default()
+        .into_verified()
+        .map_err(|(_, e)| CodecError::encode(format, e))?;
+    let samples: Vec<i32> = audio.samples.iter().map(|&s| to_i16(s) as i32).collect();
+    let source = flacenc::source::MemSource::from_samples(
+        &samples,
+        audio.channels,
+        16,
+        audio.sample_rate as usize,
+    );
+    let stream = flacenc::encode_with_fixed_block_size(&config, source, config.block_size)
+        .map_err(|e| CodecError::encode(format, format!("{e:?}")))?;
+    let mut sink = flacenc::bitsink::ByteSink::new();
+    stream
+        .write(&mut sink)
+        .map_err(|e| CodecError::encode(format, e))?;
+    Ok(sink.as_slice().to_vec())
+}
+
+/// Ogg Opus (RFC 7845) on top of the pure-Rust libopus port.
+mod opus {
+    use super::{CodecError, CodecResult, mono_or_stereo};
+    use crate::{AudioData, AudioFormat};
+    use ogg::{PacketReader, PacketWriteEndInfo, PacketWriter};
+    use std::io::Cursor;
+
+    /// Opus granule positions always count 48kHz samples.
+    const GRANULE_RATE: u32 = 48_000;
+    const ENCODER_RATES: [u32; 5] = [8_000, 12_000, 16_000, 24_000, 48_000];
+    /// Largest Opus frame (120ms at 48kHz) per channel.
+    const MAX_FRAME: usize = 5_760;
+    const MAX_PACKET: usize = 4_000;
+    const SERIAL: u32 = 0x4155_4147;
+
+    fn error(format: &str, code: i32) -> String {
+        format!("{format} (opus error {code})")
+    }
+
+    /// Owned libopus encoder, destroyed on drop.
+    struct Encoder(*mut libopus::OpusEncoder);
+
+    impl Encoder {
+        fn new(rate: u32, channels: usize) -> CodecResult<Self> {
+            let mut code = 0;
+            // SAFETY: arguments are plain values and `code` outlives the call.
+            let ptr = unsafe {
+                libopus::opus_encoder_create(
+                    rate as i32,
+                    channels as i32,
+                    libopus::OPUS_APPLICATION_AUDIO,
+                    &mut code,
+                )
+            };
+            if ptr.is_null() || code != libopus::OPUS_OK {
+                return Err(CodecError::encode(
+                    AudioFormat::Opus,
+                    error("failed to create encoder", code),
+                ));
+            }
+            Ok(Self(ptr))
+        }
+
+        fn lookahead(&mut self) -> i32 {
+            let mut lookahead = 0i32;
+            // SAFETY: the encoder is valid and the request writes one i32.
+            unsafe {
+                libopus::opus_encoder_ctl!(
+                    self.0,
+                    libopus::OPUS_GET_LOOKAHEAD_REQUEST,
+                    &mut lookahead
+                );
+            }
+            lookahead
+        }
+
+        fn encode(&mut self, pcm: &[f32], frame_size: usize) -> CodecResult<Vec<u8>> {
+            let mut packet = vec![0u8; MAX_PACKET];
+            // SAFETY: `pcm` holds `frame_size * channels` samples and `packet`
+            // has room for `MAX_PACKET` bytes.
+            let len = unsafe {
+                libopus::opus_encode_float(
+                    self.0,
+                    pcm.as_ptr(),
+                    frame_size as i32,
+                    packet.as_mut_ptr(),
+                    MAX_PACKET as i32,
+                )
+            };
+            if len < 0 {
+                return Err(CodecError::encode(
+                    AudioFormat::Opus,
+                    error("failed to encode frame", len),
+                ));
+            }
+            packet.truncate(len as usize);
+            Ok(packet)
+        }
+    }
+
+    impl Drop for Encoder {
+        fn drop(&mut self) {
+            // SAFETY: the pointer came from `opus_encoder_create` and is freed once.
+            unsafe { libopus::opus_encoder_destroy(self.0) }
+        }
+    }
+
+    /// Owned libopus decoder, destroyed on drop.
+    struct Decoder(*mut libopus::OpusDecoder);
+
+    impl Decoder {
+        fn new(channels: usize) -> CodecResult<Self> {
+            let mut code = 0;
+            // SAFETY: arguments are plain values and `code` outlives the call.
+            let ptr = unsafe {
+                libopus::opus_decoder_create(GRANULE_RATE as i32, channels as i32, &mut code)
+            };
+            if ptr.is_null() || code != libopus::OPUS_OK {
+                return Err(CodecError::decode(
+                    AudioFormat::Opus,
+                    error("failed to create decoder", code),
+                ));
+            }
+            Ok(Self(ptr))
+        }
+
+        fn decode(&mut self, packet: &[u8], pcm: &mut [f32]) -> CodecResult<usize> {
+            let channels = pcm.len() / MAX_FRAME;
+            // SAFETY: `pcm` has room for `MAX_FRAME` samples per channel.
+            let frames = unsafe {
+                libopus::opus_decode_float(
+                    self.0,
+                    packet.as_ptr(),
+                    packet.len() as i32,
+                    pcm.as_mut_ptr(),
+                    MAX_FRAME as i32,
+                    0,
+                )
+            };
+            if frames < 0 {
+                return Err(CodecError::decode(
+                    AudioFormat::Opus,
+                    error("failed to decode packet", frames),
+                ));
+            }
+            Ok(frames as usize * channels)
+        }
+    }
+
+    impl Drop for Decoder {
+        fn drop(&mut self) {
+            // SAFETY: the pointer came from `opus_decoder_create` and is freed once.
+            unsafe { libopus::opus_decoder_destroy(self.0) }
+        }
+    }
+
+    pub(super) fn encode(audio: &AudioData) -> CodecResult<Vec<u8>> {
+        let (mut samples, channels) = mono_or_stereo(audio);
+        let mut rate = audio.sample_rate;
+        if !ENCODER_RATES.contains(&rate) {
+            samples = crate::resample::resample_interleaved(
+                &samples,
+                channels,
+                rate,
+                GRANULE_RATE,
+                crate::ResampleQuality::Sinc,
+            );
+            rate = GRANULE_RATE;
+        }
+
+        let mut encoder = Encoder::new(rate, channels)?;
+        let scale = (GRANULE_RATE / rate) as u64;
+        let pre_skip = encoder.lookahead() as u64 * scale;
+
+        let mut head = Vec::with_capacity(19);
+        head.extend_from_slice(b"OpusHead");
+        head.push(1);
+        head.push(channels as u8);
+        head.extend_from_slice(&(pre_skip as u16).to_le_bytes());
+        head.extend_from_slice(&audio.sample_rate.to_le_bytes());
+        head.extend_from_slice(&0i16.to_le_bytes());
+        head.push(0);
+
+        let vendor = concat!("autoagents-speech ", env!("CARGO_PKG_VERSION"));
+        let mut tags = Vec::new();
+        tags.extend_from_slice(b"OpusTags");
+        tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+        tags.extend_from_slice(vendor.as_bytes());
+        tags.extend_from_slice(&0u32.to_le_bytes());
+
+        let ogg_error = |e: std::io::Error| CodecError::encode(AudioFormat::Opus, e);
+        let mut writer = PacketWriter::new(Cursor::new(Vec::new()));
+        writer
+            .write_packet(head, SERIAL, PacketWriteEndInfo::EndPage, 0)
+            .map_err(ogg_error)?;
+        writer
+            .write_packet(tags, SERIAL, PacketWriteEndInfo::EndPage, 0)
+            .map_err(ogg_error)?;
+
+        // 20ms frames; the last one is zero-padded and trimmed by its granule position.
+        let frame_size = rate as usize / 50;
+        let total_frames = samples.len() / channels;
+        let end_granule = pre_skip + total_frames as u64 * scale;
+        // Encode enough frames to flush the encoder's lookahead as well.
+        let lookahead = (pre_skip / scale) as usize;
+        let frame_count = (total_frames + lookahead).div_ceil(frame_size).max(1);
+        let mut pcm = vec![0.0f32; frame_size * channels];
+        for index in 0..frame_count {
+            let start = index * frame_size * channels;
+            let end = (start + frame_size * channels).min(samples.len());
+            pcm.fill(0.0);
+            if start < end {
+                pcm[..end - start].copy_from_slice(&samples[start..end]);
+            }
+            let packet = encoder.encode(&pcm, frame_size)?;
+
+            let last = index + 1 == frame_count;
+            let granule = if last {
+                end_granule
+            } else {
+                pre_skip + ((index + 1) * frame_size) as u64 * scale
+            };
+            let info = if last {
+                PacketWriteEndInfo::EndStream
+            } else {
+                PacketWriteEndInfo::NormalPacket
+            };
+            writer
+                .write_packet(packet, SERIAL, info, granule)
+                .map_err(ogg_error)?;
+        }
+
+        Ok(writer.into_inner().into_inner())
+    }
+
+    pub(super) fn decode(bytes: &[u8]) -> CodecResult<AudioData> {
+        let format = AudioFormat::Opus;
+        let mut reader = PacketReader::new(Cursor::new(bytes));
+        let mut next = || {
+            reader
+                .read_packet()
+                .map_err(|e| CodecError::decode(AudioFormat::Opus, e))
+        };
+
+        let head = next()?.ok_or_else(|| CodecError::decode(format, "empty stream"))?;
+        if head.data.len() < 19 || &head.data[..8] != b"OpusHead" {
+            return Err(CodecError::decode(format, "missing OpusHead header"));
+        }
+        let channels = head.data[9] as usize;
+        if head.data[18] != 0 || !(1..=2).contains(&channels) {
+            return Err(CodecError::decode(
+                format,
+                "only mono and stereo streams are supported",
+            ));
+        }
+        let pre_skip = u16::from_le_bytes([head.data[10], head.data[11]]) as usize * channels;
+        next()?.ok_or_else(|| CodecError::decode(format, "missing OpusTags header"))?;
+
+        let mut decoder = Decoder::new(channels)?;
+        let mut pcm = vec![0.0f32; MAX_FRAME * channels];
+        let mut samples = Vec::new();
+        let mut end_granule = None;
+        while let Some(packet) = next()? {
+            let len = decoder.decode(&packet.data, &mut pcm)?;
+            samples.extend_from_slice(&pcm[..len]);
+            if packet.last_in_stream() {
+                end_granule = Some(packet.absgp_page() as usize * channels);
+            }
+        }
+
+        if let Some(end) = end_granule {
+            samples.truncate(end);
+        }
+        samples.drain(..pre_skip.min(samples.len()));
+        Ok(AudioData {
+            samples,
+            channels,
+            sample_rate: GRANULE_RATE,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn tone(sample_rate: u32, channels: usize, secs: f32) -> AudioData {
+        let frames = (sample_rate as f32 * secs) as usize;
+        let samples = (0..frames)
+            .flat_map(|i| {
+                let value = 0.5 * (2.0 * PI * 440.0 * i as f32 / sample_rate as f32).sin();
+                std::iter::repeat_n(value, channels)
+            })
+            .collect();
+        AudioData {
+            samples,
+            channels,
+            sample_rate,
+        }
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_wav_round_trip() {
+        let audio = tone(16_000, 1, 0.25);
+        let bytes = audio.encode(AudioFormat::Wav).unwrap();
+        assert_eq!(AudioFormat::detect(&bytes), Some(AudioFormat::Wav));
+
+        let decoded = AudioData::decode(&bytes, None).unwrap();
+        assert_eq!(decoded.sample_rate, 16_000);
+        assert_eq!(decoded.channels, 1);
+        assert_eq!(decoded.samples.len(), audio.samples.len());
+        assert!((decoded.samples[100] - audio.samples[100]).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_flac_round_trip_is_lossless_at_16_bit() {
+        let audio = tone(24_000, 2, 0.5);
+        let bytes = encode(&audio, AudioFormat::Flac).unwrap();
+        assert_eq!(AudioFormat::detect(&bytes), Some(AudioFormat::Flac));
+
+        let decoded = decode(&bytes, None).unwrap();
+        assert_eq!(decoded.sample_rate, 24_000);
+        assert_eq!(decoded.channels, 2);
+        assert_eq!(decoded.samples.len(), audio.samples.len());
+        let max_error = decoded
+            .samples
+            .iter()
+            .zip(&audio.samples)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0f32, f32::max);
+        assert!(max_error < 1e-3);
+    }
+
+    #[test]
+    fn test_mp3_round_trip() {
+        let audio = tone(24_000, 1, 1.0);
+        let bytes = encode(&audio, AudioFormat::Mp3).unwrap();
+        assert_eq!(AudioFormat::detect(&bytes), Some(AudioFormat::Mp3));
+        assert!(bytes.len() < audio.samples.len() * 2);
+
+        let decoded = decode(&bytes, Some(AudioFormat::Mp3)).unwrap();
+        assert_eq!(decoded.sample_rate, 24_000);
+        assert!(decoded.samples.len() >= audio.samples.len());
+        assert!((rms(&decoded.samples) - rms(&audio.samples)).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_opus_round_trip_trims_padding() {
+        let audio = tone(16_000, 1, 0.51);
+        let bytes = encode(&audio, AudioFormat::Opus).unwrap();
+        assert_eq!(AudioFormat::detect(&bytes), Some(AudioFormat::Opus));
+
+        let decoded = decode(&bytes, None).unwrap();
+        assert_eq!(decoded.sample_rate, 48_000);
+        assert_eq!(decoded.channels, 1);
+        assert_eq!(decoded.samples.len(), audio.samples.len() * 3);
+        let middle = &decoded.samples[4_800..decoded.samples.len() - 4_800];
+        assert!((rms(middle) - rms(&audio.samples)).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_opus_resamples_unsupported_rates() {
+        let audio = tone(44_100, 2, 0.2);
+        let decoded = decode(&encode(&audio, AudioFormat::Opus).unwrap(), None).unwrap();
+        assert_eq!(decoded.channels, 2);
+        assert_eq!(decoded.samples.len(), 9_600 * 2);
+    }
+
+    #[test]
+    fn test_unsupported_and_unknown_formats() {
+        let audio = tone(16_000, 1, 0.1);
+        assert!(matches!(
+            encode(&audio, AudioFormat::Ogg),
+            Err(CodecError::UnsupportedFormat { .. })
+        ));
+        assert!(matches!(
+            decode(b"not audio", None),
+            Err(CodecError::UnknownFormat)
+        ));
+    }
+}
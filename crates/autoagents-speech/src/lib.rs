@@ -16,8 +16,10 @@
 //! ### STT (Speech-to-Text)
 //! - **Transcription**: Convert audio to text
 //! - **Streaming Support**: Real-time audio transcription
+//! - **Live Transcription**: Feed audio frames and receive interim and final transcripts
 //! - **Timestamp Support**: Token-level timestamps for transcriptions
 //! - **Multilingual**: Support for multiple languages with auto-detection
+//! - **Diarization**: Speaker labels per segment
 //!
 //! ## Architecture
 //!
@@ -38,7 +40,10 @@
 //! Enable providers using feature flags:
 //! - `pocket-tts`: Pocket-TTS model support (TTS)
 //! - `parakeet`: Parakeet (NVIDIA) model support (STT)
+//! - `elevenlabs`: ElevenLabs cloud API with websocket streaming (TTS)
+//! - `openai-realtime`: OpenAI Realtime API for speech-to-speech sessions with tool calling
 //! - `vad`: Silero VAD support (speech segmentation)
+//! - `codec`: MP3/FLAC/Opus encoding and decoding of [`AudioData`]
 //!
 
 pub mod error;
@@ -52,20 +57,33 @@ pub mod providers;
 // TTS utilities (sentence chunking, streaming pipeline)
 pub mod tts;
 
+// STT utilities (live transcription)
+pub mod stt;
+
+// Sample-rate conversion and channel mixing
+pub mod resample;
+
 // Re-export main TTS types
 pub use error::{TTSError, TTSResult};
-pub use provider::{TTSModelsProvider, TTSProvider, TTSSpeechProvider};
+pub use provider::{TTSModelsProvider, TTSProvider, TTSSpeechProvider, TTSVoiceProvider};
+pub use resample::ResampleQuality;
 pub use tts::{ChunkerConfig, SentenceChunker, StreamingTtsPipeline};
 pub use types::{
     AudioChunk, AudioData, AudioFormat, ModelInfo, SharedAudioData, SpeechRequest, SpeechResponse,
-    VoiceIdentifier,
+    VoiceIdentifier, VoiceInfo,
 };
 
 // Re-export main STT types
 pub use error::{STTError, STTResult};
 pub use model_source::ModelSource;
 pub use provider::{STTModelsProvider, STTProvider, STTSpeechProvider};
-pub use types::{TextChunk, TokenTimestamp, TranscriptionRequest, TranscriptionResponse};
+pub use stt::{
+    BatchOptions, BatchProgress, CaptionFormat, Diarizer, PartialTranscript, transcribe_to_captions,
+};
+pub use types::{
+    DiarizationOptions, SpeakerSegment, TextChunk, TokenTimestamp, TranscriptionRequest,
+    TranscriptionResponse,
+};
 
 #[cfg(feature = "playback")]
 pub mod playback;
@@ -73,5 +91,8 @@ pub mod playback;
 #[cfg(feature = "audio-capture")]
 pub mod audio_capture;
 
+#[cfg(feature = "codec")]
+pub mod codec;
+
 #[cfg(feature = "vad")]
 pub mod vad;
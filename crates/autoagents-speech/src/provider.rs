@@ -1,6 +1,7 @@
+use crate::stt::BatchOptions;
 use crate::{
-    AudioChunk, AudioFormat, ModelInfo, STTResult, SpeechRequest, SpeechResponse, TTSResult,
-    TextChunk, TranscriptionRequest, TranscriptionResponse,
+    AudioChunk, AudioData, AudioFormat, ModelInfo, STTResult, SpeechRequest, SpeechResponse,
+    TTSResult, TextChunk, TranscriptionRequest, TranscriptionResponse, VoiceIdentifier, VoiceInfo,
 };
 use async_trait::async_trait;
 use futures::Stream;
@@ -74,12 +75,43 @@ pub trait TTSModelsProvider: Send + Sync {
     /// Current model information
     fn get_current_model(&self) -> ModelInfo;
 
+    /// List voices that can be used in `SpeechRequest::voice` (optional)
+    ///
+    /// # Returns
+    /// List of available voice information
+    async fn list_voices(&self) -> TTSResult<Vec<VoiceInfo>> {
+        Ok(vec![])
+    }
+
     /// Get supported languages
     fn supported_languages(&self) -> Vec<String> {
         vec!["en".to_string()]
     }
 }
 
+/// Trait for TTS providers that can create voices from reference audio
+#[async_trait]
+pub trait TTSVoiceProvider: Send + Sync {
+    /// Create a voice from a reference recording (required)
+    ///
+    /// # Arguments
+    /// * `voice_id` - Id under which the voice is stored
+    /// * `reference` - Sample of the speaker to imitate
+    ///
+    /// # Returns
+    /// Identifier selecting the new voice in `SpeechRequest::voice`
+    async fn clone_voice(&self, voice_id: &str, reference: AudioData)
+    -> TTSResult<VoiceIdentifier>;
+
+    /// Remove a cloned voice (optional)
+    async fn delete_voice(&self, voice_id: &str) -> TTSResult<()> {
+        Err(crate::error::TTSError::Other(
+            format!("cannot delete cloned voice '{voice_id}'"),
+            "voice deletion not supported by this provider".to_string(),
+        ))
+    }
+}
+
 /// Marker trait for STT providers
 ///
 /// This trait combines all STT capabilities into a single provider interface.
@@ -114,6 +146,47 @@ pub trait STTSpeechProvider: Send + Sync {
         ))
     }
 
+    /// Transcribe live audio frames as they arrive (optional)
+    ///
+    /// Frames must be mono at `supported_sample_rate()`; a frame with
+    /// `is_final` set (or the end of the input stream) ends the session.
+    /// The output yields interim chunks (`is_final == false`) holding the
+    /// utterance heard so far, and one final chunk per completed utterance.
+    ///
+    /// # Arguments
+    /// * `frames` - Stream of captured audio frames
+    ///
+    /// # Returns
+    /// Stream of interim and final text chunks
+    async fn transcribe_frames<'a>(
+        &'a self,
+        _frames: Pin<Box<dyn Stream<Item = AudioChunk> + Send + 'a>>,
+    ) -> STTResult<Pin<Box<dyn Stream<Item = STTResult<TextChunk>> + Send + 'a>>> {
+        Err(crate::error::STTError::StreamingNotSupported(
+            "Not Supported".to_string(),
+        ))
+    }
+
+    /// Transcribe several requests with bounded parallelism
+    ///
+    /// The default runs up to `options.concurrency` calls to `transcribe` at
+    /// once. Providers that share a single model instance still accept the
+    /// batch; their requests simply queue on the model.
+    ///
+    /// # Arguments
+    /// * `requests` - Requests to transcribe
+    /// * `options` - Concurrency limit and optional progress channel
+    ///
+    /// # Returns
+    /// One result per request, in request order
+    async fn transcribe_many(
+        &self,
+        requests: Vec<TranscriptionRequest>,
+        options: BatchOptions,
+    ) -> Vec<STTResult<TranscriptionResponse>> {
+        crate::stt::batch::transcribe_concurrently(self, requests, options).await
+    }
+
     /// Check if streaming is supported (default: false)
     fn supports_streaming(&self) -> bool {
         false
@@ -207,6 +280,7 @@ mod tests {
             voice: VoiceIdentifier::new("test"),
             format: AudioFormat::Wav,
             sample_rate: None,
+            extensions: Default::default(),
         };
 
         let err = match provider.generate_speech_stream(request).await {
@@ -228,6 +302,30 @@ mod tests {
         assert_eq!(provider.supported_languages(), vec!["en".to_string()]);
     }
 
+    #[async_trait]
+    impl TTSVoiceProvider for DummyProvider {
+        async fn clone_voice(
+            &self,
+            voice_id: &str,
+            _reference: AudioData,
+        ) -> TTSResult<VoiceIdentifier> {
+            Ok(VoiceIdentifier::cloned(voice_id))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_delete_voice_not_supported() {
+        let provider = DummyProvider;
+        let reference = AudioData {
+            samples: vec![0.0; 16],
+            channels: 1,
+            sample_rate: 24000,
+        };
+        let voice = provider.clone_voice("brand", reference).await.unwrap();
+        assert_eq!(voice.cloned_id(), Some("brand"));
+        assert!(provider.delete_voice("brand").await.is_err());
+    }
+
     #[derive(Debug)]
     struct DummySTTProvider;
 
@@ -241,6 +339,7 @@ mod tests {
                 text: format!("Transcribed {} samples", request.audio.samples.len()),
                 timestamps: None,
                 duration_ms: 0,
+                segments: None,
             })
         }
     }
@@ -270,6 +369,7 @@ mod tests {
             }),
             language: None,
             include_timestamps: false,
+            diarization: None,
         };
 
         let err = match provider.transcribe_stream(request).await {
@@ -281,6 +381,12 @@ mod tests {
             crate::error::STTError::StreamingNotSupported(_)
         ));
         assert!(!provider.supports_streaming());
+
+        let frames = Box::pin(futures::stream::empty());
+        assert!(matches!(
+            provider.transcribe_frames(frames).await,
+            Err(crate::error::STTError::StreamingNotSupported(_))
+        ));
     }
 
     #[test]
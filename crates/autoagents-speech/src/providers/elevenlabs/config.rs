@@ -0,0 +1,183 @@
+//! Configuration for the ElevenLabs provider
+
+use crate::SpeechRequest;
+use serde::{Deserialize, Serialize};
+
+/// Environment variable read by [`ElevenLabsConfig::from_env`]
+pub const ELEVENLABS_API_KEY_ENV: &str = "ELEVENLABS_API_KEY";
+
+/// Configuration for the ElevenLabs provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElevenLabsConfig {
+    /// API key sent as `xi-api-key`
+    pub api_key: String,
+
+    /// Model used for synthesis (default: `eleven_multilingual_v2`)
+    pub model_id: String,
+
+    /// REST base URL; the websocket URL is derived from it
+    pub base_url: String,
+
+    /// Output sample rate when the request does not set one (default: 24000)
+    pub sample_rate: u32,
+
+    /// Voice used when the request voice name is empty
+    #[serde(default)]
+    pub default_voice: Option<String>,
+
+    /// Voice settings used when the request carries none
+    #[serde(default)]
+    pub voice_settings: Option<ElevenLabsVoiceSettings>,
+}
+
+impl ElevenLabsConfig {
+    /// Create a configuration with the default model and endpoint
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            model_id: "eleven_multilingual_v2".to_string(),
+            base_url: "https://api.elevenlabs.io".to_string(),
+            sample_rate: 24000,
+            default_voice: None,
+            voice_settings: None,
+        }
+    }
+
+    /// Create a configuration from `ELEVENLABS_API_KEY`
+    pub fn from_env() -> Option<Self> {
+        std::env::var(ELEVENLABS_API_KEY_ENV).ok().map(Self::new)
+    }
+
+    pub fn with_model(mut self, model_id: impl Into<String>) -> Self {
+        self.model_id = model_id.into();
+        self
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    pub fn with_default_voice(mut self, voice_id: impl Into<String>) -> Self {
+        self.default_voice = Some(voice_id.into());
+        self
+    }
+
+    pub fn with_voice_settings(mut self, settings: ElevenLabsVoiceSettings) -> Self {
+        self.voice_settings = Some(settings);
+        self
+    }
+}
+
+/// Per-request ElevenLabs voice settings
+///
+/// Attach to a request with [`ElevenLabsVoiceSettings::apply`], or set the
+/// `voice_settings` extension to the equivalent JSON object.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ElevenLabsVoiceSettings {
+    /// Lower values add expressiveness, higher values are more consistent (0.0 - 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability: Option<f32>,
+
+    /// How closely the output should match the original voice (0.0 - 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub similarity_boost: Option<f32>,
+
+    /// Style exaggeration (0.0 - 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<f32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub use_speaker_boost: Option<bool>,
+
+    /// Playback speed multiplier (0.7 - 1.2)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+}
+
+impl ElevenLabsVoiceSettings {
+    /// `SpeechRequest` extension key holding the settings
+    pub const EXTENSION: &'static str = "voice_settings";
+
+    pub fn new(stability: f32, similarity_boost: f32) -> Self {
+        Self {
+            stability: Some(stability),
+            similarity_boost: Some(similarity_boost),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_style(mut self, style: f32) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    pub fn with_speaker_boost(mut self, enabled: bool) -> Self {
+        self.use_speaker_boost = Some(enabled);
+        self
+    }
+
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    /// Store these settings in the request's extensions
+    pub fn apply(&self, request: SpeechRequest) -> SpeechRequest {
+        let value = serde_json::to_value(self).unwrap_or_default();
+        request.with_extension(Self::EXTENSION, value)
+    }
+
+    /// Read settings from the request's extensions
+    pub fn from_request(request: &SpeechRequest) -> Option<Self> {
+        request
+            .extension(Self::EXTENSION)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AudioFormat, VoiceIdentifier};
+
+    #[test]
+    fn test_default_config() {
+        let config = ElevenLabsConfig::new("key");
+        assert_eq!(config.model_id, "eleven_multilingual_v2");
+        assert_eq!(config.base_url, "https://api.elevenlabs.io");
+        assert_eq!(config.sample_rate, 24000);
+        assert!(config.voice_settings.is_none());
+    }
+
+    #[test]
+    fn test_voice_settings_round_trip_through_extensions() {
+        let request = SpeechRequest {
+            text: "hi".to_string(),
+            voice: VoiceIdentifier::new("voice"),
+            format: AudioFormat::Wav,
+            sample_rate: None,
+            extensions: Default::default(),
+        };
+        let settings = ElevenLabsVoiceSettings::new(0.4, 0.8).with_speaker_boost(true);
+        let request = settings.apply(request);
+
+        assert_eq!(
+            request.extension("voice_settings"),
+            Some(&serde_json::json!({
+                "stability": 0.4f32,
+                "similarity_boost": 0.8f32,
+                "use_speaker_boost": true,
+            }))
+        );
+        assert_eq!(
+            ElevenLabsVoiceSettings::from_request(&request),
+            Some(settings)
+        );
+    }
+}
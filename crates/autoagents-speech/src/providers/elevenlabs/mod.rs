@@ -0,0 +1,46 @@
+//! ElevenLabs provider for AutoAgents Speech framework
+//!
+//! This module provides an ElevenLabs cloud implementation of the TTS traits,
+//! including websocket streaming and voice listing.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use autoagents_speech::providers::elevenlabs::{
+//!     ElevenLabs, ElevenLabsConfig, ElevenLabsVoiceSettings,
+//! };
+//! use autoagents_speech::{AudioFormat, SpeechRequest, TTSSpeechProvider, VoiceIdentifier};
+//! use futures::StreamExt;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let provider = ElevenLabs::new(ElevenLabsConfig::new("ELEVENLABS_API_KEY"));
+//!
+//!     let request = SpeechRequest {
+//!         text: "Hello, world!".to_string(),
+//!         voice: VoiceIdentifier::new("21m00Tcm4TlvDq8ikWAM"),
+//!         format: AudioFormat::Wav,
+//!         sample_rate: Some(24000),
+//!         extensions: Default::default(),
+//!     };
+//!     let request = ElevenLabsVoiceSettings::new(0.4, 0.8).apply(request);
+//!
+//!     let mut stream = provider.generate_speech_stream(request).await?;
+//!     while let Some(chunk) = stream.next().await {
+//!         let chunk = chunk?;
+//!         // Play chunk.samples...
+//!         if chunk.is_final {
+//!             break;
+//!         }
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+pub mod config;
+
+mod provider;
+
+// Re-exports
+pub use config::{ELEVENLABS_API_KEY_ENV, ElevenLabsConfig, ElevenLabsVoiceSettings};
+pub use provider::ElevenLabs;
@@ -0,0 +1,486 @@
+//! ElevenLabs provider implementation
+//!
+//! Uses the REST API for one-shot synthesis and the `stream-input` websocket
+//! endpoint for streaming. Audio is requested as raw 16-bit PCM so it maps
+//! directly onto `AudioData`/`AudioChunk` without decoding.
+
+use super::config::{ElevenLabsConfig, ElevenLabsVoiceSettings};
+use crate::{
+    AudioChunk, AudioData, AudioFormat, ModelInfo, SpeechRequest, SpeechResponse, TTSError,
+    TTSModelsProvider, TTSProvider, TTSResult, TTSSpeechProvider, VoiceInfo,
+};
+use async_trait::async_trait;
+use futures::{SinkExt, Stream, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+use std::pin::Pin;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+
+const PROVIDER: &str = "ElevenLabs";
+
+/// PCM output rates offered by the ElevenLabs API
+const PCM_SAMPLE_RATES: [u32; 6] = [8000, 16000, 22050, 24000, 44100, 48000];
+
+fn provider_error(message: impl Into<String>) -> TTSError {
+    TTSError::ProviderError(message.into(), PROVIDER.to_string())
+}
+
+/// ElevenLabs TTS provider
+pub struct ElevenLabs {
+    config: ElevenLabsConfig,
+    client: reqwest::Client,
+}
+
+impl ElevenLabs {
+    /// Create a new ElevenLabs provider
+    pub fn new(config: ElevenLabsConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &ElevenLabsConfig {
+        &self.config
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!("{}/v1/{path}", self.config.base_url.trim_end_matches('/'))
+    }
+
+    fn websocket_url(&self, voice_id: &str, output_format: &str) -> String {
+        let base = self.config.base_url.trim_end_matches('/');
+        let base = base
+            .strip_prefix("https://")
+            .map(|rest| format!("wss://{rest}"))
+            .or_else(|| {
+                base.strip_prefix("http://")
+                    .map(|rest| format!("ws://{rest}"))
+            })
+            .unwrap_or_else(|| base.to_string());
+        format!(
+            "{base}/v1/text-to-speech/{voice_id}/stream-input?model_id={}&output_format={output_format}",
+            self.config.model_id
+        )
+    }
+
+    fn voice_id(&self, request: &SpeechRequest) -> TTSResult<String> {
+        if !request.voice.name.is_empty() {
+            return Ok(request.voice.name.clone());
+        }
+        self.config.default_voice.clone().ok_or_else(|| {
+            TTSError::InvalidVoiceData(
+                "no voice id in request and no default voice configured".to_string(),
+                PROVIDER.to_string(),
+            )
+        })
+    }
+
+    fn sample_rate(&self, request: &SpeechRequest) -> TTSResult<u32> {
+        let sample_rate = request.sample_rate.unwrap_or(self.config.sample_rate);
+        if PCM_SAMPLE_RATES.contains(&sample_rate) {
+            Ok(sample_rate)
+        } else {
+            Err(provider_error(format!(
+                "unsupported sample rate {sample_rate}Hz, expected one of {PCM_SAMPLE_RATES:?}"
+            )))
+        }
+    }
+
+    fn voice_settings(&self, request: &SpeechRequest) -> Option<ElevenLabsVoiceSettings> {
+        ElevenLabsVoiceSettings::from_request(request)
+            .or_else(|| self.config.voice_settings.clone())
+    }
+
+    async fn get_json<T: for<'de> Deserialize<'de>>(&self, path: &str) -> TTSResult<T> {
+        let response = self
+            .client
+            .get(self.api_url(path))
+            .header("xi-api-key", &self.config.api_key)
+            .send()
+            .await
+            .map_err(|e| provider_error(format!("request to /v1/{path} failed: {e}")))?;
+        let response = check_status(response).await?;
+        response
+            .json()
+            .await
+            .map_err(|e| provider_error(format!("invalid /v1/{path} response: {e}")))
+    }
+}
+
+async fn check_status(response: reqwest::Response) -> TTSResult<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(provider_error(format!("HTTP {status}: {body}")))
+}
+
+/// Convert little-endian 16-bit PCM into normalized samples
+fn pcm16_to_samples(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(2)
+        .map(|pair| i16::from_le_bytes([pair[0], pair[1]]) as f32 / 32768.0)
+        .collect()
+}
+
+/// Message received on the `stream-input` websocket
+#[derive(Debug, Deserialize)]
+struct StreamMessage {
+    #[serde(default)]
+    audio: Option<String>,
+    #[serde(default, rename = "isFinal")]
+    is_final: Option<bool>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// Turn a websocket message into an audio chunk, if it carries audio or ends the stream
+fn parse_stream_message(text: &str, sample_rate: u32) -> TTSResult<Option<AudioChunk>> {
+    let message: StreamMessage = serde_json::from_str(text)
+        .map_err(|e| provider_error(format!("invalid stream message: {e}")))?;
+    if let Some(error) = message.error {
+        let details = message.message.unwrap_or_default();
+        return Err(provider_error(
+            format!("{error} {details}").trim().to_string(),
+        ));
+    }
+    let is_final = message.is_final.unwrap_or(false);
+    let samples = match message.audio.filter(|audio| !audio.is_empty()) {
+        Some(audio) => {
+            let bytes = base64::Engine::decode(
+                &base64::engine::general_purpose::STANDARD,
+                audio.as_bytes(),
+            )
+            .map_err(|e| provider_error(format!("invalid audio payload: {e}")))?;
+            pcm16_to_samples(&bytes)
+        }
+        None if is_final => Vec::new(),
+        None => return Ok(None),
+    };
+    Ok(Some(AudioChunk {
+        samples,
+        sample_rate,
+        is_final,
+    }))
+}
+
+impl TTSProvider for ElevenLabs {}
+
+#[async_trait]
+impl TTSSpeechProvider for ElevenLabs {
+    async fn generate_speech(&self, request: SpeechRequest) -> TTSResult<SpeechResponse> {
+        let voice_id = self.voice_id(&request)?;
+        let sample_rate = self.sample_rate(&request)?;
+
+        let mut body = json!({
+            "text": request.text,
+            "model_id": self.config.model_id,
+        });
+        if let Some(settings) = self.voice_settings(&request) {
+            body["voice_settings"] = json!(settings);
+        }
+
+        let response = self
+            .client
+            .post(self.api_url(&format!("text-to-speech/{voice_id}")))
+            .query(&[("output_format", format!("pcm_{sample_rate}"))])
+            .header("xi-api-key", &self.config.api_key)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| {
+                TTSError::GenerationFailed(e.to_string(), request.text.len(), voice_id.clone())
+            })?;
+        let bytes = check_status(response).await?.bytes().await.map_err(|e| {
+            TTSError::GenerationFailed(e.to_string(), request.text.len(), voice_id.clone())
+        })?;
+
+        let samples = pcm16_to_samples(&bytes);
+        let duration_ms = (samples.len() as f64 / sample_rate as f64 * 1000.0) as u64;
+        Ok(SpeechResponse {
+            audio: AudioData {
+                samples,
+                channels: 1,
+                sample_rate,
+            },
+            text: request.text,
+            duration_ms,
+        })
+    }
+
+    async fn generate_speech_stream<'a>(
+        &'a self,
+        request: SpeechRequest,
+    ) -> TTSResult<Pin<Box<dyn Stream<Item = TTSResult<AudioChunk>> + Send + 'a>>> {
+        let voice_id = self.voice_id(&request)?;
+        let sample_rate = self.sample_rate(&request)?;
+        let url = self.websocket_url(&voice_id, &format!("pcm_{sample_rate}"));
+
+        let mut ws_request = url
+            .into_client_request()
+            .map_err(|e| provider_error(format!("invalid websocket url: {e}")))?;
+        let api_key = HeaderValue::from_str(&self.config.api_key)
+            .map_err(|e| provider_error(format!("invalid api key: {e}")))?;
+        ws_request.headers_mut().insert("xi-api-key", api_key);
+
+        if rustls::crypto::CryptoProvider::get_default().is_none() {
+            let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        }
+        let (mut socket, _) = tokio_tungstenite::connect_async(ws_request)
+            .await
+            .map_err(|e| provider_error(format!("websocket connect failed: {e}")))?;
+
+        // The first message opens the stream and carries the voice settings,
+        // an empty text message ends the input.
+        let mut open = json!({ "text": " " });
+        if let Some(settings) = self.voice_settings(&request) {
+            open["voice_settings"] = json!(settings);
+        }
+        let text = format!("{} ", request.text.trim_end());
+        for message in [open, json!({ "text": text }), json!({ "text": "" })] {
+            socket
+                .send(Message::text(message.to_string()))
+                .await
+                .map_err(|e| provider_error(format!("websocket send failed: {e}")))?;
+        }
+
+        let stream = futures::stream::unfold(Some(socket), move |socket| async move {
+            let mut socket = socket?;
+            let final_chunk = AudioChunk {
+                samples: Vec::new(),
+                sample_rate,
+                is_final: true,
+            };
+            loop {
+                match socket.next().await {
+                    Some(Ok(Message::Text(text))) => {
+                        match parse_stream_message(text.as_str(), sample_rate) {
+                            Ok(Some(chunk)) if chunk.is_final => {
+                                let _ = socket.close(None).await;
+                                return Some((Ok(chunk), None));
+                            }
+                            Ok(Some(chunk)) => return Some((Ok(chunk), Some(socket))),
+                            Ok(None) => continue,
+                            Err(err) => return Some((Err(err), None)),
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => return Some((Ok(final_chunk), None)),
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => {
+                        return Some((
+                            Err(provider_error(format!("websocket receive failed: {e}"))),
+                            None,
+                        ));
+                    }
+                }
+            }
+        });
+        Ok(Box::pin(stream))
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn supported_formats(&self) -> Vec<AudioFormat> {
+        vec![AudioFormat::Wav]
+    }
+
+    fn default_sample_rate(&self) -> u32 {
+        self.config.sample_rate
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiLanguage {
+    language_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiModel {
+    model_id: String,
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    can_do_text_to_speech: bool,
+    #[serde(default)]
+    languages: Vec<ApiLanguage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiVerifiedLanguage {
+    language: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiVoice {
+    voice_id: String,
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    verified_languages: Vec<ApiVerifiedLanguage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiVoices {
+    voices: Vec<ApiVoice>,
+}
+
+impl From<ApiVoice> for VoiceInfo {
+    fn from(voice: ApiVoice) -> Self {
+        let mut languages: Vec<String> = voice
+            .verified_languages
+            .into_iter()
+            .map(|language| language.language)
+            .collect();
+        languages.dedup();
+        VoiceInfo {
+            id: voice.voice_id,
+            name: voice.name,
+            description: voice.description,
+            languages,
+        }
+    }
+}
+
+#[async_trait]
+impl TTSModelsProvider for ElevenLabs {
+    async fn list_models(&self) -> TTSResult<Vec<ModelInfo>> {
+        let models: Vec<ApiModel> = self.get_json("models").await?;
+        Ok(models
+            .into_iter()
+            .filter(|model| model.can_do_text_to_speech)
+            .map(|model| ModelInfo {
+                id: model.model_id,
+                name: model.name,
+                description: model.description,
+                languages: model
+                    .languages
+                    .into_iter()
+                    .map(|language| language.language_id)
+                    .collect(),
+            })
+            .collect())
+    }
+
+    async fn list_voices(&self) -> TTSResult<Vec<VoiceInfo>> {
+        let voices: ApiVoices = self.get_json("voices").await?;
+        Ok(voices.voices.into_iter().map(VoiceInfo::from).collect())
+    }
+
+    fn get_current_model(&self) -> ModelInfo {
+        ModelInfo {
+            id: self.config.model_id.clone(),
+            name: self.config.model_id.clone(),
+            description: None,
+            languages: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VoiceIdentifier;
+
+    fn request(voice: &str) -> SpeechRequest {
+        SpeechRequest {
+            text: "Hello".to_string(),
+            voice: VoiceIdentifier::new(voice),
+            format: AudioFormat::Wav,
+            sample_rate: None,
+            extensions: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_pcm16_to_samples() {
+        let bytes = [0x00, 0x00, 0xff, 0x7f, 0x00, 0x80, 0x01];
+        let samples = pcm16_to_samples(&bytes);
+        assert_eq!(samples.len(), 3);
+        assert_eq!(samples[0], 0.0);
+        assert!((samples[1] - 1.0).abs() < 1e-4);
+        assert_eq!(samples[2], -1.0);
+    }
+
+    #[test]
+    fn test_websocket_url_uses_secure_scheme() {
+        let provider = ElevenLabs::new(ElevenLabsConfig::new("key"));
+        assert_eq!(
+            provider.websocket_url("voice", "pcm_24000"),
+            "wss://api.elevenlabs.io/v1/text-to-speech/voice/stream-input?model_id=eleven_multilingual_v2&output_format=pcm_24000"
+        );
+    }
+
+    #[test]
+    fn test_voice_and_sample_rate_resolution() {
+        let provider = ElevenLabs::new(ElevenLabsConfig::new("key").with_default_voice("fallback"));
+        assert_eq!(provider.voice_id(&request("rachel")).unwrap(), "rachel");
+        assert_eq!(provider.voice_id(&request("")).unwrap(), "fallback");
+        assert_eq!(provider.sample_rate(&request("rachel")).unwrap(), 24000);
+
+        let mut unsupported = request("rachel");
+        unsupported.sample_rate = Some(11025);
+        assert!(provider.sample_rate(&unsupported).is_err());
+    }
+
+    #[test]
+    fn test_request_voice_settings_override_config() {
+        let provider = ElevenLabs::new(
+            ElevenLabsConfig::new("key")
+                .with_voice_settings(ElevenLabsVoiceSettings::new(0.5, 0.5)),
+        );
+        let settings = ElevenLabsVoiceSettings::new(0.2, 0.9);
+        let request = settings.apply(request("rachel"));
+        assert_eq!(provider.voice_settings(&request), Some(settings));
+    }
+
+    #[test]
+    fn test_parse_stream_messages() {
+        let audio = base64::Engine::encode(
+            &base64::engine::general_purpose::STANDARD,
+            [0x00u8, 0x40, 0x00, 0xc0],
+        );
+        let chunk = parse_stream_message(&json!({ "audio": audio }).to_string(), 24000)
+            .unwrap()
+            .expect("audio chunk");
+        assert_eq!(chunk.samples, vec![0.5, -0.5]);
+        assert!(!chunk.is_final);
+
+        let done = parse_stream_message(r#"{"audio":null,"isFinal":true}"#, 24000)
+            .unwrap()
+            .expect("final chunk");
+        assert!(done.is_final && done.samples.is_empty());
+
+        assert!(
+            parse_stream_message(r#"{"normalizedAlignment":{}}"#, 24000)
+                .unwrap()
+                .is_none()
+        );
+        assert!(parse_stream_message(r#"{"error":"quota_exceeded"}"#, 24000).is_err());
+    }
+
+    #[test]
+    fn test_voice_listing_maps_languages() {
+        let voices: ApiVoices = serde_json::from_value(json!({
+            "voices": [{
+                "voice_id": "21m00Tcm4TlvDq8ikWAM",
+                "name": "Rachel",
+                "verified_languages": [{ "language": "en" }, { "language": "en" }]
+            }]
+        }))
+        .unwrap();
+        let voice = VoiceInfo::from(voices.voices.into_iter().next().unwrap());
+        assert_eq!(voice.id, "21m00Tcm4TlvDq8ikWAM");
+        assert_eq!(voice.languages, vec!["en".to_string()]);
+    }
+}
@@ -8,3 +8,9 @@ pub mod pocket_tts;
 
 #[cfg(feature = "parakeet")]
 pub mod parakeet;
+
+#[cfg(feature = "elevenlabs")]
+pub mod elevenlabs;
+
+#[cfg(feature = "openai-realtime")]
+pub mod openai_realtime;
@@ -0,0 +1,264 @@
+//! Configuration for the OpenAI Realtime provider
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// Environment variable read by [`OpenAIRealtimeConfig::from_env`]
+pub const OPENAI_API_KEY_ENV: &str = "OPENAI_API_KEY";
+
+/// Sample rate of the PCM audio exchanged with the Realtime API
+pub const REALTIME_SAMPLE_RATE: u32 = 24000;
+
+/// Configuration for the OpenAI Realtime provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAIRealtimeConfig {
+    /// API key sent as a bearer token
+    pub api_key: String,
+
+    /// Realtime model (default: `gpt-realtime`)
+    pub model: String,
+
+    /// Websocket endpoint, without the `model` query parameter
+    pub base_url: String,
+
+    /// Voice the model answers with (default: `alloy`)
+    pub voice: String,
+
+    /// System instructions for the session
+    #[serde(default)]
+    pub instructions: Option<String>,
+
+    /// Functions the model may call
+    #[serde(default)]
+    pub tools: Vec<RealtimeTool>,
+
+    /// How the server detects the end of a user turn; `None` disables
+    /// detection and turns are committed by the caller
+    #[serde(default)]
+    pub turn_detection: Option<TurnDetection>,
+
+    /// Model used to transcribe user audio; `None` skips input transcripts
+    #[serde(default)]
+    pub input_transcription_model: Option<String>,
+}
+
+impl OpenAIRealtimeConfig {
+    /// Create a configuration with the default model, voice and server VAD
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            model: "gpt-realtime".to_string(),
+            base_url: "wss://api.openai.com/v1/realtime".to_string(),
+            voice: "alloy".to_string(),
+            instructions: None,
+            tools: Vec::new(),
+            turn_detection: Some(TurnDetection::default()),
+            input_transcription_model: None,
+        }
+    }
+
+    /// Create a configuration from `OPENAI_API_KEY`
+    pub fn from_env() -> Option<Self> {
+        std::env::var(OPENAI_API_KEY_ENV).ok().map(Self::new)
+    }
+
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_voice(mut self, voice: impl Into<String>) -> Self {
+        self.voice = voice.into();
+        self
+    }
+
+    pub fn with_instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    pub fn with_tool(mut self, tool: RealtimeTool) -> Self {
+        self.tools.push(tool);
+        self
+    }
+
+    pub fn with_turn_detection(mut self, turn_detection: Option<TurnDetection>) -> Self {
+        self.turn_detection = turn_detection;
+        self
+    }
+
+    pub fn with_input_transcription(mut self, model: impl Into<String>) -> Self {
+        self.input_transcription_model = Some(model.into());
+        self
+    }
+
+    /// Websocket URL including the model
+    pub fn url(&self) -> String {
+        format!(
+            "{}?model={}",
+            self.base_url.trim_end_matches('/'),
+            self.model
+        )
+    }
+
+    /// Session settings sent in the `session.update` event
+    pub(crate) fn session(&self) -> Value {
+        let audio_format = json!({ "type": "audio/pcm", "rate": REALTIME_SAMPLE_RATE });
+        let mut input = json!({
+            "format": audio_format,
+            "turn_detection": self.turn_detection.as_ref().map(TurnDetection::to_json),
+        });
+        if let Some(model) = &self.input_transcription_model {
+            input["transcription"] = json!({ "model": model });
+        }
+
+        let mut session = json!({
+            "type": "realtime",
+            "output_modalities": ["audio"],
+            "audio": {
+                "input": input,
+                "output": { "format": audio_format, "voice": self.voice },
+            },
+        });
+        if let Some(instructions) = &self.instructions {
+            session["instructions"] = json!(instructions);
+        }
+        if !self.tools.is_empty() {
+            session["tools"] = self.tools.iter().map(RealtimeTool::to_json).collect();
+            session["tool_choice"] = json!("auto");
+        }
+        session
+    }
+}
+
+/// A function the model may call during a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RealtimeTool {
+    pub name: String,
+    pub description: String,
+
+    /// JSON schema of the arguments
+    pub parameters: Value,
+}
+
+impl RealtimeTool {
+    pub fn new(name: impl Into<String>, description: impl Into<String>, parameters: Value) -> Self {
+        Self {
+            name: name.into(),
+            description: description.into(),
+            parameters,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "type": "function",
+            "name": self.name,
+            "description": self.description,
+            "parameters": self.parameters,
+        })
+    }
+}
+
+/// Turn detection run by the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TurnDetection {
+    /// Voice activity detection on the input audio
+    ServerVad {
+        /// Activation threshold (0.0 - 1.0)
+        threshold: Option<f32>,
+        /// Audio kept from before speech started
+        prefix_padding_ms: Option<u32>,
+        /// Silence that ends a turn
+        silence_duration_ms: Option<u32>,
+    },
+    /// Model-based end-of-turn detection
+    SemanticVad,
+}
+
+impl Default for TurnDetection {
+    fn default() -> Self {
+        TurnDetection::ServerVad {
+            threshold: None,
+            prefix_padding_ms: None,
+            silence_duration_ms: None,
+        }
+    }
+}
+
+impl TurnDetection {
+    fn to_json(&self) -> Value {
+        match self {
+            TurnDetection::ServerVad {
+                threshold,
+                prefix_padding_ms,
+                silence_duration_ms,
+            } => {
+                let mut value = json!({ "type": "server_vad" });
+                if let Some(threshold) = threshold {
+                    value["threshold"] = json!(threshold);
+                }
+                if let Some(padding) = prefix_padding_ms {
+                    value["prefix_padding_ms"] = json!(padding);
+                }
+                if let Some(silence) = silence_duration_ms {
+                    value["silence_duration_ms"] = json!(silence);
+                }
+                value
+            }
+            TurnDetection::SemanticVad => json!({ "type": "semantic_vad" }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = OpenAIRealtimeConfig::new("key");
+        assert_eq!(
+            config.url(),
+            "wss://api.openai.com/v1/realtime?model=gpt-realtime"
+        );
+        assert_eq!(config.voice, "alloy");
+        assert_eq!(config.turn_detection, Some(TurnDetection::default()));
+    }
+
+    #[test]
+    fn test_session_includes_tools_and_turn_detection() {
+        let config = OpenAIRealtimeConfig::new("key")
+            .with_instructions("Be brief.")
+            .with_input_transcription("gpt-4o-mini-transcribe")
+            .with_tool(RealtimeTool::new(
+                "get_weather",
+                "Current weather for a city",
+                json!({ "type": "object", "properties": { "city": { "type": "string" } } }),
+            ));
+        let session = config.session();
+
+        assert_eq!(session["instructions"], "Be brief.");
+        assert_eq!(session["audio"]["input"]["format"]["rate"], 24000);
+        assert_eq!(
+            session["audio"]["input"]["turn_detection"]["type"],
+            "server_vad"
+        );
+        assert_eq!(
+            session["audio"]["input"]["transcription"]["model"],
+            "gpt-4o-mini-transcribe"
+        );
+        assert_eq!(session["audio"]["output"]["voice"], "alloy");
+        assert_eq!(session["tools"][0]["type"], "function");
+        assert_eq!(session["tools"][0]["name"], "get_weather");
+        assert_eq!(session["tool_choice"], "auto");
+
+        let manual = OpenAIRealtimeConfig::new("key").with_turn_detection(None);
+        assert!(manual.session()["audio"]["input"]["turn_detection"].is_null());
+    }
+}
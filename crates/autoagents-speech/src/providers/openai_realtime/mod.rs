@@ -0,0 +1,55 @@
+//! OpenAI Realtime provider for AutoAgents Speech framework
+//!
+//! This module speaks the OpenAI Realtime websocket API: user audio is
+//! streamed up, the server detects turns, and the model answers with audio,
+//! transcripts and function calls over the same connection.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use autoagents_speech::providers::openai_realtime::{
+//!     OpenAIRealtime, OpenAIRealtimeConfig, RealtimeEvent, RealtimeTool,
+//! };
+//! use futures::StreamExt;
+//! use serde_json::json;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let config = OpenAIRealtimeConfig::new("OPENAI_API_KEY")
+//!         .with_instructions("You are a helpful voice assistant.")
+//!         .with_tool(RealtimeTool::new(
+//!             "get_weather",
+//!             "Current weather for a city",
+//!             json!({ "type": "object", "properties": { "city": { "type": "string" } } }),
+//!         ));
+//!     let (mut sender, mut events) = OpenAIRealtime::new(config).connect().await?;
+//!
+//!     // Feed microphone audio with `sender.send_audio(&audio)` from the capture loop.
+//!     while let Some(event) = events.next().await {
+//!         match event? {
+//!             RealtimeEvent::Audio(chunk) => {
+//!                 // Play chunk.samples...
+//!             }
+//!             RealtimeEvent::SpeechStarted => {
+//!                 // The user interrupted: stop playback.
+//!             }
+//!             RealtimeEvent::FunctionCall { call_id, .. } => {
+//!                 sender.send_tool_output(call_id, r#"{"temp_c": 18}"#).await?;
+//!                 sender.create_response().await?;
+//!             }
+//!             _ => {}
+//!         }
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+pub mod config;
+
+mod session;
+
+// Re-exports
+pub use config::{
+    OPENAI_API_KEY_ENV, OpenAIRealtimeConfig, REALTIME_SAMPLE_RATE, RealtimeTool, TurnDetection,
+};
+pub use session::{OpenAIRealtime, RealtimeEvent, RealtimeEvents, RealtimeSender};
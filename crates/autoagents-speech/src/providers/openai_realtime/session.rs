@@ -0,0 +1,409 @@
+//! Realtime websocket session
+//!
+//! Audio goes up as base64 16-bit PCM in `input_audio_buffer.append` events
+//! and comes back the same way in `response.output_audio.delta` events. Both
+//! the GA and the beta event names are understood on the receiving side.
+
+use super::config::{OpenAIRealtimeConfig, REALTIME_SAMPLE_RATE};
+use crate::{AudioChunk, AudioData, TTSError, TTSResult};
+use futures::stream::{SplitSink, SplitStream};
+use futures::{SinkExt, Stream, StreamExt};
+use serde_json::{Value, json};
+use std::pin::Pin;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+const PROVIDER: &str = "OpenAI Realtime";
+
+type Socket = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Events received from the model, in order
+pub type RealtimeEvents = Pin<Box<dyn Stream<Item = TTSResult<RealtimeEvent>> + Send>>;
+
+fn provider_error(message: impl Into<String>) -> TTSError {
+    TTSError::ProviderError(message.into(), PROVIDER.to_string())
+}
+
+/// Something that happened on the server side of a session
+#[derive(Debug, Clone)]
+pub enum RealtimeEvent {
+    /// The session settings were applied
+    SessionUpdated,
+
+    /// The user started speaking; stop playing any queued model audio
+    SpeechStarted,
+
+    /// The user stopped speaking
+    SpeechStopped,
+
+    /// Transcript of a finished user turn
+    InputTranscript { item_id: String, text: String },
+
+    /// Model audio at [`REALTIME_SAMPLE_RATE`]; the last chunk of a response
+    /// is empty and marked final
+    Audio(AudioChunk),
+
+    /// Part of the transcript of the model's spoken answer
+    TranscriptDelta(String),
+
+    /// Part of a text answer
+    TextDelta(String),
+
+    /// The model wants a function called; answer with
+    /// [`RealtimeSender::send_tool_output`] and then
+    /// [`RealtimeSender::create_response`]
+    FunctionCall {
+        call_id: String,
+        name: String,
+        arguments: String,
+    },
+
+    /// The model finished a response
+    ResponseDone,
+
+    /// The server rejected an event; the session stays open
+    Error {
+        message: String,
+        code: Option<String>,
+    },
+}
+
+/// OpenAI Realtime (websocket) voice provider
+///
+/// Unlike the TTS and STT providers this is conversational: one session
+/// takes user audio, detects turns, calls tools and speaks the answers.
+pub struct OpenAIRealtime {
+    config: OpenAIRealtimeConfig,
+}
+
+impl OpenAIRealtime {
+    /// Create a new OpenAI Realtime provider
+    pub fn new(config: OpenAIRealtimeConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &OpenAIRealtimeConfig {
+        &self.config
+    }
+
+    /// Open a session and apply the configured session settings
+    ///
+    /// The sender and the event stream are independent, so audio can be
+    /// sent from the capture loop while events are read elsewhere.
+    pub async fn connect(&self) -> TTSResult<(RealtimeSender, RealtimeEvents)> {
+        let mut ws_request = self
+            .config
+            .url()
+            .into_client_request()
+            .map_err(|e| provider_error(format!("invalid websocket url: {e}")))?;
+        let authorization = HeaderValue::from_str(&format!("Bearer {}", self.config.api_key))
+            .map_err(|e| provider_error(format!("invalid api key: {e}")))?;
+        ws_request
+            .headers_mut()
+            .insert("Authorization", authorization);
+
+        if rustls::crypto::CryptoProvider::get_default().is_none() {
+            let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        }
+        let (socket, _) = tokio_tungstenite::connect_async(ws_request)
+            .await
+            .map_err(|e| provider_error(format!("websocket connect failed: {e}")))?;
+        let (sink, stream) = socket.split();
+
+        let mut sender = RealtimeSender { sink };
+        sender
+            .send_event(json!({ "type": "session.update", "session": self.config.session() }))
+            .await?;
+        Ok((sender, event_stream(stream)))
+    }
+}
+
+/// Sending half of a realtime session
+pub struct RealtimeSender {
+    sink: SplitSink<Socket, Message>,
+}
+
+impl RealtimeSender {
+    /// Send a raw client event
+    pub async fn send_event(&mut self, event: Value) -> TTSResult<()> {
+        self.sink
+            .send(Message::text(event.to_string()))
+            .await
+            .map_err(|e| provider_error(format!("websocket send failed: {e}")))
+    }
+
+    /// Append user audio to the input buffer
+    ///
+    /// Audio in another layout is converted to 24 kHz mono first.
+    pub async fn send_audio(&mut self, audio: &AudioData) -> TTSResult<()> {
+        if audio.samples.is_empty() {
+            return Ok(());
+        }
+        let audio = if audio.sample_rate == REALTIME_SAMPLE_RATE && audio.channels == 1 {
+            encode_pcm16(&audio.samples)
+        } else {
+            encode_pcm16(&audio.convert(REALTIME_SAMPLE_RATE, 1).samples)
+        };
+        self.send_event(json!({ "type": "input_audio_buffer.append", "audio": audio }))
+            .await
+    }
+
+    /// End the user turn; only needed when turn detection is disabled
+    pub async fn commit_audio(&mut self) -> TTSResult<()> {
+        self.send_event(json!({ "type": "input_audio_buffer.commit" }))
+            .await
+    }
+
+    /// Drop audio that was appended but not committed
+    pub async fn clear_audio(&mut self) -> TTSResult<()> {
+        self.send_event(json!({ "type": "input_audio_buffer.clear" }))
+            .await
+    }
+
+    /// Add a user text message to the conversation
+    pub async fn send_text(&mut self, text: impl Into<String>) -> TTSResult<()> {
+        self.send_event(json!({
+            "type": "conversation.item.create",
+            "item": {
+                "type": "message",
+                "role": "user",
+                "content": [{ "type": "input_text", "text": text.into() }],
+            },
+        }))
+        .await
+    }
+
+    /// Return the result of a [`RealtimeEvent::FunctionCall`]
+    pub async fn send_tool_output(
+        &mut self,
+        call_id: impl Into<String>,
+        output: impl Into<String>,
+    ) -> TTSResult<()> {
+        self.send_event(json!({
+            "type": "conversation.item.create",
+            "item": {
+                "type": "function_call_output",
+                "call_id": call_id.into(),
+                "output": output.into(),
+            },
+        }))
+        .await
+    }
+
+    /// Ask the model to answer
+    ///
+    /// Server turn detection does this by itself after each user turn, but
+    /// not after text messages or tool outputs.
+    pub async fn create_response(&mut self) -> TTSResult<()> {
+        self.send_event(json!({ "type": "response.create" })).await
+    }
+
+    /// Stop the response in progress, e.g. when the user interrupts
+    pub async fn cancel_response(&mut self) -> TTSResult<()> {
+        self.send_event(json!({ "type": "response.cancel" })).await
+    }
+
+    /// Close the session
+    pub async fn close(mut self) -> TTSResult<()> {
+        self.sink
+            .close()
+            .await
+            .map_err(|e| provider_error(format!("websocket close failed: {e}")))
+    }
+}
+
+fn event_stream(stream: SplitStream<Socket>) -> RealtimeEvents {
+    Box::pin(futures::stream::unfold(Some(stream), |stream| async move {
+        let mut stream = stream?;
+        loop {
+            match stream.next().await {
+                Some(Ok(Message::Text(text))) => match parse_server_event(text.as_str()) {
+                    Ok(Some(event)) => return Some((Ok(event), Some(stream))),
+                    Ok(None) => continue,
+                    Err(err) => return Some((Err(err), None)),
+                },
+                Some(Ok(Message::Close(_))) | None => return None,
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => {
+                    return Some((
+                        Err(provider_error(format!("websocket receive failed: {e}"))),
+                        None,
+                    ));
+                }
+            }
+        }
+    }))
+}
+
+fn string_field(event: &Value, field: &str) -> String {
+    event[field].as_str().unwrap_or_default().to_string()
+}
+
+/// Map a server event onto a [`RealtimeEvent`], skipping the ones callers don't need
+fn parse_server_event(text: &str) -> TTSResult<Option<RealtimeEvent>> {
+    let event: Value = serde_json::from_str(text)
+        .map_err(|e| provider_error(format!("invalid server event: {e}")))?;
+    let event_type = event["type"].as_str().unwrap_or_default();
+
+    let parsed = match event_type {
+        "session.updated" => RealtimeEvent::SessionUpdated,
+        "input_audio_buffer.speech_started" => RealtimeEvent::SpeechStarted,
+        "input_audio_buffer.speech_stopped" => RealtimeEvent::SpeechStopped,
+        "conversation.item.input_audio_transcription.completed" => RealtimeEvent::InputTranscript {
+            item_id: string_field(&event, "item_id"),
+            text: string_field(&event, "transcript"),
+        },
+        "response.output_audio.delta" | "response.audio.delta" => {
+            let bytes = base64::Engine::decode(
+                &base64::engine::general_purpose::STANDARD,
+                event["delta"].as_str().unwrap_or_default(),
+            )
+            .map_err(|e| provider_error(format!("invalid audio payload: {e}")))?;
+            RealtimeEvent::Audio(AudioChunk {
+                samples: decode_pcm16(&bytes),
+                sample_rate: REALTIME_SAMPLE_RATE,
+                is_final: false,
+            })
+        }
+        "response.output_audio.done" | "response.audio.done" => RealtimeEvent::Audio(AudioChunk {
+            samples: Vec::new(),
+            sample_rate: REALTIME_SAMPLE_RATE,
+            is_final: true,
+        }),
+        "response.output_audio_transcript.delta" | "response.audio_transcript.delta" => {
+            RealtimeEvent::TranscriptDelta(string_field(&event, "delta"))
+        }
+        "response.output_text.delta" | "response.text.delta" => {
+            RealtimeEvent::TextDelta(string_field(&event, "delta"))
+        }
+        "response.function_call_arguments.done" => RealtimeEvent::FunctionCall {
+            call_id: string_field(&event, "call_id"),
+            name: string_field(&event, "name"),
+            arguments: string_field(&event, "arguments"),
+        },
+        "response.done" => RealtimeEvent::ResponseDone,
+        "error" => RealtimeEvent::Error {
+            message: event["error"]["message"]
+                .as_str()
+                .unwrap_or("unknown error")
+                .to_string(),
+            code: event["error"]["code"].as_str().map(str::to_string),
+        },
+        _ => return Ok(None),
+    };
+    Ok(Some(parsed))
+}
+
+/// Base64 little-endian 16-bit PCM from normalized samples
+fn encode_pcm16(samples: &[f32]) -> String {
+    let bytes: Vec<u8> = samples
+        .iter()
+        .flat_map(|sample| ((sample.clamp(-1.0, 1.0) * 32767.0) as i16).to_le_bytes())
+        .collect();
+    base64::Engine::encode(&base64::engine::general_purpose::STANDARD, bytes)
+}
+
+/// Normalized samples from little-endian 16-bit PCM
+fn decode_pcm16(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(2)
+        .map(|pair| i16::from_le_bytes([pair[0], pair[1]]) as f32 / 32768.0)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pcm16_round_trip() {
+        let encoded = encode_pcm16(&[0.0, 0.5, -0.5, 2.0]);
+        let bytes =
+            base64::Engine::decode(&base64::engine::general_purpose::STANDARD, encoded).unwrap();
+        let samples = decode_pcm16(&bytes);
+        assert_eq!(samples.len(), 4);
+        assert!((samples[1] - 0.5).abs() < 1e-3);
+        assert!((samples[2] + 0.5).abs() < 1e-3);
+        assert!((samples[3] - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_parse_audio_events() {
+        let delta = base64::Engine::encode(
+            &base64::engine::general_purpose::STANDARD,
+            [0x00u8, 0x40, 0x00, 0xc0],
+        );
+        for event_type in ["response.output_audio.delta", "response.audio.delta"] {
+            let event = json!({ "type": event_type, "delta": delta }).to_string();
+            let Some(RealtimeEvent::Audio(chunk)) = parse_server_event(&event).unwrap() else {
+                panic!("expected audio for {event_type}");
+            };
+            assert_eq!(chunk.samples, vec![0.5, -0.5]);
+            assert_eq!(chunk.sample_rate, 24000);
+            assert!(!chunk.is_final);
+        }
+
+        let done = parse_server_event(r#"{"type":"response.output_audio.done"}"#).unwrap();
+        assert!(matches!(done, Some(RealtimeEvent::Audio(chunk)) if chunk.is_final));
+    }
+
+    #[test]
+    fn test_parse_function_call_and_errors() {
+        let event = json!({
+            "type": "response.function_call_arguments.done",
+            "call_id": "call_1",
+            "name": "get_weather",
+            "arguments": "{\"city\":\"Paris\"}",
+        });
+        let Some(RealtimeEvent::FunctionCall {
+            call_id,
+            name,
+            arguments,
+        }) = parse_server_event(&event.to_string()).unwrap()
+        else {
+            panic!("expected a function call");
+        };
+        assert_eq!(call_id, "call_1");
+        assert_eq!(name, "get_weather");
+        assert_eq!(arguments, "{\"city\":\"Paris\"}");
+
+        let error = json!({
+            "type": "error",
+            "error": { "message": "no active response", "code": "response_cancel_not_active" },
+        });
+        assert!(matches!(
+            parse_server_event(&error.to_string()).unwrap(),
+            Some(RealtimeEvent::Error { message, code })
+                if message == "no active response"
+                    && code.as_deref() == Some("response_cancel_not_active")
+        ));
+
+        assert!(
+            parse_server_event(r#"{"type":"rate_limits.updated"}"#)
+                .unwrap()
+                .is_none()
+        );
+        assert!(parse_server_event("not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_turn_events() {
+        assert!(matches!(
+            parse_server_event(r#"{"type":"input_audio_buffer.speech_started"}"#).unwrap(),
+            Some(RealtimeEvent::SpeechStarted)
+        ));
+        let transcript = json!({
+            "type": "conversation.item.input_audio_transcription.completed",
+            "item_id": "item_1",
+            "transcript": "what's the weather",
+        });
+        assert!(matches!(
+            parse_server_event(&transcript.to_string()).unwrap(),
+            Some(RealtimeEvent::InputTranscript { item_id, text })
+                if item_id == "item_1" && text == "what's the weather"
+        ));
+    }
+}
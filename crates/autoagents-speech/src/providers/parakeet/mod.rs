@@ -31,6 +31,7 @@
 //!         }),
 //!         language: None, // Auto-detect
 //!         include_timestamps: true,
+//!         diarization: None,
 //!     };
 //!
 //!     let response = provider.transcribe(request).await?;
@@ -68,6 +69,7 @@
 //!         }),
 //!         language: Some("en".to_string()),
 //!         include_timestamps: false,
+//!         diarization: None,
 //!     };
 //!
 //!     let mut stream = provider.transcribe_stream(request).await?;
@@ -103,6 +105,7 @@
 //!         }),
 //!         language: Some("en".to_string()),
 //!         include_timestamps: false,
+//!         diarization: None,
 //!     };
 //!
 //!     let mut stream = provider.transcribe_stream(request).await?;
@@ -121,6 +124,40 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! ## Live transcription from audio frames
+//!
+//! For microphone input, feed 16kHz frames as they are captured. Interim chunks
+//! carry the utterance heard so far; final chunks close an utterance.
+//!
+//! ```no_run
+//! use autoagents_speech::providers::parakeet::{Parakeet, ParakeetConfig, ModelVariant};
+//! use autoagents_speech::{AudioChunk, STTSpeechProvider};
+//! use futures::StreamExt;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let provider = Parakeet::new(ParakeetConfig::new(ModelVariant::EOU, "./models/eou"))?;
+//!
+//!     let frames = futures::stream::iter((0..50).map(|i| AudioChunk {
+//!         samples: vec![0.0; 1600], // 100ms frames from the microphone
+//!         sample_rate: 16000,
+//!         is_final: i == 49,
+//!     }));
+//!
+//!     let mut transcripts = provider.transcribe_frames(Box::pin(frames)).await?;
+//!     while let Some(chunk) = transcripts.next().await {
+//!         let chunk = chunk?;
+//!         if chunk.is_final {
+//!             println!("{}", chunk.text);
+//!         } else {
+//!             print!("\r{}", chunk.text);
+//!         }
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
 
 pub mod config;
 pub mod error;
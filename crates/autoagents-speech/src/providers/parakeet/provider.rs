@@ -3,12 +3,14 @@
 use super::config::ParakeetConfig;
 use super::error::Result;
 use super::stt::{ParakeetBackend, validate_language};
+use crate::stt::{Diarizer, assign_words};
 use crate::{
-    ModelInfo, STTModelsProvider, STTProvider, STTResult, STTSpeechProvider, TextChunk,
-    TranscriptionRequest, TranscriptionResponse,
+    AudioChunk, AudioData, ModelInfo, PartialTranscript, STTModelsProvider, STTProvider, STTResult,
+    STTSpeechProvider, SharedAudioData, TextChunk, TranscriptionRequest, TranscriptionResponse,
 };
 use async_trait::async_trait;
-use futures::Stream;
+use futures::{Stream, StreamExt};
+use std::collections::VecDeque;
 use std::pin::Pin;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -55,16 +57,140 @@ impl Parakeet {
     }
 }
 
+/// State of a live `transcribe_frames` session.
+struct LiveSession<'a> {
+    frames: Pin<Box<dyn Stream<Item = AudioChunk> + Send + 'a>>,
+    backend: Arc<Mutex<ParakeetBackend>>,
+    chunk_size: usize,
+    buffer: Vec<f32>,
+    transcript: PartialTranscript,
+    pending: VecDeque<STTResult<TextChunk>>,
+    input_done: bool,
+    finished: bool,
+}
+
+impl LiveSession<'_> {
+    /// Produce the next interim/final chunk, pulling frames as needed.
+    async fn next_chunk(&mut self) -> Option<STTResult<TextChunk>> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(item);
+            }
+            if self.finished {
+                return None;
+            }
+
+            if self.buffer.len() >= self.chunk_size {
+                let chunk: Vec<f32> = self.buffer.drain(..self.chunk_size).collect();
+                self.decode(chunk).await;
+                continue;
+            }
+
+            if self.input_done {
+                if !self.buffer.is_empty() {
+                    let mut chunk = std::mem::take(&mut self.buffer);
+                    chunk.resize(self.chunk_size, 0.0);
+                    self.decode(chunk).await;
+                }
+                if let Some(last) = self.transcript.finish() {
+                    self.pending.push_back(Ok(last));
+                }
+                self.finished = true;
+                continue;
+            }
+
+            match self.frames.next().await {
+                Some(frame) if frame.sample_rate != 16000 => {
+                    self.pending
+                        .push_back(Err(crate::error::STTError::InvalidAudioFormat(
+                            "Parakeet live transcription expects 16kHz frames".to_string(),
+                            16000,
+                            frame.sample_rate,
+                            1,
+                            1,
+                        )));
+                    self.finished = true;
+                }
+                Some(frame) => {
+                    self.buffer.extend_from_slice(&frame.samples);
+                    self.input_done = frame.is_final;
+                }
+                None => self.input_done = true,
+            }
+        }
+    }
+
+    async fn decode(&mut self, chunk: Vec<f32>) {
+        let result = self.backend.lock().await.transcribe_chunk(chunk).await;
+        match result {
+            Ok(delta) => {
+                if let Some(chunk) = self.transcript.push(delta) {
+                    self.pending.push_back(Ok(chunk));
+                }
+            }
+            Err(err) => {
+                self.pending.push_back(Err(err.into()));
+                self.finished = true;
+            }
+        }
+    }
+}
+
 // Implement the marker trait
 impl STTProvider for Parakeet {}
 
 #[async_trait]
 impl STTSpeechProvider for Parakeet {
-    async fn transcribe(&self, request: TranscriptionRequest) -> STTResult<TranscriptionResponse> {
+    async fn transcribe(
+        &self,
+        mut request: TranscriptionRequest,
+    ) -> STTResult<TranscriptionResponse> {
         validate_language(request.language.as_deref(), &self.config.model_variant)
             .map_err(crate::error::STTError::from)?;
+
+        let Some(options) = request.diarization.take() else {
+            let mut backend = self.backend.lock().await;
+            return backend.transcribe(request).await.map_err(Into::into);
+        };
+
+        // Word timestamps attribute text to speakers; variants without them
+        // transcribe each speaker segment separately instead.
+        let audio = request.audio.clone();
+        let keep_timestamps = request.include_timestamps;
+        let word_timestamps = self.config.model_variant.supports_timestamps();
+        request.include_timestamps |= word_timestamps;
+
         let mut backend = self.backend.lock().await;
-        backend.transcribe(request).await.map_err(Into::into)
+        let mut response = backend.transcribe(request.clone()).await?;
+        let mut segments = tokio::task::block_in_place(|| Diarizer::new(options).diarize(&audio));
+
+        match response.timestamps.as_deref() {
+            Some(words) if word_timestamps => assign_words(&mut segments, words),
+            _ => {
+                let rate = audio.sample_rate as f32;
+                let frame = audio.channels.max(1);
+                for segment in &mut segments {
+                    let start = (segment.start * rate) as usize * frame;
+                    let end = ((segment.end * rate) as usize * frame).min(audio.samples.len());
+                    let slice = TranscriptionRequest {
+                        audio: SharedAudioData::new(AudioData {
+                            samples: audio.samples[start.min(end)..end].to_vec(),
+                            channels: audio.channels,
+                            sample_rate: audio.sample_rate,
+                        }),
+                        include_timestamps: false,
+                        ..request.clone()
+                    };
+                    segment.text = backend.transcribe(slice).await?.text.trim().to_string();
+                }
+            }
+        }
+
+        if !keep_timestamps {
+            response.timestamps = None;
+        }
+        response.segments = Some(segments);
+        Ok(response)
     }
 
     async fn transcribe_stream<'a>(
@@ -123,6 +249,39 @@ impl STTSpeechProvider for Parakeet {
         Ok(Box::pin(stream))
     }
 
+    async fn transcribe_frames<'a>(
+        &'a self,
+        frames: Pin<Box<dyn Stream<Item = AudioChunk> + Send + 'a>>,
+    ) -> STTResult<Pin<Box<dyn Stream<Item = STTResult<TextChunk>> + Send + 'a>>> {
+        if !self.config.model_variant.supports_streaming() {
+            return Err(crate::error::STTError::StreamingNotSupported(format!(
+                "{} does not support streaming",
+                self.config.model_variant
+            )));
+        }
+
+        self.reset().await;
+
+        // Frames are buffered into model-sized chunks; Nemotron emits text deltas and
+        // EOU additionally flags utterance ends, both folded into PartialTranscript.
+        let session = LiveSession {
+            frames,
+            backend: self.backend.clone(),
+            chunk_size: self.config.model_variant.chunk_size(),
+            buffer: Vec::new(),
+            transcript: PartialTranscript::new(),
+            pending: VecDeque::new(),
+            input_done: false,
+            finished: false,
+        };
+        let stream = futures::stream::unfold(session, |mut session| async move {
+            let item = session.next_chunk().await?;
+            Some((item, session))
+        });
+
+        Ok(Box::pin(stream))
+    }
+
     fn supports_streaming(&self) -> bool {
         self.config.model_variant.supports_streaming()
     }
@@ -178,6 +178,7 @@ impl ParakeetBackend {
                 text,
                 timestamps: None,
                 duration_ms,
+                segments: None,
             },
             InternalTranscriptionResult::WithTimestamps { text, tokens } => {
                 let timestamps = tokens
@@ -193,6 +194,7 @@ impl ParakeetBackend {
                     text,
                     timestamps: Some(timestamps),
                     duration_ms,
+                    segments: None,
                 }
             }
         };
@@ -3,6 +3,7 @@
 use super::model::ModelVariant;
 use super::voices::PredefinedVoice;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Configuration for Pocket-TTS provider
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +28,39 @@ pub struct PocketTTSConfig {
     /// Default predefined voice (if not specified in requests)
     #[serde(default)]
     pub default_voice: Option<PredefinedVoice>,
+
+    /// How much audio each streamed `AudioChunk` carries
+    #[serde(default)]
+    pub stream_granularity: StreamGranularity,
+
+    /// Directory where cloned voice states are persisted. Cloned voices are
+    /// kept in memory only when unset.
+    #[serde(default)]
+    pub voice_dir: Option<PathBuf>,
+}
+
+impl PocketTTSConfig {
+    pub fn with_voice_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.voice_dir = Some(dir.into());
+        self
+    }
+}
+
+/// Chunking strategy for `generate_speech_stream`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamGranularity {
+    /// Emit every `n` decoded frames (80ms each). The first frame of a request
+    /// is always emitted on its own so playback can start immediately.
+    Frames(usize),
+    /// Emit one chunk per synthesized sentence
+    Sentence,
+}
+
+impl Default for StreamGranularity {
+    fn default() -> Self {
+        Self::Frames(3)
+    }
 }
 
 impl Default for PocketTTSConfig {
@@ -38,6 +72,8 @@ impl Default for PocketTTSConfig {
             eos_threshold: -4.0,
             noise_clamp: None,
             default_voice: Some(PredefinedVoice::default()),
+            stream_granularity: StreamGranularity::default(),
+            voice_dir: None,
         }
     }
 }
@@ -53,6 +89,7 @@ mod tests {
         assert_eq!(config.lsd_decode_steps, 1);
         assert_eq!(config.eos_threshold, -4.0);
         assert_eq!(config.model_variant, ModelVariant::default());
+        assert_eq!(config.stream_granularity, StreamGranularity::Frames(3));
     }
 
     #[test]
@@ -61,5 +98,12 @@ mod tests {
         let json = serde_json::to_string(&config).unwrap();
         let deserialized: PocketTTSConfig = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized.temperature, config.temperature);
+
+        let legacy: PocketTTSConfig = serde_json::from_str(
+            r#"{"temperature":0.7,"lsd_decode_steps":1,"eos_threshold":-4.0}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy.stream_granularity, StreamGranularity::default());
+        assert!(legacy.voice_dir.is_none());
     }
 }
@@ -19,6 +19,7 @@
 //!         voice: VoiceIdentifier::new("alba"),
 //!         format: AudioFormat::Wav,
 //!         sample_rate: Some(24000),
+//!         extensions: Default::default(),
 //!     };
 //!
 //!     let response = provider.generate_speech(request).await?;
@@ -27,6 +28,24 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! Custom voices can be cloned from a reference recording and selected with
+//! [`VoiceIdentifier::cloned`](crate::VoiceIdentifier::cloned):
+//!
+//! ```no_run
+//! use autoagents_speech::providers::pocket_tts::{PocketTTS, PocketTTSConfig};
+//! use autoagents_speech::{AudioData, TTSVoiceProvider};
+//!
+//! # async fn run(reference: AudioData) -> Result<(), Box<dyn std::error::Error>> {
+//! let config = PocketTTSConfig::default().with_voice_dir("voices");
+//! let provider = PocketTTS::new(Some(config))?;
+//!
+//! // Persisted to voices/brand.safetensors and reusable across restarts
+//! let voice = provider.clone_voice("brand", reference).await?;
+//! assert_eq!(voice.name(), "cloned:brand");
+//! # Ok(())
+//! # }
+//! ```
 
 pub mod config;
 pub mod error;
@@ -35,10 +54,12 @@ pub mod voices;
 
 mod provider;
 mod tts;
+mod voice_state;
 
 // Re-exports
-pub use config::PocketTTSConfig;
+pub use config::{PocketTTSConfig, StreamGranularity};
 pub use error::{PocketTTSError, Result};
 pub use model::ModelVariant;
 pub use provider::PocketTTS;
+pub use voice_state::{VOICE_STATE_EXTENSION, VoiceStateData};
 pub use voices::PredefinedVoice;
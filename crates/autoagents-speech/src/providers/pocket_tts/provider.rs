@@ -6,8 +6,8 @@ use super::config::PocketTTSConfig;
 use super::error::Result;
 use super::tts::PocketTTSBackend;
 use crate::{
-    AudioChunk, ModelInfo, SpeechRequest, SpeechResponse, TTSError, TTSModelsProvider, TTSProvider,
-    TTSResult, TTSSpeechProvider,
+    AudioChunk, AudioData, ModelInfo, SpeechRequest, SpeechResponse, TTSError, TTSModelsProvider,
+    TTSProvider, TTSResult, TTSSpeechProvider, TTSVoiceProvider, VoiceIdentifier, VoiceInfo,
 };
 use async_trait::async_trait;
 use futures::Stream;
@@ -29,7 +29,8 @@ impl PocketTTS {
             config.lsd_decode_steps,
             config.eos_threshold,
             config.noise_clamp,
-        )?;
+        )?
+        .with_voice_dir(config.voice_dir.clone());
 
         Ok(Self { config, backend })
     }
@@ -48,6 +49,11 @@ impl PocketTTS {
             .collect()
     }
 
+    /// List the ids of cloned voices
+    pub fn list_cloned_voices(&self) -> Result<Vec<String>> {
+        self.backend.cloned_voices()
+    }
+
     /// Get default voice name
     pub fn default_voice(&self) -> String {
         self.config
@@ -73,18 +79,21 @@ impl TTSSpeechProvider for PocketTTS {
     ) -> TTSResult<Pin<Box<dyn Stream<Item = TTSResult<AudioChunk>> + Send + 'a>>> {
         let stream = self
             .backend
-            .generate_stream(request)
+            .generate_stream(request, self.config.stream_granularity)
             .await
             .map_err(TTSError::from)?;
-        let audio_stream = futures::stream::StreamExt::map(stream, |result| {
-            result.map_err(TTSError::from).map(|response| AudioChunk {
-                samples: response.audio.samples,
-                sample_rate: response.audio.sample_rate,
-                is_final: false, // In streaming, we don't know when it's final, TODO: Can we improve this
-            })
-        });
+        let audio_stream =
+            futures::stream::StreamExt::map(stream, |result| result.map_err(TTSError::from));
         Ok(Box::pin(audio_stream))
     }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn default_sample_rate(&self) -> u32 {
+        self.backend.model.sample_rate as u32
+    }
 }
 
 #[async_trait]
@@ -94,6 +103,27 @@ impl TTSModelsProvider for PocketTTS {
         Ok(vec![self.get_current_model()])
     }
 
+    async fn list_voices(&self) -> TTSResult<Vec<VoiceInfo>> {
+        use super::voices::PredefinedVoice;
+        let mut voices: Vec<VoiceInfo> = PredefinedVoice::all()
+            .iter()
+            .map(|voice| VoiceInfo {
+                id: voice.identifier().to_string(),
+                name: voice.identifier().to_string(),
+                description: None,
+                languages: vec!["en".to_string()],
+            })
+            .collect();
+        let cloned = self.backend.cloned_voices().map_err(TTSError::from)?;
+        voices.extend(cloned.into_iter().map(|id| VoiceInfo {
+            id: VoiceIdentifier::cloned(&id).name,
+            name: id,
+            description: Some("Cloned voice".to_string()),
+            languages: vec!["en".to_string()],
+        }));
+        Ok(voices)
+    }
+
     fn get_current_model(&self) -> ModelInfo {
         let variant = &self.config.model_variant;
         ModelInfo {
@@ -105,6 +135,24 @@ impl TTSModelsProvider for PocketTTS {
     }
 }
 
+#[async_trait]
+impl TTSVoiceProvider for PocketTTS {
+    async fn clone_voice(
+        &self,
+        voice_id: &str,
+        reference: AudioData,
+    ) -> TTSResult<VoiceIdentifier> {
+        self.backend
+            .clone_voice(voice_id, reference)
+            .await
+            .map_err(TTSError::from)
+    }
+
+    async fn delete_voice(&self, voice_id: &str) -> TTSResult<()> {
+        self.backend.delete_voice(voice_id).map_err(TTSError::from)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,11 +1,15 @@
 //! Library backend - runs Pocket-TTS model locally
 
+use super::config::StreamGranularity;
 use super::error::{PocketTTSError, Result};
 use super::model::ModelVariant;
+use super::voice_state::{VoiceStateData, validate_voice_id, voice_state_path};
 use super::voices::PredefinedVoice;
-use crate::{AudioData, SpeechRequest, SpeechResponse, VoiceIdentifier};
+use crate::tts::SentenceChunker;
+use crate::{AudioChunk, AudioData, SpeechRequest, SpeechResponse, VoiceIdentifier};
 use pocket_tts::TTSModel;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 
 /// Library backend that runs Pocket-TTS locally
@@ -26,6 +30,8 @@ pub struct PocketTTSBackend {
     noise_clamp: Option<f32>,
     /// Cached voice states (in-memory)
     voice_cache: Arc<RwLock<HashMap<String, pocket_tts::ModelState>>>,
+    /// Where cloned voice states are persisted
+    voice_dir: Option<PathBuf>,
 }
 
 impl PocketTTSBackend {
@@ -56,9 +62,16 @@ impl PocketTTSBackend {
             eos_threshold,
             noise_clamp,
             voice_cache: Arc::new(RwLock::new(HashMap::new())),
+            voice_dir: None,
         })
     }
 
+    /// Persist cloned voices in `voice_dir`
+    pub fn with_voice_dir(mut self, voice_dir: Option<PathBuf>) -> Self {
+        self.voice_dir = voice_dir;
+        self
+    }
+
     /// Convert pocket-tts audio samples to AudioData
     fn samples_to_audio_data(samples: Vec<f32>, sample_rate: u32) -> AudioData {
         AudioData {
@@ -133,13 +146,19 @@ impl PocketTTSBackend {
     }
 
     /// Generate streaming audio chunks
+    ///
+    /// The text is split into sentences and each sentence is decoded frame by
+    /// frame on a blocking thread, so audio is available as soon as the first
+    /// frame is ready. Frames are grouped according to `granularity`. The
+    /// stream ends with an empty chunk marked `is_final`.
     pub async fn generate_stream(
         &self,
         request: SpeechRequest,
-    ) -> Result<impl futures::Stream<Item = Result<SpeechResponse>> + Send> {
+        granularity: StreamGranularity,
+    ) -> Result<impl futures::Stream<Item = Result<AudioChunk>> + Send> {
         let voice_state = self.resolve_voice(&request.voice)?;
         let sample_rate = self.model.sample_rate as u32;
-        let text = request.text.clone();
+        let sentences = split_sentences(&request.text);
 
         // Clone the model for use in the blocking task
         let model = self.model.clone();
@@ -149,67 +168,186 @@ impl PocketTTSBackend {
 
         // Spawn blocking task that sends chunks as they're generated
         tokio::task::spawn_blocking(move || {
-            let stream_iter = model.generate_stream(&text, &voice_state);
-
-            for (idx, result) in stream_iter.enumerate() {
-                let response = result
-                    .map_err(|e| {
-                        PocketTTSError::generation_error_detailed(
-                            e.to_string(),
-                            "streaming generation",
-                            format!("chunk index: {}", idx),
-                        )
-                    })
-                    .and_then(|tensor| {
-                        // Streaming returns tensors with shape [batch, 1, samples]
-                        // We need to squeeze to get [samples]
-                        let tensor = tensor.squeeze(0).and_then(|t| t.squeeze(0)).map_err(|e| {
-                            PocketTTSError::tensor_error(
-                                format!("Failed to squeeze tensor: {}", e),
-                                "[samples]",
-                                format!("{:?}", tensor.dims()),
-                            )
-                        })?;
-
-                        let samples = tensor.to_vec1::<f32>().map_err(|e| {
+            let send = |samples: Vec<f32>, is_final: bool| {
+                tx.send(Ok(AudioChunk {
+                    samples,
+                    sample_rate,
+                    is_final,
+                }))
+                .is_ok()
+            };
+            let mut batcher = FrameBatcher::new(granularity);
+
+            for sentence in &sentences {
+                for (idx, result) in model.generate_stream(sentence, &voice_state).enumerate() {
+                    let samples = match result
+                        .map_err(|e| {
                             PocketTTSError::generation_error_detailed(
-                                format!("Failed to extract samples: {}", e),
-                                "streaming tensor conversion",
-                                format!("chunk {}, tensor shape: {:?}", idx, tensor.dims()),
+                                e.to_string(),
+                                "streaming generation",
+                                format!("frame index: {}", idx),
                             )
-                        })?;
-
-                        let audio_data = Self::samples_to_audio_data(samples, sample_rate);
-                        let duration_ms = (audio_data.samples.len() as f64
-                            / audio_data.sample_rate as f64
-                            * 1000.0) as u64;
-
-                        Ok(SpeechResponse {
-                            text: format!("{}_{}", text, idx),
-                            audio: audio_data,
-                            duration_ms,
                         })
-                    });
-
-                // Send chunk immediately
-                if tx.send(response).is_err() {
-                    break;
+                        .and_then(|tensor| Self::frame_samples(&tensor, idx))
+                    {
+                        Ok(samples) => samples,
+                        Err(err) => {
+                            let _ = tx.send(Err(err));
+                            return;
+                        }
+                    };
+                    if let Some(samples) = batcher.push_frame(samples)
+                        && !send(samples, false)
+                    {
+                        return;
+                    }
+                }
+                if let Some(samples) = batcher.end_sentence()
+                    && !send(samples, false)
+                {
+                    return;
                 }
             }
+
+            send(Vec::new(), true);
         });
 
         // Convert the receiver to a stream
         Ok(tokio_stream::wrappers::UnboundedReceiverStream::new(rx))
     }
 
-    /// Resolve a voice identifier to a ModelState
-    fn resolve_voice(&self, voice_id: &VoiceIdentifier) -> Result<pocket_tts::ModelState> {
-        self.load_predefined_voice(&voice_id.name)
+    /// Extract samples from a streamed frame tensor
+    fn frame_samples(tensor: &candle_core::Tensor, idx: usize) -> Result<Vec<f32>> {
+        // Streaming returns tensors with shape [batch, 1, samples]
+        // We need to squeeze to get [samples]
+        let frame = tensor.squeeze(0).and_then(|t| t.squeeze(0)).map_err(|e| {
+            PocketTTSError::tensor_error(
+                format!("Failed to squeeze tensor: {}", e),
+                "[samples]",
+                format!("{:?}", tensor.dims()),
+            )
+        })?;
+
+        frame.to_vec1::<f32>().map_err(|e| {
+            PocketTTSError::generation_error_detailed(
+                format!("Failed to extract samples: {}", e),
+                "streaming tensor conversion",
+                format!("frame {}, tensor shape: {:?}", idx, frame.dims()),
+            )
+        })
     }
 
-    /// Load a predefined voice
-    fn load_predefined_voice(&self, name: &str) -> Result<pocket_tts::ModelState> {
-        // Check cache first
+    /// Create a voice state from reference audio
+    ///
+    /// The reference is downmixed to mono and resampled to the model rate
+    /// before being encoded. The state is cached and, when a voice directory
+    /// is configured, written to `<voice_dir>/<voice_id>.safetensors`.
+    pub async fn clone_voice(
+        &self,
+        voice_id: &str,
+        reference: AudioData,
+    ) -> Result<VoiceIdentifier> {
+        validate_voice_id(voice_id)?;
+        if reference.samples.is_empty() {
+            return Err(PocketTTSError::voice_error_detailed(
+                "reference audio is empty",
+                voice_id,
+                "cloning voice",
+            ));
+        }
+
+        let model = self.model.clone();
+        let name = voice_id.to_string();
+        let state = tokio::task::spawn_blocking(move || {
+            let samples = reference.to_mono().samples;
+            let len = samples.len();
+            let audio =
+                candle_core::Tensor::from_vec(samples, (1, len), &model.device).map_err(|e| {
+                    PocketTTSError::tensor_error(e.to_string(), "[1, samples]", len.to_string())
+                })?;
+            let audio = pocket_tts::audio::resample(
+                &audio,
+                reference.sample_rate,
+                model.sample_rate as u32,
+            )
+            .and_then(|audio| Ok(audio.unsqueeze(0)?))
+            .map_err(|e| {
+                PocketTTSError::voice_error_detailed(
+                    e.to_string(),
+                    name.clone(),
+                    "resampling reference audio",
+                )
+            })?;
+            model.get_voice_state_from_tensor(&audio).map_err(|e| {
+                PocketTTSError::voice_error_detailed(
+                    e.to_string(),
+                    name,
+                    "encoding reference audio",
+                )
+            })
+        })
+        .await
+        .map_err(|e| {
+            PocketTTSError::generation_error_detailed(
+                format!("Task join error: {}", e),
+                "spawn_blocking",
+                "voice cloning task failed to join",
+            )
+        })??;
+
+        if let Some(dir) = &self.voice_dir {
+            VoiceStateData::from_state(&state).save(voice_state_path(dir, voice_id))?;
+        }
+
+        let voice = VoiceIdentifier::cloned(voice_id);
+        self.cache_voice(voice.name(), state)?;
+        Ok(voice)
+    }
+
+    /// Forget a cloned voice, removing its persisted state
+    pub fn delete_voice(&self, voice_id: &str) -> Result<()> {
+        validate_voice_id(voice_id)?;
+        let cached = {
+            let mut cache = self.voice_cache.write().map_err(|e| {
+                PocketTTSError::cache_error(
+                    format!("Cache lock poisoned: {}", e),
+                    "voice cache write",
+                )
+            })?;
+            cache
+                .remove(VoiceIdentifier::cloned(voice_id).name())
+                .is_some()
+        };
+
+        let mut persisted = false;
+        if let Some(dir) = &self.voice_dir {
+            let path = voice_state_path(dir, voice_id);
+            if path.exists() {
+                std::fs::remove_file(&path).map_err(|e| {
+                    PocketTTSError::IoError(
+                        e,
+                        path.display().to_string(),
+                        "deleting voice state".to_string(),
+                    )
+                })?;
+                persisted = true;
+            }
+        }
+
+        if cached || persisted {
+            Ok(())
+        } else {
+            Err(PocketTTSError::voice_error_detailed(
+                "no cloned voice with this id",
+                voice_id,
+                "deleting cloned voice",
+            ))
+        }
+    }
+
+    /// Ids of cloned voices held in memory or persisted in the voice directory
+    pub fn cloned_voices(&self) -> Result<Vec<String>> {
+        let mut ids = BTreeSet::new();
         {
             let cache = self.voice_cache.read().map_err(|e| {
                 PocketTTSError::cache_error(
@@ -217,9 +355,85 @@ impl PocketTTSBackend {
                     "voice cache read",
                 )
             })?;
-            if let Some(state) = cache.get(name) {
-                return Ok(state.clone());
-            }
+            ids.extend(
+                cache
+                    .keys()
+                    .filter_map(|name| name.strip_prefix(VoiceIdentifier::CLONED_PREFIX))
+                    .map(str::to_string),
+            );
+        }
+
+        if let Some(dir) = &self.voice_dir
+            && let Ok(entries) = std::fs::read_dir(dir)
+        {
+            ids.extend(entries.filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != super::voice_state::VOICE_STATE_EXTENSION {
+                    return None;
+                }
+                let id = path.file_stem()?.to_str()?;
+                validate_voice_id(id).ok().map(|_| id.to_string())
+            }));
+        }
+
+        Ok(ids.into_iter().collect())
+    }
+
+    /// Resolve a voice identifier to a ModelState
+    fn resolve_voice(&self, voice_id: &VoiceIdentifier) -> Result<pocket_tts::ModelState> {
+        match voice_id.cloned_id() {
+            Some(id) => self.load_cloned_voice(id),
+            None => self.load_predefined_voice(&voice_id.name),
+        }
+    }
+
+    /// Load a cloned voice from the cache or the voice directory
+    fn load_cloned_voice(&self, voice_id: &str) -> Result<pocket_tts::ModelState> {
+        let name = VoiceIdentifier::cloned(voice_id).name;
+        if let Some(state) = self.cached_voice(&name)? {
+            return Ok(state);
+        }
+
+        validate_voice_id(voice_id)?;
+        let path = self
+            .voice_dir
+            .as_ref()
+            .map(|dir| voice_state_path(dir, voice_id))
+            .filter(|path| path.exists())
+            .ok_or_else(|| {
+                PocketTTSError::voice_error_detailed(
+                    "no cloned voice with this id; create it with clone_voice first",
+                    voice_id,
+                    "resolving cloned voice",
+                )
+            })?;
+
+        let state = VoiceStateData::load(&path, &self.model.device)?.into_state()?;
+        self.cache_voice(&name, state.clone())?;
+        Ok(state)
+    }
+
+    /// Look up a voice state in the cache
+    fn cached_voice(&self, name: &str) -> Result<Option<pocket_tts::ModelState>> {
+        let cache = self.voice_cache.read().map_err(|e| {
+            PocketTTSError::cache_error(format!("Cache lock poisoned: {}", e), "voice cache read")
+        })?;
+        Ok(cache.get(name).cloned())
+    }
+
+    /// Store a voice state in the cache
+    fn cache_voice(&self, name: &str, state: pocket_tts::ModelState) -> Result<()> {
+        let mut cache = self.voice_cache.write().map_err(|e| {
+            PocketTTSError::cache_error(format!("Cache lock poisoned: {}", e), "voice cache write")
+        })?;
+        cache.insert(name.to_string(), state);
+        Ok(())
+    }
+
+    /// Load a predefined voice
+    fn load_predefined_voice(&self, name: &str) -> Result<pocket_tts::ModelState> {
+        if let Some(state) = self.cached_voice(name)? {
+            return Ok(state);
         }
 
         // Parse the predefined voice
@@ -248,18 +462,57 @@ impl PocketTTSBackend {
                 )
             })?;
 
-        // Cache it
-        {
-            let mut cache = self.voice_cache.write().map_err(|e| {
-                PocketTTSError::cache_error(
-                    format!("Cache lock poisoned: {}", e),
-                    "voice cache write",
-                )
-            })?;
-            cache.insert(name.to_string(), state.clone());
+        self.cache_voice(name, state.clone())?;
+        Ok(state)
+    }
+}
+
+/// Split request text into the sentences synthesized one after another
+fn split_sentences(text: &str) -> Vec<String> {
+    let mut chunker = SentenceChunker::new();
+    let mut sentences = chunker.push_token(text);
+    sentences.extend(chunker.force_flush());
+    sentences
+}
+
+/// Groups decoded frames into streamed chunks
+struct FrameBatcher {
+    granularity: StreamGranularity,
+    pending: Vec<f32>,
+    frames: usize,
+    started: bool,
+}
+
+impl FrameBatcher {
+    fn new(granularity: StreamGranularity) -> Self {
+        Self {
+            granularity,
+            pending: Vec::new(),
+            frames: 0,
+            started: false,
         }
+    }
 
-        Ok(state)
+    /// Add a decoded frame, returning samples when a chunk is complete
+    fn push_frame(&mut self, samples: Vec<f32>) -> Option<Vec<f32>> {
+        self.pending.extend(samples);
+        self.frames += 1;
+        let ready = match self.granularity {
+            StreamGranularity::Frames(frames) => !self.started || self.frames >= frames.max(1),
+            StreamGranularity::Sentence => false,
+        };
+        ready.then(|| self.take())
+    }
+
+    /// Flush whatever is pending at a sentence boundary
+    fn end_sentence(&mut self) -> Option<Vec<f32>> {
+        (!self.pending.is_empty()).then(|| self.take())
+    }
+
+    fn take(&mut self) -> Vec<f32> {
+        self.started = true;
+        self.frames = 0;
+        std::mem::take(&mut self.pending)
     }
 }
 
@@ -273,4 +526,30 @@ mod tests {
         let result = PocketTTSBackend::new(ModelVariant::default(), 0.7, 1, -4.0, None);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_frame_batcher_emits_first_frame_then_batches() {
+        let mut batcher = FrameBatcher::new(StreamGranularity::Frames(2));
+        assert_eq!(batcher.push_frame(vec![0.1]), Some(vec![0.1]));
+        assert_eq!(batcher.push_frame(vec![0.2]), None);
+        assert_eq!(batcher.push_frame(vec![0.3]), Some(vec![0.2, 0.3]));
+        assert_eq!(batcher.push_frame(vec![0.4]), None);
+        assert_eq!(batcher.end_sentence(), Some(vec![0.4]));
+        assert_eq!(batcher.end_sentence(), None);
+    }
+
+    #[test]
+    fn test_frame_batcher_sentence_granularity() {
+        let mut batcher = FrameBatcher::new(StreamGranularity::Sentence);
+        assert_eq!(batcher.push_frame(vec![0.1]), None);
+        assert_eq!(batcher.push_frame(vec![0.2]), None);
+        assert_eq!(batcher.end_sentence(), Some(vec![0.1, 0.2]));
+    }
+
+    #[test]
+    fn test_split_sentences() {
+        let sentences = split_sentences("This is the first sentence. And this one is the second.");
+        assert_eq!(sentences.len(), 2);
+        assert!(split_sentences("   ").is_empty());
+    }
 }
@@ -0,0 +1,161 @@
+//! Persisted voice states for cloned Pocket-TTS voices
+
+use super::error::{PocketTTSError, Result};
+use candle_core::{Device, Tensor};
+use pocket_tts::ModelState;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// File extension used for persisted voice states
+pub const VOICE_STATE_EXTENSION: &str = "safetensors";
+
+/// Separates the module name from the tensor name in flattened keys.
+const KEY_SEPARATOR: char = '/';
+
+/// Snapshot of a voice state that can be written to and read from disk
+///
+/// Cloning a voice runs the reference audio through the model once; storing
+/// the resulting state lets later processes reuse the voice without the
+/// reference recording.
+#[derive(Debug, Clone)]
+pub struct VoiceStateData {
+    tensors: HashMap<String, Tensor>,
+}
+
+impl VoiceStateData {
+    /// Capture a model voice state
+    pub fn from_state(state: &ModelState) -> Self {
+        let tensors = state
+            .iter()
+            .flat_map(|(module, entries)| {
+                entries.iter().map(move |(name, tensor)| {
+                    (format!("{module}{KEY_SEPARATOR}{name}"), tensor.clone())
+                })
+            })
+            .collect();
+        Self { tensors }
+    }
+
+    /// Rebuild the model voice state
+    pub fn into_state(self) -> Result<ModelState> {
+        let mut state = ModelState::new();
+        for (key, tensor) in self.tensors {
+            let (module, name) = key.rsplit_once(KEY_SEPARATOR).ok_or_else(|| {
+                PocketTTSError::voice_error_detailed(
+                    format!("malformed voice state key '{key}'"),
+                    "cloned",
+                    "restoring voice state",
+                )
+            })?;
+            state
+                .entry(module.to_string())
+                .or_default()
+                .insert(name.to_string(), tensor);
+        }
+        Ok(state)
+    }
+
+    /// Write the state as a safetensors file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                PocketTTSError::IoError(
+                    e,
+                    parent.display().to_string(),
+                    "creating voice directory".to_string(),
+                )
+            })?;
+        }
+        candle_core::safetensors::save(&self.tensors, path).map_err(|e| {
+            PocketTTSError::voice_error_detailed(
+                e.to_string(),
+                path.display().to_string(),
+                "saving voice state",
+            )
+        })
+    }
+
+    /// Read a state written by [`VoiceStateData::save`]
+    pub fn load(path: impl AsRef<Path>, device: &Device) -> Result<Self> {
+        let path = path.as_ref();
+        let tensors = candle_core::safetensors::load(path, device).map_err(|e| {
+            PocketTTSError::voice_error_detailed(
+                e.to_string(),
+                path.display().to_string(),
+                "loading voice state",
+            )
+        })?;
+        Ok(Self { tensors })
+    }
+}
+
+/// Path of the persisted state for `voice_id` inside `dir`
+pub(crate) fn voice_state_path(dir: &Path, voice_id: &str) -> PathBuf {
+    dir.join(format!("{voice_id}.{VOICE_STATE_EXTENSION}"))
+}
+
+/// Cloned voice ids become file names, so keep them to a safe character set
+pub(crate) fn validate_voice_id(voice_id: &str) -> Result<()> {
+    let valid = !voice_id.is_empty()
+        && voice_id.len() <= 64
+        && voice_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(PocketTTSError::voice_error_detailed(
+            "voice ids may only contain ASCII letters, digits, '-' and '_' (max 64 characters)",
+            voice_id,
+            "validating cloned voice id",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voice_state_round_trip() {
+        let device = Device::Cpu;
+        let mut state = ModelState::new();
+        state
+            .entry("flow_lm.transformer.layers.0".into())
+            .or_default()
+            .insert(
+                "cache".into(),
+                Tensor::new(&[[1.0f32, 2.0], [3.0, 4.0]], &device).unwrap(),
+            );
+        state
+            .entry("flow_lm.transformer.layers.0".into())
+            .or_default()
+            .insert("offset".into(), Tensor::new(&[7i64], &device).unwrap());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = voice_state_path(&dir.path().join("voices"), "brand");
+        VoiceStateData::from_state(&state).save(&path).unwrap();
+
+        let restored = VoiceStateData::load(&path, &device)
+            .unwrap()
+            .into_state()
+            .unwrap();
+        let layer = &restored["flow_lm.transformer.layers.0"];
+        assert_eq!(
+            layer["cache"].to_vec2::<f32>().unwrap(),
+            vec![vec![1.0, 2.0], vec![3.0, 4.0]]
+        );
+        assert_eq!(layer["offset"].to_vec1::<i64>().unwrap(), vec![7]);
+    }
+
+    #[test]
+    fn test_validate_voice_id() {
+        assert!(validate_voice_id("brand_voice-2").is_ok());
+        assert!(validate_voice_id("").is_err());
+        assert!(validate_voice_id("../escape").is_err());
+        assert!(validate_voice_id("with space").is_err());
+    }
+}
@@ -0,0 +1,311 @@
+//! Sample-rate conversion and channel mixing for [`AudioData`]
+//!
+//! STT models mostly expect 16kHz mono, TTS models emit 24kHz, and capture
+//! devices deliver 44.1/48kHz stereo. These helpers convert between them:
+//!
+//! ```
+//! use autoagents_speech::AudioData;
+//!
+//! let mic = AudioData {
+//!     samples: vec![0.0; 48_000 * 2],
+//!     channels: 2,
+//!     sample_rate: 48_000,
+//! };
+//! let stt_input = mic.convert(16_000, 1);
+//! assert_eq!(stt_input.samples.len(), 16_000);
+//! ```
+
+use crate::AudioData;
+use std::f64::consts::PI;
+
+/// Zero crossings of the sinc kernel on each side of an output sample
+const SINC_ZERO_CROSSINGS: f64 = 16.0;
+
+/// Fraction of the lower Nyquist frequency kept by the anti-aliasing filter
+const SINC_ROLLOFF: f64 = 0.95;
+
+/// Interpolation used when changing the sample rate
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResampleQuality {
+    /// Linear interpolation; cheap, but aliases when downsampling
+    Linear,
+    /// Band-limited windowed-sinc interpolation
+    #[default]
+    Sinc,
+}
+
+/// Resample interleaved samples from `from_rate` to `to_rate`
+///
+/// The output holds `ceil(frames * to_rate / from_rate)` frames.
+pub fn resample_interleaved(
+    samples: &[f32],
+    channels: usize,
+    from_rate: u32,
+    to_rate: u32,
+    quality: ResampleQuality,
+) -> Vec<f32> {
+    let channels = channels.max(1);
+    if from_rate == to_rate || from_rate == 0 || to_rate == 0 {
+        return samples.to_vec();
+    }
+
+    let frames = samples.len() / channels;
+    if frames == 0 {
+        return Vec::new();
+    }
+
+    let out_frames = (frames as u64 * to_rate as u64).div_ceil(from_rate as u64) as usize;
+    let step = from_rate as f64 / to_rate as f64;
+    let mut out = Vec::with_capacity(out_frames * channels);
+    match quality {
+        ResampleQuality::Linear => {
+            for i in 0..out_frames {
+                let position = i as f64 * step;
+                let index = position as usize;
+                let frac = (position - index as f64) as f32;
+                let index = index.min(frames - 1);
+                let next = (index + 1).min(frames - 1);
+                for channel in 0..channels {
+                    let a = samples[index * channels + channel];
+                    let b = samples[next * channels + channel];
+                    out.push(a + (b - a) * frac);
+                }
+            }
+        }
+        ResampleQuality::Sinc => {
+            // Cutoff relative to the input Nyquist frequency; downsampling
+            // lowers it to the output Nyquist to suppress aliasing.
+            let cutoff = (to_rate as f64 / from_rate as f64).min(1.0) * SINC_ROLLOFF;
+            let radius = SINC_ZERO_CROSSINGS / cutoff;
+            let mut weights = Vec::with_capacity(2 * radius.ceil() as usize + 2);
+            for i in 0..out_frames {
+                let position = i as f64 * step;
+                let first = (position - radius).ceil().max(0.0) as usize;
+                let last = ((position + radius).floor() as usize).min(frames - 1);
+
+                weights.clear();
+                weights.extend((first..=last).map(|k| {
+                    let x = position - k as f64;
+                    cutoff * sinc(cutoff * x) * blackman(x / radius)
+                }));
+                let total: f64 = weights.iter().sum();
+                let norm = if total.abs() > f64::EPSILON {
+                    1.0 / total
+                } else {
+                    1.0
+                };
+
+                for channel in 0..channels {
+                    let acc: f64 = weights
+                        .iter()
+                        .enumerate()
+                        .map(|(j, w)| w * samples[(first + j) * channels + channel] as f64)
+                        .sum();
+                    out.push((acc * norm) as f32);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Average interleaved channels into one
+pub fn downmix_to_mono(samples: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return samples.to_vec();
+    }
+    samples
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect()
+}
+
+/// Change the channel count of interleaved samples
+///
+/// Reducing channels averages every input channel into output channel
+/// `index % to_channels`; adding channels repeats input channels in order,
+/// so mono becomes dual-mono.
+pub fn remix_channels(samples: &[f32], from_channels: usize, to_channels: usize) -> Vec<f32> {
+    let from = from_channels.max(1);
+    let to = to_channels.max(1);
+    if from == to {
+        return samples.to_vec();
+    }
+    if to == 1 {
+        return downmix_to_mono(samples, from);
+    }
+
+    let mut out = Vec::with_capacity(samples.len() / from * to);
+    let mut sums = vec![0.0f32; to];
+    let mut counts = vec![0usize; to];
+    for frame in samples.chunks_exact(from) {
+        if to > from {
+            out.extend((0..to).map(|channel| frame[channel % from]));
+            continue;
+        }
+        sums.fill(0.0);
+        counts.fill(0);
+        for (channel, sample) in frame.iter().enumerate() {
+            sums[channel % to] += sample;
+            counts[channel % to] += 1;
+        }
+        out.extend(sums.iter().zip(&counts).map(|(sum, n)| sum / *n as f32));
+    }
+    out
+}
+
+impl AudioData {
+    /// Number of sample frames (samples per channel)
+    pub fn frames(&self) -> usize {
+        self.samples.len() / self.channels.max(1)
+    }
+
+    /// Copy of the audio averaged down to one channel
+    pub fn to_mono(&self) -> AudioData {
+        self.remix(1)
+    }
+
+    /// Copy of the audio with `channels` channels (see [`remix_channels`])
+    pub fn remix(&self, channels: usize) -> AudioData {
+        AudioData {
+            samples: remix_channels(&self.samples, self.channels, channels),
+            channels: channels.max(1),
+            sample_rate: self.sample_rate,
+        }
+    }
+
+    /// Copy of the audio at `sample_rate`, using windowed-sinc interpolation
+    pub fn resample(&self, sample_rate: u32) -> AudioData {
+        self.resample_with(sample_rate, ResampleQuality::default())
+    }
+
+    /// Copy of the audio at `sample_rate` with the given interpolation
+    pub fn resample_with(&self, sample_rate: u32, quality: ResampleQuality) -> AudioData {
+        AudioData {
+            samples: resample_interleaved(
+                &self.samples,
+                self.channels,
+                self.sample_rate,
+                sample_rate,
+                quality,
+            ),
+            channels: self.channels,
+            sample_rate,
+        }
+    }
+
+    /// Copy of the audio at `sample_rate` with `channels` channels
+    ///
+    /// Channels are reduced before resampling and added after it, so the
+    /// resampler always processes the smaller layout.
+    pub fn convert(&self, sample_rate: u32, channels: usize) -> AudioData {
+        if channels < self.channels {
+            self.remix(channels).resample(sample_rate)
+        } else {
+            self.resample(sample_rate).remix(channels)
+        }
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        let px = PI * x;
+        px.sin() / px
+    }
+}
+
+/// Blackman window over `u` in [-1, 1]
+fn blackman(u: f64) -> f64 {
+    if u.abs() >= 1.0 {
+        return 0.0;
+    }
+    0.42 + 0.5 * (PI * u).cos() + 0.08 * (2.0 * PI * u).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(freq: f32, rate: u32, secs: f32) -> Vec<f32> {
+        let n = (rate as f32 * secs) as usize;
+        (0..n)
+            .map(|i| (2.0 * std::f32::consts::PI * freq * i as f32 / rate as f32).sin() * 0.5)
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_output_length_and_passthrough() {
+        let samples: Vec<f32> = (0..441).map(|i| i as f32).collect();
+        for quality in [ResampleQuality::Linear, ResampleQuality::Sinc] {
+            assert_eq!(
+                resample_interleaved(&samples, 1, 44_100, 48_000, quality).len(),
+                480
+            );
+            assert_eq!(
+                resample_interleaved(&samples, 1, 16_000, 16_000, quality),
+                samples
+            );
+            assert!(resample_interleaved(&[], 1, 8_000, 16_000, quality).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_linear_holds_last_sample() {
+        let out = resample_interleaved(&[0.0, 1.0], 1, 8_000, 16_000, ResampleQuality::Linear);
+        assert_eq!(out, vec![0.0, 0.5, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_sinc_preserves_passband_tone() {
+        let input = tone(440.0, 48_000, 0.5);
+        let out = resample_interleaved(&input, 1, 48_000, 16_000, ResampleQuality::Sinc);
+        let expected = tone(440.0, 16_000, 0.5);
+        let middle = 400..out.len() - 400;
+        let max_err = middle
+            .map(|i| (out[i] - expected[i]).abs())
+            .fold(0.0f32, f32::max);
+        assert!(max_err < 0.01, "max error {max_err}");
+    }
+
+    #[test]
+    fn test_sinc_suppresses_aliasing() {
+        // 10kHz is above the 8kHz Nyquist frequency of a 16kHz output
+        let input = tone(10_000.0, 48_000, 0.5);
+        let sinc = resample_interleaved(&input, 1, 48_000, 16_000, ResampleQuality::Sinc);
+        let linear = resample_interleaved(&input, 1, 48_000, 16_000, ResampleQuality::Linear);
+        assert!(rms(&sinc[400..sinc.len() - 400]) < 0.1 * rms(&linear));
+    }
+
+    #[test]
+    fn test_remix_channels() {
+        assert_eq!(downmix_to_mono(&[1.0, 3.0, 2.0, 4.0], 2), vec![2.0, 3.0]);
+        assert_eq!(remix_channels(&[1.0, 2.0], 1, 2), vec![1.0, 1.0, 2.0, 2.0]);
+        assert_eq!(remix_channels(&[1.0, 2.0, 3.0, 5.0], 4, 2), vec![2.0, 3.5]);
+    }
+
+    #[test]
+    fn test_audio_data_convert() {
+        let stereo = AudioData {
+            samples: tone(440.0, 48_000, 0.1)
+                .into_iter()
+                .flat_map(|s| [s, s])
+                .collect(),
+            channels: 2,
+            sample_rate: 48_000,
+        };
+        let converted = stereo.convert(16_000, 1);
+        assert_eq!(converted.channels, 1);
+        assert_eq!(converted.sample_rate, 16_000);
+        assert_eq!(converted.frames(), 1_600);
+
+        let upmixed = converted.convert(24_000, 2);
+        assert_eq!(upmixed.channels, 2);
+        assert_eq!(upmixed.frames(), 2_400);
+    }
+}
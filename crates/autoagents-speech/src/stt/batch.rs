@@ -0,0 +1,196 @@
+//! Bounded-parallel batch transcription
+
+use crate::{STTResult, STTSpeechProvider, TranscriptionRequest, TranscriptionResponse};
+use futures::StreamExt;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Options for [`STTSpeechProvider::transcribe_many`]
+#[derive(Clone, Debug)]
+pub struct BatchOptions {
+    /// Requests transcribed at the same time (default: 4)
+    pub concurrency: usize,
+    /// Receives one event per finished request
+    pub progress: Option<UnboundedSender<BatchProgress>>,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            progress: None,
+        }
+    }
+}
+
+impl BatchOptions {
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub fn with_progress(mut self, progress: UnboundedSender<BatchProgress>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+}
+
+/// Progress event emitted when a batch item finishes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BatchProgress {
+    /// Position of the finished request in the batch
+    pub index: usize,
+    /// Requests finished so far, including this one
+    pub completed: usize,
+    /// Number of requests in the batch
+    pub total: usize,
+    /// Error message if the request failed
+    pub error: Option<String>,
+}
+
+/// Transcribe `requests` with at most `options.concurrency` in flight
+///
+/// Results are returned in request order; a failing request does not stop
+/// the rest of the batch.
+pub(crate) async fn transcribe_concurrently<P>(
+    provider: &P,
+    requests: Vec<TranscriptionRequest>,
+    options: BatchOptions,
+) -> Vec<STTResult<TranscriptionResponse>>
+where
+    P: STTSpeechProvider + ?Sized,
+{
+    let total = requests.len();
+    let mut results: Vec<Option<STTResult<TranscriptionResponse>>> =
+        std::iter::repeat_with(|| None).take(total).collect();
+
+    let mut pending = futures::stream::iter(requests.into_iter().enumerate())
+        .map(|(index, request)| async move { (index, provider.transcribe(request).await) })
+        .buffer_unordered(options.concurrency.max(1));
+
+    let mut completed = 0;
+    while let Some((index, result)) = pending.next().await {
+        completed += 1;
+        if let Some(progress) = &options.progress {
+            // A dropped receiver only means nobody is watching
+            let _ = progress.send(BatchProgress {
+                index,
+                completed,
+                total,
+                error: result.as_ref().err().map(ToString::to_string),
+            });
+        }
+        results[index] = Some(result);
+    }
+
+    // buffer_unordered drives every request to completion, so no slot is empty
+    results.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AudioData, STTError, SharedAudioData};
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct CountingProvider {
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl STTSpeechProvider for CountingProvider {
+        async fn transcribe(
+            &self,
+            request: TranscriptionRequest,
+        ) -> STTResult<TranscriptionResponse> {
+            let running = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(running, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            if request.audio.samples.is_empty() {
+                return Err(STTError::Other(
+                    "empty audio".to_string(),
+                    "test".to_string(),
+                ));
+            }
+            Ok(TranscriptionResponse {
+                text: request.audio.samples.len().to_string(),
+                timestamps: None,
+                duration_ms: 0,
+                segments: None,
+            })
+        }
+    }
+
+    fn request(samples: usize) -> TranscriptionRequest {
+        TranscriptionRequest {
+            audio: SharedAudioData::new(AudioData {
+                samples: vec![0.0; samples],
+                channels: 1,
+                sample_rate: 16_000,
+            }),
+            language: None,
+            include_timestamps: false,
+            diarization: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_many_keeps_order_and_reports_errors() {
+        let provider = CountingProvider::default();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let requests = vec![request(3), request(0), request(1), request(2), request(4)];
+
+        let results = provider
+            .transcribe_many(
+                requests,
+                BatchOptions::default()
+                    .with_concurrency(2)
+                    .with_progress(tx),
+            )
+            .await;
+
+        let texts: Vec<Option<String>> = results
+            .iter()
+            .map(|r| r.as_ref().ok().map(|r| r.text.clone()))
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                Some("3".to_string()),
+                None,
+                Some("1".to_string()),
+                Some("2".to_string()),
+                Some("4".to_string())
+            ]
+        );
+        assert_eq!(provider.max_in_flight.load(Ordering::SeqCst), 2);
+
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            events.push(event);
+        }
+        assert_eq!(events.len(), 5);
+        assert_eq!(events.last().unwrap().completed, 5);
+        assert!(events.iter().all(|e| e.total == 5));
+        let failed: Vec<usize> = events
+            .iter()
+            .filter(|e| e.error.is_some())
+            .map(|e| e.index)
+            .collect();
+        assert_eq!(failed, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_many_empty_batch() {
+        let provider = CountingProvider::default();
+        let results = provider
+            .transcribe_many(Vec::new(), BatchOptions::default().with_concurrency(0))
+            .await;
+        assert!(results.is_empty());
+    }
+}
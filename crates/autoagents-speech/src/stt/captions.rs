@@ -0,0 +1,475 @@
+//! SRT and WebVTT caption export
+
+use crate::{
+    STTResult, STTSpeechProvider, SpeakerSegment, TokenTimestamp, TranscriptionRequest,
+    TranscriptionResponse,
+};
+use std::fmt::Write;
+
+/// Caption file format
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CaptionFormat {
+    /// SubRip (`.srt`)
+    Srt,
+    /// WebVTT (`.vtt`)
+    WebVtt,
+}
+
+impl CaptionFormat {
+    /// File extension without the leading dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CaptionFormat::Srt => "srt",
+            CaptionFormat::WebVtt => "vtt",
+        }
+    }
+
+    /// MIME type of the rendered captions
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            CaptionFormat::Srt => "application/x-subrip",
+            CaptionFormat::WebVtt => "text/vtt",
+        }
+    }
+
+    /// Render cues in this format
+    pub fn render(&self, cues: &[CaptionCue], options: &CaptionOptions) -> String {
+        match self {
+            CaptionFormat::Srt => to_srt(cues, options),
+            CaptionFormat::WebVtt => to_vtt(cues, options),
+        }
+    }
+}
+
+/// Limits used when grouping words into cues
+#[derive(Clone, Debug)]
+pub struct CaptionOptions {
+    /// Characters per caption line (default: 42)
+    pub max_line_chars: usize,
+    /// Lines per cue (default: 2)
+    pub max_lines: usize,
+    /// Longest time a cue stays on screen, in seconds (default: 6.0)
+    pub max_cue_secs: f32,
+    /// Pause between words that starts a new cue, in seconds (default: 1.0)
+    pub max_gap_secs: f32,
+}
+
+impl Default for CaptionOptions {
+    fn default() -> Self {
+        Self {
+            max_line_chars: 42,
+            max_lines: 2,
+            max_cue_secs: 6.0,
+            max_gap_secs: 1.0,
+        }
+    }
+}
+
+impl CaptionOptions {
+    pub fn with_max_line_chars(mut self, max_line_chars: usize) -> Self {
+        self.max_line_chars = max_line_chars;
+        self
+    }
+
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = max_lines;
+        self
+    }
+
+    pub fn with_max_cue_secs(mut self, max_cue_secs: f32) -> Self {
+        self.max_cue_secs = max_cue_secs;
+        self
+    }
+
+    pub fn with_max_gap_secs(mut self, max_gap_secs: f32) -> Self {
+        self.max_gap_secs = max_gap_secs;
+        self
+    }
+
+    fn max_cue_chars(&self) -> usize {
+        (self.max_line_chars * self.max_lines.max(1)).max(1)
+    }
+}
+
+/// A single timed caption
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaptionCue {
+    /// Start time in seconds
+    pub start: f32,
+    /// End time in seconds
+    pub end: f32,
+    /// Caption text
+    pub text: String,
+    /// Speaker label, when the transcription was diarized
+    pub speaker: Option<String>,
+}
+
+/// Group a transcription into caption cues
+///
+/// Word timestamps give the most precise cues. Without them, speaker segments
+/// are split into cues with interpolated times. Responses carrying neither
+/// produce no cues.
+pub fn captions_from_response(
+    response: &TranscriptionResponse,
+    options: &CaptionOptions,
+) -> Vec<CaptionCue> {
+    let segments = response.segments.as_deref().unwrap_or_default();
+    match response.timestamps.as_deref() {
+        Some(words) if !words.is_empty() => cues_from_words(words, segments, options),
+        _ => segments
+            .iter()
+            .flat_map(|segment| cues_from_segment(segment, options))
+            .collect(),
+    }
+}
+
+/// Render cues as SubRip
+pub fn to_srt(cues: &[CaptionCue], options: &CaptionOptions) -> String {
+    let mut out = String::new();
+    for (index, cue) in cues.iter().enumerate() {
+        let text = match &cue.speaker {
+            Some(speaker) => format!("{speaker}: {}", cue.text),
+            None => cue.text.clone(),
+        };
+        let _ = writeln!(out, "{}", index + 1);
+        let _ = writeln!(
+            out,
+            "{} --> {}",
+            format_timestamp(cue.start, ','),
+            format_timestamp(cue.end, ',')
+        );
+        let _ = writeln!(out, "{}\n", wrap_lines(&text, options.max_line_chars));
+    }
+    out
+}
+
+/// Render cues as WebVTT, tagging speakers with `<v>` voice spans
+pub fn to_vtt(cues: &[CaptionCue], options: &CaptionOptions) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for cue in cues {
+        let _ = writeln!(
+            out,
+            "{} --> {}",
+            format_timestamp(cue.start, '.'),
+            format_timestamp(cue.end, '.')
+        );
+        let text = wrap_lines(&escape_vtt(&cue.text), options.max_line_chars);
+        match &cue.speaker {
+            Some(speaker) => {
+                let _ = writeln!(out, "<v {}>{text}\n", escape_vtt(speaker));
+            }
+            None => {
+                let _ = writeln!(out, "{text}\n");
+            }
+        }
+    }
+    out
+}
+
+/// Transcribe `request` and render the result as captions
+///
+/// Timestamps are requested when the provider supports them. If the response
+/// carries no timing information, the whole transcript becomes one cue
+/// spanning the audio.
+pub async fn transcribe_to_captions<P>(
+    provider: &P,
+    mut request: TranscriptionRequest,
+    format: CaptionFormat,
+    options: &CaptionOptions,
+) -> STTResult<String>
+where
+    P: STTSpeechProvider + ?Sized,
+{
+    request.include_timestamps |= provider.supports_timestamps();
+    let audio_secs = request.audio.samples.len() as f32
+        / request.audio.channels.max(1) as f32
+        / request.audio.sample_rate.max(1) as f32;
+
+    let response = provider.transcribe(request).await?;
+    let mut cues = captions_from_response(&response, options);
+    let text = response.text.trim();
+    if cues.is_empty() && !text.is_empty() {
+        cues.push(CaptionCue {
+            start: 0.0,
+            end: audio_secs,
+            text: text.to_string(),
+            speaker: None,
+        });
+    }
+
+    Ok(format.render(&cues, options))
+}
+
+fn cues_from_words(
+    words: &[TokenTimestamp],
+    segments: &[SpeakerSegment],
+    options: &CaptionOptions,
+) -> Vec<CaptionCue> {
+    let max_chars = options.max_cue_chars();
+    let mut cues: Vec<CaptionCue> = Vec::new();
+    let mut current: Option<CaptionCue> = None;
+
+    for word in words {
+        let text = word.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let speaker = speaker_at(segments, (word.start + word.end) / 2.0);
+
+        if let Some(cue) = current.as_mut() {
+            let fits = cue.speaker == speaker
+                && word.start - cue.end <= options.max_gap_secs
+                && word.end - cue.start <= options.max_cue_secs
+                && cue.text.chars().count() + 1 + text.chars().count() <= max_chars;
+            if fits {
+                cue.text.push(' ');
+                cue.text.push_str(text);
+                cue.end = cue.end.max(word.end);
+                continue;
+            }
+        }
+
+        cues.extend(current.replace(CaptionCue {
+            start: word.start,
+            end: word.end,
+            text: text.to_string(),
+            speaker,
+        }));
+    }
+
+    cues.extend(current);
+    cues
+}
+
+fn cues_from_segment(segment: &SpeakerSegment, options: &CaptionOptions) -> Vec<CaptionCue> {
+    let max_chars = options.max_cue_chars();
+    let mut chunks: Vec<String> = Vec::new();
+    for word in segment.text.split_whitespace() {
+        match chunks.last_mut() {
+            Some(chunk) if chunk.chars().count() + 1 + word.chars().count() <= max_chars => {
+                chunk.push(' ');
+                chunk.push_str(word);
+            }
+            _ => chunks.push(word.to_string()),
+        }
+    }
+
+    // Distribute the segment's time over its chunks by character count
+    let total_chars: usize = chunks.iter().map(|c| c.chars().count()).sum();
+    let duration = (segment.end - segment.start).max(0.0);
+    let mut start = segment.start;
+    chunks
+        .into_iter()
+        .map(|text| {
+            let share = text.chars().count() as f32 / total_chars.max(1) as f32;
+            let end = (start + duration * share).min(segment.end);
+            let cue = CaptionCue {
+                start,
+                end,
+                text,
+                speaker: Some(segment.speaker.clone()),
+            };
+            start = end;
+            cue
+        })
+        .collect()
+}
+
+fn speaker_at(segments: &[SpeakerSegment], time: f32) -> Option<String> {
+    let gap = |segment: &SpeakerSegment| {
+        if time < segment.start {
+            segment.start - time
+        } else if time > segment.end {
+            time - segment.end
+        } else {
+            0.0
+        }
+    };
+    segments
+        .iter()
+        .min_by(|a, b| gap(a).total_cmp(&gap(b)))
+        .map(|segment| segment.speaker.clone())
+}
+
+fn format_timestamp(secs: f32, separator: char) -> String {
+    let total_ms = (secs.max(0.0) as f64 * 1000.0).round() as u64;
+    let hours = total_ms / 3_600_000;
+    let minutes = total_ms / 60_000 % 60;
+    let seconds = total_ms / 1000 % 60;
+    let millis = total_ms % 1000;
+    format!("{hours:02}:{minutes:02}:{seconds:02}{separator}{millis:03}")
+}
+
+fn wrap_lines(text: &str, max_line_chars: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= max_line_chars => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines.join("\n")
+}
+
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AudioData, SharedAudioData};
+    use async_trait::async_trait;
+
+    fn word(text: &str, start: f32, end: f32) -> TokenTimestamp {
+        TokenTimestamp {
+            text: text.to_string(),
+            start,
+            end,
+        }
+    }
+
+    fn response(
+        timestamps: Option<Vec<TokenTimestamp>>,
+        segments: Option<Vec<SpeakerSegment>>,
+    ) -> TranscriptionResponse {
+        TranscriptionResponse {
+            text: "hello there general kenobi".to_string(),
+            timestamps,
+            duration_ms: 0,
+            segments,
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0.0, ','), "00:00:00,000");
+        assert_eq!(format_timestamp(3723.456, '.'), "01:02:03.456");
+    }
+
+    #[test]
+    fn test_words_split_on_pause_and_length() {
+        let words = vec![
+            word("hello", 0.0, 0.4),
+            word("there", 0.5, 0.9),
+            word("general", 2.5, 3.0),
+            word("kenobi", 3.1, 3.6),
+        ];
+        let cues = captions_from_response(
+            &response(Some(words.clone()), None),
+            &CaptionOptions::default(),
+        );
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].text, "hello there");
+        assert_eq!((cues[0].start, cues[0].end), (0.0, 0.9));
+        assert_eq!(cues[1].text, "general kenobi");
+
+        let narrow = CaptionOptions::default()
+            .with_max_line_chars(8)
+            .with_max_lines(1);
+        let cues = captions_from_response(&response(Some(words), None), &narrow);
+        assert_eq!(cues.len(), 4);
+    }
+
+    #[test]
+    fn test_words_split_on_speaker_change() {
+        let words = vec![word("hello", 0.0, 0.4), word("there", 0.5, 0.9)];
+        let segments = vec![
+            SpeakerSegment {
+                speaker: "SPEAKER_0".to_string(),
+                start: 0.0,
+                end: 0.45,
+                text: "hello".to_string(),
+            },
+            SpeakerSegment {
+                speaker: "SPEAKER_1".to_string(),
+                start: 0.45,
+                end: 1.0,
+                text: "there".to_string(),
+            },
+        ];
+        let cues = captions_from_response(
+            &response(Some(words), Some(segments)),
+            &CaptionOptions::default(),
+        );
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[1].speaker.as_deref(), Some("SPEAKER_1"));
+
+        let vtt = to_vtt(&cues, &CaptionOptions::default());
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:00.000 --> 00:00:00.400\n<v SPEAKER_0>hello\n"));
+        let srt = to_srt(&cues, &CaptionOptions::default());
+        assert!(srt.contains("2\n00:00:00,500 --> 00:00:00,900\nSPEAKER_1: there\n"));
+    }
+
+    #[test]
+    fn test_segments_without_words() {
+        let segments = vec![SpeakerSegment {
+            speaker: "SPEAKER_0".to_string(),
+            start: 1.0,
+            end: 3.0,
+            text: "aaaa bbbb".to_string(),
+        }];
+        let options = CaptionOptions::default()
+            .with_max_line_chars(4)
+            .with_max_lines(1);
+        let cues = captions_from_response(&response(None, Some(segments)), &options);
+        assert_eq!(cues.len(), 2);
+        assert_eq!((cues[0].start, cues[0].end), (1.0, 2.0));
+        assert_eq!((cues[1].start, cues[1].end), (2.0, 3.0));
+    }
+
+    #[test]
+    fn test_srt_wraps_lines() {
+        let cues = vec![CaptionCue {
+            start: 0.0,
+            end: 1.0,
+            text: "one two three".to_string(),
+            speaker: None,
+        }];
+        let srt = to_srt(&cues, &CaptionOptions::default().with_max_line_chars(8));
+        assert_eq!(srt, "1\n00:00:00,000 --> 00:00:01,000\none two\nthree\n\n");
+    }
+
+    struct UntimedProvider;
+
+    #[async_trait]
+    impl STTSpeechProvider for UntimedProvider {
+        async fn transcribe(
+            &self,
+            _request: TranscriptionRequest,
+        ) -> STTResult<TranscriptionResponse> {
+            Ok(response(None, None))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_to_captions_falls_back_to_single_cue() {
+        let request = TranscriptionRequest {
+            audio: SharedAudioData::new(AudioData {
+                samples: vec![0.0; 32_000],
+                channels: 1,
+                sample_rate: 16_000,
+            }),
+            language: None,
+            include_timestamps: false,
+            diarization: None,
+        };
+        let vtt = transcribe_to_captions(
+            &UntimedProvider,
+            request,
+            CaptionFormat::WebVtt,
+            &CaptionOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            vtt,
+            "WEBVTT\n\n00:00:00.000 --> 00:00:02.000\nhello there general kenobi\n\n"
+        );
+    }
+}
@@ -0,0 +1,551 @@
+//! Speaker diarization for transcriptions.
+//!
+//! The audio is cut into overlapping analysis windows, silent windows are
+//! dropped, and each remaining window is turned into a speaker embedding by a
+//! [`SpeakerEmbedder`]. Embeddings are grouped by agglomerative clustering and
+//! consecutive windows with the same speaker are merged into
+//! [`SpeakerSegment`]s. Transcript words are then attributed to segments by
+//! their timestamps.
+
+use crate::{AudioData, DiarizationOptions, SpeakerSegment, TokenTimestamp};
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+const SILENT_FRAME_RMS: f32 = 1e-3;
+
+/// Turns a window of mono audio into a fixed-size speaker embedding.
+///
+/// Implement this to plug in a neural speaker model; [`SpectralEmbedder`] is
+/// the built-in default and needs no model files.
+pub trait SpeakerEmbedder: Send + Sync {
+    fn embed(&self, samples: &[f32], sample_rate: u32) -> Vec<f32>;
+}
+
+/// Embedder built from log-mel spectral statistics.
+///
+/// Each frame's log-mel spectrum is normalised for loudness, and the window
+/// embedding is the per-band mean and standard deviation, which captures the
+/// timbre of a voice well enough to separate speakers in conversations.
+#[derive(Debug, Clone)]
+pub struct SpectralEmbedder {
+    mel_bands: usize,
+}
+
+impl Default for SpectralEmbedder {
+    fn default() -> Self {
+        Self { mel_bands: 24 }
+    }
+}
+
+impl SpectralEmbedder {
+    pub fn new(mel_bands: usize) -> Self {
+        Self {
+            mel_bands: mel_bands.max(2),
+        }
+    }
+}
+
+impl SpeakerEmbedder for SpectralEmbedder {
+    fn embed(&self, samples: &[f32], sample_rate: u32) -> Vec<f32> {
+        let frame_len = ((sample_rate as f32 * 0.025) as usize).max(16);
+        let frame_hop = ((sample_rate as f32 * 0.010) as usize).max(8);
+        let n_fft = frame_len.next_power_of_two();
+        let window: Vec<f32> = (0..frame_len)
+            .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / frame_len as f32).cos())
+            .collect();
+        let filters = mel_filterbank(self.mel_bands, n_fft, sample_rate);
+
+        let mut sum = vec![0.0f32; self.mel_bands];
+        let mut sum_sq = vec![0.0f32; self.mel_bands];
+        let mut frames = 0usize;
+        let mut buffer = vec![(0.0f32, 0.0f32); n_fft];
+
+        let mut offset = 0;
+        while offset + frame_len <= samples.len() {
+            let frame = &samples[offset..offset + frame_len];
+            offset += frame_hop;
+            // Silent frames carry no voice information and would pull windows on a
+            // speech/silence edge away from their speaker.
+            if rms(frame) < SILENT_FRAME_RMS {
+                continue;
+            }
+            for (i, slot) in buffer.iter_mut().enumerate() {
+                *slot = if i < frame_len {
+                    (frame[i] * window[i], 0.0)
+                } else {
+                    (0.0, 0.0)
+                };
+            }
+            fft(&mut buffer);
+            let power: Vec<f32> = buffer[..=n_fft / 2]
+                .iter()
+                .map(|(re, im)| re * re + im * im)
+                .collect();
+
+            let mut bands: Vec<f32> = filters
+                .iter()
+                .map(|filter| {
+                    let energy: f32 = filter.iter().map(|&(bin, w)| power[bin] * w).sum();
+                    (energy + 1e-8).ln()
+                })
+                .collect();
+            let loudness = bands.iter().sum::<f32>() / bands.len() as f32;
+            for (band, value) in bands.iter_mut().enumerate() {
+                *value -= loudness;
+                sum[band] += *value;
+                sum_sq[band] += *value * *value;
+            }
+            frames += 1;
+        }
+
+        if frames == 0 {
+            return vec![0.0; self.mel_bands * 2];
+        }
+        let n = frames as f32;
+        let mean: Vec<f32> = sum.iter().map(|s| s / n).collect();
+        let std = sum_sq
+            .iter()
+            .zip(&mean)
+            .map(|(sq, m)| (sq / n - m * m).max(0.0).sqrt());
+        mean.iter().copied().chain(std).collect()
+    }
+}
+
+/// Speaker diarization pipeline.
+#[derive(Clone)]
+pub struct Diarizer {
+    options: DiarizationOptions,
+    embedder: Arc<dyn SpeakerEmbedder>,
+}
+
+impl Diarizer {
+    /// Create a diarizer using the built-in [`SpectralEmbedder`].
+    pub fn new(options: DiarizationOptions) -> Self {
+        Self {
+            options,
+            embedder: Arc::new(SpectralEmbedder::default()),
+        }
+    }
+
+    /// Use a custom speaker embedder.
+    pub fn with_embedder(mut self, embedder: impl SpeakerEmbedder + 'static) -> Self {
+        self.embedder = Arc::new(embedder);
+        self
+    }
+
+    pub fn options(&self) -> &DiarizationOptions {
+        &self.options
+    }
+
+    /// Split `audio` into speaker segments. Segment text is left empty.
+    pub fn diarize(&self, audio: &AudioData) -> Vec<SpeakerSegment> {
+        let samples = audio.to_mono().samples;
+        let sample_rate = audio.sample_rate.max(1);
+        let rate = sample_rate as f32;
+        let win = ((self.options.window_secs * rate) as usize).max(1);
+        let hop = ((self.options.hop_secs * rate) as usize).max(1);
+
+        let mut starts = Vec::new();
+        let mut start = 0;
+        while start + win < samples.len() {
+            starts.push(start);
+            start += hop;
+        }
+        let last = samples.len().saturating_sub(win);
+        if !samples.is_empty() && starts.last() != Some(&last) {
+            starts.push(last);
+        }
+
+        let windows: Vec<(usize, usize)> = starts
+            .into_iter()
+            .map(|start| (start, (start + win).min(samples.len())))
+            .filter(|&(start, end)| rms(&samples[start..end]) >= self.options.min_rms)
+            .collect();
+        if windows.is_empty() {
+            return Vec::new();
+        }
+
+        let embeddings: Vec<Vec<f32>> = windows
+            .iter()
+            .map(|&(start, end)| self.embedder.embed(&samples[start..end], sample_rate))
+            .collect();
+        let labels = self.cluster(&embeddings);
+
+        let mut segments: Vec<SpeakerSegment> = Vec::new();
+        let mut previous: Option<(usize, usize)> = None;
+        for (&(start, end), &label) in windows.iter().zip(&labels) {
+            let t0 = start as f32 / rate;
+            let t1 = end as f32 / rate;
+            let speaker = format!("SPEAKER_{label}");
+            match (segments.last_mut(), previous) {
+                (Some(current), Some((prev_start, prev_end))) if start <= prev_end => {
+                    if current.speaker == speaker {
+                        current.end = t1;
+                    } else {
+                        let boundary = (prev_start + prev_end + start + end) as f32 / 4.0 / rate;
+                        current.end = boundary;
+                        segments.push(SpeakerSegment {
+                            speaker,
+                            start: boundary,
+                            end: t1,
+                            text: String::new(),
+                        });
+                    }
+                }
+                _ => segments.push(SpeakerSegment {
+                    speaker,
+                    start: t0,
+                    end: t1,
+                    text: String::new(),
+                }),
+            }
+            previous = Some((start, end));
+        }
+        segments
+    }
+
+    /// Cluster embeddings and return one label per embedding, numbered by first appearance.
+    fn cluster(&self, embeddings: &[Vec<f32>]) -> Vec<usize> {
+        let threshold = self.options.threshold;
+        let target = self
+            .options
+            .num_speakers
+            .unwrap_or(self.options.max_speakers)
+            .max(1);
+
+        // Leader pass: group near-identical windows so the agglomerative pass stays small.
+        let mut clusters: Vec<(Vec<f32>, usize)> = Vec::new();
+        for embedding in embeddings {
+            let nearest = clusters
+                .iter()
+                .enumerate()
+                .map(|(i, (centroid, _))| (i, distance(centroid, embedding)))
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            match nearest {
+                Some((i, d)) if d <= threshold / 2.0 => {
+                    let (centroid, count) = &mut clusters[i];
+                    merge_into(centroid, *count, embedding, 1);
+                    *count += 1;
+                }
+                _ => clusters.push((embedding.clone(), 1)),
+            }
+        }
+
+        // Agglomerative pass over centroids.
+        while clusters.len() > 1 {
+            let mut best = (0, 1, f32::INFINITY);
+            for i in 0..clusters.len() {
+                for j in (i + 1)..clusters.len() {
+                    let d = distance(&clusters[i].0, &clusters[j].0);
+                    if d < best.2 {
+                        best = (i, j, d);
+                    }
+                }
+            }
+            let (i, j, d) = best;
+            let must_merge = clusters.len() > target;
+            let may_merge = self.options.num_speakers.is_none() && d <= threshold;
+            if !must_merge && !may_merge {
+                break;
+            }
+            let (other, other_count) = clusters.swap_remove(j);
+            let (centroid, count) = &mut clusters[i];
+            merge_into(centroid, *count, &other, other_count);
+            *count += other_count;
+        }
+
+        let mut centroids: Vec<Vec<f32>> = clusters.into_iter().map(|(c, _)| c).collect();
+        let nearest = |centroids: &[Vec<f32>], embedding: &[f32]| {
+            centroids
+                .iter()
+                .enumerate()
+                .min_by(|a, b| distance(a.1, embedding).total_cmp(&distance(b.1, embedding)))
+                .map(|(i, _)| i)
+                .unwrap_or(0)
+        };
+
+        // Windows straddling a speaker change form small clusters of their own;
+        // drop clusters that speak for less than `min_speaker_secs` in total.
+        let min_windows = (self.options.min_speaker_secs / self.options.hop_secs.max(f32::EPSILON))
+            .ceil() as usize;
+        while centroids.len() > 1 {
+            let mut counts = vec![0usize; centroids.len()];
+            for embedding in embeddings {
+                counts[nearest(&centroids, embedding)] += 1;
+            }
+            let (smallest, count) = counts
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, count)| **count)
+                .map(|(i, count)| (i, *count))
+                .unwrap_or_default();
+            if count >= min_windows {
+                break;
+            }
+            centroids.swap_remove(smallest);
+        }
+
+        let mut order: Vec<Option<usize>> = vec![None; centroids.len()];
+        let mut next = 0;
+        embeddings
+            .iter()
+            .map(|embedding| {
+                *order[nearest(&centroids, embedding)].get_or_insert_with(|| {
+                    next += 1;
+                    next - 1
+                })
+            })
+            .collect()
+    }
+}
+
+/// Fill segment text from word timestamps, attributing each word by its midpoint.
+///
+/// Words that fall between segments go to the nearest one.
+pub fn assign_words(segments: &mut [SpeakerSegment], words: &[TokenTimestamp]) {
+    if segments.is_empty() {
+        return;
+    }
+    for word in words {
+        let text = word.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let mid = (word.start + word.end) / 2.0;
+        let gap = |segment: &SpeakerSegment| {
+            if mid < segment.start {
+                segment.start - mid
+            } else if mid > segment.end {
+                mid - segment.end
+            } else {
+                0.0
+            }
+        };
+        let Some(segment) = segments.iter_mut().min_by(|a, b| gap(a).total_cmp(&gap(b))) else {
+            continue;
+        };
+        if !segment.text.is_empty() {
+            segment.text.push(' ');
+        }
+        segment.text.push_str(text);
+    }
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+/// Root-mean-square difference between two embeddings.
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len().min(b.len()).max(1);
+    (a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum::<f32>() / len as f32).sqrt()
+}
+
+fn merge_into(centroid: &mut [f32], count: usize, other: &[f32], other_count: usize) {
+    let total = (count + other_count) as f32;
+    for (c, o) in centroid.iter_mut().zip(other) {
+        *c = (*c * count as f32 + o * other_count as f32) / total;
+    }
+}
+
+/// Triangular mel filters as `(bin, weight)` lists.
+fn mel_filterbank(bands: usize, n_fft: usize, sample_rate: u32) -> Vec<Vec<(usize, f32)>> {
+    let to_mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+    let to_hz = |mel: f32| 700.0 * (10f32.powf(mel / 2595.0) - 1.0);
+    let nyquist = sample_rate as f32 / 2.0;
+    let (low, high) = (to_mel(60.0), to_mel(nyquist.min(8000.0)));
+    let bin_hz = sample_rate as f32 / n_fft as f32;
+    let edges: Vec<f32> = (0..bands + 2)
+        .map(|i| to_hz(low + (high - low) * i as f32 / (bands + 1) as f32) / bin_hz)
+        .collect();
+
+    (0..bands)
+        .map(|band| {
+            let (left, center, right) = (edges[band], edges[band + 1], edges[band + 2]);
+            (0..=n_fft / 2)
+                .filter_map(|bin| {
+                    let b = bin as f32;
+                    let weight = if b > left && b <= center {
+                        (b - left) / (center - left).max(f32::EPSILON)
+                    } else if b > center && b < right {
+                        (right - b) / (right - center).max(f32::EPSILON)
+                    } else {
+                        0.0
+                    };
+                    (weight > 0.0).then_some((bin, weight))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// In-place iterative radix-2 FFT; `data.len()` must be a power of two.
+fn fft(data: &mut [(f32, f32)]) {
+    let n = data.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let (re, im) = data[start + k + len / 2];
+                let t = (re * cos - im * sin, re * sin + im * cos);
+                let u = data[start + k];
+                data[start + k] = (u.0 + t.0, u.1 + t.1);
+                data[start + k + len / 2] = (u.0 - t.0, u.1 - t.1);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16000;
+
+    /// Deterministic voice-like signal: harmonics of `pitch` shaped by a formant at `formant`.
+    fn voice(pitch: f32, formant: f32, secs: f32, seed: u32) -> Vec<f32> {
+        let mut state = seed;
+        (0..(secs * RATE as f32) as usize)
+            .map(|i| {
+                let t = i as f32 / RATE as f32;
+                let mut sample = 0.0;
+                let mut harmonic = pitch;
+                while harmonic < 4000.0 {
+                    let gain = 1.0 / (1.0 + ((harmonic - formant) / 300.0).powi(2));
+                    sample += gain * (2.0 * PI * harmonic * t).sin();
+                    harmonic += pitch;
+                }
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let noise = (state >> 8) as f32 / (1u32 << 24) as f32 - 0.5;
+                0.2 * sample + 0.01 * noise
+            })
+            .collect()
+    }
+
+    fn audio(samples: Vec<f32>) -> AudioData {
+        AudioData {
+            samples,
+            channels: 1,
+            sample_rate: RATE,
+        }
+    }
+
+    #[test]
+    fn test_fft_matches_single_tone_bin() {
+        let mut data: Vec<(f32, f32)> = (0..64)
+            .map(|i| ((2.0 * PI * 4.0 * i as f32 / 64.0).cos(), 0.0))
+            .collect();
+        fft(&mut data);
+        let peak = data[..32]
+            .iter()
+            .enumerate()
+            .max_by(|a, b| (a.1.0.hypot(a.1.1)).total_cmp(&b.1.0.hypot(b.1.1)))
+            .map(|(i, _)| i);
+        assert_eq!(peak, Some(4));
+    }
+
+    #[test]
+    fn test_single_speaker_yields_one_segment() {
+        let diarizer = Diarizer::new(DiarizationOptions::default());
+        let segments = diarizer.diarize(&audio(voice(120.0, 700.0, 6.0, 1)));
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].speaker, "SPEAKER_0");
+        assert!(segments[0].start < 0.01);
+        assert!((segments[0].end - 6.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_alternating_speakers_are_separated() {
+        let mut samples = voice(110.0, 600.0, 3.0, 1);
+        samples.extend(voice(220.0, 2200.0, 3.0, 2));
+        samples.extend(voice(110.0, 600.0, 3.0, 3));
+
+        let diarizer = Diarizer::new(DiarizationOptions::default());
+        let segments = diarizer.diarize(&audio(samples));
+        let speakers: Vec<&str> = segments.iter().map(|s| s.speaker.as_str()).collect();
+        assert_eq!(speakers, vec!["SPEAKER_0", "SPEAKER_1", "SPEAKER_0"]);
+        assert!((segments[0].end - 3.0).abs() < 0.8);
+        assert!((segments[1].end - 6.0).abs() < 0.8);
+    }
+
+    #[test]
+    fn test_num_speakers_forces_cluster_count() {
+        let mut samples = voice(110.0, 600.0, 3.0, 1);
+        samples.extend(voice(220.0, 2200.0, 3.0, 2));
+
+        let options = DiarizationOptions::default().with_num_speakers(1);
+        let segments = Diarizer::new(options).diarize(&audio(samples));
+        assert!(segments.iter().all(|s| s.speaker == "SPEAKER_0"));
+    }
+
+    #[test]
+    fn test_silence_is_skipped() {
+        let mut samples = voice(120.0, 700.0, 2.0, 1);
+        samples.extend(vec![0.0; RATE as usize * 3]);
+        samples.extend(voice(120.0, 700.0, 2.0, 2));
+
+        let segments = Diarizer::new(DiarizationOptions::default()).diarize(&audio(samples));
+        assert_eq!(segments.len(), 2);
+        assert!(segments.iter().all(|s| s.speaker == "SPEAKER_0"));
+        assert!(segments[0].end <= 3.0);
+        assert!(segments[1].start >= 3.5);
+        assert!(
+            Diarizer::new(DiarizationOptions::default())
+                .diarize(&audio(vec![0.0; RATE as usize]))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_assign_words_uses_midpoints() {
+        let mut segments = vec![
+            SpeakerSegment {
+                speaker: "SPEAKER_0".into(),
+                start: 0.0,
+                end: 2.0,
+                text: String::new(),
+            },
+            SpeakerSegment {
+                speaker: "SPEAKER_1".into(),
+                start: 2.0,
+                end: 4.0,
+                text: String::new(),
+            },
+        ];
+        let word = |text: &str, start: f32, end: f32| TokenTimestamp {
+            text: text.into(),
+            start,
+            end,
+        };
+        assign_words(
+            &mut segments,
+            &[
+                word("hello", 0.2, 0.6),
+                word(" there", 1.8, 2.1),
+                word("hi", 2.5, 2.8),
+                word("bye", 4.5, 4.8),
+            ],
+        );
+        assert_eq!(segments[0].text, "hello there");
+        assert_eq!(segments[1].text, "hi bye");
+    }
+}
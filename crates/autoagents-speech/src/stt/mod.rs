@@ -0,0 +1,22 @@
+//! STT utilities for live transcription.
+//!
+//! This module provides:
+//! - [`PartialTranscript`]: Turns incremental decoder output into interim and
+//!   final transcripts for [`STTSpeechProvider::transcribe_frames`](crate::STTSpeechProvider::transcribe_frames)
+//! - [`Diarizer`]: Splits audio into speaker segments and attributes words to them
+//! - [`BatchOptions`]: Concurrency and progress reporting for
+//!   [`STTSpeechProvider::transcribe_many`](crate::STTSpeechProvider::transcribe_many)
+//! - [`transcribe_to_captions`]: Renders transcriptions as SRT or WebVTT captions
+
+pub(crate) mod batch;
+mod captions;
+mod diarization;
+mod partial;
+
+pub use batch::{BatchOptions, BatchProgress};
+pub use captions::{
+    CaptionCue, CaptionFormat, CaptionOptions, captions_from_response, to_srt, to_vtt,
+    transcribe_to_captions,
+};
+pub use diarization::{Diarizer, SpeakerEmbedder, SpectralEmbedder, assign_words};
+pub use partial::PartialTranscript;
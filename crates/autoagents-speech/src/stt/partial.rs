@@ -0,0 +1,96 @@
+//! Interim/final transcript accumulation for live STT.
+//!
+//! Streaming decoders emit small text deltas per audio chunk. Voice agents
+//! want the whole utterance heard so far, so each delta is folded into the
+//! current utterance and re-emitted as an interim [`TextChunk`]. An utterance
+//! ends when the decoder reports an end of utterance or the input ends, at
+//! which point the full text is emitted once with `is_final` set.
+
+use crate::TextChunk;
+
+/// Accumulates decoder deltas into utterance-level transcripts.
+#[derive(Debug, Default)]
+pub struct PartialTranscript {
+    utterance: String,
+}
+
+impl PartialTranscript {
+    /// Create an empty transcript.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Text of the current, unfinished utterance.
+    pub fn current(&self) -> &str {
+        self.utterance.trim()
+    }
+
+    /// Fold a decoder delta into the current utterance.
+    ///
+    /// `delta.is_final` marks an end of utterance detected by the decoder.
+    /// Returns the chunk to emit, or `None` when nothing new was heard.
+    pub fn push(&mut self, delta: TextChunk) -> Option<TextChunk> {
+        self.utterance.push_str(&delta.text);
+        if delta.is_final {
+            return self.finish();
+        }
+        if delta.text.trim().is_empty() {
+            return None;
+        }
+        Some(TextChunk {
+            text: self.current().to_string(),
+            is_final: false,
+        })
+    }
+
+    /// End the current utterance, returning it as a final chunk if anything was heard.
+    pub fn finish(&mut self) -> Option<TextChunk> {
+        let text = std::mem::take(&mut self.utterance);
+        let text = text.trim();
+        (!text.is_empty()).then(|| TextChunk {
+            text: text.to_string(),
+            is_final: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delta(text: &str, is_final: bool) -> TextChunk {
+        TextChunk {
+            text: text.to_string(),
+            is_final,
+        }
+    }
+
+    #[test]
+    fn test_interim_chunks_carry_the_whole_utterance() {
+        let mut transcript = PartialTranscript::new();
+        let first = transcript.push(delta("hello", false)).unwrap();
+        assert_eq!(first.text, "hello");
+        assert!(!first.is_final);
+
+        assert!(transcript.push(delta("", false)).is_none());
+
+        let second = transcript.push(delta(" world", false)).unwrap();
+        assert_eq!(second.text, "hello world");
+        assert!(!second.is_final);
+    }
+
+    #[test]
+    fn test_end_of_utterance_emits_final_and_resets() {
+        let mut transcript = PartialTranscript::new();
+        transcript.push(delta("turn on", false));
+        let last = transcript.push(delta(" the lights", true)).unwrap();
+        assert_eq!(last.text, "turn on the lights");
+        assert!(last.is_final);
+        assert_eq!(transcript.current(), "");
+
+        transcript.push(delta("thanks", false));
+        let tail = transcript.finish().unwrap();
+        assert_eq!(tail.text, "thanks");
+        assert!(transcript.finish().is_none());
+    }
+}
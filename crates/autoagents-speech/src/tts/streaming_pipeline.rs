@@ -160,6 +160,7 @@ impl<T: TTSSpeechProvider + Send + Sync + 'static> StreamingTtsPipeline<T> {
                 voice: base_request.voice,
                 format: base_request.format,
                 sample_rate: base_request.sample_rate,
+                extensions: base_request.extensions,
             };
 
             let result = match tts.generate_speech(request).await {
@@ -338,6 +339,7 @@ mod tests {
             voice: VoiceIdentifier::new("test"),
             format: AudioFormat::Wav,
             sample_rate: Some(24000),
+            extensions: Default::default(),
         }
     }
 
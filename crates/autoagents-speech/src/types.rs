@@ -1,4 +1,5 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Model information
@@ -14,6 +15,19 @@ pub struct ModelInfo {
     pub languages: Vec<String>,
 }
 
+/// Voice information
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VoiceInfo {
+    /// Identifier to pass in `VoiceIdentifier`
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Voice description
+    pub description: Option<String>,
+    /// Languages the voice is tuned for
+    pub languages: Vec<String>,
+}
+
 /// Audio data with normalized samples
 #[derive(Clone, Debug)]
 pub struct AudioData {
@@ -85,32 +99,145 @@ impl<'de> Deserialize<'de> for AudioData {
 pub type SharedAudioData = Arc<AudioData>;
 
 /// Audio format for output
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum AudioFormat {
     #[default]
     Wav,
     Mp3,
     Flac,
+    /// Ogg Vorbis
     Ogg,
+    /// Opus in an Ogg container
+    Opus,
 }
 
-/// Voice identifier for TTS generation (predefined voices only)
+impl AudioFormat {
+    /// File extension without the leading dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Ogg => "ogg",
+            AudioFormat::Opus => "opus",
+        }
+    }
+
+    /// MIME type for HTTP responses and uploads
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "audio/wav",
+            AudioFormat::Mp3 => "audio/mpeg",
+            AudioFormat::Flac => "audio/flac",
+            AudioFormat::Ogg => "audio/ogg",
+            AudioFormat::Opus => "audio/ogg; codecs=opus",
+        }
+    }
+
+    /// Format for a file extension (case-insensitive)
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension
+            .trim_start_matches('.')
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "wav" | "wave" => Some(AudioFormat::Wav),
+            "mp3" => Some(AudioFormat::Mp3),
+            "flac" => Some(AudioFormat::Flac),
+            "ogg" | "oga" => Some(AudioFormat::Ogg),
+            "opus" => Some(AudioFormat::Opus),
+            _ => None,
+        }
+    }
+
+    /// Format for a MIME type, ignoring parameters other than `codecs=opus`
+    pub fn from_mime_type(mime: &str) -> Option<Self> {
+        let mime = mime.to_ascii_lowercase();
+        let essence = mime.split(';').next().unwrap_or_default().trim();
+        match essence {
+            "audio/wav" | "audio/wave" | "audio/x-wav" | "audio/vnd.wave" => Some(AudioFormat::Wav),
+            "audio/mpeg" | "audio/mp3" => Some(AudioFormat::Mp3),
+            "audio/flac" | "audio/x-flac" => Some(AudioFormat::Flac),
+            "audio/opus" => Some(AudioFormat::Opus),
+            "audio/ogg" if mime.contains("opus") => Some(AudioFormat::Opus),
+            "audio/ogg" => Some(AudioFormat::Ogg),
+            _ => None,
+        }
+    }
+
+    /// Detect the format of encoded audio from its leading bytes
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [
+                b'R',
+                b'I',
+                b'F',
+                b'F',
+                _,
+                _,
+                _,
+                _,
+                b'W',
+                b'A',
+                b'V',
+                b'E',
+                ..,
+            ] => Some(AudioFormat::Wav),
+            [b'f', b'L', b'a', b'C', ..] => Some(AudioFormat::Flac),
+            [b'O', b'g', b'g', b'S', ..] => {
+                let head = &bytes[..bytes.len().min(128)];
+                if head.windows(8).any(|w| w == b"OpusHead") {
+                    Some(AudioFormat::Opus)
+                } else {
+                    Some(AudioFormat::Ogg)
+                }
+            }
+            [b'I', b'D', b'3', ..] => Some(AudioFormat::Mp3),
+            [0xFF, second, ..] if second & 0xE0 == 0xE0 => Some(AudioFormat::Mp3),
+            _ => None,
+        }
+    }
+}
+
+/// Voice identifier for TTS generation
+///
+/// Names refer to a provider's predefined voices unless they carry the
+/// `cloned:` prefix, which selects a voice created through
+/// [`TTSVoiceProvider::clone_voice`](crate::TTSVoiceProvider::clone_voice).
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct VoiceIdentifier {
-    /// Predefined voice name (e.g., "alba", "marius")
+    /// Voice name (e.g., "alba", "marius", "cloned:brand")
     pub name: String,
 }
 
 impl VoiceIdentifier {
+    /// Prefix marking a cloned voice
+    pub const CLONED_PREFIX: &'static str = "cloned:";
+
     /// Create a voice identifier from a predefined voice name
     pub fn new(name: impl Into<String>) -> Self {
         Self { name: name.into() }
     }
 
+    /// Create a voice identifier for a cloned voice
+    pub fn cloned(voice_id: impl AsRef<str>) -> Self {
+        Self::new(format!("{}{}", Self::CLONED_PREFIX, voice_id.as_ref()))
+    }
+
     /// Get the voice name
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Id of the cloned voice, if this identifier selects one
+    pub fn cloned_id(&self) -> Option<&str> {
+        self.name.strip_prefix(Self::CLONED_PREFIX)
+    }
+
+    /// Whether this identifier selects a cloned voice
+    pub fn is_cloned(&self) -> bool {
+        self.cloned_id().is_some()
+    }
 }
 
 impl From<String> for VoiceIdentifier {
@@ -132,6 +259,22 @@ pub struct SpeechRequest {
     pub voice: VoiceIdentifier,
     pub format: AudioFormat,
     pub sample_rate: Option<u32>,
+    /// Provider-specific options keyed by name (e.g. ElevenLabs `voice_settings`).
+    /// Providers ignore keys they do not understand.
+    pub extensions: HashMap<String, serde_json::Value>,
+}
+
+impl SpeechRequest {
+    /// Set a provider-specific option
+    pub fn with_extension(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.extensions.insert(key.into(), value);
+        self
+    }
+
+    /// Get a provider-specific option
+    pub fn extension(&self, key: &str) -> Option<&serde_json::Value> {
+        self.extensions.get(key)
+    }
 }
 
 /// Speech generation response
@@ -170,6 +313,72 @@ pub struct TranscriptionRequest {
     pub language: Option<String>,
     /// Whether to include timestamps
     pub include_timestamps: bool,
+    /// Attribute the transcript to speakers (see [`crate::stt::Diarizer`])
+    pub diarization: Option<DiarizationOptions>,
+}
+
+/// Options for speaker diarization
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiarizationOptions {
+    /// Exact number of speakers, when known
+    pub num_speakers: Option<usize>,
+    /// Upper bound on detected speakers (default: 8)
+    pub max_speakers: usize,
+    /// Embedding distance above which windows belong to different speakers (default: 0.6)
+    pub threshold: f32,
+    /// Analysis window length in seconds (default: 1.5)
+    pub window_secs: f32,
+    /// Hop between analysis windows in seconds (default: 0.75)
+    pub hop_secs: f32,
+    /// RMS level below which a window is treated as silence (default: 0.01)
+    pub min_rms: f32,
+    /// Speakers heard for less than this many seconds in total are folded into
+    /// the nearest other speaker (default: 2.0)
+    pub min_speaker_secs: f32,
+}
+
+impl Default for DiarizationOptions {
+    fn default() -> Self {
+        Self {
+            num_speakers: None,
+            max_speakers: 8,
+            threshold: 0.6,
+            window_secs: 1.5,
+            hop_secs: 0.75,
+            min_rms: 0.01,
+            min_speaker_secs: 2.0,
+        }
+    }
+}
+
+impl DiarizationOptions {
+    pub fn with_num_speakers(mut self, num_speakers: usize) -> Self {
+        self.num_speakers = Some(num_speakers);
+        self
+    }
+
+    pub fn with_max_speakers(mut self, max_speakers: usize) -> Self {
+        self.max_speakers = max_speakers;
+        self
+    }
+
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+}
+
+/// A span of audio attributed to one speaker
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpeakerSegment {
+    /// Speaker label (`SPEAKER_0`, `SPEAKER_1`, ... in order of first appearance)
+    pub speaker: String,
+    /// Start time in seconds
+    pub start: f32,
+    /// End time in seconds
+    pub end: f32,
+    /// Text spoken in this segment
+    pub text: String,
 }
 
 /// Transcription response from STT
@@ -181,6 +390,9 @@ pub struct TranscriptionResponse {
     pub timestamps: Option<Vec<TokenTimestamp>>,
     /// Processing duration in milliseconds
     pub duration_ms: u64,
+    /// Speaker-attributed segments, when diarization was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segments: Option<Vec<SpeakerSegment>>,
 }
 
 /// Text chunk for streaming STT
@@ -196,6 +408,42 @@ pub struct TextChunk {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_audio_format_lookup() {
+        assert_eq!(AudioFormat::from_extension(".MP3"), Some(AudioFormat::Mp3));
+        assert_eq!(AudioFormat::from_extension("aac"), None);
+        assert_eq!(
+            AudioFormat::from_mime_type("audio/ogg; codecs=opus"),
+            Some(AudioFormat::Opus)
+        );
+        assert_eq!(
+            AudioFormat::from_mime_type("audio/ogg"),
+            Some(AudioFormat::Ogg)
+        );
+        assert_eq!(AudioFormat::Mp3.mime_type(), "audio/mpeg");
+        assert_eq!(AudioFormat::Opus.extension(), "opus");
+    }
+
+    #[test]
+    fn test_audio_format_detect() {
+        assert_eq!(
+            AudioFormat::detect(b"RIFF\0\0\0\0WAVEfmt "),
+            Some(AudioFormat::Wav)
+        );
+        assert_eq!(AudioFormat::detect(b"fLaC\0"), Some(AudioFormat::Flac));
+        assert_eq!(AudioFormat::detect(b"ID3\x04"), Some(AudioFormat::Mp3));
+        assert_eq!(
+            AudioFormat::detect(&[0xFF, 0xFB, 0x90]),
+            Some(AudioFormat::Mp3)
+        );
+        let mut ogg = b"OggS\0\x02".to_vec();
+        ogg.extend_from_slice(&[0; 22]);
+        assert_eq!(AudioFormat::detect(&ogg), Some(AudioFormat::Ogg));
+        ogg.extend_from_slice(b"OpusHead");
+        assert_eq!(AudioFormat::detect(&ogg), Some(AudioFormat::Opus));
+        assert_eq!(AudioFormat::detect(b"nope"), None);
+    }
+
     #[test]
     fn test_audio_data_serialization() {
         let audio = AudioData {
@@ -229,5 +477,14 @@ mod tests {
     fn test_voice_identifier_from_string() {
         let voice: VoiceIdentifier = "marius".into();
         assert_eq!(voice.name(), "marius");
+        assert!(!voice.is_cloned());
+    }
+
+    #[test]
+    fn test_cloned_voice_identifier() {
+        let voice = VoiceIdentifier::cloned("brand");
+        assert_eq!(voice.name(), "cloned:brand");
+        assert_eq!(voice.cloned_id(), Some("brand"));
+        assert!(voice.is_cloned());
     }
 }
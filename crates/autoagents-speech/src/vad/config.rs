@@ -89,6 +89,56 @@ impl Default for SegmenterConfig {
     }
 }
 
+/// Configuration for frame-level voice activity detection.
+#[derive(Debug, Clone)]
+pub struct DetectorConfig {
+    /// Length of each analysed frame. 32 ms matches Silero's 512-sample window at 16 kHz.
+    pub frame_ms: u32,
+    /// Consecutive speech required before speech is reported.
+    pub min_speech_ms: u32,
+    /// Silence tolerated after speech before speech is reported as ended.
+    pub hangover_ms: u32,
+    pub thresholds: VadThresholds,
+}
+
+impl DetectorConfig {
+    pub fn frame_samples(&self, sample_rate: u32) -> usize {
+        let samples = (sample_rate as f32 * self.frame_ms as f32 / 1000.0).round() as usize;
+        samples.max(1)
+    }
+
+    pub fn with_frame_ms(mut self, ms: u32) -> Self {
+        self.frame_ms = ms;
+        self
+    }
+
+    pub fn with_min_speech_ms(mut self, ms: u32) -> Self {
+        self.min_speech_ms = ms;
+        self
+    }
+
+    pub fn with_hangover_ms(mut self, ms: u32) -> Self {
+        self.hangover_ms = ms;
+        self
+    }
+
+    pub fn with_thresholds(mut self, thresholds: VadThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+}
+
+impl Default for DetectorConfig {
+    fn default() -> Self {
+        Self {
+            frame_ms: 32,
+            min_speech_ms: 64,
+            hangover_ms: 320,
+            thresholds: VadThresholds::default(),
+        }
+    }
+}
+
 /// Configuration for the VAD + STT pipeline.
 #[derive(Debug, Clone, Default)]
 pub struct VadSttConfig {
@@ -0,0 +1,289 @@
+use super::VadEngine;
+use super::config::DetectorConfig;
+use super::error::{VadError, VadResult};
+use super::result::VadStatus;
+
+/// Change in speech state reported by a frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VadTransition {
+    SpeechStart,
+    SpeechEnd,
+}
+
+/// Detection result for a single frame.
+#[derive(Debug, Clone, Copy)]
+pub struct VadFrame {
+    /// Offset of the frame from the start of the stream.
+    pub start_ms: u64,
+    /// Raw speech probability reported by the engine.
+    pub probability: f32,
+    /// Status of this frame alone, from the configured thresholds.
+    pub status: VadStatus,
+    /// Smoothed speech state after onset and hangover are applied.
+    pub is_speech: bool,
+    pub transition: Option<VadTransition>,
+}
+
+/// Frame-by-frame voice activity detection.
+///
+/// Unlike [`VadSegmenter`](super::VadSegmenter), a detector does not buffer
+/// audio; it only reports whether speech is active, which is what STT
+/// providers and duplex pipelines need to gate inference or detect barge-in.
+pub trait VoiceActivityDetector: Send {
+    fn sample_rate(&self) -> u32;
+
+    /// Number of samples expected by [`VoiceActivityDetector::process_frame`].
+    fn frame_samples(&self) -> usize;
+
+    fn process_frame(&mut self, frame: &[f32]) -> VadResult<VadFrame>;
+
+    /// Current smoothed speech state.
+    fn is_speech(&self) -> bool;
+
+    fn reset(&mut self);
+}
+
+/// [`VoiceActivityDetector`] built on any [`VadEngine`].
+pub struct VadDetector<E: VadEngine> {
+    vad: E,
+    config: DetectorConfig,
+    sample_rate: u32,
+    frame_samples: usize,
+    pending_samples: Vec<f32>,
+    in_speech: bool,
+    speech_ms: u32,
+    silence_ms: u32,
+    processed_samples: u64,
+}
+
+/// Detector backed by the Silero ONNX model.
+pub type SileroDetector = VadDetector<super::SileroVad>;
+
+impl<E: VadEngine> VadDetector<E> {
+    pub fn new(mut vad: E, config: DetectorConfig) -> VadResult<Self> {
+        let sample_rate = vad.sample_rate();
+        if sample_rate == 0 {
+            return Err(VadError::InvalidInput(
+                "sample rate must be greater than zero".to_string(),
+            ));
+        }
+        if config.frame_ms == 0 {
+            return Err(VadError::InvalidInput(
+                "frame_ms must be greater than zero".to_string(),
+            ));
+        }
+        let frame_samples = config.frame_samples(sample_rate);
+
+        vad.reset();
+
+        Ok(Self {
+            vad,
+            config,
+            sample_rate,
+            frame_samples,
+            pending_samples: Vec::new(),
+            in_speech: false,
+            speech_ms: 0,
+            silence_ms: 0,
+            processed_samples: 0,
+        })
+    }
+
+    pub fn config(&self) -> &DetectorConfig {
+        &self.config
+    }
+
+    /// Split arbitrary-length audio into frames, keeping the remainder for the next call.
+    pub fn process_samples(&mut self, samples: &[f32]) -> VadResult<Vec<VadFrame>> {
+        self.pending_samples.extend_from_slice(samples);
+        let mut frames = Vec::with_capacity(self.pending_samples.len() / self.frame_samples);
+
+        while self.pending_samples.len() >= self.frame_samples {
+            let frame: Vec<f32> = self.pending_samples.drain(..self.frame_samples).collect();
+            frames.push(self.process_frame(&frame)?);
+        }
+
+        Ok(frames)
+    }
+
+    fn update_state(&mut self, status: VadStatus) -> Option<VadTransition> {
+        let frame_ms = self.config.frame_ms;
+        if !self.in_speech {
+            if status != VadStatus::Speech {
+                self.speech_ms = 0;
+                return None;
+            }
+            self.speech_ms = self.speech_ms.saturating_add(frame_ms);
+            if self.speech_ms < self.config.min_speech_ms {
+                return None;
+            }
+            self.in_speech = true;
+            self.speech_ms = 0;
+            self.silence_ms = 0;
+            return Some(VadTransition::SpeechStart);
+        }
+
+        // Frames between the two thresholds keep ongoing speech alive.
+        if status != VadStatus::Silence {
+            self.silence_ms = 0;
+            return None;
+        }
+        self.silence_ms = self.silence_ms.saturating_add(frame_ms);
+        if self.silence_ms <= self.config.hangover_ms {
+            return None;
+        }
+        self.in_speech = false;
+        self.silence_ms = 0;
+        Some(VadTransition::SpeechEnd)
+    }
+}
+
+impl<E: VadEngine> VoiceActivityDetector for VadDetector<E> {
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn frame_samples(&self) -> usize {
+        self.frame_samples
+    }
+
+    fn process_frame(&mut self, frame: &[f32]) -> VadResult<VadFrame> {
+        if frame.len() != self.frame_samples {
+            return Err(VadError::InvalidInput(format!(
+                "expected frames of {} samples, got {}",
+                self.frame_samples,
+                frame.len()
+            )));
+        }
+
+        let output = self.vad.compute(frame)?;
+        let status = output.status(self.config.thresholds);
+        let transition = self.update_state(status);
+        let start_ms = self.processed_samples * 1000 / self.sample_rate as u64;
+        self.processed_samples += frame.len() as u64;
+
+        Ok(VadFrame {
+            start_ms,
+            probability: output.probability,
+            status,
+            is_speech: self.in_speech,
+            transition,
+        })
+    }
+
+    fn is_speech(&self) -> bool {
+        self.in_speech
+    }
+
+    fn reset(&mut self) {
+        self.pending_samples.clear();
+        self.in_speech = false;
+        self.speech_ms = 0;
+        self.silence_ms = 0;
+        self.processed_samples = 0;
+        self.vad.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vad::VadOutput;
+
+    /// Reports each frame's first sample as its speech probability.
+    struct MockVad;
+
+    impl VadEngine for MockVad {
+        fn sample_rate(&self) -> u32 {
+            16_000
+        }
+
+        fn reset(&mut self) {}
+
+        fn compute(&mut self, samples: &[f32]) -> VadResult<VadOutput> {
+            Ok(VadOutput {
+                probability: samples[0],
+            })
+        }
+    }
+
+    fn detector(config: DetectorConfig) -> VadDetector<MockVad> {
+        VadDetector::new(MockVad, config.with_frame_ms(10)).unwrap()
+    }
+
+    fn run(detector: &mut VadDetector<MockVad>, probabilities: &[f32]) -> Vec<VadFrame> {
+        probabilities
+            .iter()
+            .map(|&p| {
+                let frame = vec![p; detector.frame_samples()];
+                detector.process_frame(&frame).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn requires_min_speech_before_onset() {
+        let mut detector = detector(DetectorConfig::default().with_min_speech_ms(30));
+        let frames = run(&mut detector, &[0.9, 0.9, 0.1, 0.9, 0.9, 0.9]);
+
+        let speech: Vec<bool> = frames.iter().map(|f| f.is_speech).collect();
+        assert_eq!(speech, vec![false, false, false, false, false, true]);
+        assert_eq!(frames[5].transition, Some(VadTransition::SpeechStart));
+        assert_eq!(frames[5].start_ms, 50);
+    }
+
+    #[test]
+    fn hangover_bridges_short_pauses() {
+        let mut detector = detector(
+            DetectorConfig::default()
+                .with_min_speech_ms(10)
+                .with_hangover_ms(20),
+        );
+        let frames = run(&mut detector, &[0.9, 0.1, 0.1, 0.9, 0.4, 0.1, 0.1, 0.1]);
+
+        let speech: Vec<bool> = frames.iter().map(|f| f.is_speech).collect();
+        assert_eq!(
+            speech,
+            vec![true, true, true, true, true, true, true, false]
+        );
+        let transitions: Vec<VadTransition> = frames.iter().filter_map(|f| f.transition).collect();
+        assert_eq!(
+            transitions,
+            vec![VadTransition::SpeechStart, VadTransition::SpeechEnd]
+        );
+    }
+
+    #[test]
+    fn process_samples_buffers_partial_frames() {
+        let mut detector = detector(DetectorConfig::default().with_min_speech_ms(10));
+        let frame = detector.frame_samples();
+
+        assert!(
+            detector
+                .process_samples(&vec![0.9; frame / 2])
+                .unwrap()
+                .is_empty()
+        );
+        let frames = detector.process_samples(&vec![0.9; frame * 2]).unwrap();
+        assert_eq!(frames.len(), 2);
+        assert!(detector.is_speech());
+
+        detector.reset();
+        assert!(!detector.is_speech());
+        assert!(
+            detector
+                .process_samples(&vec![0.9; frame / 2])
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_frame_length() {
+        let mut detector = detector(DetectorConfig::default());
+        assert!(matches!(
+            detector.process_frame(&[0.5; 3]),
+            Err(VadError::InvalidInput(_))
+        ));
+    }
+}
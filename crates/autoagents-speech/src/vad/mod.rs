@@ -1,6 +1,11 @@
 //! Voice Activity Detection (VAD) utilities.
+//!
+//! [`VadEngine`] produces raw speech probabilities. [`VadDetector`] turns them
+//! into a frame-by-frame speech state with onset and hangover smoothing, and
+//! [`VadSegmenter`] cuts complete utterances out of a stream.
 
 mod config;
+mod detector;
 mod error;
 mod pipeline;
 mod result;
@@ -8,12 +13,13 @@ mod segmenter;
 mod session;
 mod silero;
 
-pub use config::{SegmenterConfig, VadConfig, VadSttConfig};
+pub use config::{DetectorConfig, SegmenterConfig, VadConfig, VadSttConfig};
+pub use detector::{SileroDetector, VadDetector, VadFrame, VadTransition, VoiceActivityDetector};
 pub use error::{VadError, VadResult};
 pub use pipeline::{SegmentTranscription, VadPipelineError, VadSttPipeline};
 pub use result::{VadOutput, VadStatus, VadThresholds};
 pub use segmenter::{SegmentEndReason, SpeechSegment, VadSegmenter};
-pub use silero::SileroVad;
+pub use silero::{SILERO_VAD_HF_FILE, SILERO_VAD_HF_REPO, SileroVad};
 
 /// Trait abstraction for VAD engines.
 pub trait VadEngine: Send {
@@ -95,6 +95,7 @@ impl<V: VadEngine, S: STTSpeechProvider> VadSttPipeline<V, S> {
             audio: segment.audio.clone(), // cheap Arc clone — no audio data is copied
             language: self.config.language.clone(),
             include_timestamps: self.config.include_timestamps,
+            diarization: None,
         };
         let response = self.stt.transcribe(request).await?;
         Ok(response)
@@ -139,6 +140,7 @@ mod tests {
                 text: format!("{} samples", request.audio.samples.len()),
                 timestamps: None,
                 duration_ms: 1,
+                segments: None,
             })
         }
     }
@@ -1,5 +1,6 @@
 use super::VadEngine;
-use super::config::VadConfig;
+use super::config::{DetectorConfig, VadConfig};
+use super::detector::VadDetector;
 use super::error::{VadError, VadResult};
 use super::result::VadOutput;
 use super::session::create_session;
@@ -8,6 +9,12 @@ use ndarray::{Array1, Array2, Array3, ArrayBase, Ix3, OwnedRepr};
 use ort::session::Session;
 use ort::value::Value;
 
+/// Hugging Face repository hosting the Silero VAD ONNX model.
+pub const SILERO_VAD_HF_REPO: &str = "freddyaboulton/silero-vad";
+
+/// Model file inside [`SILERO_VAD_HF_REPO`].
+pub const SILERO_VAD_HF_FILE: &str = "silero_vad.onnx";
+
 /// Silero VAD engine backed by an ONNX model.
 pub struct SileroVad {
     session: Session,
@@ -46,6 +53,16 @@ impl SileroVad {
         Self::new(ModelSource::from_hf(repo_id, filename), config)
     }
 
+    /// Load the model from [`SILERO_VAD_HF_REPO`].
+    pub fn from_default_hf(config: VadConfig) -> VadResult<Self> {
+        Self::from_hf(SILERO_VAD_HF_REPO, SILERO_VAD_HF_FILE, config)
+    }
+
+    /// Wrap the engine in a [`VadDetector`] for frame-level detection.
+    pub fn into_detector(self, config: DetectorConfig) -> VadResult<VadDetector<Self>> {
+        VadDetector::new(self, config)
+    }
+
     pub fn reset(&mut self) {
         self.h_tensor.fill(0.0);
         self.c_tensor.fill(0.0);
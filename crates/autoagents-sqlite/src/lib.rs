@@ -0,0 +1,580 @@
+//! SQLite vector store for AutoAgents
+//!
+//! Documents and their embeddings are kept in two SQLite tables and searched
+//! by brute force: every stored vector is compared with the query. That
+//! needs no extension and no server, and builds everywhere SQLite does,
+//! including `wasm32-unknown-unknown` and mobile targets, which makes it a
+//! good fit for on-device agents with up to tens of thousands of chunks. Use
+//! a dedicated vector database such as Qdrant beyond that.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use autoagents_core::embeddings::distance::VectorDistance;
+use autoagents_core::embeddings::{Embed, EmbeddingError, SharedEmbeddingProvider};
+use autoagents_core::vector_store::request::Filter;
+use autoagents_core::vector_store::{
+    DEFAULT_VECTOR_NAME, NamedVectorDocument, ScrollPage, VectorSearchRequest, VectorStoreError,
+    VectorStoreIndex, embed_documents, embed_named_documents, normalize_id, rerank_top_n,
+    rerank_top_n_ids,
+};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub use rusqlite;
+
+/// A document id, its JSON and its `(vector name, vector)` pairs
+type StoredRow = (String, Value, Vec<(String, Vec<f32>)>);
+
+/// Table used by [`SqliteVectorStore::open`] and [`SqliteVectorStore::open_in_memory`]
+pub const DEFAULT_TABLE: &str = "documents";
+
+/// Vector store over a SQLite database
+///
+/// Documents go into the table `<table>` and their vectors into
+/// `<table>_vectors`, both created on construction if missing. Several
+/// stores can share a database under different table names. Queries run on
+/// the calling task while holding the connection, so keep the database
+/// small or local.
+#[derive(Clone)]
+pub struct SqliteVectorStore {
+    conn: Arc<Mutex<Connection>>,
+    provider: SharedEmbeddingProvider,
+    table: String,
+}
+
+impl SqliteVectorStore {
+    /// Open or create the database file at `path`
+    pub fn open(
+        path: impl AsRef<Path>,
+        provider: SharedEmbeddingProvider,
+    ) -> Result<Self, VectorStoreError> {
+        let conn = Connection::open(path).map_err(datastore_error)?;
+        Self::new(conn, provider, DEFAULT_TABLE)
+    }
+
+    /// A database that lives only as long as the store
+    pub fn open_in_memory(provider: SharedEmbeddingProvider) -> Result<Self, VectorStoreError> {
+        let conn = Connection::open_in_memory().map_err(datastore_error)?;
+        Self::new(conn, provider, DEFAULT_TABLE)
+    }
+
+    /// Use an existing connection, e.g. one opened on a browser VFS
+    ///
+    /// `table` may contain ASCII letters, digits and `_`.
+    pub fn new(
+        conn: Connection,
+        provider: SharedEmbeddingProvider,
+        table: &str,
+    ) -> Result<Self, VectorStoreError> {
+        let valid = !table.is_empty()
+            && !table.starts_with(|c: char| c.is_ascii_digit())
+            && table.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(VectorStoreError::Unsupported(format!(
+                "invalid table name `{table}`"
+            )));
+        }
+        conn.execute_batch(&format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                 id TEXT PRIMARY KEY,
+                 raw TEXT NOT NULL
+             );
+             CREATE TABLE IF NOT EXISTS {table}_vectors (
+                 doc_id TEXT NOT NULL,
+                 name TEXT NOT NULL,
+                 vector BLOB NOT NULL
+             );
+             CREATE INDEX IF NOT EXISTS {table}_vectors_doc ON {table}_vectors (doc_id);
+             CREATE INDEX IF NOT EXISTS {table}_vectors_name ON {table}_vectors (name);"
+        ))
+        .map_err(datastore_error)?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            provider,
+            table: table.to_string(),
+        })
+    }
+
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().expect("lock poisoned")
+    }
+
+    /// Replace the stored documents and vectors with `rows`, in one transaction
+    fn upsert(&self, rows: Vec<StoredRow>) -> Result<(), VectorStoreError> {
+        let table = &self.table;
+        let mut conn = self.lock();
+        let tx = conn.transaction().map_err(datastore_error)?;
+        {
+            let mut insert_doc = tx
+                .prepare(&format!(
+                    "INSERT OR REPLACE INTO {table} (id, raw) VALUES (?1, ?2)"
+                ))
+                .map_err(datastore_error)?;
+            let mut clear_vectors = tx
+                .prepare(&format!("DELETE FROM {table}_vectors WHERE doc_id = ?1"))
+                .map_err(datastore_error)?;
+            let mut insert_vector = tx
+                .prepare(&format!(
+                    "INSERT INTO {table}_vectors (doc_id, name, vector) VALUES (?1, ?2, ?3)"
+                ))
+                .map_err(datastore_error)?;
+            for (id, raw, vectors) in rows {
+                insert_doc
+                    .execute(params![id, serde_json::to_string(&raw)?])
+                    .map_err(datastore_error)?;
+                clear_vectors.execute([&id]).map_err(datastore_error)?;
+                for (name, vector) in vectors {
+                    insert_vector
+                        .execute(params![id, name, encode_vector(&vector)])
+                        .map_err(datastore_error)?;
+                }
+            }
+        }
+        tx.commit().map_err(datastore_error)
+    }
+
+    async fn embed_query(&self, query: &str) -> Result<Option<Vec<f32>>, VectorStoreError> {
+        let vectors = self
+            .provider
+            .embed(vec![query.to_string()])
+            .await
+            .map_err(EmbeddingError::Provider)?;
+        Ok(vectors.into_iter().next())
+    }
+
+    /// Best matches for the request as `(score, id, raw)`, best first
+    async fn search(
+        &self,
+        req: &VectorSearchRequest<Filter<Value>>,
+    ) -> Result<Vec<(f64, String, Value)>, VectorStoreError> {
+        let Some(query) = self.embed_query(req.query()).await? else {
+            return Ok(Vec::new());
+        };
+        let vector_name = req.query_vector_name().unwrap_or(DEFAULT_VECTOR_NAME);
+        let table = &self.table;
+        let conn = self.lock();
+
+        let mut scores: HashMap<String, f32> = HashMap::new();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT doc_id, vector FROM {table}_vectors WHERE name = ?1"
+            ))
+            .map_err(datastore_error)?;
+        let mut rows = stmt.query([vector_name]).map_err(datastore_error)?;
+        while let Some(row) = rows.next().map_err(datastore_error)? {
+            let id: String = row.get(0).map_err(datastore_error)?;
+            let vector: Vec<u8> = row.get(1).map_err(datastore_error)?;
+            let vector = decode_vector(&vector);
+            let score = vector.cosine_similarity(&query, true);
+            let best = scores.entry(id).or_insert(f32::NEG_INFINITY);
+            *best = best.max(score);
+        }
+
+        let mut ranked: Vec<(f64, String)> = scores
+            .into_iter()
+            .map(|(id, score)| (score as f64, id))
+            .filter(|(score, _)| req.threshold().is_none_or(|threshold| *score >= threshold))
+            .collect();
+        ranked.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut raw_by_id = conn
+            .prepare(&format!("SELECT raw FROM {table} WHERE id = ?1"))
+            .map_err(datastore_error)?;
+        let mut matches = Vec::new();
+        for (score, id) in ranked {
+            if matches.len() as u64 >= req.samples() {
+                break;
+            }
+            let Some(raw) = raw_by_id
+                .query_row([&id], |row| row.get::<_, String>(0))
+                .optional()
+                .map_err(datastore_error)?
+            else {
+                continue;
+            };
+            let raw: Value = serde_json::from_str(&raw)?;
+            if req
+                .filter()
+                .as_ref()
+                .is_none_or(|filter| filter.satisfies(&raw))
+            {
+                matches.push((score, id, raw));
+            }
+        }
+        Ok(matches)
+    }
+}
+
+#[async_trait]
+impl VectorStoreIndex for SqliteVectorStore {
+    type Filter = Filter<Value>;
+
+    async fn insert_documents<T>(&self, documents: Vec<T>) -> Result<(), VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+    {
+        let documents = documents
+            .into_iter()
+            .map(|doc| (normalize_id(None), doc))
+            .collect();
+        self.insert_documents_with_ids(documents).await
+    }
+
+    async fn insert_documents_with_ids<T>(
+        &self,
+        documents: Vec<(String, T)>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: Embed + Serialize + Send + Sync + Clone,
+    {
+        let documents = documents
+            .into_iter()
+            .map(|(id, doc)| (normalize_id(Some(id)), doc))
+            .collect();
+        let prepared = embed_documents(&self.provider, documents).await?;
+        self.upsert(
+            prepared
+                .into_iter()
+                .map(|doc| {
+                    let vectors = doc
+                        .embeddings
+                        .iter()
+                        .map(|embedding| (DEFAULT_VECTOR_NAME.to_string(), embedding.vec.to_vec()))
+                        .collect();
+                    (doc.id, doc.raw, vectors)
+                })
+                .collect(),
+        )
+    }
+
+    async fn top_n<T>(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+    ) -> Result<Vec<(f64, String, T)>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        if req.reranker().is_some() {
+            return rerank_top_n(self, req).await;
+        }
+        self.search(&req)
+            .await?
+            .into_iter()
+            .map(|(score, id, raw)| Ok((score, id, serde_json::from_value(raw)?)))
+            .collect()
+    }
+
+    async fn top_n_ids(
+        &self,
+        req: VectorSearchRequest<Self::Filter>,
+    ) -> Result<Vec<(f64, String)>, VectorStoreError> {
+        if req.reranker().is_some() {
+            return rerank_top_n_ids(self, req).await;
+        }
+        Ok(self
+            .search(&req)
+            .await?
+            .into_iter()
+            .map(|(score, id, _)| (score, id))
+            .collect())
+    }
+
+    async fn insert_documents_with_named_vectors<T>(
+        &self,
+        documents: Vec<NamedVectorDocument<T>>,
+    ) -> Result<(), VectorStoreError>
+    where
+        T: Serialize + Send + Sync + Clone,
+    {
+        let documents = documents
+            .into_iter()
+            .map(|doc| NamedVectorDocument {
+                id: normalize_id(Some(doc.id)),
+                raw: doc.raw,
+                vectors: doc.vectors,
+            })
+            .collect();
+        let prepared = embed_named_documents(&self.provider, documents).await?;
+        self.upsert(
+            prepared
+                .into_iter()
+                .map(|doc| (doc.id, doc.raw, doc.vectors.into_iter().collect()))
+                .collect(),
+        )
+    }
+
+    async fn delete_documents_by_ids(&self, ids: &[String]) -> Result<(), VectorStoreError> {
+        let table = &self.table;
+        let mut conn = self.lock();
+        let tx = conn.transaction().map_err(datastore_error)?;
+        for id in ids {
+            tx.execute(&format!("DELETE FROM {table} WHERE id = ?1"), [id])
+                .map_err(datastore_error)?;
+            tx.execute(
+                &format!("DELETE FROM {table}_vectors WHERE doc_id = ?1"),
+                [id],
+            )
+            .map_err(datastore_error)?;
+        }
+        tx.commit().map_err(datastore_error)
+    }
+
+    /// Pages are in id order; the cursor is the id that starts the next page.
+    async fn scroll<T>(
+        &self,
+        filter: Option<Self::Filter>,
+        cursor: Option<String>,
+        limit: u64,
+    ) -> Result<ScrollPage<T>, VectorStoreError>
+    where
+        T: for<'de> Deserialize<'de> + Send + Sync,
+    {
+        let limit = limit.max(1) as usize;
+        let table = &self.table;
+        let conn = self.lock();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT id, raw FROM {table} WHERE ?1 IS NULL OR id >= ?1 ORDER BY id"
+            ))
+            .map_err(datastore_error)?;
+        let mut rows = stmt.query([&cursor]).map_err(datastore_error)?;
+
+        let mut documents = Vec::new();
+        let mut next_cursor = None;
+        while let Some(row) = rows.next().map_err(datastore_error)? {
+            let id: String = row.get(0).map_err(datastore_error)?;
+            let raw: Value =
+                serde_json::from_str(&row.get::<_, String>(1).map_err(datastore_error)?)?;
+            if filter
+                .as_ref()
+                .is_some_and(|filter| !filter.satisfies(&raw))
+            {
+                continue;
+            }
+            if documents.len() == limit {
+                next_cursor = Some(id);
+                break;
+            }
+            documents.push((id, serde_json::from_value(raw)?));
+        }
+        Ok(ScrollPage {
+            documents,
+            next_cursor,
+        })
+    }
+
+    async fn count(&self, filter: Option<Self::Filter>) -> Result<u64, VectorStoreError> {
+        let table = &self.table;
+        let conn = self.lock();
+        let Some(filter) = filter else {
+            return conn
+                .query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                    row.get::<_, i64>(0)
+                })
+                .map(|count| count as u64)
+                .map_err(datastore_error);
+        };
+
+        let mut stmt = conn
+            .prepare(&format!("SELECT raw FROM {table}"))
+            .map_err(datastore_error)?;
+        let mut rows = stmt.query([]).map_err(datastore_error)?;
+        let mut count = 0;
+        while let Some(row) = rows.next().map_err(datastore_error)? {
+            let raw: Value =
+                serde_json::from_str(&row.get::<_, String>(0).map_err(datastore_error)?)?;
+            count += filter.satisfies(&raw) as u64;
+        }
+        Ok(count)
+    }
+}
+
+fn datastore_error(err: rusqlite::Error) -> VectorStoreError {
+    VectorStoreError::DatastoreError(Box::new(err))
+}
+
+/// Little-endian `f32`s, the layout sqlite-vec also uses
+fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autoagents_core::document::Document;
+    use autoagents_core::testing::KeywordEmbeddingProvider;
+    use autoagents_core::vector_store::request::SearchFilter;
+    use serde_json::json;
+
+    fn provider() -> Arc<KeywordEmbeddingProvider> {
+        Arc::new(KeywordEmbeddingProvider::new(["rust", "python", "sqlite"]))
+    }
+
+    fn store() -> SqliteVectorStore {
+        SqliteVectorStore::open_in_memory(provider()).unwrap()
+    }
+
+    fn docs() -> Vec<(String, Document)> {
+        vec![
+            (
+                "a".to_string(),
+                Document::with_metadata("rust ownership", json!({"lang": "en"})),
+            ),
+            (
+                "b".to_string(),
+                Document::with_metadata("python typing", json!({"lang": "en"})),
+            ),
+            (
+                "c".to_string(),
+                Document::with_metadata("rust und sqlite", json!({"lang": "de"})),
+            ),
+        ]
+    }
+
+    fn request(query: &str, samples: u64) -> VectorSearchRequest<Filter<Value>> {
+        VectorSearchRequest::builder()
+            .query(query)
+            .samples(samples)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_vector_blobs_round_trip() {
+        let vector = vec![0.5, -1.25, 3.0];
+        assert_eq!(decode_vector(&encode_vector(&vector)), vector);
+    }
+
+    #[test]
+    fn test_rejects_invalid_table_names() {
+        let conn = Connection::open_in_memory().unwrap();
+        let err = SqliteVectorStore::new(conn, provider(), "docs; DROP")
+            .err()
+            .unwrap();
+        assert!(matches!(err, VectorStoreError::Unsupported(_)));
+    }
+
+    #[tokio::test]
+    async fn test_top_n_ranks_and_filters() {
+        let store = store();
+        store.insert_documents_with_ids(docs()).await.unwrap();
+
+        let results: Vec<(f64, String, Document)> = store.top_n(request("rust", 2)).await.unwrap();
+        let ids: Vec<&str> = results.iter().map(|(_, id, _)| id.as_str()).collect();
+        assert_eq!(ids, ["a", "c"]);
+
+        let filtered = VectorSearchRequest::builder()
+            .query("rust")
+            .samples(5)
+            .filter(Filter::eq("metadata.lang".to_string(), json!("de")))
+            .build()
+            .unwrap();
+        let ids = store.top_n_ids(filtered).await.unwrap();
+        assert_eq!(ids.len(), 1);
+        assert_eq!(ids[0].1, "c");
+    }
+
+    #[tokio::test]
+    async fn test_reinsert_replaces_and_delete_removes() {
+        let store = store();
+        store.insert_documents_with_ids(docs()).await.unwrap();
+        store
+            .insert_documents_with_ids(vec![("a".to_string(), Document::new("sqlite only"))])
+            .await
+            .unwrap();
+        let results: Vec<(f64, String, Document)> =
+            store.top_n(request("sqlite", 1)).await.unwrap();
+        assert_eq!(results[0].2.page_content, "sqlite only");
+
+        store
+            .delete_documents_by_ids(&["a".to_string(), "missing".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(store.count(None).await.unwrap(), 2);
+        let ids = store.top_n_ids(request("python", 5)).await.unwrap();
+        assert!(ids.iter().all(|(_, id)| id != "a"));
+    }
+
+    #[tokio::test]
+    async fn test_scroll_pages_and_count() {
+        let store = store();
+        store.insert_documents_with_ids(docs()).await.unwrap();
+
+        let first: ScrollPage<Document> = store.scroll(None, None, 2).await.unwrap();
+        let ids: Vec<&str> = first.documents.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, ["a", "b"]);
+        assert_eq!(first.next_cursor.as_deref(), Some("c"));
+        let second: ScrollPage<Document> = store.scroll(None, first.next_cursor, 2).await.unwrap();
+        assert_eq!(second.documents.len(), 1);
+        assert!(second.next_cursor.is_none());
+
+        let english = Filter::eq("metadata.lang".to_string(), json!("en"));
+        assert_eq!(store.count(Some(english)).await.unwrap(), 2);
+        assert_eq!(store.count(None).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_named_vectors_are_searched_by_name() {
+        let store = store();
+        store
+            .insert_documents_with_named_vectors(vec![
+                NamedVectorDocument {
+                    id: "x".to_string(),
+                    raw: json!({"title": "x"}),
+                    vectors: HashMap::from([
+                        ("title".to_string(), "python".to_string()),
+                        ("body".to_string(), "rust".to_string()),
+                    ]),
+                },
+                NamedVectorDocument {
+                    id: "y".to_string(),
+                    raw: json!({"title": "y"}),
+                    vectors: HashMap::from([
+                        ("title".to_string(), "rust".to_string()),
+                        ("body".to_string(), "python".to_string()),
+                    ]),
+                },
+            ])
+            .await
+            .unwrap();
+
+        let by_title = VectorSearchRequest::builder()
+            .query("rust")
+            .samples(1)
+            .query_vector_name("title")
+            .build()
+            .unwrap();
+        let ids = store.top_n_ids(by_title).await.unwrap();
+        assert_eq!(ids[0].1, "y");
+    }
+
+    #[tokio::test]
+    async fn test_documents_persist_in_a_database_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vectors.db");
+        {
+            let store = SqliteVectorStore::open(&path, provider()).unwrap();
+            store.insert_documents_with_ids(docs()).await.unwrap();
+        }
+        let reopened = SqliteVectorStore::open(&path, provider()).unwrap();
+        assert_eq!(reopened.count(None).await.unwrap(), 3);
+    }
+}
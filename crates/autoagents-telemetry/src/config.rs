@@ -1,5 +1,9 @@
+use autoagents_core::pii::{PiiKind, redact_pii};
 use autoagents_protocol::RuntimeID;
+use regex::Regex;
 use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
 
 /// Top-level telemetry configuration applied when a tracer starts.
 #[derive(Debug, Clone)]
@@ -8,6 +12,8 @@ pub struct TelemetryConfig {
     pub service_version: Option<String>,
     pub environment: Option<String>,
     pub runtime_id: Option<RuntimeID>,
+    /// Extra resource attributes attached to every exported span and metric.
+    pub resource_attributes: HashMap<String, String>,
     pub exporter: ExporterConfig,
     pub span_batch: SpanBatchConfig,
     pub redaction: RedactionConfig,
@@ -22,6 +28,7 @@ impl TelemetryConfig {
             service_version: None,
             environment: None,
             runtime_id: None,
+            resource_attributes: HashMap::new(),
             exporter: ExporterConfig::default(),
             span_batch: SpanBatchConfig::default(),
             redaction: RedactionConfig::default(),
@@ -34,6 +41,15 @@ impl TelemetryConfig {
         self.runtime_id = Some(runtime_id);
         self
     }
+
+    pub fn with_resource_attribute(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.resource_attributes.insert(key.into(), value.into());
+        self
+    }
 }
 
 impl Default for TelemetryConfig {
@@ -43,6 +59,7 @@ impl Default for TelemetryConfig {
             service_version: None,
             environment: None,
             runtime_id: None,
+            resource_attributes: HashMap::new(),
             exporter: ExporterConfig::default(),
             span_batch: SpanBatchConfig::default(),
             redaction: RedactionConfig::default(),
@@ -57,6 +74,60 @@ impl Default for TelemetryConfig {
 pub struct ExporterConfig {
     pub otlp: Option<OtlpConfig>,
     pub stdout: bool,
+    pub prometheus: Option<PrometheusConfig>,
+    pub file: Option<FileExporterConfig>,
+}
+
+/// Local JSONL trace file, one finished span per line.
+#[derive(Debug, Clone)]
+pub struct FileExporterConfig {
+    pub path: PathBuf,
+    /// Keep existing lines instead of truncating the file on startup.
+    pub append: bool,
+}
+
+impl FileExporterConfig {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            append: false,
+        }
+    }
+
+    pub fn with_append(mut self, append: bool) -> Self {
+        self.append = append;
+        self
+    }
+}
+
+/// Prometheus scrape endpoint serving the metrics pipeline.
+#[derive(Debug, Clone)]
+pub struct PrometheusConfig {
+    pub bind_address: SocketAddr,
+    pub path: String,
+}
+
+impl PrometheusConfig {
+    pub fn new(bind_address: SocketAddr) -> Self {
+        Self {
+            bind_address,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+}
+
+impl Default for PrometheusConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: SocketAddr::from(([127, 0, 0, 1], 9464)),
+            path: "/metrics".to_string(),
+        }
+    }
 }
 
 /// Span batcher configuration to avoid per-span exports.
@@ -119,13 +190,80 @@ pub enum OtlpProtocol {
     HttpJson,
 }
 
-/// Redaction flags for telemetry payloads.
+/// Capture policy for telemetry payloads, applied before anything is exported.
+///
+/// The `redact_*` toggles replace a field with `[REDACTED]`. Fields that are
+/// captured are scrubbed with `scrub_patterns` (plus built-in email, phone, SSN,
+/// and card patterns when `scrub_pii` is set) and then truncated to
+/// `max_value_length` characters.
 #[derive(Debug, Clone, Default)]
 pub struct RedactionConfig {
+    /// Task prompts.
     pub redact_task_inputs: bool,
+    /// Final task completions.
     pub redact_task_outputs: bool,
     pub redact_tool_arguments: bool,
     pub redact_tool_results: bool,
+    /// Task and tool error messages.
+    pub redact_errors: bool,
+    pub max_value_length: Option<usize>,
+    pub scrub_pii: bool,
+    /// Every match is replaced with `[REDACTED]`.
+    pub scrub_patterns: Vec<Regex>,
+}
+
+impl RedactionConfig {
+    /// Drop every prompt, completion, tool payload, and error message.
+    pub fn redact_all() -> Self {
+        Self {
+            redact_task_inputs: true,
+            redact_task_outputs: true,
+            redact_tool_arguments: true,
+            redact_tool_results: true,
+            redact_errors: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_max_value_length(mut self, max_chars: usize) -> Self {
+        self.max_value_length = Some(max_chars);
+        self
+    }
+
+    pub fn with_pii_scrubbing(mut self, enabled: bool) -> Self {
+        self.scrub_pii = enabled;
+        self
+    }
+
+    pub fn with_scrub_pattern(mut self, pattern: Regex) -> Self {
+        self.scrub_patterns.push(pattern);
+        self
+    }
+
+    pub(crate) fn apply(&self, value: String, redact: bool) -> String {
+        if redact {
+            return "[REDACTED]".to_string();
+        }
+        let mut value = value;
+        for pattern in &self.scrub_patterns {
+            if let std::borrow::Cow::Owned(scrubbed) = pattern.replace_all(&value, "[REDACTED]") {
+                value = scrubbed;
+            }
+        }
+        if self.scrub_pii
+            && let std::borrow::Cow::Owned(scrubbed) =
+                redact_pii(&value, PiiKind::default_replacement)
+        {
+            value = scrubbed;
+        }
+        if let Some(max_chars) = self.max_value_length
+            && let Some((cut, _)) = value.char_indices().nth(max_chars)
+        {
+            value.truncate(cut);
+            value.push_str("...[truncated]");
+        }
+        value
+    }
 }
 
 #[cfg(test)]
@@ -177,11 +315,16 @@ mod tests {
 
         assert!(exporter.otlp.is_none());
         assert!(!exporter.stdout);
+        assert!(exporter.prometheus.is_none());
 
         assert!(!redaction.redact_task_inputs);
         assert!(!redaction.redact_task_outputs);
         assert!(!redaction.redact_tool_arguments);
         assert!(!redaction.redact_tool_results);
+        assert!(!redaction.redact_errors);
+        assert!(!redaction.scrub_pii);
+        assert!(redaction.max_value_length.is_none());
+        assert!(redaction.scrub_patterns.is_empty());
 
         assert_eq!(
             otlp.endpoint.as_deref(),
@@ -191,4 +334,53 @@ mod tests {
         assert!(otlp.headers.is_empty());
         assert!(!otlp.debug_http);
     }
+
+    #[test]
+    fn prometheus_config_defaults_to_local_scrape_port() {
+        let config = PrometheusConfig::default();
+        assert_eq!(
+            config.bind_address,
+            SocketAddr::from(([127, 0, 0, 1], 9464))
+        );
+        assert_eq!(config.path, "/metrics");
+
+        let custom =
+            PrometheusConfig::new(SocketAddr::from(([0, 0, 0, 0], 9100))).with_path("/prom");
+        assert_eq!(custom.bind_address.port(), 9100);
+        assert_eq!(custom.path, "/prom");
+    }
+
+    #[test]
+    fn redaction_toggle_replaces_whole_value() {
+        let redaction = RedactionConfig::redact_all().with_pii_scrubbing(true);
+        assert_eq!(
+            redaction.apply("mail me at a@b.io".to_string(), redaction.redact_errors),
+            "[REDACTED]"
+        );
+    }
+
+    #[test]
+    fn redaction_scrubs_pii_and_custom_patterns() {
+        let redaction = RedactionConfig::default()
+            .with_pii_scrubbing(true)
+            .with_scrub_pattern(Regex::new(r"sk-[A-Za-z0-9]+").unwrap());
+        let scrubbed = redaction.apply(
+            "key sk-abc123 for jane@example.com, ssn 123-45-6789, call 555-123-4567".to_string(),
+            false,
+        );
+        assert_eq!(
+            scrubbed,
+            "key [REDACTED] for [redacted:email], ssn [redacted:ssn], call [redacted:phone]"
+        );
+    }
+
+    #[test]
+    fn redaction_truncates_on_char_boundaries() {
+        let redaction = RedactionConfig::default().with_max_value_length(3);
+        assert_eq!(
+            redaction.apply("héllo".to_string(), false),
+            "hél...[truncated]"
+        );
+        assert_eq!(redaction.apply("hé".to_string(), false), "hé");
+    }
 }
@@ -0,0 +1,235 @@
+use autoagents_protocol::SubmissionId;
+use opentelemetry::trace::SpanContext;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Name of the span core opens around each agent run. It only carries the
+/// submission id for correlation and is never exported on its own.
+pub(crate) const RUN_SPAN_NAME: &str = "autoagents.agent.run";
+
+/// Shared map from submission id to the exported task span.
+///
+/// The telemetry runner fills it as tasks start, and [`TraceCorrelationLayer`]
+/// together with [`CorrelatedFormat`] use it to stamp `trace_id`/`span_id` onto
+/// every log record emitted while that task runs, including `log` records
+/// bridged into `tracing` from core, LLM backends, and tools.
+#[derive(Debug, Clone, Default)]
+pub struct TraceCorrelation {
+    spans: Arc<RwLock<HashMap<SubmissionId, SpanContext>>>,
+}
+
+impl TraceCorrelation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Span context of the task span exported for `sub_id`, if it is still running.
+    pub fn lookup(&self, sub_id: SubmissionId) -> Option<SpanContext> {
+        self.spans
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&sub_id)
+            .cloned()
+    }
+
+    /// Layer that tags agent run spans so their log records can be correlated.
+    pub fn layer(&self) -> TraceCorrelationLayer {
+        TraceCorrelationLayer
+    }
+
+    /// Wrap an event formatter so each line is prefixed with `trace_id=.. span_id=..`.
+    pub fn format<F>(&self, inner: F) -> CorrelatedFormat<F> {
+        CorrelatedFormat {
+            inner,
+            correlation: self.clone(),
+        }
+    }
+
+    pub(crate) fn insert(&self, sub_id: SubmissionId, context: SpanContext) {
+        self.spans
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(sub_id)
+            .or_insert(context);
+    }
+
+    pub(crate) fn remove(&self, sub_id: SubmissionId) {
+        self.spans
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&sub_id);
+    }
+
+    fn current<S, N>(&self, ctx: &FmtContext<'_, S, N>) -> Option<SpanContext>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+        N: for<'a> FormatFields<'a> + 'static,
+    {
+        ctx.event_scope()?.find_map(|span| {
+            let sub_id = span.extensions().get::<CorrelatedSubmission>()?.0;
+            self.lookup(sub_id)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CorrelatedSubmission(SubmissionId);
+
+#[derive(Default)]
+struct SubmissionVisitor(Option<SubmissionId>);
+
+impl SubmissionVisitor {
+    fn parse(&mut self, field: &Field, value: &str) {
+        if field.name() == "submission_id" {
+            self.0 = value.parse().ok();
+        }
+    }
+}
+
+impl Visit for SubmissionVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.parse(field, value);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.parse(field, &format!("{value:?}"));
+    }
+}
+
+/// `tracing-subscriber` layer that remembers the submission id of agent spans.
+///
+/// Install it next to your own fmt layer (see [`TraceCorrelation::format`]) when
+/// telemetry is started with `install_tracing_subscriber = false`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TraceCorrelationLayer;
+
+impl<S> Layer<S> for TraceCorrelationLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = SubmissionVisitor::default();
+        attrs.record(&mut visitor);
+        if let Some(sub_id) = visitor.0
+            && let Some(span) = ctx.span(id)
+        {
+            span.extensions_mut().insert(CorrelatedSubmission(sub_id));
+        }
+    }
+}
+
+/// Event formatter that prefixes records with the active task's trace and span ids.
+#[derive(Debug, Clone)]
+pub struct CorrelatedFormat<F> {
+    inner: F,
+    correlation: TraceCorrelation,
+}
+
+impl<S, N, F> FormatEvent<S, N> for CorrelatedFormat<F>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+    F: FormatEvent<S, N>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        if let Some(context) = self.correlation.current(ctx) {
+            write!(
+                writer,
+                "trace_id={} span_id={} ",
+                context.trace_id(),
+                context.span_id()
+            )?;
+        }
+        self.inner.format_event(ctx, writer, event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{SpanId, TraceFlags, TraceId, TraceState};
+    use std::io;
+    use std::sync::Mutex;
+    use tracing_subscriber::fmt::MakeWriter;
+    use tracing_subscriber::fmt::format::Format;
+    use tracing_subscriber::prelude::*;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn log_records_carry_task_trace_ids() {
+        let correlation = TraceCorrelation::new();
+        let buffer = Buffer::default();
+        let subscriber = tracing_subscriber::Registry::default()
+            .with(correlation.layer())
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(buffer.clone())
+                    .event_format(correlation.format(Format::default())),
+            );
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let sub_id = SubmissionId::new_v4();
+        let context = SpanContext::new(
+            TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(),
+            SpanId::from_hex("00f067aa0ba902b7").unwrap(),
+            TraceFlags::SAMPLED,
+            false,
+            TraceState::default(),
+        );
+        correlation.insert(sub_id, context);
+
+        tracing::info!("outside any run");
+        let span = tracing::info_span!(RUN_SPAN_NAME, submission_id = %sub_id);
+        span.in_scope(|| {
+            tracing::info_span!("tool").in_scope(|| tracing::info!("inside the run"));
+        });
+        correlation.remove(sub_id);
+        span.in_scope(|| tracing::info!("after the task finished"));
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(!lines[0].contains("trace_id="));
+        assert!(
+            lines[1]
+                .starts_with("trace_id=4bf92f3577b34da6a3ce929d0e0e4736 span_id=00f067aa0ba902b7 ")
+        );
+        assert!(lines[1].contains("inside the run"));
+        assert!(!lines[2].contains("trace_id="));
+    }
+}
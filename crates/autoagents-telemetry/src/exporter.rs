@@ -1,4 +1,6 @@
 use crate::config::{OtlpConfig, OtlpProtocol, TelemetryConfig};
+use crate::jsonl::JsonlSpanExporter;
+use crate::runner::TelemetryError;
 use opentelemetry::KeyValue;
 #[cfg(not(target_arch = "wasm32"))]
 use opentelemetry_http::{Bytes, HttpClient, HttpError, Request, Response};
@@ -35,6 +37,7 @@ impl MultiSpanExporter {
 pub(crate) enum SpanExporterWrapper {
     Otlp(Box<OtlpSpanExporter>),
     Stdout(StdoutSpanExporter),
+    File(JsonlSpanExporter),
 }
 
 impl SpanExporterWrapper {
@@ -42,6 +45,7 @@ impl SpanExporterWrapper {
         match self {
             SpanExporterWrapper::Otlp(exporter) => exporter.export(batch).await,
             SpanExporterWrapper::Stdout(exporter) => exporter.export(batch).await,
+            SpanExporterWrapper::File(exporter) => exporter.export(batch).await,
         }
     }
 
@@ -49,6 +53,7 @@ impl SpanExporterWrapper {
         match self {
             SpanExporterWrapper::Otlp(exporter) => exporter.force_flush(),
             SpanExporterWrapper::Stdout(exporter) => exporter.force_flush(),
+            SpanExporterWrapper::File(exporter) => exporter.force_flush(),
         }
     }
 
@@ -56,6 +61,7 @@ impl SpanExporterWrapper {
         match self {
             SpanExporterWrapper::Otlp(exporter) => exporter.shutdown_with_timeout(timeout),
             SpanExporterWrapper::Stdout(exporter) => exporter.shutdown_with_timeout(timeout),
+            SpanExporterWrapper::File(exporter) => exporter.shutdown_with_timeout(timeout),
         }
     }
 
@@ -63,6 +69,7 @@ impl SpanExporterWrapper {
         match self {
             SpanExporterWrapper::Otlp(exporter) => exporter.set_resource(resource),
             SpanExporterWrapper::Stdout(exporter) => exporter.set_resource(resource),
+            SpanExporterWrapper::File(exporter) => exporter.set_resource(resource),
         }
     }
 }
@@ -193,7 +200,7 @@ impl SpanExporter for StdoutSpanExporter {
 
 pub(crate) fn build_span_exporter(
     config: &TelemetryConfig,
-) -> Result<MultiSpanExporter, opentelemetry_otlp::ExporterBuildError> {
+) -> Result<MultiSpanExporter, TelemetryError> {
     let mut exporters = Vec::new();
 
     if let Some(otlp) = &config.exporter.otlp {
@@ -205,6 +212,11 @@ pub(crate) fn build_span_exporter(
         exporters.push(SpanExporterWrapper::Stdout(StdoutSpanExporter));
     }
 
+    if let Some(file) = &config.exporter.file {
+        let exporter = JsonlSpanExporter::new(file).map_err(TelemetryError::TraceFile)?;
+        exporters.push(SpanExporterWrapper::File(exporter));
+    }
+
     Ok(MultiSpanExporter::new(exporters))
 }
 
@@ -281,6 +293,10 @@ pub(crate) fn resource_attributes(config: &TelemetryConfig) -> Vec<KeyValue> {
         attributes.push(KeyValue::new("runtime.id", runtime_id.to_string()));
     }
 
+    for (key, value) in &config.resource_attributes {
+        attributes.push(KeyValue::new(key.clone(), value.clone()));
+    }
+
     attributes
 }
 
@@ -303,6 +319,21 @@ mod tests {
         assert!(!exporter.is_empty());
     }
 
+    #[test]
+    fn test_build_span_exporter_file() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let mut config = TelemetryConfig::default();
+        config.exporter.file = Some(crate::FileExporterConfig::new(dir.path().join("run.jsonl")));
+        let exporter = build_span_exporter(&config).expect("span exporter");
+        assert!(!exporter.is_empty());
+
+        config.exporter.file = Some(crate::FileExporterConfig::new(dir.path()));
+        assert!(matches!(
+            build_span_exporter(&config),
+            Err(TelemetryError::TraceFile(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_multi_span_exporter_export_and_flush() {
         let exporter =
@@ -321,12 +352,14 @@ mod tests {
             environment: Some("staging".to_string()),
             runtime_id: Some(autoagents_protocol::RuntimeID::new_v4()),
             ..Default::default()
-        };
+        }
+        .with_resource_attribute("openinference.project.name", "demo");
 
         let attributes = resource_attributes(&config);
         let keys: Vec<_> = attributes.iter().map(|kv| kv.key.as_str()).collect();
         assert!(keys.contains(&"service.version"));
         assert!(keys.contains(&"deployment.environment"));
         assert!(keys.contains(&"runtime.id"));
+        assert!(keys.contains(&"openinference.project.name"));
     }
 }
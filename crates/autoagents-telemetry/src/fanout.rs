@@ -47,6 +47,7 @@ mod tests {
             actor_id: autoagents_protocol::ActorID::new_v4(),
             actor_name: "agent".to_string(),
             task_description: "task".to_string(),
+            trace: None,
         };
         let stream = Box::pin(iter(vec![event.clone()]));
         let fanout = EventFanout::new(stream, 8);
@@ -0,0 +1,223 @@
+use crate::config::FileExporterConfig;
+use opentelemetry::trace::{SpanId, Status};
+use opentelemetry::{Array, Value};
+use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
+use opentelemetry_sdk::trace::{SpanData, SpanExporter};
+use serde_json::{Map, Value as JsonValue, json};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Span exporter that appends one JSON object per finished span to a local file.
+#[derive(Debug)]
+pub(crate) struct JsonlSpanExporter {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl JsonlSpanExporter {
+    pub(crate) fn new(config: &FileExporterConfig) -> io::Result<Self> {
+        if let Some(parent) = config.path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(config.append)
+            .truncate(!config.append)
+            .open(&config.path)?;
+        Ok(Self {
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    fn write_batch(&self, batch: &[SpanData]) -> io::Result<()> {
+        let mut writer = self
+            .writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        for span in batch {
+            serde_json::to_writer(&mut *writer, &span_to_json(span))?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()
+    }
+}
+
+impl SpanExporter for JsonlSpanExporter {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        self.write_batch(&batch)
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.writer
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .flush()
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))
+    }
+
+    fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+        self.force_flush()
+    }
+}
+
+fn span_to_json(span: &SpanData) -> JsonValue {
+    let mut line = Map::new();
+    line.insert(
+        "trace_id".into(),
+        span.span_context.trace_id().to_string().into(),
+    );
+    line.insert(
+        "span_id".into(),
+        span.span_context.span_id().to_string().into(),
+    );
+    if span.parent_span_id != SpanId::INVALID {
+        line.insert(
+            "parent_span_id".into(),
+            span.parent_span_id.to_string().into(),
+        );
+    }
+    line.insert("name".into(), span.name.to_string().into());
+    line.insert(
+        "start_time_unix_nano".into(),
+        unix_nanos(span.start_time).into(),
+    );
+    line.insert(
+        "end_time_unix_nano".into(),
+        unix_nanos(span.end_time).into(),
+    );
+    let duration_ms = span
+        .end_time
+        .duration_since(span.start_time)
+        .unwrap_or_default()
+        .as_secs_f64()
+        * 1000.0;
+    line.insert("duration_ms".into(), json!(duration_ms));
+    let (status, message) = match &span.status {
+        Status::Unset => ("unset", None),
+        Status::Ok => ("ok", None),
+        Status::Error { description } => ("error", Some(description.to_string())),
+    };
+    line.insert("status".into(), status.into());
+    if let Some(message) = message {
+        line.insert("status_message".into(), message.into());
+    }
+    line.insert(
+        "attributes".into(),
+        JsonValue::Object(
+            span.attributes
+                .iter()
+                .map(|kv| (kv.key.to_string(), otel_to_json(&kv.value)))
+                .collect(),
+        ),
+    );
+    let events: Vec<JsonValue> = span
+        .events
+        .iter()
+        .map(|event| {
+            json!({
+                "name": event.name.to_string(),
+                "time_unix_nano": unix_nanos(event.timestamp),
+                "attributes": event
+                    .attributes
+                    .iter()
+                    .map(|kv| (kv.key.to_string(), otel_to_json(&kv.value)))
+                    .collect::<Map<_, _>>(),
+            })
+        })
+        .collect();
+    if !events.is_empty() {
+        line.insert("events".into(), events.into());
+    }
+    JsonValue::Object(line)
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+fn otel_to_json(value: &Value) -> JsonValue {
+    match value {
+        Value::Bool(value) => (*value).into(),
+        Value::I64(value) => (*value).into(),
+        Value::F64(value) => json!(value),
+        Value::String(value) => value.as_str().into(),
+        Value::Array(array) => match array {
+            Array::Bool(values) => json!(values),
+            Array::I64(values) => json!(values),
+            Array::F64(values) => json!(values),
+            Array::String(values) => {
+                json!(values.iter().map(|v| v.as_str()).collect::<Vec<_>>())
+            }
+            other => other.to_string().into(),
+        },
+        other => other.to_string().into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::KeyValue;
+    use opentelemetry::trace::{Span, Tracer, TracerProvider};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+
+    #[test]
+    fn writes_one_json_line_per_span() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("traces/run.jsonl");
+        let exporter = JsonlSpanExporter::new(&FileExporterConfig::new(&path)).expect("exporter");
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .build();
+        let tracer = provider.tracer("jsonl-test");
+
+        let mut span = tracer.start("autoagents.task");
+        span.set_attribute(KeyValue::new("task.description", "hello"));
+        span.set_attribute(KeyValue::new("turn_number", 2_i64));
+        span.add_event("checkpoint", vec![KeyValue::new("ok", true)]);
+        span.set_status(Status::error("boom"));
+        span.end();
+        provider.force_flush().expect("flush");
+
+        let contents = std::fs::read_to_string(&path).expect("trace file");
+        let lines: Vec<JsonValue> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("valid json line"))
+            .collect();
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0];
+        assert_eq!(line["name"], "autoagents.task");
+        assert_eq!(line["attributes"]["task.description"], "hello");
+        assert_eq!(line["attributes"]["turn_number"], 2);
+        assert_eq!(line["status"], "error");
+        assert_eq!(line["status_message"], "boom");
+        assert_eq!(line["events"][0]["attributes"]["ok"], true);
+        assert!(line.get("parent_span_id").is_none());
+    }
+
+    #[test]
+    fn append_mode_preserves_existing_lines() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("run.jsonl");
+        std::fs::write(&path, "{\"existing\":true}\n").expect("seed file");
+
+        let exporter = JsonlSpanExporter::new(&FileExporterConfig::new(&path).with_append(true))
+            .expect("exporter");
+        exporter.write_batch(&[]).expect("empty batch");
+        drop(exporter);
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("trace file"),
+            "{\"existing\":true}\n"
+        );
+
+        JsonlSpanExporter::new(&FileExporterConfig::new(&path)).expect("exporter");
+        assert_eq!(std::fs::read_to_string(&path).expect("trace file"), "");
+    }
+}
@@ -1,14 +1,29 @@
 mod config;
+mod correlation;
 mod exporter;
 mod fanout;
+mod jsonl;
+mod prometheus;
 mod providers;
 mod runner;
 mod tracer;
+mod usage;
 
-pub use config::{ExporterConfig, OtlpConfig, OtlpProtocol, RedactionConfig, TelemetryConfig};
+pub use config::{
+    ExporterConfig, FileExporterConfig, OtlpConfig, OtlpProtocol, PrometheusConfig,
+    RedactionConfig, TelemetryConfig,
+};
+pub use correlation::{CorrelatedFormat, TraceCorrelation, TraceCorrelationLayer};
 pub use fanout::EventFanout;
+#[cfg(feature = "braintrust")]
+pub use providers::braintrust::{BraintrustProject, BraintrustTelemetry};
 #[cfg(feature = "langfuse")]
 pub use providers::langfuse::{LangfuseRegion, LangfuseTelemetry};
+#[cfg(feature = "openinference")]
+pub use providers::openinference::{OpenInferenceTarget, OpenInferenceTelemetry};
+#[cfg(feature = "weave")]
+pub use providers::weave::WeaveTelemetry;
 pub use providers::{TelemetryAttributeProvider, TelemetryProvider};
 pub use runner::{TelemetryError, TelemetryHandle};
 pub use tracer::Tracer;
+pub use usage::{ModelPricing, UsageAggregator, UsageKey, UsageRecord, UsageSummary, UsageTotals};
@@ -0,0 +1,432 @@
+use crate::config::PrometheusConfig;
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData, ResourceMetrics};
+use opentelemetry_sdk::metrics::reader::MetricReader;
+use opentelemetry_sdk::metrics::{InstrumentKind, ManualReader, Pipeline, Temporality};
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+const MAX_REQUEST_HEAD_BYTES: usize = 8 * 1024;
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Pull-based metric reader shared between the meter provider and the scrape endpoint.
+#[derive(Debug, Clone)]
+pub(crate) struct PrometheusReader {
+    inner: Arc<ManualReader>,
+}
+
+impl PrometheusReader {
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: Arc::new(
+                ManualReader::builder()
+                    .with_temporality(Temporality::Cumulative)
+                    .build(),
+            ),
+        }
+    }
+
+    /// Collect the current metric state and encode it in the Prometheus text format.
+    pub(crate) fn render(&self) -> Result<String, String> {
+        let mut metrics = ResourceMetrics::default();
+        self.inner
+            .collect(&mut metrics)
+            .map_err(|err| err.to_string())?;
+        Ok(encode_text(&metrics))
+    }
+}
+
+impl MetricReader for PrometheusReader {
+    fn register_pipeline(&self, pipeline: Weak<Pipeline>) {
+        self.inner.register_pipeline(pipeline);
+    }
+
+    fn collect(&self, rm: &mut ResourceMetrics) -> OTelSdkResult {
+        self.inner.collect(rm)
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn temporality(&self, kind: InstrumentKind) -> Temporality {
+        self.inner.temporality(kind)
+    }
+}
+
+/// Running scrape endpoint; stopped when the telemetry handle shuts down.
+pub(crate) struct PrometheusServer {
+    pub(crate) local_addr: SocketAddr,
+    pub(crate) task: JoinHandle<()>,
+    pub(crate) shutdown_tx: watch::Sender<bool>,
+}
+
+/// Bind the scrape endpoint and serve `reader` until shutdown is signalled.
+pub(crate) fn spawn_server(
+    config: &PrometheusConfig,
+    reader: PrometheusReader,
+) -> std::io::Result<PrometheusServer> {
+    let listener = std::net::TcpListener::bind(config.bind_address)?;
+    listener.set_nonblocking(true)?;
+    let listener = TcpListener::from_std(listener)?;
+    let local_addr = listener.local_addr()?;
+    let path: Arc<str> = Arc::from(config.path.as_str());
+    let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+
+    let task = tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.changed() => break,
+                accepted = listener.accept() => {
+                    let Ok((stream, _)) = accepted else {
+                        continue;
+                    };
+                    let reader = reader.clone();
+                    let path = path.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = handle_connection(stream, &reader, &path).await {
+                            tracing::debug!(
+                                target: "autoagents.telemetry.prometheus",
+                                error = %err,
+                                "Prometheus scrape connection failed"
+                            );
+                        }
+                    });
+                }
+            }
+        }
+    });
+
+    Ok(PrometheusServer {
+        local_addr,
+        task,
+        shutdown_tx,
+    })
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    reader: &PrometheusReader,
+    path: &str,
+) -> std::io::Result<()> {
+    let mut head = Vec::with_capacity(1024);
+    let mut buffer = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buffer[..read]);
+        if head.len() > MAX_REQUEST_HEAD_BYTES {
+            return write_response(&mut stream, "431 Request Header Fields Too Large", "").await;
+        }
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let target = request_line.next().unwrap_or_default();
+    let target_path = target.split('?').next().unwrap_or_default();
+
+    if target_path != path {
+        return write_response(&mut stream, "404 Not Found", "").await;
+    }
+    if method != "GET" && method != "HEAD" {
+        return write_response(&mut stream, "405 Method Not Allowed", "").await;
+    }
+
+    match reader.render() {
+        Ok(body) if method == "HEAD" => {
+            write_head(&mut stream, "200 OK", body.len()).await?;
+            stream.shutdown().await
+        }
+        Ok(body) => write_response(&mut stream, "200 OK", &body).await,
+        Err(err) => write_response(&mut stream, "500 Internal Server Error", &err).await,
+    }
+}
+
+async fn write_head(stream: &mut TcpStream, status: &str, body_len: usize) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {CONTENT_TYPE}\r\nContent-Length: {body_len}\r\nConnection: close\r\n\r\n"
+    );
+    stream.write_all(head.as_bytes()).await
+}
+
+async fn write_response(stream: &mut TcpStream, status: &str, body: &str) -> std::io::Result<()> {
+    write_head(stream, status, body.len()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+trait PrometheusValue: Copy {
+    fn render(self) -> String;
+}
+
+impl PrometheusValue for u64 {
+    fn render(self) -> String {
+        self.to_string()
+    }
+}
+
+impl PrometheusValue for i64 {
+    fn render(self) -> String {
+        self.to_string()
+    }
+}
+
+impl PrometheusValue for f64 {
+    fn render(self) -> String {
+        format_float(self)
+    }
+}
+
+/// Encode collected metrics using the Prometheus text exposition format.
+pub(crate) fn encode_text(metrics: &ResourceMetrics) -> String {
+    let mut output = String::new();
+    for scope in metrics.scope_metrics() {
+        for metric in scope.metrics() {
+            let name = sanitize_name(metric.name());
+            match metric.data() {
+                AggregatedMetrics::F64(data) => {
+                    encode_metric(&mut output, &name, metric.description(), data)
+                }
+                AggregatedMetrics::U64(data) => {
+                    encode_metric(&mut output, &name, metric.description(), data)
+                }
+                AggregatedMetrics::I64(data) => {
+                    encode_metric(&mut output, &name, metric.description(), data)
+                }
+            }
+        }
+    }
+    output
+}
+
+fn encode_metric<T: PrometheusValue>(
+    output: &mut String,
+    name: &str,
+    description: &str,
+    data: &MetricData<T>,
+) {
+    match data {
+        MetricData::Sum(sum) => {
+            let name = if sum.is_monotonic() && !name.ends_with("_total") {
+                format!("{name}_total")
+            } else {
+                name.to_string()
+            };
+            let kind = if sum.is_monotonic() {
+                "counter"
+            } else {
+                "gauge"
+            };
+            write_header(output, &name, description, kind);
+            for point in sum.data_points() {
+                let labels = render_labels(point.attributes(), None);
+                let _ = writeln!(output, "{name}{labels} {}", point.value().render());
+            }
+        }
+        MetricData::Gauge(gauge) => {
+            write_header(output, name, description, "gauge");
+            for point in gauge.data_points() {
+                let labels = render_labels(point.attributes(), None);
+                let _ = writeln!(output, "{name}{labels} {}", point.value().render());
+            }
+        }
+        MetricData::Histogram(histogram) => {
+            write_header(output, name, description, "histogram");
+            for point in histogram.data_points() {
+                let mut cumulative = 0u64;
+                let bounds: Vec<f64> = point.bounds().collect();
+                for (index, count) in point.bucket_counts().enumerate() {
+                    cumulative += count;
+                    let bound = bounds
+                        .get(index)
+                        .map(|bound| format_float(*bound))
+                        .unwrap_or_else(|| "+Inf".to_string());
+                    let labels = render_labels(point.attributes(), Some(&bound));
+                    let _ = writeln!(output, "{name}_bucket{labels} {cumulative}");
+                }
+                let labels = render_labels(point.attributes(), None);
+                let _ = writeln!(output, "{name}_sum{labels} {}", point.sum().render());
+                let _ = writeln!(output, "{name}_count{labels} {}", point.count());
+            }
+        }
+        MetricData::ExponentialHistogram(_) => {}
+    }
+}
+
+fn write_header(output: &mut String, name: &str, description: &str, kind: &str) {
+    if !description.is_empty() {
+        let _ = writeln!(output, "# HELP {name} {}", escape_help(description));
+    }
+    let _ = writeln!(output, "# TYPE {name} {kind}");
+}
+
+fn render_labels<'a>(attributes: impl Iterator<Item = &'a KeyValue>, le: Option<&str>) -> String {
+    let mut labels: Vec<String> = attributes
+        .map(|kv| {
+            format!(
+                "{}=\"{}\"",
+                sanitize_name(kv.key.as_str()),
+                escape_label_value(&kv.value.to_string())
+            )
+        })
+        .collect();
+    labels.sort();
+    if let Some(le) = le {
+        labels.push(format!("le=\"{le}\""));
+    }
+    if labels.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", labels.join(","))
+    }
+}
+
+fn sanitize_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '_' || ch == ':' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.starts_with(|ch: char| ch.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_help(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value.is_sign_positive() {
+            "+Inf".to_string()
+        } else {
+            "-Inf".to_string()
+        }
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::metrics::MeterProvider as _;
+    use opentelemetry_sdk::metrics::SdkMeterProvider;
+
+    fn provider_with_reader() -> (SdkMeterProvider, PrometheusReader) {
+        let reader = PrometheusReader::new();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(reader.clone())
+            .build();
+        (provider, reader)
+    }
+
+    #[test]
+    fn renders_counters_and_histograms() {
+        let (provider, reader) = provider_with_reader();
+        let meter = provider.meter("test");
+        let counter = meter.u64_counter("autoagents.tasks.total").build();
+        counter.add(2, &[KeyValue::new("status", "completed")]);
+        let histogram = meter
+            .f64_histogram("autoagents.task.duration.seconds")
+            .with_boundaries(vec![1.0, 5.0])
+            .build();
+        histogram.record(0.5, &[]);
+        histogram.record(3.0, &[]);
+
+        let text = reader.render().expect("render metrics");
+
+        assert!(text.contains("# TYPE autoagents_tasks_total counter"));
+        assert!(text.contains("autoagents_tasks_total{status=\"completed\"} 2"));
+        assert!(text.contains("# TYPE autoagents_task_duration_seconds histogram"));
+        assert!(text.contains("autoagents_task_duration_seconds_bucket{le=\"1\"} 1"));
+        assert!(text.contains("autoagents_task_duration_seconds_bucket{le=\"5\"} 2"));
+        assert!(text.contains("autoagents_task_duration_seconds_bucket{le=\"+Inf\"} 2"));
+        assert!(text.contains("autoagents_task_duration_seconds_count 2"));
+        assert!(text.contains("autoagents_task_duration_seconds_sum 3.5"));
+    }
+
+    #[test]
+    fn sanitizes_names_and_escapes_label_values() {
+        assert_eq!(sanitize_name("tool.name"), "tool_name");
+        assert_eq!(sanitize_name("9lives"), "_9lives");
+        assert_eq!(escape_label_value("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+        assert_eq!(format_float(f64::INFINITY), "+Inf");
+    }
+
+    #[tokio::test]
+    async fn scrape_endpoint_serves_metrics() {
+        let (provider, reader) = provider_with_reader();
+        provider
+            .meter("test")
+            .u64_counter("autoagents.tool_calls.total")
+            .build()
+            .add(1, &[]);
+
+        let config = PrometheusConfig::new(SocketAddr::from(([127, 0, 0, 1], 0)));
+        let server = spawn_server(&config, reader).expect("bind scrape endpoint");
+
+        let mut stream = TcpStream::connect(server.local_addr)
+            .await
+            .expect("connect");
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .expect("write request");
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .await
+            .expect("read response");
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("autoagents_tool_calls_total 1"));
+
+        let mut stream = TcpStream::connect(server.local_addr)
+            .await
+            .expect("connect");
+        stream
+            .write_all(b"GET /other HTTP/1.1\r\n\r\n")
+            .await
+            .expect("write request");
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .await
+            .expect("read response");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+
+        let _ = server.shutdown_tx.send(true);
+        server.task.await.expect("server stops");
+    }
+}
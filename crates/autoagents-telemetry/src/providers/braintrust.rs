@@ -0,0 +1,265 @@
+use crate::config::{ExporterConfig, OtlpConfig, OtlpProtocol, TelemetryConfig};
+use crate::providers::{TelemetryAttributeProvider, TelemetryProvider};
+use autoagents_protocol::Usage;
+use opentelemetry::Value;
+use serde_json::{Value as JsonValue, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Braintrust project the spans are logged to.
+#[derive(Debug, Clone)]
+pub enum BraintrustProject {
+    Id(String),
+    Name(String),
+}
+
+impl BraintrustProject {
+    fn parent_header(&self) -> String {
+        match self {
+            BraintrustProject::Id(id) => format!("project_id:{id}"),
+            BraintrustProject::Name(name) => format!("project_name:{name}"),
+        }
+    }
+}
+
+/// Braintrust-specific telemetry configuration builder.
+///
+/// Metrics export is disabled by default because Braintrust only ingests traces.
+#[derive(Debug, Clone)]
+pub struct BraintrustTelemetry {
+    api_key: String,
+    project: BraintrustProject,
+    base_url: String,
+    stdout: bool,
+    service_name: String,
+    debug_http: bool,
+    metrics_enabled: bool,
+    install_tracing_subscriber: bool,
+}
+
+impl BraintrustTelemetry {
+    pub fn new(api_key: impl Into<String>, project: BraintrustProject) -> Self {
+        Self {
+            api_key: api_key.into(),
+            project,
+            base_url: "https://api.braintrust.dev".to_string(),
+            stdout: false,
+            service_name: "autoagents".to_string(),
+            debug_http: false,
+            metrics_enabled: false,
+            install_tracing_subscriber: true,
+        }
+    }
+
+    /// Point at a self-hosted Braintrust data plane.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_stdout(mut self, enabled: bool) -> Self {
+        self.stdout = enabled;
+        self
+    }
+
+    pub fn with_service_name(mut self, name: impl Into<String>) -> Self {
+        self.service_name = name.into();
+        self
+    }
+
+    pub fn with_http_debug(mut self, enabled: bool) -> Self {
+        self.debug_http = enabled;
+        self
+    }
+
+    pub fn with_metrics(mut self, enabled: bool) -> Self {
+        self.metrics_enabled = enabled;
+        self
+    }
+
+    pub fn with_tracing_subscriber(mut self, enabled: bool) -> Self {
+        self.install_tracing_subscriber = enabled;
+        self
+    }
+
+    pub fn build(self) -> TelemetryConfig {
+        let mut config = TelemetryConfig::new(self.service_name.clone());
+        config.install_tracing_subscriber = self.install_tracing_subscriber;
+        config.metrics_enabled = self.metrics_enabled;
+        config.exporter = ExporterConfig {
+            otlp: Some(self.otlp_config()),
+            stdout: self.stdout,
+            ..Default::default()
+        };
+        config
+    }
+
+    fn otlp_config(&self) -> OtlpConfig {
+        let mut otlp = OtlpConfig::new(format!("{}/otel", self.base_url.trim_end_matches('/')));
+        otlp.protocol = OtlpProtocol::HttpBinary;
+        otlp.headers = HashMap::from([
+            (
+                "Authorization".to_string(),
+                format!("Bearer {}", self.api_key),
+            ),
+            ("x-bt-parent".to_string(), self.project.parent_header()),
+        ]);
+        otlp.debug_http = self.debug_http;
+        otlp
+    }
+}
+
+impl TelemetryProvider for BraintrustTelemetry {
+    fn telemetry_config(&self) -> TelemetryConfig {
+        self.clone().build()
+    }
+
+    fn attribute_provider(&self) -> Option<Arc<dyn TelemetryAttributeProvider>> {
+        Some(Arc::new(BraintrustAttributeProvider))
+    }
+}
+
+#[derive(Debug)]
+struct BraintrustAttributeProvider;
+
+fn span_attributes(kind: &str, name: &str) -> Value {
+    Value::from(json!({ "type": kind, "name": name }).to_string())
+}
+
+impl TelemetryAttributeProvider for BraintrustAttributeProvider {
+    fn task_started_attributes(
+        &self,
+        actor_name: &str,
+        task_input: &str,
+    ) -> Vec<(&'static str, Value)> {
+        vec![
+            (
+                "braintrust.span_attributes",
+                span_attributes("task", actor_name),
+            ),
+            ("braintrust.input_json", Value::from(as_json(task_input))),
+        ]
+    }
+
+    fn task_completed_attributes(&self, task_output: &str) -> Vec<(&'static str, Value)> {
+        vec![("braintrust.output_json", Value::from(as_json(task_output)))]
+    }
+
+    fn tool_started_attributes(
+        &self,
+        tool_name: &str,
+        tool_args: &str,
+    ) -> Vec<(&'static str, Value)> {
+        vec![
+            (
+                "braintrust.span_attributes",
+                span_attributes("tool", tool_name),
+            ),
+            ("braintrust.input_json", Value::from(as_json(tool_args))),
+        ]
+    }
+
+    fn tool_completed_attributes(
+        &self,
+        _tool_name: &str,
+        tool_output: &str,
+    ) -> Vec<(&'static str, Value)> {
+        vec![("braintrust.output_json", Value::from(as_json(tool_output)))]
+    }
+
+    fn tool_failed_attributes(&self, tool_name: &str, error: &str) -> Vec<(&'static str, Value)> {
+        vec![
+            (
+                "braintrust.span_attributes",
+                span_attributes("tool", tool_name),
+            ),
+            (
+                "braintrust.metadata",
+                Value::from(json!({ "error": error }).to_string()),
+            ),
+        ]
+    }
+
+    fn llm_call_attributes(
+        &self,
+        model: &str,
+        usage: Option<&Usage>,
+    ) -> Vec<(&'static str, Value)> {
+        let mut attributes = vec![
+            ("braintrust.span_attributes", span_attributes("llm", model)),
+            (
+                "braintrust.metadata",
+                Value::from(json!({ "model": model }).to_string()),
+            ),
+        ];
+        if let Some(usage) = usage {
+            attributes.push((
+                "braintrust.metrics",
+                Value::from(
+                    json!({
+                        "prompt_tokens": usage.prompt_tokens,
+                        "completion_tokens": usage.completion_tokens,
+                        "tokens": usage.total_tokens,
+                    })
+                    .to_string(),
+                ),
+            ));
+        }
+        attributes
+    }
+}
+
+fn as_json(value: &str) -> String {
+    if serde_json::from_str::<JsonValue>(value).is_ok() {
+        value.to_string()
+    } else {
+        serde_json::to_string(value).unwrap_or_else(|_| value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_braintrust_build_headers() {
+        let config =
+            BraintrustTelemetry::new("key", BraintrustProject::Name("agents".to_string())).build();
+        let otlp = config.exporter.otlp.expect("otlp exporter");
+        assert_eq!(
+            otlp.endpoint.as_deref(),
+            Some("https://api.braintrust.dev/otel")
+        );
+        assert_eq!(
+            otlp.headers.get("Authorization").map(String::as_str),
+            Some("Bearer key")
+        );
+        assert_eq!(
+            otlp.headers.get("x-bt-parent").map(String::as_str),
+            Some("project_name:agents")
+        );
+        assert!(!config.metrics_enabled);
+    }
+
+    #[test]
+    fn test_braintrust_attribute_provider_schema() {
+        let provider = BraintrustAttributeProvider;
+        let attrs = provider.task_started_attributes("agent", "plain input");
+        let span: JsonValue = match &attrs[0].1 {
+            Value::String(value) => serde_json::from_str(value.as_str()).expect("json"),
+            other => panic!("unexpected value: {other:?}"),
+        };
+        assert_eq!(span["type"], "task");
+        assert_eq!(attrs[1].1, Value::from("\"plain input\""));
+
+        let usage = Usage {
+            prompt_tokens: 1,
+            completion_tokens: 2,
+            total_tokens: 3,
+            completion_tokens_details: None,
+            prompt_tokens_details: None,
+        };
+        let attrs = provider.llm_call_attributes("gpt-4o", Some(&usage));
+        assert!(attrs.iter().any(|(k, _)| *k == "braintrust.metrics"));
+    }
+}
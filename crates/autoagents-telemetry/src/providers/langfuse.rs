@@ -1,5 +1,6 @@
 use crate::config::{ExporterConfig, OtlpConfig, OtlpProtocol, TelemetryConfig};
 use crate::providers::{TelemetryAttributeProvider, TelemetryProvider};
+use autoagents_protocol::TraceAttributes;
 use base64::{Engine as _, engine::general_purpose};
 use opentelemetry::Value;
 use serde_json::Value as JsonValue;
@@ -85,6 +86,7 @@ impl LangfuseTelemetry {
         config.exporter = ExporterConfig {
             otlp: Some(self.otlp_config()),
             stdout: self.stdout,
+            ..Default::default()
         };
         config
     }
@@ -193,6 +195,26 @@ impl TelemetryAttributeProvider for LangfuseAttributeProvider {
             ),
         ]
     }
+
+    fn trace_attributes(&self, trace: &TraceAttributes) -> Vec<(String, Value)> {
+        let mut attributes = Vec::new();
+        if let Some(user_id) = &trace.user_id {
+            attributes.push(("langfuse.user.id".to_string(), Value::from(user_id.clone())));
+        }
+        if let Some(session_id) = &trace.session_id {
+            attributes.push((
+                "langfuse.session.id".to_string(),
+                Value::from(session_id.clone()),
+            ));
+        }
+        for (key, value) in &trace.attributes {
+            attributes.push((
+                format!("langfuse.trace.metadata.{key}"),
+                crate::runner::json_to_otel(value),
+            ));
+        }
+        attributes
+    }
 }
 
 fn normalize_langfuse_json(value: &str) -> String {
@@ -250,4 +272,27 @@ mod tests {
         let attrs = provider.tool_failed_attributes("tool", "err");
         assert!(attrs.iter().any(|(k, _)| *k == "langfuse.observation.name"));
     }
+
+    #[test]
+    fn test_langfuse_trace_attributes() {
+        let provider = LangfuseAttributeProvider;
+        let trace = TraceAttributes {
+            user_id: Some("user".to_string()),
+            session_id: Some("session".to_string()),
+            attributes: [("plan".to_string(), serde_json::json!("pro"))].into(),
+        };
+        let keys: Vec<_> = provider
+            .trace_attributes(&trace)
+            .into_iter()
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "langfuse.user.id",
+                "langfuse.session.id",
+                "langfuse.trace.metadata.plan"
+            ]
+        );
+    }
 }
@@ -2,6 +2,7 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use crate::TelemetryConfig;
+use autoagents_protocol::{TraceAttributes, Usage};
 use opentelemetry::Value;
 
 /// Provides a telemetry configuration per tracer instance.
@@ -31,7 +32,31 @@ pub trait TelemetryAttributeProvider: Debug + Send + Sync {
         tool_output: &str,
     ) -> Vec<(&'static str, Value)>;
     fn tool_failed_attributes(&self, tool_name: &str, error: &str) -> Vec<(&'static str, Value)>;
+    fn turn_started_attributes(
+        &self,
+        _turn_number: usize,
+        _max_turns: usize,
+    ) -> Vec<(&'static str, Value)> {
+        Vec::new()
+    }
+    fn llm_call_attributes(
+        &self,
+        _model: &str,
+        _usage: Option<&Usage>,
+    ) -> Vec<(&'static str, Value)> {
+        Vec::new()
+    }
+    /// Provider-specific keys for the user/session attribution of a task.
+    fn trace_attributes(&self, _trace: &TraceAttributes) -> Vec<(String, Value)> {
+        Vec::new()
+    }
 }
 
+#[cfg(feature = "braintrust")]
+pub mod braintrust;
 #[cfg(feature = "langfuse")]
 pub mod langfuse;
+#[cfg(feature = "openinference")]
+pub mod openinference;
+#[cfg(feature = "weave")]
+pub mod weave;
@@ -0,0 +1,387 @@
+use crate::config::{ExporterConfig, OtlpConfig, OtlpProtocol, TelemetryConfig};
+use crate::providers::{TelemetryAttributeProvider, TelemetryProvider};
+use autoagents_protocol::{TraceAttributes, Usage};
+use opentelemetry::Value;
+use serde_json::Value as JsonValue;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+const SPAN_KIND: &str = "openinference.span.kind";
+const MIME_JSON: &str = "application/json";
+const MIME_TEXT: &str = "text/plain";
+
+/// OpenInference-compatible backend receiving the traces.
+#[derive(Debug, Clone)]
+pub enum OpenInferenceTarget {
+    /// Self-hosted or cloud Arize Phoenix collector.
+    Phoenix {
+        base_url: String,
+        api_key: Option<String>,
+    },
+    /// Arize AX OTLP ingestion.
+    Arize { space_id: String, api_key: String },
+}
+
+impl OpenInferenceTarget {
+    fn base_url(&self) -> String {
+        match self {
+            OpenInferenceTarget::Phoenix { base_url, .. } => base_url.clone(),
+            OpenInferenceTarget::Arize { .. } => "https://otlp.arize.com".to_string(),
+        }
+    }
+
+    fn headers(&self) -> HashMap<String, String> {
+        match self {
+            OpenInferenceTarget::Phoenix { api_key, .. } => api_key
+                .iter()
+                .map(|key| ("Authorization".to_string(), format!("Bearer {key}")))
+                .collect(),
+            OpenInferenceTarget::Arize { space_id, api_key } => HashMap::from([
+                ("space_id".to_string(), space_id.clone()),
+                ("api_key".to_string(), api_key.clone()),
+            ]),
+        }
+    }
+}
+
+/// Telemetry configuration builder emitting OpenInference semantic conventions,
+/// so traces render natively in Arize Phoenix and Arize AX.
+///
+/// Metrics export is disabled by default because both backends only ingest traces.
+#[derive(Debug, Clone)]
+pub struct OpenInferenceTelemetry {
+    target: OpenInferenceTarget,
+    project_name: Option<String>,
+    retriever_tools: HashSet<String>,
+    stdout: bool,
+    service_name: String,
+    debug_http: bool,
+    metrics_enabled: bool,
+    install_tracing_subscriber: bool,
+}
+
+impl OpenInferenceTelemetry {
+    pub fn new(target: OpenInferenceTarget) -> Self {
+        Self {
+            target,
+            project_name: None,
+            retriever_tools: HashSet::new(),
+            stdout: false,
+            service_name: "autoagents".to_string(),
+            debug_http: false,
+            metrics_enabled: false,
+            install_tracing_subscriber: true,
+        }
+    }
+
+    /// Export to a Phoenix collector, e.g. `http://localhost:6006`.
+    pub fn phoenix(base_url: impl Into<String>) -> Self {
+        Self::new(OpenInferenceTarget::Phoenix {
+            base_url: base_url.into(),
+            api_key: None,
+        })
+    }
+
+    pub fn arize(space_id: impl Into<String>, api_key: impl Into<String>) -> Self {
+        Self::new(OpenInferenceTarget::Arize {
+            space_id: space_id.into(),
+            api_key: api_key.into(),
+        })
+    }
+
+    /// Set the Phoenix API key; ignored for Arize targets.
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        if let OpenInferenceTarget::Phoenix { api_key, .. } = &mut self.target {
+            *api_key = Some(key.into());
+        }
+        self
+    }
+
+    pub fn with_project_name(mut self, name: impl Into<String>) -> Self {
+        self.project_name = Some(name.into());
+        self
+    }
+
+    /// Tools whose spans should be reported with the `RETRIEVER` span kind.
+    pub fn with_retriever_tools<I, S>(mut self, tools: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.retriever_tools
+            .extend(tools.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn with_stdout(mut self, enabled: bool) -> Self {
+        self.stdout = enabled;
+        self
+    }
+
+    pub fn with_service_name(mut self, name: impl Into<String>) -> Self {
+        self.service_name = name.into();
+        self
+    }
+
+    pub fn with_http_debug(mut self, enabled: bool) -> Self {
+        self.debug_http = enabled;
+        self
+    }
+
+    pub fn with_metrics(mut self, enabled: bool) -> Self {
+        self.metrics_enabled = enabled;
+        self
+    }
+
+    pub fn with_tracing_subscriber(mut self, enabled: bool) -> Self {
+        self.install_tracing_subscriber = enabled;
+        self
+    }
+
+    pub fn build(self) -> TelemetryConfig {
+        let mut config = TelemetryConfig::new(self.service_name.clone());
+        config.install_tracing_subscriber = self.install_tracing_subscriber;
+        config.metrics_enabled = self.metrics_enabled;
+        config.exporter = ExporterConfig {
+            otlp: Some(self.otlp_config()),
+            stdout: self.stdout,
+            ..Default::default()
+        };
+        if let Some(project) = &self.project_name {
+            config = config.with_resource_attribute("openinference.project.name", project.clone());
+            if matches!(self.target, OpenInferenceTarget::Arize { .. }) {
+                config = config.with_resource_attribute("model_id", project.clone());
+            }
+        }
+        config
+    }
+
+    fn otlp_config(&self) -> OtlpConfig {
+        let mut otlp = OtlpConfig::new(self.target.base_url().trim_end_matches('/').to_string());
+        otlp.protocol = OtlpProtocol::HttpBinary;
+        otlp.headers = self.target.headers();
+        otlp.debug_http = self.debug_http;
+        otlp
+    }
+}
+
+impl TelemetryProvider for OpenInferenceTelemetry {
+    fn telemetry_config(&self) -> TelemetryConfig {
+        self.clone().build()
+    }
+
+    fn attribute_provider(&self) -> Option<Arc<dyn TelemetryAttributeProvider>> {
+        Some(Arc::new(OpenInferenceAttributeProvider {
+            retriever_tools: self.retriever_tools.clone(),
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct OpenInferenceAttributeProvider {
+    retriever_tools: HashSet<String>,
+}
+
+impl OpenInferenceAttributeProvider {
+    fn tool_kind(&self, tool_name: &str) -> &'static str {
+        if self.retriever_tools.contains(tool_name) {
+            "RETRIEVER"
+        } else {
+            "TOOL"
+        }
+    }
+}
+
+impl TelemetryAttributeProvider for OpenInferenceAttributeProvider {
+    fn task_started_attributes(
+        &self,
+        actor_name: &str,
+        task_input: &str,
+    ) -> Vec<(&'static str, Value)> {
+        vec![
+            (SPAN_KIND, Value::from("AGENT")),
+            ("agent.name", Value::from(actor_name.to_string())),
+            ("input.value", Value::from(task_input.to_string())),
+            ("input.mime_type", Value::from(mime_type(task_input))),
+        ]
+    }
+
+    fn task_completed_attributes(&self, task_output: &str) -> Vec<(&'static str, Value)> {
+        vec![
+            ("output.value", Value::from(task_output.to_string())),
+            ("output.mime_type", Value::from(mime_type(task_output))),
+        ]
+    }
+
+    fn tool_started_attributes(
+        &self,
+        tool_name: &str,
+        tool_args: &str,
+    ) -> Vec<(&'static str, Value)> {
+        vec![
+            (SPAN_KIND, Value::from(self.tool_kind(tool_name))),
+            ("tool.name", Value::from(tool_name.to_string())),
+            ("tool.parameters", Value::from(tool_args.to_string())),
+            ("input.value", Value::from(tool_args.to_string())),
+            ("input.mime_type", Value::from(mime_type(tool_args))),
+        ]
+    }
+
+    fn tool_completed_attributes(
+        &self,
+        tool_name: &str,
+        tool_output: &str,
+    ) -> Vec<(&'static str, Value)> {
+        vec![
+            (SPAN_KIND, Value::from(self.tool_kind(tool_name))),
+            ("output.value", Value::from(tool_output.to_string())),
+            ("output.mime_type", Value::from(mime_type(tool_output))),
+        ]
+    }
+
+    fn tool_failed_attributes(&self, tool_name: &str, _error: &str) -> Vec<(&'static str, Value)> {
+        vec![(SPAN_KIND, Value::from(self.tool_kind(tool_name)))]
+    }
+
+    fn turn_started_attributes(
+        &self,
+        _turn_number: usize,
+        _max_turns: usize,
+    ) -> Vec<(&'static str, Value)> {
+        vec![(SPAN_KIND, Value::from("CHAIN"))]
+    }
+
+    fn llm_call_attributes(
+        &self,
+        model: &str,
+        usage: Option<&Usage>,
+    ) -> Vec<(&'static str, Value)> {
+        let mut attributes = vec![
+            (SPAN_KIND, Value::from("LLM")),
+            ("llm.model_name", Value::from(model.to_string())),
+        ];
+        if let Some(usage) = usage {
+            attributes.extend([
+                (
+                    "llm.token_count.prompt",
+                    Value::from(i64::from(usage.prompt_tokens)),
+                ),
+                (
+                    "llm.token_count.completion",
+                    Value::from(i64::from(usage.completion_tokens)),
+                ),
+                (
+                    "llm.token_count.total",
+                    Value::from(i64::from(usage.total_tokens)),
+                ),
+            ]);
+        }
+        attributes
+    }
+
+    fn trace_attributes(&self, trace: &TraceAttributes) -> Vec<(String, Value)> {
+        if trace.attributes.is_empty() {
+            return Vec::new();
+        }
+        let metadata = serde_json::to_string(&trace.attributes).unwrap_or_default();
+        vec![("metadata".to_string(), Value::from(metadata))]
+    }
+}
+
+fn mime_type(value: &str) -> &'static str {
+    if serde_json::from_str::<JsonValue>(value)
+        .is_ok_and(|json| json.is_object() || json.is_array())
+    {
+        MIME_JSON
+    } else {
+        MIME_TEXT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attr<'a>(attrs: &'a [(&'static str, Value)], key: &str) -> Option<&'a Value> {
+        attrs.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    #[test]
+    fn phoenix_config_targets_collector_with_project() {
+        let config = OpenInferenceTelemetry::phoenix("http://localhost:6006/")
+            .with_api_key("secret")
+            .with_project_name("agents")
+            .build();
+        let otlp = config.exporter.otlp.expect("otlp exporter");
+        assert_eq!(otlp.endpoint.as_deref(), Some("http://localhost:6006"));
+        assert_eq!(
+            otlp.headers.get("Authorization").map(String::as_str),
+            Some("Bearer secret")
+        );
+        assert_eq!(
+            config
+                .resource_attributes
+                .get("openinference.project.name")
+                .map(String::as_str),
+            Some("agents")
+        );
+        assert!(!config.metrics_enabled);
+    }
+
+    #[test]
+    fn arize_config_sets_space_headers() {
+        let config = OpenInferenceTelemetry::arize("space", "key")
+            .with_project_name("agents")
+            .build();
+        let otlp = config.exporter.otlp.expect("otlp exporter");
+        assert_eq!(otlp.endpoint.as_deref(), Some("https://otlp.arize.com"));
+        assert_eq!(
+            otlp.headers.get("space_id").map(String::as_str),
+            Some("space")
+        );
+        assert_eq!(otlp.headers.get("api_key").map(String::as_str), Some("key"));
+        assert!(config.resource_attributes.contains_key("model_id"));
+    }
+
+    #[test]
+    fn maps_span_kinds_and_io() {
+        let telemetry = OpenInferenceTelemetry::phoenix("http://localhost:6006")
+            .with_retriever_tools(["search"]);
+        let provider = telemetry.attribute_provider().expect("attribute provider");
+
+        let attrs = provider.task_started_attributes("agent", "{\"q\":1}");
+        assert_eq!(attr(&attrs, SPAN_KIND), Some(&Value::from("AGENT")));
+        assert_eq!(
+            attr(&attrs, "input.mime_type"),
+            Some(&Value::from(MIME_JSON))
+        );
+
+        let attrs = provider.task_completed_attributes("done");
+        assert_eq!(
+            attr(&attrs, "output.mime_type"),
+            Some(&Value::from(MIME_TEXT))
+        );
+
+        let attrs = provider.tool_started_attributes("search", "{}");
+        assert_eq!(attr(&attrs, SPAN_KIND), Some(&Value::from("RETRIEVER")));
+        let attrs = provider.tool_started_attributes("calculator", "{}");
+        assert_eq!(attr(&attrs, SPAN_KIND), Some(&Value::from("TOOL")));
+
+        let usage = Usage {
+            prompt_tokens: 10,
+            completion_tokens: 5,
+            total_tokens: 15,
+            completion_tokens_details: None,
+            prompt_tokens_details: None,
+        };
+        let attrs = provider.llm_call_attributes("gpt-4o", Some(&usage));
+        assert_eq!(attr(&attrs, SPAN_KIND), Some(&Value::from("LLM")));
+        assert_eq!(
+            attr(&attrs, "llm.token_count.total"),
+            Some(&Value::from(15_i64))
+        );
+
+        let attrs = provider.turn_started_attributes(0, 3);
+        assert_eq!(attr(&attrs, SPAN_KIND), Some(&Value::from("CHAIN")));
+    }
+}
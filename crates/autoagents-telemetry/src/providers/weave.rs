@@ -0,0 +1,216 @@
+use crate::config::{ExporterConfig, OtlpConfig, OtlpProtocol, TelemetryConfig};
+use crate::providers::{TelemetryAttributeProvider, TelemetryProvider};
+use autoagents_protocol::Usage;
+use base64::{Engine as _, engine::general_purpose};
+use opentelemetry::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Weights & Biases Weave telemetry configuration builder.
+///
+/// Metrics export is disabled by default because Weave only ingests traces.
+#[derive(Debug, Clone)]
+pub struct WeaveTelemetry {
+    api_key: String,
+    entity: String,
+    project: String,
+    base_url: String,
+    stdout: bool,
+    service_name: String,
+    debug_http: bool,
+    metrics_enabled: bool,
+    install_tracing_subscriber: bool,
+}
+
+impl WeaveTelemetry {
+    pub fn new(
+        api_key: impl Into<String>,
+        entity: impl Into<String>,
+        project: impl Into<String>,
+    ) -> Self {
+        Self {
+            api_key: api_key.into(),
+            entity: entity.into(),
+            project: project.into(),
+            base_url: "https://trace.wandb.ai".to_string(),
+            stdout: false,
+            service_name: "autoagents".to_string(),
+            debug_http: false,
+            metrics_enabled: false,
+            install_tracing_subscriber: true,
+        }
+    }
+
+    /// Point at a dedicated or self-managed W&B deployment.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_stdout(mut self, enabled: bool) -> Self {
+        self.stdout = enabled;
+        self
+    }
+
+    pub fn with_service_name(mut self, name: impl Into<String>) -> Self {
+        self.service_name = name.into();
+        self
+    }
+
+    pub fn with_http_debug(mut self, enabled: bool) -> Self {
+        self.debug_http = enabled;
+        self
+    }
+
+    pub fn with_metrics(mut self, enabled: bool) -> Self {
+        self.metrics_enabled = enabled;
+        self
+    }
+
+    pub fn with_tracing_subscriber(mut self, enabled: bool) -> Self {
+        self.install_tracing_subscriber = enabled;
+        self
+    }
+
+    pub fn build(self) -> TelemetryConfig {
+        let mut config = TelemetryConfig::new(self.service_name.clone());
+        config.install_tracing_subscriber = self.install_tracing_subscriber;
+        config.metrics_enabled = self.metrics_enabled;
+        config.exporter = ExporterConfig {
+            otlp: Some(self.otlp_config()),
+            stdout: self.stdout,
+            ..Default::default()
+        };
+        config
+    }
+
+    fn otlp_config(&self) -> OtlpConfig {
+        let mut otlp = OtlpConfig::new(format!("{}/otel", self.base_url.trim_end_matches('/')));
+        otlp.protocol = OtlpProtocol::HttpBinary;
+        let creds = general_purpose::STANDARD.encode(format!("api:{}", self.api_key));
+        otlp.headers = HashMap::from([
+            ("Authorization".to_string(), format!("Basic {creds}")),
+            (
+                "project_id".to_string(),
+                format!("{}/{}", self.entity, self.project),
+            ),
+        ]);
+        otlp.debug_http = self.debug_http;
+        otlp
+    }
+}
+
+impl TelemetryProvider for WeaveTelemetry {
+    fn telemetry_config(&self) -> TelemetryConfig {
+        self.clone().build()
+    }
+
+    fn attribute_provider(&self) -> Option<Arc<dyn TelemetryAttributeProvider>> {
+        Some(Arc::new(WeaveAttributeProvider))
+    }
+}
+
+#[derive(Debug)]
+struct WeaveAttributeProvider;
+
+impl TelemetryAttributeProvider for WeaveAttributeProvider {
+    fn task_started_attributes(
+        &self,
+        actor_name: &str,
+        task_input: &str,
+    ) -> Vec<(&'static str, Value)> {
+        vec![
+            ("wandb.display_name", Value::from(actor_name.to_string())),
+            ("gen_ai.operation.name", Value::from("invoke_agent")),
+            ("gen_ai.agent.name", Value::from(actor_name.to_string())),
+            ("input.value", Value::from(task_input.to_string())),
+        ]
+    }
+
+    fn task_completed_attributes(&self, task_output: &str) -> Vec<(&'static str, Value)> {
+        vec![("output.value", Value::from(task_output.to_string()))]
+    }
+
+    fn tool_started_attributes(
+        &self,
+        tool_name: &str,
+        tool_args: &str,
+    ) -> Vec<(&'static str, Value)> {
+        vec![
+            ("wandb.display_name", Value::from(tool_name.to_string())),
+            ("gen_ai.operation.name", Value::from("execute_tool")),
+            ("gen_ai.tool.name", Value::from(tool_name.to_string())),
+            ("input.value", Value::from(tool_args.to_string())),
+        ]
+    }
+
+    fn tool_completed_attributes(
+        &self,
+        _tool_name: &str,
+        tool_output: &str,
+    ) -> Vec<(&'static str, Value)> {
+        vec![("output.value", Value::from(tool_output.to_string()))]
+    }
+
+    fn tool_failed_attributes(&self, tool_name: &str, _error: &str) -> Vec<(&'static str, Value)> {
+        vec![("gen_ai.tool.name", Value::from(tool_name.to_string()))]
+    }
+
+    fn llm_call_attributes(
+        &self,
+        model: &str,
+        usage: Option<&Usage>,
+    ) -> Vec<(&'static str, Value)> {
+        let mut attributes = vec![
+            ("wandb.display_name", Value::from(model.to_string())),
+            ("gen_ai.operation.name", Value::from("chat")),
+            ("gen_ai.request.model", Value::from(model.to_string())),
+        ];
+        if let Some(usage) = usage {
+            attributes.extend([
+                (
+                    "gen_ai.usage.input_tokens",
+                    Value::from(i64::from(usage.prompt_tokens)),
+                ),
+                (
+                    "gen_ai.usage.output_tokens",
+                    Value::from(i64::from(usage.completion_tokens)),
+                ),
+            ]);
+        }
+        attributes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weave_build_headers() {
+        let config = WeaveTelemetry::new("secret", "team", "agents").build();
+        let otlp = config.exporter.otlp.expect("otlp exporter");
+        assert_eq!(
+            otlp.endpoint.as_deref(),
+            Some("https://trace.wandb.ai/otel")
+        );
+        let expected = general_purpose::STANDARD.encode("api:secret");
+        assert_eq!(
+            otlp.headers.get("Authorization"),
+            Some(&format!("Basic {expected}"))
+        );
+        assert_eq!(
+            otlp.headers.get("project_id").map(String::as_str),
+            Some("team/agents")
+        );
+    }
+
+    #[test]
+    fn test_weave_attribute_provider_keys() {
+        let provider = WeaveAttributeProvider;
+        let attrs = provider.tool_started_attributes("search", "{}");
+        assert!(attrs.iter().any(|(k, _)| *k == "gen_ai.tool.name"));
+        let attrs = provider.llm_call_attributes("gpt-4o", None);
+        assert!(attrs.iter().any(|(k, _)| *k == "gen_ai.request.model"));
+    }
+}
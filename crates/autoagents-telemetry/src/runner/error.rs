@@ -13,6 +13,10 @@ pub enum TelemetryError {
     AlreadyStarted,
     #[error("Telemetry event stream not available")]
     MissingEventStream,
+    #[error("Failed to bind Prometheus scrape endpoint: {0}")]
+    PrometheusBind(std::io::Error),
+    #[error("Failed to open trace file: {0}")]
+    TraceFile(std::io::Error),
 }
 
 impl From<EnvironmentError> for TelemetryError {
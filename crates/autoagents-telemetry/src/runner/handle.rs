@@ -1,5 +1,7 @@
+use crate::prometheus::PrometheusServer;
 use opentelemetry_sdk::metrics::SdkMeterProvider;
 use opentelemetry_sdk::trace::SdkTracerProvider;
+use std::net::SocketAddr;
 use std::time::Duration;
 use tokio::sync::watch;
 use tokio::task::JoinHandle;
@@ -11,9 +13,15 @@ pub struct TelemetryHandle {
     pub(crate) tracer_provider: SdkTracerProvider,
     pub(crate) meter_provider: Option<SdkMeterProvider>,
     pub(crate) shutdown_tx: Option<watch::Sender<bool>>,
+    pub(crate) prometheus: Option<PrometheusServer>,
 }
 
 impl TelemetryHandle {
+    /// Address the Prometheus scrape endpoint is listening on, when enabled.
+    pub fn prometheus_address(&self) -> Option<SocketAddr> {
+        self.prometheus.as_ref().map(|server| server.local_addr)
+    }
+
     pub async fn shutdown(mut self) {
         const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
         const OTEL_TIMEOUT: Duration = Duration::from_secs(2);
@@ -31,6 +39,15 @@ impl TelemetryHandle {
             }
         }
 
+        if let Some(server) = self.prometheus.take() {
+            let _ = server.shutdown_tx.send(true);
+            let mut task = server.task;
+            if timeout(SHUTDOWN_TIMEOUT, &mut task).await.is_err() {
+                task.abort();
+                let _ = task.await;
+            }
+        }
+
         let tracer_provider = self.tracer_provider;
         let _ = timeout(
             OTEL_TIMEOUT,
@@ -73,6 +90,7 @@ mod tests {
             tracer_provider: SdkTracerProvider::builder().build(),
             meter_provider: Some(SdkMeterProvider::builder().build()),
             shutdown_tx: Some(shutdown_tx),
+            prometheus: None,
         };
 
         handle.shutdown().await;
@@ -93,6 +111,7 @@ mod tests {
             tracer_provider: SdkTracerProvider::builder().build(),
             meter_provider: None,
             shutdown_tx: Some(shutdown_tx),
+            prometheus: None,
         };
 
         timeout(Duration::from_secs(5), handle.shutdown())
@@ -1,10 +1,11 @@
 use crate::config::RedactionConfig;
+use crate::correlation::TraceCorrelation;
 use crate::providers::TelemetryAttributeProvider;
 use crate::runner::metrics::TelemetryMetrics;
-use autoagents_protocol::{ActorID, Event, RuntimeID, SubmissionId};
+use autoagents_protocol::{ActorID, Event, RuntimeID, SubmissionId, TraceAttributes, Usage};
 use opentelemetry::KeyValue;
 use opentelemetry::Value;
-use opentelemetry::trace::Status;
+use opentelemetry::trace::{Status, TraceContextExt};
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
@@ -111,6 +112,7 @@ impl Eq for ToolKey {}
 struct TelemetryState {
     task_spans: HashMap<TaskKey, tracing::Span>,
     task_start: HashMap<TaskKey, Instant>,
+    actor_names: HashMap<TaskKey, String>,
     turn_spans: HashMap<TurnKey, tracing::Span>,
     turn_start: HashMap<TurnKey, Instant>,
     tool_spans: HashMap<ToolKey, tracing::Span>,
@@ -122,6 +124,7 @@ impl TelemetryState {
         Self {
             task_spans: HashMap::new(),
             task_start: HashMap::new(),
+            actor_names: HashMap::new(),
             turn_spans: HashMap::new(),
             turn_start: HashMap::new(),
             tool_spans: HashMap::new(),
@@ -137,6 +140,7 @@ pub(crate) struct EventMapper {
     runtime_id: Option<RuntimeID>,
     attributes: Option<Arc<dyn TelemetryAttributeProvider>>,
     flush_tx: Option<tokio::sync::mpsc::UnboundedSender<()>>,
+    correlation: TraceCorrelation,
     state: TelemetryState,
 }
 
@@ -147,6 +151,7 @@ impl EventMapper {
         runtime_id: Option<RuntimeID>,
         attributes: Option<Arc<dyn TelemetryAttributeProvider>>,
         flush_tx: Option<tokio::sync::mpsc::UnboundedSender<()>>,
+        correlation: TraceCorrelation,
     ) -> Self {
         Self {
             metrics,
@@ -154,6 +159,7 @@ impl EventMapper {
             runtime_id,
             attributes,
             flush_tx,
+            correlation,
             state: TelemetryState::new(),
         }
     }
@@ -165,7 +171,8 @@ impl EventMapper {
                 actor_id,
                 actor_name,
                 task_description,
-            } => self.on_task_started(sub_id, actor_id, actor_name, task_description),
+                trace,
+            } => self.on_task_started(sub_id, actor_id, actor_name, task_description, trace),
             Event::TaskComplete {
                 sub_id,
                 actor_id,
@@ -210,6 +217,13 @@ impl EventMapper {
                 tool_name,
                 error,
             } => self.on_tool_failed(sub_id, actor_id, id, tool_name, error),
+            Event::LLMCallCompleted {
+                sub_id,
+                actor_id,
+                model,
+                duration_ms,
+                usage,
+            } => self.on_llm_call_completed(sub_id, actor_id, model, duration_ms, usage),
             _ => {}
         }
     }
@@ -221,9 +235,11 @@ impl EventMapper {
         for (_, span) in self.state.turn_spans.drain() {
             drop(span);
         }
-        for (_, span) in self.state.task_spans.drain() {
+        for (key, span) in self.state.task_spans.drain() {
+            self.correlation.remove(key.sub_id);
             drop(span);
         }
+        self.state.actor_names.clear();
     }
 
     fn on_task_started(
@@ -232,6 +248,7 @@ impl EventMapper {
         actor_id: ActorID,
         actor_name: String,
         task_description: String,
+        trace: Option<TraceAttributes>,
     ) {
         let span = tracing::info_span!(
             "autoagents.task",
@@ -252,9 +269,18 @@ impl EventMapper {
                 .as_ref()
                 .map(|provider| provider.task_started_attributes(&actor_name, &description)),
         );
+        if let Some(trace) = &trace {
+            self.apply_trace_attributes(&span, trace);
+        }
+
+        let context = span.context().span().span_context().clone();
+        if context.is_valid() {
+            self.correlation.insert(sub_id, context);
+        }
 
         let key = TaskKey::new(sub_id, actor_id);
         self.state.task_spans.insert(key, span);
+        self.state.actor_names.insert(key, actor_name);
         self.state
             .task_start
             .insert(TaskKey::new(sub_id, actor_id), Instant::now());
@@ -268,6 +294,8 @@ impl EventMapper {
         result: String,
     ) {
         let key = TaskKey::new(sub_id, actor_id);
+        self.correlation.remove(sub_id);
+        self.state.actor_names.remove(&key);
         if let Some(span) = self.state.task_spans.remove(&key) {
             span.set_attribute("actor_name", actor_name);
             let redacted = self.redact_value(result, self.redaction.redact_task_outputs);
@@ -301,7 +329,10 @@ impl EventMapper {
     }
 
     fn on_task_error(&mut self, sub_id: SubmissionId, actor_id: ActorID, error: String) {
+        let error = self.redact_value(error, self.redaction.redact_errors);
         let key = TaskKey::new(sub_id, actor_id);
+        self.correlation.remove(sub_id);
+        self.state.actor_names.remove(&key);
         if let Some(span) = self.state.task_spans.remove(&key) {
             span.set_status(Status::error(error.clone()));
             span.set_attribute("error.message", error.clone());
@@ -356,6 +387,12 @@ impl EventMapper {
                 turn_max = max_turns as i64
             )
         };
+        self.apply_attributes(
+            &span,
+            self.attributes
+                .as_ref()
+                .map(|provider| provider.turn_started_attributes(turn_number, max_turns)),
+        );
 
         self.state.turn_spans.insert(key, span);
         self.state
@@ -384,6 +421,81 @@ impl EventMapper {
                 start.elapsed().as_secs_f64(),
                 &self.task_metric_attributes(sub_id, actor_id),
             );
+            let mut attrs = self.task_metric_attributes(sub_id, actor_id);
+            attrs.push(KeyValue::new("turn.final", final_turn));
+            metrics.turns_total.add(1, &attrs);
+        }
+    }
+
+    fn on_llm_call_completed(
+        &mut self,
+        sub_id: SubmissionId,
+        actor_id: ActorID,
+        model: String,
+        duration_ms: u64,
+        usage: Option<Usage>,
+    ) {
+        let parent = self
+            .state
+            .turn_spans
+            .iter()
+            .filter(|(key, _)| key.sub_id == sub_id && key.actor_id == actor_id)
+            .max_by_key(|(key, _)| key.turn_number)
+            .map(|(_, span)| span)
+            .or_else(|| self.state.task_spans.get(&TaskKey::new(sub_id, actor_id)));
+        let span = if let Some(parent) = parent {
+            tracing::info_span!(
+                parent: parent,
+                "autoagents.llm_call",
+                submission_id = %sub_id,
+                actor_id = %actor_id,
+                llm_model = %model
+            )
+        } else {
+            tracing::info_span!(
+                "autoagents.llm_call",
+                submission_id = %sub_id,
+                actor_id = %actor_id,
+                llm_model = %model
+            )
+        };
+        span.set_attribute("llm.model", model.clone());
+        span.set_attribute("llm.duration_ms", duration_ms as i64);
+        if let Some(usage) = &usage {
+            span.set_attribute("llm.usage.prompt_tokens", i64::from(usage.prompt_tokens));
+            span.set_attribute(
+                "llm.usage.completion_tokens",
+                i64::from(usage.completion_tokens),
+            );
+            span.set_attribute("llm.usage.total_tokens", i64::from(usage.total_tokens));
+        }
+        self.apply_attributes(
+            &span,
+            self.attributes
+                .as_ref()
+                .map(|provider| provider.llm_call_attributes(&model, usage.as_ref())),
+        );
+        span.set_status(Status::Ok);
+        drop(span);
+
+        let Some(metrics) = &self.metrics else {
+            return;
+        };
+        let attrs = self.llm_metric_attributes(sub_id, actor_id, model);
+        metrics.llm_calls_total.add(1, &attrs);
+        metrics
+            .llm_duration
+            .record(duration_ms as f64 / 1000.0, &attrs);
+
+        if let Some(usage) = usage {
+            for (token_type, count) in [
+                ("prompt", usage.prompt_tokens),
+                ("completion", usage.completion_tokens),
+            ] {
+                let mut token_attrs = attrs.clone();
+                token_attrs.push(KeyValue::new("token.type", token_type));
+                metrics.llm_tokens_total.add(u64::from(count), &token_attrs);
+            }
         }
     }
 
@@ -474,6 +586,7 @@ impl EventMapper {
         tool_name: String,
         error: String,
     ) {
+        let error = self.redact_value(error, self.redaction.redact_errors);
         let key = ToolKey::new(sub_id, actor_id, id.clone());
         if let Some(span) = self.state.tool_spans.remove(&key) {
             span.set_attribute("tool.name", tool_name.clone());
@@ -506,11 +619,7 @@ impl EventMapper {
     }
 
     fn redact_value(&self, value: String, enabled: bool) -> String {
-        if enabled {
-            "[REDACTED]".to_string()
-        } else {
-            value
-        }
+        self.redaction.apply(value, enabled)
     }
 
     fn task_metric_attributes(&self, sub_id: SubmissionId, actor_id: ActorID) -> Vec<KeyValue> {
@@ -524,6 +633,27 @@ impl EventMapper {
         attrs
     }
 
+    /// Labels for the LLM instruments
+    ///
+    /// These feed a cumulative scrape endpoint, so they identify the agent
+    /// and model but not the submission or actor instance, which stay on the
+    /// `autoagents.llm_call` span.
+    fn llm_metric_attributes(
+        &self,
+        sub_id: SubmissionId,
+        actor_id: ActorID,
+        model: String,
+    ) -> Vec<KeyValue> {
+        let mut attrs = vec![KeyValue::new("llm.model", model)];
+        if let Some(actor_name) = self.state.actor_names.get(&TaskKey::new(sub_id, actor_id)) {
+            attrs.push(KeyValue::new("actor_name", actor_name.clone()));
+        }
+        if let Some(runtime_id) = &self.runtime_id {
+            attrs.push(KeyValue::new("runtime_id", runtime_id.to_string()));
+        }
+        attrs
+    }
+
     fn task_metric_attributes_with_status(
         &self,
         sub_id: SubmissionId,
@@ -558,11 +688,40 @@ impl EventMapper {
         }
     }
 
+    fn apply_trace_attributes(&self, span: &tracing::Span, trace: &TraceAttributes) {
+        if let Some(user_id) = &trace.user_id {
+            span.set_attribute("user.id", user_id.clone());
+        }
+        if let Some(session_id) = &trace.session_id {
+            span.set_attribute("session.id", session_id.clone());
+        }
+        for (key, value) in &trace.attributes {
+            span.set_attribute(key.clone(), json_to_otel(value));
+        }
+        if let Some(provider) = &self.attributes {
+            for (key, value) in provider.trace_attributes(trace) {
+                span.set_attribute(key, value);
+            }
+        }
+    }
+
     pub(crate) fn has_open_tasks(&self) -> bool {
         !self.state.task_spans.is_empty()
     }
 }
 
+pub(crate) fn json_to_otel(value: &serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::String(value) => Value::from(value.clone()),
+        serde_json::Value::Bool(value) => Value::from(*value),
+        serde_json::Value::Number(number) => match number.as_i64() {
+            Some(value) => Value::from(value),
+            None => Value::from(number.as_f64().unwrap_or_default()),
+        },
+        other => Value::from(other.to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -597,7 +756,14 @@ mod tests {
         let subscriber = tracing_subscriber::Registry::default().with(otel_layer);
         let _guard = tracing::subscriber::set_default(subscriber);
 
-        let mut mapper = EventMapper::new(None, RedactionConfig::default(), None, None, None);
+        let mut mapper = EventMapper::new(
+            None,
+            RedactionConfig::default(),
+            None,
+            None,
+            None,
+            TraceCorrelation::default(),
+        );
 
         let sub_id = SubmissionId::new_v4();
         let actor_id = ActorID::new_v4();
@@ -607,6 +773,7 @@ mod tests {
             actor_id,
             actor_name: "test-agent".to_string(),
             task_description: "test task".to_string(),
+            trace: None,
         });
         mapper.handle_event(Event::TurnStarted {
             sub_id,
@@ -653,6 +820,108 @@ mod tests {
         assert_eq!(tool_span.parent_span_id, task_id);
     }
 
+    #[test]
+    fn task_spans_are_registered_for_log_correlation() {
+        let exporter = InMemorySpanExporterBuilder::new().build();
+        let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let tracer = provider.tracer("autoagents.telemetry.test");
+        let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
+        let subscriber = tracing_subscriber::Registry::default().with(otel_layer);
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let correlation = TraceCorrelation::new();
+        let mut mapper = EventMapper::new(
+            None,
+            RedactionConfig::default(),
+            None,
+            None,
+            None,
+            correlation.clone(),
+        );
+
+        let sub_id = SubmissionId::new_v4();
+        let actor_id = ActorID::new_v4();
+        mapper.handle_event(Event::TaskStarted {
+            sub_id,
+            actor_id,
+            actor_name: "agent".to_string(),
+            task_description: "task".to_string(),
+            trace: None,
+        });
+        let context = correlation.lookup(sub_id).expect("task registered");
+
+        mapper.handle_event(Event::TaskComplete {
+            sub_id,
+            actor_id,
+            actor_name: "agent".to_string(),
+            result: "ok".to_string(),
+        });
+        assert!(correlation.lookup(sub_id).is_none());
+
+        let spans = exporter.get_finished_spans().expect("spans");
+        let task_span = find_span(&spans, "autoagents.task");
+        assert_eq!(task_span.span_context.trace_id(), context.trace_id());
+        assert_eq!(task_span.span_context.span_id(), context.span_id());
+    }
+
+    #[test]
+    fn llm_call_span_is_nested_under_turn() {
+        let exporter = InMemorySpanExporterBuilder::new().build();
+        let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let tracer = provider.tracer("autoagents.telemetry.test.llm");
+        let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
+        let subscriber = tracing_subscriber::Registry::default().with(otel_layer);
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let mut mapper = EventMapper::new(
+            None,
+            RedactionConfig::default(),
+            None,
+            None,
+            None,
+            TraceCorrelation::default(),
+        );
+
+        let sub_id = SubmissionId::new_v4();
+        let actor_id = ActorID::new_v4();
+
+        mapper.handle_event(Event::TurnStarted {
+            sub_id,
+            actor_id,
+            turn_number: 0,
+            max_turns: 1,
+        });
+        mapper.handle_event(Event::LLMCallCompleted {
+            sub_id,
+            actor_id,
+            model: "mock".to_string(),
+            duration_ms: 42,
+            usage: Some(Usage {
+                prompt_tokens: 3,
+                completion_tokens: 2,
+                total_tokens: 5,
+                completion_tokens_details: None,
+                prompt_tokens_details: None,
+            }),
+        });
+        mapper.flush();
+
+        let spans = exporter.get_finished_spans().expect("spans available");
+        let turn_span = find_span(&spans, "autoagents.turn");
+        let llm_span = find_span(&spans, "autoagents.llm_call");
+
+        assert_eq!(llm_span.parent_span_id, turn_span.span_context.span_id());
+        assert_eq!(attr_value(llm_span, "llm.model"), Some(Value::from("mock")));
+        assert_eq!(
+            attr_value(llm_span, "llm.usage.total_tokens"),
+            Some(Value::from(5_i64))
+        );
+    }
+
     #[derive(Debug)]
     struct TestAttributes;
 
@@ -713,7 +982,14 @@ mod tests {
 
         let attributes: Option<Arc<dyn TelemetryAttributeProvider>> =
             Some(Arc::new(TestAttributes));
-        let mut mapper = EventMapper::new(None, RedactionConfig::default(), None, attributes, None);
+        let mut mapper = EventMapper::new(
+            None,
+            RedactionConfig::default(),
+            None,
+            attributes,
+            None,
+            TraceCorrelation::default(),
+        );
 
         let sub_id = SubmissionId::new_v4();
         let actor_id = ActorID::new_v4();
@@ -723,6 +999,15 @@ mod tests {
             actor_id,
             actor_name: "provider-test".to_string(),
             task_description: "task".to_string(),
+            trace: Some(TraceAttributes {
+                user_id: Some("user-1".to_string()),
+                session_id: Some("session-1".to_string()),
+                attributes: [
+                    ("customer.tier".to_string(), json!("gold")),
+                    ("customer.seats".to_string(), json!(12)),
+                ]
+                .into(),
+            }),
         });
         mapper.handle_event(Event::ToolCallRequested {
             sub_id,
@@ -753,6 +1038,22 @@ mod tests {
 
         assert!(attr_value(task_span, "provider.task.actor").is_some());
         assert!(attr_value(tool_span, "provider.tool.name").is_some());
+        assert_eq!(
+            attr_value(task_span, "user.id"),
+            Some(Value::from("user-1"))
+        );
+        assert_eq!(
+            attr_value(task_span, "session.id"),
+            Some(Value::from("session-1"))
+        );
+        assert_eq!(
+            attr_value(task_span, "customer.tier"),
+            Some(Value::from("gold"))
+        );
+        assert_eq!(
+            attr_value(task_span, "customer.seats"),
+            Some(Value::from(12_i64))
+        );
     }
 
     #[test]
@@ -771,7 +1072,14 @@ mod tests {
             redact_tool_arguments: true,
             ..Default::default()
         };
-        let mut mapper = EventMapper::new(None, redaction, None, None, None);
+        let mut mapper = EventMapper::new(
+            None,
+            redaction,
+            None,
+            None,
+            None,
+            TraceCorrelation::default(),
+        );
 
         let sub_id = SubmissionId::new_v4();
         let actor_id = ActorID::new_v4();
@@ -781,6 +1089,7 @@ mod tests {
             actor_id,
             actor_name: "test-agent".to_string(),
             task_description: "secret task".to_string(),
+            trace: None,
         });
         mapper.handle_event(Event::ToolCallRequested {
             sub_id,
@@ -806,6 +1115,168 @@ mod tests {
         );
     }
 
+    #[test]
+    fn capture_policy_scrubs_and_truncates_before_export() {
+        let exporter = InMemorySpanExporterBuilder::new().build();
+        let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let tracer = provider.tracer("autoagents.telemetry.test.capture");
+        let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
+        let subscriber = tracing_subscriber::Registry::default().with(otel_layer);
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let redaction = RedactionConfig::default()
+            .with_pii_scrubbing(true)
+            .with_max_value_length(24);
+        let mut mapper = EventMapper::new(
+            None,
+            redaction,
+            None,
+            None,
+            None,
+            TraceCorrelation::default(),
+        );
+
+        let sub_id = SubmissionId::new_v4();
+        let actor_id = ActorID::new_v4();
+        mapper.handle_event(Event::TaskStarted {
+            sub_id,
+            actor_id,
+            actor_name: "test-agent".to_string(),
+            task_description: "email jane@example.com".to_string(),
+            trace: None,
+        });
+        mapper.handle_event(Event::ToolCallRequested {
+            sub_id,
+            actor_id,
+            id: "call_1".to_string(),
+            tool_name: "lookup".to_string(),
+            arguments: "{}".to_string(),
+        });
+        mapper.handle_event(Event::ToolCallFailed {
+            sub_id,
+            actor_id,
+            id: "call_1".to_string(),
+            tool_name: "lookup".to_string(),
+            error: "no account for 123-45-6789 in the directory".to_string(),
+        });
+        mapper.flush();
+
+        let spans = exporter.get_finished_spans().expect("spans available");
+        let task_span = find_span(&spans, "autoagents.task");
+        let tool_span = find_span(&spans, "autoagents.tool_call");
+
+        assert_eq!(
+            attr_value(task_span, "task.description"),
+            Some(Value::from("email [redacted:email]"))
+        );
+        assert_eq!(
+            attr_value(tool_span, "error.message"),
+            Some(Value::from("no account for [redacted...[truncated]"))
+        );
+    }
+
+    #[test]
+    fn llm_calls_and_turns_are_recorded_as_metrics() {
+        use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData, ResourceMetrics};
+        use opentelemetry_sdk::metrics::{InMemoryMetricExporter, PeriodicReader};
+
+        let exporter = InMemoryMetricExporter::default();
+        let provider = opentelemetry_sdk::metrics::SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let metrics = TelemetryMetrics::new(&provider);
+        let mut mapper = EventMapper::new(
+            Some(metrics),
+            RedactionConfig::default(),
+            None,
+            None,
+            None,
+            TraceCorrelation::default(),
+        );
+
+        let sub_id = SubmissionId::new_v4();
+        let actor_id = ActorID::new_v4();
+
+        mapper.handle_event(Event::TaskStarted {
+            sub_id,
+            actor_id,
+            actor_name: "support".to_string(),
+            task_description: "test task".to_string(),
+            trace: None,
+        });
+        mapper.handle_event(Event::TurnStarted {
+            sub_id,
+            actor_id,
+            turn_number: 0,
+            max_turns: 1,
+        });
+        mapper.handle_event(Event::LLMCallCompleted {
+            sub_id,
+            actor_id,
+            model: "mock".to_string(),
+            duration_ms: 250,
+            usage: Some(Usage {
+                prompt_tokens: 12,
+                completion_tokens: 4,
+                total_tokens: 16,
+                completion_tokens_details: None,
+                prompt_tokens_details: None,
+            }),
+        });
+        mapper.handle_event(Event::TurnCompleted {
+            sub_id,
+            actor_id,
+            turn_number: 0,
+            final_turn: true,
+        });
+
+        provider.force_flush().expect("flush metrics");
+        let exported: Vec<ResourceMetrics> = exporter.get_finished_metrics().expect("metrics");
+        let sum_for = |name: &str| -> u64 {
+            exported
+                .iter()
+                .flat_map(|rm| rm.scope_metrics())
+                .flat_map(|scope| scope.metrics())
+                .filter(|metric| metric.name() == name)
+                .map(|metric| match metric.data() {
+                    AggregatedMetrics::U64(MetricData::Sum(sum)) => {
+                        sum.data_points().map(|point| point.value()).sum::<u64>()
+                    }
+                    _ => 0,
+                })
+                .sum()
+        };
+
+        assert_eq!(sum_for("autoagents.turns.total"), 1);
+        assert_eq!(sum_for("autoagents.llm.calls.total"), 1);
+        assert_eq!(sum_for("autoagents.llm.tokens.total"), 16);
+
+        let llm_labels: Vec<String> = exported
+            .iter()
+            .flat_map(|rm| rm.scope_metrics())
+            .flat_map(|scope| scope.metrics())
+            .filter(|metric| metric.name() == "autoagents.llm.calls.total")
+            .flat_map(|metric| match metric.data() {
+                AggregatedMetrics::U64(MetricData::Sum(sum)) => sum
+                    .data_points()
+                    .flat_map(|point| point.attributes())
+                    .map(|kv| format!("{}={}", kv.key, kv.value))
+                    .collect(),
+                _ => Vec::new(),
+            })
+            .collect();
+        assert!(llm_labels.contains(&"actor_name=support".to_string()));
+        assert!(llm_labels.contains(&"llm.model=mock".to_string()));
+        assert!(
+            !llm_labels
+                .iter()
+                .any(|label| label.starts_with("submission_id") || label.starts_with("actor_id")),
+            "{llm_labels:?}"
+        );
+    }
+
     #[test]
     fn tool_failure_sets_error_status() {
         let exporter = InMemorySpanExporterBuilder::new().build();
@@ -817,7 +1288,14 @@ mod tests {
         let subscriber = tracing_subscriber::Registry::default().with(otel_layer);
         let _guard = tracing::subscriber::set_default(subscriber);
 
-        let mut mapper = EventMapper::new(None, RedactionConfig::default(), None, None, None);
+        let mut mapper = EventMapper::new(
+            None,
+            RedactionConfig::default(),
+            None,
+            None,
+            None,
+            TraceCorrelation::default(),
+        );
 
         let sub_id = SubmissionId::new_v4();
         let actor_id = ActorID::new_v4();
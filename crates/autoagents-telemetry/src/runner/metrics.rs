@@ -4,11 +4,15 @@ use opentelemetry_sdk::metrics::SdkMeterProvider;
 /// Metric instruments used by the event mapper.
 pub(crate) struct TelemetryMetrics {
     pub(crate) tasks_total: Counter<u64>,
+    pub(crate) turns_total: Counter<u64>,
     pub(crate) tool_calls_total: Counter<u64>,
+    pub(crate) llm_calls_total: Counter<u64>,
+    pub(crate) llm_tokens_total: Counter<u64>,
     pub(crate) errors_total: Counter<u64>,
     pub(crate) task_duration: Histogram<f64>,
     pub(crate) turn_duration: Histogram<f64>,
     pub(crate) tool_duration: Histogram<f64>,
+    pub(crate) llm_duration: Histogram<f64>,
 }
 
 impl TelemetryMetrics {
@@ -17,7 +21,10 @@ impl TelemetryMetrics {
 
         Self {
             tasks_total: meter.u64_counter("autoagents.tasks.total").build(),
+            turns_total: meter.u64_counter("autoagents.turns.total").build(),
             tool_calls_total: meter.u64_counter("autoagents.tool_calls.total").build(),
+            llm_calls_total: meter.u64_counter("autoagents.llm.calls.total").build(),
+            llm_tokens_total: meter.u64_counter("autoagents.llm.tokens.total").build(),
             errors_total: meter.u64_counter("autoagents.errors.total").build(),
             task_duration: meter
                 .f64_histogram("autoagents.task.duration.seconds")
@@ -31,6 +38,10 @@ impl TelemetryMetrics {
                 .f64_histogram("autoagents.tool.duration.seconds")
                 .with_unit("s")
                 .build(),
+            llm_duration: meter
+                .f64_histogram("autoagents.llm.duration.seconds")
+                .with_unit("s")
+                .build(),
         }
     }
 }
@@ -44,10 +55,14 @@ mod tests {
         let provider = SdkMeterProvider::default();
         let metrics = TelemetryMetrics::new(&provider);
         metrics.tasks_total.add(1, &[]);
+        metrics.turns_total.add(1, &[]);
         metrics.tool_calls_total.add(2, &[]);
+        metrics.llm_calls_total.add(1, &[]);
+        metrics.llm_tokens_total.add(42, &[]);
         metrics.errors_total.add(0, &[]);
         metrics.task_duration.record(0.5, &[]);
         metrics.turn_duration.record(1.0, &[]);
         metrics.tool_duration.record(0.25, &[]);
+        metrics.llm_duration.record(0.75, &[]);
     }
 }
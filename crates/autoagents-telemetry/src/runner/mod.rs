@@ -4,7 +4,9 @@ mod mapper;
 mod metrics;
 
 use crate::config::TelemetryConfig;
+use crate::correlation::{RUN_SPAN_NAME, TraceCorrelation};
 use crate::exporter::{build_metric_exporter, build_span_exporter, resource_attributes};
+use crate::prometheus::{PrometheusReader, spawn_server};
 use autoagents_core::utils::BoxEventStream;
 use autoagents_protocol::Event;
 use futures_util::StreamExt;
@@ -19,12 +21,17 @@ use opentelemetry_sdk::trace::{SdkTracerProvider, SpanExporter};
 use tokio::sync::{mpsc, watch};
 use tokio::time::{Duration, Instant};
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::fmt::format::Format;
 use tracing_subscriber::layer::Layer;
 use tracing_subscriber::prelude::*;
 
 pub use error::TelemetryError;
 pub use handle::TelemetryHandle;
 
+#[cfg(feature = "langfuse")]
+pub(crate) use mapper::json_to_otel;
+
 use mapper::EventMapper;
 use metrics::TelemetryMetrics;
 
@@ -49,10 +56,16 @@ pub(crate) fn start_telemetry(
     event_stream: BoxEventStream<Event>,
     config: TelemetryConfig,
     attributes: Option<std::sync::Arc<dyn crate::providers::TelemetryAttributeProvider>>,
+    correlation: TraceCorrelation,
     shutdown_grace: Duration,
 ) -> Result<TelemetryHandle, TelemetryError> {
     let mut exporters = build_span_exporter(&config)?;
-    if exporters.is_empty() {
+    let prometheus = config
+        .exporter
+        .prometheus
+        .as_ref()
+        .filter(|_| config.metrics_enabled);
+    if exporters.is_empty() && prometheus.is_none() {
         return Err(TelemetryError::MissingExporter);
     }
 
@@ -83,9 +96,21 @@ pub(crate) fn start_telemetry(
     if config.install_tracing_subscriber {
         let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
         let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+        // Run spans only carry the submission id for log correlation; the
+        // exported task span is created by the mapper from TaskStarted.
+        let otel_filter = filter_fn(|metadata| metadata.name() != RUN_SPAN_NAME);
         let subscriber = tracing_subscriber::Registry::default()
-            .with(otel_layer.with_filter(filter.clone()))
-            .with(tracing_subscriber::fmt::layer().with_filter(filter));
+            .with(correlation.layer())
+            .with(
+                otel_layer
+                    .with_filter(otel_filter)
+                    .with_filter(filter.clone()),
+            )
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .event_format(correlation.format(Format::default()))
+                    .with_filter(filter),
+            );
         if subscriber.try_init().is_err() {
             eprintln!(
                 "[autoagents-telemetry] tracing subscriber already set; OTLP layer not installed"
@@ -93,6 +118,7 @@ pub(crate) fn start_telemetry(
         }
     }
 
+    let mut prometheus_server = None;
     let meter_provider = if config.metrics_enabled {
         let mut builder = SdkMeterProvider::builder().with_resource(resource);
         if let Some(otlp) = &config.exporter.otlp {
@@ -100,6 +126,13 @@ pub(crate) fn start_telemetry(
             let reader = PeriodicReader::builder(metric_exporter, runtime::Tokio).build();
             builder = builder.with_reader(reader);
         }
+        if let Some(prometheus) = prometheus {
+            let reader = PrometheusReader::new();
+            prometheus_server = Some(
+                spawn_server(prometheus, reader.clone()).map_err(TelemetryError::PrometheusBind)?,
+            );
+            builder = builder.with_reader(reader);
+        }
         Some(builder.build())
     } else {
         None
@@ -115,8 +148,14 @@ pub(crate) fn start_telemetry(
     let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
     let (flush_tx, mut flush_rx) = mpsc::unbounded_channel();
     let task = tokio::spawn(async move {
-        let mut mapper =
-            EventMapper::new(metrics, redaction, runtime_id, attributes, Some(flush_tx));
+        let mut mapper = EventMapper::new(
+            metrics,
+            redaction,
+            runtime_id,
+            attributes,
+            Some(flush_tx),
+            correlation,
+        );
         let mut event_stream = event_stream;
         let mut shutdown_requested = false;
         let mut shutdown_deadline: Option<Instant> = None;
@@ -200,6 +239,7 @@ pub(crate) fn start_telemetry(
         tracer_provider,
         meter_provider,
         shutdown_tx: Some(shutdown_tx),
+        prometheus: prometheus_server,
     })
 }
 
@@ -228,19 +268,58 @@ mod tests {
         config.metrics_enabled = false;
         config.install_tracing_subscriber = false;
 
-        let err = match start_telemetry(stream, config, None, Duration::from_secs(2)) {
+        let err = match start_telemetry(
+            stream,
+            config,
+            None,
+            TraceCorrelation::default(),
+            Duration::from_secs(2),
+        ) {
             Ok(_) => panic!("missing exporter"),
             Err(err) => err,
         };
         assert!(matches!(err, TelemetryError::MissingExporter));
     }
 
+    #[tokio::test]
+    async fn prometheus_only_config_starts_scrape_endpoint() {
+        let (tx, rx) = mpsc::channel::<Event>(1);
+        let stream: BoxEventStream<Event> = Box::pin(ReceiverStream::new(rx));
+        let mut config = TelemetryConfig::new("autoagents-test");
+        config.install_tracing_subscriber = false;
+        config.exporter.prometheus = Some(crate::PrometheusConfig::new(
+            std::net::SocketAddr::from(([127, 0, 0, 1], 0)),
+        ));
+
+        let handle = start_telemetry(
+            stream,
+            config,
+            None,
+            TraceCorrelation::default(),
+            Duration::from_secs(2),
+        )
+        .expect("telemetry starts");
+        let address = handle.prometheus_address().expect("scrape endpoint bound");
+        assert_ne!(address.port(), 0);
+
+        drop(tx);
+        timeout(Duration::from_secs(5), handle.shutdown())
+            .await
+            .expect("shutdown completes");
+    }
+
     #[tokio::test]
     async fn shutdown_completes_without_hanging() {
         let (tx, rx) = mpsc::channel::<Event>(4);
         let stream: BoxEventStream<Event> = Box::pin(ReceiverStream::new(rx));
-        let handle = start_telemetry(stream, test_config(), None, Duration::from_secs(2))
-            .expect("telemetry starts");
+        let handle = start_telemetry(
+            stream,
+            test_config(),
+            None,
+            TraceCorrelation::default(),
+            Duration::from_secs(2),
+        )
+        .expect("telemetry starts");
 
         let _ = tx
             .send(Event::TaskStarted {
@@ -248,6 +327,7 @@ mod tests {
                 actor_id: ActorID::new_v4(),
                 actor_name: "tester".to_string(),
                 task_description: "test".to_string(),
+                trace: None,
             })
             .await;
 
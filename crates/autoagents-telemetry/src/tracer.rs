@@ -5,7 +5,11 @@ use autoagents_protocol::{Event, RuntimeID};
 use std::sync::Arc;
 
 use crate::runner::start_telemetry;
-use crate::{TelemetryConfig, TelemetryError, TelemetryHandle, TelemetryProvider};
+use crate::{
+    TelemetryConfig, TelemetryError, TelemetryHandle, TelemetryProvider, TraceCorrelation,
+    UsageAggregator,
+};
+use futures_util::StreamExt;
 
 /// Owns the telemetry lifecycle for a specific event stream.
 pub struct Tracer {
@@ -14,6 +18,8 @@ pub struct Tracer {
     runtime_id: Option<RuntimeID>,
     handle: Option<TelemetryHandle>,
     shutdown_grace: std::time::Duration,
+    usage: Option<UsageAggregator>,
+    correlation: TraceCorrelation,
 }
 
 impl Tracer {
@@ -24,6 +30,8 @@ impl Tracer {
             runtime_id: None,
             handle: None,
             shutdown_grace: std::time::Duration::from_secs(10),
+            usage: None,
+            correlation: TraceCorrelation::default(),
         }
     }
 
@@ -50,6 +58,8 @@ impl Tracer {
             runtime_id,
             handle: None,
             shutdown_grace: std::time::Duration::from_secs(2),
+            usage: None,
+            correlation: TraceCorrelation::default(),
         })
     }
 
@@ -58,6 +68,23 @@ impl Tracer {
         self
     }
 
+    /// Feed every traced event into `aggregator` as well.
+    pub fn with_usage_aggregator(mut self, aggregator: UsageAggregator) -> Self {
+        self.usage = Some(aggregator);
+        self
+    }
+
+    /// Share `correlation` with a tracing subscriber you install yourself.
+    pub fn with_log_correlation(mut self, correlation: TraceCorrelation) -> Self {
+        self.correlation = correlation;
+        self
+    }
+
+    /// Registry used to stamp trace and span ids onto log records.
+    pub fn log_correlation(&self) -> TraceCorrelation {
+        self.correlation.clone()
+    }
+
     /// Start exporting spans and metrics from the configured event stream.
     pub fn start(&mut self) -> Result<(), TelemetryError> {
         if self.handle.is_some() {
@@ -68,13 +95,32 @@ impl Tracer {
             .event_stream
             .take()
             .ok_or(TelemetryError::MissingEventStream)?;
+        let event_stream = match self.usage.clone() {
+            Some(aggregator) => {
+                Box::pin(event_stream.inspect(move |event| aggregator.record(event)))
+            }
+            None => event_stream,
+        };
         let config = self.provider_config();
         let attributes = self.provider.attribute_provider();
-        let handle = start_telemetry(event_stream, config, attributes, self.shutdown_grace)?;
+        let handle = start_telemetry(
+            event_stream,
+            config,
+            attributes,
+            self.correlation.clone(),
+            self.shutdown_grace,
+        )?;
         self.handle = Some(handle);
         Ok(())
     }
 
+    /// Address of the Prometheus scrape endpoint once the tracer has started.
+    pub fn prometheus_address(&self) -> Option<std::net::SocketAddr> {
+        self.handle
+            .as_ref()
+            .and_then(TelemetryHandle::prometheus_address)
+    }
+
     /// Flush and shut down exporters.
     pub async fn shutdown(&mut self) -> Result<(), TelemetryError> {
         if let Some(handle) = self.handle.take() {
@@ -150,4 +196,31 @@ mod tests {
             .expect("shutdown completes")
             .expect("shutdown is idempotent");
     }
+
+    #[tokio::test]
+    async fn tracer_feeds_usage_aggregator() {
+        let (tx, rx) = mpsc::channel::<Event>(2);
+        let stream: BoxEventStream<Event> = Box::pin(ReceiverStream::new(rx));
+        let provider: Arc<dyn TelemetryProvider> = Arc::new(TestProvider);
+        let aggregator = UsageAggregator::new();
+        let mut tracer = Tracer::new(provider, stream).with_usage_aggregator(aggregator.clone());
+
+        tracer.start().expect("start succeeds");
+        tx.send(Event::LLMCallCompleted {
+            sub_id: autoagents_protocol::SubmissionId::new_v4(),
+            actor_id: autoagents_protocol::ActorID::new_v4(),
+            model: "model".to_string(),
+            duration_ms: 5,
+            usage: None,
+        })
+        .await
+        .expect("event sent");
+        drop(tx);
+        timeout(Duration::from_secs(2), tracer.shutdown())
+            .await
+            .expect("shutdown completes")
+            .expect("shutdown succeeds");
+
+        assert_eq!(aggregator.totals().calls, 1);
+    }
 }
@@ -0,0 +1,482 @@
+use autoagents_core::utils::BoxEventStream;
+use autoagents_protocol::{Event, SubmissionId, Usage};
+use futures_util::StreamExt;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Price of a model expressed in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ModelPricing {
+    pub prompt_per_million: f64,
+    pub completion_per_million: f64,
+}
+
+impl ModelPricing {
+    pub fn per_million(prompt: f64, completion: f64) -> Self {
+        Self {
+            prompt_per_million: prompt,
+            completion_per_million: completion,
+        }
+    }
+
+    fn cost(&self, prompt_tokens: u64, completion_tokens: u64) -> f64 {
+        (prompt_tokens as f64 * self.prompt_per_million
+            + completion_tokens as f64 * self.completion_per_million)
+            / 1_000_000.0
+    }
+}
+
+/// Grouping key for aggregated usage.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct UsageKey {
+    pub agent: String,
+    pub model: String,
+    pub session: Option<String>,
+}
+
+/// Accumulated token counts and the derived cost for a group of LLM calls.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageTotals {
+    pub calls: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    /// `None` when no pricing is registered for any of the models involved.
+    pub cost_usd: Option<f64>,
+}
+
+impl UsageTotals {
+    fn merge(&mut self, other: &UsageTotals) {
+        self.calls += other.calls;
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.total_tokens += other.total_tokens;
+        self.cost_usd = match (self.cost_usd, other.cost_usd) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+/// Usage for a single agent/model/session group.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageRecord {
+    pub key: UsageKey,
+    pub totals: UsageTotals,
+}
+
+/// Point-in-time snapshot of everything the aggregator has seen.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageSummary {
+    pub records: Vec<UsageRecord>,
+    pub totals: UsageTotals,
+}
+
+impl UsageSummary {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+    }
+
+    /// Write one CSV row per record, preceded by a header row.
+    pub fn write_csv<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        let mut csv = csv::Writer::from_writer(writer);
+        csv.write_record([
+            "agent",
+            "model",
+            "session",
+            "calls",
+            "prompt_tokens",
+            "completion_tokens",
+            "total_tokens",
+            "cost_usd",
+        ])?;
+        for record in &self.records {
+            csv.write_record([
+                record.key.agent.clone(),
+                record.key.model.clone(),
+                record.key.session.clone().unwrap_or_default(),
+                record.totals.calls.to_string(),
+                record.totals.prompt_tokens.to_string(),
+                record.totals.completion_tokens.to_string(),
+                record.totals.total_tokens.to_string(),
+                record
+                    .totals
+                    .cost_usd
+                    .map(|cost| format!("{cost:.6}"))
+                    .unwrap_or_default(),
+            ])?;
+        }
+        csv.flush()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Counts {
+    calls: u64,
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    total_tokens: u64,
+}
+
+impl Counts {
+    fn add(&mut self, usage: Option<&Usage>) {
+        self.calls += 1;
+        if let Some(usage) = usage {
+            self.prompt_tokens += u64::from(usage.prompt_tokens);
+            self.completion_tokens += u64::from(usage.completion_tokens);
+            self.total_tokens += u64::from(usage.total_tokens);
+        }
+    }
+}
+
+/// Agent and session the LLM calls of a running task are attributed to.
+#[derive(Debug)]
+struct RunAttribution {
+    agent: String,
+    session: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct AggregatorState {
+    counts: HashMap<UsageKey, Counts>,
+    /// Tasks between `TaskStarted` and `TaskComplete`/`TaskError`.
+    runs: HashMap<SubmissionId, RunAttribution>,
+    pricing: HashMap<String, ModelPricing>,
+}
+
+impl AggregatorState {
+    fn totals(&self, key: &UsageKey, counts: &Counts) -> UsageTotals {
+        UsageTotals {
+            calls: counts.calls,
+            prompt_tokens: counts.prompt_tokens,
+            completion_tokens: counts.completion_tokens,
+            total_tokens: counts.total_tokens,
+            cost_usd: self
+                .pricing
+                .get(&key.model)
+                .map(|pricing| pricing.cost(counts.prompt_tokens, counts.completion_tokens)),
+        }
+    }
+}
+
+/// Accumulates LLM token usage from the event stream per agent, model, and session.
+///
+/// The aggregator is cheap to clone; clones share the same counters.
+#[derive(Debug, Clone, Default)]
+pub struct UsageAggregator {
+    state: Arc<Mutex<AggregatorState>>,
+}
+
+impl UsageAggregator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register pricing for a model so summaries include a cost estimate.
+    pub fn with_pricing(self, model: impl Into<String>, pricing: ModelPricing) -> Self {
+        self.set_pricing(model, pricing);
+        self
+    }
+
+    pub fn set_pricing(&self, model: impl Into<String>, pricing: ModelPricing) {
+        self.lock().pricing.insert(model.into(), pricing);
+    }
+
+    /// Feed a single protocol event into the aggregator.
+    ///
+    /// Calls are attributed to the agent name and the session id of the
+    /// task they belong to, as announced by `TaskStarted`; tasks created
+    /// with `Task::with_session_id` are grouped under that session. The
+    /// attribution is dropped once the task completes or fails.
+    pub fn record(&self, event: &Event) {
+        let mut state = self.lock();
+        match event {
+            Event::TaskStarted {
+                sub_id,
+                actor_name,
+                trace,
+                ..
+            } => {
+                state.runs.insert(
+                    *sub_id,
+                    RunAttribution {
+                        agent: actor_name.clone(),
+                        session: trace.as_ref().and_then(|t| t.session_id.clone()),
+                    },
+                );
+            }
+            Event::TaskComplete { sub_id, .. } | Event::TaskError { sub_id, .. } => {
+                state.runs.remove(sub_id);
+            }
+            Event::LLMCallCompleted {
+                sub_id,
+                actor_id,
+                model,
+                usage,
+                ..
+            } => {
+                let run = state.runs.get(sub_id);
+                let key = UsageKey {
+                    agent: run.map_or_else(|| actor_id.to_string(), |run| run.agent.clone()),
+                    model: model.clone(),
+                    session: run.and_then(|run| run.session.clone()),
+                };
+                state.counts.entry(key).or_default().add(usage.as_ref());
+            }
+            _ => {}
+        }
+    }
+
+    /// Consume an event stream in the background, recording every event.
+    pub fn spawn(&self, mut event_stream: BoxEventStream<Event>) -> JoinHandle<()> {
+        let aggregator = self.clone();
+        tokio::spawn(async move {
+            while let Some(event) = event_stream.next().await {
+                aggregator.record(&event);
+            }
+        })
+    }
+
+    /// Snapshot of all groups, ordered by agent, model, then session.
+    pub fn summary(&self) -> UsageSummary {
+        let state = self.lock();
+        let mut records: Vec<UsageRecord> = state
+            .counts
+            .iter()
+            .map(|(key, counts)| UsageRecord {
+                key: key.clone(),
+                totals: state.totals(key, counts),
+            })
+            .collect();
+        records.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let mut totals = UsageTotals::default();
+        for record in &records {
+            totals.merge(&record.totals);
+        }
+        UsageSummary { records, totals }
+    }
+
+    pub fn totals(&self) -> UsageTotals {
+        self.summary().totals
+    }
+
+    pub fn by_agent(&self) -> BTreeMap<String, UsageTotals> {
+        self.group_by(|key| Some(key.agent.clone()))
+    }
+
+    pub fn by_model(&self) -> BTreeMap<String, UsageTotals> {
+        self.group_by(|key| Some(key.model.clone()))
+    }
+
+    /// Totals per session; calls without an assigned session are omitted.
+    pub fn by_session(&self) -> BTreeMap<String, UsageTotals> {
+        self.group_by(|key| key.session.clone())
+    }
+
+    /// Emit a summary every `interval`, starting one interval from now.
+    pub fn summaries(&self, interval: Duration) -> BoxEventStream<UsageSummary> {
+        let aggregator = self.clone();
+        Box::pin(futures_util::stream::unfold(
+            aggregator,
+            move |aggregator| async move {
+                tokio::time::sleep(interval).await;
+                let summary = aggregator.summary();
+                tracing::info!(
+                    target: "autoagents.telemetry.usage",
+                    calls = summary.totals.calls,
+                    total_tokens = summary.totals.total_tokens,
+                    cost_usd = summary.totals.cost_usd,
+                    "usage summary"
+                );
+                Some((summary, aggregator))
+            },
+        ))
+    }
+
+    /// Clear accumulated counts, keeping pricing and the attribution of running tasks.
+    pub fn reset(&self) {
+        self.lock().counts.clear();
+    }
+
+    fn group_by(
+        &self,
+        key_fn: impl Fn(&UsageKey) -> Option<String>,
+    ) -> BTreeMap<String, UsageTotals> {
+        let mut groups: BTreeMap<String, UsageTotals> = BTreeMap::new();
+        for record in self.summary().records {
+            if let Some(group) = key_fn(&record.key) {
+                groups.entry(group).or_default().merge(&record.totals);
+            }
+        }
+        groups
+    }
+
+    fn lock(&self) -> MutexGuard<'_, AggregatorState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autoagents_protocol::ActorID;
+
+    fn usage(prompt: u32, completion: u32) -> Option<Usage> {
+        Some(Usage {
+            prompt_tokens: prompt,
+            completion_tokens: completion,
+            total_tokens: prompt + completion,
+            completion_tokens_details: None,
+            prompt_tokens_details: None,
+        })
+    }
+
+    fn llm_call(
+        sub_id: SubmissionId,
+        actor_id: ActorID,
+        model: &str,
+        usage: Option<Usage>,
+    ) -> Event {
+        Event::LLMCallCompleted {
+            sub_id,
+            actor_id,
+            model: model.to_string(),
+            duration_ms: 10,
+            usage,
+        }
+    }
+
+    #[test]
+    fn aggregates_usage_per_agent_model_and_session() {
+        let aggregator =
+            UsageAggregator::new().with_pricing("gpt-4o", ModelPricing::per_million(2.5, 10.0));
+        let sub_id = SubmissionId::new_v4();
+        let actor_id = ActorID::new_v4();
+
+        aggregator.record(&Event::TaskStarted {
+            sub_id,
+            actor_id,
+            actor_name: "planner".to_string(),
+            task_description: "plan".to_string(),
+            trace: Some(autoagents_protocol::TraceAttributes {
+                session_id: Some("session-1".to_string()),
+                ..Default::default()
+            }),
+        });
+        aggregator.record(&llm_call(sub_id, actor_id, "gpt-4o", usage(1000, 100)));
+        aggregator.record(&llm_call(sub_id, actor_id, "gpt-4o", usage(1000, 100)));
+        aggregator.record(&llm_call(
+            SubmissionId::new_v4(),
+            actor_id,
+            "llama3",
+            usage(50, 5),
+        ));
+
+        let summary = aggregator.summary();
+        assert_eq!(summary.records.len(), 2);
+        assert_eq!(summary.totals.calls, 3);
+        assert_eq!(summary.totals.prompt_tokens, 2050);
+
+        let models = aggregator.by_model();
+        let gpt = &models["gpt-4o"];
+        assert_eq!(gpt.calls, 2);
+        assert!((gpt.cost_usd.unwrap() - 0.007).abs() < 1e-9);
+        assert_eq!(models["llama3"].cost_usd, None);
+
+        // The third call belongs to no started task, so only its actor id is known
+        let agents = aggregator.by_agent();
+        assert_eq!(agents["planner"].calls, 2);
+        assert_eq!(agents[&actor_id.to_string()].calls, 1);
+        let sessions = aggregator.by_session();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions["session-1"].total_tokens, 2200);
+    }
+
+    #[test]
+    fn finished_tasks_are_forgotten() {
+        let aggregator = UsageAggregator::new();
+        let actor_id = ActorID::new_v4();
+        let (done, failed) = (SubmissionId::new_v4(), SubmissionId::new_v4());
+        for sub_id in [done, failed] {
+            aggregator.record(&Event::TaskStarted {
+                sub_id,
+                actor_id,
+                actor_name: "worker".to_string(),
+                task_description: "work".to_string(),
+                trace: None,
+            });
+        }
+        assert_eq!(aggregator.lock().runs.len(), 2);
+
+        aggregator.record(&llm_call(done, actor_id, "m", usage(1, 1)));
+        aggregator.record(&Event::TaskComplete {
+            sub_id: done,
+            actor_id,
+            actor_name: "worker".to_string(),
+            result: "ok".to_string(),
+        });
+        aggregator.record(&Event::TaskError {
+            sub_id: failed,
+            actor_id,
+            error: "boom".to_string(),
+        });
+
+        assert!(aggregator.lock().runs.is_empty());
+        assert_eq!(aggregator.by_agent()["worker"].calls, 1);
+    }
+
+    #[test]
+    fn exports_csv_and_json() {
+        let aggregator = UsageAggregator::new();
+        let actor_id = ActorID::new_v4();
+        aggregator.record(&llm_call(
+            SubmissionId::new_v4(),
+            actor_id,
+            "model, with comma",
+            usage(3, 4),
+        ));
+
+        let summary = aggregator.summary();
+        let mut out = Vec::new();
+        summary.write_csv(&mut out).expect("csv written");
+        let csv = String::from_utf8(out).expect("utf8");
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("agent,model,session,calls,prompt_tokens,completion_tokens,total_tokens,cost_usd")
+        );
+        assert_eq!(
+            lines.next(),
+            Some(format!("{actor_id},\"model, with comma\",,1,3,4,7,").as_str())
+        );
+
+        let json = summary.to_json();
+        assert_eq!(json["totals"]["total_tokens"], 7);
+        assert_eq!(json["records"][0]["key"]["model"], "model, with comma");
+    }
+
+    #[tokio::test]
+    async fn summaries_stream_emits_snapshots() {
+        let aggregator = UsageAggregator::new();
+        aggregator.record(&llm_call(
+            SubmissionId::new_v4(),
+            ActorID::new_v4(),
+            "m",
+            None,
+        ));
+        let mut summaries = aggregator.summaries(Duration::from_millis(5));
+        let summary = summaries.next().await.expect("summary");
+        assert_eq!(summary.totals.calls, 1);
+        assert_eq!(summary.totals.total_tokens, 0);
+
+        aggregator.reset();
+        assert_eq!(aggregator.totals(), UsageTotals::default());
+    }
+}
@@ -1,5 +1,11 @@
 pub mod tools;
 
+#[cfg(all(not(target_arch = "wasm32"), feature = "readers"))]
+pub mod readers;
+
+#[cfg(feature = "rerank")]
+pub mod rerankers;
+
 pub(crate) mod utils;
 
 #[cfg(all(not(target_arch = "wasm32"), feature = "mcp"))]
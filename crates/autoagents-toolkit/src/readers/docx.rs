@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use autoagents::core::chunking::HEADING_PATH_KEY;
+use autoagents::core::document::Document;
+use autoagents::core::readers::simple_directory_reader::{FileReader, ReaderError};
+use quick_xml::events::{BytesStart, Event};
+use serde_json::Value;
+
+use super::ooxml::{
+    attribute, core_properties, open_archive, parse_error, read_entry, read_file_bytes,
+    resolve_ref, text_of,
+};
+
+/// A paragraph of a Word document and its outline level, if it is a heading
+struct Paragraph {
+    level: Option<usize>,
+    text: String,
+}
+
+/// Reads `.docx` files, one document per heading section
+///
+/// Headings are recognised from the paragraph outline level or the heading
+/// styles defined in the document (including localized style names that map
+/// to "heading N"). Each document records `source`, its [`HEADING_PATH_KEY`]
+/// and `section_index`, plus the title, author, subject, keywords and
+/// creation date from the document properties; with `split_sections(false)`
+/// the whole file becomes a single document.
+#[derive(Debug, Clone)]
+pub struct DocxReader {
+    split_sections: bool,
+}
+
+impl Default for DocxReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DocxReader {
+    pub fn new() -> Self {
+        Self {
+            split_sections: true,
+        }
+    }
+
+    /// Return one document per heading section (default) or one per file
+    pub fn split_sections(mut self, split_sections: bool) -> Self {
+        self.split_sections = split_sections;
+        self
+    }
+
+    /// Extract documents from an in-memory `.docx`; `source` is recorded as metadata
+    pub fn load_bytes(&self, bytes: &[u8], source: &str) -> Result<Vec<Document>, ReaderError> {
+        let mut archive = open_archive(bytes, source)?;
+        let body = read_entry(&mut archive, "word/document.xml", source)?
+            .ok_or_else(|| parse_error(source, "missing word/document.xml"))?;
+        let styles = match read_entry(&mut archive, "word/styles.xml", source)? {
+            Some(xml) => heading_styles(&xml),
+            None => HashMap::new(),
+        };
+        let paragraphs = paragraphs(&body, &styles).map_err(|err| parse_error(source, err))?;
+
+        let mut metadata = core_properties(&mut archive, source)?;
+        metadata.insert("source".into(), source.into());
+
+        if !self.split_sections {
+            let text = paragraphs
+                .into_iter()
+                .map(|paragraph| paragraph.text)
+                .collect::<Vec<_>>()
+                .join("\n");
+            if text.is_empty() {
+                return Ok(Vec::new());
+            }
+            return Ok(vec![Document::with_metadata(text, Value::Object(metadata))]);
+        }
+
+        Ok(sections(paragraphs)
+            .into_iter()
+            .enumerate()
+            .map(|(index, (path, text))| {
+                let mut metadata = metadata.clone();
+                metadata.insert(HEADING_PATH_KEY.into(), path.into());
+                metadata.insert("section_index".into(), index.into());
+                Document::with_metadata(text, Value::Object(metadata))
+            })
+            .collect())
+    }
+}
+
+impl FileReader for DocxReader {
+    fn extensions(&self) -> &[&str] {
+        &["docx"]
+    }
+
+    fn read_file(&self, path: &Path) -> Result<Vec<Document>, ReaderError> {
+        self.load_bytes(&read_file_bytes(path)?, &path.to_string_lossy())
+    }
+}
+
+/// Group paragraphs into `(heading path, text)` sections, each starting at a heading
+fn sections(paragraphs: Vec<Paragraph>) -> Vec<(Vec<String>, String)> {
+    let mut sections = Vec::new();
+    let mut path: Vec<(usize, String)> = Vec::new();
+    let mut lines: Vec<String> = Vec::new();
+
+    let flush = |path: &[(usize, String)], lines: &mut Vec<String>, out: &mut Vec<_>| {
+        if !lines.is_empty() {
+            let titles: Vec<String> = path.iter().map(|(_, title)| title.clone()).collect();
+            out.push((titles, std::mem::take(lines).join("\n")));
+        }
+    };
+
+    for paragraph in paragraphs {
+        if let Some(level) = paragraph.level {
+            flush(&path, &mut lines, &mut sections);
+            path.retain(|(existing, _)| *existing < level);
+            path.push((level, paragraph.text.clone()));
+        }
+        lines.push(paragraph.text);
+    }
+    flush(&path, &mut lines, &mut sections);
+    sections
+}
+
+/// Map paragraph style ids to heading levels using `word/styles.xml`
+fn heading_styles(xml: &str) -> HashMap<String, usize> {
+    let mut styles = HashMap::new();
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut current: Option<String> = None;
+    let mut level: Option<usize> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) if e.name().as_ref() == b"w:style" => {
+                current = attribute(e, "w:styleId");
+                level = None;
+            }
+            Ok(Event::Start(ref e) | Event::Empty(ref e)) if current.is_some() => {
+                match e.name().as_ref() {
+                    b"w:name" => {
+                        level =
+                            level.or_else(|| attribute(e, "w:val").and_then(|n| style_level(&n)));
+                    }
+                    b"w:outlineLvl" => level = outline_level(e).or(level),
+                    _ => {}
+                }
+            }
+            Ok(Event::End(ref e)) if e.name().as_ref() == b"w:style" => {
+                if let (Some(id), Some(level)) = (current.take(), level) {
+                    styles.insert(id, level);
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    styles
+}
+
+fn paragraphs(xml: &str, styles: &HashMap<String, usize>) -> Result<Vec<Paragraph>, String> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut paragraphs = Vec::new();
+    let mut text = String::new();
+    let mut style: Option<String> = None;
+    let mut outline: Option<usize> = None;
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) if e.name().as_ref() == b"w:t" => in_text = true,
+            Ok(Event::End(ref e)) if e.name().as_ref() == b"w:t" => in_text = false,
+            Ok(Event::Text(ref e)) if in_text => text.push_str(&text_of(e)),
+            Ok(Event::GeneralRef(ref e)) if in_text => text.push_str(&resolve_ref(e)),
+            Ok(Event::Start(ref e) | Event::Empty(ref e)) => match e.name().as_ref() {
+                b"w:pStyle" => style = attribute(e, "w:val"),
+                b"w:outlineLvl" => outline = outline_level(e),
+                b"w:tab" => text.push('\t'),
+                b"w:br" | b"w:cr" => text.push('\n'),
+                _ => {}
+            },
+            Ok(Event::End(ref e)) if e.name().as_ref() == b"w:p" => {
+                let level = outline.or_else(|| {
+                    style
+                        .as_deref()
+                        .and_then(|id| styles.get(id).copied().or_else(|| style_level(id)))
+                });
+                let paragraph = std::mem::take(&mut text).trim().to_string();
+                if !paragraph.is_empty() {
+                    paragraphs.push(Paragraph {
+                        level,
+                        text: paragraph,
+                    });
+                }
+                style = None;
+                outline = None;
+            }
+            Ok(Event::Eof) => break,
+            Err(err) => return Err(format!("XML parse error: {err}")),
+            _ => {}
+        }
+    }
+    Ok(paragraphs)
+}
+
+/// Heading level of a style named `Heading N`, `heading N` or `Title`
+fn style_level(name: &str) -> Option<usize> {
+    let name = name.to_ascii_lowercase().replace(' ', "");
+    if name == "title" {
+        return Some(1);
+    }
+    name.strip_prefix("heading")?
+        .parse::<usize>()
+        .ok()
+        .filter(|level| (1..=9).contains(level))
+}
+
+/// `w:outlineLvl` is zero-based and uses 9 for body text
+fn outline_level(element: &BytesStart<'_>) -> Option<usize> {
+    attribute(element, "w:val")?
+        .parse::<usize>()
+        .ok()
+        .filter(|level| *level < 9)
+        .map(|level| level + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::{Cursor, Write};
+
+    const STYLES: &str = r#"<w:styles xmlns:w="w">
+        <w:style w:type="paragraph" w:styleId="berschrift1"><w:name w:val="heading 1"/></w:style>
+        <w:style w:type="paragraph" w:styleId="Custom"><w:name w:val="Custom"/>
+            <w:pPr><w:outlineLvl w:val="1"/></w:pPr></w:style>
+    </w:styles>"#;
+
+    fn paragraph(style: Option<&str>, text: &str) -> String {
+        let style = style
+            .map(|s| format!(r#"<w:pPr><w:pStyle w:val="{s}"/></w:pPr>"#))
+            .unwrap_or_default();
+        format!("<w:p>{style}<w:r><w:t>{text}</w:t></w:r></w:p>")
+    }
+
+    fn build_docx(paragraphs: &[String]) -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("word/document.xml", options).unwrap();
+        write!(
+            zip,
+            r#"<w:document xmlns:w="w"><w:body>{}</w:body></w:document>"#,
+            paragraphs.concat()
+        )
+        .unwrap();
+        zip.start_file("word/styles.xml", options).unwrap();
+        zip.write_all(STYLES.as_bytes()).unwrap();
+        zip.start_file("docProps/core.xml", options).unwrap();
+        zip.write_all(
+            br#"<cp:coreProperties xmlns:cp="cp" xmlns:dc="dc"><dc:title>Handbook</dc:title><dc:creator>HR &amp; Ops</dc:creator></cp:coreProperties>"#,
+        )
+        .unwrap();
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_sections_follow_heading_styles() {
+        let docx = build_docx(&[
+            paragraph(None, "Preface"),
+            paragraph(Some("berschrift1"), "Leave"),
+            paragraph(None, "Ask your manager."),
+            paragraph(Some("Custom"), "Sick leave"),
+            paragraph(None, "Call in &amp; rest."),
+            paragraph(Some("Heading1"), "Travel"),
+        ]);
+        let docs = DocxReader::new()
+            .load_bytes(&docx, "handbook.docx")
+            .unwrap();
+
+        let sections: Vec<(&str, Value)> = docs
+            .iter()
+            .map(|doc| {
+                (
+                    doc.page_content.as_str(),
+                    doc.metadata[HEADING_PATH_KEY].clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                ("Preface", json!([])),
+                ("Leave\nAsk your manager.", json!(["Leave"])),
+                (
+                    "Sick leave\nCall in & rest.",
+                    json!(["Leave", "Sick leave"])
+                ),
+                ("Travel", json!(["Travel"])),
+            ]
+        );
+        assert_eq!(docs[0].metadata["title"], "Handbook");
+        assert_eq!(docs[0].metadata["author"], "HR & Ops");
+        assert_eq!(docs[3].metadata["section_index"], 3);
+
+        let whole = DocxReader::new()
+            .split_sections(false)
+            .load_bytes(&docx, "handbook.docx")
+            .unwrap();
+        assert_eq!(whole.len(), 1);
+        assert!(whole[0].page_content.starts_with("Preface\nLeave\n"));
+    }
+
+    #[test]
+    fn test_invalid_docx() {
+        let err = DocxReader::new()
+            .load_bytes(b"not a zip", "bad.docx")
+            .unwrap_err();
+        assert!(matches!(err, ReaderError::Parse { .. }));
+    }
+}
@@ -0,0 +1,377 @@
+use std::fs;
+use std::path::Path;
+
+use autoagents::core::chunking::HEADING_PATH_KEY;
+use autoagents::core::document::Document;
+use autoagents::core::readers::simple_directory_reader::{FileReader, ReaderError};
+use scraper::{ElementRef, Html, Node, Selector};
+use serde_json::{Map, Value};
+
+/// Elements whose content is never part of the readable text.
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "iframe", "nav", "header", "footer", "aside",
+    "form", "button",
+];
+
+/// Elements that start and end a line in the extracted text.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "li",
+    "main",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "td",
+    "th",
+    "tr",
+    "ul",
+];
+
+/// Text under one heading of an HTML page, starting with the heading itself.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct HtmlSection {
+    /// Titles of the enclosing headings, outermost first.
+    pub heading_path: Vec<String>,
+    pub text: String,
+}
+
+/// Readable content and crawl hints extracted from an HTML page.
+#[derive(Debug, Default)]
+pub(crate) struct HtmlPage {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub text: String,
+    pub sections: Vec<HtmlSection>,
+    /// `href` values of links not marked `rel="nofollow"`, unresolved.
+    pub links: Vec<String>,
+    /// `<meta name="robots" content="noindex">` is present.
+    pub noindex: bool,
+    /// `<meta name="robots" content="nofollow">` is present.
+    pub nofollow: bool,
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("static selector is valid")
+}
+
+/// Extract the main content of `html`.
+///
+/// The text comes from the first `<main>`, `<article>` or `role="main"`
+/// element, falling back to `<body>`; navigation, headers, footers, scripts
+/// and forms are dropped.
+pub(crate) fn extract_page(html: &str) -> HtmlPage {
+    let document = Html::parse_document(html);
+
+    let title = document
+        .select(&selector("title"))
+        .next()
+        .or_else(|| document.select(&selector("h1")).next())
+        .map(|element| collapse_whitespace(&element.text().collect::<String>()))
+        .filter(|title| !title.is_empty());
+
+    let mut page = HtmlPage {
+        title,
+        ..HtmlPage::default()
+    };
+
+    for meta in document.select(&selector("meta[name][content]")) {
+        let name = meta.value().attr("name").unwrap_or_default();
+        let content = meta.value().attr("content").unwrap_or_default();
+        if name.eq_ignore_ascii_case("description") {
+            page.description = Some(collapse_whitespace(content)).filter(|d| !d.is_empty());
+        } else if name.eq_ignore_ascii_case("robots") {
+            let content = content.to_ascii_lowercase();
+            page.noindex |= content.contains("noindex") || content.contains("none");
+            page.nofollow |= content.contains("nofollow") || content.contains("none");
+        }
+    }
+
+    page.links = document
+        .select(&selector("a[href]"))
+        .filter(|link| {
+            !link
+                .value()
+                .attr("rel")
+                .is_some_and(|rel| rel.split_whitespace().any(|r| r == "nofollow"))
+        })
+        .filter_map(|link| link.value().attr("href"))
+        .map(|href| href.trim().to_string())
+        .collect();
+
+    let root = ["main", "article", "[role=main]", "body"]
+        .iter()
+        .find_map(|css| document.select(&selector(css)).next())
+        .unwrap_or_else(|| document.root_element());
+
+    let mut walker = TextWalker::default();
+    walker.walk(root);
+    walker.flush();
+    page.sections = walker.sections;
+    page.text = page
+        .sections
+        .iter()
+        .map(|section| section.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    page
+}
+
+/// Collects readable text, starting a new section at every heading.
+#[derive(Default)]
+struct TextWalker {
+    out: String,
+    path: Vec<(usize, String)>,
+    sections: Vec<HtmlSection>,
+}
+
+impl TextWalker {
+    fn walk(&mut self, element: ElementRef<'_>) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.push_text(text),
+                Node::Element(node) => {
+                    let name = node.name();
+                    if SKIPPED_ELEMENTS.contains(&name) || node.attr("hidden").is_some() {
+                        continue;
+                    }
+                    let Some(child) = ElementRef::wrap(child) else {
+                        continue;
+                    };
+                    if let Some(level) = heading_level(name) {
+                        self.heading(level, child);
+                        continue;
+                    }
+                    let block = BLOCK_ELEMENTS.contains(&name);
+                    if block {
+                        self.out.push('\n');
+                    }
+                    self.walk(child);
+                    if block {
+                        self.out.push('\n');
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if text.starts_with(char::is_whitespace) && !self.out.ends_with(char::is_whitespace) {
+            self.out.push(' ');
+        }
+        self.out.push_str(&collapse_whitespace(text));
+        if text.ends_with(char::is_whitespace) && !text.trim().is_empty() {
+            self.out.push(' ');
+        }
+    }
+
+    fn heading(&mut self, level: usize, element: ElementRef<'_>) {
+        let title = collapse_whitespace(&element.text().collect::<String>());
+        if title.is_empty() {
+            return;
+        }
+        self.flush();
+        self.path.retain(|(existing, _)| *existing < level);
+        self.path.push((level, title.clone()));
+        self.out.push_str(&title);
+        self.out.push('\n');
+    }
+
+    fn flush(&mut self) {
+        let text = std::mem::take(&mut self.out)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if !text.is_empty() {
+            self.sections.push(HtmlSection {
+                heading_path: self.path.iter().map(|(_, title)| title.clone()).collect(),
+                text,
+            });
+        }
+    }
+}
+
+fn heading_level(name: &str) -> Option<usize> {
+    match name {
+        "h1" => Some(1),
+        "h2" => Some(2),
+        "h3" => Some(3),
+        "h4" => Some(4),
+        "h5" => Some(5),
+        "h6" => Some(6),
+        _ => None,
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads `.html` files into their main content, one document per heading section
+///
+/// Each document records `source`, `title`, `description`, the
+/// [`HEADING_PATH_KEY`] of its section and its `section_index`; with
+/// `split_sections(false)` the whole page becomes a single document.
+#[derive(Debug, Clone)]
+pub struct HtmlReader {
+    split_sections: bool,
+}
+
+impl Default for HtmlReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HtmlReader {
+    pub fn new() -> Self {
+        Self {
+            split_sections: true,
+        }
+    }
+
+    /// Return one document per heading section (default) or one per file
+    pub fn split_sections(mut self, split_sections: bool) -> Self {
+        self.split_sections = split_sections;
+        self
+    }
+
+    /// Extract documents from `html`; `source` is recorded as metadata
+    pub fn load_str(&self, html: &str, source: &str) -> Vec<Document> {
+        let page = extract_page(html);
+        let mut metadata = Map::new();
+        metadata.insert("source".into(), source.into());
+        if let Some(title) = page.title {
+            metadata.insert("title".into(), title.into());
+        }
+        if let Some(description) = page.description {
+            metadata.insert("description".into(), description.into());
+        }
+
+        if !self.split_sections {
+            if page.text.is_empty() {
+                return Vec::new();
+            }
+            return vec![Document::with_metadata(page.text, Value::Object(metadata))];
+        }
+
+        page.sections
+            .into_iter()
+            .enumerate()
+            .map(|(index, section)| {
+                let mut metadata = metadata.clone();
+                metadata.insert(HEADING_PATH_KEY.into(), section.heading_path.into());
+                metadata.insert("section_index".into(), index.into());
+                Document::with_metadata(section.text, Value::Object(metadata))
+            })
+            .collect()
+    }
+}
+
+impl FileReader for HtmlReader {
+    fn extensions(&self) -> &[&str] {
+        &["html", "htm", "xhtml"]
+    }
+
+    fn read_file(&self, path: &Path) -> Result<Vec<Document>, ReaderError> {
+        let bytes = fs::read(path).map_err(|source| ReaderError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Ok(self.load_str(&String::from_utf8_lossy(&bytes), &path.to_string_lossy()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extracts_main_content_and_links() {
+        let html = r#"<html><head><title> Docs | Home </title>
+            <meta name="description" content="All about it">
+            <script>var x = 1;</script></head>
+            <body><nav><a href="/menu">Menu</a></nav>
+            <main><h1>Welcome</h1><p>First <b>bold</b> paragraph.</p>
+            <ul><li>One</li><li>Two</li></ul>
+            <a href="/next">Next page</a> <a rel="nofollow" href="/login">Login</a></main>
+            <footer>Copyright</footer></body></html>"#;
+        let page = extract_page(html);
+
+        assert_eq!(page.title.as_deref(), Some("Docs | Home"));
+        assert_eq!(page.description.as_deref(), Some("All about it"));
+        assert_eq!(
+            page.text,
+            "Welcome\nFirst bold paragraph.\nOne\nTwo\nNext page Login"
+        );
+        assert_eq!(page.links, vec!["/menu", "/next"]);
+        assert!(!page.noindex && !page.nofollow);
+    }
+
+    #[test]
+    fn test_robots_meta_and_body_fallback() {
+        let html = r#"<html><head><meta name="ROBOTS" content="noindex, nofollow"></head>
+            <body><div>Plain body</div><div hidden>secret</div></body></html>"#;
+        let page = extract_page(html);
+
+        assert!(page.noindex);
+        assert!(page.nofollow);
+        assert_eq!(page.text, "Plain body");
+        assert_eq!(page.title, None);
+    }
+
+    #[test]
+    fn test_html_reader_sections() {
+        let html = "<title>Guide</title><body><p>Intro</p><h1>Setup</h1><p>Install it.</p>\
+            <h2>Linux</h2><p>Use apt.</p><h1>Usage</h1><p>Run it.</p></body>";
+        let docs = HtmlReader::new().load_str(html, "guide.html");
+
+        let sections: Vec<(&str, Value)> = docs
+            .iter()
+            .map(|doc| {
+                (
+                    doc.page_content.as_str(),
+                    doc.metadata[HEADING_PATH_KEY].clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                ("Intro", serde_json::json!([])),
+                ("Setup\nInstall it.", serde_json::json!(["Setup"])),
+                ("Linux\nUse apt.", serde_json::json!(["Setup", "Linux"])),
+                ("Usage\nRun it.", serde_json::json!(["Usage"])),
+            ]
+        );
+        assert_eq!(docs[2].metadata["section_index"], 2);
+        assert_eq!(docs[0].metadata["title"], "Guide");
+        assert_eq!(docs[0].metadata["source"], "guide.html");
+
+        let whole = HtmlReader::new()
+            .split_sections(false)
+            .load_str(html, "guide.html");
+        assert_eq!(whole.len(), 1);
+        assert!(whole[0].metadata.get(HEADING_PATH_KEY).is_none());
+    }
+}
@@ -0,0 +1,22 @@
+//! Readers that turn external sources into [`Document`](autoagents::core::document::Document)s
+//! for ingestion into vector stores.
+//!
+//! [`DocxReader`], [`PptxReader`] and [`HtmlReader`] implement
+//! [`FileReader`](autoagents::core::readers::simple_directory_reader::FileReader), so they can
+//! be registered on a `SimpleDirectoryReader` to ingest folders of Office documents and web
+//! pages alongside plain text. They split files at headings or slides and record the section in
+//! the [`HEADING_PATH_KEY`](autoagents::core::chunking::HEADING_PATH_KEY) metadata field.
+
+mod docx;
+mod html;
+mod ooxml;
+mod pptx;
+mod robots;
+mod web;
+
+pub use docx::DocxReader;
+pub use html::HtmlReader;
+pub use pptx::PptxReader;
+pub use web::{
+    DEFAULT_MAX_CRAWL_DELAY, DEFAULT_MAX_PAGES, DEFAULT_USER_AGENT, WebReader, WebReaderError,
+};
@@ -0,0 +1,139 @@
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use autoagents::core::readers::simple_directory_reader::ReaderError;
+use quick_xml::events::{BytesRef, BytesStart, BytesText};
+use serde_json::{Map, Value};
+use zip::ZipArchive;
+
+pub(crate) type OoxmlArchive<'a> = ZipArchive<Cursor<&'a [u8]>>;
+
+/// Fields of `docProps/core.xml` copied into document metadata.
+const CORE_PROPERTIES: [(&[u8], &str); 5] = [
+    (b"dc:title", "title"),
+    (b"dc:creator", "author"),
+    (b"dc:subject", "subject"),
+    (b"cp:keywords", "keywords"),
+    (b"dcterms:created", "created"),
+];
+
+pub(crate) fn read_file_bytes(path: &Path) -> Result<Vec<u8>, ReaderError> {
+    fs::read(path).map_err(|source| ReaderError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+pub(crate) fn parse_error(source: &str, message: impl Into<String>) -> ReaderError {
+    ReaderError::Parse {
+        path: source.into(),
+        message: message.into(),
+    }
+}
+
+pub(crate) fn open_archive<'a>(
+    bytes: &'a [u8],
+    source: &str,
+) -> Result<OoxmlArchive<'a>, ReaderError> {
+    ZipArchive::new(Cursor::new(bytes))
+        .map_err(|err| parse_error(source, format!("not a valid Office/ZIP file: {err}")))
+}
+
+/// Contents of the archive entry `name`, or `None` if it does not exist
+pub(crate) fn read_entry(
+    archive: &mut OoxmlArchive<'_>,
+    name: &str,
+    source: &str,
+) -> Result<Option<String>, ReaderError> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(err) => return Err(parse_error(source, format!("{name}: {err}"))),
+    };
+    let mut content = String::new();
+    entry
+        .read_to_string(&mut content)
+        .map_err(|err| parse_error(source, format!("{name}: {err}")))?;
+    Ok(Some(content))
+}
+
+/// Title, author, subject, keywords and creation date from `docProps/core.xml`
+pub(crate) fn core_properties(
+    archive: &mut OoxmlArchive<'_>,
+    source: &str,
+) -> Result<Map<String, Value>, ReaderError> {
+    let mut metadata = Map::new();
+    let Some(xml) = read_entry(archive, "docProps/core.xml", source)? else {
+        return Ok(metadata);
+    };
+
+    let mut reader = quick_xml::Reader::from_str(&xml);
+    let mut current: Option<&str> = None;
+    let mut value = String::new();
+    loop {
+        match reader.read_event() {
+            Ok(quick_xml::events::Event::Start(ref e)) => {
+                current = CORE_PROPERTIES
+                    .iter()
+                    .find(|(tag, _)| e.name().as_ref() == *tag)
+                    .map(|(_, key)| *key);
+                value.clear();
+            }
+            Ok(quick_xml::events::Event::Text(ref e)) if current.is_some() => {
+                value.push_str(&text_of(e));
+            }
+            Ok(quick_xml::events::Event::GeneralRef(ref e)) if current.is_some() => {
+                value.push_str(&resolve_ref(e));
+            }
+            Ok(quick_xml::events::Event::End(_)) => {
+                if let Some(key) = current.take()
+                    && !value.trim().is_empty()
+                {
+                    metadata.insert(key.into(), value.trim().into());
+                }
+            }
+            Ok(quick_xml::events::Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+    Ok(metadata)
+}
+
+pub(crate) fn text_of(text: &BytesText<'_>) -> String {
+    text.decode()
+        .ok()
+        .and_then(|decoded| {
+            quick_xml::escape::unescape(&decoded)
+                .ok()
+                .map(|text| text.into_owned())
+        })
+        .unwrap_or_default()
+}
+
+/// Resolve an entity or character reference such as `&amp;` or `&#x41;`
+pub(crate) fn resolve_ref(reference: &BytesRef<'_>) -> String {
+    reference
+        .decode()
+        .ok()
+        .and_then(|name| {
+            quick_xml::escape::unescape(&format!("&{name};"))
+                .ok()
+                .map(|text| text.into_owned())
+        })
+        .unwrap_or_default()
+}
+
+/// Value of the attribute `name` on `element`
+pub(crate) fn attribute(element: &BytesStart<'_>, name: &str) -> Option<String> {
+    element
+        .try_get_attribute(name)
+        .ok()
+        .flatten()
+        .and_then(|attr| {
+            let raw = String::from_utf8_lossy(&attr.value).into_owned();
+            quick_xml::escape::unescape(&raw)
+                .ok()
+                .map(|value| value.into_owned())
+        })
+}
@@ -0,0 +1,245 @@
+use std::path::Path;
+
+use autoagents::core::chunking::HEADING_PATH_KEY;
+use autoagents::core::document::Document;
+use autoagents::core::readers::simple_directory_reader::{FileReader, ReaderError};
+use quick_xml::events::Event;
+use serde_json::Value;
+
+use super::ooxml::{
+    attribute, core_properties, open_archive, parse_error, read_entry, read_file_bytes,
+    resolve_ref, text_of,
+};
+
+/// Text of one slide and the title placeholder's text, if any
+struct Slide {
+    title: Option<String>,
+    text: String,
+}
+
+/// Reads `.pptx` files, one document per slide
+///
+/// Each document records `source`, `slide` (1-based), `slide_count`, the
+/// slide title as `slide_title` and [`HEADING_PATH_KEY`], plus the title,
+/// author, subject, keywords and creation date from the presentation
+/// properties. Slides without text are skipped; with `split_slides(false)`
+/// the whole presentation becomes a single document.
+#[derive(Debug, Clone)]
+pub struct PptxReader {
+    split_slides: bool,
+}
+
+impl Default for PptxReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PptxReader {
+    pub fn new() -> Self {
+        Self { split_slides: true }
+    }
+
+    /// Return one document per slide (default) or one per file
+    pub fn split_slides(mut self, split_slides: bool) -> Self {
+        self.split_slides = split_slides;
+        self
+    }
+
+    /// Extract documents from an in-memory `.pptx`; `source` is recorded as metadata
+    pub fn load_bytes(&self, bytes: &[u8], source: &str) -> Result<Vec<Document>, ReaderError> {
+        let mut archive = open_archive(bytes, source)?;
+
+        // Zip order is arbitrary, and slide10 must come after slide9
+        let mut numbers: Vec<u32> = archive
+            .file_names()
+            .filter_map(|name| {
+                name.strip_prefix("ppt/slides/slide")?
+                    .strip_suffix(".xml")?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        numbers.sort_unstable();
+
+        let mut slides = Vec::with_capacity(numbers.len());
+        for number in &numbers {
+            let name = format!("ppt/slides/slide{number}.xml");
+            let xml = read_entry(&mut archive, &name, source)?.unwrap_or_default();
+            let slide =
+                parse_slide(&xml).map_err(|err| parse_error(source, format!("{name}: {err}")))?;
+            slides.push((*number, slide));
+        }
+
+        let mut metadata = core_properties(&mut archive, source)?;
+        metadata.insert("source".into(), source.into());
+        metadata.insert("slide_count".into(), slides.len().into());
+
+        if !self.split_slides {
+            let text = slides
+                .into_iter()
+                .map(|(_, slide)| slide.text)
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            if text.is_empty() {
+                return Ok(Vec::new());
+            }
+            return Ok(vec![Document::with_metadata(text, Value::Object(metadata))]);
+        }
+
+        Ok(slides
+            .into_iter()
+            .filter(|(_, slide)| !slide.text.is_empty())
+            .map(|(number, slide)| {
+                let mut metadata = metadata.clone();
+                metadata.insert("slide".into(), number.into());
+                let path: Vec<String> = slide.title.iter().cloned().collect();
+                if let Some(title) = slide.title {
+                    metadata.insert("slide_title".into(), title.into());
+                }
+                metadata.insert(HEADING_PATH_KEY.into(), path.into());
+                Document::with_metadata(slide.text, Value::Object(metadata))
+            })
+            .collect())
+    }
+}
+
+impl FileReader for PptxReader {
+    fn extensions(&self) -> &[&str] {
+        &["pptx"]
+    }
+
+    fn read_file(&self, path: &Path) -> Result<Vec<Document>, ReaderError> {
+        self.load_bytes(&read_file_bytes(path)?, &path.to_string_lossy())
+    }
+}
+
+fn parse_slide(xml: &str) -> Result<Slide, String> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut title = None;
+    let mut lines: Vec<String> = Vec::new();
+    let mut shape_lines: Option<Vec<String>> = None;
+    let mut shape_is_title = false;
+    let mut paragraph = String::new();
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => match e.name().as_ref() {
+                b"p:sp" => {
+                    shape_lines = Some(Vec::new());
+                    shape_is_title = false;
+                }
+                b"a:t" => in_text = true,
+                b"p:ph" => shape_is_title |= is_title_placeholder(e),
+                _ => {}
+            },
+            Ok(Event::Empty(ref e)) => match e.name().as_ref() {
+                b"p:ph" => shape_is_title |= is_title_placeholder(e),
+                b"a:br" => paragraph.push('\n'),
+                _ => {}
+            },
+            Ok(Event::Text(ref e)) if in_text => paragraph.push_str(&text_of(e)),
+            Ok(Event::GeneralRef(ref e)) if in_text => paragraph.push_str(&resolve_ref(e)),
+            Ok(Event::End(ref e)) => match e.name().as_ref() {
+                b"a:t" => in_text = false,
+                b"a:p" => {
+                    let text = std::mem::take(&mut paragraph).trim().to_string();
+                    if !text.is_empty() {
+                        shape_lines.as_mut().unwrap_or(&mut lines).push(text);
+                    }
+                }
+                b"p:sp" => {
+                    let shape = shape_lines.take().unwrap_or_default();
+                    if shape_is_title && title.is_none() && !shape.is_empty() {
+                        title = Some(shape.join(" "));
+                    }
+                    lines.extend(shape);
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(err) => return Err(format!("XML parse error: {err}")),
+            _ => {}
+        }
+    }
+
+    Ok(Slide {
+        title,
+        text: lines.join("\n"),
+    })
+}
+
+fn is_title_placeholder(element: &quick_xml::events::BytesStart<'_>) -> bool {
+    matches!(
+        attribute(element, "type").as_deref(),
+        Some("title" | "ctrTitle")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::{Cursor, Write};
+
+    fn slide(title: &str, body: &[&str]) -> String {
+        let body: String = body
+            .iter()
+            .map(|line| format!("<a:p><a:r><a:t>{line}</a:t></a:r></a:p>"))
+            .collect();
+        format!(
+            r#"<p:sld xmlns:a="a" xmlns:p="p"><p:cSld><p:spTree>
+            <p:sp><p:nvSpPr><p:nvPr><p:ph type="title"/></p:nvPr></p:nvSpPr>
+                <p:txBody><a:p><a:r><a:t>{title}</a:t></a:r></a:p></p:txBody></p:sp>
+            <p:sp><p:txBody>{body}</p:txBody></p:sp>
+            </p:spTree></p:cSld></p:sld>"#
+        )
+    }
+
+    fn build_pptx(slides: &[String]) -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        // Written in reverse to check that slides are ordered by number
+        for (index, slide) in slides.iter().enumerate().rev() {
+            zip.start_file(format!("ppt/slides/slide{}.xml", index + 1), options)
+                .unwrap();
+            zip.write_all(slide.as_bytes()).unwrap();
+        }
+        zip.start_file("docProps/core.xml", options).unwrap();
+        zip.write_all(br#"<cp:coreProperties xmlns:cp="cp" xmlns:dc="dc"><dc:title>Q3 Review</dc:title></cp:coreProperties>"#)
+            .unwrap();
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_one_document_per_slide_with_titles() {
+        let mut slides: Vec<String> = (1..=10)
+            .map(|n| slide(&format!("Slide {n}"), &["Point"]))
+            .collect();
+        slides[1] = slide("Revenue", &["Up 10%", "Costs &amp; risks"]);
+        let pptx = build_pptx(&slides);
+
+        let docs = PptxReader::new().load_bytes(&pptx, "q3.pptx").unwrap();
+        assert_eq!(docs.len(), 10);
+        assert_eq!(docs[1].page_content, "Revenue\nUp 10%\nCosts & risks");
+        assert_eq!(docs[1].metadata["slide"], 2);
+        assert_eq!(docs[1].metadata["slide_title"], "Revenue");
+        assert_eq!(docs[1].metadata[HEADING_PATH_KEY], json!(["Revenue"]));
+        assert_eq!(docs[1].metadata["slide_count"], 10);
+        assert_eq!(docs[1].metadata["title"], "Q3 Review");
+        assert_eq!(docs[9].metadata["slide_title"], "Slide 10");
+
+        let whole = PptxReader::new()
+            .split_slides(false)
+            .load_bytes(&pptx, "q3.pptx")
+            .unwrap();
+        assert_eq!(whole.len(), 1);
+        assert!(
+            whole[0]
+                .page_content
+                .starts_with("Slide 1\nPoint\n\nRevenue")
+        );
+    }
+}
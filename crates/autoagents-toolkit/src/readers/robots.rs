@@ -0,0 +1,205 @@
+use std::time::Duration;
+
+/// The rules of a `robots.txt` file that apply to one user agent.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RobotsRules {
+    /// `(allow, pattern)` pairs from the selected group.
+    rules: Vec<(bool, String)>,
+    crawl_delay: Option<Duration>,
+}
+
+impl RobotsRules {
+    pub fn allow_all() -> Self {
+        Self::default()
+    }
+
+    pub fn disallow_all() -> Self {
+        Self {
+            rules: vec![(false, "/".to_string())],
+            crawl_delay: None,
+        }
+    }
+
+    /// Parse `content` and keep the group that best matches `user_agent`.
+    ///
+    /// The group whose `User-agent` value is the longest match of the agent's
+    /// product token wins, falling back to the `*` group.
+    pub fn parse(content: &str, user_agent: &str) -> Self {
+        let product = user_agent
+            .split(['/', ' '])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        let mut best: Option<(usize, RobotsRules)> = None;
+        let mut agents: Vec<String> = Vec::new();
+        let mut group = RobotsRules::default();
+        let mut in_rules = false;
+
+        let mut finish_group = |agents: &mut Vec<String>, group: &mut RobotsRules| {
+            let specificity = agents
+                .iter()
+                .filter_map(|agent| {
+                    if agent == "*" {
+                        Some(0)
+                    } else if !product.is_empty() && product.contains(agent.as_str()) {
+                        Some(agent.len())
+                    } else {
+                        None
+                    }
+                })
+                .max();
+            let group = std::mem::take(group);
+            if let Some(specificity) = specificity {
+                match &mut best {
+                    Some((current, rules)) if *current == specificity => {
+                        rules.rules.extend(group.rules);
+                        rules.crawl_delay = rules.crawl_delay.or(group.crawl_delay);
+                    }
+                    Some((current, _)) if *current > specificity => {}
+                    _ => best = Some((specificity, group)),
+                }
+            }
+            agents.clear();
+        };
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+
+            match key.trim().to_ascii_lowercase().as_str() {
+                "user-agent" => {
+                    if in_rules {
+                        finish_group(&mut agents, &mut group);
+                        in_rules = false;
+                    }
+                    agents.push(value.to_ascii_lowercase());
+                }
+                "allow" | "disallow" if !agents.is_empty() => {
+                    in_rules = true;
+                    if !value.is_empty() {
+                        group
+                            .rules
+                            .push((key.trim().eq_ignore_ascii_case("allow"), value.to_string()));
+                    }
+                }
+                "crawl-delay" if !agents.is_empty() => {
+                    in_rules = true;
+                    group.crawl_delay = value
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok());
+                }
+                _ => {}
+            }
+        }
+        finish_group(&mut agents, &mut group);
+
+        best.map(|(_, rules)| rules).unwrap_or_default()
+    }
+
+    pub fn crawl_delay(&self) -> Option<Duration> {
+        self.crawl_delay
+    }
+
+    /// Whether `path` (including any query string) may be fetched.
+    ///
+    /// The longest matching pattern decides; `Allow` wins ties.
+    pub fn is_allowed(&self, path: &str) -> bool {
+        let mut decision: Option<(usize, bool)> = None;
+        for (allow, pattern) in &self.rules {
+            if !pattern_matches(pattern, path) {
+                continue;
+            }
+            let length = pattern.len();
+            match decision {
+                Some((best, best_allow)) if best > length || (best == length && best_allow) => {}
+                _ => decision = Some((length, *allow)),
+            }
+        }
+        decision.is_none_or(|(_, allow)| allow)
+    }
+}
+
+/// Match a robots.txt path pattern supporting `*` wildcards and a trailing `$`.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let Some(first) = parts.first() else {
+        return true;
+    };
+    if !path.starts_with(first) {
+        return false;
+    }
+
+    let mut position = first.len();
+    for (index, part) in parts.iter().enumerate().skip(1) {
+        if anchored && index == parts.len() - 1 {
+            return path[position..].ends_with(part);
+        }
+        match path[position..].find(part) {
+            Some(offset) => position += offset + part.len(),
+            None => return false,
+        }
+    }
+    !anchored || position == path.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROBOTS: &str = "\
+User-agent: *
+Disallow: /private/
+Allow: /private/public-*.html$
+Crawl-delay: 2
+
+User-agent: otherbot
+User-agent: autoagents
+Disallow: /
+Allow: /docs # keep docs crawlable
+";
+
+    #[test]
+    fn test_selects_most_specific_group() {
+        let rules = RobotsRules::parse(ROBOTS, "autoagents/0.4");
+        assert!(rules.is_allowed("/docs/intro"));
+        assert!(!rules.is_allowed("/blog"));
+        assert_eq!(rules.crawl_delay(), None);
+
+        let rules = RobotsRules::parse(ROBOTS, "SomeCrawler/1.0");
+        assert!(rules.is_allowed("/blog"));
+        assert!(!rules.is_allowed("/private/secret.html"));
+        assert!(rules.is_allowed("/private/public-page.html"));
+        assert!(!rules.is_allowed("/private/public-page.html?x=1"));
+        assert_eq!(rules.crawl_delay(), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_pattern_matching() {
+        assert!(pattern_matches("/a", "/abc"));
+        assert!(pattern_matches("/*.pdf$", "/files/report.pdf"));
+        assert!(!pattern_matches("/*.pdf$", "/files/report.pdf.html"));
+        assert!(pattern_matches("/a*c", "/abbbc/d"));
+        assert!(!pattern_matches("/b", "/abc"));
+        assert!(RobotsRules::allow_all().is_allowed("/anything"));
+        assert!(!RobotsRules::disallow_all().is_allowed("/anything"));
+    }
+
+    #[test]
+    fn test_out_of_range_crawl_delay_is_ignored() {
+        for value in ["1e30", "-1", "NaN", "inf", "soon"] {
+            let rules = RobotsRules::parse(&format!("User-agent: *\nCrawl-delay: {value}\n"), "a");
+            assert_eq!(rules.crawl_delay(), None, "{value}");
+        }
+        let rules = RobotsRules::parse("User-agent: *\nCrawl-delay: 0.5\n", "a");
+        assert_eq!(rules.crawl_delay(), Some(Duration::from_millis(500)));
+    }
+}
@@ -0,0 +1,572 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use autoagents::core::document::Document;
+use serde_json::{Map, Value};
+use url::Url;
+
+use super::html::extract_page;
+use super::robots::RobotsRules;
+use crate::tools::document_parsing::DocumentParserConfig;
+use crate::tools::document_parsing::source::{
+    DocumentSourceError, FetchedResource, fetch_resource,
+};
+
+/// User agent sent with every request; its product token selects the robots.txt group.
+pub const DEFAULT_USER_AGENT: &str = concat!("autoagents/", env!("CARGO_PKG_VERSION"));
+
+/// Default maximum number of pages fetched by one [`WebReader`] call.
+pub const DEFAULT_MAX_PAGES: usize = 50;
+
+/// Default upper bound on the `Crawl-delay` a site can impose between requests.
+pub const DEFAULT_MAX_CRAWL_DELAY: Duration = Duration::from_secs(30);
+
+/// Nested sitemap indexes followed before giving up.
+const MAX_SITEMAP_DEPTH: usize = 3;
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebReaderError {
+    #[error("invalid URL {url}: {message}")]
+    InvalidUrl { url: String, message: String },
+
+    #[error("invalid sitemap {url}: {message}")]
+    Sitemap { url: String, message: String },
+
+    #[error(transparent)]
+    Source(#[from] DocumentSourceError),
+}
+
+/// Fetches web pages into [`Document`]s for building knowledge bases.
+///
+/// Pages are fetched through the same hardened client as
+/// [`DocumentParser`](crate::tools::document_parsing::DocumentParser), so the
+/// SSRF protections, size limits and timeouts of its [`DocumentParserConfig`]
+/// apply to every request. Starting from the given URLs (or the pages listed
+/// in a sitemap), links are followed breadth-first up to `max_depth` hops
+/// while at most `max_pages` pages are fetched. `robots.txt`, `Crawl-delay`
+/// (capped at `max_crawl_delay`) and `<meta name="robots">` are honoured
+/// unless disabled.
+///
+/// HTML pages are reduced to their main content; plain-text responses are
+/// kept as they are and other content types are skipped. Each document
+/// records `source` and `url` (the final URL after redirects), `depth`,
+/// `content_type`, and the page `title` and `description` when present.
+/// Pages that fail to download are logged and skipped.
+#[derive(Debug, Clone)]
+pub struct WebReader {
+    config: DocumentParserConfig,
+    user_agent: String,
+    max_depth: usize,
+    max_pages: usize,
+    same_host_only: bool,
+    respect_robots_txt: bool,
+    request_delay: Duration,
+    max_crawl_delay: Duration,
+}
+
+impl Default for WebReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct CrawlState {
+    queue: VecDeque<(Url, usize)>,
+    seen: HashSet<String>,
+    hosts: HashSet<String>,
+    robots: HashMap<String, RobotsRules>,
+    last_request: Option<Instant>,
+    fetched: usize,
+}
+
+impl WebReader {
+    pub fn new() -> Self {
+        Self {
+            config: DocumentParserConfig::default(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            max_depth: 0,
+            max_pages: DEFAULT_MAX_PAGES,
+            same_host_only: true,
+            respect_robots_txt: true,
+            request_delay: Duration::ZERO,
+            max_crawl_delay: DEFAULT_MAX_CRAWL_DELAY,
+        }
+    }
+
+    pub fn with_config(mut self, config: DocumentParserConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Follow links up to this many hops from the start pages (default: 0)
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Stop after fetching this many pages (default: [`DEFAULT_MAX_PAGES`])
+    pub fn with_max_pages(mut self, max_pages: usize) -> Self {
+        self.max_pages = max_pages;
+        self
+    }
+
+    /// Only follow links to the hosts of the start pages (default: true)
+    pub fn same_host_only(mut self, same_host_only: bool) -> Self {
+        self.same_host_only = same_host_only;
+        self
+    }
+
+    pub fn respect_robots_txt(mut self, respect: bool) -> Self {
+        self.respect_robots_txt = respect;
+        self
+    }
+
+    /// Minimum time between two requests; a longer `Crawl-delay` takes precedence
+    pub fn with_request_delay(mut self, delay: Duration) -> Self {
+        self.request_delay = delay;
+        self
+    }
+
+    /// Longest `Crawl-delay` to honour; longer delays are clamped to it
+    /// (default: [`DEFAULT_MAX_CRAWL_DELAY`])
+    pub fn with_max_crawl_delay(mut self, max_crawl_delay: Duration) -> Self {
+        self.max_crawl_delay = max_crawl_delay;
+        self
+    }
+
+    /// Fetch `urls` and, up to `max_depth`, the pages they link to.
+    pub async fn load_urls<I, S>(&self, urls: I) -> Result<Vec<Document>, WebReaderError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let seeds = urls
+            .into_iter()
+            .map(|url| parse_url(url.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.crawl(seeds).await
+    }
+
+    /// Fetch the pages listed in the sitemap at `sitemap_url`.
+    ///
+    /// Sitemap indexes are followed to the sitemaps they list. Listed pages
+    /// are treated as start pages, so links are still followed up to
+    /// `max_depth`.
+    pub async fn load_sitemap(&self, sitemap_url: &str) -> Result<Vec<Document>, WebReaderError> {
+        let mut pages = Vec::new();
+        let mut sitemaps = vec![(parse_url(sitemap_url)?, 0usize)];
+        let mut seen = HashSet::new();
+
+        while let Some((url, depth)) = sitemaps.pop() {
+            if !seen.insert(url.to_string()) || pages.len() >= self.max_pages {
+                continue;
+            }
+            let resource =
+                fetch_resource(url.as_str(), &self.config, Some(&self.user_agent)).await?;
+            let (is_index, locations) =
+                parse_sitemap(&resource.bytes).map_err(|message| WebReaderError::Sitemap {
+                    url: url.to_string(),
+                    message,
+                })?;
+
+            let locations = locations.into_iter().filter_map(|loc| {
+                resource
+                    .url
+                    .join(&loc)
+                    .inspect_err(|err| log::warn!("Skipping sitemap entry {loc}: {err}"))
+                    .ok()
+            });
+            if is_index {
+                if depth < MAX_SITEMAP_DEPTH {
+                    sitemaps.extend(locations.map(|loc| (loc, depth + 1)));
+                }
+            } else {
+                pages.extend(locations);
+            }
+        }
+
+        pages.truncate(self.max_pages);
+        self.crawl(pages).await
+    }
+
+    async fn crawl(&self, seeds: Vec<Url>) -> Result<Vec<Document>, WebReaderError> {
+        let mut state = CrawlState {
+            queue: VecDeque::new(),
+            seen: HashSet::new(),
+            hosts: seeds
+                .iter()
+                .filter_map(|url| url.host_str().map(str::to_string))
+                .collect(),
+            robots: HashMap::new(),
+            last_request: None,
+            fetched: 0,
+        };
+        for seed in seeds {
+            if state.seen.insert(normalized(&seed)) {
+                state.queue.push_back((seed, 0));
+            }
+        }
+
+        let mut documents = Vec::new();
+        while let Some((url, depth)) = state.queue.pop_front() {
+            if state.fetched >= self.max_pages {
+                break;
+            }
+            let delay = match self.robots_for(&url, &mut state).await {
+                Some(rules) if !rules.is_allowed(&path_and_query(&url)) => {
+                    log::debug!("robots.txt disallows {url}");
+                    continue;
+                }
+                Some(rules) => rules
+                    .crawl_delay()
+                    .unwrap_or_default()
+                    .min(self.max_crawl_delay),
+                None => Duration::ZERO,
+            };
+
+            self.wait(delay.max(self.request_delay), &mut state).await;
+            state.fetched += 1;
+            let resource =
+                match fetch_resource(url.as_str(), &self.config, Some(&self.user_agent)).await {
+                    Ok(resource) => resource,
+                    Err(err) => {
+                        log::warn!("Skipping {url}: {err}");
+                        continue;
+                    }
+                };
+            state.seen.insert(normalized(&resource.url));
+
+            let links = self.read_resource(&resource, depth, &mut documents);
+            if depth < self.max_depth {
+                for link in links {
+                    self.enqueue(&resource.url, &link, depth + 1, &mut state);
+                }
+            }
+        }
+        Ok(documents)
+    }
+
+    /// Turn a fetched resource into a document and return the links to follow
+    fn read_resource(
+        &self,
+        resource: &FetchedResource,
+        depth: usize,
+        documents: &mut Vec<Document>,
+    ) -> Vec<String> {
+        let content_type = resource
+            .content_type
+            .as_deref()
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase())
+            .unwrap_or_else(|| "text/html".to_string());
+        let body = String::from_utf8_lossy(&resource.bytes);
+
+        let mut metadata = Map::new();
+        metadata.insert("source".into(), resource.url.as_str().into());
+        metadata.insert("url".into(), resource.url.as_str().into());
+        metadata.insert("depth".into(), depth.into());
+        metadata.insert("content_type".into(), content_type.clone().into());
+
+        let (text, links) = match content_type.as_str() {
+            "text/html" | "application/xhtml+xml" => {
+                let page = extract_page(&body);
+                if let Some(title) = page.title {
+                    metadata.insert("title".into(), title.into());
+                }
+                if let Some(description) = page.description {
+                    metadata.insert("description".into(), description.into());
+                }
+                let links = if page.nofollow && self.respect_robots_txt {
+                    Vec::new()
+                } else {
+                    page.links
+                };
+                let text = if page.noindex && self.respect_robots_txt {
+                    String::new()
+                } else {
+                    page.text
+                };
+                (text, links)
+            }
+            "text/plain" | "text/markdown" => (body.trim().to_string(), Vec::new()),
+            other => {
+                log::debug!("Skipping {} with content type {other}", resource.url);
+                return Vec::new();
+            }
+        };
+
+        if !text.is_empty() {
+            documents.push(Document::with_metadata(text, Value::Object(metadata)));
+        }
+        links
+    }
+
+    fn enqueue(&self, base: &Url, link: &str, depth: usize, state: &mut CrawlState) {
+        let Ok(mut url) = base.join(link) else {
+            return;
+        };
+        url.set_fragment(None);
+        if !matches!(url.scheme(), "http" | "https") {
+            return;
+        }
+        if self.same_host_only && !url.host_str().is_some_and(|h| state.hosts.contains(h)) {
+            return;
+        }
+        if state.seen.insert(normalized(&url)) {
+            state.queue.push_back((url, depth));
+        }
+    }
+
+    async fn robots_for<'a>(
+        &self,
+        url: &Url,
+        state: &'a mut CrawlState,
+    ) -> Option<&'a RobotsRules> {
+        if !self.respect_robots_txt {
+            return None;
+        }
+        let origin = url.origin().ascii_serialization();
+        if !state.robots.contains_key(&origin) {
+            let rules = self.fetch_robots(url).await;
+            state.robots.insert(origin.clone(), rules);
+        }
+        state.robots.get(&origin)
+    }
+
+    async fn fetch_robots(&self, url: &Url) -> RobotsRules {
+        let Ok(robots_url) = url.join("/robots.txt") else {
+            return RobotsRules::allow_all();
+        };
+        match fetch_resource(robots_url.as_str(), &self.config, Some(&self.user_agent)).await {
+            Ok(resource) => {
+                RobotsRules::parse(&String::from_utf8_lossy(&resource.bytes), &self.user_agent)
+            }
+            // A missing robots.txt allows everything; an unreachable one allows nothing
+            Err(DocumentSourceError::HttpStatus { status }) if (400..500).contains(&status) => {
+                RobotsRules::allow_all()
+            }
+            Err(err) => {
+                log::warn!("Could not fetch {robots_url}, skipping its host: {err}");
+                RobotsRules::disallow_all()
+            }
+        }
+    }
+
+    async fn wait(&self, delay: Duration, state: &mut CrawlState) {
+        if let Some(last) = state.last_request {
+            let elapsed = last.elapsed();
+            if elapsed < delay {
+                tokio::time::sleep(delay - elapsed).await;
+            }
+        }
+        state.last_request = Some(Instant::now());
+    }
+}
+
+fn parse_url(url: &str) -> Result<Url, WebReaderError> {
+    Url::parse(url).map_err(|err| WebReaderError::InvalidUrl {
+        url: url.to_string(),
+        message: err.to_string(),
+    })
+}
+
+fn normalized(url: &Url) -> String {
+    let mut url = url.clone();
+    url.set_fragment(None);
+    url.to_string()
+}
+
+fn path_and_query(url: &Url) -> String {
+    match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_string(),
+    }
+}
+
+/// Parse a sitemap into whether it is a sitemap index and the `<loc>` values it lists
+fn parse_sitemap(bytes: &[u8]) -> Result<(bool, Vec<String>), String> {
+    use quick_xml::events::Event;
+
+    let mut reader = quick_xml::Reader::from_reader(bytes);
+    let mut buf = Vec::new();
+    let mut is_index = false;
+    let mut in_loc = false;
+    let mut locations = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                b"sitemapindex" => is_index = true,
+                b"loc" => {
+                    in_loc = true;
+                    locations.push(String::new());
+                }
+                _ => {}
+            },
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"loc" => in_loc = false,
+            Ok(Event::Text(ref e)) if in_loc => {
+                let decoded = e.decode().map_err(|err| err.to_string())?;
+                if let Some(location) = locations.last_mut() {
+                    location.push_str(&decoded);
+                }
+            }
+            Ok(Event::GeneralRef(ref e)) if in_loc => {
+                let name = e.decode().map_err(|err| err.to_string())?;
+                let resolved = quick_xml::escape::unescape(&format!("&{name};"))
+                    .map_err(|err| err.to_string())?
+                    .into_owned();
+                if let Some(location) = locations.last_mut() {
+                    location.push_str(&resolved);
+                }
+            }
+            Ok(Event::CData(ref e)) if in_loc => {
+                if let Some(location) = locations.last_mut() {
+                    location.push_str(&String::from_utf8_lossy(e));
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(err) => return Err(err.to_string()),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    let locations = locations
+        .into_iter()
+        .map(|location| location.trim().to_string())
+        .filter(|location| !location.is_empty())
+        .collect();
+    Ok((is_index, locations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::{Method::GET, MockServer};
+
+    fn test_reader() -> WebReader {
+        let config = DocumentParserConfig::default()
+            .with_allow_private_networks(true)
+            .with_allowed_hosts(vec!["127.0.0.1".to_string()])
+            .expect("hosts")
+            .with_request_timeout(Duration::from_secs(5));
+        WebReader::new().with_config(config)
+    }
+
+    fn html_page(server: &MockServer, path: &str, body: &str) {
+        server.mock(|when, then| {
+            when.method(GET).path(path);
+            then.status(200)
+                .header("Content-Type", "text/html; charset=utf-8")
+                .body(body);
+        });
+    }
+
+    #[tokio::test]
+    async fn test_crawls_links_within_budget_and_robots() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/robots.txt");
+            then.status(200).body("User-agent: *\nDisallow: /private\n");
+        });
+        html_page(
+            &server,
+            "/",
+            r##"<title>Home</title><main>Welcome home
+            <a href="/a">A</a> <a href="b#section">B</a> <a href="/private/x">X</a>
+            <a href="https://example.com/">External</a></main>"##,
+        );
+        html_page(&server, "/a", r#"<p>Page A</p><a href="/deep">Deep</a>"#);
+        html_page(&server, "/b", "<p>Page B</p>");
+        let private = server.mock(|when, then| {
+            when.method(GET).path("/private/x");
+            then.status(200).body("secret");
+        });
+
+        let reader = test_reader().with_max_depth(1);
+        let docs = reader.load_urls([server.url("/")]).await.unwrap();
+
+        let sources: Vec<&str> = docs
+            .iter()
+            .map(|doc| doc.metadata["source"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            sources,
+            vec![server.url("/"), server.url("/a"), server.url("/b")]
+        );
+        assert_eq!(docs[0].page_content, "Welcome home A B X External");
+        assert_eq!(docs[0].metadata["title"], "Home");
+        assert_eq!(docs[1].metadata["depth"], 1);
+        private.assert_calls(0);
+
+        let limited = test_reader().with_max_depth(3).with_max_pages(2);
+        assert_eq!(limited.load_urls([server.url("/")]).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sitemap_index_lists_pages() {
+        let server = MockServer::start();
+        let index = format!(
+            r#"<?xml version="1.0"?><sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+            <sitemap><loc>{}</loc></sitemap></sitemapindex>"#,
+            server.url("/pages.xml")
+        );
+        server.mock(|when, then| {
+            when.method(GET).path("/sitemap.xml");
+            then.status(200).body(&index);
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/pages.xml");
+            then.status(200).body(
+                r#"<urlset><url><loc>/one?a=1&amp;b=2</loc></url><url><loc> /two.txt </loc></url></urlset>"#,
+            );
+        });
+        html_page(&server, "/one", "<p>One</p>");
+        server.mock(|when, then| {
+            when.method(GET).path("/two.txt");
+            then.status(200)
+                .header("Content-Type", "text/plain")
+                .body("Two");
+        });
+
+        let docs = test_reader()
+            .load_sitemap(&server.url("/sitemap.xml"))
+            .await
+            .unwrap();
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].page_content, "One");
+        assert_eq!(docs[0].metadata["url"], server.url("/one?a=1&b=2"));
+        assert_eq!(docs[1].page_content, "Two");
+        assert_eq!(docs[1].metadata["content_type"], "text/plain");
+    }
+
+    #[tokio::test]
+    async fn test_crawl_delay_is_clamped() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/robots.txt");
+            then.status(200).body("User-agent: *\nCrawl-delay: 86400\n");
+        });
+        html_page(&server, "/", r#"<p>Home</p><a href="/a">A</a>"#);
+        html_page(&server, "/a", "<p>Page A</p>");
+
+        let reader = test_reader()
+            .with_max_depth(1)
+            .with_max_crawl_delay(Duration::from_millis(10));
+        let started = Instant::now();
+        let docs = reader.load_urls([server.url("/")]).await.unwrap();
+        assert_eq!(docs.len(), 2);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_url_is_rejected() {
+        let err = test_reader().load_urls(["not a url"]).await.unwrap_err();
+        assert!(matches!(err, WebReaderError::InvalidUrl { .. }));
+    }
+}
@@ -0,0 +1,220 @@
+//! [`Reranker`](autoagents::core::vector_store::Reranker)s backed by hosted rerank APIs.
+//!
+//! Cohere and Jina AI expose the same request shape: a query, a list of
+//! documents, and a relevance score per document index in the response.
+//! Attach one to a search with
+//! [`VectorSearchRequest::with_reranker`](autoagents::core::vector_store::VectorSearchRequest::with_reranker).
+
+use autoagents::async_trait;
+use autoagents::core::vector_store::{Reranker, VectorStoreError};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+pub const COHERE_API_KEY_ENV: &str = "COHERE_API_KEY";
+pub const JINA_API_KEY_ENV: &str = "JINA_API_KEY";
+
+const COHERE_RERANK_ENDPOINT: &str = "https://api.cohere.com/v2/rerank";
+const JINA_RERANK_ENDPOINT: &str = "https://api.jina.ai/v1/rerank";
+
+const COHERE_DEFAULT_MODEL: &str = "rerank-v3.5";
+const JINA_DEFAULT_MODEL: &str = "jina-reranker-v2-base-multilingual";
+
+/// Reranker calling a Cohere-compatible `/rerank` endpoint
+#[derive(Debug, Clone)]
+pub struct ApiReranker {
+    client: Client,
+    endpoint: String,
+    api_key: String,
+    model: String,
+}
+
+impl ApiReranker {
+    /// Reranker for any endpoint speaking the Cohere rerank format
+    pub fn new(
+        endpoint: impl Into<String>,
+        api_key: impl Into<String>,
+        model: impl Into<String>,
+    ) -> Self {
+        Self {
+            client: Client::new(),
+            endpoint: endpoint.into(),
+            api_key: api_key.into(),
+            model: model.into(),
+        }
+    }
+
+    /// Cohere rerank with `rerank-v3.5`
+    pub fn cohere(api_key: impl Into<String>) -> Self {
+        Self::new(COHERE_RERANK_ENDPOINT, api_key, COHERE_DEFAULT_MODEL)
+    }
+
+    /// Cohere rerank with the key from `COHERE_API_KEY`
+    pub fn cohere_from_env() -> Option<Self> {
+        std::env::var(COHERE_API_KEY_ENV).ok().map(Self::cohere)
+    }
+
+    /// Jina AI rerank with `jina-reranker-v2-base-multilingual`
+    pub fn jina(api_key: impl Into<String>) -> Self {
+        Self::new(JINA_RERANK_ENDPOINT, api_key, JINA_DEFAULT_MODEL)
+    }
+
+    /// Jina AI rerank with the key from `JINA_API_KEY`
+    pub fn jina_from_env() -> Option<Self> {
+        std::env::var(JINA_API_KEY_ENV).ok().map(Self::jina)
+    }
+
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RerankResult {
+    index: usize,
+    relevance_score: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RerankResponse {
+    results: Vec<RerankResult>,
+}
+
+fn rerank_error(message: impl Into<String>) -> VectorStoreError {
+    VectorStoreError::RerankError(message.into())
+}
+
+#[async_trait]
+impl Reranker for ApiReranker {
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: &[String],
+    ) -> Result<Vec<f64>, VectorStoreError> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .bearer_auth(&self.api_key)
+            .json(&json!({
+                "model": self.model,
+                "query": query,
+                "documents": documents,
+                "top_n": documents.len(),
+            }))
+            .send()
+            .await
+            .map_err(|err| rerank_error(format!("request to {} failed: {err}", self.endpoint)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(rerank_error(format!("HTTP {status}: {body}")));
+        }
+        let response: RerankResponse = response
+            .json()
+            .await
+            .map_err(|err| rerank_error(format!("invalid rerank response: {err}")))?;
+
+        // Documents the API leaves out rank last
+        let mut scores = vec![f64::NEG_INFINITY; documents.len()];
+        for result in response.results {
+            let score = scores.get_mut(result.index).ok_or_else(|| {
+                rerank_error(format!(
+                    "rerank response refers to document {} of {}",
+                    result.index,
+                    documents.len()
+                ))
+            })?;
+            *score = result.relevance_score;
+        }
+        Ok(scores)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::{Method::POST, MockServer};
+
+    fn documents() -> Vec<String> {
+        ["cats", "rust ownership", "borrow checker"]
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_scores_follow_document_order() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/v2/rerank")
+                .header("authorization", "Bearer key")
+                .json_body_includes(r#"{"model":"rerank-v3.5","query":"rust","top_n":3}"#);
+            then.status(200).json_body(json!({
+                "results": [
+                    { "index": 1, "relevance_score": 0.9 },
+                    { "index": 2, "relevance_score": 0.4 },
+                    { "index": 0, "relevance_score": 0.01 },
+                ]
+            }));
+        });
+
+        let reranker = ApiReranker::new(server.url("/v2/rerank"), "key", COHERE_DEFAULT_MODEL);
+        let scores = reranker.rerank("rust", &documents()).await.unwrap();
+        mock.assert();
+        assert_eq!(scores, vec![0.01, 0.9, 0.4]);
+    }
+
+    #[tokio::test]
+    async fn test_missing_results_rank_last_and_bad_indexes_fail() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST).path("/partial");
+            then.status(200)
+                .json_body(json!({ "results": [{ "index": 0, "relevance_score": 0.5 }] }));
+        });
+        server.mock(|when, then| {
+            when.method(POST).path("/bad");
+            then.status(200)
+                .json_body(json!({ "results": [{ "index": 7, "relevance_score": 0.5 }] }));
+        });
+        server.mock(|when, then| {
+            when.method(POST).path("/denied");
+            then.status(401).body("invalid api key");
+        });
+
+        let partial = ApiReranker::new(server.url("/partial"), "key", "m");
+        let scores = partial.rerank("q", &documents()).await.unwrap();
+        assert_eq!(scores[0], 0.5);
+        assert!(scores[1..].iter().all(|score| *score == f64::NEG_INFINITY));
+
+        let bad = ApiReranker::new(server.url("/bad"), "key", "m");
+        assert!(bad.rerank("q", &documents()).await.is_err());
+
+        let denied = ApiReranker::new(server.url("/denied"), "key", "m");
+        let err = denied.rerank("q", &documents()).await.unwrap_err();
+        assert!(err.to_string().contains("invalid api key"));
+    }
+
+    #[test]
+    fn test_provider_defaults() {
+        assert_eq!(ApiReranker::cohere("k").endpoint, COHERE_RERANK_ENDPOINT);
+        assert_eq!(ApiReranker::jina("k").model(), JINA_DEFAULT_MODEL);
+        assert_eq!(ApiReranker::jina("k").with_model("m").model(), "m");
+    }
+}
@@ -15,10 +15,11 @@
 mod config;
 mod parse_document;
 pub(crate) mod parsers;
-mod source;
+pub(crate) mod source;
 
 pub use config::DocumentParserConfig;
 pub use parse_document::DocumentParser;
+pub use source::DocumentSourceError;
 
 use std::path::Path;
 
@@ -21,15 +21,40 @@ fn build_pinned_client(
         .map_err(DocumentSourceError::from)
 }
 
+/// A downloaded resource together with the URL it was finally served from.
+pub struct FetchedResource {
+    pub bytes: Vec<u8>,
+    pub url: Url,
+    /// Read by the web reader to pick how a page is extracted
+    #[cfg_attr(not(feature = "readers"), allow(dead_code))]
+    pub content_type: Option<String>,
+}
+
 pub async fn fetch_url(
     url: &str,
     config: &DocumentParserConfig,
 ) -> Result<(Vec<u8>, Option<String>), DocumentSourceError> {
+    let resource = fetch_resource(url, config, None).await?;
+    let filename = resource
+        .url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.to_string());
+
+    Ok((resource.bytes, filename))
+}
+
+pub async fn fetch_resource(
+    url: &str,
+    config: &DocumentParserConfig,
+    user_agent: Option<&str>,
+) -> Result<FetchedResource, DocumentSourceError> {
     let mut current = validate_url_str(url, config)?;
     let mut redirects = 0usize;
 
     loop {
-        let response = send_request(&current, config).await?;
+        let response = send_request(&current, config, user_agent).await?;
 
         if response.status().is_redirection() {
             if redirects >= config.max_redirects {
@@ -56,21 +81,26 @@ pub async fn fetch_url(
         }
 
         let checked = response.error_for_status().map_err(map_status_error)?;
+        let content_type = checked
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
 
         let bytes = read_bounded_body(checked, config.max_download_bytes).await?;
-        let filename = current
-            .path_segments()
-            .and_then(|mut segments| segments.next_back())
-            .filter(|segment| !segment.is_empty())
-            .map(|segment| segment.to_string());
 
-        return Ok((bytes, filename));
+        return Ok(FetchedResource {
+            bytes,
+            url: current,
+            content_type,
+        });
     }
 }
 
 async fn send_request(
     url: &Url,
     config: &DocumentParserConfig,
+    user_agent: Option<&str>,
 ) -> Result<Response, DocumentSourceError> {
     let host = url
         .host_str()
@@ -79,11 +109,11 @@ async fn send_request(
     let addresses = resolve_host(host, port, config).await?;
     let client = build_pinned_client(host, &addresses, config)?;
 
-    client
-        .get(url.clone())
-        .send()
-        .await
-        .map_err(DocumentSourceError::from)
+    let mut request = client.get(url.clone());
+    if let Some(user_agent) = user_agent {
+        request = request.header(reqwest::header::USER_AGENT, user_agent);
+    }
+    request.send().await.map_err(DocumentSourceError::from)
 }
 
 fn map_status_error(error: reqwest::Error) -> DocumentSourceError {
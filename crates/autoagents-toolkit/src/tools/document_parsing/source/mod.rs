@@ -5,4 +5,6 @@ mod url_policy;
 
 pub use error::DocumentSourceError;
 pub use http_fetch::fetch_url;
+#[cfg(feature = "readers")]
+pub use http_fetch::{FetchedResource, fetch_resource};
 pub use local_file::load_local_file;
@@ -0,0 +1,172 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use autoagents::core::{
+    ractor::async_trait,
+    tool::{ToolCallError, ToolRuntime, ToolT},
+};
+use autoagents::llm::image_generation::{ImageGenerationProvider, ImageGenerationRequest};
+use autoagents_derive::{ToolInput, tool};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::fs;
+
+/// Upper bound on images per call, so one tool call cannot run up a large bill
+const MAX_IMAGES_PER_CALL: u32 = 4;
+
+#[derive(Serialize, Deserialize, ToolInput, Debug)]
+pub struct GenerateImageArgs {
+    #[input(description = "Detailed description of the image to generate")]
+    prompt: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(description = "Things the image should not contain")]
+    negative_prompt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(description = "Image width in pixels")]
+    width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(description = "Image height in pixels")]
+    height: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[input(description = "Number of images to generate (1-4, default 1)")]
+    count: Option<u32>,
+}
+
+#[tool(
+    name = "generate_image",
+    description = "Generate images from a text prompt and save them as files. Returns the path of each image.",
+    input = GenerateImageArgs,
+)]
+pub struct GenerateImage {
+    provider: Arc<dyn ImageGenerationProvider>,
+    output_dir: PathBuf,
+}
+
+impl GenerateImage {
+    /// Save generated images into `output_dir`, which is created if missing
+    pub fn new(provider: Arc<dyn ImageGenerationProvider>, output_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            provider,
+            output_dir: output_dir.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolRuntime for GenerateImage {
+    async fn execute(&self, args: Value) -> Result<Value, ToolCallError> {
+        let GenerateImageArgs {
+            prompt,
+            negative_prompt,
+            width,
+            height,
+            count,
+        } = serde_json::from_value(args)?;
+
+        let mut request =
+            ImageGenerationRequest::new(prompt).count(count.unwrap_or(1).min(MAX_IMAGES_PER_CALL));
+        request.negative_prompt = negative_prompt;
+        if let (Some(width), Some(height)) = (width, height) {
+            request = request.size(width, height);
+        }
+
+        debug!("Generate Image Executing: {} image(s)", request.count);
+
+        let images = self
+            .provider
+            .generate_images(&request)
+            .await
+            .map_err(|e| ToolCallError::RuntimeError(Box::new(e)))?;
+
+        fs::create_dir_all(&self.output_dir)
+            .await
+            .map_err(|e| ToolCallError::RuntimeError(Box::new(e)))?;
+
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let mut saved = Vec::with_capacity(images.len());
+        for (index, image) in images.into_iter().enumerate() {
+            let path = self
+                .output_dir
+                .join(format!("image-{stamp}-{index}.{}", image.extension()));
+            fs::write(&path, &image.data)
+                .await
+                .map_err(|e| ToolCallError::RuntimeError(Box::new(e)))?;
+            saved.push(json!({
+                "path": path.to_string_lossy(),
+                "mime_type": image.mime_type,
+                "revised_prompt": image.revised_prompt,
+                "seed": image.seed,
+            }));
+        }
+
+        Ok(json!({ "images": saved }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use autoagents::llm::error::LLMError;
+    use autoagents::llm::image_generation::GeneratedImage;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    #[derive(Default)]
+    struct RecordingProvider {
+        requests: Mutex<Vec<ImageGenerationRequest>>,
+    }
+
+    #[async_trait]
+    impl ImageGenerationProvider for RecordingProvider {
+        async fn generate_images(
+            &self,
+            request: &ImageGenerationRequest,
+        ) -> Result<Vec<GeneratedImage>, LLMError> {
+            self.requests.lock().unwrap().push(request.clone());
+            Ok((0..request.count)
+                .map(|index| GeneratedImage {
+                    data: vec![index as u8],
+                    mime_type: "image/png".to_string(),
+                    revised_prompt: None,
+                    seed: Some(index as u64),
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_image_saves_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let output_dir = temp_dir.path().join("images");
+        let provider = Arc::new(RecordingProvider::default());
+        let tool = GenerateImage::new(provider.clone(), &output_dir);
+
+        let result = tool
+            .execute(json!({
+                "prompt": "a lighthouse",
+                "width": 512,
+                "height": 512,
+                "count": 10
+            }))
+            .await
+            .unwrap();
+
+        let images = result["images"].as_array().unwrap();
+        assert_eq!(images.len(), MAX_IMAGES_PER_CALL as usize);
+        let path = PathBuf::from(images[1]["path"].as_str().unwrap());
+        assert!(path.starts_with(&output_dir));
+        assert_eq!(path.extension().unwrap(), "png");
+        assert_eq!(std::fs::read(&path).unwrap(), vec![1]);
+
+        let requests = provider.requests.lock().unwrap();
+        assert_eq!(
+            (requests[0].width, requests[0].height),
+            (Some(512), Some(512))
+        );
+    }
+}
@@ -0,0 +1,3 @@
+mod generate_image;
+
+pub use generate_image::GenerateImage;
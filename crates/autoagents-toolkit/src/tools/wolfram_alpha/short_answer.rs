@@ -32,7 +32,10 @@ pub struct WolframAlphaShortAnswerArgs {
     #[input(description = "Question to send to Wolfram|Alpha Short Answers API")]
     query: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    #[input(description = "Unit system to prefer in the response (metric or imperial)")]
+    #[input(
+        description = "Unit system to prefer in the response (metric or imperial)",
+        choice = ["metric", "imperial"]
+    )]
     units: Option<ShortAnswerUnits>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[input(description = "Maximum time to wait for a response in seconds (default 5)")]
@@ -180,4 +183,14 @@ mod tests {
         .unwrap_err();
         assert!(err.to_string().contains("unauthorized"));
     }
+
+    #[test]
+    fn test_units_schema_lists_choices() {
+        use autoagents::core::tool::ToolInputSchema;
+        let schema = WolframAlphaShortAnswerArgs::io_schema_value();
+        assert_eq!(
+            schema["properties"]["units"]["enum"],
+            json!(["metric", "imperial"])
+        );
+    }
 }
@@ -1,7 +1,7 @@
 //! AutoAgents prelude: common traits, types, and macros for quick start.
 
 // Macros and derives
-pub use autoagents_derive::{AgentHooks, AgentOutput, ToolInput, agent, tool};
+pub use autoagents_derive::{AgentHooks, AgentOutput, ToolInput, agent, tool, toolkit};
 
 // Core agent types
 pub use crate::core::agent::memory::SlidingWindowMemory;
@@ -8,7 +8,7 @@ use autoagents::core::agent::{AgentDeriveT, AgentOutputT};
 use autoagents::core::tool::{
     ToolCallError, ToolInputSchema, ToolInputT, ToolOutputT, ToolRuntime, ToolT,
 };
-use autoagents_derive::{AgentHooks, AgentOutput, ToolInput, agent, tool};
+use autoagents_derive::{AgentHooks, AgentOutput, ToolInput, agent, tool, toolkit};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -37,6 +37,38 @@ struct RichToolArgs {
     large_id: u64,
 }
 
+#[derive(Serialize, Deserialize, ToolInput, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Serialize, Deserialize, ToolInput, Debug)]
+struct PriceRange {
+    #[input(description = "Lower bound")]
+    min: u32,
+    #[input(description = "Upper bound", max = 10000)]
+    max: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, ToolInput, Debug)]
+enum SearchFilter {
+    InStock,
+    Price(#[input(nested)] PriceRange),
+    Tag { name: String },
+}
+
+#[derive(Serialize, Deserialize, ToolInput, Debug)]
+struct SearchArgs {
+    #[input(description = "Search terms")]
+    terms: Vec<String>,
+    #[input(description = "Sort order", nested)]
+    order: Option<SortOrder>,
+    #[input(description = "Filters to apply", nested)]
+    filters: Vec<SearchFilter>,
+}
+
 /// First line of docs
 /// Second line of docs
 #[derive(Debug, Serialize, Deserialize, AgentOutput)]
@@ -125,6 +157,262 @@ fn tool_input_schema_covers_numeric_and_string_choices() {
     assert_eq!(static_value["type"], "object");
 }
 
+#[test]
+fn tool_input_schema_covers_nested_types_and_enums() {
+    let schema = SearchArgs::io_schema_value();
+    assert_eq!(schema["properties"]["terms"]["items"]["type"], "string");
+
+    let order = &schema["properties"]["order"];
+    assert_eq!(order["description"], "Sort order");
+    assert_eq!(order["enum"], serde_json::json!(["asc", "desc"]));
+    assert_eq!(schema["required"], serde_json::json!(["filters", "terms"]));
+
+    let variants = schema["properties"]["filters"]["items"]["oneOf"]
+        .as_array()
+        .unwrap();
+    assert_eq!(variants[0]["enum"], serde_json::json!(["InStock"]));
+    let price = &variants[1]["properties"]["Price"];
+    assert_eq!(price["properties"]["min"]["description"], "Lower bound");
+    assert_eq!(price["required"], serde_json::json!(["min"]));
+    assert_eq!(
+        variants[2]["properties"]["Tag"]["required"],
+        serde_json::json!(["name"])
+    );
+
+    let parsed: Value = serde_json::from_str(SearchArgs::io_schema()).unwrap();
+    assert_eq!(&parsed, schema);
+}
+
+#[test]
+fn tool_input_from_args_reports_field_paths() {
+    let args = SearchArgs::from_args(serde_json::json!({
+        "terms": ["lamp"],
+        "order": "desc",
+        "filters": ["InStock", {"Price": {"min": 10}}]
+    }))
+    .unwrap();
+    assert_eq!(args.order, Some(SortOrder::Desc));
+    assert_eq!(args.filters.len(), 2);
+
+    let err = SearchArgs::from_args(serde_json::json!({
+        "terms": [],
+        "filters": [{"Price": {"min": "ten"}}]
+    }))
+    .unwrap_err();
+    match err {
+        ToolCallError::InvalidArgument { field, .. } => assert_eq!(field, "filters[0].Price.min"),
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+#[derive(Serialize, Deserialize, ToolInput, Debug)]
+struct LookupArgs {
+    #[input(description = "Word to look up", pattern = "^[a-z]+$", max_length = 12)]
+    word: String,
+    #[input(description = "Number of senses", min = 1, max = 5)]
+    senses: u8,
+}
+
+#[tool(name = "lookup", description = "Dictionary lookup", input = LookupArgs)]
+struct LookupTool;
+
+#[async_trait]
+impl ToolRuntime for LookupTool {
+    async fn execute(&self, _args: Value) -> Result<Value, ToolCallError> {
+        Ok(Value::Null)
+    }
+}
+
+#[test]
+fn tool_input_constraints_are_validated() {
+    let schema = LookupArgs::io_schema_value();
+    assert_eq!(schema["properties"]["senses"]["maximum"], 5);
+    assert_eq!(schema["properties"]["word"]["maxLength"], 12);
+
+    let tool = LookupTool;
+    tool.validate_args(&serde_json::json!({"word": "lamp", "senses": 2}))
+        .unwrap();
+    let err = tool
+        .validate_args(&serde_json::json!({"word": "lamp", "senses": 9}))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid argument `senses`: must be at most 5, got 9"
+    );
+    let err = tool
+        .validate_args(&serde_json::json!({"word": "Lamp", "senses": 1}))
+        .unwrap_err();
+    assert!(matches!(err, ToolCallError::ConstraintViolation { field, .. } if field == "word"));
+
+    // Constraints of nested inputs are checked through the outer type.
+    let err = SearchArgs::validate_args(&serde_json::json!({
+        "terms": [],
+        "filters": [{"Price": {"min": 1, "max": 20000}}]
+    }))
+    .unwrap_err();
+    assert!(
+        matches!(err, ToolCallError::ConstraintViolation { field, .. } if field == "filters[0].Price.max")
+    );
+    RichToolArgs::validate_args(&serde_json::json!({"small": 1000})).unwrap();
+}
+
+#[derive(Serialize, Deserialize, ToolInput, Debug)]
+struct PingArgs {
+    #[input(description = "Host to ping")]
+    host: std::net::IpAddr,
+    #[input(description = "Round trips to wait for")]
+    count: Option<u8>,
+}
+
+#[tool(name = "ping", description = "Ping a host", input = PingArgs)]
+struct PingTool;
+
+#[async_trait]
+impl ToolRuntime for PingTool {
+    async fn execute(&self, _args: Value) -> Result<Value, ToolCallError> {
+        Ok(Value::Null)
+    }
+}
+
+#[test]
+fn tool_args_are_deserialized_before_execute() {
+    // Types that do not derive `ToolInput` are described as strings.
+    assert_eq!(
+        PingArgs::io_schema_value()["properties"]["host"]["type"],
+        "string"
+    );
+
+    let tool = PingTool;
+    tool.validate_args(&serde_json::json!({"host": "10.0.0.1"}))
+        .unwrap();
+    let err = tool
+        .validate_args(&serde_json::json!({"host": "10.0.0.1", "count": "three"}))
+        .unwrap_err();
+    assert!(matches!(err, ToolCallError::InvalidArgument { field, .. } if field == "count"));
+}
+
+#[derive(Serialize, Deserialize, ToolInput, Debug)]
+struct DocumentedArgs {
+    /// Text to echo back
+    text: String,
+}
+
+/// Echo the text back
+#[tool(name = "echo", input = DocumentedArgs)]
+struct DocumentedTool;
+
+#[async_trait]
+impl ToolRuntime for DocumentedTool {
+    async fn execute(&self, args: Value) -> Result<Value, ToolCallError> {
+        Ok(args)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, AgentOutput)]
+struct DocumentedOutput {
+    /// The echoed text
+    text: String,
+}
+
+/// Echo whatever the user says
+/// using the echo tool.
+#[agent(name = "echo_agent", tools = [DocumentedTool], output = DocumentedOutput)]
+#[derive(Clone, AgentHooks, Default)]
+struct DocumentedAgent;
+
+#[test]
+fn doc_comments_fill_in_missing_descriptions() {
+    let tool = DocumentedTool;
+    assert_eq!(tool.description(), "Echo the text back");
+    assert_eq!(
+        tool.args_schema()["properties"]["text"]["description"],
+        "Text to echo back"
+    );
+
+    let agent = DocumentedAgent;
+    assert_eq!(
+        agent.description(),
+        "Echo whatever the user says using the echo tool."
+    );
+    let output = DocumentedOutput::structured_output_format();
+    assert_eq!(
+        output["schema"]["properties"]["text"]["description"],
+        "The echoed text"
+    );
+}
+
+struct Notes {
+    owner: String,
+    entries: std::sync::Mutex<Vec<String>>,
+}
+
+#[toolkit(prefix = "notes_")]
+impl Notes {
+    /// Store a note
+    #[tool]
+    async fn add(
+        &self,
+        #[input(description = "Note text", max_length = 20)] text: String,
+    ) -> Result<usize, ToolCallError> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.push(text);
+        Ok(entries.len())
+    }
+
+    #[tool(name = "list", description = "List stored notes")]
+    fn list_notes(&self, limit: Option<usize>) -> Vec<String> {
+        let entries = self.entries.lock().unwrap();
+        let limit = limit.unwrap_or(entries.len());
+        entries
+            .iter()
+            .take(limit)
+            .map(|entry| format!("{}: {entry}", self.owner))
+            .collect()
+    }
+
+    fn count(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+}
+
+#[tokio::test]
+async fn toolkit_methods_share_state() {
+    let notes = std::sync::Arc::new(Notes {
+        owner: "ada".to_string(),
+        entries: Default::default(),
+    });
+    let tools = std::sync::Arc::clone(&notes).into_tools();
+    let names: Vec<&str> = tools.iter().map(|tool| tool.name()).collect();
+    assert_eq!(names, ["notes_add", "notes_list"]);
+    assert_eq!(tools[0].description(), "Store a note");
+
+    let schema = tools[0].args_schema();
+    assert_eq!(schema["properties"]["text"]["maxLength"], 20);
+    assert_eq!(schema["required"], serde_json::json!(["text"]));
+    assert!(tools[1].args_schema().get("required").is_none());
+
+    let added = tools[0]
+        .execute(serde_json::json!({"text": "buy milk"}))
+        .await
+        .unwrap();
+    assert_eq!(added, 1);
+    assert!(
+        tools[0]
+            .validate_args(&serde_json::json!({"text": "x".repeat(21)}))
+            .is_err()
+    );
+
+    let listed = NotesListNotes::new(std::sync::Arc::clone(&notes))
+        .execute(serde_json::json!({}))
+        .await
+        .unwrap();
+    assert_eq!(listed, serde_json::json!(["ada: buy milk"]));
+    assert_eq!(notes.count(), 1);
+
+    let err = tools[0].execute(serde_json::json!({})).await.unwrap_err();
+    assert!(err.to_string().contains("text"), "{err}");
+}
+
 #[test]
 fn tool_output_schema_is_exposed() {
     let tool = RichTool;
@@ -157,6 +445,228 @@ fn agent_output_schema_covers_vectors_choices_and_docs() {
     assert!(raw.contains("RichAgentOutput"));
 }
 
+#[derive(Debug, Serialize, Deserialize, AgentOutput, PartialEq)]
+#[output(from = [react, basic], fallback = explanation, error = parse_error)]
+struct ConvertedOutput {
+    #[output(description = "Value")]
+    value: i64,
+    #[output(description = "Explanation")]
+    explanation: String,
+    #[output(description = "Why the response was not valid JSON")]
+    parse_error: Option<String>,
+}
+
+fn plain_text_output(resp: &str) -> PlainTextOutput {
+    PlainTextOutput {
+        text: resp.to_uppercase(),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, AgentOutput)]
+#[output(from = react, fallback_with = plain_text_output)]
+struct PlainTextOutput {
+    #[output(description = "Text")]
+    text: String,
+}
+
+#[test]
+fn agent_output_converts_from_executor_outputs() {
+    use autoagents::core::agent::prebuilt::executor::{BasicAgentOutput, ReActAgentOutput};
+
+    let parsed = ConvertedOutput::from(ReActAgentOutput {
+        response: r#" {"value": 4, "explanation": "2 + 2"} "#.to_string(),
+        tool_calls: Vec::new(),
+        done: true,
+    });
+    assert_eq!(
+        parsed,
+        ConvertedOutput {
+            value: 4,
+            explanation: "2 + 2".to_string(),
+            parse_error: None,
+        }
+    );
+
+    let fallback = ConvertedOutput::from(BasicAgentOutput {
+        response: "four".to_string(),
+        done: true,
+    });
+    assert_eq!(fallback.value, 0);
+    assert_eq!(fallback.explanation, "four");
+    assert!(fallback.parse_error.is_some());
+
+    let mapped = PlainTextOutput::from(ReActAgentOutput {
+        response: "done".to_string(),
+        tool_calls: Vec::new(),
+        done: true,
+    });
+    assert_eq!(mapped.text, "DONE");
+}
+
+#[agent(
+    name = "tuned",
+    description = "Agent with executor settings",
+    tools = [RichTool],
+    output = RichAgentOutput,
+    max_turns = 3,
+    temperature = 0.25,
+    tool_choice = "rich",
+    output_strict = true
+)]
+#[derive(Clone, AgentHooks, Default)]
+struct TunedAgent;
+
+#[test]
+fn agent_executor_settings_are_declared() {
+    use autoagents::core::agent::prebuilt::executor::ReActAgent;
+    use autoagents::core::agent::{AgentExecutor, ToolChoice};
+
+    let tuned = TunedAgent;
+    assert_eq!(tuned.max_turns(), Some(3));
+    assert_eq!(tuned.temperature(), Some(0.25));
+    assert!(matches!(tuned.tool_choice(), Some(ToolChoice::Tool(name)) if name == "rich"));
+    assert_eq!(tuned.output_strict(), Some(true));
+    assert_eq!(ReActAgent::new(TunedAgent).config().max_turns, 3);
+
+    let plain = PlainAgent;
+    assert_eq!(plain.max_turns(), None);
+    assert!(plain.tool_choice().is_none());
+    assert_eq!(ReActAgent::new(PlainAgent).config().max_turns, 10);
+}
+
+fn dated_prompt(
+    context: &autoagents::core::agent::Context,
+    task: &autoagents::core::agent::task::Task,
+) -> String {
+    format!(
+        "You are {}. Today is 2024-01-01. Task: {}",
+        context.config().name,
+        task.prompt
+    )
+}
+
+#[agent(
+    name = "dated",
+    description = "Agent with a dynamic system prompt",
+    prompt_fn = dated_prompt
+)]
+#[derive(Clone, AgentHooks, Default)]
+struct DatedAgent;
+
+/// Provider that fails every call; only used to build a `Context`
+struct OfflineProvider;
+
+#[async_trait]
+impl autoagents::llm::chat::ChatProvider for OfflineProvider {
+    async fn chat_with_tools(
+        &self,
+        _messages: &[autoagents::llm::chat::ChatMessage],
+        _tools: Option<&[autoagents::llm::chat::Tool]>,
+        _json_schema: Option<autoagents::llm::chat::StructuredOutputFormat>,
+    ) -> Result<Box<dyn autoagents::llm::chat::ChatResponse>, autoagents::llm_error::LLMError> {
+        Err(autoagents::llm_error::LLMError::HttpError("offline".into()))
+    }
+}
+
+#[async_trait]
+impl autoagents::llm::completion::CompletionProvider for OfflineProvider {
+    async fn complete(
+        &self,
+        _req: &autoagents::llm::completion::CompletionRequest,
+        _json_schema: Option<autoagents::llm::chat::StructuredOutputFormat>,
+    ) -> Result<autoagents::llm::completion::CompletionResponse, autoagents::llm_error::LLMError>
+    {
+        Err(autoagents::llm_error::LLMError::HttpError("offline".into()))
+    }
+}
+
+#[async_trait]
+impl autoagents::llm::embedding::EmbeddingProvider for OfflineProvider {
+    async fn embed(
+        &self,
+        _input: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, autoagents::llm_error::LLMError> {
+        Err(autoagents::llm_error::LLMError::HttpError("offline".into()))
+    }
+}
+
+#[async_trait]
+impl autoagents::llm::models::ModelsProvider for OfflineProvider {}
+
+impl autoagents::llm::LLMProvider for OfflineProvider {}
+
+#[test]
+fn agent_prompt_fn_overrides_system_prompt() {
+    use autoagents::core::agent::prebuilt::executor::BasicAgent;
+    use autoagents::core::agent::task::Task;
+    use autoagents::core::agent::{AgentConfig, Context};
+
+    let config = AgentConfig::new("dated".into(), DatedAgent.description().into());
+    let context = Context::new(std::sync::Arc::new(OfflineProvider), None).with_config(config);
+    let task = Task::new("plan my week");
+
+    let expected = "You are dated. Today is 2024-01-01. Task: plan my week";
+    assert_eq!(
+        DatedAgent.system_prompt(&context, &task).as_deref(),
+        Some(expected)
+    );
+    assert_eq!(
+        BasicAgent::new(DatedAgent)
+            .system_prompt(&context, &task)
+            .as_deref(),
+        Some(expected)
+    );
+    assert!(PlainAgent.system_prompt(&context, &task).is_none());
+}
+
+async fn reject_long_prompts(
+    _agent: &HookedAgent,
+    task: &autoagents::core::agent::task::Task,
+    _ctx: &autoagents::core::agent::Context,
+) -> autoagents::core::agent::HookOutcome {
+    if task.prompt.len() > 10 {
+        autoagents::core::agent::HookOutcome::Abort
+    } else {
+        autoagents::core::agent::HookOutcome::Continue
+    }
+}
+
+async fn count_turns(agent: &HookedAgent, _turn: usize, _ctx: &autoagents::core::agent::Context) {
+    agent
+        .turns
+        .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+}
+
+#[agent(name = "hooked", description = "Agent with delegated hooks")]
+#[derive(Default, AgentHooks)]
+#[hooks(on_run_start = reject_long_prompts, on_turn_start = "count_turns")]
+struct HookedAgent {
+    turns: std::sync::atomic::AtomicUsize,
+}
+
+#[tokio::test]
+async fn agent_hooks_delegate_selected_hooks() {
+    use autoagents::core::agent::task::Task;
+    use autoagents::core::agent::{AgentHooks, Context, HookOutcome};
+
+    let agent = HookedAgent::default();
+    let context = Context::new(std::sync::Arc::new(OfflineProvider), None);
+
+    assert!(agent.on_run_start(&Task::new("short"), &context).await == HookOutcome::Continue);
+    assert!(
+        agent
+            .on_run_start(&Task::new("a much longer prompt"), &context)
+            .await
+            == HookOutcome::Abort
+    );
+
+    agent.on_turn_start(0, &context).await;
+    agent.on_turn_start(1, &context).await;
+    // Hooks without an entry keep their default, no-op body.
+    agent.on_turn_complete(1, &context).await;
+    assert_eq!(agent.turns.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
+
 #[test]
 fn agents_hooks_and_output_schema_branches() {
     let plain = PlainAgent;
@@ -1,7 +1,7 @@
 use crate::utils::handle_events;
 use autoagents::async_trait;
 use autoagents::core::agent::memory::SlidingWindowMemory;
-use autoagents::core::agent::prebuilt::executor::{ReActAgent, ReActAgentOutput};
+use autoagents::core::agent::prebuilt::executor::ReActAgent;
 use autoagents::core::agent::task::Task;
 use autoagents::core::agent::{AgentBuilder, AgentOutputT, DirectAgent};
 use autoagents::core::error::Error;
@@ -61,6 +61,7 @@ impl ToolRuntime for Multiplication {
 
 /// Math agent output with Value and Explanation
 #[derive(Debug, Serialize, Deserialize, AgentOutput)]
+#[output(from = react, fallback = explanation)]
 pub struct MathAgentOutput {
     #[output(description = "The addition result")]
     value: i64,
@@ -91,16 +92,6 @@ CRITICAL: Your final response MUST be valid JSON with 'value' and 'explanation'
 #[derive(Default, Clone, AgentHooks)]
 pub struct MathAgent {}
 
-impl From<ReActAgentOutput> for MathAgentOutput {
-    fn from(output: ReActAgentOutput) -> Self {
-        output.parse_or_map(|resp| MathAgentOutput {
-            value: 0,
-            explanation: resp.to_string(),
-            generic: None,
-        })
-    }
-}
-
 pub async fn simple_agent(llm: Arc<dyn LLMProvider>) -> Result<(), Error> {
     let sliding_window_memory = Box::new(SlidingWindowMemory::new(10));
 
@@ -130,6 +121,7 @@ pub async fn simple_agent(llm: Arc<dyn LLMProvider>) -> Result<(), Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use autoagents::core::agent::prebuilt::executor::ReActAgentOutput;
     use serde_json::json;
 
     #[tokio::test]
@@ -392,6 +392,7 @@ async fn handle_user_text(
         voice: VoiceIdentifier::new(args.voice.as_deref().unwrap_or(DEFAULT_VOICE)),
         format: AudioFormat::Wav,
         sample_rate: Some(24_000),
+        extensions: Default::default(),
     };
 
     // Spawn the TTS pipeline, fed by the token channel
@@ -19,6 +19,7 @@ pub async fn run(args: SttArgs) -> Result<(), Box<dyn std::error::Error>> {
         audio: SharedAudioData::new(audio),
         language: args.language,
         include_timestamps: true,
+        diarization: None,
     };
 
     let response = provider.transcribe(request).await?;
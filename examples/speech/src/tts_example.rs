@@ -18,6 +18,7 @@ pub async fn run(args: TtsArgs) -> Result<(), Box<dyn std::error::Error>> {
         voice: VoiceIdentifier::new(args.voice.as_deref().unwrap_or("alba")),
         format: AudioFormat::Wav,
         sample_rate: Some(24_000),
+        extensions: Default::default(),
     };
 
     let response = provider.generate_speech(request).await?;
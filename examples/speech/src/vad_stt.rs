@@ -10,8 +10,6 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use std::time::{Duration, Instant};
 
-const VAD_HF_REPO: &str = "freddyaboulton/silero-vad";
-const VAD_HF_FILE: &str = "silero_vad.onnx";
 const PARAKEET_HF_REPO: &str = "altunenes/parakeet-rs";
 const PARAKEET_NEMOTRON_DIR: &str = "nemotron-speech-streaming-en-0.6b";
 const PARAKEET_TDT_DIR: &str = "parakeet-tdt-0.6b-v2";
@@ -63,8 +61,7 @@ pub async fn run(args: VadArgs) -> Result<(), Box<dyn std::error::Error>> {
 }
 
 pub fn build_vad_segmenter() -> Result<VadSegmenter<SileroVad>, Box<dyn std::error::Error>> {
-    let vad_source = ModelSource::from_hf(VAD_HF_REPO, VAD_HF_FILE);
-    let vad = SileroVad::new(vad_source, VadConfig::default())?;
+    let vad = SileroVad::from_default_hf(VadConfig::default())?;
     let config = SegmenterConfig::default()
         .with_window_ms(30)
         .with_min_speech_ms(120)
@@ -5,18 +5,32 @@ use autoagents_core::readers::simple_directory_reader::SimpleDirectoryReader;
 use autoagents_core::vector_store::VectorStoreIndex;
 use autoagents_core::vector_store::in_memory_store::InMemoryVectorStore;
 use autoagents_core::vector_store::request::VectorSearchRequest;
+use autoagents_fastembed::{FastEmbedModel, FastEmbedProvider};
 use autoagents_llm::backends::openai::OpenAI;
 use autoagents_llm::embedding::EmbeddingBuilder;
 use std::env;
+use std::sync::Arc;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let api_key = env::var("OPENAI_API_KEY").context("OPENAI_API_KEY is required")?;
-    let provider: SharedEmbeddingProvider = EmbeddingBuilder::<OpenAI>::new()
-        .api_key(api_key)
-        .model("text-embedding-3-small")
-        .build()
-        .context("failed to build OpenAI embedding client")?;
+    // Fall back to a local model when no OpenAI key is configured
+    let provider: SharedEmbeddingProvider = match env::var("OPENAI_API_KEY") {
+        Ok(api_key) => EmbeddingBuilder::<OpenAI>::new()
+            .api_key(api_key)
+            .model("text-embedding-3-small")
+            .build()
+            .context("failed to build OpenAI embedding client")?,
+        Err(_) => {
+            println!("OPENAI_API_KEY not set, using local bge-small-en-v1.5 embeddings");
+            Arc::new(
+                FastEmbedProvider::builder()
+                    .model(FastEmbedModel::BgeSmallEnV15)
+                    .show_download_progress(true)
+                    .build()
+                    .context("failed to build fastembed provider")?,
+            )
+        }
+    };
 
     let documents = SimpleDirectoryReader::new("examples/vector_store_in_memory/data")
         .with_extensions(["txt"])